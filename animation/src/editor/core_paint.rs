@@ -59,6 +59,17 @@ impl StreamAnimationCore {
                     (element_id, Some(wrapper))
                 }
 
+                CreateBrushStroke(element_id, points)   => {
+                    // The points are already fitted, so the brush stroke element can be created directly
+                    let brush_element   = BrushElement::new(*element_id, Arc::clone(points));
+                    let element         = Vector::BrushStroke(brush_element);
+                    let element_id      = element_id.id().unwrap_or(0);
+                    let mut wrapper     = ElementWrapper::attached_with_element(element, when);
+
+                    wrapper.attachments = vec![self.brush_defn, self.brush_props].into_iter().flatten().collect();
+
+                    (element_id, Some(wrapper))
+                }
 
                 Fill(element_id, point, options)        => {
                     let element_id = element_id.id().unwrap_or(0);
@@ -153,6 +153,13 @@ impl TransformsForElements {
     fn rotate(&mut self, angle: f64, origin: Coord2) {
         self.transform(|_| smallvec![Transformation::Rotate(angle, (origin.x(), origin.y()))]);
     }
+
+    ///
+    /// Applies a transformation matrix to the elements
+    ///
+    fn matrix(&mut self, matrix: [[f64; 3]; 3]) {
+        self.transform(|_| smallvec![Transformation::Matrix(matrix)]);
+    }
 }
 
 impl StreamAnimationCore {
@@ -269,6 +276,10 @@ impl StreamAnimationCore {
                             element_transforms.rotate(*angle, origin);
                         }
                     }
+
+                    ElementTransform::Matrix(matrix)    => {
+                        element_transforms.matrix(*matrix);
+                    }
                 }
             }
 
@@ -13,7 +13,7 @@ mod keyframe_core;
 mod keyframe_raycast;
 mod pending_storage_change;
 mod paint_fill;
-mod element_wrapper;
+pub (crate) mod element_wrapper;
 mod element_collide;
 mod element_transform;
 mod element_convert_to_path;
@@ -25,6 +25,7 @@ mod stream_layer_cache;
 
 use self::stream_animation::*;
 use crate::storage::storage_api::*;
+use crate::storage::edit_journal::*;
 use crate::traits::*;
 
 use futures::stream::{BoxStream};
@@ -39,3 +40,12 @@ use futures::stream::{BoxStream};
 pub fn create_animation_editor<ConnectStream: FnOnce(BoxStream<'static, Vec<StorageCommand>>) -> BoxStream<'static, Vec<StorageResponse>>>(connect_stream: ConnectStream) -> impl EditableAnimation {
     StreamAnimation::new(connect_stream)
 }
+
+///
+/// Creates an editable animation for a stream from the storage layer, which records any edits that are waiting to be stored
+/// in a journal so that they can be recovered if the application exits unexpectedly. If no journal is supplied, this is the
+/// same as `create_animation_editor()`.
+///
+pub fn create_animation_editor_with_journal<ConnectStream: FnOnce(BoxStream<'static, Vec<StorageCommand>>) -> BoxStream<'static, Vec<StorageResponse>>>(connect_stream: ConnectStream, journal: Option<EditJournal>) -> impl EditableAnimation {
+    StreamAnimation::new_with_journal(connect_stream, journal)
+}
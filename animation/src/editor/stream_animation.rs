@@ -2,6 +2,7 @@ use super::stream_layer::*;
 use super::element_wrapper::*;
 use super::stream_animation_core::*;
 use crate::storage::storage_api::*;
+use crate::storage::edit_journal::*;
 use crate::storage::file_properties::*;
 use crate::storage::layer_properties::*;
 use crate::traits::*;
@@ -18,6 +19,7 @@ use futures::stream::{BoxStream};
 use std::sync::*;
 use std::ops::{Range};
 use std::time::{Duration};
+use std::collections::{HashMap};
 
 ///
 /// Animation that sends its updates to a storage stream
@@ -39,6 +41,13 @@ impl StreamAnimation {
     /// stream of requests to be sent to the storage layer
     ///
     pub fn new<ConnectStream: FnOnce(BoxStream<'static, Vec<StorageCommand>>) -> BoxStream<'static, Vec<StorageResponse>>>(connect_stream: ConnectStream) -> StreamAnimation {
+        Self::new_with_journal(connect_stream, None)
+    }
+
+    ///
+    /// Creates a new stream animation that records the edits that are waiting to be written to storage in a journal
+    ///
+    pub fn new_with_journal<ConnectStream: FnOnce(BoxStream<'static, Vec<StorageCommand>>) -> BoxStream<'static, Vec<StorageResponse>>>(connect_stream: ConnectStream, journal: Option<EditJournal>) -> StreamAnimation {
         // Create the storage requests. When the storage layer is running behind, we'll buffer up to 10 of these
         let mut requests        = Publisher::new(10);
         let commands            = requests.subscribe().boxed();
@@ -58,10 +67,24 @@ impl StreamAnimation {
         };
         let core            = Arc::new(Desync::new(core));
 
+        // Edits are recorded in the journal as soon as they're published, so they can be recovered if they never reach the storage
+        let journal         = journal.map(|journal| Arc::new(journal));
+        if let Some(journal) = journal.as_ref() {
+            journal.record_edits_from(edit_publisher.subscribe());
+        }
+
         // Anything published to the editor is piped into the core
-        pipe_in(Arc::clone(&core), edit_publisher.subscribe(), |core, edits| {
+        pipe_in(Arc::clone(&core), edit_publisher.subscribe(), move |core, edits| {
+            let journal = journal.clone();
+
             async move {
+                let num_edits = edits.len();
                 core.perform_edits(edits).await;
+
+                // The edits are now in storage and no longer need to be recovered from the journal
+                if let Some(journal) = journal {
+                    journal.commit_edits(num_edits).ok();
+                }
             }.boxed()
         });
 
@@ -155,6 +178,14 @@ impl Animation for StreamAnimation {
         self.file_properties().frame_length
     }
 
+    ///
+    /// Retrieves the preferences that are overridden by this animation
+    ///
+    fn preference_overrides(&self) -> HashMap<String, String> {
+        self.wait_for_edits();
+        self.file_properties().preferences
+    }
+
     ///
     /// Retrieves the IDs of the layers in this object
     ///
@@ -213,6 +244,21 @@ impl Animation for StreamAnimation {
         }
     }
 
+    ///
+    /// Retrieves the size of the edit log for this animation (the total length of the serialized edits)
+    ///
+    fn get_edit_log_size(&self) -> usize {
+        self.wait_for_edits();
+
+        let mut response = self.request_sync(vec![StorageCommand::ReadEditLogSize]).unwrap_or_else(|| vec![]);
+
+        match response.pop() {
+            Some(StorageResponse::EditLogSize(_num_edits, size)) => size,
+
+            _ => panic!("Unexpected response while reading the size of the edit log")
+        }
+    }
+
     ///
     /// Reads from the edit log for this animation
     ///
@@ -284,6 +330,26 @@ impl Animation for StreamAnimation {
         }).fuse().boxed()
     }
 
+    ///
+    /// Retrieves the named checkpoints in the edit log for this animation, in the order that they occur in the log
+    ///
+    fn get_checkpoints(&self) -> Vec<AnimationCheckpoint> {
+        self.wait_for_edits();
+
+        let checkpoint_responses = self.request_sync(vec![StorageCommand::ReadCheckpoints]).unwrap_or_else(|| vec![]);
+
+        checkpoint_responses
+            .into_iter()
+            .map(|response| {
+                match response {
+                    StorageResponse::Checkpoint(name, edit_index)   => Some(AnimationCheckpoint { name: name, edit_index: edit_index }),
+                    _                                               => None
+                }
+            })
+            .flatten()
+            .collect()
+    }
+
     ///
     /// Supplies a reference which can be used to find the motions associated with this animation
     ///
@@ -364,6 +430,43 @@ impl EditableAnimation for StreamAnimation {
         self.idle_sync_requests.desync(move |reqs| { reqs.push(sync_request) });
     }
 
+    ///
+    /// Replaces the first `num_edits` edits in the edit log with a new set of edits
+    ///
+    fn replace_edit_log(&self, num_edits: usize, new_edits: Vec<AnimationEdit>) {
+        self.wait_for_edits();
+
+        // Serialize the new edits
+        let new_edits = new_edits.into_iter()
+            .map(|edit| {
+                let mut serialized = String::new();
+                edit.serialize(&mut serialized);
+                serialized
+            })
+            .collect();
+
+        // Replace them in the storage
+        self.request_sync(vec![StorageCommand::ReplaceEdits(num_edits, new_edits)]);
+    }
+
+    ///
+    /// Creates a checkpoint with the specified name at the current end of the edit log
+    ///
+    fn set_checkpoint(&self, name: &str) {
+        let edit_index = self.get_num_edits();
+
+        self.request_sync(vec![StorageCommand::WriteCheckpoint(name.to_string(), edit_index)]);
+    }
+
+    ///
+    /// Removes the checkpoint with the specified name
+    ///
+    fn remove_checkpoint(&self, name: &str) {
+        self.wait_for_edits();
+
+        self.request_sync(vec![StorageCommand::DeleteCheckpoint(name.to_string())]);
+    }
+
     ///
     /// Flushes any caches this might have (forces reload from data storage)
     ///
@@ -174,6 +174,9 @@ impl StreamAnimationCore {
                 Layer(layer_id, Paint(when, BrushStroke(element, points))) =>
                     Layer(*layer_id, Paint(*when, BrushStroke(self.assign_element_id(*element).await, points.clone()))),
 
+                Layer(layer_id, Paint(when, CreateBrushStroke(element, points))) =>
+                    Layer(*layer_id, Paint(*when, CreateBrushStroke(self.assign_element_id(*element).await, points.clone()))),
+
                 Layer(layer_id, Paint(when, Fill(element, point, options))) =>
                     Layer(*layer_id, Paint(*when, Fill(self.assign_element_id(*element).await, point.clone(), options.clone()))),
 
@@ -228,6 +231,7 @@ impl StreamAnimationCore {
                     Element(element_ids, element_edit)      => { self.element_edit(element_ids, element_edit).await; }
                     Motion(motion_id, motion_edit)          => { self.motion_edit(*motion_id, motion_edit).await; }
                     SetSize(width, height)                  => { self.set_size(*width, *height).await }
+                    SetPreference(key, value)               => { self.set_preference(key, value.clone()).await }
                     AddNewLayer(layer_id)                   => { self.add_new_layer(*layer_id).await; }
                     RemoveLayer(layer_id)                   => { self.remove_layer(*layer_id).await; }
                 }
@@ -313,6 +317,34 @@ impl StreamAnimationCore {
         }
     }
 
+    ///
+    /// Sets or removes a preference override stored with the animation
+    ///
+    pub fn set_preference<'a>(&'a mut self, key: &'a str, value: Option<String>) -> impl 'a+Future<Output=()> {
+        async move {
+            // Get the current animation properties
+            let properties      = self.request_one(StorageCommand::ReadAnimationProperties).await;
+            let properties      = if let Some(StorageResponse::AnimationProperties(properties)) = properties {
+                FileProperties::deserialize(&mut properties.chars())
+            } else {
+                None
+            };
+            let mut properties  = properties.unwrap_or_else(|| FileProperties::default());
+
+            // Update the preference
+            if let Some(value) = value {
+                properties.preferences.insert(key.to_string(), value);
+            } else {
+                properties.preferences.remove(key);
+            }
+
+            // Send the new properties to the storage
+            let mut new_properties = String::new();
+            properties.serialize(&mut new_properties);
+            self.request_one(StorageCommand::WriteAnimationProperties(new_properties)).await;
+        }
+    }
+
     ///
     /// Adds a key frame to a layer
     ///
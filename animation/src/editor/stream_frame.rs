@@ -206,6 +206,18 @@ impl Frame for StreamFrame {
         }
     }
 
+    ///
+    /// Retrieves the time when the element with the specified ID first appears in the keyframe containing this frame
+    ///
+    fn element_start_time(&self, id: ElementId) -> Option<Duration> {
+        if let Some(core) = self.keyframe_core.as_ref() {
+            core.elements.get(&id).map(|wrapper| wrapper.start_time)
+        } else {
+            // No elements
+            None
+        }
+    }
+
     ///
     /// Retrieves the IDs and types of the elements attached to the element with a particular ID
     ///
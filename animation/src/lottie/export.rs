@@ -0,0 +1,377 @@
+use super::keyframes::*;
+use super::super::traits::*;
+
+use serde_json::{json, Value};
+
+use std::u32;
+use std::sync::*;
+use std::time::{Duration};
+
+///
+/// Converts the elements of an animation to Lottie shapes
+///
+struct LottieExporter<'a, Anim: ?Sized> {
+    /// The animation being exported
+    animation: &'a Anim,
+
+    /// The number of Lottie frames per millisecond of the animation
+    frames_per_millisecond: f64,
+
+    /// The height of the animation (Lottie's y axis runs downwards, so the coordinates are flipped)
+    height: f64
+}
+
+///
+/// Builds up the vertices of a Lottie shape
+///
+struct LottieShapeBuilder {
+    vertices:       Vec<(f64, f64)>,
+    in_tangents:    Vec<(f64, f64)>,
+    out_tangents:   Vec<(f64, f64)>
+}
+
+impl LottieShapeBuilder {
+    fn new() -> LottieShapeBuilder {
+        LottieShapeBuilder {
+            vertices:       vec![],
+            in_tangents:    vec![],
+            out_tangents:   vec![]
+        }
+    }
+
+    ///
+    /// Adds a vertex with an incoming tangent relative to its position
+    ///
+    fn add_vertex(&mut self, vertex: (f64, f64), in_tangent: (f64, f64)) {
+        self.vertices.push(vertex);
+        self.in_tangents.push(in_tangent);
+        self.out_tangents.push((0.0, 0.0));
+    }
+
+    ///
+    /// Adds a bezier curve from the last vertex
+    ///
+    fn add_curve(&mut self, end: (f64, f64), cp1: (f64, f64), cp2: (f64, f64)) {
+        if let Some(&(x, y)) = self.vertices.last() {
+            let last                = self.out_tangents.len()-1;
+            self.out_tangents[last] = (cp1.0-x, cp1.1-y);
+        }
+
+        self.add_vertex(end, (cp2.0-end.0, cp2.1-end.1));
+    }
+
+    ///
+    /// Returns the shape built so far (if it has more than one vertex) and starts a new shape
+    ///
+    fn finish(&mut self, closed: bool) -> Option<Value> {
+        let mut vertices        = vec![];
+        let mut in_tangents     = vec![];
+        let mut out_tangents    = vec![];
+
+        std::mem::swap(&mut vertices, &mut self.vertices);
+        std::mem::swap(&mut in_tangents, &mut self.in_tangents);
+        std::mem::swap(&mut out_tangents, &mut self.out_tangents);
+
+        if vertices.len() < 2 { return None; }
+
+        // Closed shapes implicitly join the last vertex to the first, so a final vertex at the start point is merged with it
+        if closed {
+            let (first, last) = (vertices[0], vertices[vertices.len()-1]);
+
+            if (first.0-last.0).abs() < 1e-6 && (first.1-last.1).abs() < 1e-6 {
+                vertices.pop();
+                out_tangents.pop();
+                in_tangents[0] = in_tangents.pop().unwrap();
+            }
+        }
+
+        let points = |points: Vec<(f64, f64)>| points.into_iter().map(|(x, y)| json!([x, y])).collect::<Vec<_>>();
+
+        Some(json!({
+            "ty":   "sh",
+            "ks":   static_property(json!({
+                "i": points(in_tangents),
+                "o": points(out_tangents),
+                "v": points(vertices),
+                "c": closed
+            }))
+        }))
+    }
+}
+
+///
+/// Creates a Lottie transform that has no effect other than to move the anchor point to a position
+///
+fn lottie_transform(anchor: (f64, f64), position: Value) -> Value {
+    json!({
+        "ty":   "tr",
+        "p":    position,
+        "a":    static_property(json!([anchor.0, anchor.1])),
+        "s":    static_property(json!([100.0, 100.0])),
+        "r":    static_property(json!(0.0)),
+        "o":    static_property(json!(100.0)),
+        "sk":   static_property(json!(0.0)),
+        "sa":   static_property(json!(0.0))
+    })
+}
+
+impl<'a, Anim: Animation+?Sized> LottieExporter<'a, Anim> {
+    ///
+    /// Converts a point from animation coordinates to Lottie coordinates
+    ///
+    fn point(&self, x: f64, y: f64) -> (f64, f64) {
+        (x, self.height - y)
+    }
+
+    ///
+    /// Converts a path to Lottie shapes (one for each subpath)
+    ///
+    fn path_shapes(&self, path: &Path) -> Vec<Value> {
+        let point       = |point: PathPoint| self.point(point.position.0, point.position.1);
+        let mut shapes  = vec![];
+        let mut shape   = LottieShapeBuilder::new();
+
+        for component in path.elements() {
+            match component {
+                PathComponent::Move(start)              => {
+                    shapes.extend(shape.finish(false));
+                    shape.add_vertex(point(start), (0.0, 0.0));
+                }
+
+                PathComponent::Line(end)                => shape.add_vertex(point(end), (0.0, 0.0)),
+                PathComponent::Bezier(end, cp1, cp2)    => shape.add_curve(point(end), point(cp1), point(cp2)),
+                PathComponent::Close                    => shapes.extend(shape.finish(true))
+            }
+        }
+
+        shapes.extend(shape.finish(false));
+        shapes
+    }
+
+    ///
+    /// Creates a Lottie fill for a set of brush properties
+    ///
+    /// Lottie ignores the alpha component of a colour, so it's combined with the brush opacity
+    ///
+    fn fill(&self, brush_properties: &BrushProperties) -> Value {
+        let (r, g, b, a) = brush_properties.color.to_rgba_components();
+
+        json!({
+            "ty":   "fl",
+            "c":    static_property(json!([r as f64, g as f64, b as f64, 1.0])),
+            "o":    static_property(json!((a as f64) * (brush_properties.opacity as f64) * 100.0)),
+            "r":    1
+        })
+    }
+
+    ///
+    /// Creates a Lottie transform that follows a translate motion
+    ///
+    fn motion_transform(&self, translate: &TranslateMotion) -> Value {
+        let anchor      = self.point(translate.origin.0 as f64, translate.origin.1 as f64);
+        let position    = lottie_property_for_time_curve(&translate.translate, self.frames_per_millisecond, |x, y| self.point(x, y));
+
+        lottie_transform(anchor, position)
+    }
+
+    ///
+    /// Converts an element (without its motions applied) to a Lottie shape group
+    ///
+    fn element_shape(&self, frame: &dyn Frame, element: &Vector, properties: Arc<VectorProperties>) -> Option<Value> {
+        let when            = frame.time_index();
+
+        // Apply the brushes and transformations attached to the element. Motions are exported as animated transforms instead.
+        let mut properties  = properties;
+        let mut motions     = vec![];
+
+        for (attachment_id, attachment_type) in frame.attached_elements(element.id()) {
+            match attachment_type {
+                VectorType::Motion  => {
+                    if let Some(Motion::Translate(translate)) = self.animation.motion().get_motion(attachment_id) {
+                        motions.push(translate);
+                    }
+                }
+
+                _                   => {
+                    if let Some(attachment) = frame.element_with_id(attachment_id) {
+                        properties = attachment.update_properties(properties, when);
+                    }
+                }
+            }
+        }
+
+        let properties      = element.update_properties(properties, when);
+
+        // Generate the shapes for this element (the first item in a Lottie group is drawn on top)
+        let mut items       = match element {
+            Vector::Group(group)                        => {
+                let grouped_elements = group.elements().collect::<Vec<_>>();
+
+                grouped_elements.into_iter()
+                    .rev()
+                    .filter_map(|grouped_element| self.element_shape(frame, grouped_element, Arc::clone(&properties)))
+                    .collect::<Vec<_>>()
+            }
+
+            Vector::BrushStroke(_) | Vector::Path(_)    => {
+                let mut items = element.to_path(&properties, PathConversion::Fastest)?
+                    .iter()
+                    .flat_map(|path| self.path_shapes(path))
+                    .collect::<Vec<_>>();
+
+                if items.len() > 0 {
+                    items.push(self.fill(&properties.brush_properties));
+                }
+
+                items
+            }
+
+            _                                           => { return None; }
+        };
+
+        if items.len() == 0 { return None; }
+
+        items.push(lottie_transform((0.0, 0.0), static_property(json!([0.0, 0.0]))));
+        let mut shape = json!({ "ty": "gr", "it": items });
+
+        // Each motion moves the group containing the element
+        for translate in motions.iter() {
+            shape = json!({ "ty": "gr", "it": [shape, self.motion_transform(translate)] });
+        }
+
+        Some(shape)
+    }
+
+    ///
+    /// Converts the elements in a frame to Lottie shapes, grouped by the time that they first appear
+    ///
+    /// Lottie shapes have no start time of their own, so each run of elements (from top to bottom) that appear at the same
+    /// time is returned separately, along with that time. Elements with no start time appear at the start of the keyframe.
+    ///
+    fn frame_shapes(&self, frame: &dyn Frame, keyframe: Duration) -> Vec<(Duration, Vec<Value>)> {
+        let elements    = frame.vector_elements().into_iter().flatten().collect::<Vec<_>>();
+        let mut runs    = vec![];
+
+        for element in elements.into_iter().rev() {
+            let start_time  = frame.element_start_time(element.id()).unwrap_or(keyframe);
+            let shape       = match self.element_shape(frame, &element.original_without_transformations(), Arc::new(VectorProperties::default())) {
+                Some(shape) => shape,
+                None        => { continue; }
+            };
+
+            match runs.last_mut() {
+                Some((run_start, shapes)) if *run_start == start_time   => shapes.push(shape),
+                _                                                       => runs.push((start_time, vec![shape]))
+            }
+        }
+
+        runs
+    }
+}
+
+///
+/// Creates a Lottie shape layer
+///
+fn lottie_shape_layer(index: usize, name: &str, layer_id: u64, shapes: Vec<Value>, in_point: f64, out_point: f64) -> Value {
+    json!({
+        "ddd":      0,
+        "ind":      index,
+        "ty":       4,
+        "nm":       name,
+        "ln":       format!("layer-{}", layer_id),
+        "sr":       1,
+        "ks":       {
+            "o":    static_property(json!(100.0)),
+            "r":    static_property(json!(0.0)),
+            "p":    static_property(json!([0.0, 0.0, 0.0])),
+            "a":    static_property(json!([0.0, 0.0, 0.0])),
+            "s":    static_property(json!([100.0, 100.0, 100.0]))
+        },
+        "ao":       0,
+        "shapes":   shapes,
+        "ip":       in_point,
+        "op":       out_point,
+        "st":       0,
+        "bm":       0
+    })
+}
+
+///
+/// Converts an animation to a Lottie document
+///
+/// Lottie has no concept of keyframes, so every keyframe in a layer becomes a set of Lottie shape layers that are visible
+/// until the start of the next keyframe. Elements that appear part-way through a keyframe are put in a Lottie layer whose
+/// in point is their start time, and there's always a layer that starts with the keyframe (which may be empty). The layers
+/// generated for a single layer of the animation all have the same `ln` attribute, and the layers for a single keyframe
+/// all have the same out point, so they can be recombined when they're imported. The elements are taken from the end of
+/// each keyframe, without their motions: translate motions are converted to animated positions.
+///
+pub fn lottie_export<Anim: Animation+?Sized>(animation: &Anim) -> String {
+    let (width, height)         = animation.size();
+    let frame_length            = animation.frame_length().as_secs_f64();
+    let frames_per_second       = if frame_length > 0.0 { 1.0 / frame_length } else { 30.0 };
+    let total_frames            = (animation.duration().as_secs_f64() * frames_per_second).ceil().max(1.0);
+    let frame_number            = |when: Duration| (when.as_secs_f64() * frames_per_second).round();
+
+    let exporter                = LottieExporter {
+        animation:              animation,
+        frames_per_millisecond: frames_per_second / 1000.0,
+        height:                 height
+    };
+
+    // Lottie layers are listed from top to bottom
+    let mut lottie_layers = vec![];
+
+    for layer_id in animation.get_layer_ids().into_iter().rev() {
+        let layer = match animation.get_layer_with_id(layer_id) {
+            Some(layer) => layer,
+            None        => { continue; }
+        };
+
+        let name        = layer.name().unwrap_or_else(|| format!("Layer {}", layer_id));
+        let keyframes   = layer.get_key_frames().collect::<Vec<_>>();
+
+        for (index, keyframe) in keyframes.iter().enumerate() {
+            let next_keyframe   = keyframes.get(index+1).cloned();
+            let start_frame     = frame_number(*keyframe);
+            let end_frame       = next_keyframe.map(|next_keyframe| frame_number(next_keyframe)).unwrap_or(total_frames);
+
+            if end_frame <= start_frame { continue; }
+
+            // The last frame in the keyframe contains every element
+            let last_time       = next_keyframe.map(|next_keyframe| next_keyframe - Duration::from_micros(1)).unwrap_or_else(|| Duration::from_secs(u32::MAX as u64));
+            let frame           = layer.get_frame_at_time(last_time);
+
+            // Each run of elements is visible from its own start time (the layer at the bottom always starts with the keyframe)
+            let mut runs        = exporter.frame_shapes(&*frame, *keyframe)
+                .into_iter()
+                .map(|(start_time, shapes)| (frame_number(start_time).max(start_frame), shapes))
+                .collect::<Vec<_>>();
+
+            if !runs.iter().any(|(in_point, _)| *in_point == start_frame) {
+                runs.push((start_frame, vec![]));
+            }
+
+            for (in_point, shapes) in runs {
+                // Elements that only appear after the end of the keyframe can't be displayed
+                if in_point >= end_frame { continue; }
+
+                let lottie_layer = lottie_shape_layer(lottie_layers.len() + 1, &name, layer_id, shapes, in_point, end_frame);
+                lottie_layers.push(lottie_layer);
+            }
+        }
+    }
+
+    json!({
+        "v":        "5.7.0",
+        "fr":       frames_per_second,
+        "ip":       0,
+        "op":       total_frames,
+        "w":        width,
+        "h":        height,
+        "nm":       "FlowBetween",
+        "ddd":      0,
+        "assets":   [],
+        "layers":   lottie_layers
+    }).to_string()
+}
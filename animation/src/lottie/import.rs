@@ -0,0 +1,438 @@
+use super::keyframes::*;
+use super::super::svg::*;
+use super::super::traits::*;
+
+use flo_canvas::*;
+use flo_curves::*;
+use flo_curves::bezier;
+use serde_json::{Value};
+
+use std::fmt;
+use std::time::{Duration};
+
+///
+/// Errors that can occur while importing a Lottie file
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum LottieImportError {
+    /// The file could not be read as JSON
+    CannotReadJson(String),
+
+    /// The file is JSON but does not contain a Lottie animation
+    NotLottie
+}
+
+impl fmt::Display for LottieImportError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LottieImportError::CannotReadJson(msg)  => write!(fmt, "Could not read Lottie file: {}", msg),
+            LottieImportError::NotLottie            => write!(fmt, "The file does not contain a Lottie animation")
+        }
+    }
+}
+
+///
+/// The fill and stroke that apply to the shapes in a Lottie group
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct LottieStyle {
+    /// The colour and opacity of the fill
+    fill: Option<(Color, f64)>,
+
+    /// The colour, opacity and width of the stroke
+    stroke: Option<(Color, f64, f64)>,
+
+    /// The opacity of the group (including the opacity of any group it's in)
+    opacity: f64
+}
+
+///
+/// The static part of a Lottie transform, along with its position if the position is animated
+///
+struct LottieTransform<'a> {
+    /// The transform (if the position is animated, this leaves the anchor point where it is)
+    transform: SvgTransform,
+
+    /// The anchor point of the transform
+    anchor: Coord2,
+
+    /// The position property, if it's animated
+    animated_position: Option<&'a Value>
+}
+
+///
+/// Reads a 2D point from a Lottie property
+///
+fn lottie_point(property: &Value, default: (f64, f64)) -> (f64, f64) {
+    initial_value(property)
+        .map(|value| lottie_numbers(value))
+        .filter(|numbers| numbers.len() >= 2)
+        .map(|numbers| (numbers[0], numbers[1]))
+        .unwrap_or(default)
+}
+
+///
+/// Reads an opacity (Lottie opacities are percentages)
+///
+fn lottie_opacity(property: &Value) -> f64 {
+    initial_value(property)
+        .and_then(|value| lottie_number(value))
+        .map(|opacity| (opacity/100.0).max(0.0).min(1.0))
+        .unwrap_or(1.0)
+}
+
+///
+/// Reads a colour (Lottie colours have components from 0-1)
+///
+fn lottie_color(property: &Value) -> Option<Color> {
+    let components = lottie_numbers(initial_value(property)?);
+
+    if components.len() >= 3 {
+        let component = |value: f64| value.max(0.0).min(1.0) as f32;
+        Some(Color::Rgba(component(components[0]), component(components[1]), component(components[2]), 1.0))
+    } else {
+        None
+    }
+}
+
+///
+/// Reads a Lottie transform (either the transform of a layer or a `tr` item in a shape group)
+///
+fn lottie_transform(transform: &Value) -> LottieTransform {
+    let anchor              = lottie_point(&transform["a"], (0.0, 0.0));
+    let animated_position   = if is_animated(&transform["p"]) { Some(&transform["p"]) } else { None };
+    let position            = if animated_position.is_some() { anchor } else { lottie_point(&transform["p"], (0.0, 0.0)) };
+    let scale               = lottie_point(&transform["s"], (100.0, 100.0));
+    let rotation            = initial_value(&transform["r"]).and_then(|rotation| lottie_number(rotation)).unwrap_or(0.0);
+
+    LottieTransform {
+        transform:          SvgTransform::translate(position.0, position.1)
+            .then_apply_to(&SvgTransform::rotate(rotation))
+            .then_apply_to(&SvgTransform::scale(scale.0/100.0, scale.1/100.0))
+            .then_apply_to(&SvgTransform::translate(-anchor.0, -anchor.1)),
+        anchor:             Coord2(anchor.0, anchor.1),
+        animated_position:  animated_position
+    }
+}
+
+///
+/// Reads the subpaths from a Lottie bezier shape
+///
+fn bezier_shape_subpaths(shape: &Value) -> Vec<SvgSubpath> {
+    let points = |name: &str| shape[name].as_array()
+        .map(|points| points.iter()
+            .map(|point| {
+                let coords = lottie_numbers(point);
+                Coord2(coords.get(0).cloned().unwrap_or(0.0), coords.get(1).cloned().unwrap_or(0.0))
+            })
+            .collect::<Vec<_>>())
+        .unwrap_or_else(|| vec![]);
+
+    let vertices        = points("v");
+    let in_tangents     = points("i");
+    let out_tangents    = points("o");
+    let closed          = shape["c"].as_bool().unwrap_or(false);
+
+    if vertices.len() < 2 { return vec![]; }
+
+    // Tangents are relative to their vertex
+    let tangent         = |tangents: &Vec<Coord2>, index: usize| tangents.get(index).cloned().unwrap_or(Coord2(0.0, 0.0));
+    let curve_between   = |from: usize, to: usize| bezier::Curve::from_points(vertices[from], (vertices[from] + tangent(&out_tangents, from), vertices[to] + tangent(&in_tangents, to)), vertices[to]);
+
+    let mut curves      = (0..(vertices.len()-1)).map(|index| curve_between(index, index+1)).collect::<Vec<_>>();
+    if closed {
+        curves.push(curve_between(vertices.len()-1, 0));
+    }
+
+    vec![SvgSubpath { curves: curves, closed: closed }]
+}
+
+///
+/// Returns the subpaths for a Lottie shape item (a bezier shape, rectangle or ellipse)
+///
+fn shape_item_subpaths(item: &Value) -> Vec<SvgSubpath> {
+    match item["ty"].as_str() {
+        Some("sh") => {
+            // Animated shapes use the shape from their first keyframe (which is stored in an array)
+            match initial_value(&item["ks"]) {
+                Some(Value::Array(shapes))  => shapes.get(0).map(|shape| bezier_shape_subpaths(shape)).unwrap_or_else(|| vec![]),
+                Some(shape)                 => bezier_shape_subpaths(shape),
+                None                        => vec![]
+            }
+        }
+
+        Some("rc") => {
+            let (x, y)          = lottie_point(&item["p"], (0.0, 0.0));
+            let (width, height) = lottie_point(&item["s"], (0.0, 0.0));
+            let radius          = initial_value(&item["r"]).and_then(|radius| lottie_number(radius)).unwrap_or(0.0).max(0.0).min(width/2.0).min(height/2.0);
+
+            rect(x - width/2.0, y - height/2.0, width, height, radius, radius)
+        }
+
+        Some("el") => {
+            let (x, y)          = lottie_point(&item["p"], (0.0, 0.0));
+            let (width, height) = lottie_point(&item["s"], (0.0, 0.0));
+
+            ellipse(Coord2(x, y), width/2.0, height/2.0)
+        }
+
+        _ => vec![]
+    }
+}
+
+///
+/// Generates the edits for the shape layers in a Lottie document
+///
+struct LottieImporter<'a, Target: ?Sized> {
+    /// The animation that will receive the edits
+    target: &'a Target,
+
+    /// The length of a Lottie frame in milliseconds
+    milliseconds_per_frame: f64,
+
+    /// The edits that create the motions for animated positions (these are added once all of the elements exist)
+    motion_edits: Vec<AnimationEdit>
+}
+
+impl<'a, Target: EditableAnimation+?Sized> LottieImporter<'a, Target> {
+    ///
+    /// Converts a Lottie frame number to a time in the animation
+    ///
+    fn time(&self, frame: f64) -> Duration {
+        Duration::from_secs_f64((frame * self.milliseconds_per_frame).max(0.0) / 1000.0)
+    }
+
+    ///
+    /// Creates a translate motion for an animated position and attaches it to some elements
+    ///
+    fn attach_motion(&mut self, element_ids: Vec<ElementId>, anchor: Coord2, position: &Value, parent_transform: &SvgTransform) {
+        if element_ids.len() == 0 { return; }
+
+        let map_point   = |x: f64, y: f64| {
+            let point = parent_transform.apply(Coord2(x, y));
+            (point.x(), point.y())
+        };
+        let curve       = match time_curve_for_lottie_property(position, self.milliseconds_per_frame, map_point) {
+            Some(curve) => curve,
+            None        => { return; }
+        };
+        let origin      = parent_transform.apply(anchor);
+
+        let motion_id   = self.target.assign_element_id();
+        let motion      = vec![
+            MotionEdit::Create,
+            MotionEdit::SetType(MotionType::Translate),
+            MotionEdit::SetOrigin(origin.x() as f32, origin.y() as f32),
+            MotionEdit::SetPath(curve)
+        ];
+
+        self.motion_edits.extend(motion.into_iter().map(|motion_edit| AnimationEdit::Motion(motion_id, motion_edit)));
+        self.motion_edits.push(AnimationEdit::Element(element_ids, ElementEdit::AddAttachment(motion_id)));
+    }
+
+    ///
+    /// Creates the paths that fill and stroke a set of subpaths, returning the ID of the element that was created
+    ///
+    fn styled_paths(&self, paths: &mut SvgImporter<Target>, subpaths: Vec<SvgSubpath>, style: &LottieStyle, length_scale: f64) -> Option<ElementId> {
+        if subpaths.len() == 0 { return None; }
+
+        let mut element_ids = vec![];
+
+        if let Some((color, opacity)) = style.fill {
+            element_ids.extend(paths.create_path(&subpaths, color, opacity*style.opacity, 1.0));
+        }
+
+        if let Some((color, opacity, width)) = style.stroke {
+            let width   = width * length_scale;
+            let outline = stroke_outline(&subpaths, width);
+
+            element_ids.extend(paths.create_path(&outline, color, opacity*style.opacity, width));
+        }
+
+        paths.group(element_ids)
+    }
+
+    ///
+    /// Imports the items in a Lottie shape group, returning the IDs of the elements that were created
+    ///
+    /// The fill and stroke in a group apply to all of the shapes directly in that group, and to any groups inside it
+    /// that don't have their own fill or stroke.
+    ///
+    fn import_items(&mut self, paths: &mut SvgImporter<Target>, items: &[Value], style: &LottieStyle, transform: &SvgTransform) -> Vec<ElementId> {
+        // Read the style for this group
+        let mut style   = *style;
+        let fill        = items.iter().filter(|item| item["ty"].as_str() == Some("fl")).last();
+        let stroke      = items.iter().filter(|item| item["ty"].as_str() == Some("st")).last();
+
+        if let Some(fill) = fill {
+            style.fill      = lottie_color(&fill["c"]).map(|color| (color, lottie_opacity(&fill["o"])));
+        }
+
+        if let Some(stroke) = stroke {
+            let width       = initial_value(&stroke["w"]).and_then(|width| lottie_number(width)).unwrap_or(1.0);
+            style.stroke    = lottie_color(&stroke["c"]).map(|color| (color, lottie_opacity(&stroke["o"]), width));
+        }
+
+        // Lottie draws the first item on top, so the items are imported in reverse. Shapes are combined into a single path
+        // until there's a group in the way.
+        let mut element_ids = vec![];
+        let mut subpaths    = vec![];
+
+        for item in items.iter().rev() {
+            match item["ty"].as_str() {
+                Some("sh") | Some("rc") | Some("el") => {
+                    subpaths.extend(shape_item_subpaths(item).into_iter().map(|subpath| subpath.transform(transform)));
+                }
+
+                Some("gr") => {
+                    element_ids.extend(self.styled_paths(paths, subpaths.drain(..).collect(), &style, transform.length_scale()));
+                    element_ids.extend(self.import_group(paths, item, &style, transform));
+                }
+
+                _ => { }
+            }
+        }
+
+        element_ids.extend(self.styled_paths(paths, subpaths, &style, transform.length_scale()));
+        element_ids
+    }
+
+    ///
+    /// Imports a Lottie shape group, returning the ID of the element that was created for it
+    ///
+    fn import_group(&mut self, paths: &mut SvgImporter<Target>, group: &Value, style: &LottieStyle, parent_transform: &SvgTransform) -> Option<ElementId> {
+        let items           = group["it"].as_array().map(|items| items.as_slice()).unwrap_or(&[]);
+        let group_transform = items.iter().find(|item| item["ty"].as_str() == Some("tr"));
+
+        let mut style       = *style;
+        let (transform, motion) = match group_transform {
+            Some(group_transform)   => {
+                let transform   = lottie_transform(group_transform);
+                style.opacity   *= lottie_opacity(&group_transform["o"]);
+
+                (parent_transform.then_apply_to(&transform.transform), transform.animated_position.map(|position| (transform.anchor, position)))
+            }
+
+            None                    => (*parent_transform, None)
+        };
+
+        let element_ids     = self.import_items(paths, items, &style, &transform);
+        let group_id        = paths.group(element_ids)?;
+
+        if let Some((anchor, position)) = motion {
+            self.attach_motion(vec![group_id], anchor, position, parent_transform);
+        }
+
+        Some(group_id)
+    }
+
+    ///
+    /// Generates the edits for a Lottie shape layer, adding its shapes to the keyframe at the specified time
+    ///
+    fn import_layer(&mut self, layer: &Value, layer_id: u64, when: Duration, document_transform: &SvgTransform) -> Vec<AnimationEdit> {
+        let mut paths           = SvgImporter::new(self.target, layer_id, when);
+        let layer_transform     = lottie_transform(&layer["ks"]);
+        let transform           = document_transform.then_apply_to(&layer_transform.transform);
+        let style               = LottieStyle { fill: None, stroke: None, opacity: lottie_opacity(&layer["ks"]["o"]) };
+
+        let shapes              = layer["shapes"].as_array().map(|shapes| shapes.as_slice()).unwrap_or(&[]);
+        let element_ids         = self.import_items(&mut paths, shapes, &style, &transform);
+
+        if let Some(position) = layer_transform.animated_position {
+            self.attach_motion(element_ids, layer_transform.anchor, position, document_transform);
+        }
+
+        paths.into_edits()
+    }
+}
+
+///
+/// Generates the edits that will add the shape layers from a Lottie document to an animation
+///
+/// Layers with the same `ln` attribute (as written by `lottie_export()`) become a single new layer in the animation, and
+/// every other shape layer becomes a new layer of its own. Within a layer, Lottie layers that share an out point become a
+/// single keyframe that starts at the earliest of their in points, and the shapes from each Lottie layer are added at that
+/// layer's own in point. An empty keyframe is added if a keyframe ends before the next one starts.
+///
+/// Bezier shapes, rectangles and ellipses are imported along with their fills and strokes (strokes are converted to filled
+/// outlines), and shape groups become groups in the animation. Animated positions are imported as translate motions; any
+/// other animated property takes the value from its first keyframe. Other kinds of layer, gradients, masks, mattes and
+/// other effects are skipped.
+///
+pub fn lottie_import_edits<Target: EditableAnimation+?Sized>(lottie: &str, target: &Target) -> Result<Vec<AnimationEdit>, LottieImportError> {
+    let document: Value = serde_json::from_str(lottie).map_err(|err| LottieImportError::CannotReadJson(err.to_string()))?;
+
+    let lottie_layers   = document["layers"].as_array().ok_or(LottieImportError::NotLottie)?;
+    let frame_rate      = document["fr"].as_f64().filter(|frame_rate| *frame_rate > 0.0).ok_or(LottieImportError::NotLottie)?;
+    let height          = document["h"].as_f64().ok_or(LottieImportError::NotLottie)?;
+    let end_frame       = document["op"].as_f64().unwrap_or(0.0);
+
+    // Lottie's y axis runs downwards
+    let flip            = SvgTransform([1.0, 0.0, 0.0, -1.0, 0.0, height]);
+
+    // Gather the shape layers into the layers to create (Lottie lists its layers from top to bottom)
+    let mut layers: Vec<(Option<&str>, Vec<&Value>)> = vec![];
+
+    for lottie_layer in lottie_layers.iter().rev().filter(|lottie_layer| lottie_layer["ty"].as_f64() == Some(4.0)) {
+        let layer_name = lottie_layer["ln"].as_str();
+
+        match layers.iter_mut().find(|(name, _)| name.is_some() && *name == layer_name) {
+            Some((_, keyframes))    => keyframes.push(lottie_layer),
+            None                    => layers.push((layer_name, vec![lottie_layer]))
+        }
+    }
+
+    // Generate the edits for each layer (new layers are added after the existing ones)
+    let mut next_layer_id   = target.get_layer_ids().into_iter().max().unwrap_or(0) + 1;
+    let mut importer        = LottieImporter {
+        target:                 target,
+        milliseconds_per_frame: 1000.0 / frame_rate,
+        motion_edits:           vec![]
+    };
+    let mut edits           = vec![];
+
+    for (_, lottie_layers) in layers {
+        let layer_id    = next_layer_id;
+        next_layer_id   += 1;
+
+        let in_point    = |lottie_layer: &Value| lottie_layer["ip"].as_f64().unwrap_or(0.0).max(0.0);
+        let out_point   = |lottie_layer: &Value| lottie_layer["op"].as_f64().unwrap_or(end_frame);
+
+        edits.push(AnimationEdit::AddNewLayer(layer_id));
+        if let Some(name) = lottie_layers[0]["nm"].as_str() {
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetName(name.to_string())));
+        }
+
+        // Lottie layers that end at the same time are part of the same keyframe (they stay in bottom-to-top order)
+        let mut keyframes: Vec<(f64, f64, Vec<&Value>)> = vec![];
+        for lottie_layer in lottie_layers {
+            match keyframes.iter_mut().find(|(_, keyframe_out, _)| *keyframe_out == out_point(lottie_layer)) {
+                Some((keyframe_in, _, members)) => { *keyframe_in = keyframe_in.min(in_point(lottie_layer)); members.push(lottie_layer); }
+                None                            => keyframes.push((in_point(lottie_layer), out_point(lottie_layer), vec![lottie_layer]))
+            }
+        }
+
+        keyframes.sort_by(|(a, _, _), (b, _, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        for index in 0..keyframes.len() {
+            let (keyframe_in, keyframe_out, members) = &keyframes[index];
+
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(importer.time(*keyframe_in))));
+            for lottie_layer in members.iter() {
+                let when = importer.time(in_point(lottie_layer));
+                edits.extend(importer.import_layer(lottie_layer, layer_id, when, &flip));
+            }
+
+            // Keyframes last until the next keyframe, so a gap needs an empty keyframe
+            let next_start = keyframes.get(index+1).map(|(next_in, _, _)| *next_in).unwrap_or(end_frame);
+
+            if *keyframe_out < next_start {
+                edits.push(AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(importer.time(*keyframe_out))));
+            }
+        }
+    }
+
+    edits.extend(importer.motion_edits);
+
+    Ok(edits)
+}
@@ -0,0 +1,241 @@
+use super::super::traits::*;
+
+use serde_json::{json, Value};
+
+///
+/// Reads a number, or the first component of an array (Lottie uses both forms for single values)
+///
+pub (crate) fn lottie_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Array(values)    => values.get(0).and_then(|value| value.as_f64()),
+        other                   => other.as_f64()
+    }
+}
+
+///
+/// Reads a list of numbers (a single number is read as a list with one entry)
+///
+pub (crate) fn lottie_numbers(value: &Value) -> Vec<f64> {
+    match value {
+        Value::Array(values)    => values.iter().filter_map(|value| value.as_f64()).collect(),
+        other                   => other.as_f64().into_iter().collect()
+    }
+}
+
+///
+/// True if a Lottie property is animated
+///
+pub (crate) fn is_animated(property: &Value) -> bool {
+    property["a"].as_f64() == Some(1.0)
+}
+
+///
+/// Returns the value of a Lottie property, or the value at its first keyframe if it's animated
+///
+pub (crate) fn initial_value(property: &Value) -> Option<&Value> {
+    if is_animated(property) {
+        property["k"].get(0).and_then(|keyframe| keyframe.get("s"))
+    } else {
+        property.get("k")
+    }
+}
+
+///
+/// Creates a Lottie property that is not animated
+///
+pub (crate) fn static_property(value: Value) -> Value {
+    json!({ "a": 0, "k": value })
+}
+
+///
+/// Converts a time curve (such as the path of a translate motion) to an animated Lottie property
+///
+/// The points on the curve are passed through `map_point` to convert them to Lottie coordinates. Each section of the curve
+/// becomes a keyframe: the control points set the spatial tangents (`to` and `ti`) and their timing sets the easing. Points
+/// that do not move forward in time are skipped, and a curve that is left with a single point becomes a static property.
+///
+pub fn lottie_property_for_time_curve<MapPoint: Fn(f64, f64) -> (f64, f64)>(curve: &TimeCurve, frames_per_millisecond: f64, map_point: MapPoint) -> Value {
+    let map = |TimePoint(x, y, _): TimePoint| map_point(x as f64, y as f64);
+
+    // Only points that move forward in time can become keyframes
+    let mut points: Vec<&TimeControlPoint> = vec![];
+    for point in curve.points.iter() {
+        if points.last().map(|last| point.point.milliseconds() > last.point.milliseconds()).unwrap_or(true) {
+            points.push(point);
+        }
+    }
+
+    match points.len() {
+        0 => static_property(json!([0.0, 0.0])),
+        1 => {
+            let (x, y) = map(points[0].point);
+            static_property(json!([x, y]))
+        }
+
+        _ => {
+            let mut keyframes = vec![];
+
+            for section in points.windows(2) {
+                let (start, end)    = (section[0], section[1]);
+                let duration        = (end.point.milliseconds() - start.point.milliseconds()) as f64;
+
+                let (x0, y0)        = map(start.point);
+                let (x1, y1)        = map(start.future);
+                let (x2, y2)        = map(end.past);
+                let (x3, y3)        = map(end.point);
+
+                // The easing is the time of each control point as a proportion of the duration of the section
+                let ease_out        = (((start.future.milliseconds() - start.point.milliseconds()) as f64) / duration).max(0.0).min(1.0);
+                let ease_in         = (((end.past.milliseconds() - start.point.milliseconds()) as f64) / duration).max(0.0).min(1.0);
+
+                keyframes.push(json!({
+                    "t":    (start.point.milliseconds() as f64) * frames_per_millisecond,
+                    "s":    [x0, y0],
+                    "o":    { "x": [ease_out], "y": [1.0/3.0] },
+                    "i":    { "x": [ease_in], "y": [2.0/3.0] },
+                    "to":   [x1-x0, y1-y0],
+                    "ti":   [x2-x3, y2-y3]
+                }));
+            }
+
+            let last    = points[points.len()-1];
+            let (x, y)  = map(last.point);
+            keyframes.push(json!({ "t": (last.point.milliseconds() as f64) * frames_per_millisecond, "s": [x, y] }));
+
+            json!({ "a": 1, "k": keyframes })
+        }
+    }
+}
+
+///
+/// A keyframe read from an animated Lottie property
+///
+/// The tangents and easing of a keyframe describe the section between it and the following keyframe.
+///
+struct LottieKeyframe {
+    millis:         f64,
+    position:       (f64, f64),
+    out_tangent:    (f64, f64),
+    in_tangent:     (f64, f64),
+    ease_out:       f64,
+    ease_in:        f64,
+    hold:           bool
+}
+
+///
+/// Converts an animated Lottie property with a 2D value (such as a position) to a time curve
+///
+/// This is the reverse of `lottie_property_for_time_curve()`: the positions are passed through `map_point` to convert them
+/// from Lottie coordinates. Only the timing of the easing curves can be represented, so the progress values of the easing
+/// are ignored. Returns None if the property is not animated.
+///
+pub fn time_curve_for_lottie_property<MapPoint: Fn(f64, f64) -> (f64, f64)>(property: &Value, milliseconds_per_frame: f64, map_point: MapPoint) -> Option<TimeCurve> {
+    if !is_animated(property) { return None; }
+
+    let pair        = |value: &Value| { let numbers = lottie_numbers(value); if numbers.len() >= 2 { Some((numbers[0], numbers[1])) } else { None } };
+    let easing      = |value: &Value, default: f64| lottie_number(&value["x"]).unwrap_or(default).max(0.0).min(1.0);
+
+    // Read the keyframes (older files put the end value of each keyframe in 'e' rather than in the 's' of the next keyframe)
+    let lottie_keyframes    = property["k"].as_array()?;
+    let mut keyframes       = vec![];
+
+    for (index, keyframe) in lottie_keyframes.iter().enumerate() {
+        let millis      = match lottie_number(&keyframe["t"]) { Some(frame) => frame * milliseconds_per_frame, None => { continue; } };
+        let previous    = if index > 0 { lottie_keyframes.get(index-1) } else { None };
+        let position    = pair(&keyframe["s"]).or_else(|| previous.and_then(|previous| pair(&previous["e"])));
+        let position    = match position { Some(position) => position, None => { continue; } };
+
+        if keyframes.last().map(|last: &LottieKeyframe| last.millis >= millis).unwrap_or(false) {
+            continue;
+        }
+
+        keyframes.push(LottieKeyframe {
+            millis:         millis,
+            position:       position,
+            out_tangent:    pair(&keyframe["to"]).unwrap_or((0.0, 0.0)),
+            in_tangent:     pair(&keyframe["ti"]).unwrap_or((0.0, 0.0)),
+            ease_out:       easing(&keyframe["o"], 1.0/3.0),
+            ease_in:        easing(&keyframe["i"], 2.0/3.0),
+            hold:           keyframe["h"].as_f64() == Some(1.0)
+        });
+    }
+
+    // Convert to control points
+    let time_point  = |(x, y): (f64, f64), millis: f64| {
+        let (x, y) = map_point(x, y);
+        TimePoint(x as f32, y as f32, millis as f32)
+    };
+    let mut points: Vec<TimeControlPoint> = vec![];
+
+    for (index, keyframe) in keyframes.iter().enumerate() {
+        let point               = time_point(keyframe.position, keyframe.millis);
+        let mut control_point   = TimeControlPoint::new(point, point, point);
+
+        if index > 0 {
+            let previous    = &keyframes[index-1];
+            let duration    = keyframe.millis - previous.millis;
+
+            if previous.hold {
+                // Hold keyframes keep their value until just before the next keyframe
+                let held = time_point(previous.position, keyframe.millis - duration.min(2.0)/2.0);
+                points.push(TimeControlPoint::new(held, held, held));
+            } else {
+                let (x, y)          = previous.position;
+                let (dx, dy)        = previous.out_tangent;
+                let last            = points.len()-1;
+                points[last].future = time_point((x+dx, y+dy), previous.millis + previous.ease_out*duration);
+
+                let (x, y)          = keyframe.position;
+                let (dx, dy)        = previous.in_tangent;
+                control_point.past  = time_point((x+dx, y+dy), previous.millis + previous.ease_in*duration);
+            }
+        }
+
+        points.push(control_point);
+    }
+
+    match points.len() {
+        0 => None,
+        1 => Some(TimeCurve::new(points[0].point, points[0].point)),
+        _ => Some(TimeCurve { points: points })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn time_curve_round_trip() {
+        let curve   = TimeCurve::new(TimePoint::new(10.0, 20.0, Duration::from_millis(0)), TimePoint::new(110.0, 60.0, Duration::from_millis(1000)));
+        let lottie  = lottie_property_for_time_curve(&curve, 0.03, |x, y| (x, 100.0-y));
+
+        assert!(is_animated(&lottie));
+        assert!(lottie["k"].as_array().unwrap().len() == 2);
+        assert!(lottie["k"][1]["t"].as_f64() == Some(30.0));
+        assert!(lottie_numbers(&lottie["k"][0]["s"]) == vec![10.0, 80.0]);
+
+        let round_trip = time_curve_for_lottie_property(&lottie, 1.0/0.03, |x, y| (x, 100.0-y)).unwrap();
+        assert!(round_trip.is_close_to(&curve));
+    }
+
+    #[test]
+    fn single_point_is_static() {
+        let point   = TimePoint::new(10.0, 20.0, Duration::from_millis(500));
+        let lottie  = lottie_property_for_time_curve(&TimeCurve::new(point, point), 0.03, |x, y| (x, y));
+
+        assert!(!is_animated(&lottie));
+        assert!(lottie_numbers(initial_value(&lottie).unwrap()) == vec![10.0, 20.0]);
+    }
+
+    #[test]
+    fn hold_keyframes_keep_their_value() {
+        let lottie  = json!({ "a": 1, "k": [ { "t": 0, "s": [0, 0], "h": 1 }, { "t": 10, "s": [100, 0] } ] });
+        let curve   = time_curve_for_lottie_property(&lottie, 100.0, |x, y| (x, y)).unwrap();
+
+        assert!(curve.points.len() == 3);
+        assert!(curve.point_at_time(500.0).map(|point| point.0.abs() < 0.01).unwrap_or(false));
+    }
+}
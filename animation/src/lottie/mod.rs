@@ -0,0 +1,17 @@
+//!
+//! Converting animations to and from the Lottie (Bodymovin) JSON format
+//!
+//! Each keyframe of a layer becomes a Lottie shape layer that is visible until the next keyframe, with the elements in the
+//! keyframe as shape groups. Brush strokes and paths are both exported as filled outlines, and translate motions become
+//! animated positions. Only the shape layer subset of Lottie can be imported.
+//!
+
+mod keyframes;
+mod export;
+mod import;
+
+#[cfg(test)] mod tests;
+
+pub use self::keyframes::*;
+pub use self::export::*;
+pub use self::import::*;
@@ -0,0 +1,249 @@
+use super::*;
+use crate::*;
+use crate::svg::*;
+use crate::editor::*;
+use crate::storage::*;
+
+use flo_canvas::*;
+use futures::prelude::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates an in-memory animation for the tests
+///
+fn create_animation() -> impl EditableAnimation {
+    let in_memory_store = InMemoryStorage::new();
+    create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed())
+}
+
+///
+/// Creates an animation with a layer containing a rectangle at the specified keyframes
+///
+fn animation_with_rects(keyframes: &[Duration]) -> impl EditableAnimation {
+    let anim    = create_animation();
+    let svg     = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100"><rect x="10" y="20" width="30" height="40" fill="#ff0000"/></svg>"##;
+
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(1), AnimationEdit::Layer(1, LayerEdit::SetName("Rects".to_string()))]);
+    for keyframe in keyframes.iter() {
+        anim.perform_edits(vec![AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(*keyframe))]);
+        anim.perform_edits(svg_import_edits(svg, &anim, 1, *keyframe).unwrap());
+    }
+
+    anim
+}
+
+///
+/// Retrieves the elements in a layer at a particular time
+///
+fn elements<Anim: Animation>(anim: &Anim, layer_id: u64, when: Duration) -> Vec<Vector> {
+    let frame = anim.get_layer_with_id(layer_id).unwrap().get_frame_at_time(when);
+
+    frame.vector_elements().unwrap().collect()
+}
+
+///
+/// Attaches a motion that moves an element 100 units to the right over the first second
+///
+fn add_motion<Anim: EditableAnimation>(anim: &Anim, element_id: ElementId) {
+    let motion_id = ElementId::Assigned(1000);
+
+    anim.perform_edits(vec![
+        AnimationEdit::Motion(motion_id, MotionEdit::Create),
+        AnimationEdit::Motion(motion_id, MotionEdit::SetType(MotionType::Translate)),
+        AnimationEdit::Motion(motion_id, MotionEdit::SetOrigin(0.0, 0.0)),
+        AnimationEdit::Motion(motion_id, MotionEdit::SetPath(TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(100.0, 0.0, Duration::from_millis(1000))))),
+        AnimationEdit::Element(vec![element_id], ElementEdit::AddAttachment(motion_id))
+    ]);
+}
+
+#[test]
+fn export_path_with_motion() {
+    let anim        = animation_with_rects(&[Duration::from_millis(0)]);
+    let element_id  = elements(&anim, 1, Duration::from_millis(0))[0].id();
+    add_motion(&anim, element_id);
+
+    let lottie: serde_json::Value = serde_json::from_str(&lottie_export(&anim)).unwrap();
+
+    let layers = lottie["layers"].as_array().unwrap();
+    assert!(layers.len() == 1);
+    assert!(layers[0]["ty"].as_f64() == Some(4.0));
+    assert!(layers[0]["nm"].as_str() == Some("Rects"));
+
+    // The element is in a group that follows the motion
+    let shapes = layers[0]["shapes"].as_array().unwrap();
+    assert!(shapes.len() == 1);
+    assert!(shapes[0]["ty"].as_str() == Some("gr"));
+
+    let motion_transform = &shapes[0]["it"][1];
+    assert!(motion_transform["ty"].as_str() == Some("tr"));
+    assert!(motion_transform["p"]["a"].as_f64() == Some(1.0));
+    assert!(motion_transform["p"]["k"].as_array().unwrap().len() == 2);
+
+    // The path is filled with the colour of the rectangle
+    let path_group  = shapes[0]["it"][0]["it"].as_array().unwrap();
+    let fill        = path_group.iter().filter(|item| item["ty"].as_str() == Some("fl")).nth(0).unwrap();
+    assert!(fill["c"]["k"] == serde_json::json!([1.0, 0.0, 0.0, 1.0]));
+}
+
+#[test]
+fn motion_survives_round_trip() {
+    let anim        = animation_with_rects(&[Duration::from_millis(0)]);
+    let element_id  = elements(&anim, 1, Duration::from_millis(0))[0].id();
+    add_motion(&anim, element_id);
+
+    let imported    = create_animation();
+    imported.perform_edits(lottie_import_edits(&lottie_export(&anim), &imported).unwrap());
+
+    assert!(imported.get_layer_ids() == vec![1]);
+
+    let imported_elements = elements(&imported, 1, Duration::from_millis(0));
+    assert!(imported_elements.len() == 1);
+
+    let motions = imported.motion().get_motions_for_element(imported_elements[0].id());
+    assert!(motions.len() == 1);
+
+    match imported.motion().get_motion(motions[0]) {
+        Some(Motion::Translate(translate))  => assert!(translate.translate.is_close_to(&TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(100.0, 0.0, Duration::from_millis(1000))))),
+        _                                   => assert!(false)
+    }
+}
+
+#[test]
+fn keyframes_survive_round_trip() {
+    let anim    = animation_with_rects(&[Duration::from_millis(0), Duration::from_millis(1000)]);
+    let lottie  = lottie_export(&anim);
+
+    let document: serde_json::Value = serde_json::from_str(&lottie).unwrap();
+    assert!(document["layers"].as_array().unwrap().len() == 2);
+
+    // Both Lottie layers become keyframes of the same layer
+    let imported = create_animation();
+    imported.perform_edits(lottie_import_edits(&lottie, &imported).unwrap());
+
+    assert!(imported.get_layer_ids() == vec![1]);
+
+    let keyframes = imported.get_layer_with_id(1).unwrap().get_key_frames().collect::<Vec<_>>();
+    assert!(keyframes.len() == 2);
+    assert!(keyframes[0] == Duration::from_millis(0));
+    assert!((keyframes[1].as_secs_f64() - 1.0).abs() < 0.001);
+
+    assert!(elements(&imported, 1, Duration::from_millis(0)).len() == 1);
+    assert!(elements(&imported, 1, Duration::from_millis(1500)).len() == 1);
+}
+
+#[test]
+fn export_fill_alpha_as_opacity() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Assigned(100), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Assigned(101), BrushProperties { color: Color::Rgba(1.0, 0.0, 0.0, 0.5), opacity: 0.5, ..BrushProperties::new() }))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(102), Arc::new(vec![
+            PathComponent::Move(PathPoint::new(10.0, 20.0)),
+            PathComponent::Line(PathPoint::new(20.0, 30.0)),
+            PathComponent::Line(PathPoint::new(10.0, 30.0)),
+            PathComponent::Close
+        ]))))
+    ]);
+
+    let lottie: serde_json::Value = serde_json::from_str(&lottie_export(&anim)).unwrap();
+
+    // The colour's alpha is combined with the brush opacity
+    let shapes  = lottie["layers"][0]["shapes"].as_array().unwrap();
+    let items   = shapes[0]["it"].as_array().unwrap();
+    let fill    = items.iter().filter(|item| item["ty"].as_str() == Some("fl")).nth(0).unwrap();
+    assert!(fill["c"]["k"] == serde_json::json!([1.0, 0.0, 0.0, 1.0]));
+    assert!((fill["o"]["k"].as_f64().unwrap() - 25.0).abs() < 0.001);
+}
+
+#[test]
+fn export_element_added_part_way_through_keyframe() {
+    let anim    = animation_with_rects(&[Duration::from_millis(0)]);
+    let svg     = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100"><rect x="50" y="20" width="30" height="40" fill="#0000ff"/></svg>"##;
+    anim.perform_edits(svg_import_edits(svg, &anim, 1, Duration::from_millis(500)).unwrap());
+
+    let lottie: serde_json::Value = serde_json::from_str(&lottie_export(&anim)).unwrap();
+
+    // The later element is in a layer of its own, above the first one, that starts when it appears
+    let layers = lottie["layers"].as_array().unwrap();
+    assert!(layers.len() == 2);
+    assert!(layers[0]["ln"] == layers[1]["ln"]);
+    assert!(layers[0]["op"] == layers[1]["op"]);
+    assert!(layers[1]["ip"].as_f64() == Some(0.0));
+    assert!(layers[0]["ip"].as_f64() == Some((lottie["fr"].as_f64().unwrap() * 0.5).round()));
+}
+
+#[test]
+fn element_start_times_survive_round_trip() {
+    let anim    = animation_with_rects(&[Duration::from_millis(0)]);
+    let svg     = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100"><rect x="50" y="20" width="30" height="40" fill="#0000ff"/></svg>"##;
+    anim.perform_edits(svg_import_edits(svg, &anim, 1, Duration::from_millis(500)).unwrap());
+
+    let imported = create_animation();
+    imported.perform_edits(lottie_import_edits(&lottie_export(&anim), &imported).unwrap());
+
+    // Both Lottie layers are part of the same keyframe
+    let keyframes = imported.get_layer_with_id(1).unwrap().get_key_frames().collect::<Vec<_>>();
+    assert!(keyframes == vec![Duration::from_millis(0)]);
+
+    assert!(elements(&imported, 1, Duration::from_millis(250)).len() == 1);
+
+    let later_elements  = elements(&imported, 1, Duration::from_millis(750));
+    let frame           = imported.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(750));
+    assert!(later_elements.len() == 2);
+    assert!(frame.element_start_time(later_elements[0].id()) == Some(Duration::from_millis(0)));
+    assert!((frame.element_start_time(later_elements[1].id()).unwrap().as_secs_f64() - 0.5).abs() < 0.001);
+}
+
+#[test]
+fn import_lottie_rectangle() {
+    let anim    = create_animation();
+    let lottie  = r#"{
+        "v": "5.7.0", "fr": 30, "ip": 0, "op": 60, "w": 200, "h": 100,
+        "layers": [{
+            "ty": 4, "nm": "Shapes", "ip": 0, "op": 60,
+            "ks": { "p": { "a": 0, "k": [10, 0, 0] } },
+            "shapes": [{
+                "ty": "gr",
+                "it": [
+                    { "ty": "rc", "p": { "a": 0, "k": [50, 50] }, "s": { "a": 0, "k": [20, 10] }, "r": { "a": 0, "k": 0 } },
+                    { "ty": "fl", "c": { "a": 0, "k": [0, 0, 1, 1] }, "o": { "a": 0, "k": 50 } },
+                    { "ty": "tr", "p": { "a": 0, "k": [0, 0] }, "a": { "a": 0, "k": [0, 0] } }
+                ]
+            }]
+        }]
+    }"#;
+
+    anim.perform_edits(lottie_import_edits(lottie, &anim).unwrap());
+
+    let imported_elements = elements(&anim, 1, Duration::from_millis(0));
+    assert!(imported_elements.len() == 1);
+
+    match &imported_elements[0] {
+        Vector::Path(path) => {
+            assert!(path.properties().brush_properties().color == Color::Rgba(0.0, 0.0, 1.0, 1.0));
+            assert!((path.properties().brush_properties().opacity - 0.5).abs() < 0.001);
+
+            // Moved by the layer position and flipped so the document is the right way up
+            let start = path.path().elements().filter_map(|component| match component {
+                PathComponent::Move(point)  => Some(point.position),
+                _                           => None
+            }).nth(0);
+            assert!(start == Some((50.0, 55.0)));
+        }
+
+        _ => assert!(false)
+    }
+}
+
+#[test]
+fn reject_documents_that_are_not_lottie() {
+    let anim = create_animation();
+
+    assert!(lottie_import_edits(r#"{ "type": "something else" }"#, &anim) == Err(LottieImportError::NotLottie));
+    assert!(lottie_import_edits("{", &anim).is_err());
+}
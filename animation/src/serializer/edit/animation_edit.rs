@@ -14,6 +14,11 @@ impl AnimationEdit {
             Element(elements, edit)     => { data.write_chr('E'); data.write_usize(elements.len()); elements.iter().for_each(|elem| elem.serialize(data)); edit.serialize(data); },
             Motion(element, edit)       => { data.write_chr('M'); element.serialize(data); edit.serialize(data); },
             SetSize(width, height)      => { data.write_chr('S'); data.write_f64(*width); data.write_f64(*height); },
+            SetPreference(key, value)   => {
+                data.write_chr('P');
+                data.write_str(key);
+                if let Some(value) = value { data.write_chr('+'); data.write_str(value); } else { data.write_chr('-'); }
+            },
             AddNewLayer(layer_id)       => { data.write_chr('+'); data.write_small_u64(*layer_id); },
             RemoveLayer(layer_id)       => { data.write_chr('-'); data.write_small_u64(*layer_id); }
         }
@@ -27,6 +32,14 @@ impl AnimationEdit {
             'L' => { let layer_id = data.next_small_u64(); LayerEdit::deserialize(data).map(move |edit| AnimationEdit::Layer(layer_id, edit)) }
             'M' => { ElementId::deserialize(data).and_then(|elem| MotionEdit::deserialize(data).map(move |edit| AnimationEdit::Motion(elem, edit))) }
             'S' => { Some(AnimationEdit::SetSize(data.next_f64(), data.next_f64())) }
+            'P' => {
+                let key = data.next_string();
+                match data.next_chr() {
+                    '+' => Some(AnimationEdit::SetPreference(key, Some(data.next_string()))),
+                    '-' => Some(AnimationEdit::SetPreference(key, None)),
+                    _   => None
+                }
+            }
             '+' => { Some(AnimationEdit::AddNewLayer(data.next_small_u64())) }
             '-' => { Some(AnimationEdit::RemoveLayer(data.next_small_u64())) }
 
@@ -101,6 +114,22 @@ mod test {
         assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::SetSize(1024.0, 768.0)));
     }
 
+    #[test]
+    fn set_preference() {
+        let mut encoded = String::new();
+        AnimationEdit::SetPreference("ink.size".to_string(), Some("12.0".to_string())).serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::SetPreference("ink.size".to_string(), Some("12.0".to_string()))));
+    }
+
+    #[test]
+    fn remove_preference() {
+        let mut encoded = String::new();
+        AnimationEdit::SetPreference("ink.size".to_string(), None).serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::SetPreference("ink.size".to_string(), None)));
+    }
+
     #[test]
     fn add_new_layer() {
         let mut encoded = String::new();
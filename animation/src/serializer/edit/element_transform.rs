@@ -48,6 +48,15 @@ impl ElementTransform {
                 data.write_chr('r');
                 data.write_f64(*angle);
             }
+
+            Matrix(matrix) => {
+                data.write_chr('M');
+                for x in 0..3 {
+                    for y in 0..3 {
+                        data.write_f64(matrix[x][y]);
+                    }
+                }
+            }
         }
     }
 
@@ -93,6 +102,17 @@ impl ElementTransform {
                 Some(ElementTransform::Rotate(angle))
             }
 
+            'M' => {
+                let mut matrix = [[0.0; 3]; 3];
+                for x in 0..3 {
+                    for y in 0..3 {
+                        matrix[x][y] = data.next_f64();
+                    }
+                }
+
+                Some(ElementTransform::Matrix(matrix))
+            }
+
             _ => None
         }
     }
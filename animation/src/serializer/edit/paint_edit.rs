@@ -36,6 +36,19 @@ impl PaintEdit {
                 }
             },
 
+            CreateBrushStroke(elem, points)                 => {
+                data.write_chr('B');
+                elem.serialize(data);
+
+                // Version 0 (points are written at full precision so the stroke is recreated exactly)
+                data.write_small_u64(0);
+
+                data.write_usize(points.len());
+                for point in points.iter() {
+                    point.serialize(data);
+                }
+            }
+
             Fill(elem, point, options)                      => {
                 data.write_chr('F');
                 elem.serialize(data);
@@ -98,6 +111,26 @@ impl PaintEdit {
                     })
             }
 
+            'B' => {
+                let elem_id     = ElementId::deserialize(data)?;
+
+                match data.next_small_u64() {
+                    0 => {
+                        // v0
+                        let num_points  = data.next_usize();
+                        let mut points  = Vec::with_capacity(num_points);
+
+                        for _point_num in 0..num_points {
+                            points.push(BrushPoint::deserialize(data));
+                        }
+
+                        Some(PaintEdit::CreateBrushStroke(elem_id, Arc::new(points)))
+                    }
+
+                    _ => None
+                }
+            }
+
             'F' => {
                 let elem_id     = ElementId::deserialize(data)?;
                 let position    = (data.next_f64() as f32, data.next_f64() as f32);
@@ -154,4 +187,16 @@ mod test {
 
         assert!(PaintEdit::deserialize(&mut encoded.chars()) == Some(        PaintEdit::Fill(ElementId::Assigned(42), RawPoint::from((1.0, 2.0)), vec![FillOption::Algorithm(FillAlgorithm::Concave), FillOption::Position(FillPosition::Behind)])));
     }
+
+    #[test]
+    fn create_brush_stroke() {
+        let points      = vec![
+            BrushPoint { position: (1.0, 2.0), cp1: (3.0, 4.0), cp2: (5.0, 6.0), width: 0.1 },
+            BrushPoint { position: (7.123456, 8.654321), cp1: (9.0, 10.0), cp2: (11.0, 12.0), width: 0.7 }
+        ];
+        let mut encoded = String::new();
+        PaintEdit::CreateBrushStroke(ElementId::Assigned(42), Arc::new(points.clone())).serialize(&mut encoded);
+
+        assert!(PaintEdit::deserialize(&mut encoded.chars()) == Some(PaintEdit::CreateBrushStroke(ElementId::Assigned(42), Arc::new(points))));
+    }
 }
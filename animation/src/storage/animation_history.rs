@@ -0,0 +1,147 @@
+use super::in_memory_storage::*;
+use super::file_properties::*;
+use super::super::editor::*;
+use super::super::traits::*;
+
+use flo_stream::*;
+
+use futures::prelude::*;
+
+use std::sync::*;
+
+/// The number of edits to send at once when replaying the history of an animation
+const REPLAY_BATCH_SIZE: usize = 1000;
+
+///
+/// Creates a new in-memory animation by replaying the first `edit_index` edits from the log of an existing animation
+///
+/// The result represents the state of the animation as it was just after the specified edit was performed. It is
+/// independent of the original animation, so it won't change if the original animation is edited further.
+///
+pub fn animation_at_edit<'a, Anim: 'a+Animation+?Sized>(animation: &'a Anim, edit_index: usize) -> impl 'a+Send+Future<Output=impl 'static+EditableAnimation> {
+    async move {
+        let edit_index      = edit_index.min(animation.get_num_edits());
+        let in_memory_store = InMemoryStorage::new();
+        let history         = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+
+        {
+            let mut replay      = history.edit();
+            let mut old_edits   = animation.read_edit_log(0..edit_index).chunks(REPLAY_BATCH_SIZE);
+
+            while let Some(edits) = old_edits.next().await {
+                replay.publish(Arc::new(edits)).await;
+            }
+            replay.when_empty().await;
+        }
+
+        history
+    }
+}
+
+///
+/// Creates a new in-memory animation by performing a list of edits
+///
+pub fn animation_from_edits(edits: Vec<AnimationEdit>) -> impl Send+Future<Output=impl 'static+EditableAnimation> {
+    async move {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+
+        {
+            let mut editor  = animation.edit();
+            editor.publish(Arc::new(edits)).await;
+            editor.when_empty().await;
+        }
+
+        animation
+    }
+}
+
+///
+/// Creates a new in-memory animation representing the state of an existing animation at the specified checkpoint
+///
+/// Returns `None` if the checkpoint does not exist
+///
+pub fn animation_at_checkpoint<'a, Anim: 'a+Animation+?Sized>(animation: &'a Anim, checkpoint_name: &'a str) -> impl 'a+Send+Future<Output=Option<impl 'static+EditableAnimation>> {
+    async move {
+        let checkpoint = animation.get_checkpoints()
+            .into_iter()
+            .filter(|checkpoint| checkpoint.name == checkpoint_name)
+            .nth(0);
+
+        match checkpoint {
+            Some(checkpoint)    => Some(animation_at_edit(animation, checkpoint.edit_index).await),
+            None                => None
+        }
+    }
+}
+
+///
+/// Generates the edits that will restore an animation to the state it was in after the specified edit
+///
+/// These remove the current layers and preferences, reset the size in case the original edits don't set it, then replay the
+/// original edits up to the specified point from the log so the animation is recreated exactly as it was.
+///
+pub fn restore_edits<'a, Anim: 'a+Animation+?Sized>(animation: &'a Anim, edit_index: usize) -> impl 'a+Send+Future<Output=Vec<AnimationEdit>> {
+    async move {
+        let edit_index      = edit_index.min(animation.get_num_edits());
+
+        // Remove the layers and preferences as they are now
+        let mut restore     = animation.get_layer_ids()
+            .into_iter()
+            .map(|layer_id| AnimationEdit::RemoveLayer(layer_id))
+            .collect::<Vec<_>>();
+
+        let mut preferences = animation.preference_overrides().into_iter().map(|(key, _)| key).collect::<Vec<_>>();
+        preferences.sort();
+        restore.extend(preferences.into_iter().map(|key| AnimationEdit::SetPreference(key, None)));
+
+        let (width, height) = FileProperties::default().size;
+        restore.push(AnimationEdit::SetSize(width, height));
+
+        // Replay the original edits from the log
+        let old_edits       = animation.read_edit_log(0..edit_index).collect::<Vec<_>>().await;
+        restore.extend(old_edits);
+
+        restore
+    }
+}
+
+///
+/// Restores an animation to the state it was in after the specified edit
+///
+/// This doesn't discard any history: instead, the edits generated by `restore_edits()` are added to the end of the log.
+/// Returns the number of edits that were added to the log.
+///
+pub fn restore_to_edit<'a, Anim: 'a+EditableAnimation+?Sized>(animation: &'a Anim, edit_index: usize) -> impl 'a+Send+Future<Output=usize> {
+    async move {
+        let restore         = restore_edits(animation, edit_index).await;
+
+        let num_edits       = restore.len();
+        let mut editor      = animation.edit();
+        for edits in restore.chunks(REPLAY_BATCH_SIZE) {
+            editor.publish(Arc::new(edits.to_vec())).await;
+        }
+        editor.when_empty().await;
+
+        num_edits
+    }
+}
+
+///
+/// Restores an animation to the state it was in at the named checkpoint
+///
+/// Returns `None` if the checkpoint does not exist, or the number of edits that were added to the log
+///
+pub fn restore_to_checkpoint<'a, Anim: 'a+EditableAnimation+?Sized>(animation: &'a Anim, checkpoint_name: &'a str) -> impl 'a+Send+Future<Output=Option<usize>> {
+    async move {
+        let checkpoint = animation.get_checkpoints()
+            .into_iter()
+            .filter(|checkpoint| checkpoint.name == checkpoint_name)
+            .nth(0);
+
+        match checkpoint {
+            Some(checkpoint)    => Some(restore_to_edit(animation, checkpoint.edit_index).await),
+            None                => None
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use super::storage_api::*;
+use super::super::editor::element_wrapper::*;
+use super::super::serializer::*;
+use super::super::traits::*;
+
+use flo_stream::*;
+
+use futures::prelude::*;
+use futures::stream::{BoxStream};
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration};
+
+/// The number of elements to read from storage in a single request
+const VALIDATE_BATCH_SIZE: i64 = 1000;
+
+///
+/// Describes a problem found in the storage for an animation
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnimationIssue {
+    /// The element with this ID could not be deserialized (or deserialized as an error element)
+    UnreadableElement(ElementId),
+
+    /// There's a keyframe attachment for an element that no longer exists
+    DanglingAttachment(ElementId, u64, Duration),
+
+    /// An element is attached to a layer that does not exist
+    ElementOnMissingLayer(ElementId, u64),
+
+    /// An element is attached to a keyframe that does not exist in its layer
+    ElementOnMissingKeyFrame(ElementId, u64, Duration),
+
+    /// An element (first ID) has an attachment (second ID) that does not exist
+    MissingAttachment(ElementId, ElementId),
+
+    /// A motion (first ID) is attached to an element (second ID) that does not exist
+    MotionForMissingElement(ElementId, ElementId),
+
+    /// A motion is only attached to elements that no longer exist
+    OrphanedMotion(ElementId)
+}
+
+impl AnimationIssue {
+    ///
+    /// Returns the edits that will repair this issue
+    ///
+    /// Some issues (such as a motion that is attached to a mix of existing and missing elements) do not affect how
+    /// the animation is displayed and can't be corrected with an edit, so this can return an empty list.
+    ///
+    pub fn repair_edits(&self) -> Vec<AnimationEdit> {
+        use self::AnimationIssue::*;
+
+        match self {
+            UnreadableElement(element_id)                   => vec![AnimationEdit::Element(vec![*element_id], ElementEdit::Delete)],
+            DanglingAttachment(element_id, _, _)            => vec![AnimationEdit::Element(vec![*element_id], ElementEdit::DetachFromFrame)],
+            ElementOnMissingLayer(element_id, _)            => vec![AnimationEdit::Element(vec![*element_id], ElementEdit::Delete)],
+            ElementOnMissingKeyFrame(element_id, _, _)      => vec![AnimationEdit::Element(vec![*element_id], ElementEdit::Delete)],
+            MissingAttachment(element_id, attachment_id)    => vec![AnimationEdit::Element(vec![*element_id], ElementEdit::RemoveAttachment(*attachment_id))],
+            MotionForMissingElement(_, _)                   => vec![],
+            OrphanedMotion(motion_id)                       => vec![AnimationEdit::Motion(*motion_id, MotionEdit::Delete)]
+        }
+    }
+}
+
+///
+/// Generates the edits needed to repair a set of issues
+///
+/// Elements are only removed once, even if they have several issues that would cause them to be deleted
+///
+pub fn repair_edits_for_issues(issues: &[AnimationIssue]) -> Vec<AnimationEdit> {
+    let mut deleted_elements    = HashSet::new();
+    let mut edits               = vec![];
+
+    for edit in issues.iter().flat_map(|issue| issue.repair_edits()) {
+        match &edit {
+            AnimationEdit::Element(element_ids, ElementEdit::Delete)    |
+            AnimationEdit::Element(element_ids, ElementEdit::DetachFromFrame) => {
+                if element_ids.iter().all(|element_id| deleted_elements.contains(element_id)) { continue; }
+                deleted_elements.extend(element_ids.iter().cloned());
+            }
+
+            AnimationEdit::Motion(motion_id, MotionEdit::Delete)        => {
+                if deleted_elements.contains(motion_id) { continue; }
+                deleted_elements.insert(*motion_id);
+            }
+
+            AnimationEdit::Element(element_ids, _)                      => {
+                if element_ids.iter().any(|element_id| deleted_elements.contains(element_id)) { continue; }
+            }
+
+            _                                                           => { }
+        }
+
+        edits.push(edit);
+    }
+
+    edits
+}
+
+///
+/// Sends requests to the storage for an animation
+///
+struct StorageRequests {
+    /// Where the requests are sent
+    requests: Publisher<Vec<StorageCommand>>,
+
+    /// The responses from the storage
+    responses: BoxStream<'static, Vec<StorageResponse>>
+}
+
+impl StorageRequests {
+    ///
+    /// Sends a request to the storage and waits for the response
+    ///
+    async fn request(&mut self, commands: Vec<StorageCommand>) -> Vec<StorageResponse> {
+        self.requests.publish(commands).await;
+        self.responses.next().await.unwrap_or_else(|| vec![])
+    }
+}
+
+///
+/// Checks the storage for an animation for problems such as elements that can't be read or which are attached to
+/// layers or keyframes that no longer exist
+///
+/// This works directly with the storage API rather than via an animation object, as the editor will generally hide
+/// these issues (for example, by not displaying elements that are attached to missing keyframes). The issues that are
+/// found can be fixed by performing the edits returned by `repair_edits_for_issues()`
+///
+pub fn validate_animation_storage<ConnectStream>(connect_stream: ConnectStream) -> impl Send+Future<Output=Vec<AnimationIssue>>
+where ConnectStream: FnOnce(BoxStream<'static, Vec<StorageCommand>>) -> BoxStream<'static, Vec<StorageResponse>> {
+    let mut requests    = Publisher::new(10);
+    let commands        = requests.subscribe().boxed();
+    let responses       = connect_stream(commands);
+    let mut storage     = StorageRequests { requests, responses };
+
+    async move {
+        let mut issues = vec![];
+
+        // Read the layers and the highest element ID from the storage
+        let layer_ids = storage.request(vec![StorageCommand::ReadLayers]).await
+            .into_iter()
+            .filter_map(|response| match response {
+                StorageResponse::LayerProperties(layer_id, _)   => Some(layer_id),
+                _                                               => None
+            })
+            .collect::<HashSet<_>>();
+        let highest_id = storage.request(vec![StorageCommand::ReadHighestUnusedElementId]).await
+            .into_iter()
+            .filter_map(|response| match response {
+                StorageResponse::HighestUnusedElementId(highest_id) => Some(highest_id),
+                _                                                   => None
+            })
+            .nth(0)
+            .unwrap_or(0);
+
+        // Read the serialized elements and their attachments
+        let mut serialized  = HashMap::new();
+        let mut attachments = HashMap::new();
+        let mut batch_start = 0;
+
+        while batch_start < highest_id {
+            let batch_end       = (batch_start + VALIDATE_BATCH_SIZE).min(highest_id);
+
+            for response in storage.request((batch_start..batch_end).map(|element_id| StorageCommand::ReadElement(element_id)).collect()).await {
+                if let StorageResponse::Element(element_id, element) = response {
+                    serialized.insert(element_id, element);
+                }
+            }
+
+            for response in storage.request((batch_start..batch_end).map(|element_id| StorageCommand::ReadElementAttachments(element_id)).collect()).await {
+                if let StorageResponse::ElementAttachments(element_id, element_attachments) = response {
+                    attachments.insert(element_id, element_attachments);
+                }
+            }
+
+            batch_start = batch_end;
+        }
+
+        // Deserialize the elements (references to other elements are resolved without looking at their references)
+        let mut element_ids = serialized.keys().cloned().collect::<Vec<_>>();
+        element_ids.sort();
+
+        let mut wrappers    = HashMap::new();
+        for element_id in element_ids.iter() {
+            let wrapper = ElementWrapper::deserialize(ElementId::Assigned(*element_id), &mut serialized[element_id].chars())
+                .and_then(|resolver| resolver.resolve(&mut |reference_id| {
+                    let reference = serialized.get(&reference_id.id()?)?;
+                    ElementWrapper::deserialize(reference_id, &mut reference.chars())
+                        .and_then(|resolver| resolver.resolve(&mut |_| None))
+                        .map(|wrapper| wrapper.element)
+                }));
+
+            match wrapper {
+                Some(ElementWrapper { element: Vector::Error, .. }) |
+                None                                                => { issues.push(AnimationIssue::UnreadableElement(ElementId::Assigned(*element_id))); }
+                Some(wrapper)                                       => { wrappers.insert(*element_id, wrapper); }
+            }
+        }
+
+        // Check the keyframe attachments
+        let mut attached_ids = attachments.keys().cloned().collect::<Vec<_>>();
+        attached_ids.sort();
+
+        let mut keyframe_exists = HashMap::new();
+        for element_id in attached_ids {
+            for (layer_id, when) in attachments[&element_id].iter().cloned() {
+                if !serialized.contains_key(&element_id) {
+                    // The element was deleted but is still attached to a keyframe
+                    issues.push(AnimationIssue::DanglingAttachment(ElementId::Assigned(element_id), layer_id, when));
+                } else if !layer_ids.contains(&layer_id) {
+                    // The layer for this element was deleted
+                    issues.push(AnimationIssue::ElementOnMissingLayer(ElementId::Assigned(element_id), layer_id));
+                } else {
+                    // Check that the keyframe exists
+                    if !keyframe_exists.contains_key(&(layer_id, when)) {
+                        let keyframes   = storage.request(vec![StorageCommand::ReadKeyFrames(layer_id, when..(when + Duration::from_micros(1)))]).await;
+                        let exists      = keyframes.into_iter().any(|response| match response {
+                            StorageResponse::KeyFrame(start, _end)  => start == when,
+                            _                                       => false
+                        });
+
+                        keyframe_exists.insert((layer_id, when), exists);
+                    }
+
+                    if !keyframe_exists[&(layer_id, when)] {
+                        issues.push(AnimationIssue::ElementOnMissingKeyFrame(ElementId::Assigned(element_id), layer_id, when));
+                    }
+                }
+            }
+        }
+
+        // Check the references between elements
+        for element_id in element_ids.iter() {
+            let wrapper = if let Some(wrapper) = wrappers.get(element_id) { wrapper } else { continue; };
+            let exists  = |reference_id: &ElementId| reference_id.id().map(|id| serialized.contains_key(&id)).unwrap_or(false);
+
+            for attachment_id in wrapper.attachments.iter() {
+                if !exists(attachment_id) {
+                    issues.push(AnimationIssue::MissingAttachment(ElementId::Assigned(*element_id), *attachment_id));
+                }
+            }
+
+            if let Vector::Motion(_) = &wrapper.element {
+                let missing = wrapper.attached_to.iter().filter(|attached_to| !exists(attached_to)).cloned().collect::<Vec<_>>();
+
+                if missing.len() > 0 && missing.len() == wrapper.attached_to.len() {
+                    issues.push(AnimationIssue::OrphanedMotion(ElementId::Assigned(*element_id)));
+                } else {
+                    issues.extend(missing.into_iter().map(|missing_id| AnimationIssue::MotionForMissingElement(ElementId::Assigned(*element_id), missing_id)));
+                }
+            }
+        }
+
+        issues
+    }
+}
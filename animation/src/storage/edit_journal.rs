@@ -0,0 +1,258 @@
+use super::super::traits::*;
+
+use ::desync::*;
+
+use futures::future;
+use futures::prelude::*;
+
+use std::io;
+use std::io::{Write, Seek, SeekFrom, BufRead, BufReader};
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::sync::*;
+use std::thread;
+use std::ffi::{OsString};
+use std::path::{Path, PathBuf};
+use std::time::{Duration};
+
+/// How often the journal is written to disk
+const JOURNAL_FLUSH_INTERVAL: Duration = Duration::from_millis(1000);
+
+///
+/// The data stored for an edit journal
+///
+struct EditJournalCore {
+    /// Where the journal is stored
+    path: PathBuf,
+
+    /// The file that the journal is being written to
+    file: File,
+
+    /// Journal entries that have not been written to the file yet
+    unflushed: Vec<String>,
+
+    /// The number of edits that have been recorded but not committed to the animation yet
+    num_pending: i64,
+
+    /// True if the journal contained edits from an earlier session when it was opened (these are kept until recovered or discarded)
+    has_earlier_sessions: bool
+}
+
+///
+/// The edit journal records edits that have been sent to an animation but which might not have reached its storage yet
+///
+/// Edits are written to the animation's storage as they are processed, but there's a queue of edits waiting to be
+/// processed: if the application crashes, those edits would be lost. The journal records both the edits that have
+/// been sent to the animation and the number that have been stored, so that any edits that were lost can be recovered
+/// the next time the file is opened. Edits are written to disk periodically, but the number of stored edits is written
+/// as soon as the storage has accepted them, so edits that are already in the file are never replayed.
+///
+/// The journal is a text file: lines starting 'E' are serialized edits, lines starting 'C' indicate how many edits
+/// have been committed to storage and 'S' indicates the start of a new session. The file is deleted when the
+/// journal is closed with no outstanding edits.
+///
+pub struct EditJournal {
+    core: Arc<Desync<EditJournalCore>>
+}
+
+impl EditJournal {
+    ///
+    /// Returns the path of the journal used for an animation file
+    ///
+    pub fn path_for_file(animation_path: &Path) -> PathBuf {
+        let mut journal_path = OsString::from(animation_path.as_os_str());
+        journal_path.push(".journal");
+
+        PathBuf::from(journal_path)
+    }
+
+    ///
+    /// Opens (or creates) the journal at the specified path
+    ///
+    /// If the journal has edits left over from an earlier session, these are kept so they can be recovered later on.
+    /// Otherwise, the journal is cleared.
+    ///
+    pub fn open(path: &Path) -> io::Result<EditJournal> {
+        let has_earlier_sessions    = Self::read_pending_edits(path).map(|edits| edits.len() > 0).unwrap_or(false);
+        let mut file                = if has_earlier_sessions {
+            OpenOptions::new().append(true).open(path)?
+        } else {
+            OpenOptions::new().write(true).create(true).truncate(true).open(path)?
+        };
+
+        // Mark the start of the session
+        file.write_all(b"S\n")?;
+        file.sync_data()?;
+
+        let core = EditJournalCore {
+            path:                   PathBuf::from(path),
+            file:                   file,
+            unflushed:              vec![],
+            num_pending:            0,
+            has_earlier_sessions:   has_earlier_sessions
+        };
+        let core = Arc::new(Desync::new(core));
+
+        // Flush the journal periodically until it's closed
+        let weak_core = Arc::downgrade(&core);
+        thread::spawn(move || {
+            loop {
+                thread::sleep(JOURNAL_FLUSH_INTERVAL);
+
+                match weak_core.upgrade() {
+                    Some(core)  => core.desync(|core| { core.flush().ok(); }),
+                    None        => { break; }
+                }
+            }
+        });
+
+        Ok(EditJournal {
+            core: core
+        })
+    }
+
+    ///
+    /// Records that some edits have been sent to the animation
+    ///
+    pub fn record_edits(&self, edits: Arc<Vec<AnimationEdit>>) {
+        self.core.desync(move |core| core.record_edits(&*edits));
+    }
+
+    ///
+    /// Records edits in the journal as they arrive from a stream (eg, a subscription to the edit publisher for an animation)
+    ///
+    pub fn record_edits_from<EditStream: 'static+Send+Unpin+Stream<Item=Arc<Vec<AnimationEdit>>>>(&self, edits: EditStream) {
+        pipe_in(Arc::clone(&self.core), edits, |core, edits| {
+            core.record_edits(&*edits);
+            future::ready(()).boxed()
+        });
+    }
+
+    ///
+    /// Records that some edits have been written to the animation's storage
+    ///
+    /// This waits for the journal to be written to disk: if the commit record was lost, recovering the file would
+    /// replay edits that had already been stored.
+    ///
+    pub fn commit_edits(&self, num_edits: usize) -> io::Result<()> {
+        self.core.sync(move |core| {
+            core.num_pending -= num_edits as i64;
+            core.unflushed.push(format!("C{}", num_edits));
+
+            core.flush()
+        })
+    }
+
+    ///
+    /// Writes any outstanding journal entries to disk
+    ///
+    pub fn flush(&self) -> io::Result<()> {
+        self.core.sync(|core| core.flush())
+    }
+
+    ///
+    /// Reads the edits from a journal that were never committed to the animation's storage
+    ///
+    pub fn read_pending_edits(path: &Path) -> io::Result<Vec<AnimationEdit>> {
+        let file            = File::open(path)?;
+        let mut pending     = vec![];
+        let mut session     = vec![];
+        let mut committed   = 0;
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+
+            match line.chars().nth(0) {
+                Some('S') => {
+                    // The remaining edits from the previous session are pending
+                    pending.extend(session.drain(committed.min(session.len())..));
+                    committed = 0;
+                }
+
+                Some('E') => {
+                    // Edits that can't be read are ignored (they might have been partially written when the journal was interrupted)
+                    if let Some(edit) = AnimationEdit::deserialize(&mut line[1..].chars()) {
+                        session.push(edit);
+                    }
+                }
+
+                Some('C') => {
+                    committed += usize::from_str_radix(&line[1..], 10).unwrap_or(0);
+                }
+
+                _ => { }
+            }
+        }
+
+        // Add the pending edits from the last session
+        pending.extend(session.drain(committed.min(session.len())..));
+
+        Ok(pending)
+    }
+
+    ///
+    /// Removes the journal at the specified path once its edits have been recovered or discarded
+    ///
+    pub fn remove(path: &Path) -> io::Result<()> {
+        if path.exists() {
+            fs::remove_file(path)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl EditJournalCore {
+    ///
+    /// Adds some edits to the list of entries waiting to be written to the journal
+    ///
+    fn record_edits(&mut self, edits: &Vec<AnimationEdit>) {
+        let entries = edits.iter()
+            .map(|edit| {
+                let mut entry = String::from("E");
+                edit.serialize(&mut entry);
+                entry
+            });
+
+        self.num_pending += edits.len() as i64;
+        self.unflushed.extend(entries);
+    }
+
+    ///
+    /// Writes the unflushed entries to the journal file
+    ///
+    fn flush(&mut self) -> io::Result<()> {
+        if self.unflushed.len() == 0 {
+            return Ok(());
+        }
+
+        if self.num_pending == 0 && !self.has_earlier_sessions {
+            // Everything has been committed, so the journal can be cleared
+            self.unflushed.clear();
+
+            self.file.set_len(0)?;
+            self.file.seek(SeekFrom::Start(0))?;
+            self.file.write_all(b"S\n")?;
+        } else {
+            // Append the new entries to the journal
+            let mut entries = self.unflushed.drain(..).collect::<Vec<_>>().join("\n");
+            entries.push('\n');
+
+            self.file.write_all(entries.as_bytes())?;
+        }
+
+        self.file.sync_data()
+    }
+}
+
+impl Drop for EditJournalCore {
+    fn drop(&mut self) {
+        // Write out the final set of edits
+        self.flush().ok();
+
+        // Remove the journal if there is nothing left to recover
+        if self.num_pending == 0 && !self.has_earlier_sessions {
+            fs::remove_file(&self.path).ok();
+        }
+    }
+}
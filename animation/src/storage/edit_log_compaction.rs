@@ -0,0 +1,69 @@
+use super::animation_history::*;
+use super::super::traits::*;
+
+use futures::prelude::*;
+
+///
+/// The result of compacting an edit log
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct EditLogCompaction {
+    /// True if the edit log was replaced, false if it was left unchanged
+    pub compacted: bool,
+
+    /// The number of edits in the log before it was compacted
+    pub original_length: usize,
+
+    /// The number of edits in the log after it was compacted
+    pub compacted_length: usize,
+
+    /// The number of recent edits that were kept as they were in the original log (the rest of the log is replaced by the snapshot)
+    pub retained_edits: usize
+}
+
+///
+/// Compacts the edit log for an animation by replacing its history with a snapshot of the animation
+///
+/// The most recent `retain_edits` edits are kept as they are in the log: the snapshot is generated from the state of the
+/// animation before these edits were performed. The log is left unchanged if the snapshot is not shorter than the
+/// history it would replace, or if replaying the snapshot does not recreate exactly the same animation.
+///
+pub fn compact_edit_log<'a, Anim: 'a+EditableAnimation+?Sized>(animation: &'a Anim, retain_edits: usize) -> impl 'a+Send+Future<Output=EditLogCompaction> {
+    async move {
+        let num_edits       = animation.get_num_edits();
+        let retain_edits    = retain_edits.min(num_edits);
+        let snapshot_at     = num_edits - retain_edits;
+        let unchanged       = EditLogCompaction { compacted: false, original_length: num_edits, compacted_length: num_edits, retained_edits: retain_edits };
+
+        let (snapshot, replayed_exactly) = if retain_edits == 0 {
+            // The snapshot is just the current state of the animation
+            let snapshot = snapshot_edits(animation);
+            let replayed = animation_from_edits(snapshot.clone()).await;
+
+            let replayed_exactly = animations_match(animation, &replayed);
+            (snapshot, replayed_exactly)
+        } else {
+            // Replay the edits that are being replaced into a new animation to get the state to snapshot
+            let history  = animation_at_edit(animation, snapshot_at).await;
+            let snapshot = snapshot_edits(&history);
+            let replayed = animation_from_edits(snapshot.clone()).await;
+
+            let replayed_exactly = animations_match(&history, &replayed);
+            (snapshot, replayed_exactly)
+        };
+
+        if !replayed_exactly {
+            // Never replace the history with a snapshot that would lose information
+            unchanged
+        } else if snapshot.len() < snapshot_at {
+            // Replace the history with the snapshot
+            let compacted_length = snapshot.len() + retain_edits;
+            animation.replace_edit_log(snapshot_at, snapshot);
+
+            EditLogCompaction { compacted: true, original_length: num_edits, compacted_length: compacted_length, retained_edits: retain_edits }
+        } else {
+            // The log is already as short as it can be
+            unchanged
+        }
+    }
+}
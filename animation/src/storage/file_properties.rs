@@ -1,6 +1,7 @@
 use super::super::serializer::*;
 
 use std::time::{Duration};
+use std::collections::{HashMap};
 
 ///
 /// Storage/serialization structure used to represent the properties of a file
@@ -17,7 +18,10 @@ pub struct FileProperties {
     pub duration: Duration,
 
     /// The length of a frame in the animation
-    pub frame_length: Duration
+    pub frame_length: Duration,
+
+    /// The preferences that are overridden by this animation (serialized preference values, indexed by key)
+    pub preferences: HashMap<String, String>
 }
 
 impl Default for FileProperties {
@@ -27,7 +31,8 @@ impl Default for FileProperties {
             name:           "".to_string(),
             size:           (1920.0, 1080.0),
             duration:       Duration::from_millis(1000 * 60 * 2),
-            frame_length:   Duration::new(0, 33_333_333)
+            frame_length:   Duration::new(0, 33_333_333),
+            preferences:    HashMap::new()
         }
     }
 }
@@ -37,14 +42,30 @@ impl FileProperties {
     /// Serializes these file properties to a target
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
-        // Version 0 of the properties
-        data.write_small_u64(0);
+        // Version 0 of the properties has no preferences, so we only need version 1 if there are some
+        if self.preferences.len() == 0 {
+            data.write_small_u64(0);
+        } else {
+            data.write_small_u64(1);
+        }
 
         data.write_str(&self.name);
         data.write_f64(self.size.0);
         data.write_f64(self.size.1);
         data.write_duration(self.duration);
         data.write_duration(self.frame_length);
+
+        if self.preferences.len() > 0 {
+            // Write the preferences in key order so the properties always serialize the same way
+            let mut keys = self.preferences.keys().collect::<Vec<_>>();
+            keys.sort();
+
+            data.write_usize(keys.len());
+            for key in keys {
+                data.write_str(key);
+                data.write_str(&self.preferences[key]);
+            }
+        }
     }
 
     ///
@@ -63,7 +84,53 @@ impl FileProperties {
                 Some(result)
             }
 
+            1 => {
+                result.name             = data.next_string();
+                result.size             = (data.next_f64(), data.next_f64());
+                result.duration         = data.next_duration();
+                result.frame_length     = data.next_duration();
+
+                let num_preferences     = data.next_usize();
+                for _ in 0..num_preferences {
+                    let key     = data.next_string();
+                    let value   = data.next_string();
+
+                    result.preferences.insert(key, value);
+                }
+
+                Some(result)
+            }
+
             _ => None
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn properties_without_preferences_are_version_0() {
+        let mut encoded = String::new();
+        FileProperties::default().serialize(&mut encoded);
+
+        assert!(encoded.chars().next() == Some('A'));
+        assert!(FileProperties::deserialize(&mut encoded.chars()).unwrap().preferences.len() == 0);
+    }
+
+    #[test]
+    fn preferences_round_trip() {
+        let mut properties = FileProperties::default();
+        properties.size = (800.0, 600.0);
+        properties.preferences.insert("ink.size".to_string(), "12.0".to_string());
+        properties.preferences.insert("onion_skin.frames_before".to_string(), "5".to_string());
+
+        let mut encoded = String::new();
+        properties.serialize(&mut encoded);
+
+        let decoded = FileProperties::deserialize(&mut encoded.chars()).unwrap();
+        assert!(decoded.size == (800.0, 600.0));
+        assert!(decoded.preferences == properties.preferences);
+    }
+}
@@ -74,7 +74,10 @@ struct InMemoryStorageCore {
     element_attachments: HashMap<i64, Vec<ElementAttachment>>,
 
     /// The layers
-    layers: HashMap<u64, InMemoryLayerStorage>
+    layers: HashMap<u64, InMemoryLayerStorage>,
+
+    /// The named checkpoints in the edit log
+    checkpoints: HashMap<String, usize>
 }
 
 ///
@@ -96,7 +99,8 @@ impl InMemoryStorage {
             edit_log:               vec![],
             elements:               HashMap::new(),
             layers:                 HashMap::new(),
-            element_attachments:    HashMap::new()
+            element_attachments:    HashMap::new(),
+            checkpoints:            HashMap::new()
         };
 
         // And the storage
@@ -209,6 +213,45 @@ impl InMemoryStorageCore {
                         .map(|index| StorageResponse::Edit(index, self.edit_log[index].clone()))); 
                 }
 
+                ReadEditLogSize                                     => {
+                    let total_length = self.edit_log.iter().map(|edit| edit.len()).sum();
+                    response.push(StorageResponse::EditLogSize(self.edit_log.len(), total_length));
+                }
+
+                ReplaceEdits(num_edits, new_edits)                  => {
+                    let num_edits       = num_edits.min(self.edit_log.len());
+                    let num_new_edits   = new_edits.len();
+                    self.edit_log.splice(0..num_edits, new_edits);
+
+                    // Checkpoints in the replaced part of the log are removed, and the rest are moved to follow the new edits
+                    self.checkpoints.retain(|_name, edit_index| *edit_index >= num_edits);
+                    self.checkpoints.values_mut().for_each(|edit_index| *edit_index = *edit_index - num_edits + num_new_edits);
+
+                    response.push(StorageResponse::Updated);
+                }
+
+                WriteCheckpoint(name, edit_index)                   => {
+                    self.checkpoints.insert(name, edit_index);
+                    response.push(StorageResponse::Updated);
+                }
+
+                DeleteCheckpoint(name)                              => {
+                    if self.checkpoints.remove(&name).is_some() {
+                        response.push(StorageResponse::Updated);
+                    } else {
+                        response.push(StorageResponse::NotFound);
+                    }
+                }
+
+                ReadCheckpoints                                     => {
+                    let mut checkpoints = self.checkpoints.iter()
+                        .map(|(name, edit_index)| (name.clone(), *edit_index))
+                        .collect::<Vec<_>>();
+                    checkpoints.sort_by(|(name_a, index_a), (name_b, index_b)| index_a.cmp(index_b).then(name_a.cmp(name_b)));
+
+                    response.extend(checkpoints.into_iter().map(|(name, edit_index)| StorageResponse::Checkpoint(name, edit_index)));
+                }
+
                 WriteElement(element_id, value)                     => { 
                     self.elements.insert(element_id, value); 
                     response.push(StorageResponse::Updated);
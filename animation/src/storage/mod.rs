@@ -23,6 +23,10 @@ pub (super) mod layer_properties;
 pub (super) mod storage_api;
 pub (super) mod in_memory_storage;
 pub (super) mod animation_loader;
+pub (super) mod edit_log_compaction;
+pub (super) mod animation_history;
+pub (super) mod edit_journal;
+pub (super) mod animation_validation;
 
 #[cfg(test)] mod tests;
 
@@ -30,3 +34,7 @@ pub use super::editor::*;
 pub use self::storage_api::*;
 pub use self::in_memory_storage::*;
 pub use self::animation_loader::*;
+pub use self::edit_log_compaction::*;
+pub use self::animation_history::*;
+pub use self::edit_journal::*;
+pub use self::animation_validation::*;
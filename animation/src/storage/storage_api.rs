@@ -24,6 +24,21 @@ pub enum StorageCommand {
     /// Reads the edits in a particular range
     ReadEdits(Range<usize>),
 
+    /// Reads the size of the edit log (the number of edits and the total length of the serialized edits)
+    ReadEditLogSize,
+
+    /// Replaces the first edits in the edit log with a new set of edits (used to compact the log: the remaining edits are preserved after the new ones)
+    ReplaceEdits(usize, Vec<String>),
+
+    /// Sets a named checkpoint at the specified edit index (replacing any existing checkpoint with the same name)
+    WriteCheckpoint(String, usize),
+
+    /// Removes the checkpoint with the specified name
+    DeleteCheckpoint(String),
+
+    /// Reads all of the checkpoints in the edit log, in order of edit index
+    ReadCheckpoints,
+
     /// Writes the serialized value of an element
     WriteElement(i64, String),
 
@@ -96,6 +111,12 @@ pub enum StorageResponse {
     /// The number of edits
     NumberOfEdits(usize),
 
+    /// The size of the edit log, as the number of edits and the total length of the serialized edits
+    EditLogSize(usize, usize),
+
+    /// A named checkpoint and the edit index that it refers to
+    Checkpoint(String, usize),
+
     /// The serialized version of the file properites
     AnimationProperties(String),
 
@@ -139,5 +160,14 @@ pub enum StorageError {
     FailedToInitialise,
 
     /// The storage cannot continue because of an eariler error
-    CannotContinueAfterError
+    CannotContinueAfterError,
+
+    /// The file was created by a newer version of FlowBetween and cannot be read
+    FileFormatTooNew,
+
+    /// The file is in an older format that cannot be upgraded to the current version
+    CannotUpgradeFileFormat,
+
+    /// The file is not a FlowBetween animation
+    NotAnAnimation
 }
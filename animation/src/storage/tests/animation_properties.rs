@@ -54,3 +54,29 @@ fn size_changes_after_being_set() {
     assert!((anim.size().1-200.0).abs() < 0.01);
 
 }
+
+#[test]
+fn no_preferences_by_default() {
+    let anim = create_animation();
+
+    assert!(anim.preference_overrides().len() == 0);
+}
+
+#[test]
+fn preference_changes_after_being_set() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetSize(100.0, 200.0),
+        AnimationEdit::SetPreference("ink.size".to_string(), Some("12.0".to_string()))
+    ]);
+
+    assert!(anim.preference_overrides().get("ink.size") == Some(&"12.0".to_string()));
+    assert!((anim.size().0-100.0).abs() < 0.01);
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetPreference("ink.size".to_string(), None)
+    ]);
+
+    assert!(anim.preference_overrides().get("ink.size") == None);
+}
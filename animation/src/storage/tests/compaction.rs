@@ -0,0 +1,301 @@
+use super::*;
+
+use futures::executor;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Draws some brush strokes (on layer 1) and a path (on layer 2), then deletes one of the brush strokes
+///
+fn draw_and_delete<Anim: EditableAnimation>(anim: &Anim) {
+    use self::LayerEdit::*;
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, AddKeyFrame(Duration::from_millis(0)))
+    ]);
+    anim.perform_edits(select_brush_edits(1, 10));
+    anim.perform_edits(vec![
+        brush_stroke_edit(1, 12, 10.0),
+        brush_stroke_edit(1, 13, 30.0),
+        AnimationEdit::Element(vec![ElementId::Assigned(12)], ElementEdit::Delete),
+
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Assigned(20), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(2, Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Assigned(21), BrushProperties::new()))),
+        AnimationEdit::Layer(2, Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(22), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 20.0)),
+                PathComponent::Line(PathPoint::new(20.0, 30.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::RemoveLayer(2)
+    ]);
+}
+
+///
+/// Replays the edit log of an animation into a new animation
+///
+fn replay_edit_log<Anim: EditableAnimation>(anim: &Anim) -> impl EditableAnimation {
+    let edits   = executor::block_on(anim.read_edit_log(0..anim.get_num_edits()).collect::<Vec<_>>());
+    let replay  = create_animation();
+    replay.perform_edits(edits);
+
+    replay
+}
+
+#[test]
+fn snapshot_recreates_elements() {
+    let anim = create_animation();
+    draw_and_delete(&anim);
+
+    let replay  = create_animation();
+    replay.perform_edits(snapshot_edits(&anim));
+
+    assert!(replay.get_layer_ids() == vec![1]);
+    assert!(element_ids(&replay, 1, Duration::from_millis(0)) == vec![ElementId::Assigned(13)]);
+}
+
+///
+/// Replays the snapshot of an animation into a new animation
+///
+fn replay_snapshot<Anim: EditableAnimation>(anim: &Anim) -> impl EditableAnimation {
+    let replay  = create_animation();
+    replay.perform_edits(snapshot_edits(anim));
+
+    replay
+}
+
+///
+/// Draws a brush stroke with enough points that it would change if it was re-fitted
+///
+fn draw_wiggly_stroke<Anim: EditableAnimation>(anim: &Anim, when: Duration, element_id: i64) {
+    let points = (0..40)
+        .map(|index| {
+            let t = index as f32;
+            RawPoint { position: (t * 3.0, (t * 0.7).sin() * 20.0), pressure: 0.2 + (t * 0.3).cos().abs() * 0.8, tilt: (0.0, 0.0) }
+        })
+        .collect::<Vec<_>>();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::Paint(when, PaintEdit::BrushStroke(ElementId::Assigned(element_id), Arc::new(points))))
+    ]);
+}
+
+///
+/// Creates an animation with a single layer and keyframe and selects a brush
+///
+fn create_layer_with_brush() -> impl EditableAnimation {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, AddKeyFrame(Duration::from_millis(0)))
+    ]);
+    anim.perform_edits(select_brush_edits(1, 10));
+
+    anim
+}
+
+///
+/// Reads the brush points for an element in layer 1
+///
+fn brush_points<Anim: Animation>(anim: &Anim, when: Duration, element_id: i64) -> Vec<BrushPoint> {
+    let frame = anim.get_layer_with_id(1).unwrap().get_frame_at_time(when);
+
+    match frame.element_with_id(ElementId::Assigned(element_id)) {
+        Some(Vector::BrushStroke(brush_stroke)) => (*brush_stroke.points()).clone(),
+        _                                       => panic!("Not a brush stroke")
+    }
+}
+
+///
+/// Reads the transformations attached to an element in layer 1
+///
+fn transformations<Anim: Animation>(anim: &Anim, when: Duration, element_id: i64) -> Vec<Transformation> {
+    let frame = anim.get_layer_with_id(1).unwrap().get_frame_at_time(when);
+
+    frame.attached_elements(ElementId::Assigned(element_id))
+        .into_iter()
+        .flat_map(|(attachment_id, _)| match frame.element_with_id(attachment_id) {
+            Some(Vector::Transformation((_, transformations)))  => transformations.into_iter().collect::<Vec<_>>(),
+            _                                                   => vec![]
+        })
+        .collect()
+}
+
+#[test]
+fn snapshot_matches_original() {
+    let anim = create_animation();
+    draw_and_delete(&anim);
+
+    let replay = replay_snapshot(&anim);
+
+    assert!(animations_match(&anim, &replay));
+}
+
+#[test]
+fn snapshot_preserves_brush_points_exactly() {
+    let anim = create_layer_with_brush();
+    draw_wiggly_stroke(&anim, Duration::from_millis(0), 12);
+
+    let replay = replay_snapshot(&anim);
+
+    assert!(brush_points(&replay, Duration::from_millis(0), 12) == brush_points(&anim, Duration::from_millis(0), 12));
+    assert!(animations_match(&anim, &replay));
+}
+
+#[test]
+fn snapshot_preserves_transformations() {
+    let anim = create_layer_with_brush();
+    draw_wiggly_stroke(&anim, Duration::from_millis(0), 12);
+
+    anim.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(12)], ElementEdit::Transform(vec![ElementTransform::SetAnchor(10.0, 20.0), ElementTransform::Rotate(0.7), ElementTransform::Scale(1.5, 0.5)])),
+        AnimationEdit::Element(vec![ElementId::Assigned(12)], ElementEdit::Transform(vec![ElementTransform::Matrix([[1.0, 0.2, 5.0], [0.3, 1.0, -7.0], [0.0, 0.0, 1.0]])]))
+    ]);
+
+    let original    = transformations(&anim, Duration::from_millis(0), 12);
+    let replay      = replay_snapshot(&anim);
+
+    assert!(original.len() == 3);
+    assert!(transformations(&replay, Duration::from_millis(0), 12) == original);
+    assert!(animations_match(&anim, &replay));
+}
+
+#[test]
+fn snapshot_preserves_preferences() {
+    let anim = create_animation();
+    anim.perform_edits(vec![
+        AnimationEdit::SetSize(640.0, 480.0),
+        AnimationEdit::SetPreference("ink.size".to_string(), Some("12.0".to_string())),
+        AnimationEdit::SetPreference("onion_skin.frames".to_string(), Some("3".to_string()))
+    ]);
+
+    let replay = replay_snapshot(&anim);
+
+    assert!(replay.preference_overrides() == anim.preference_overrides());
+    assert!(replay.preference_overrides().len() == 2);
+    assert!(animations_match(&anim, &replay));
+}
+
+#[test]
+fn snapshot_preserves_element_start_times() {
+    let anim = create_layer_with_brush();
+    draw_wiggly_stroke(&anim, Duration::from_millis(0), 12);
+    draw_wiggly_stroke(&anim, Duration::from_millis(510), 13);
+
+    let replay  = replay_snapshot(&anim);
+    let late    = replay.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(510));
+
+    assert!(element_ids(&replay, 1, Duration::from_millis(500)) == vec![ElementId::Assigned(12)]);
+    assert!(element_ids(&replay, 1, Duration::from_millis(510)) == vec![ElementId::Assigned(12), ElementId::Assigned(13)]);
+    assert!(late.element_start_time(ElementId::Assigned(13)) == Some(Duration::from_millis(510)));
+    assert!(animations_match(&anim, &replay));
+}
+
+#[test]
+fn changed_animation_does_not_match() {
+    let anim = create_layer_with_brush();
+    draw_wiggly_stroke(&anim, Duration::from_millis(0), 12);
+
+    let replay = replay_snapshot(&anim);
+    replay.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(12)], ElementEdit::Transform(vec![ElementTransform::SetAnchor(0.0, 0.0), ElementTransform::MoveTo(1.0, 0.0)]))
+    ]);
+
+    assert!(!animations_match(&anim, &replay));
+}
+
+#[test]
+fn compact_edit_log_reduces_edits() {
+    let anim        = create_animation();
+    draw_and_delete(&anim);
+
+    let original    = anim.get_num_edits();
+    let compaction  = executor::block_on(compact_edit_log(&anim, 0));
+
+    assert!(compaction.compacted);
+    assert!(compaction.original_length == original);
+    assert!(compaction.compacted_length < original);
+    assert!(anim.get_num_edits() == compaction.compacted_length);
+
+    // Replaying the compacted log should produce the same animation
+    let replay      = replay_edit_log(&anim);
+
+    assert!(replay.get_layer_ids() == vec![1]);
+    assert!(element_ids(&replay, 1, Duration::from_millis(0)) == vec![ElementId::Assigned(13)]);
+}
+
+#[test]
+fn compact_edit_log_retains_recent_edits() {
+    let anim        = create_animation();
+    draw_and_delete(&anim);
+
+    // Draw another brush stroke after the history we're going to compact
+    let recent_edit = brush_stroke_edit(1, 14, 50.0);
+    anim.perform_edits(vec![recent_edit.clone()]);
+
+    let compaction  = executor::block_on(compact_edit_log(&anim, 1));
+    let num_edits   = anim.get_num_edits();
+    let last_edit   = executor::block_on(anim.read_edit_log((num_edits-1)..num_edits).collect::<Vec<_>>());
+
+    assert!(compaction.retained_edits == 1);
+    assert!(compaction.compacted_length < compaction.original_length);
+    assert!(last_edit == vec![recent_edit]);
+
+    // The retained edit should apply on top of the snapshot
+    let replay      = replay_edit_log(&anim);
+
+    assert!(element_ids(&replay, 1, Duration::from_millis(0)) == vec![ElementId::Assigned(13), ElementId::Assigned(14)]);
+}
+
+#[test]
+fn compact_edit_log_preserves_brush_strokes() {
+    let anim        = create_layer_with_brush();
+    draw_wiggly_stroke(&anim, Duration::from_millis(0), 12);
+    anim.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(12)], ElementEdit::Delete)]);
+    draw_wiggly_stroke(&anim, Duration::from_millis(0), 13);
+
+    let original    = brush_points(&anim, Duration::from_millis(0), 13);
+    let compaction  = executor::block_on(compact_edit_log(&anim, 0));
+
+    assert!(compaction.compacted);
+
+    let replay      = replay_edit_log(&anim);
+    assert!(brush_points(&replay, Duration::from_millis(0), 13) == original);
+    assert!(animations_match(&anim, &replay));
+}
+
+#[test]
+fn uncompacted_log_reports_requested_retained_edits() {
+    let anim        = create_layer_with_brush();
+    let original    = anim.get_num_edits();
+    let compaction  = executor::block_on(compact_edit_log(&anim, 2));
+
+    // The snapshot is no shorter than the two edits it would replace, so the log is left alone
+    assert!(!compaction.compacted);
+    assert!(compaction.original_length == original);
+    assert!(compaction.compacted_length == original);
+    assert!(compaction.retained_edits == 2);
+    assert!(anim.get_num_edits() == original);
+}
+
+#[test]
+fn edit_log_size_is_length_of_serialized_edits() {
+    let anim            = create_animation();
+    draw_and_delete(&anim);
+
+    let edits           = executor::block_on(anim.read_edit_log(0..anim.get_num_edits()).collect::<Vec<_>>());
+    let serialized_size = edits.iter()
+        .map(|edit| {
+            let mut serialized = String::new();
+            edit.serialize(&mut serialized);
+            serialized.len()
+        })
+        .sum::<usize>();
+
+    assert!(anim.get_edit_log_size() == serialized_size);
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+use futures::executor;
+
+use std::time::Duration;
+
+///
+/// Creates an animation with a single layer and a brush selected
+///
+fn create_drawing_animation() -> impl EditableAnimation {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+    ]);
+    anim.perform_edits(select_brush_edits(1, 100));
+
+    anim
+}
+
+#[test]
+fn set_and_read_checkpoints() {
+    let anim = create_drawing_animation();
+
+    anim.set_checkpoint("first");
+    anim.perform_edits(vec![brush_stroke_edit(1, 1, 10.0)]);
+    anim.set_checkpoint("second");
+
+    assert!(anim.get_checkpoints() == vec![
+        AnimationCheckpoint { name: "first".to_string(), edit_index: 4 },
+        AnimationCheckpoint { name: "second".to_string(), edit_index: 5 }
+    ]);
+}
+
+#[test]
+fn remove_checkpoint() {
+    let anim = create_drawing_animation();
+
+    anim.set_checkpoint("first");
+    anim.set_checkpoint("second");
+    anim.remove_checkpoint("first");
+
+    assert!(anim.get_checkpoints() == vec![AnimationCheckpoint { name: "second".to_string(), edit_index: 4 }]);
+}
+
+#[test]
+fn read_animation_at_edit() {
+    let anim = create_drawing_animation();
+
+    anim.perform_edits(vec![brush_stroke_edit(1, 1, 10.0)]);
+    anim.perform_edits(vec![brush_stroke_edit(1, 2, 30.0)]);
+
+    let history = executor::block_on(animation_at_edit(&anim, 5));
+
+    assert!(history.get_num_edits() == 5);
+    assert!(element_ids(&history, 1, Duration::from_millis(0)) == vec![ElementId::Assigned(1)]);
+    assert!(element_ids(&anim, 1, Duration::from_millis(0)) == vec![ElementId::Assigned(1), ElementId::Assigned(2)]);
+}
+
+#[test]
+fn read_animation_at_missing_checkpoint() {
+    let anim    = create_drawing_animation();
+    let history = executor::block_on(animation_at_checkpoint(&anim, "missing"));
+
+    assert!(history.is_none());
+}
+
+#[test]
+fn restore_checkpoint() {
+    let anim = create_drawing_animation();
+
+    anim.perform_edits(vec![brush_stroke_edit(1, 1, 10.0)]);
+    anim.set_checkpoint("one stroke");
+    anim.perform_edits(vec![brush_stroke_edit(1, 2, 30.0)]);
+    anim.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(1)], ElementEdit::Delete)]);
+
+    assert!(element_ids(&anim, 1, Duration::from_millis(0)) == vec![ElementId::Assigned(2)]);
+
+    let num_edits   = anim.get_num_edits();
+    let restored    = executor::block_on(restore_to_checkpoint(&anim, "one stroke"));
+
+    // History is preserved: the restore is added to the end of the log
+    assert!(restored.is_some());
+    assert!(anim.get_num_edits() == num_edits + restored.unwrap());
+    assert!(anim.get_layer_ids() == vec![1]);
+    assert!(element_ids(&anim, 1, Duration::from_millis(0)) == vec![ElementId::Assigned(1)]);
+}
+
+#[test]
+fn restore_checkpoint_recreates_animation_exactly() {
+    let anim = create_drawing_animation();
+
+    anim.perform_edits(vec![brush_stroke_edit(1, 1, 10.0)]);
+    anim.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(1)], ElementEdit::Transform(vec![ElementTransform::SetAnchor(0.0, 0.0), ElementTransform::Rotate(0.5)]))]);
+    anim.set_checkpoint("rotated");
+
+    let at_checkpoint = executor::block_on(animation_at_checkpoint(&anim, "rotated")).unwrap();
+
+    anim.perform_edits(vec![
+        brush_stroke_edit(1, 2, 30.0),
+        AnimationEdit::SetPreference("ink.size".to_string(), Some("12.0".to_string())),
+        AnimationEdit::SetSize(100.0, 100.0),
+        AnimationEdit::AddNewLayer(2)
+    ]);
+
+    executor::block_on(restore_to_checkpoint(&anim, "rotated"));
+
+    assert!(anim.preference_overrides().len() == 0);
+    assert!(anim.get_layer_ids() == vec![1]);
+    assert!(element_ids(&anim, 1, Duration::from_millis(0)) == vec![ElementId::Assigned(1)]);
+    assert!(animations_match(&at_checkpoint, &anim));
+}
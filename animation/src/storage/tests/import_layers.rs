@@ -0,0 +1,72 @@
+use super::*;
+
+use std::time::Duration;
+
+///
+/// Creates an animation with a single layer containing a brush stroke with the specified ID
+///
+fn animation_with_stroke(layer_name: &str, stroke_id: i64) -> impl EditableAnimation {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, SetName(layer_name.to_string())),
+        AnimationEdit::Layer(1, AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, AddKeyFrame(Duration::from_millis(1000)))
+    ]);
+    anim.perform_edits(select_brush_edits(1, 10));
+    anim.perform_edits(vec![brush_stroke_edit(1, stroke_id, 10.0)]);
+
+    anim
+}
+
+#[test]
+fn import_layer_as_new_layer() {
+    let source = animation_with_stroke("Source", 12);
+    let target = animation_with_stroke("Target", 12);
+
+    target.perform_edits(import_layer_edits(&source, &target, &[1], false));
+
+    assert!(target.get_layer_ids() == vec![1, 2]);
+    assert!(target.get_layer_with_id(1).unwrap().name() == Some("Target".to_string()));
+    assert!(target.get_layer_with_id(2).unwrap().name() == Some("Source".to_string()));
+}
+
+#[test]
+fn imported_layer_has_keyframes() {
+    let source = animation_with_stroke("Source", 12);
+    let target = animation_with_stroke("Target", 12);
+
+    target.perform_edits(import_layer_edits(&source, &target, &[1], false));
+
+    let keyframes = target.get_layer_with_id(2).unwrap().get_key_frames().collect::<Vec<_>>();
+    assert!(keyframes == vec![Duration::from_millis(0), Duration::from_millis(1000)]);
+}
+
+#[test]
+fn imported_elements_are_renumbered() {
+    let source = animation_with_stroke("Source", 12);
+    let target = animation_with_stroke("Target", 12);
+
+    target.perform_edits(import_layer_edits(&source, &target, &[1], false));
+
+    let original_ids = element_ids(&target, 1, Duration::from_millis(0));
+    let imported_ids = element_ids(&target, 2, Duration::from_millis(0));
+
+    assert!(original_ids == vec![ElementId::Assigned(12)]);
+    assert!(imported_ids.len() == 1);
+    assert!(imported_ids[0] != ElementId::Assigned(12));
+    assert!(imported_ids[0].is_assigned());
+}
+
+#[test]
+fn missing_layers_are_not_imported() {
+    let source = animation_with_stroke("Source", 12);
+    let target = animation_with_stroke("Target", 12);
+
+    target.perform_edits(import_layer_edits(&source, &target, &[42], false));
+
+    assert!(target.get_layer_ids() == vec![1]);
+}
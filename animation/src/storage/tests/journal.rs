@@ -0,0 +1,99 @@
+use super::*;
+
+use std::fs;
+use std::sync::*;
+use std::env;
+use std::process;
+use std::path::{PathBuf};
+
+///
+/// Creates a path for a journal used by a test
+///
+fn journal_path(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("flo-journal-test-{}-{}.journal", process::id(), name));
+    fs::remove_file(&path).ok();
+
+    path
+}
+
+#[test]
+fn journal_path_for_file() {
+    assert!(EditJournal::path_for_file(&PathBuf::from("animation.flo")) == PathBuf::from("animation.flo.journal"));
+}
+
+#[test]
+fn uncommitted_edits_are_pending() {
+    let path    = journal_path("uncommitted");
+    let journal = EditJournal::open(&path).unwrap();
+
+    journal.record_edits(Arc::new(vec![AnimationEdit::AddNewLayer(1), AnimationEdit::AddNewLayer(2)]));
+    journal.commit_edits(1).unwrap();
+    journal.flush().unwrap();
+
+    assert!(EditJournal::read_pending_edits(&path).unwrap() == vec![AnimationEdit::AddNewLayer(2)]);
+
+    EditJournal::remove(&path).unwrap();
+}
+
+#[test]
+fn committed_edits_are_not_pending() {
+    let path    = journal_path("committed");
+    let journal = EditJournal::open(&path).unwrap();
+
+    journal.record_edits(Arc::new(vec![AnimationEdit::AddNewLayer(1), AnimationEdit::AddNewLayer(2)]));
+    journal.commit_edits(2).unwrap();
+    journal.flush().unwrap();
+
+    assert!(EditJournal::read_pending_edits(&path).unwrap() == vec![]);
+
+    EditJournal::remove(&path).unwrap();
+}
+
+#[test]
+fn commits_are_written_without_flushing() {
+    let path    = journal_path("commit_written");
+    let journal = EditJournal::open(&path).unwrap();
+
+    journal.record_edits(Arc::new(vec![AnimationEdit::AddNewLayer(1), AnimationEdit::AddNewLayer(2)]));
+    journal.commit_edits(1).unwrap();
+
+    assert!(EditJournal::read_pending_edits(&path).unwrap() == vec![AnimationEdit::AddNewLayer(2)]);
+
+    EditJournal::remove(&path).unwrap();
+}
+
+#[test]
+fn journal_is_removed_when_closed_with_no_pending_edits() {
+    let path    = journal_path("closed");
+
+    {
+        let journal = EditJournal::open(&path).unwrap();
+        journal.record_edits(Arc::new(vec![AnimationEdit::AddNewLayer(1)]));
+        journal.commit_edits(1).unwrap();
+    }
+
+    assert!(!path.exists());
+}
+
+#[test]
+fn pending_edits_survive_a_new_session() {
+    let path    = journal_path("new_session");
+
+    {
+        // Simulate a crash by leaving an edit uncommitted
+        let journal = EditJournal::open(&path).unwrap();
+        journal.record_edits(Arc::new(vec![AnimationEdit::AddNewLayer(1)]));
+    }
+
+    {
+        // The next session commits all of its edits
+        let journal = EditJournal::open(&path).unwrap();
+        journal.record_edits(Arc::new(vec![AnimationEdit::AddNewLayer(2)]));
+        journal.commit_edits(1).unwrap();
+    }
+
+    assert!(EditJournal::read_pending_edits(&path).unwrap() == vec![AnimationEdit::AddNewLayer(1)]);
+
+    EditJournal::remove(&path).unwrap();
+}
+
@@ -3,6 +3,9 @@ use crate::editor::*;
 use crate::storage::*;
 use futures::*;
 
+use std::sync::*;
+use std::time::Duration;
+
 mod animation_properties;
 mod layers;
 mod edit_log;
@@ -13,6 +16,11 @@ mod caching;
 mod collide_paths;
 mod grouping;
 mod transformation;
+mod compaction;
+mod history;
+mod journal;
+mod validation;
+mod import_layers;
 
 ///
 /// Creates an in-memory animaton for the tests
@@ -24,6 +32,35 @@ pub fn create_animation() -> impl EditableAnimation {
     animation
 }
 
+///
+/// Returns the edits that select an ink brush on a layer at time 0 (the brush properties use the element ID after `brush_id`)
+///
+pub fn select_brush_edits(layer_id: u64, brush_id: i64) -> Vec<AnimationEdit> {
+    vec![
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Assigned(brush_id), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Assigned(brush_id+1), BrushProperties::new())))
+    ]
+}
+
+///
+/// Returns the edit that draws a short brush stroke on a layer at time 0, starting at the specified x position
+///
+pub fn brush_stroke_edit(layer_id: u64, element_id: i64, x: f32) -> AnimationEdit {
+    AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(element_id), Arc::new(vec![
+            RawPoint::from((x, 10.0)),
+            RawPoint::from((x+10.0, 5.0))
+        ]))))
+}
+
+///
+/// Retrieves the IDs of the vector elements in a layer at the specified time
+///
+pub fn element_ids<Anim: Animation>(anim: &Anim, layer_id: u64, when: Duration) -> Vec<ElementId> {
+    let frame = anim.get_layer_with_id(layer_id).unwrap().get_frame_at_time(when);
+
+    frame.vector_elements().unwrap().map(|element| element.id()).collect()
+}
+
 ///
 /// Deserializes some edits and runs them on the animation. The edit string can be generated
 /// by the diagnostics command line tool.
@@ -0,0 +1,101 @@
+use super::*;
+
+use futures::executor;
+use futures::stream;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates an animation with a brush stroke (element 50) that has a motion attached to it (element 100)
+///
+fn create_animation_with_motion(storage: &Arc<InMemoryStorage>) -> impl EditableAnimation {
+    let storage = Arc::clone(storage);
+    let anim    = create_animation_editor(move |commands| storage.get_responses(commands).boxed());
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+    ]);
+    anim.perform_edits(select_brush_edits(2, 10));
+    anim.perform_edits(vec![
+        brush_stroke_edit(2, 50, 10.0),
+
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Translate)),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(100)))
+    ]);
+
+    // Wait for the edits to reach the storage
+    anim.get_num_edits();
+
+    anim
+}
+
+///
+/// Sends some commands directly to a storage object (bypassing the editor)
+///
+fn send_storage_commands(storage: &Arc<InMemoryStorage>, commands: Vec<StorageCommand>) {
+    let responses = storage.get_responses(stream::iter(vec![commands]));
+    executor::block_on(responses.collect::<Vec<_>>());
+}
+
+///
+/// Validates the contents of a storage object
+///
+fn validate(storage: &Arc<InMemoryStorage>) -> Vec<AnimationIssue> {
+    let storage = Arc::clone(storage);
+    executor::block_on(validate_animation_storage(move |commands| storage.get_responses(commands).boxed()))
+}
+
+#[test]
+fn valid_animation_has_no_issues() {
+    let storage = Arc::new(InMemoryStorage::new());
+    let _anim   = create_animation_with_motion(&storage);
+
+    assert!(validate(&storage) == vec![]);
+}
+
+#[test]
+fn find_unreadable_element() {
+    let storage = Arc::new(InMemoryStorage::new());
+    let _anim   = create_animation_with_motion(&storage);
+
+    send_storage_commands(&storage, vec![StorageCommand::WriteElement(200, "Not an element".to_string())]);
+
+    assert!(validate(&storage) == vec![AnimationIssue::UnreadableElement(ElementId::Assigned(200))]);
+}
+
+#[test]
+fn find_orphaned_motion() {
+    let storage = Arc::new(InMemoryStorage::new());
+    let _anim   = create_animation_with_motion(&storage);
+
+    send_storage_commands(&storage, vec![StorageCommand::DeleteElement(50)]);
+
+    assert!(validate(&storage) == vec![AnimationIssue::OrphanedMotion(ElementId::Assigned(100))]);
+}
+
+#[test]
+fn repair_unreadable_element() {
+    let storage = Arc::new(InMemoryStorage::new());
+    let anim    = create_animation_with_motion(&storage);
+
+    send_storage_commands(&storage, vec![StorageCommand::WriteElement(200, "Not an element".to_string())]);
+
+    let issues  = validate(&storage);
+    anim.perform_edits(repair_edits_for_issues(&issues));
+    anim.get_num_edits();
+
+    assert!(validate(&storage) == vec![]);
+}
+
+#[test]
+fn repair_deletes_elements_once() {
+    let issues = vec![
+        AnimationIssue::UnreadableElement(ElementId::Assigned(42)),
+        AnimationIssue::ElementOnMissingLayer(ElementId::Assigned(42), 3)
+    ];
+
+    assert!(repair_edits_for_issues(&issues) == vec![AnimationEdit::Element(vec![ElementId::Assigned(42)], ElementEdit::Delete)]);
+}
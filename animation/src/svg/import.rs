@@ -0,0 +1,323 @@
+use super::style::*;
+use super::shapes::*;
+use super::stroke::*;
+use super::transform::*;
+use super::path_data::*;
+use super::number_reader::*;
+use super::super::traits::*;
+
+use flo_canvas::*;
+use flo_curves::*;
+use xml::reader::{EventReader, XmlEvent};
+
+use std::fmt;
+use std::sync::*;
+use std::time::{Duration};
+use std::collections::{HashMap};
+
+///
+/// Errors that can occur while importing an SVG file
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum SvgImportError {
+    /// The file could not be read as XML
+    CannotReadXml(String),
+
+    /// The file is XML but does not contain an SVG document
+    NotSvg
+}
+
+impl fmt::Display for SvgImportError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SvgImportError::CannotReadXml(msg)  => write!(fmt, "Could not read SVG file: {}", msg),
+            SvgImportError::NotSvg              => write!(fmt, "The file does not contain an SVG document")
+        }
+    }
+}
+
+///
+/// An element read from an SVG document
+///
+struct SvgNode {
+    name:       String,
+    attributes: HashMap<String, String>,
+    children:   Vec<SvgNode>
+}
+
+///
+/// Reads the elements from an SVG document
+///
+fn read_svg_document(svg: &str) -> Result<SvgNode, SvgImportError> {
+    let mut parents     = vec![];
+    let mut root        = None;
+
+    for event in EventReader::from_str(svg) {
+        match event.map_err(|err| SvgImportError::CannotReadXml(err.to_string()))? {
+            XmlEvent::StartElement { name, attributes, .. } => {
+                parents.push(SvgNode {
+                    name:       name.local_name,
+                    attributes: attributes.into_iter().map(|attribute| (attribute.name.local_name, attribute.value)).collect(),
+                    children:   vec![]
+                });
+            }
+
+            XmlEvent::EndElement { .. } => {
+                if let Some(node) = parents.pop() {
+                    match parents.last_mut() {
+                        Some(parent)    => parent.children.push(node),
+                        None            => root = Some(node)
+                    }
+                }
+            }
+
+            _ => { }
+        }
+    }
+
+    match root {
+        Some(root) if root.name == "svg"    => Ok(root),
+        _                                   => Err(SvgImportError::NotSvg)
+    }
+}
+
+///
+/// Works out the transform from the coordinates in an SVG document to the coordinates in an animation
+///
+/// SVG documents have their origin at the top left and FlowBetween animations have it at the bottom left, so the
+/// document is flipped vertically. Any view box is scaled to fit the document size (centered, preserving its aspect ratio).
+///
+fn document_transform(root: &SvgNode, default_size: (f64, f64)) -> SvgTransform {
+    // Percentage sizes are relative to whatever contains the document, so they're treated as if they're missing
+    let length = |name: &str| root.attributes.get(name)
+        .filter(|value| !value.trim().ends_with('%'))
+        .and_then(|value| parse_length(value))
+        .filter(|length| *length > 0.0);
+
+    let view_box = root.attributes.get("viewBox")
+        .map(|view_box| NumberReader::new(view_box).all_numbers())
+        .filter(|view_box| view_box.len() == 4 && view_box[2] > 0.0 && view_box[3] > 0.0);
+
+    let width   = length("width").or_else(|| view_box.as_ref().map(|view_box| view_box[2])).unwrap_or(default_size.0);
+    let height  = length("height").or_else(|| view_box.as_ref().map(|view_box| view_box[3])).unwrap_or(default_size.1);
+
+    let view_box_transform = if let Some(view_box) = view_box {
+        let scale = (width/view_box[2]).min(height/view_box[3]);
+        let x_off = (width - view_box[2]*scale)/2.0 - view_box[0]*scale;
+        let y_off = (height - view_box[3]*scale)/2.0 - view_box[1]*scale;
+
+        SvgTransform::translate(x_off, y_off).then_apply_to(&SvgTransform::scale(scale, scale))
+    } else {
+        SvgTransform::identity()
+    };
+
+    SvgTransform([1.0, 0.0, 0.0, -1.0, 0.0, height]).then_apply_to(&view_box_transform)
+}
+
+///
+/// Generates the edits that create path elements in a keyframe from the shapes read from a document
+///
+pub (crate) struct SvgImporter<'a, Target: ?Sized> {
+    /// The animation that will receive the edits (used to assign element IDs)
+    target: &'a Target,
+
+    /// The layer the elements are being added to
+    layer_id: u64,
+
+    /// The time of the keyframe the elements are being added to
+    when: Duration,
+
+    /// The edits generated so far
+    edits: Vec<AnimationEdit>,
+
+    /// The brush properties most recently set for the layer
+    brush_properties: Option<BrushProperties>
+}
+
+impl<'a, Target: EditableAnimation+?Sized> SvgImporter<'a, Target> {
+    ///
+    /// Creates an importer that adds elements to the keyframe at the specified time (paths are drawn with the ink brush)
+    ///
+    pub (crate) fn new(target: &'a Target, layer_id: u64, when: Duration) -> SvgImporter<'a, Target> {
+        let mut importer = SvgImporter {
+            target:             target,
+            layer_id:           layer_id,
+            when:               when,
+            edits:              vec![],
+            brush_properties:   None
+        };
+
+        let brush_id = target.assign_element_id();
+        importer.path_edit(PathEdit::SelectBrush(brush_id, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw));
+
+        importer
+    }
+
+    ///
+    /// Returns the edits generated by this importer
+    ///
+    pub (crate) fn into_edits(self) -> Vec<AnimationEdit> {
+        self.edits
+    }
+
+    ///
+    /// Adds a path edit to the list of edits
+    ///
+    fn path_edit(&mut self, edit: PathEdit) {
+        self.edits.push(AnimationEdit::Layer(self.layer_id, LayerEdit::Path(self.when, edit)));
+    }
+
+    ///
+    /// Creates a path element from a set of subpaths, returning its ID
+    ///
+    pub (crate) fn create_path(&mut self, subpaths: &[SvgSubpath], color: Color, opacity: f64, size: f64) -> Option<ElementId> {
+        let point = |Coord2(x, y): Coord2| PathPoint::new(x as f32, y as f32);
+
+        // Convert the subpaths to path components
+        let mut components = vec![];
+        for subpath in subpaths.iter() {
+            if subpath.curves.len() == 0 { continue; }
+
+            components.push(PathComponent::Move(point(subpath.curves[0].start_point())));
+            for curve in subpath.curves.iter() {
+                let (cp1, cp2) = curve.control_points();
+                components.push(PathComponent::Bezier(point(curve.end_point()), point(cp1), point(cp2)));
+            }
+
+            if subpath.closed {
+                components.push(PathComponent::Close);
+            }
+        }
+
+        if components.len() == 0 { return None; }
+
+        // Update the brush properties if they're different from the last path
+        let properties = BrushProperties {
+            size:       size as f32,
+            opacity:    opacity as f32,
+            color:      color
+        };
+
+        if self.brush_properties != Some(properties) {
+            let properties_id = self.target.assign_element_id();
+            self.path_edit(PathEdit::BrushProperties(properties_id, properties));
+            self.brush_properties = Some(properties);
+        }
+
+        // Create the path
+        let element_id = self.target.assign_element_id();
+        self.path_edit(PathEdit::CreatePath(element_id, Arc::new(components)));
+
+        Some(element_id)
+    }
+
+    ///
+    /// Groups a set of elements, returning the ID of the group (or the original element if there's only one)
+    ///
+    pub (crate) fn group(&mut self, element_ids: Vec<ElementId>) -> Option<ElementId> {
+        match element_ids.len() {
+            0 => None,
+            1 => Some(element_ids[0]),
+            _ => {
+                let group_id = self.target.assign_element_id();
+                self.edits.push(AnimationEdit::Element(element_ids, ElementEdit::Group(group_id, GroupType::Normal)));
+
+                Some(group_id)
+            }
+        }
+    }
+
+    ///
+    /// Imports the children of an element, returning the IDs of the elements that were created
+    ///
+    fn import_children(&mut self, node: &SvgNode, style: &SvgStyle, transform: &SvgTransform) -> Vec<ElementId> {
+        node.children.iter()
+            .flat_map(|child| self.import_node(child, style, transform))
+            .collect()
+    }
+
+    ///
+    /// Imports an element from the SVG document, returning the ID of the element that was created for it
+    ///
+    fn import_node(&mut self, node: &SvgNode, parent_style: &SvgStyle, parent_transform: &SvgTransform) -> Option<ElementId> {
+        // Work out the style and transform for this element
+        let style       = parent_style.for_child(&node.attributes);
+        let transform   = node.attributes.get("transform")
+            .and_then(|transform| SvgTransform::parse(transform))
+            .map(|transform| parent_transform.then_apply_to(&transform))
+            .unwrap_or(*parent_transform);
+
+        if !style.visible { return None; }
+
+        match node.name.as_str() {
+            // Containers become groups
+            "g" | "svg" | "a" | "switch" => {
+                let children = self.import_children(node, &style, &transform);
+                self.group(children)
+            }
+
+            // Shapes are converted to paths: a shape that is filled and stroked becomes a group of two paths
+            name => {
+                let subpaths        = shape_subpaths(name, &node.attributes)?;
+                let subpaths        = subpaths.iter().map(|subpath| subpath.transform(&transform)).collect::<Vec<_>>();
+                let mut element_ids = vec![];
+
+                if let (Some(fill), true) = (style.fill, name != "line") {
+                    element_ids.extend(self.create_path(&subpaths, fill, style.fill_opacity*style.opacity, 1.0));
+                }
+
+                if let Some(stroke) = style.stroke {
+                    let width   = style.stroke_width * transform.length_scale();
+                    let outline = stroke_outline(&subpaths, width);
+
+                    element_ids.extend(self.create_path(&outline, stroke, style.stroke_opacity*style.opacity, width));
+                }
+
+                self.group(element_ids)
+            }
+        }
+    }
+}
+
+///
+/// Generates the edits that will add the contents of an SVG document to a layer in an animation
+///
+/// Paths, basic shapes, fills, strokes, groups and transforms are imported: each shape becomes a path element (strokes are
+/// converted to filled outlines), and groups in the SVG document become groups in the animation. If there is more than one
+/// element at the top level of the document, they're all put in a single group so the import can be moved around as one.
+/// Elements that can't be represented in an animation (text, images, gradients, clipping and so on) are skipped.
+///
+/// The elements are added to the keyframe at `when`, which is created if the layer has no keyframe at or before this time.
+/// The document size (or the size of the animation if the document doesn't have one) is used to flip the coordinates so
+/// the document appears the right way up.
+///
+pub fn svg_import_edits<Target: EditableAnimation+?Sized>(svg: &str, target: &Target, layer_id: u64, when: Duration) -> Result<Vec<AnimationEdit>, SvgImportError> {
+    let root            = read_svg_document(svg)?;
+    let transform       = document_transform(&root, target.size());
+
+    let mut importer    = SvgImporter::new(target, layer_id, when);
+
+    // Import the document
+    let root_style      = SvgStyle::new().for_child(&root.attributes);
+    let element_ids     = if root_style.visible { importer.import_children(&root, &root_style, &transform) } else { vec![] };
+
+    if element_ids.len() == 0 {
+        return Ok(vec![]);
+    }
+
+    importer.group(element_ids);
+
+    // Create a keyframe to add the elements to if there isn't one already
+    let mut edits       = vec![];
+    let has_keyframe    = target.get_layer_with_id(layer_id)
+        .map(|layer| layer.get_key_frames_during_time(Duration::from_millis(0)..(when + Duration::from_nanos(1))).next().is_some())
+        .unwrap_or(true);
+
+    if !has_keyframe {
+        edits.push(AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(when)));
+    }
+
+    edits.extend(importer.into_edits());
+
+    Ok(edits)
+}
@@ -0,0 +1,20 @@
+//!
+//! Importing SVG documents as vector elements
+//!
+
+mod number_reader;
+mod transform;
+mod path_data;
+mod shapes;
+mod style;
+mod stroke;
+mod import;
+
+#[cfg(test)] mod tests;
+
+pub use self::transform::*;
+pub use self::path_data::*;
+pub use self::stroke::*;
+pub use self::import::*;
+
+pub (crate) use self::shapes::*;
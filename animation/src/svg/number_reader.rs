@@ -0,0 +1,191 @@
+use std::iter::{Peekable};
+use std::str::{Chars};
+
+///
+/// Reads the numbers, flags and commands found in SVG attributes such as path data and transforms
+///
+/// SVG allows numbers to be separated by whitespace or commas, or not at all if the next number starts with a sign or a
+/// decimal point (so `10-5.5.5` is the three numbers `10`, `-5.5` and `.5`)
+///
+pub (crate) struct NumberReader<'a> {
+    chars: Peekable<Chars<'a>>
+}
+
+impl<'a> NumberReader<'a> {
+    ///
+    /// Creates a reader for a string
+    ///
+    pub fn new(source: &'a str) -> NumberReader<'a> {
+        NumberReader {
+            chars: source.chars().peekable()
+        }
+    }
+
+    ///
+    /// Skips any whitespace and commas
+    ///
+    pub fn skip_separators(&mut self) {
+        while let Some(chr) = self.chars.peek() {
+            if chr.is_whitespace() || *chr == ',' {
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+    }
+
+    ///
+    /// Returns the next character after any separators without consuming it
+    ///
+    pub fn peek(&mut self) -> Option<char> {
+        self.skip_separators();
+        self.chars.peek().cloned()
+    }
+
+    ///
+    /// Reads the next character after any separators
+    ///
+    pub fn next_char(&mut self) -> Option<char> {
+        self.skip_separators();
+        self.chars.next()
+    }
+
+    ///
+    /// True if there are no more characters to read
+    ///
+    pub fn at_end(&mut self) -> bool {
+        self.peek().is_none()
+    }
+
+    ///
+    /// Reads an identifier (a sequence of letters, used for transform names)
+    ///
+    pub fn identifier(&mut self) -> String {
+        self.skip_separators();
+
+        let mut identifier = String::new();
+        while let Some(chr) = self.chars.peek() {
+            if chr.is_ascii_alphabetic() {
+                identifier.push(*chr);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+
+        identifier
+    }
+
+    ///
+    /// Reads a number, returning None if the next item is not a number
+    ///
+    pub fn number(&mut self) -> Option<f64> {
+        self.skip_separators();
+
+        let mut number = String::new();
+
+        // Optional sign
+        if let Some(sign) = self.chars.peek().cloned() {
+            if sign == '-' || sign == '+' {
+                number.push(sign);
+                self.chars.next();
+            }
+        }
+
+        // Integer and fractional parts (a second decimal point starts a new number)
+        let mut seen_point = false;
+        while let Some(chr) = self.chars.peek().cloned() {
+            if chr.is_ascii_digit() {
+                number.push(chr);
+                self.chars.next();
+            } else if chr == '.' && !seen_point {
+                seen_point = true;
+                number.push(chr);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+
+        // Exponent
+        if let Some(chr) = self.chars.peek().cloned() {
+            if chr == 'e' || chr == 'E' {
+                number.push(chr);
+                self.chars.next();
+
+                if let Some(sign) = self.chars.peek().cloned() {
+                    if sign == '-' || sign == '+' {
+                        number.push(sign);
+                        self.chars.next();
+                    }
+                }
+
+                while let Some(chr) = self.chars.peek().cloned() {
+                    if chr.is_ascii_digit() {
+                        number.push(chr);
+                        self.chars.next();
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+
+        number.parse().ok()
+    }
+
+    ///
+    /// Reads a flag (used by arcs, which allow flags to be written without separators, eg `a10 10 0 11 20 20`)
+    ///
+    pub fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+
+        match self.chars.peek() {
+            Some('0')   => { self.chars.next(); Some(false) },
+            Some('1')   => { self.chars.next(); Some(true) },
+            _           => None
+        }
+    }
+
+    ///
+    /// Reads all of the remaining numbers in the string
+    ///
+    pub fn all_numbers(&mut self) -> Vec<f64> {
+        let mut numbers = vec![];
+
+        while let Some(number) = self.number() {
+            numbers.push(number);
+        }
+
+        numbers
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_separated_numbers() {
+        assert!(NumberReader::new("1 2,3 , 4").all_numbers() == vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn read_compact_numbers() {
+        assert!(NumberReader::new("10-5.5.5").all_numbers() == vec![10.0, -5.5, 0.5]);
+    }
+
+    #[test]
+    fn read_exponents() {
+        assert!(NumberReader::new("1e2 1.5E-1").all_numbers() == vec![100.0, 0.15]);
+    }
+
+    #[test]
+    fn read_compact_flags() {
+        let mut reader = NumberReader::new("11 20");
+
+        assert!(reader.flag() == Some(true));
+        assert!(reader.flag() == Some(true));
+        assert!(reader.number() == Some(20.0));
+    }
+}
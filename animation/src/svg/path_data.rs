@@ -0,0 +1,365 @@
+use super::transform::*;
+use super::number_reader::*;
+
+use flo_curves::*;
+use flo_curves::bezier;
+
+use std::f64;
+
+///
+/// A subpath read from an SVG file, converted to bezier curves
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct SvgSubpath {
+    /// The curves making up this subpath, in order
+    pub curves: Vec<bezier::Curve<Coord2>>,
+
+    /// True if this subpath was closed (so its last curve ends where the first one starts)
+    pub closed: bool
+}
+
+impl SvgSubpath {
+    ///
+    /// Applies a transform to the points in this subpath
+    ///
+    pub fn transform(&self, transform: &SvgTransform) -> SvgSubpath {
+        let curves = self.curves.iter()
+            .map(|curve| {
+                let (cp1, cp2) = curve.control_points();
+                bezier::Curve::from_points(transform.apply(curve.start_point()), (transform.apply(cp1), transform.apply(cp2)), transform.apply(curve.end_point()))
+            })
+            .collect();
+
+        SvgSubpath { curves: curves, closed: self.closed }
+    }
+}
+
+///
+/// Creates a curve representing a straight line
+///
+pub (crate) fn line_curve(from: Coord2, to: Coord2) -> bezier::Curve<Coord2> {
+    let cp1 = from + (to-from)*(1.0/3.0);
+    let cp2 = from + (to-from)*(2.0/3.0);
+
+    bezier::Curve::from_points(from, (cp1, cp2), to)
+}
+
+///
+/// Creates the curves for an elliptical arc, using the endpoint parameterisation used by SVG
+///
+/// `x_axis_rotation` is in degrees. See the 'implementation notes' section of the SVG specification for how this works.
+///
+pub (crate) fn arc_curves(from: Coord2, radius: (f64, f64), x_axis_rotation: f64, large_arc: bool, sweep: bool, to: Coord2) -> Vec<bezier::Curve<Coord2>> {
+    let (rx, ry) = (radius.0.abs(), radius.1.abs());
+
+    // Degenerate arcs are either omitted or become straight lines
+    if from == to                   { return vec![]; }
+    if rx == 0.0 || ry == 0.0       { return vec![line_curve(from, to)]; }
+
+    // Work out the position of the start point relative to the center
+    let (sin_phi, cos_phi)  = x_axis_rotation.to_radians().sin_cos();
+    let half_x              = (from.x() - to.x()) / 2.0;
+    let half_y              = (from.y() - to.y()) / 2.0;
+    let x1                  = cos_phi*half_x + sin_phi*half_y;
+    let y1                  = -sin_phi*half_x + cos_phi*half_y;
+
+    // Radii that are too small are scaled up so the arc can reach the end point
+    let lambda              = (x1*x1)/(rx*rx) + (y1*y1)/(ry*ry);
+    let (rx, ry)            = if lambda > 1.0 { (rx*lambda.sqrt(), ry*lambda.sqrt()) } else { (rx, ry) };
+
+    // Find the center
+    let numerator           = rx*rx*ry*ry - rx*rx*y1*y1 - ry*ry*x1*x1;
+    let denominator         = rx*rx*y1*y1 + ry*ry*x1*x1;
+    let sign                = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coefficient         = sign * (numerator/denominator).max(0.0).sqrt();
+    let cx1                 = coefficient * rx*y1/ry;
+    let cy1                 = coefficient * -ry*x1/rx;
+    let cx                  = cos_phi*cx1 - sin_phi*cy1 + (from.x()+to.x())/2.0;
+    let cy                  = sin_phi*cx1 + cos_phi*cy1 + (from.y()+to.y())/2.0;
+
+    // Find the start angle and the angle covered by the arc
+    let angle_between       = |ux: f64, uy: f64, vx: f64, vy: f64| (ux*vy - uy*vx).atan2(ux*vx + uy*vy);
+    let start_angle         = angle_between(1.0, 0.0, (x1-cx1)/rx, (y1-cy1)/ry);
+    let mut sweep_angle     = angle_between((x1-cx1)/rx, (y1-cy1)/ry, (-x1-cx1)/rx, (-y1-cy1)/ry);
+
+    if !sweep && sweep_angle > 0.0 { sweep_angle -= 2.0*f64::consts::PI; }
+    if sweep && sweep_angle < 0.0 { sweep_angle += 2.0*f64::consts::PI; }
+
+    // Approximate the arc with one bezier curve for every quarter-turn
+    let num_curves          = (sweep_angle.abs() / (f64::consts::PI/2.0) - 1e-9).ceil().max(1.0) as usize;
+    let curve_angle         = sweep_angle / (num_curves as f64);
+    let control_distance    = 4.0/3.0 * (curve_angle/4.0).tan();
+
+    let to_ellipse          = |ux: f64, uy: f64| Coord2(cx + rx*ux*cos_phi - ry*uy*sin_phi, cy + rx*ux*sin_phi + ry*uy*cos_phi);
+
+    let mut curves          = vec![];
+    let mut start_point     = from;
+
+    for curve_num in 0..num_curves {
+        let angle1          = start_angle + (curve_num as f64)*curve_angle;
+        let angle2          = angle1 + curve_angle;
+        let (sin1, cos1)    = angle1.sin_cos();
+        let (sin2, cos2)    = angle2.sin_cos();
+
+        let cp1             = to_ellipse(cos1 - control_distance*sin1, sin1 + control_distance*cos1);
+        let cp2             = to_ellipse(cos2 + control_distance*sin2, sin2 - control_distance*cos2);
+        let end_point       = if curve_num == num_curves-1 { to } else { to_ellipse(cos2, sin2) };
+
+        curves.push(bezier::Curve::from_points(start_point, (cp1, cp2), end_point));
+        start_point         = end_point;
+    }
+
+    curves
+}
+
+///
+/// Builds up the subpaths for a path
+///
+struct SubpathBuilder {
+    subpaths:       Vec<SvgSubpath>,
+    current:        Vec<bezier::Curve<Coord2>>,
+    start_point:    Coord2,
+    current_point:  Coord2
+}
+
+impl SubpathBuilder {
+    fn new() -> SubpathBuilder {
+        SubpathBuilder {
+            subpaths:       vec![],
+            current:        vec![],
+            start_point:    Coord2(0.0, 0.0),
+            current_point:  Coord2(0.0, 0.0)
+        }
+    }
+
+    fn finish_subpath(&mut self, closed: bool) {
+        if self.current.len() > 0 {
+            let curves = self.current.drain(..).collect();
+            self.subpaths.push(SvgSubpath { curves: curves, closed: closed });
+        }
+    }
+
+    fn move_to(&mut self, point: Coord2) {
+        self.finish_subpath(false);
+
+        self.start_point    = point;
+        self.current_point  = point;
+    }
+
+    fn add_curves(&mut self, curves: Vec<bezier::Curve<Coord2>>) {
+        if let Some(last_curve) = curves.last() {
+            self.current_point = last_curve.end_point();
+        }
+
+        self.current.extend(curves);
+    }
+
+    fn close(&mut self) {
+        if self.current_point != self.start_point {
+            self.current.push(line_curve(self.current_point, self.start_point));
+        }
+
+        self.finish_subpath(true);
+        self.current_point = self.start_point;
+    }
+
+    fn finish(mut self) -> Vec<SvgSubpath> {
+        self.finish_subpath(false);
+        self.subpaths
+    }
+}
+
+///
+/// Reads the subpaths from the value of the `d` attribute of a path element
+///
+/// Reading stops at the first error, as the SVG specification requires, so everything up to that point is still returned.
+///
+pub fn parse_path_data(path_data: &str) -> Vec<SvgSubpath> {
+    let mut reader          = NumberReader::new(path_data);
+    let mut builder         = SubpathBuilder::new();
+    let mut command         = None;
+
+    // The second control point of the last curve, used for the smooth curve commands
+    let mut last_cubic_cp   = None;
+    let mut last_quad_cp    = None;
+
+    loop {
+        // Commands can be repeated by just supplying more numbers
+        match reader.peek() {
+            None                                => { break; }
+            Some(chr) if chr.is_ascii_alphabetic() => { command = reader.next_char(); }
+            Some(_)                             => {
+                // Extra coordinates after a move command are treated as lines
+                command = match command {
+                    Some('M')   => Some('L'),
+                    Some('m')   => Some('l'),
+                    Some('Z')   |
+                    Some('z')   |
+                    None        => { break; }
+                    other       => other
+                };
+            }
+        }
+
+        let current     = builder.current_point;
+        let relative    = |x: f64, y: f64, is_relative: bool| if is_relative { Coord2(current.x()+x, current.y()+y) } else { Coord2(x, y) };
+        let command_chr = match command { Some(command_chr) => command_chr, None => { break; } };
+        let is_relative = command_chr.is_ascii_lowercase();
+
+        let (next_cubic_cp, next_quad_cp) = match command_chr.to_ascii_uppercase() {
+            'M' => {
+                let (x, y) = match (reader.number(), reader.number()) { (Some(x), Some(y)) => (x, y), _ => { break; } };
+                builder.move_to(relative(x, y, is_relative));
+                (None, None)
+            }
+
+            'L' => {
+                let (x, y) = match (reader.number(), reader.number()) { (Some(x), Some(y)) => (x, y), _ => { break; } };
+                builder.add_curves(vec![line_curve(current, relative(x, y, is_relative))]);
+                (None, None)
+            }
+
+            'H' => {
+                let x = match reader.number() { Some(x) => x, None => { break; } };
+                let x = if is_relative { current.x() + x } else { x };
+                builder.add_curves(vec![line_curve(current, Coord2(x, current.y()))]);
+                (None, None)
+            }
+
+            'V' => {
+                let y = match reader.number() { Some(y) => y, None => { break; } };
+                let y = if is_relative { current.y() + y } else { y };
+                builder.add_curves(vec![line_curve(current, Coord2(current.x(), y))]);
+                (None, None)
+            }
+
+            'C' => {
+                let coords = (0..6).map(|_| reader.number()).collect::<Option<Vec<_>>>();
+                let coords = match coords { Some(coords) => coords, None => { break; } };
+
+                let cp1 = relative(coords[0], coords[1], is_relative);
+                let cp2 = relative(coords[2], coords[3], is_relative);
+                let end = relative(coords[4], coords[5], is_relative);
+
+                builder.add_curves(vec![bezier::Curve::from_points(current, (cp1, cp2), end)]);
+                (Some(cp2), None)
+            }
+
+            'S' => {
+                let coords = (0..4).map(|_| reader.number()).collect::<Option<Vec<_>>>();
+                let coords = match coords { Some(coords) => coords, None => { break; } };
+
+                // The first control point is the reflection of the last one
+                let cp1 = last_cubic_cp.map(|last_cp: Coord2| current*2.0 - last_cp).unwrap_or(current);
+                let cp2 = relative(coords[0], coords[1], is_relative);
+                let end = relative(coords[2], coords[3], is_relative);
+
+                builder.add_curves(vec![bezier::Curve::from_points(current, (cp1, cp2), end)]);
+                (Some(cp2), None)
+            }
+
+            'Q' => {
+                let coords = (0..4).map(|_| reader.number()).collect::<Option<Vec<_>>>();
+                let coords = match coords { Some(coords) => coords, None => { break; } };
+
+                let cp  = relative(coords[0], coords[1], is_relative);
+                let end = relative(coords[2], coords[3], is_relative);
+
+                builder.add_curves(vec![quadratic_curve(current, cp, end)]);
+                (None, Some(cp))
+            }
+
+            'T' => {
+                let coords = (0..2).map(|_| reader.number()).collect::<Option<Vec<_>>>();
+                let coords = match coords { Some(coords) => coords, None => { break; } };
+
+                let cp  = last_quad_cp.map(|last_cp: Coord2| current*2.0 - last_cp).unwrap_or(current);
+                let end = relative(coords[0], coords[1], is_relative);
+
+                builder.add_curves(vec![quadratic_curve(current, cp, end)]);
+                (None, Some(cp))
+            }
+
+            'A' => {
+                let radius      = match (reader.number(), reader.number()) { (Some(rx), Some(ry)) => (rx, ry), _ => { break; } };
+                let rotation    = match reader.number() { Some(rotation) => rotation, None => { break; } };
+                let flags       = match (reader.flag(), reader.flag()) { (Some(large_arc), Some(sweep)) => (large_arc, sweep), _ => { break; } };
+                let (x, y)      = match (reader.number(), reader.number()) { (Some(x), Some(y)) => (x, y), _ => { break; } };
+
+                builder.add_curves(arc_curves(current, radius, rotation, flags.0, flags.1, relative(x, y, is_relative)));
+                (None, None)
+            }
+
+            'Z' => {
+                builder.close();
+                (None, None)
+            }
+
+            _ => { break; }
+        };
+
+        last_cubic_cp   = next_cubic_cp;
+        last_quad_cp    = next_quad_cp;
+    }
+
+    builder.finish()
+}
+
+///
+/// Converts a quadratic curve to a cubic one
+///
+fn quadratic_curve(from: Coord2, control_point: Coord2, to: Coord2) -> bezier::Curve<Coord2> {
+    let cp1 = from + (control_point-from)*(2.0/3.0);
+    let cp2 = to + (control_point-to)*(2.0/3.0);
+
+    bezier::Curve::from_points(from, (cp1, cp2), to)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_closed_triangle() {
+        let subpaths = parse_path_data("M10 10 L20 10 L20 20 Z");
+
+        assert!(subpaths.len() == 1);
+        assert!(subpaths[0].closed);
+        assert!(subpaths[0].curves.len() == 3);
+        assert!(subpaths[0].curves[2].end_point() == Coord2(10.0, 10.0));
+    }
+
+    #[test]
+    fn parse_relative_lines() {
+        let subpaths = parse_path_data("m10,10 10,0 v10 h-5");
+
+        assert!(subpaths.len() == 1);
+        assert!(!subpaths[0].closed);
+        assert!(subpaths[0].curves.iter().map(|curve| curve.end_point()).collect::<Vec<_>>() == vec![Coord2(20.0, 10.0), Coord2(20.0, 20.0), Coord2(15.0, 20.0)]);
+    }
+
+    #[test]
+    fn parse_multiple_subpaths() {
+        let subpaths = parse_path_data("M0 0 L10 0 Z M20 20 C 25 20 30 25 30 30");
+
+        assert!(subpaths.len() == 2);
+        assert!(subpaths[1].curves[0].control_points() == (Coord2(25.0, 20.0), Coord2(30.0, 25.0)));
+    }
+
+    #[test]
+    fn parse_arc() {
+        let subpaths    = parse_path_data("M0 0 A10 10 0 0 1 20 0");
+        let end_point   = subpaths[0].curves.last().unwrap().end_point();
+
+        assert!(subpaths[0].curves.len() == 2);
+        assert!(end_point == Coord2(20.0, 0.0));
+    }
+
+    #[test]
+    fn stop_at_error() {
+        let subpaths = parse_path_data("M0 0 L10 0 L20 X 30 30");
+
+        assert!(subpaths.len() == 1);
+        assert!(subpaths[0].curves.len() == 1);
+    }
+}
@@ -0,0 +1,119 @@
+use super::style::*;
+use super::path_data::*;
+use super::number_reader::*;
+
+use flo_curves::*;
+
+use std::collections::{HashMap};
+
+///
+/// Reads a length attribute, using 0 if it's missing
+///
+fn length_attribute(attributes: &HashMap<String, String>, name: &str) -> f64 {
+    attributes.get(name).and_then(|value| parse_length(value)).unwrap_or(0.0)
+}
+
+///
+/// Creates a subpath that visits a list of points
+///
+fn polyline(points: &[Coord2], closed: bool) -> Vec<SvgSubpath> {
+    if points.len() < 2 { return vec![]; }
+
+    let mut curves = points.windows(2)
+        .map(|line| line_curve(line[0], line[1]))
+        .collect::<Vec<_>>();
+
+    if closed && points[0] != points[points.len()-1] {
+        curves.push(line_curve(points[points.len()-1], points[0]));
+    }
+
+    vec![SvgSubpath { curves: curves, closed: closed }]
+}
+
+///
+/// Reads the value of a `points` attribute
+///
+fn parse_points(points: &str) -> Vec<Coord2> {
+    NumberReader::new(points).all_numbers()
+        .chunks_exact(2)
+        .map(|coords| Coord2(coords[0], coords[1]))
+        .collect()
+}
+
+///
+/// Creates an ellipse as a closed subpath
+///
+pub (crate) fn ellipse(center: Coord2, rx: f64, ry: f64) -> Vec<SvgSubpath> {
+    if rx <= 0.0 || ry <= 0.0 { return vec![]; }
+
+    let start   = Coord2(center.x()+rx, center.y());
+    let middle  = Coord2(center.x()-rx, center.y());
+
+    let mut curves = arc_curves(start, (rx, ry), 0.0, false, true, middle);
+    curves.extend(arc_curves(middle, (rx, ry), 0.0, false, true, start));
+
+    vec![SvgSubpath { curves: curves, closed: true }]
+}
+
+///
+/// Creates a rectangle (which may have rounded corners)
+///
+pub (crate) fn rect(x: f64, y: f64, width: f64, height: f64, rx: f64, ry: f64) -> Vec<SvgSubpath> {
+    if width <= 0.0 || height <= 0.0 { return vec![]; }
+
+    if rx <= 0.0 || ry <= 0.0 {
+        polyline(&[Coord2(x, y), Coord2(x+width, y), Coord2(x+width, y+height), Coord2(x, y+height)], true)
+    } else {
+        // Corners are quarter-ellipses between the straight edges
+        let radius  = (rx, ry);
+        let mut curves = vec![];
+
+        curves.push(line_curve(Coord2(x+rx, y), Coord2(x+width-rx, y)));
+        curves.extend(arc_curves(Coord2(x+width-rx, y), radius, 0.0, false, true, Coord2(x+width, y+ry)));
+        curves.push(line_curve(Coord2(x+width, y+ry), Coord2(x+width, y+height-ry)));
+        curves.extend(arc_curves(Coord2(x+width, y+height-ry), radius, 0.0, false, true, Coord2(x+width-rx, y+height)));
+        curves.push(line_curve(Coord2(x+width-rx, y+height), Coord2(x+rx, y+height)));
+        curves.extend(arc_curves(Coord2(x+rx, y+height), radius, 0.0, false, true, Coord2(x, y+height-ry)));
+        curves.push(line_curve(Coord2(x, y+height-ry), Coord2(x, y+ry)));
+        curves.extend(arc_curves(Coord2(x, y+ry), radius, 0.0, false, true, Coord2(x+rx, y)));
+
+        // Omit the straight edges that have no length (when the corners meet)
+        let curves = curves.into_iter().filter(|curve| curve.start_point() != curve.end_point()).collect();
+
+        vec![SvgSubpath { curves: curves, closed: true }]
+    }
+}
+
+///
+/// Returns the subpaths for an SVG shape element, or None if the element is not a shape
+///
+pub (crate) fn shape_subpaths(element_name: &str, attributes: &HashMap<String, String>) -> Option<Vec<SvgSubpath>> {
+    let length = |name: &str| length_attribute(attributes, name);
+
+    match element_name {
+        "path"      => Some(attributes.get("d").map(|path_data| parse_path_data(path_data)).unwrap_or_else(|| vec![])),
+        "line"      => Some(polyline(&[Coord2(length("x1"), length("y1")), Coord2(length("x2"), length("y2"))], false)),
+        "polyline"  => Some(attributes.get("points").map(|points| polyline(&parse_points(points), false)).unwrap_or_else(|| vec![])),
+        "polygon"   => Some(attributes.get("points").map(|points| polyline(&parse_points(points), true)).unwrap_or_else(|| vec![])),
+        "circle"    => Some(ellipse(Coord2(length("cx"), length("cy")), length("r"), length("r"))),
+        "ellipse"   => Some(ellipse(Coord2(length("cx"), length("cy")), length("rx"), length("ry"))),
+
+        "rect"      => {
+            let (width, height) = (length("width"), length("height"));
+
+            // If only one radius is specified, it's used for both
+            let rx = attributes.get("rx").and_then(|rx| parse_length(rx));
+            let ry = attributes.get("ry").and_then(|ry| parse_length(ry));
+            let (rx, ry) = match (rx, ry) {
+                (Some(rx), Some(ry))    => (rx, ry),
+                (Some(rx), None)        => (rx, rx),
+                (None, Some(ry))        => (ry, ry),
+                (None, None)            => (0.0, 0.0)
+            };
+
+            Some(rect(length("x"), length("y"), width, height, rx.min(width/2.0), ry.min(height/2.0)))
+        }
+
+        _           => None
+    }
+}
@@ -0,0 +1,156 @@
+use super::path_data::*;
+
+use flo_curves::*;
+use flo_curves::bezier;
+
+///
+/// True if a curve is (close enough to) a straight line
+///
+fn is_straight(curve: &bezier::Curve<Coord2>) -> bool {
+    let start       = curve.start_point();
+    let end         = curve.end_point();
+    let (cp1, cp2)  = curve.control_points();
+    let length      = start.distance_to(&end);
+
+    if length < 1e-9 {
+        start.distance_to(&cp1) < 1e-9 && start.distance_to(&cp2) < 1e-9
+    } else {
+        let direction       = (end-start) * (1.0/length);
+        let distance_from   = |point: Coord2| {
+            let offset = point-start;
+            (offset.x()*direction.y() - offset.y()*direction.x()).abs()
+        };
+
+        distance_from(cp1) < 1e-6 && distance_from(cp2) < 1e-6
+    }
+}
+
+///
+/// Returns the curves offset to each side of a curve
+///
+fn offset_curves(curve: &bezier::Curve<Coord2>, distance: f64) -> (Vec<bezier::Curve<Coord2>>, Vec<bezier::Curve<Coord2>>) {
+    if is_straight(curve) {
+        // Straight lines are offset along their normal
+        let start   = curve.start_point();
+        let end     = curve.end_point();
+        let length  = start.distance_to(&end);
+
+        if length < 1e-9 { return (vec![], vec![]); }
+
+        let normal  = Coord2(-(end.y()-start.y()), end.x()-start.x()) * (distance/length);
+
+        (vec![line_curve(start+normal, end+normal)], vec![line_curve(start-normal, end-normal)])
+    } else {
+        (bezier::offset(curve, distance, distance), bezier::offset(curve, -distance, -distance))
+    }
+}
+
+///
+/// Reverses the direction of a curve
+///
+fn reverse_curve(curve: &bezier::Curve<Coord2>) -> bezier::Curve<Coord2> {
+    let (cp1, cp2) = curve.control_points();
+
+    bezier::Curve::from_points(curve.end_point(), (cp2, cp1), curve.start_point())
+}
+
+///
+/// Returns the approximate signed area of a closed set of curves (positive if the curves run anticlockwise)
+///
+fn signed_area(curves: &[bezier::Curve<Coord2>]) -> f64 {
+    curves.iter()
+        .flat_map(|curve| {
+            let (cp1, cp2) = curve.control_points();
+            vec![(curve.start_point(), cp1), (cp1, cp2), (cp2, curve.end_point())]
+        })
+        .map(|(p1, p2)| p1.x()*p2.y() - p2.x()*p1.y())
+        .sum::<f64>() / 2.0
+}
+
+///
+/// Ensures that a closed subpath runs anticlockwise
+///
+fn anticlockwise(curves: Vec<bezier::Curve<Coord2>>) -> Vec<bezier::Curve<Coord2>> {
+    if signed_area(&curves) < 0.0 {
+        curves.iter().rev().map(|curve| reverse_curve(curve)).collect()
+    } else {
+        curves
+    }
+}
+
+///
+/// Creates a circle as a closed subpath
+///
+fn circle(center: Coord2, radius: f64) -> SvgSubpath {
+    let start   = Coord2(center.x()+radius, center.y());
+    let middle  = Coord2(center.x()-radius, center.y());
+
+    let mut curves = arc_curves(start, (radius, radius), 0.0, false, true, middle);
+    curves.extend(arc_curves(middle, (radius, radius), 0.0, false, true, start));
+
+    SvgSubpath { curves: anticlockwise(curves), closed: true }
+}
+
+///
+/// Converts the outline of a set of subpaths into a set of closed subpaths that can be filled to draw the outline
+///
+/// Every curve in the original subpaths becomes a closed shape, and the joins between curves are rounded off. All of the
+/// shapes run in the same direction so that they combine rather than cancel out when filled. The ends of subpaths that
+/// are not closed are left square.
+///
+pub fn stroke_outline(subpaths: &[SvgSubpath], width: f64) -> Vec<SvgSubpath> {
+    let distance    = width / 2.0;
+    let mut outline = vec![];
+
+    if distance <= 0.0 { return outline; }
+
+    for subpath in subpaths.iter() {
+        for (curve_num, curve) in subpath.curves.iter().enumerate() {
+            // Outline of the curve itself
+            let (up, down)  = offset_curves(curve, distance);
+            if up.len() == 0 || down.len() == 0 { continue; }
+
+            let mut shape   = up.clone();
+            let up_end      = up.last().unwrap().end_point();
+            let down_end    = down.last().unwrap().end_point();
+            let down_start  = down[0].start_point();
+            let up_start    = up[0].start_point();
+
+            shape.push(line_curve(up_end, down_end));
+            shape.extend(down.iter().rev().map(|curve| reverse_curve(curve)));
+            shape.push(line_curve(down_start, up_start));
+
+            outline.push(SvgSubpath { curves: anticlockwise(shape), closed: true });
+
+            // Round join at the start of every curve after the first (and at the start of the first curve in a closed subpath)
+            if curve_num > 0 || subpath.closed {
+                outline.push(circle(curve.start_point(), distance));
+            }
+        }
+    }
+
+    outline
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn outline_of_line_is_rectangle() {
+        let line    = SvgSubpath { curves: vec![line_curve(Coord2(0.0, 0.0), Coord2(10.0, 0.0))], closed: false };
+        let outline = stroke_outline(&[line], 2.0);
+
+        assert!(outline.len() == 1);
+        assert!(outline[0].curves.len() == 4);
+        assert!(signed_area(&outline[0].curves) > 19.9 && signed_area(&outline[0].curves) < 20.1);
+    }
+
+    #[test]
+    fn closed_path_has_joins() {
+        let triangle = parse_path_data("M0 0 L10 0 L10 10 Z");
+        let outline  = stroke_outline(&triangle, 2.0);
+
+        assert!(outline.len() == 6);
+    }
+}
@@ -0,0 +1,212 @@
+use super::number_reader::*;
+
+use flo_canvas::*;
+
+use std::collections::{HashMap};
+
+///
+/// The presentation properties of an SVG element that affect how it's imported
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub (crate) struct SvgStyle {
+    /// The colour that the element is filled with (None if it's not filled)
+    pub fill: Option<Color>,
+
+    /// The colour that the outline of the element is drawn with (None if it has no outline)
+    pub stroke: Option<Color>,
+
+    /// The width of the outline
+    pub stroke_width: f64,
+
+    /// The opacity of the fill
+    pub fill_opacity: f64,
+
+    /// The opacity of the outline
+    pub stroke_opacity: f64,
+
+    /// The opacity of the element (including the opacity of any group it's in)
+    pub opacity: f64,
+
+    /// False if the element is hidden
+    pub visible: bool
+}
+
+impl SvgStyle {
+    ///
+    /// The style used for the root element (black fill and no stroke)
+    ///
+    pub fn new() -> SvgStyle {
+        SvgStyle {
+            fill:           Some(Color::Rgba(0.0, 0.0, 0.0, 1.0)),
+            stroke:         None,
+            stroke_width:   1.0,
+            fill_opacity:   1.0,
+            stroke_opacity: 1.0,
+            opacity:        1.0,
+            visible:        true
+        }
+    }
+
+    ///
+    /// Creates the style for an element with the specified attributes whose parent has this style
+    ///
+    /// Presentation attributes are read first and can be overridden by declarations in the `style` attribute.
+    ///
+    pub fn for_child(&self, attributes: &HashMap<String, String>) -> SvgStyle {
+        let mut style = *self;
+
+        for (name, value) in attributes.iter() {
+            style.set_property(name, value);
+        }
+
+        if let Some(declarations) = attributes.get("style") {
+            for declaration in declarations.split(';') {
+                let mut parts = declaration.splitn(2, ':');
+
+                if let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+                    style.set_property(name.trim(), value.trim());
+                }
+            }
+        }
+
+        style
+    }
+
+    ///
+    /// Updates a property in this style. Values that can't be read are ignored.
+    ///
+    fn set_property(&mut self, name: &str, value: &str) {
+        match name {
+            "fill"              => { if let Some(fill) = parse_paint(value) { self.fill = fill; } }
+            "stroke"            => { if let Some(stroke) = parse_paint(value) { self.stroke = stroke; } }
+            "stroke-width"      => { if let Some(width) = parse_length(value) { self.stroke_width = width; } }
+            "fill-opacity"      => { if let Some(opacity) = parse_length(value) { self.fill_opacity = opacity.max(0.0).min(1.0); } }
+            "stroke-opacity"    => { if let Some(opacity) = parse_length(value) { self.stroke_opacity = opacity.max(0.0).min(1.0); } }
+            "opacity"           => { if let Some(opacity) = parse_length(value) { self.opacity *= opacity.max(0.0).min(1.0); } }
+            "display"           => { if value.trim() == "none" { self.visible = false; } }
+            "visibility"        => { self.visible = value.trim() == "visible"; }
+            _                   => { }
+        }
+    }
+}
+
+///
+/// Reads a length, ignoring any units (so `10px` and `10` are both read as 10)
+///
+pub (crate) fn parse_length(length: &str) -> Option<f64> {
+    NumberReader::new(length).number()
+}
+
+///
+/// Reads a paint value: returns `Some(None)` for `none` and None if the paint can't be read
+///
+/// Only plain colours are supported: gradients and patterns can't be read.
+///
+fn parse_paint(paint: &str) -> Option<Option<Color>> {
+    let paint = paint.trim();
+
+    if paint == "none" || paint == "transparent" {
+        Some(None)
+    } else {
+        parse_color(paint).map(|color| Some(color))
+    }
+}
+
+///
+/// Reads a colour in one of the forms `#rgb`, `#rrggbb`, `rgb(r, g, b)` or a basic colour name
+///
+pub (crate) fn parse_color(color: &str) -> Option<Color> {
+    let color = color.trim().to_lowercase();
+
+    if color.starts_with('#') {
+        // Hex colour
+        let digits = color[1..].chars().map(|chr| chr.to_digit(16)).collect::<Option<Vec<_>>>()?;
+
+        match digits.len() {
+            3 => Some(Color::Rgba((digits[0]*17) as f32/255.0, (digits[1]*17) as f32/255.0, (digits[2]*17) as f32/255.0, 1.0)),
+            6 => Some(Color::Rgba((digits[0]*16+digits[1]) as f32/255.0, (digits[2]*16+digits[3]) as f32/255.0, (digits[4]*16+digits[5]) as f32/255.0, 1.0)),
+            _ => None
+        }
+    } else if color.starts_with("rgb(") && color.ends_with(')') {
+        // Components can be numbers from 0-255 or percentages
+        let components = color[4..color.len()-1].split(',')
+            .map(|component| {
+                let component = component.trim();
+
+                if component.ends_with('%') {
+                    parse_length(component).map(|percent| percent/100.0)
+                } else {
+                    parse_length(component).map(|value| value/255.0)
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        if components.len() == 3 {
+            Some(Color::Rgba(components[0].max(0.0).min(1.0) as f32, components[1].max(0.0).min(1.0) as f32, components[2].max(0.0).min(1.0) as f32, 1.0))
+        } else {
+            None
+        }
+    } else {
+        let (r, g, b) = match color.as_str() {
+            "black"                 => (0, 0, 0),
+            "white"                 => (255, 255, 255),
+            "red"                   => (255, 0, 0),
+            "lime"                  => (0, 255, 0),
+            "green"                 => (0, 128, 0),
+            "blue"                  => (0, 0, 255),
+            "yellow"                => (255, 255, 0),
+            "cyan" | "aqua"         => (0, 255, 255),
+            "magenta" | "fuchsia"   => (255, 0, 255),
+            "gray" | "grey"         => (128, 128, 128),
+            "silver"                => (192, 192, 192),
+            "maroon"                => (128, 0, 0),
+            "olive"                 => (128, 128, 0),
+            "navy"                  => (0, 0, 128),
+            "purple"                => (128, 0, 128),
+            "teal"                  => (0, 128, 128),
+            "orange"                => (255, 165, 0),
+            "brown"                 => (165, 42, 42),
+            "pink"                  => (255, 192, 203),
+            _                       => { return None; }
+        };
+
+        Some(Color::Rgba(r as f32/255.0, g as f32/255.0, b as f32/255.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_hex_colors() {
+        assert!(parse_color("#f00") == Some(Color::Rgba(1.0, 0.0, 0.0, 1.0)));
+        assert!(parse_color("#0000FF") == Some(Color::Rgba(0.0, 0.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn parse_rgb_color() {
+        assert!(parse_color("rgb(255, 0, 100%)") == Some(Color::Rgba(1.0, 0.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn style_attribute_overrides_presentation_attributes() {
+        let mut attributes = HashMap::new();
+        attributes.insert("fill".to_string(), "red".to_string());
+        attributes.insert("style".to_string(), "fill: blue; stroke:#000; stroke-width: 4px".to_string());
+
+        let style = SvgStyle::new().for_child(&attributes);
+
+        assert!(style.fill == Some(Color::Rgba(0.0, 0.0, 1.0, 1.0)));
+        assert!(style.stroke == Some(Color::Rgba(0.0, 0.0, 0.0, 1.0)));
+        assert!(style.stroke_width == 4.0);
+    }
+
+    #[test]
+    fn fill_can_be_removed() {
+        let mut attributes = HashMap::new();
+        attributes.insert("fill".to_string(), "none".to_string());
+
+        assert!(SvgStyle::new().for_child(&attributes).fill == None);
+    }
+}
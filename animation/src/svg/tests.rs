@@ -0,0 +1,132 @@
+use super::*;
+use crate::*;
+use crate::editor::*;
+use crate::storage::*;
+
+use flo_canvas::*;
+use futures::prelude::*;
+
+use std::time::Duration;
+
+///
+/// Creates an in-memory animation for the tests
+///
+fn create_animation() -> impl EditableAnimation {
+    let in_memory_store = InMemoryStorage::new();
+    create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed())
+}
+
+///
+/// Creates an animation with a single empty layer
+///
+fn animation_with_layer() -> impl EditableAnimation {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+    ]);
+
+    anim
+}
+
+///
+/// Retrieves the elements in layer 1 at time 0
+///
+fn elements<Anim: Animation>(anim: &Anim) -> Vec<Vector> {
+    let frame = anim.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0));
+
+    frame.vector_elements().unwrap().collect()
+}
+
+#[test]
+fn import_filled_rect() {
+    let anim    = animation_with_layer();
+    let svg     = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100"><rect x="10" y="20" width="30" height="40" fill="#ff0000"/></svg>"##;
+
+    anim.perform_edits(svg_import_edits(svg, &anim, 1, Duration::from_millis(0)).unwrap());
+
+    let elements = elements(&anim);
+    assert!(elements.len() == 1);
+
+    match &elements[0] {
+        Vector::Path(path) => {
+            assert!(path.properties().brush_properties().color == Color::Rgba(1.0, 0.0, 0.0, 1.0));
+
+            // SVG coordinates are flipped so the document is the right way up
+            let points = path.path().elements().filter_map(|component| match component {
+                PathComponent::Move(point)  => Some(point.position),
+                _                           => None
+            }).collect::<Vec<_>>();
+            assert!(points == vec![(10.0, 80.0)]);
+        }
+
+        _ => assert!(false)
+    }
+}
+
+#[test]
+fn filled_and_stroked_shape_becomes_group() {
+    let anim    = animation_with_layer();
+    let svg     = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100"><circle cx="50" cy="50" r="20" fill="blue" stroke="black" stroke-width="2"/></svg>"##;
+
+    anim.perform_edits(svg_import_edits(svg, &anim, 1, Duration::from_millis(0)).unwrap());
+
+    let elements = elements(&anim);
+    assert!(elements.len() == 1);
+
+    match &elements[0] {
+        Vector::Group(group)    => assert!(group.num_elements() == 2),
+        _                       => assert!(false)
+    }
+}
+
+#[test]
+fn groups_are_imported() {
+    let anim    = animation_with_layer();
+    let svg     = r##"
+        <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 200 200">
+            <g transform="translate(10 10)" fill="green">
+                <path d="M0 0 L10 0 L10 10 Z"/>
+                <polygon points="20,20 30,20 30,30"/>
+            </g>
+            <ellipse cx="100" cy="100" rx="20" ry="10"/>
+        </svg>"##;
+
+    anim.perform_edits(svg_import_edits(svg, &anim, 1, Duration::from_millis(0)).unwrap());
+
+    // Everything is put in a single group, which contains the SVG group and the ellipse
+    let elements = elements(&anim);
+    assert!(elements.len() == 1);
+
+    let top_level = match &elements[0] {
+        Vector::Group(group)    => group.elements().cloned().collect::<Vec<_>>(),
+        _                       => vec![]
+    };
+    assert!(top_level.len() == 2);
+
+    match &top_level[0] {
+        Vector::Group(group)    => assert!(group.num_elements() == 2),
+        _                       => assert!(false)
+    }
+}
+
+#[test]
+fn keyframe_is_created_if_needed() {
+    let anim    = create_animation();
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(1)]);
+
+    let svg     = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100"><rect width="10" height="10"/></svg>"##;
+    anim.perform_edits(svg_import_edits(svg, &anim, 1, Duration::from_millis(0)).unwrap());
+
+    assert!(anim.get_layer_with_id(1).unwrap().get_key_frames().collect::<Vec<_>>() == vec![Duration::from_millis(0)]);
+    assert!(elements(&anim).len() == 1);
+}
+
+#[test]
+fn reject_documents_that_are_not_svg() {
+    let anim = animation_with_layer();
+
+    assert!(svg_import_edits("<html></html>", &anim, 1, Duration::from_millis(0)) == Err(SvgImportError::NotSvg));
+    assert!(svg_import_edits("<svg", &anim, 1, Duration::from_millis(0)).is_err());
+}
@@ -0,0 +1,167 @@
+use super::number_reader::*;
+
+use flo_curves::*;
+
+///
+/// An affine transform as used by SVG: the matrix `[a c e; b d f; 0 0 1]` stored as `[a, b, c, d, e, f]`
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SvgTransform(pub [f64; 6]);
+
+impl SvgTransform {
+    ///
+    /// The transform that leaves points unchanged
+    ///
+    pub fn identity() -> SvgTransform {
+        SvgTransform([1.0, 0.0, 0.0, 1.0, 0.0, 0.0])
+    }
+
+    ///
+    /// A transform that moves points by an offset
+    ///
+    pub fn translate(dx: f64, dy: f64) -> SvgTransform {
+        SvgTransform([1.0, 0.0, 0.0, 1.0, dx, dy])
+    }
+
+    ///
+    /// A transform that scales points about the origin
+    ///
+    pub fn scale(sx: f64, sy: f64) -> SvgTransform {
+        SvgTransform([sx, 0.0, 0.0, sy, 0.0, 0.0])
+    }
+
+    ///
+    /// A transform that rotates points about the origin by an angle in degrees
+    ///
+    pub fn rotate(degrees: f64) -> SvgTransform {
+        let radians = degrees.to_radians();
+        let (sin, cos) = radians.sin_cos();
+
+        SvgTransform([cos, sin, -sin, cos, 0.0, 0.0])
+    }
+
+    ///
+    /// A transform that skews along the X axis by an angle in degrees
+    ///
+    pub fn skew_x(degrees: f64) -> SvgTransform {
+        SvgTransform([1.0, 0.0, degrees.to_radians().tan(), 1.0, 0.0, 0.0])
+    }
+
+    ///
+    /// A transform that skews along the Y axis by an angle in degrees
+    ///
+    pub fn skew_y(degrees: f64) -> SvgTransform {
+        SvgTransform([1.0, degrees.to_radians().tan(), 0.0, 1.0, 0.0, 0.0])
+    }
+
+    ///
+    /// Returns the transform that applies `other` and then this transform
+    ///
+    pub fn then_apply_to(&self, other: &SvgTransform) -> SvgTransform {
+        let SvgTransform([a1, b1, c1, d1, e1, f1]) = *self;
+        let SvgTransform([a2, b2, c2, d2, e2, f2]) = *other;
+
+        SvgTransform([
+            a1*a2 + c1*b2,
+            b1*a2 + d1*b2,
+            a1*c2 + c1*d2,
+            b1*c2 + d1*d2,
+            a1*e2 + c1*f2 + e1,
+            b1*e2 + d1*f2 + f1
+        ])
+    }
+
+    ///
+    /// Transforms a point
+    ///
+    pub fn apply(&self, point: Coord2) -> Coord2 {
+        let SvgTransform([a, b, c, d, e, f]) = *self;
+        let Coord2(x, y) = point;
+
+        Coord2(a*x + c*y + e, b*x + d*y + f)
+    }
+
+    ///
+    /// The amount that this transform scales lengths by (on average: the square root of the change in area)
+    ///
+    pub fn length_scale(&self) -> f64 {
+        let SvgTransform([a, b, c, d, _, _]) = *self;
+
+        (a*d - b*c).abs().sqrt()
+    }
+
+    ///
+    /// Parses the value of a `transform` attribute, returning None if it's not valid
+    ///
+    pub fn parse(transform: &str) -> Option<SvgTransform> {
+        let mut reader = NumberReader::new(transform);
+        let mut result = SvgTransform::identity();
+
+        while !reader.at_end() {
+            // Each transform is written as `name(arg1 arg2 ...)`
+            let name = reader.identifier();
+            if reader.next_char() != Some('(') { return None; }
+
+            let mut args = vec![];
+            while let Some(arg) = reader.number() {
+                args.push(arg);
+            }
+            if reader.next_char() != Some(')') { return None; }
+
+            let next_transform = match (name.as_str(), args.len()) {
+                ("matrix", 6)       => SvgTransform([args[0], args[1], args[2], args[3], args[4], args[5]]),
+                ("translate", 1)    => SvgTransform::translate(args[0], 0.0),
+                ("translate", 2)    => SvgTransform::translate(args[0], args[1]),
+                ("scale", 1)        => SvgTransform::scale(args[0], args[0]),
+                ("scale", 2)        => SvgTransform::scale(args[0], args[1]),
+                ("rotate", 1)       => SvgTransform::rotate(args[0]),
+                ("rotate", 3)       => SvgTransform::translate(args[1], args[2])
+                                            .then_apply_to(&SvgTransform::rotate(args[0]))
+                                            .then_apply_to(&SvgTransform::translate(-args[1], -args[2])),
+                ("skewX", 1)        => SvgTransform::skew_x(args[0]),
+                ("skewY", 1)        => SvgTransform::skew_y(args[0]),
+                _                   => { return None; }
+            };
+
+            // Transforms in a list are applied from right to left
+            result = result.then_apply_to(&next_transform);
+        }
+
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn is_close(a: Coord2, b: Coord2) -> bool {
+        (a.0-b.0).abs() < 0.0001 && (a.1-b.1).abs() < 0.0001
+    }
+
+    #[test]
+    fn parse_translate() {
+        let transform = SvgTransform::parse("translate(10, 20)").unwrap();
+
+        assert!(is_close(transform.apply(Coord2(1.0, 2.0)), Coord2(11.0, 22.0)));
+    }
+
+    #[test]
+    fn parse_rotate_about_point() {
+        let transform = SvgTransform::parse("rotate(90 10 10)").unwrap();
+
+        assert!(is_close(transform.apply(Coord2(20.0, 10.0)), Coord2(10.0, 20.0)));
+    }
+
+    #[test]
+    fn transform_list_is_applied_right_to_left() {
+        let transform = SvgTransform::parse("translate(10 0) scale(2)").unwrap();
+
+        assert!(is_close(transform.apply(Coord2(1.0, 1.0)), Coord2(12.0, 2.0)));
+    }
+
+    #[test]
+    fn invalid_transform() {
+        assert!(SvgTransform::parse("spin(10)") == None);
+    }
+}
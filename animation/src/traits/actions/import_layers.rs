@@ -0,0 +1,212 @@
+use super::snapshot::*;
+use super::super::edit::*;
+use super::super::animation::*;
+use super::super::time_path::*;
+
+use std::time::{Duration};
+use std::collections::{HashMap};
+
+///
+/// Maps the layer IDs, element IDs and times in a set of edits being imported from one animation into another
+///
+struct ImportMapping<'a, Target: ?Sized> {
+    /// The animation that the edits are being imported into (used to assign new element IDs)
+    target: &'a Target,
+
+    /// Maps layer IDs in the source animation to layer IDs in the target animation
+    layers: HashMap<u64, u64>,
+
+    /// Maps element IDs in the source animation to element IDs in the target animation
+    elements: HashMap<ElementId, ElementId>,
+
+    /// The ratio to apply to times in the source animation
+    time_scale: f64
+}
+
+///
+/// Generates the edits that will copy layers from one animation into another
+///
+/// The layers are added after any existing layers in the target animation, with new layer and element IDs so they do
+/// not collide with the existing content. Keyframes, elements, brushes and motions are all copied across. If `retime`
+/// is true, times are scaled so that frames in the source animation line up with the frames in the target animation
+/// (otherwise, the times are copied unchanged).
+///
+/// The edits are generated from a snapshot of the source layers, so the edit history of those layers is not imported.
+///
+pub fn import_layer_edits<Source, Target>(source: &Source, target: &Target, layer_ids: &[u64], retime: bool) -> Vec<AnimationEdit>
+where   Source: Animation+?Sized,
+        Target: EditableAnimation+?Sized {
+    // New layer IDs follow on from the highest existing layer ID in the target
+    let mut next_layer_id   = target.get_layer_ids().into_iter().max().unwrap_or(0) + 1;
+    let mut layers          = HashMap::new();
+
+    for layer_id in layer_ids.iter() {
+        if !layers.contains_key(layer_id) {
+            layers.insert(*layer_id, next_layer_id);
+            next_layer_id += 1;
+        }
+    }
+
+    // Work out how to retime the layers
+    let source_frame_length = source.frame_length();
+    let target_frame_length = target.frame_length();
+    let time_scale          = if retime && source_frame_length > Duration::from_micros(0) {
+        target_frame_length.as_secs_f64() / source_frame_length.as_secs_f64()
+    } else {
+        1.0
+    };
+
+    // Snapshot the layers and map them into the target animation
+    let mut mapping = ImportMapping {
+        target:     target,
+        layers:     layers,
+        elements:   HashMap::new(),
+        time_scale: time_scale
+    };
+
+    snapshot_layer_edits(source, layer_ids).into_iter()
+        .map(|edit| mapping.map_edit(edit))
+        .collect()
+}
+
+impl<'a, Target: EditableAnimation+?Sized> ImportMapping<'a, Target> {
+    ///
+    /// Maps a layer ID from the source animation to the target animation
+    ///
+    fn layer(&self, layer_id: u64) -> u64 {
+        self.layers.get(&layer_id).cloned().unwrap_or(layer_id)
+    }
+
+    ///
+    /// Maps an element ID from the source animation to the target animation (assigning new IDs as needed)
+    ///
+    fn element(&mut self, element_id: ElementId) -> ElementId {
+        if element_id.is_unassigned() {
+            return element_id;
+        }
+
+        let target = self.target;
+        *self.elements.entry(element_id).or_insert_with(|| target.assign_element_id())
+    }
+
+    ///
+    /// Maps a time from the source animation to the target animation
+    ///
+    fn time(&self, when: Duration) -> Duration {
+        if self.time_scale == 1.0 {
+            when
+        } else {
+            Duration::from_secs_f64(when.as_secs_f64() * self.time_scale)
+        }
+    }
+
+    ///
+    /// Maps a time curve from the source animation to the target animation
+    ///
+    fn time_curve(&self, curve: TimeCurve) -> TimeCurve {
+        let time_scale  = self.time_scale as f32;
+        let map_point   = |TimePoint(x, y, millis)| TimePoint(x, y, millis * time_scale);
+
+        TimeCurve {
+            points: curve.points.into_iter()
+                .map(|control_point| TimeControlPoint {
+                    point:  map_point(control_point.point),
+                    past:   map_point(control_point.past),
+                    future: map_point(control_point.future)
+                })
+                .collect()
+        }
+    }
+
+    ///
+    /// Maps an edit from the source animation to the target animation
+    ///
+    fn map_edit(&mut self, edit: AnimationEdit) -> AnimationEdit {
+        use self::AnimationEdit::*;
+
+        match edit {
+            Layer(layer_id, layer_edit)     => Layer(self.layer(layer_id), self.map_layer_edit(layer_edit)),
+            Element(element_ids, edit)      => {
+                let element_ids = element_ids.into_iter().map(|element_id| self.element(element_id)).collect();
+                Element(element_ids, self.map_element_edit(edit))
+            }
+            Motion(motion_id, motion_edit)  => Motion(self.element(motion_id), self.map_motion_edit(motion_edit)),
+            SetSize(width, height)          => SetSize(width, height),
+            SetPreference(key, value)       => SetPreference(key, value),
+            AddNewLayer(layer_id)           => AddNewLayer(self.layer(layer_id)),
+            RemoveLayer(layer_id)           => RemoveLayer(self.layer(layer_id))
+        }
+    }
+
+    ///
+    /// Maps a layer edit from the source animation to the target animation
+    ///
+    fn map_layer_edit(&mut self, edit: LayerEdit) -> LayerEdit {
+        use self::LayerEdit::*;
+
+        match edit {
+            Paint(when, paint_edit)     => Paint(self.time(when), self.map_paint_edit(paint_edit)),
+            Path(when, path_edit)       => Path(self.time(when), self.map_path_edit(path_edit)),
+            AddKeyFrame(when)           => AddKeyFrame(self.time(when)),
+            RemoveKeyFrame(when)        => RemoveKeyFrame(self.time(when)),
+            SetName(name)               => SetName(name),
+            SetOrdering(layer_id)       => SetOrdering(self.layer(layer_id))
+        }
+    }
+
+    ///
+    /// Maps a paint edit from the source animation to the target animation
+    ///
+    fn map_paint_edit(&mut self, edit: PaintEdit) -> PaintEdit {
+        use self::PaintEdit::*;
+
+        match edit {
+            SelectBrush(element_id, defn, style)    => SelectBrush(self.element(element_id), defn, style),
+            BrushProperties(element_id, props)      => BrushProperties(self.element(element_id), props),
+            BrushStroke(element_id, points)         => BrushStroke(self.element(element_id), points),
+            CreateBrushStroke(element_id, points)   => CreateBrushStroke(self.element(element_id), points),
+            Fill(element_id, point, options)        => Fill(self.element(element_id), point, options)
+        }
+    }
+
+    ///
+    /// Maps a path edit from the source animation to the target animation
+    ///
+    fn map_path_edit(&mut self, edit: PathEdit) -> PathEdit {
+        use self::PathEdit::*;
+
+        match edit {
+            CreatePath(element_id, components)      => CreatePath(self.element(element_id), components),
+            SelectBrush(element_id, defn, style)    => SelectBrush(self.element(element_id), defn, style),
+            BrushProperties(element_id, props)      => BrushProperties(self.element(element_id), props)
+        }
+    }
+
+    ///
+    /// Maps an element edit from the source animation to the target animation
+    ///
+    fn map_element_edit(&mut self, edit: ElementEdit) -> ElementEdit {
+        use self::ElementEdit::*;
+
+        match edit {
+            AddAttachment(element_id)               => AddAttachment(self.element(element_id)),
+            RemoveAttachment(element_id)            => RemoveAttachment(self.element(element_id)),
+            SetControlPoints(points, when)          => SetControlPoints(points, self.time(when)),
+            Order(ElementOrdering::Before(before))  => Order(ElementOrdering::Before(self.element(before))),
+            Group(group_id, group_type)             => Group(self.element(group_id), group_type),
+            other                                   => other
+        }
+    }
+
+    ///
+    /// Maps a motion edit from the source animation to the target animation
+    ///
+    fn map_motion_edit(&mut self, edit: MotionEdit) -> MotionEdit {
+        use self::MotionEdit::*;
+
+        match edit {
+            SetPath(curve)  => SetPath(self.time_curve(curve)),
+            other           => other
+        }
+    }
+}
@@ -5,6 +5,10 @@
 
 mod edit_action;
 mod motion_actions;
+mod snapshot;
+mod import_layers;
 
 pub use self::edit_action::*;
 pub use self::motion_actions::*;
+pub use self::snapshot::*;
+pub use self::import_layers::*;
@@ -185,6 +185,7 @@ mod test {
     use futures::stream::{BoxStream};
     use std::sync::*;
     use std::ops::Range;
+    use std::collections::HashMap;
 
     #[test]
     fn move_static_element() {
@@ -195,16 +196,22 @@ mod test {
             fn size(&self) -> (f64, f64) { unimplemented!() }
             fn duration(&self) -> Duration { unimplemented!() }
             fn frame_length(&self) -> Duration { unimplemented!() }
+            fn preference_overrides(&self) -> HashMap<String, String> { unimplemented!() }
             fn get_layer_ids(&self) -> Vec<u64> { unimplemented!() }
             fn get_layer_with_id<'a>(&'a self, _layer_id: u64) -> Option<Arc<dyn Layer>> { unimplemented!() }
             fn get_num_edits(&self) -> usize { unimplemented!() }
+            fn get_edit_log_size(&self) -> usize { unimplemented!() }
             fn read_edit_log<'a>(&'a self, _range: Range<usize>) -> BoxStream<'a, AnimationEdit> { unimplemented!() }
+            fn get_checkpoints(&self) -> Vec<AnimationCheckpoint> { unimplemented!() }
             fn motion<'a>(&'a self) -> &'a dyn AnimationMotion { self }
         }
 
         impl EditableAnimation for TestAnimation {
             fn edit(&self) -> Publisher<Arc<Vec<AnimationEdit>>> { unimplemented!() }
             fn perform_edits(&self, _edits: Vec<AnimationEdit>) { unimplemented!() }
+            fn replace_edit_log(&self, _num_edits: usize, _new_edits: Vec<AnimationEdit>) { unimplemented!() }
+            fn set_checkpoint(&self, _name: &str) { unimplemented!() }
+            fn remove_checkpoint(&self, _name: &str) { unimplemented!() }
             fn flush_caches(&self) { unimplemented!() }
 
             fn assign_element_id(&self) -> ElementId {
@@ -256,16 +263,22 @@ mod test {
             fn size(&self) -> (f64, f64) { unimplemented!() }
             fn duration(&self) -> Duration { unimplemented!() }
             fn frame_length(&self) -> Duration { unimplemented!() }
+            fn preference_overrides(&self) -> HashMap<String, String> { unimplemented!() }
             fn get_layer_ids(&self) -> Vec<u64> { unimplemented!() }
             fn get_layer_with_id<'a>(&'a self, _layer_id: u64) -> Option<Arc<dyn Layer>> { unimplemented!() }
             fn get_num_edits(&self) -> usize { unimplemented!() }
+            fn get_edit_log_size(&self) -> usize { unimplemented!() }
             fn read_edit_log<'a>(&'a self, _range: Range<usize>) -> BoxStream<'a, AnimationEdit> { unimplemented!() }
+            fn get_checkpoints(&self) -> Vec<AnimationCheckpoint> { unimplemented!() }
             fn motion<'a>(&'a self) -> &'a dyn AnimationMotion { self }
         }
 
         impl EditableAnimation for TestAnimation {
             fn edit(&self) -> Publisher<Arc<Vec<AnimationEdit>>> { unimplemented!() }
             fn perform_edits(&self, _edits: Vec<AnimationEdit>) { unimplemented!() }
+            fn replace_edit_log(&self, _num_edits: usize, _new_edits: Vec<AnimationEdit>) { unimplemented!() }
+            fn set_checkpoint(&self, _name: &str) { unimplemented!() }
+            fn remove_checkpoint(&self, _name: &str) { unimplemented!() }
             fn flush_caches(&self) { unimplemented!() }
 
             fn assign_element_id(&self) -> ElementId {
@@ -0,0 +1,464 @@
+use super::super::edit::*;
+use super::super::frame::*;
+use super::super::layer::*;
+use super::super::motion::*;
+use super::super::vector::*;
+use super::super::animation::*;
+
+use std::u32;
+use std::sync::*;
+use std::time::{Duration};
+use std::collections::{HashSet};
+
+///
+/// Tracks the state of the animation editor while generating a snapshot
+///
+struct SnapshotState {
+    /// The edits that recreate the elements of the animation
+    edits: Vec<AnimationEdit>,
+
+    /// Edits that attach motions and transformations to elements (these are generated after all of the elements are created)
+    attachment_edits: Vec<AnimationEdit>,
+
+    /// The brush definition selected for brush strokes
+    brush_defn: Option<ElementId>,
+
+    /// The brush properties selected for brush strokes
+    brush_props: Option<ElementId>,
+
+    /// The brush definition selected for paths
+    path_brush_defn: Option<ElementId>,
+
+    /// The brush properties selected for paths
+    path_brush_props: Option<ElementId>,
+
+    /// The brush definitions and properties that have been created so far
+    brushes: HashSet<ElementId>,
+
+    /// The motions that have been created so far
+    motions: HashSet<ElementId>
+}
+
+///
+/// Generates a set of edits that will recreate the current state of an animation
+///
+/// This reconstructs the animation from its layers, keyframes and elements rather than from its edit log, so the
+/// result is generally much shorter than the original log. Element IDs, start times and ordering are preserved, so
+/// any edits made after the snapshot was taken can be applied to the result. Brush strokes are recreated from their
+/// fitted points, so replaying the snapshot produces exactly the same element data (`animations_match()` can be
+/// used to check this).
+///
+pub fn snapshot_edits<Anim: Animation+?Sized>(animation: &Anim) -> Vec<AnimationEdit> {
+    // Animation properties
+    let (width, height) = animation.size();
+    let mut edits       = vec![AnimationEdit::SetSize(width, height)];
+
+    // Preferences that are overridden by the animation (in key order so the snapshot is always the same)
+    let preferences     = animation.preference_overrides();
+    let mut keys        = preferences.keys().collect::<Vec<_>>();
+    keys.sort();
+
+    for key in keys {
+        edits.push(AnimationEdit::SetPreference(key.clone(), Some(preferences[key].clone())));
+    }
+
+    // Recreate every layer
+    edits.extend(snapshot_layer_edits(animation, &animation.get_layer_ids()));
+
+    edits
+}
+
+///
+/// Generates a set of edits that will recreate the specified layers of an animation (including any motions attached to
+/// the elements in those layers)
+///
+/// This works in the same way as `snapshot_edits()`, except the animation properties are not included. Layers that
+/// don't exist in the animation are skipped.
+///
+pub fn snapshot_layer_edits<Anim: Animation+?Sized>(animation: &Anim, layer_ids: &[u64]) -> Vec<AnimationEdit> {
+    let mut state = SnapshotState {
+        edits:              vec![],
+        attachment_edits:   vec![],
+        brush_defn:         None,
+        brush_props:        None,
+        path_brush_defn:    None,
+        path_brush_props:   None,
+        brushes:            HashSet::new(),
+        motions:            HashSet::new()
+    };
+
+    // Recreate each layer in turn
+    for layer_id in layer_ids.iter().cloned() {
+        let layer = match animation.get_layer_with_id(layer_id) {
+            Some(layer) => layer,
+            None        => { continue; }
+        };
+
+        state.edits.push(AnimationEdit::AddNewLayer(layer_id));
+        if let Some(name) = layer.name() {
+            state.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetName(name)));
+        }
+
+        // Add the keyframes, then the elements for each keyframe
+        let keyframes = layer.get_key_frames().collect::<Vec<_>>();
+        for keyframe in keyframes.iter() {
+            state.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(*keyframe)));
+        }
+
+        for (index, keyframe) in keyframes.iter().enumerate() {
+            let end_time = keyframes.get(index+1).cloned();
+            keyframe_snapshot(animation, &*layer, *keyframe, end_time, &mut state);
+        }
+    }
+
+    // Motions and transformations are attached once all of the elements exist (transformations are assigned new element IDs)
+    let mut edits = state.edits;
+    edits.extend(state.attachment_edits);
+
+    edits
+}
+
+///
+/// Returns the frame containing every element in the keyframe that starts at the specified time
+///
+fn last_frame_in_keyframe(layer: &dyn Layer, end_time: Option<Duration>) -> Arc<dyn Frame> {
+    let last_time = end_time.map(|end_time| end_time - Duration::from_micros(1)).unwrap_or_else(|| Duration::from_secs(u32::MAX as u64));
+    layer.get_frame_at_time(last_time)
+}
+
+///
+/// Generates the edits for the elements in a single keyframe
+///
+fn keyframe_snapshot<Anim: Animation+?Sized>(animation: &Anim, layer: &dyn Layer, keyframe: Duration, end_time: Option<Duration>, state: &mut SnapshotState) {
+    let layer_id        = layer.id();
+
+    // The last frame in the keyframe contains every element, in its final order
+    let last_frame      = last_frame_in_keyframe(layer, end_time);
+    let final_elements  = last_frame.vector_elements().map(|elements| elements.collect::<Vec<_>>()).unwrap_or_else(|| vec![]);
+
+    // New elements are always added to the top, so creating the elements in order preserves the ordering. Each element is
+    // created at its own start time, so elements that appear part-way through the keyframe do so at the same point.
+    for element in final_elements.iter() {
+        let when = last_frame.element_start_time(element.id()).unwrap_or(keyframe);
+        element_snapshot(animation, &*last_frame, layer_id, when, &element.original_without_transformations(), state);
+    }
+}
+
+///
+/// Generates the edits to select the brush definition or properties attached to a brush stroke that's about to be created,
+/// returning the attachment that needs to be fixed up after the stroke is created if the brush was already created
+///
+fn select_brush_attachment(frame: &dyn Frame, layer_id: u64, when: Duration, attachment_id: ElementId, attachment_type: VectorType, state: &mut SnapshotState) -> Option<(Option<ElementId>, ElementId)> {
+    let selected = match attachment_type {
+        VectorType::BrushDefinition => state.brush_defn,
+        VectorType::BrushProperties => state.brush_props,
+        _                           => { return None; }
+    };
+
+    if selected == Some(attachment_id) {
+        // Already selected
+        None
+    } else if state.brushes.contains(&attachment_id) {
+        // Can't select an existing brush again without creating a new element, so the attachment is replaced after the stroke is created
+        Some((selected, attachment_id))
+    } else {
+        // Create the brush element here
+        brush_snapshot(frame, layer_id, when, attachment_id, state);
+        None
+    }
+}
+
+///
+/// Generates the edits to create a brush definition or properties element for brush strokes
+///
+fn brush_snapshot(frame: &dyn Frame, layer_id: u64, when: Duration, element_id: ElementId, state: &mut SnapshotState) {
+    match frame.element_with_id(element_id) {
+        Some(Vector::BrushDefinition(defn)) => {
+            state.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::SelectBrush(element_id, defn.definition().clone(), defn.drawing_style()))));
+            state.brush_defn = Some(element_id);
+        }
+
+        Some(Vector::BrushProperties(props)) => {
+            state.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushProperties(element_id, props.brush_properties().clone()))));
+            state.brush_props = Some(element_id);
+        }
+
+        _ => { return; }
+    }
+
+    state.brushes.insert(element_id);
+}
+
+///
+/// Generates the edits to create a single element
+///
+fn element_snapshot<Anim: Animation+?Sized>(animation: &Anim, frame: &dyn Frame, layer_id: u64, when: Duration, element: &Vector, state: &mut SnapshotState) {
+    let element_id = element.id();
+    if element_id.is_unassigned() {
+        return;
+    }
+
+    match element {
+        Vector::BrushStroke(brush_stroke)   => {
+            // Select the brush that's attached to this element
+            let mut fix_attachments = vec![];
+            for (attachment_id, attachment_type) in frame.attached_elements(element_id) {
+                fix_attachments.extend(select_brush_attachment(frame, layer_id, when, attachment_id, attachment_type, state));
+            }
+
+            // Draw the brush stroke using its existing points
+            state.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::CreateBrushStroke(element_id, brush_stroke.points()))));
+
+            // Attach any brushes that weren't selected when the stroke was created
+            for (selected, attachment_id) in fix_attachments {
+                if let Some(selected) = selected {
+                    state.edits.push(AnimationEdit::Element(vec![element_id], ElementEdit::RemoveAttachment(selected)));
+                }
+                state.edits.push(AnimationEdit::Element(vec![element_id], ElementEdit::AddAttachment(attachment_id)));
+            }
+        }
+
+        Vector::Path(path)                  => {
+            // Paths carry their brush with them
+            let defn    = path.brush();
+            let props   = path.properties();
+
+            if state.path_brush_defn != Some(defn.id()) {
+                state.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::SelectBrush(defn.id(), defn.definition().clone(), defn.drawing_style()))));
+                state.path_brush_defn = Some(defn.id());
+            }
+
+            if state.path_brush_props != Some(props.id()) {
+                state.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::BrushProperties(props.id(), props.brush_properties().clone()))));
+                state.path_brush_props = Some(props.id());
+            }
+
+            let components = path.path().elements().collect::<Vec<_>>();
+            state.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::CreatePath(element_id, Arc::new(components)))));
+        }
+
+        Vector::Group(group)                => {
+            // Create the elements in the group, then group them together
+            let mut group_ids = vec![];
+
+            for grouped_element in group.elements() {
+                element_snapshot(animation, frame, layer_id, when, grouped_element, state);
+                group_ids.push(grouped_element.id());
+            }
+
+            state.edits.push(AnimationEdit::Element(group_ids, ElementEdit::Group(element_id, group.group_type())));
+        }
+
+        _ => { return; }
+    }
+
+    attachment_snapshot(animation, frame, element_id, state);
+}
+
+///
+/// Generates the edits to recreate the motions and transformations attached to an element
+///
+fn attachment_snapshot<Anim: Animation+?Sized>(animation: &Anim, frame: &dyn Frame, element_id: ElementId, state: &mut SnapshotState) {
+    for (attachment_id, attachment_type) in frame.attached_elements(element_id) {
+        match attachment_type {
+            VectorType::Motion          => {
+                // Create the motion the first time it's encountered
+                if !state.motions.contains(&attachment_id) {
+                    let motion = match animation.motion().get_motion(attachment_id) {
+                        Some(motion)    => motion,
+                        None            => { continue; }
+                    };
+
+                    state.attachment_edits.push(AnimationEdit::Motion(attachment_id, MotionEdit::Create));
+                    state.attachment_edits.push(AnimationEdit::Motion(attachment_id, MotionEdit::SetType(motion.motion_type())));
+
+                    if let Motion::Translate(translate) = motion {
+                        state.attachment_edits.push(AnimationEdit::Motion(attachment_id, MotionEdit::SetOrigin(translate.origin.0, translate.origin.1)));
+                        state.attachment_edits.push(AnimationEdit::Motion(attachment_id, MotionEdit::SetPath(translate.translate)));
+                    }
+
+                    state.motions.insert(attachment_id);
+                }
+
+                state.attachment_edits.push(AnimationEdit::Element(vec![element_id], ElementEdit::AddAttachment(attachment_id)));
+            }
+
+            VectorType::Transformation  => {
+                if let Some(Vector::Transformation((_, transformations))) = frame.element_with_id(attachment_id) {
+                    let element_transforms = transformations.iter()
+                        .flat_map(|transformation| element_transforms_for_transformation(transformation))
+                        .collect::<Vec<_>>();
+
+                    if element_transforms.len() > 0 {
+                        state.attachment_edits.push(AnimationEdit::Element(vec![element_id], ElementEdit::Transform(element_transforms)));
+                    }
+                }
+            }
+
+            _ => { }
+        }
+    }
+}
+
+///
+/// Converts a transformation into the element transforms that will recreate it
+///
+fn element_transforms_for_transformation(transformation: &Transformation) -> Vec<ElementTransform> {
+    use self::ElementTransform::*;
+
+    match transformation {
+        Transformation::Translate(dx, dy)               => vec![SetAnchor(0.0, 0.0), MoveTo(*dx, *dy)],
+        Transformation::FlipHoriz(x, y)                 => vec![SetAnchor(*x, *y), FlipHorizontal],
+        Transformation::FlipVert(x, y)                  => vec![SetAnchor(*x, *y), FlipVertical],
+        Transformation::Scale(xratio, yratio, (x, y))   => vec![SetAnchor(*x, *y), Scale(*xratio, *yratio)],
+        Transformation::Rotate(angle, (x, y))           => vec![SetAnchor(*x, *y), Rotate(*angle)],
+
+        Transformation::Matrix(matrix)                  => vec![Matrix(*matrix)]
+    }
+}
+
+///
+/// Returns true if two animations have exactly the same content
+///
+/// This compares the size, preferences, layers and keyframes of the animations along with the data, start time and attachments
+/// of every element. Element IDs must be the same in both animations, except for transformations, which are compared by content.
+///
+pub fn animations_match<AnimA: Animation+?Sized, AnimB: Animation+?Sized>(a: &AnimA, b: &AnimB) -> bool {
+    if a.size() != b.size() || a.preference_overrides() != b.preference_overrides() {
+        return false;
+    }
+
+    let layer_ids = a.get_layer_ids();
+    if layer_ids != b.get_layer_ids() {
+        return false;
+    }
+
+    for layer_id in layer_ids {
+        let (layer_a, layer_b) = match (a.get_layer_with_id(layer_id), b.get_layer_with_id(layer_id)) {
+            (Some(layer_a), Some(layer_b))  => (layer_a, layer_b),
+            _                               => { return false; }
+        };
+
+        if layer_a.name() != layer_b.name() {
+            return false;
+        }
+
+        let keyframes = layer_a.get_key_frames().collect::<Vec<_>>();
+        if keyframes != layer_b.get_key_frames().collect::<Vec<_>>() {
+            return false;
+        }
+
+        for index in 0..keyframes.len() {
+            let end_time = keyframes.get(index+1).cloned();
+            let frame_a  = last_frame_in_keyframe(&*layer_a, end_time);
+            let frame_b  = last_frame_in_keyframe(&*layer_b, end_time);
+
+            if !frames_match(a, &*frame_a, b, &*frame_b) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+///
+/// Returns true if two frames contain the same elements
+///
+fn frames_match<AnimA: Animation+?Sized, AnimB: Animation+?Sized>(anim_a: &AnimA, a: &dyn Frame, anim_b: &AnimB, b: &dyn Frame) -> bool {
+    let elements_a = a.vector_elements().map(|elements| elements.collect::<Vec<_>>()).unwrap_or_else(|| vec![]);
+    let elements_b = b.vector_elements().map(|elements| elements.collect::<Vec<_>>()).unwrap_or_else(|| vec![]);
+
+    if elements_a.len() != elements_b.len() {
+        return false;
+    }
+
+    elements_a.iter().zip(elements_b.iter()).all(|(element_a, element_b)| {
+        let id = element_a.id();
+
+        vectors_match(&element_a.original_without_transformations(), &element_b.original_without_transformations())
+            && a.element_start_time(id) == b.element_start_time(id)
+            && attachments_match(anim_a, a, anim_b, b, id)
+    })
+}
+
+///
+/// Returns true if the attachments for an element are the same in two frames
+///
+fn attachments_match<AnimA: Animation+?Sized, AnimB: Animation+?Sized>(anim_a: &AnimA, a: &dyn Frame, anim_b: &AnimB, b: &dyn Frame, id: ElementId) -> bool {
+    let attachments_a = a.attached_elements(id);
+    let attachments_b = b.attached_elements(id);
+
+    if attachments_a.len() != attachments_b.len() {
+        return false;
+    }
+
+    attachments_a.iter().all(|(attachment_id, attachment_type)| {
+        match attachment_type {
+            VectorType::Transformation  => {
+                // Transformations are assigned new IDs when they're recreated, so match them by content
+                let transform_a = a.element_with_id(*attachment_id);
+                attachments_b.iter()
+                    .filter(|(_, type_b)| *type_b == VectorType::Transformation)
+                    .any(|(id_b, _)| match (&transform_a, b.element_with_id(*id_b)) {
+                        (Some(transform_a), Some(transform_b))  => vectors_match(transform_a, &transform_b),
+                        _                                       => false
+                    })
+            }
+
+            VectorType::Motion          => {
+                attachments_b.contains(&(*attachment_id, *attachment_type))
+                    && anim_a.motion().get_motion(*attachment_id) == anim_b.motion().get_motion(*attachment_id)
+            }
+
+            _                           => {
+                attachments_b.contains(&(*attachment_id, *attachment_type))
+                    && match (a.element_with_id(*attachment_id), b.element_with_id(*attachment_id)) {
+                        (Some(attachment_a), Some(attachment_b))    => vectors_match(&attachment_a, &attachment_b),
+                        (None, None)                                => true,
+                        _                                           => false
+                    }
+            }
+        }
+    })
+}
+
+///
+/// Returns true if two vector elements contain exactly the same data
+///
+fn vectors_match(a: &Vector, b: &Vector) -> bool {
+    use self::Vector::*;
+
+    match (a, b) {
+        (Transformation((_, a)), Transformation((_, b)))    => a == b,
+        (Error, Error)                                      => true,
+
+        _                                                   => {
+            if a.id() != b.id() {
+                return false;
+            }
+
+            match (a, b) {
+                (Transformed(a), Transformed(b))            => vectors_match(&*a.without_transformations(), &*b.without_transformations()) && vectors_match(&*a.transformed_vector(), &*b.transformed_vector()),
+                (BrushDefinition(a), BrushDefinition(b))    => a.definition() == b.definition() && a.drawing_style() == b.drawing_style(),
+                (BrushProperties(a), BrushProperties(b))    => a.brush_properties() == b.brush_properties(),
+                (BrushStroke(a), BrushStroke(b))            => a.points() == b.points(),
+                (Motion(a), Motion(b))                      => a.motion() == b.motion(),
+
+                (Path(a), Path(b))                          => {
+                    a.path().elements().collect::<Vec<_>>() == b.path().elements().collect::<Vec<_>>()
+                        && vectors_match(&BrushDefinition((*a.brush()).clone()), &BrushDefinition((*b.brush()).clone()))
+                        && vectors_match(&BrushProperties((*a.properties()).clone()), &BrushProperties((*b.properties()).clone()))
+                }
+
+                (Group(a), Group(b))                        => {
+                    a.group_type() == b.group_type()
+                        && a.num_elements() == b.num_elements()
+                        && a.elements().zip(b.elements()).all(|(a, b)| vectors_match(a, b))
+                }
+
+                _                                           => false
+            }
+        }
+    }
+}
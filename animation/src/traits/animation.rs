@@ -1,6 +1,7 @@
 use super::edit::*;
 use super::layer::*;
 use super::animation_motion::*;
+use super::checkpoint::*;
 
 use flo_stream::*;
 
@@ -9,6 +10,7 @@ use futures::stream::{BoxStream};
 use std::time::Duration;
 use std::sync::*;
 use std::ops::Range;
+use std::collections::HashMap;
 
 ///
 /// Represents an animation
@@ -29,6 +31,11 @@ pub trait Animation : Send+Sync {
     ///
     fn frame_length(&self) -> Duration;
 
+    ///
+    /// Retrieves the preferences that are overridden by this animation (as serialized values, indexed by key)
+    ///
+    fn preference_overrides(&self) -> HashMap<String, String>;
+
     ///
     /// Retrieves the IDs of the layers in this object
     ///
@@ -44,11 +51,21 @@ pub trait Animation : Send+Sync {
     ///
     fn get_num_edits(&self) -> usize;
 
+    ///
+    /// Retrieves the size of the edit log for this animation (the total length of the serialized edits)
+    ///
+    fn get_edit_log_size(&self) -> usize;
+
     ///
     /// Reads from the edit log for this animation
     ///
     fn read_edit_log<'a>(&'a self, range: Range<usize>) -> BoxStream<'a, AnimationEdit>;
 
+    ///
+    /// Retrieves the named checkpoints in the edit log for this animation, in the order that they occur in the log
+    ///
+    fn get_checkpoints(&self) -> Vec<AnimationCheckpoint>;
+
     ///
     /// Supplies a reference which can be used to find the motions associated with this animation
     ///
@@ -81,6 +98,24 @@ pub trait EditableAnimation : Animation+Send+Sync {
     ///
     fn perform_edits(&self, edits: Vec<AnimationEdit>);
 
+    ///
+    /// Replaces the first `num_edits` edits in the edit log with a new set of edits
+    ///
+    /// This changes the history of the animation without changing the animation itself: the new edits should
+    /// produce the same animation as the ones they replace (this is used when compacting the edit log)
+    ///
+    fn replace_edit_log(&self, num_edits: usize, new_edits: Vec<AnimationEdit>);
+
+    ///
+    /// Creates a checkpoint with the specified name at the current end of the edit log (replacing any existing checkpoint with the same name)
+    ///
+    fn set_checkpoint(&self, name: &str);
+
+    ///
+    /// Removes the checkpoint with the specified name
+    ///
+    fn remove_checkpoint(&self, name: &str);
+
     ///
     /// Flushes any caches this might have (forces reload from data storage)
     ///
@@ -0,0 +1,11 @@
+///
+/// A named point in the edit log of an animation
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct AnimationCheckpoint {
+    /// The name of this checkpoint
+    pub name: String,
+
+    /// The number of edits that had been performed when this checkpoint was created
+    pub edit_index: usize
+}
@@ -21,6 +21,10 @@ pub enum AnimationEdit {
     /// Sets the canvas size for this animation
     SetSize(f64, f64),
 
+    /// Sets the value of a preference for this animation, overriding the user's preference while it's being edited
+    /// (or removes the override if the value is `None`)
+    SetPreference(String, Option<String>),
+
     /// Adds a new layer and assigns it the specified ID
     /// Has no effect if a layer with that ID already exists
     AddNewLayer(u64),
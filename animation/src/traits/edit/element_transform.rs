@@ -24,5 +24,8 @@ pub enum ElementTransform {
     Scale(f64, f64),
 
     /// Rotates the element around the anchor
-    Rotate(f64)
+    Rotate(f64),
+
+    /// Applies a transformation matrix to the element (the anchor is ignored)
+    Matrix([[f64; 3]; 3])
 }
@@ -2,6 +2,7 @@ use super::element_id::*;
 
 use super::super::path::*;
 use super::super::raw_point::*;
+use super::super::brush::*;
 use super::super::fill_option::*;
 use super::super::brush_properties::*;
 use super::super::brush_definition::*;
@@ -38,6 +39,11 @@ pub enum PaintEdit {
     /// Draws a brush stroke using the current brush and the specified set of input points
     BrushStroke(ElementId, Arc<Vec<RawPoint>>),
 
+    /// Creates a brush stroke using the current brush from a set of points that have already been fitted to curves
+    ///
+    /// Unlike `BrushStroke`, the points are used as they are, so this can recreate an existing brush stroke exactly
+    CreateBrushStroke(ElementId, Arc<Vec<BrushPoint>>),
+
     /// Creates a path by flood-filling at the specified point on the current layer. The current brush/properties are used to generate
     /// the fill path, and some other options can be set in the fill options.
     Fill(ElementId, RawPoint, Vec<FillOption>)
@@ -54,6 +60,7 @@ impl PaintEdit {
             SelectBrush(id, _, _)   => *id,
             BrushProperties(id, _)  => *id,
             BrushStroke(id, _)      => *id,
+            CreateBrushStroke(id, _)=> *id,
             Fill(id, _, _)          => *id
         }
     }
@@ -70,6 +77,7 @@ impl PaintEdit {
             SelectBrush(Unassigned, brush_def, brush_style) => SelectBrush(Assigned(assign_element_id()), brush_def, brush_style),
             BrushProperties(Unassigned, brush_props)        => BrushProperties(Assigned(assign_element_id()), brush_props),
             BrushStroke(Unassigned, points)                 => BrushStroke(Assigned(assign_element_id()), points),
+            CreateBrushStroke(Unassigned, points)           => CreateBrushStroke(Assigned(assign_element_id()), points),
 
             assigned => assigned
         }
@@ -1,5 +1,7 @@
 use super::animation::*;
+use super::super::storage::{StorageError};
 
+use std::sync::*;
 use std::path::Path;
 
 ///
@@ -12,4 +14,29 @@ pub trait FileAnimation : Send+Sync {
     /// Opens an animation from a file on disk
     ///
     fn open(&self, path: &Path) -> Self::NewAnimation;
+
+    ///
+    /// Opens an animation from a file on disk for reading only
+    ///
+    /// Unlike `open()`, this doesn't change the file in any way (for instance by marking it as being edited), and returns an
+    /// error if the file can't be read as an animation.
+    ///
+    fn open_read_only(&self, _path: &Path) -> Result<Arc<dyn Animation>, StorageError> { Err(StorageError::General) }
+
+    ///
+    /// Returns true if the animation at the specified path was not closed cleanly and may have edits that can be recovered
+    ///
+    fn needs_recovery(&self, _path: &Path) -> bool { false }
+
+    ///
+    /// Recovers any edits that were lost when the animation at the specified path was not closed cleanly
+    ///
+    /// Returns an error if the animation could not be opened (for instance, because the file no longer exists)
+    ///
+    fn recover(&self, _path: &Path) -> Result<(), StorageError> { Ok(()) }
+
+    ///
+    /// Discards any edits that could be recovered for the animation at the specified path
+    ///
+    fn discard_recovery(&self, _path: &Path) { }
 }
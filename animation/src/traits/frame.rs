@@ -35,6 +35,13 @@ pub trait Frame : Send+Sync {
     ///
     fn element_with_id(&self, id: ElementId) -> Option<Vector>;
 
+    ///
+    /// Retrieves the time when the element with the specified ID first appears in the keyframe containing this frame
+    ///
+    /// This can be later than the time of this frame, for elements that are not displayed yet
+    ///
+    fn element_start_time(&self, id: ElementId) -> Option<Duration>;
+
     ///
     /// Retrieves the IDs and types of the elements attached to the element with a particular ID
     ///
@@ -69,6 +76,11 @@ impl Frame for Arc<dyn Frame> {
     ///
     #[inline] fn element_with_id(&self, id: ElementId) -> Option<Vector> { (**self).element_with_id(id) }
 
+    ///
+    /// Retrieves the time when the element with the specified ID first appears in the keyframe containing this frame
+    ///
+    #[inline] fn element_start_time(&self, id: ElementId) -> Option<Duration> { (**self).element_start_time(id) }
+
     ///
     /// Retrieves the IDs and types of the elements attached to the element with a particular ID
     ///
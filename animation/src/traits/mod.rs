@@ -2,6 +2,7 @@ mod edit;
 mod actions;
 mod animation;
 mod animation_motion;
+mod checkpoint;
 mod file_animation;
 mod frame;
 mod layer;
@@ -23,6 +24,7 @@ pub use self::edit::*;
 pub use self::actions::*;
 pub use self::animation::*;
 pub use self::animation_motion::*;
+pub use self::checkpoint::*;
 pub use self::file_animation::*;
 pub use self::frame::*;
 pub use self::layer::*;
@@ -0,0 +1,82 @@
+use super::storage_descriptor::*;
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::{FromStr};
+
+///
+/// Describes a version of an animation that can be compared against another version
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnimationVersion {
+    /// The current input animation
+    Input,
+
+    /// The current output animation
+    Output,
+
+    /// The input animation as it was after the specified number of edits from its edit log had been applied
+    InputAtEdit(usize),
+
+    /// An animation from storage
+    Storage(StorageDescriptor)
+}
+
+impl Display for AnimationVersion {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), fmt::Error> {
+        use self::AnimationVersion::*;
+
+        match self {
+            Input                   => write!(fmt, "input"),
+            Output                  => write!(fmt, "output"),
+            InputAtEdit(num_edits)  => write!(fmt, "@{}", num_edits),
+            Storage(storage)        => write!(fmt, "{}", storage)
+        }
+    }
+}
+
+impl AnimationVersion {
+    ///
+    /// Parses a string into an animation version
+    ///
+    /// 'input' and 'output' refer to the current input and output animations, '@n' refers to the input animation after
+    /// the first n edits were performed, and anything else is treated as a catalog string.
+    ///
+    pub fn parse_version_string(val: &str) -> AnimationVersion {
+        match val.to_lowercase().as_str() {
+            "input"     => { return AnimationVersion::Input; }
+            "output"    => { return AnimationVersion::Output; }
+            _           => { }
+        }
+
+        if val.len() > 1 && val.chars().nth(0) == Some('@') {
+            if let Ok(num_edits) = usize::from_str(&val[1..]) {
+                return AnimationVersion::InputAtEdit(num_edits);
+            }
+        }
+
+        AnimationVersion::Storage(StorageDescriptor::parse_catalog_string(val))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_input() {
+        assert!(AnimationVersion::parse_version_string("input") == AnimationVersion::Input);
+        assert!(AnimationVersion::parse_version_string("Output") == AnimationVersion::Output);
+    }
+
+    #[test]
+    fn parse_edit_index() {
+        assert!(AnimationVersion::parse_version_string("@42") == AnimationVersion::InputAtEdit(42));
+    }
+
+    #[test]
+    fn parse_catalog_version() {
+        assert!(AnimationVersion::parse_version_string("#3#") == AnimationVersion::Storage(StorageDescriptor::CatalogNumber(3)));
+        assert!(AnimationVersion::parse_version_string("@name") == AnimationVersion::Storage(StorageDescriptor::CatalogName("@name".to_string())));
+    }
+}
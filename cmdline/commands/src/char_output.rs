@@ -14,6 +14,7 @@ where InputStream: Stream<Item=FloCommandOutput>+Send+Unpin {
             match output {
                 BeginCommand(_cmd)          => stream::iter(vec![]).boxed(),
                 Message(msg)                => stream::iter((msg + "\n").chars().collect::<Vec<_>>()).boxed(),
+                Data(_data)                 => stream::iter(vec![]).boxed(),
                 BeginOutput(_file)          => stream::iter(vec![]).boxed(),
                 Output(_output)             => stream::iter(vec![]).boxed(),
                 Error(err)                  => stream::iter((err + "\n").chars().collect::<Vec<_>>()).boxed(),
@@ -1,5 +1,7 @@
 use super::state::*;
+use super::error::*;
 use super::storage_descriptor::*;
+use super::animation_version::*;
 
 use flo_animation::*;
 
@@ -20,6 +22,12 @@ pub enum FloCommand {
     /// Sets the state for future commands
     SetState(CommandState),
 
+    /// Sets whether or not to keep running commands after one fails (the default is to stop)
+    ContinueOnError(bool),
+
+    /// Fails with the specified error (used to report errors in scripts)
+    ScriptError(CommandError),
+
     /// Sets the input animation
     ReadFrom(StorageDescriptor),
 
@@ -29,9 +37,36 @@ pub enum FloCommand {
     /// Moves the current 'write' animation into the 'read' position
     ReadFromWriteAnimation,
 
+    /// Uses the current 'read' animation as the 'write' animation too
+    WriteToReadAnimation,
+
     /// Lists the files in the main index
     ListAnimations,
 
+    /// Copies an animation in the catalog, optionally giving the copy a new name
+    DuplicateAnimation(StorageDescriptor, Option<String>),
+
+    /// Changes the name of an animation in the catalog
+    RenameAnimation(StorageDescriptor, String),
+
+    /// Removes an animation from the catalog
+    DeleteAnimation(StorageDescriptor),
+
+    /// Copies an animation from the catalog to the specified file
+    ExportAnimation(StorageDescriptor, String),
+
+    /// Copies an animation file into the catalog, optionally with the specified name
+    ImportAnimation(String, Option<String>),
+
+    /// Adds tags to an animation in the catalog
+    TagAnimation(StorageDescriptor, Vec<String>),
+
+    /// Removes tags from an animation in the catalog
+    UntagAnimation(StorageDescriptor, Vec<String>),
+
+    /// Lists the animations in the catalog whose name or tags contain the specified text
+    SearchCatalog(String),
+
     /// Clears the current set of edits
     ClearEdits,
 
@@ -41,6 +76,27 @@ pub enum FloCommand {
     /// Writes out a summary of the edits in the edit buffer
     SummarizeEdits,
 
+    /// Writes out the number of edits and the size of the edit log in the input animation
+    ShowEditLogSize,
+
+    /// Compacts the edit log of the input animation, keeping the specified number of the most recent edits
+    CompactEditLog(usize),
+
+    /// Writes out the named checkpoints in the input animation
+    ListCheckpoints,
+
+    /// Creates a checkpoint with the specified name at the end of the edit log of the input animation
+    AddCheckpoint(String),
+
+    /// Restores the input animation to the state it was in at the named checkpoint
+    RestoreCheckpoint(String),
+
+    /// Checks the storage of the input animation for problems
+    ValidateAnimation,
+
+    /// Checks the storage of the input animation for problems and writes edits that fix them to the output animation
+    RepairAnimation,
+
     /// Serializes the edits to the output
     SerializeEdits,
 
@@ -56,6 +112,21 @@ pub enum FloCommand {
     /// Lists all the layers in the input animation (IDs and names)
     ListLayers,
 
+    /// Copies layers (all layers if the list is empty) from another animation into the output animation, optionally retiming them to match its frame length
+    ImportLayers(StorageDescriptor, Vec<u64>, bool),
+
+    /// Adds the shapes in an SVG file to the keyframe at the specified layer and frame of the output animation
+    ImportSvg(String, u64, usize),
+
+    /// Writes the input animation to a Lottie JSON file
+    ExportLottie(String),
+
+    /// Adds the shape layers in a Lottie JSON file to the output animation as new layers
+    ImportLottie(String),
+
+    /// Compares two versions of an animation, optionally writing an SVG file highlighting the elements that changed
+    DiffAnimations(AnimationVersion, AnimationVersion, Option<String>),
+
     /// Selects the specified frame in the inout animation
     SelectFrame(u64, usize),
 
@@ -65,3 +136,57 @@ pub enum FloCommand {
     /// Writes out debugging SVG files for raycasting a particular element
     RayCastToSvg(ElementId)
 }
+
+impl FloCommand {
+    ///
+    /// Retrieves the name of this command (this is the name used for the command in scripts, where it has one)
+    ///
+    pub fn name(&self) -> &'static str {
+        use self::FloCommand::*;
+
+        match self {
+            Version                     => "version",
+            SetCatalogFolder(_)         => "catalog",
+            ReadState                   => "read-state",
+            SetState(_)                 => "set-state",
+            ContinueOnError(_)          => "on-error",
+            ScriptError(_)              => "script-error",
+            ReadFrom(_)                 => "input",
+            WriteToCatalog(_)           => "output",
+            ReadFromWriteAnimation      => "read-from-output",
+            WriteToReadAnimation        => "write-to-input",
+            ListAnimations              => "ls",
+            DuplicateAnimation(_, _)    => "duplicate",
+            RenameAnimation(_, _)       => "rename",
+            DeleteAnimation(_)          => "delete",
+            ExportAnimation(_, _)       => "export",
+            ImportAnimation(_, _)       => "import",
+            TagAnimation(_, _)          => "tag",
+            UntagAnimation(_, _)        => "untag",
+            SearchCatalog(_)            => "search",
+            ClearEdits                  => "clear-edits",
+            ReadAllEdits                => "read-all-edits",
+            SummarizeEdits              => "summarize-edits",
+            ShowEditLogSize             => "edit-log-size",
+            CompactEditLog(_)           => "compact-edits",
+            ListCheckpoints             => "ls-checkpoints",
+            AddCheckpoint(_)            => "checkpoint",
+            RestoreCheckpoint(_)        => "restore-checkpoint",
+            ValidateAnimation           => "validate",
+            RepairAnimation             => "repair",
+            SerializeEdits              => "serialize-edits",
+            DeserializeEdits(_)         => "deserialize-edits",
+            WriteAllEdits               => "write-all-edits",
+            DumpCatalogAsEdits          => "dump-all-catalog-edits",
+            ListLayers                  => "ls-layers",
+            ImportLayers(_, _, _)       => "import-layers",
+            ImportSvg(_, _, _)          => "import-svg",
+            ExportLottie(_)             => "export-lottie",
+            ImportLottie(_)             => "import-lottie",
+            DiffAnimations(_, _, _)     => "diff",
+            SelectFrame(_, _)           => "frame",
+            ListElements                => "ls-elements",
+            RayCastToSvg(_)             => "debug-raycasting"
+        }
+    }
+}
@@ -8,6 +8,7 @@ use flo_stream::*;
 use futures::prelude::*;
 use futures::stream;
 use futures::task::{Poll};
+use serde_json::json;
 
 ///
 /// Runs a series of commands provided by a stream and returns a stream of the resulting output
@@ -48,25 +49,49 @@ fn run_command<'a>(command: FloCommand, output: &'a mut Publisher<FloCommandOutp
                 let msg = format!("{} ({}) v{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_DESCRIPTION"), env!("CARGO_PKG_VERSION"));
 
                 output.publish(FloCommandOutput::Message(msg)).await;
+                output.publish(FloCommandOutput::Data(json!({ "type": "version", "name": env!("CARGO_PKG_NAME"), "description": env!("CARGO_PKG_DESCRIPTION"), "version": env!("CARGO_PKG_VERSION") }))).await;
             }
 
             FloCommand::ReadState                       => { output.publish(FloCommandOutput::State(state.clone())).await; }
             FloCommand::SetState(ref new_state)         => { *state = new_state.clone(); }
+            FloCommand::ContinueOnError(_)              => { }
+            FloCommand::ScriptError(ref err)            => { return Err(err.clone()); }
 
             FloCommand::SetCatalogFolder(ref folder)    => { set_catalog_folder(folder, output, state).await?; }
 
             FloCommand::ListAnimations                  => { list_files(output, state).await; }
+            FloCommand::DuplicateAnimation(ref from, ref name) => { duplicate_animation(output, state, from.clone(), name.clone()).await?; }
+            FloCommand::RenameAnimation(ref from, ref name) => { rename_animation(output, state, from.clone(), name.clone()).await?; }
+            FloCommand::DeleteAnimation(ref from)       => { delete_animation(output, state, from.clone()).await?; }
+            FloCommand::ExportAnimation(ref from, ref file) => { export_animation(output, state, from.clone(), file.clone()).await?; }
+            FloCommand::ImportAnimation(ref file, ref name) => { import_animation(output, state, file.clone(), name.clone()).await?; }
+            FloCommand::TagAnimation(ref from, ref tags) => { add_tags(output, state, from.clone(), tags.clone()).await?; }
+            FloCommand::UntagAnimation(ref from, ref tags) => { remove_tags(output, state, from.clone(), tags.clone()).await?; }
+            FloCommand::SearchCatalog(ref text)         => { search_catalog(output, state, text.clone()).await?; }
             FloCommand::ReadFrom(ref read_location)     => { read_from(read_location.clone(), output, state).await?; }
             FloCommand::WriteToCatalog(ref name)        => { write_to_catalog(name.clone(), output, state).await?; }
             FloCommand::ReadFromWriteAnimation          => { *state = state.read_from_write_side(); }
+            FloCommand::WriteToReadAnimation            => { *state = state.write_to_read_side(); }
             FloCommand::ReadAllEdits                    => { read_all_edits(output, state).await?; }
             FloCommand::SummarizeEdits                  => { summarize_edit_log(output, state).await?; }
+            FloCommand::ShowEditLogSize                 => { show_edit_log_size(output, state).await?; }
+            FloCommand::CompactEditLog(retain_edits)    => { compact_input_edit_log(output, state, retain_edits).await?; }
+            FloCommand::ListCheckpoints                 => { list_checkpoints(output, state).await?; }
+            FloCommand::AddCheckpoint(ref name)         => { add_checkpoint(output, state, name.clone()).await?; }
+            FloCommand::RestoreCheckpoint(ref name)     => { restore_checkpoint(output, state, name.clone()).await?; }
+            FloCommand::ValidateAnimation               => { validate_animation(output, state).await?; }
+            FloCommand::RepairAnimation                 => { repair_animation(output, state).await?; }
             FloCommand::WriteAllEdits                   => { write_all_edits(output, state).await?; }
             FloCommand::SerializeEdits                  => { serialize_edits(output, state).await?; }
             FloCommand::ClearEdits                      => { *state = state.clear_edit_buffer(); }
             FloCommand::DumpCatalogAsEdits              => { dump_catalog_as_edits(output, state).await; }
             FloCommand::DeserializeEdits(ref edits)     => { deserialize_edits(stream::iter(edits.chars()), output, state).await?; }
             FloCommand::ListLayers                      => { list_layers(output, state).await; }
+            FloCommand::ImportLayers(ref from, ref ids, retime) => { import_layers(output, state, from.clone(), ids.clone(), retime).await?; }
+            FloCommand::ImportSvg(ref file, layer, frame) => { import_svg(output, state, file.clone(), layer, frame).await?; }
+            FloCommand::ExportLottie(ref file)          => { export_lottie(output, state, file.clone()).await?; }
+            FloCommand::ImportLottie(ref file)          => { import_lottie(output, state, file.clone()).await?; }
+            FloCommand::DiffAnimations(ref before, ref after, ref svg) => { diff_animations_command(output, state, before.clone(), after.clone(), svg.clone()).await?; }
             FloCommand::SelectFrame(layer, when)        => { select_frame(output, state, layer, when).await; }
             FloCommand::ListElements                    => { list_elements(output, state).await; }
             FloCommand::RayCastToSvg(element_id)        => { raycast_to_svg(output, state, element_id).await?; }
@@ -85,17 +110,26 @@ fn run_command<'a>(command: FloCommand, output: &'a mut Publisher<FloCommandOutp
 fn run_commands<InputStream>(mut commands: InputStream, mut output: Publisher<FloCommandOutput>) -> impl Future<Output=()>+Send
 where InputStream: 'static+Stream<Item=FloCommand>+Send+Unpin {
     // Create the initial state of the command
-    let mut state = CommandState::new();
+    let mut state               = CommandState::new();
+    let mut continue_on_error   = false;
 
     async move {
         while let Some(command) = commands.next().await {
+            // The error mode applies to the commands that follow it
+            if let FloCommand::ContinueOnError(new_mode) = command {
+                continue_on_error = new_mode;
+            }
+
             // Run the next command
             match run_command(command, &mut output, &mut state).await {
                 Ok(())      => { }
                 Err(err)    => {
-                    // Stop running commands if we get an error
+                    // Stop running commands if we get an error (unless we've been asked to continue)
                     output.publish(FloCommandOutput::Failure(err)).await;
-                    break;
+
+                    if !continue_on_error {
+                        break;
+                    }
                 }
             }
         }
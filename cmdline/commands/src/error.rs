@@ -21,7 +21,34 @@ pub enum CommandError {
     NoFrameSelected,
 
     /// The element ID was not found
-    ElementNotFound(ElementId)
+    ElementNotFound(ElementId),
+
+    /// The layer with the specified ID was not found
+    LayerNotFound(u64),
+
+    /// The animation is not stored in the catalog
+    NotInCatalog(String),
+
+    /// A file could not be copied or read (the message describes the problem)
+    FileOperationFailed(String),
+
+    /// An SVG file could not be imported (the message describes the problem)
+    CannotImportSvg(String),
+
+    /// A Lottie file could not be imported (the message describes the problem)
+    CannotImportLottie(String),
+
+    /// The named checkpoint was not found
+    CheckpointNotFound(String),
+
+    /// An edit index (first value) was beyond the end of the edit log (which has the length of the second value)
+    EditIndexOutOfRange(usize, usize),
+
+    /// A line in a script could not be parsed
+    ScriptSyntaxError(usize, String),
+
+    /// The operation needs to access the storage for an animation, which is not available
+    StorageNotAvailable
 }
 
 impl Display for CommandError {
@@ -33,7 +60,16 @@ impl Display for CommandError {
             CouldNotCreateAnimation(name)   => write!(fmt, "Coult not create animation '{}'", name),
             CannotParseEdit(line, edit)     => write!(fmt, "{}: cannot parse edit '{}'", line, edit),
             NoFrameSelected                 => write!(fmt, "A frame must be selected for this operation"),
-            ElementNotFound(id)             => write!(fmt, "Element {} was not found", id.id().map(|id| id.to_string()).unwrap_or("<unassigned>".to_string()))
+            ElementNotFound(id)             => write!(fmt, "Element {} was not found", id.id().map(|id| id.to_string()).unwrap_or("<unassigned>".to_string())),
+            LayerNotFound(layer_id)         => write!(fmt, "Layer {} was not found", layer_id),
+            NotInCatalog(name)              => write!(fmt, "'{}' is not an animation in the catalog", name),
+            FileOperationFailed(msg)        => write!(fmt, "{}", msg),
+            CannotImportSvg(msg)            => write!(fmt, "{}", msg),
+            CannotImportLottie(msg)         => write!(fmt, "{}", msg),
+            CheckpointNotFound(name)        => write!(fmt, "Checkpoint '{}' was not found", name),
+            EditIndexOutOfRange(index, len) => write!(fmt, "Edit {} is beyond the end of the edit log ({} edits)", index, len),
+            ScriptSyntaxError(line, msg)    => write!(fmt, "{}: {}", line, msg),
+            StorageNotAvailable             => write!(fmt, "The storage for this animation is not available")
         }
     }
 }
@@ -9,15 +9,19 @@ mod command;
 mod error;
 mod state;
 mod storage_descriptor;
+mod animation_version;
 mod command_runner;
 mod output;
 mod char_output;
 mod subcommands;
+mod script;
 
 pub use self::command::*;
 pub use self::error::*;
 pub use self::state::*;
 pub use self::storage_descriptor::*;
+pub use self::animation_version::*;
 pub use self::command_runner::*;
 pub use self::output::*;
 pub use self::char_output::*;
+pub use self::script::*;
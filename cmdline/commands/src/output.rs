@@ -2,6 +2,8 @@ use super::state::*;
 use super::error::*;
 use super::command::*;
 
+use serde_json::json;
+
 ///
 /// Possible types of output from a FloCommand
 ///
@@ -13,6 +15,9 @@ pub enum FloCommandOutput {
     /// Display a message to the user
     Message(String),
 
+    /// Machine-readable data generated by a command (an object with a 'type' field describing what it represents)
+    Data(serde_json::Value),
+
     /// Starts writing output to a particular file
     BeginOutput(String),
 
@@ -40,3 +45,66 @@ pub enum FloCommandOutput {
     /// A command generated an error (this is generally the last item in the stream)
     Failure(CommandError)
 }
+
+impl FloCommandOutput {
+    ///
+    /// Converts this output to a JSON record, for tools that need to process the output of commands
+    ///
+    /// Every record is an object with a 'type' field. Returns None for output that has no JSON representation (such as
+    /// the command state).
+    ///
+    pub fn to_json(&self) -> Option<serde_json::Value> {
+        use self::FloCommandOutput::*;
+
+        match self {
+            BeginCommand(cmd)           => Some(json!({ "type": "begin_command", "command": cmd.name() })),
+            Message(msg)                => Some(json!({ "type": "message", "text": msg })),
+            Data(data)                  => Some(data.clone()),
+            BeginOutput(filename)       => Some(json!({ "type": "begin_output", "filename": filename })),
+            Output(output)              => Some(json!({ "type": "output", "text": output })),
+            Error(err)                  => Some(json!({ "type": "error", "text": err })),
+            State(_state)               => None,
+            FinishCommand(cmd)          => Some(json!({ "type": "finish_command", "command": cmd.name() })),
+            StartTask(task)             => Some(json!({ "type": "start_task", "name": task })),
+            TaskProgress(done, todo)    => Some(json!({ "type": "task_progress", "complete": done, "total": todo })),
+            FinishTask                  => Some(json!({ "type": "finish_task" })),
+            Failure(err)                => Some(json!({ "type": "failure", "error": err.to_string() }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn message_to_json() {
+        let json = FloCommandOutput::Message("Hello".to_string()).to_json();
+
+        assert!(json == Some(json!({ "type": "message", "text": "Hello" })));
+    }
+
+    #[test]
+    fn data_is_unchanged() {
+        let data = json!({ "type": "layer", "id": 1, "name": "Layer 1", "index": 0 });
+        let json = FloCommandOutput::Data(data.clone()).to_json();
+
+        assert!(json == Some(data));
+    }
+
+    #[test]
+    fn command_records_include_the_command_name() {
+        let begin   = FloCommandOutput::BeginCommand(FloCommand::ListLayers).to_json();
+        let finish  = FloCommandOutput::FinishCommand(FloCommand::ReadAllEdits).to_json();
+
+        assert!(begin == Some(json!({ "type": "begin_command", "command": "ls-layers" })));
+        assert!(finish == Some(json!({ "type": "finish_command", "command": "read-all-edits" })));
+    }
+
+    #[test]
+    fn state_has_no_json() {
+        let json = FloCommandOutput::State(CommandState::new()).to_json();
+
+        assert!(json.is_none());
+    }
+}
@@ -0,0 +1,613 @@
+use super::error::*;
+use super::command::*;
+use super::storage_descriptor::*;
+use super::animation_version::*;
+
+use flo_animation::*;
+
+use futures::prelude::*;
+use futures::stream;
+
+use std::str::{FromStr};
+use std::collections::{HashMap};
+
+/// The largest number of arguments that need to be tried to find out if a word is the name of a command
+const MAX_FIXED_ARGUMENTS: usize = 3;
+
+///
+/// Parses a script made up of one command per line into a series of `FloCommand`s
+///
+/// Each line contains a command name followed by its arguments, which are separated by whitespace. Arguments containing
+/// spaces can be surrounded by double quotes. Lines starting with '#' are comments. Variables can be set with
+/// `set <name> <value>` and are substituted into arguments written as `$name` or `${name}`: `$$` stands for a literal `$`.
+/// The `input`, `input-file`, `output` and `frame` commands also set the variables `$input`, `$output` and `$frame`.
+///
+pub struct ScriptParser {
+    /// The number of the line that was last parsed
+    line_number: usize,
+
+    /// The variables that have been set in the script so far
+    variables: HashMap<String, String>,
+
+    /// The animation that the `$input` variable refers to (so a file path isn't read as a catalog name when it's passed back in)
+    input: Option<StorageDescriptor>
+}
+
+impl ScriptParser {
+    ///
+    /// Creates a new script parser with no variables set
+    ///
+    pub fn new() -> ScriptParser {
+        ScriptParser {
+            line_number:    0,
+            variables:      HashMap::new(),
+            input:          None
+        }
+    }
+
+    ///
+    /// Sets the value of a variable that can be used by the script
+    ///
+    pub fn set_variable(&mut self, name: &str, value: &str) {
+        self.variables.insert(name.to_string(), value.to_string());
+    }
+
+    ///
+    /// Sets the animation that the `$input` variable refers to
+    ///
+    pub fn set_input(&mut self, input: StorageDescriptor) {
+        self.set_variable("input", &input.to_string());
+        self.input = Some(input);
+    }
+
+    ///
+    /// Retrieves the value of a variable set for this script
+    ///
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(|value| value.as_str())
+    }
+
+    ///
+    /// Parses the next line of a script, returning the commands that it represents
+    ///
+    pub fn parse_line(&mut self, line: &str) -> Result<Vec<FloCommand>, CommandError> {
+        self.line_number += 1;
+
+        // Split the line into words and substitute the variables
+        let words = self.split_words(line)?;
+        let words = words.into_iter()
+            .map(|word| self.substitute_variables(&word))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if words.len() == 0 {
+            return Ok(vec![]);
+        }
+
+        let command     = words[0].to_lowercase();
+        let arguments   = &words[1..];
+
+        match self.parse_command(&command, arguments) {
+            Some(result)    => result,
+            None            => {
+                let num_arguments = arguments.len();
+
+                if Self::is_command(&command) {
+                    Err(self.syntax_error(format!("'{}' does not take {} argument{}", command, num_arguments, if num_arguments == 1 { "" } else { "s" })))
+                } else {
+                    Err(self.syntax_error(format!("'{}' is not a known command", command)))
+                }
+            }
+        }
+    }
+
+    ///
+    /// Parses a command with a list of arguments, returning `None` if there's no command with this name that takes this many arguments
+    ///
+    fn parse_command(&mut self, command: &str, arguments: &[String]) -> Option<Result<Vec<FloCommand>, CommandError>> {
+        let result = match (command, arguments.len()) {
+            ("version", 0)                  => Ok(vec![FloCommand::Version]),
+            ("catalog", 1)                  => Ok(vec![FloCommand::SetCatalogFolder(arguments[0].clone())]),
+
+            ("set", 2)                      => self.set(&arguments[0], &arguments[1]),
+            ("input", 1)                    => self.set("input", &arguments[0]),
+            ("output", 1)                   => self.set("output", &arguments[0]),
+            ("frame", 1)                    => self.set("frame", &arguments[0]),
+            ("input-file", 1)               => {
+                let input = StorageDescriptor::File(arguments[0].clone());
+                self.set_input(input.clone());
+                Ok(vec![FloCommand::ReadFrom(input)])
+            }
+            ("read-from-output", 0)         => Ok(vec![FloCommand::ReadFromWriteAnimation]),
+            ("write-to-input", 0)           => Ok(vec![FloCommand::WriteToReadAnimation]),
+
+            ("on-error", 1)                 => {
+                match arguments[0].to_lowercase().as_str() {
+                    "stop"      => Ok(vec![FloCommand::ContinueOnError(false)]),
+                    "continue"  => Ok(vec![FloCommand::ContinueOnError(true)]),
+                    _           => Err(self.syntax_error(format!("'{}' should be 'stop' or 'continue'", arguments[0])))
+                }
+            }
+
+            ("ls", 0)                       => Ok(vec![FloCommand::ListAnimations]),
+            ("ls-layers", 0)                => Ok(vec![FloCommand::ListLayers]),
+            ("ls-elements", 0)              => Ok(vec![FloCommand::ListElements]),
+
+            ("duplicate", 1)                => Ok(vec![FloCommand::DuplicateAnimation(StorageDescriptor::parse_catalog_string(&arguments[0]), None)]),
+            ("duplicate", 2)                => Ok(vec![FloCommand::DuplicateAnimation(StorageDescriptor::parse_catalog_string(&arguments[0]), Some(arguments[1].clone()))]),
+            ("rename", 2)                   => Ok(vec![FloCommand::RenameAnimation(StorageDescriptor::parse_catalog_string(&arguments[0]), arguments[1].clone())]),
+            ("delete", 1)                   => Ok(vec![FloCommand::DeleteAnimation(StorageDescriptor::parse_catalog_string(&arguments[0]))]),
+            ("export", 2)                   => Ok(vec![FloCommand::ExportAnimation(StorageDescriptor::parse_catalog_string(&arguments[0]), arguments[1].clone())]),
+            ("import", 1)                   => Ok(vec![FloCommand::ImportAnimation(arguments[0].clone(), None)]),
+            ("import", 2)                   => Ok(vec![FloCommand::ImportAnimation(arguments[0].clone(), Some(arguments[1].clone()))]),
+            ("tag", n) if n >= 2            => Ok(vec![FloCommand::TagAnimation(StorageDescriptor::parse_catalog_string(&arguments[0]), arguments[1..].to_vec())]),
+            ("untag", n) if n >= 2          => Ok(vec![FloCommand::UntagAnimation(StorageDescriptor::parse_catalog_string(&arguments[0]), arguments[1..].to_vec())]),
+            ("search", 1)                   => Ok(vec![FloCommand::SearchCatalog(arguments[0].clone())]),
+
+            ("read-all-edits", 0)           => Ok(vec![FloCommand::ReadAllEdits]),
+            ("write-all-edits", 0)          => Ok(vec![FloCommand::WriteAllEdits]),
+            ("clear-edits", 0)              => Ok(vec![FloCommand::ClearEdits]),
+            ("summarize-edits", 0)          => Ok(vec![FloCommand::SummarizeEdits]),
+            ("serialize-edits", 0)          => Ok(vec![FloCommand::SerializeEdits]),
+            ("dump-all-catalog-edits", 0)   => Ok(vec![FloCommand::DumpCatalogAsEdits]),
+
+            ("edit-log-size", 0)            => Ok(vec![FloCommand::ShowEditLogSize]),
+            ("compact-edits", 0)            => Ok(vec![FloCommand::CompactEditLog(0)]),
+            ("compact-edits", 1)            => self.parse_number(&arguments[0]).map(|retained| vec![FloCommand::CompactEditLog(retained)]),
+
+            ("ls-checkpoints", 0)           => Ok(vec![FloCommand::ListCheckpoints]),
+            ("checkpoint", 1)               => Ok(vec![FloCommand::AddCheckpoint(arguments[0].clone())]),
+            ("restore-checkpoint", 1)       => Ok(vec![FloCommand::RestoreCheckpoint(arguments[0].clone())]),
+
+            ("validate", 0)                 => Ok(vec![FloCommand::ValidateAnimation]),
+            ("repair", 0)                   => Ok(vec![FloCommand::RepairAnimation]),
+            ("import-layers", n) if n >= 1  => self.import_layers(arguments),
+            ("import-svg", 3)               => self.import_svg(arguments),
+            ("export-lottie", 1)            => Ok(vec![FloCommand::ExportLottie(arguments[0].clone())]),
+            ("import-lottie", 1)            => Ok(vec![FloCommand::ImportLottie(arguments[0].clone())]),
+            ("diff", 2)                     => Ok(vec![FloCommand::DiffAnimations(AnimationVersion::parse_version_string(&arguments[0]), AnimationVersion::parse_version_string(&arguments[1]), None)]),
+            ("diff", 3)                     => Ok(vec![FloCommand::DiffAnimations(AnimationVersion::parse_version_string(&arguments[0]), AnimationVersion::parse_version_string(&arguments[1]), Some(arguments[2].clone()))]),
+
+            ("debug-raycasting", 1)         => self.parse_number(&arguments[0]).map(|element_id| vec![FloCommand::RayCastToSvg(ElementId::Assigned(element_id))]),
+
+            _                               => { return None; }
+        };
+
+        Some(result)
+    }
+
+    ///
+    /// Returns true if the specified string is the name of a script command
+    ///
+    /// This tries parsing the command with every number of arguments that a command can take, using a parser of its own so
+    /// that any variables it sets are thrown away.
+    ///
+    fn is_command(command: &str) -> bool {
+        let arguments = vec!["0".to_string(); MAX_FIXED_ARGUMENTS];
+
+        (0..=MAX_FIXED_ARGUMENTS).any(|num_arguments| ScriptParser::new().parse_command(command, &arguments[0..num_arguments]).is_some())
+    }
+
+    ///
+    /// Generates a syntax error for the current line
+    ///
+    fn syntax_error(&self, message: String) -> CommandError {
+        CommandError::ScriptSyntaxError(self.line_number, message)
+    }
+
+    ///
+    /// Parses a number argument
+    ///
+    fn parse_number<Number: FromStr>(&self, argument: &str) -> Result<Number, CommandError> {
+        Number::from_str(argument).map_err(|_| self.syntax_error(format!("'{}' is not a valid number", argument)))
+    }
+
+    ///
+    /// Parses an argument that refers to an animation
+    ///
+    /// This is usually a reference to the catalog, but the value of the `$input` variable refers to the same animation as the
+    /// input, which might be a file.
+    ///
+    fn parse_storage(&self, argument: &str) -> StorageDescriptor {
+        match &self.input {
+            Some(input) if input.to_string() == argument    => input.clone(),
+            _                                               => StorageDescriptor::parse_catalog_string(argument)
+        }
+    }
+
+    ///
+    /// Parses the arguments to the import-layers command: `import-layers <source> [--retime] [<layer_id> ...]`
+    ///
+    fn import_layers(&self, arguments: &[String]) -> Result<Vec<FloCommand>, CommandError> {
+        let source          = self.parse_storage(&arguments[0]);
+        let mut retime      = false;
+        let mut layer_ids   = vec![];
+
+        for argument in arguments[1..].iter() {
+            if argument == "--retime" {
+                retime = true;
+            } else {
+                layer_ids.push(self.parse_number(argument)?);
+            }
+        }
+
+        Ok(vec![FloCommand::ImportLayers(source, layer_ids, retime)])
+    }
+
+    ///
+    /// Parses the arguments to the import-svg command: `import-svg <file> <layer_id> <frame>`
+    ///
+    fn import_svg(&self, arguments: &[String]) -> Result<Vec<FloCommand>, CommandError> {
+        Ok(vec![FloCommand::ImportSvg(arguments[0].clone(), self.parse_number(&arguments[1])?, self.parse_number(&arguments[2])?)])
+    }
+
+    ///
+    /// Sets a variable, returning the commands needed to update the command state if it's one of the special variables
+    ///
+    fn set(&mut self, name: &str, value: &str) -> Result<Vec<FloCommand>, CommandError> {
+        let commands = match name {
+            "input"     => {
+                let input   = self.parse_storage(value);
+                self.input  = Some(input.clone());
+                vec![FloCommand::ReadFrom(input)]
+            }
+            "output"    => vec![FloCommand::WriteToCatalog(value.to_string())],
+            "frame"     => {
+                // Frames are specified as <layer>:<frame>
+                let (layer_id, frame_number) = match value.find(':') {
+                    Some(sep_pos)   => (self.parse_number(&value[0..sep_pos])?, self.parse_number(&value[sep_pos+1..])?),
+                    None            => { return Err(self.syntax_error(format!("'{}' is not a valid frame: frames should be specified as <layer_id>:<frame_number>", value))); }
+                };
+
+                vec![FloCommand::SelectFrame(layer_id, frame_number)]
+            }
+            _           => vec![]
+        };
+
+        self.set_variable(name, value);
+
+        Ok(commands)
+    }
+
+    ///
+    /// Splits a line into words
+    ///
+    fn split_words(&self, line: &str) -> Result<Vec<String>, CommandError> {
+        let line = line.trim();
+
+        // Lines starting with '#' are comments
+        if line.starts_with('#') {
+            return Ok(vec![]);
+        }
+
+        let mut words       = vec![];
+        let mut word        = String::new();
+        let mut in_word     = false;
+        let mut in_quotes   = false;
+        let mut chars       = line.chars();
+
+        while let Some(chr) = chars.next() {
+            match (chr, in_quotes) {
+                ('"', false)    => { in_quotes = true; in_word = true; }
+                ('"', true)     => { in_quotes = false; }
+                ('\\', true)    => { if let Some(quoted) = chars.next() { word.push(quoted); } }
+
+                (chr, false) if chr.is_whitespace() => {
+                    if in_word {
+                        words.push(word);
+                        word    = String::new();
+                        in_word = false;
+                    }
+                }
+
+                (chr, _)        => { word.push(chr); in_word = true; }
+            }
+        }
+
+        if in_quotes {
+            return Err(self.syntax_error("Missing closing '\"'".to_string()));
+        }
+
+        if in_word {
+            words.push(word);
+        }
+
+        Ok(words)
+    }
+
+    ///
+    /// Replaces the variables in a word with their values
+    ///
+    fn substitute_variables(&self, word: &str) -> Result<String, CommandError> {
+        let mut result  = String::new();
+        let mut chars   = word.chars().peekable();
+
+        while let Some(chr) = chars.next() {
+            if chr != '$' {
+                result.push(chr);
+                continue;
+            }
+
+            // '$$' is a literal '$'
+            if chars.peek() == Some(&'$') {
+                chars.next();
+                result.push('$');
+                continue;
+            }
+
+            // Read the variable name, which is either surrounded by '{}' or made up of alphanumeric characters
+            let mut name = String::new();
+
+            if chars.peek() == Some(&'{') {
+                chars.next();
+                loop {
+                    match chars.next() {
+                        Some('}')   => { break; }
+                        Some(chr)   => { name.push(chr); }
+                        None        => { return Err(self.syntax_error(format!("Missing closing '}}' for variable '{}'", name))); }
+                    }
+                }
+            } else {
+                while let Some(chr) = chars.peek() {
+                    if chr.is_alphanumeric() || *chr == '_' || *chr == '-' {
+                        name.push(*chr);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+            }
+
+            // '$' on its own is left as it is
+            if name.len() == 0 {
+                result.push('$');
+                continue;
+            }
+
+            match self.variables.get(&name) {
+                Some(value) => { result.push_str(value); }
+                None        => { return Err(self.syntax_error(format!("Variable '{}' has not been set", name))); }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+///
+/// Converts a stream of script lines into a stream of commands
+///
+/// Lines that can't be parsed generate a `FloCommand::ScriptError` command, which will fail with the syntax error
+/// when it's run.
+///
+pub fn flo_script_commands<LineStream>(lines: LineStream, parser: ScriptParser) -> impl Stream<Item=FloCommand>+Send+Unpin
+where LineStream: 'static+Stream<Item=String>+Send+Unpin {
+    let mut parser = parser;
+
+    lines
+        .map(move |line| {
+            let commands = match parser.parse_line(&line) {
+                Ok(commands)    => commands,
+                Err(err)        => vec![FloCommand::ScriptError(err)]
+            };
+
+            stream::iter(commands)
+        })
+        .flatten()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_simple_command() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("ls-layers").unwrap().as_slice() {
+            [FloCommand::ListLayers]    => { }
+            _                           => assert!(false)
+        }
+    }
+
+    #[test]
+    fn parse_repair() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("write-to-input").unwrap().as_slice() {
+            [FloCommand::WriteToReadAnimation]  => { }
+            _                                   => assert!(false)
+        }
+
+        match parser.parse_line("repair").unwrap().as_slice() {
+            [FloCommand::RepairAnimation]       => { }
+            _                                   => assert!(false)
+        }
+    }
+
+    #[test]
+    fn parse_import_layers() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("import-layers #2# --retime 1 3").unwrap().as_slice() {
+            [FloCommand::ImportLayers(StorageDescriptor::CatalogNumber(2), layer_ids, true)] => { assert!(layer_ids == &vec![1, 3]); }
+            _                                                                                => assert!(false)
+        }
+    }
+
+    #[test]
+    fn parse_import_svg() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("import-svg logo.svg 1 12").unwrap().as_slice() {
+            [FloCommand::ImportSvg(file, 1, 12)]    => { assert!(file == "logo.svg"); }
+            _                                       => assert!(false)
+        }
+    }
+
+    #[test]
+    fn parse_lottie_commands() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("export-lottie anim.json").unwrap().as_slice() {
+            [FloCommand::ExportLottie(file)]    => { assert!(file == "anim.json"); }
+            _                                   => assert!(false)
+        }
+
+        match parser.parse_line("import-lottie anim.json").unwrap().as_slice() {
+            [FloCommand::ImportLottie(file)]    => { assert!(file == "anim.json"); }
+            _                                   => assert!(false)
+        }
+    }
+
+    #[test]
+    fn parse_diff() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("diff @10 input changes.svg").unwrap().as_slice() {
+            [FloCommand::DiffAnimations(AnimationVersion::InputAtEdit(10), AnimationVersion::Input, Some(svg))] => { assert!(svg == "changes.svg"); }
+            _                                                                                                  => assert!(false)
+        }
+    }
+
+    #[test]
+    fn parse_catalog_commands() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("duplicate #1# \"Copy of one\"").unwrap().as_slice() {
+            [FloCommand::DuplicateAnimation(StorageDescriptor::CatalogNumber(1), Some(name))]   => { assert!(name == "Copy of one"); }
+            _                                                                                   => assert!(false)
+        }
+
+        match parser.parse_line("tag #1# walk cycle").unwrap().as_slice() {
+            [FloCommand::TagAnimation(StorageDescriptor::CatalogNumber(1), tags)]               => { assert!(tags == &vec!["walk".to_string(), "cycle".to_string()]); }
+            _                                                                                   => assert!(false)
+        }
+
+        match parser.parse_line("search walk").unwrap().as_slice() {
+            [FloCommand::SearchCatalog(text)]                                                   => { assert!(text == "walk"); }
+            _                                                                                   => assert!(false)
+        }
+
+        assert!(parser.parse_line("tag #1#").is_err());
+    }
+
+    #[test]
+    fn parse_comment() {
+        let mut parser = ScriptParser::new();
+
+        assert!(parser.parse_line("# ls-layers").unwrap().len() == 0);
+        assert!(parser.parse_line("   ").unwrap().len() == 0);
+    }
+
+    #[test]
+    fn parse_quoted_argument() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("checkpoint \"Before fix\"").unwrap().as_slice() {
+            [FloCommand::AddCheckpoint(name)]   => assert!(name == "Before fix"),
+            _                                   => assert!(false)
+        }
+    }
+
+    #[test]
+    fn substitute_variable() {
+        let mut parser = ScriptParser::new();
+
+        parser.parse_line("set name fixed").unwrap();
+
+        match parser.parse_line("checkpoint \"${name} version\"").unwrap().as_slice() {
+            [FloCommand::AddCheckpoint(name)]   => assert!(name == "fixed version"),
+            _                                   => assert!(false)
+        }
+    }
+
+    #[test]
+    fn escaped_dollar_is_not_a_variable() {
+        let mut parser = ScriptParser::new();
+
+        parser.parse_line("set name fixed").unwrap();
+
+        match parser.parse_line("checkpoint \"$$name costs $$5 ($name)\"").unwrap().as_slice() {
+            [FloCommand::AddCheckpoint(name)]   => assert!(name == "$name costs $5 (fixed)"),
+            _                                   => assert!(false)
+        }
+    }
+
+    #[test]
+    fn set_input_variable() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("input #3#").unwrap().as_slice() {
+            [FloCommand::ReadFrom(StorageDescriptor::CatalogNumber(3))] => { }
+            _                                                           => assert!(false)
+        }
+
+        assert!(parser.variable("input") == Some("#3#"));
+    }
+
+    #[test]
+    fn input_file_is_not_a_catalog_name() {
+        let mut parser = ScriptParser::new();
+
+        parser.set_input(StorageDescriptor::File("/tmp/animation.flo".to_string()));
+
+        match parser.parse_line("input $input").unwrap().as_slice() {
+            [FloCommand::ReadFrom(StorageDescriptor::File(path))]   => { assert!(path == "/tmp/animation.flo"); }
+            _                                                       => assert!(false)
+        }
+    }
+
+    #[test]
+    fn set_frame_variable() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("set frame 2:10").unwrap().as_slice() {
+            [FloCommand::SelectFrame(2, 10)]    => { }
+            _                                   => assert!(false)
+        }
+
+        assert!(parser.variable("frame") == Some("2:10"));
+    }
+
+    #[test]
+    fn unknown_variable_is_an_error() {
+        let mut parser = ScriptParser::new();
+
+        assert!(parser.parse_line("checkpoint $missing").err() == Some(CommandError::ScriptSyntaxError(1, "Variable 'missing' has not been set".to_string())));
+    }
+
+    #[test]
+    fn unknown_command_is_an_error() {
+        let mut parser = ScriptParser::new();
+
+        parser.parse_line("ls").unwrap();
+        assert!(parser.parse_line("not-a-command").err() == Some(CommandError::ScriptSyntaxError(2, "'not-a-command' is not a known command".to_string())));
+    }
+
+    #[test]
+    fn wrong_number_of_arguments_is_an_error() {
+        let mut parser = ScriptParser::new();
+
+        assert!(parser.parse_line("ls-layers 1").is_err());
+        assert!(parser.parse_line("diff @1").err() == Some(CommandError::ScriptSyntaxError(2, "'diff' does not take 1 argument".to_string())));
+        assert!(parser.parse_line("tag #1#").err() == Some(CommandError::ScriptSyntaxError(3, "'tag' does not take 1 argument".to_string())));
+    }
+
+    #[test]
+    fn checking_for_a_command_does_not_set_variables() {
+        let mut parser = ScriptParser::new();
+
+        assert!(parser.parse_line("set").is_err());
+        assert!(parser.variable("0") == None);
+    }
+
+    #[test]
+    fn parse_on_error() {
+        let mut parser = ScriptParser::new();
+
+        match parser.parse_line("on-error continue").unwrap().as_slice() {
+            [FloCommand::ContinueOnError(true)] => { }
+            _                                   => assert!(false)
+        }
+    }
+}
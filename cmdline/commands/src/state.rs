@@ -7,6 +7,7 @@ use flo_ui_files::*;
 use flo_ui_files::sqlite::*;
 
 use futures::prelude::*;
+use futures::stream::{BoxStream};
 
 use std::sync::*;
 
@@ -22,11 +23,16 @@ pub const DEFAULT_USER_FOLDER: &str = "default";
 #[derive(Clone)]
 pub struct CommandState(Arc<StateValue>);
 
+///
+/// Function that connects a stream of commands to the storage for an animation
+///
+pub type StorageConnection = Arc<dyn Send+Sync+Fn(BoxStream<'static, Vec<StorageCommand>>) -> BoxStream<'static, Vec<StorageResponse>>>;
+
 ///
 /// How an animation is stored within the command state
 ///
 #[derive(Clone)]
-struct AnimationState(StorageDescriptor, Arc<dyn Animation>, Arc<dyn EditableAnimation>);
+struct AnimationState(StorageDescriptor, Arc<dyn Animation>, Arc<dyn EditableAnimation>, Option<StorageConnection>);
 
 ///
 /// The internal value of a command state
@@ -58,12 +64,14 @@ impl CommandState {
         let file_manager        = Arc::new(file_manager);
 
         // Create the input and output animation in memory
-        let input_animation     = InMemoryStorage::new();
-        let input_animation     = Arc::new(create_animation_editor(move |commands| input_animation.get_responses(commands).boxed()));
-        let output_animation    = InMemoryStorage::new();
-        let output_animation    = Arc::new(create_animation_editor(move |commands| output_animation.get_responses(commands).boxed()));
-        let input_animation     = AnimationState(StorageDescriptor::InMemory, input_animation.clone(), input_animation);
-        let output_animation    = AnimationState(StorageDescriptor::InMemory, output_animation.clone(), output_animation);
+        let input_storage       = InMemoryStorage::new();
+        let input_storage       = Arc::new(move |commands: BoxStream<'static, Vec<StorageCommand>>| input_storage.get_responses(commands).boxed()) as StorageConnection;
+        let input_animation     = { let input_storage = Arc::clone(&input_storage); Arc::new(create_animation_editor(move |commands| (*input_storage)(commands))) };
+        let output_storage      = InMemoryStorage::new();
+        let output_storage      = Arc::new(move |commands: BoxStream<'static, Vec<StorageCommand>>| output_storage.get_responses(commands).boxed()) as StorageConnection;
+        let output_animation    = { let output_storage = Arc::clone(&output_storage); Arc::new(create_animation_editor(move |commands| (*output_storage)(commands))) };
+        let input_animation     = AnimationState(StorageDescriptor::InMemory, input_animation.clone(), input_animation, Some(input_storage));
+        let output_animation    = AnimationState(StorageDescriptor::InMemory, output_animation.clone(), output_animation, Some(output_storage));
 
         // Generate the initial command state
         CommandState(Arc::new(StateValue {
@@ -89,6 +97,20 @@ impl CommandState {
         Arc::clone(&self.0.input_animation.1)
     }
 
+    ///
+    /// Retrieves the current input animation for this state as an animation that can be edited
+    ///
+    pub fn input_editable_animation(&self) -> Arc<dyn EditableAnimation> {
+        Arc::clone(&self.0.input_animation.2)
+    }
+
+    ///
+    /// Retrieves a function that can be used to send storage commands directly to the input animation, if it's available
+    ///
+    pub fn input_storage(&self) -> Option<StorageConnection> {
+        self.0.input_animation.3.clone()
+    }
+
     ///
     /// Retrieves the current output animation for this state
     ///
@@ -96,6 +118,13 @@ impl CommandState {
         Arc::clone(&self.0.output_animation.2)
     }
 
+    ///
+    /// Retrieves the current output animation for this state as an animation that can be read from
+    ///
+    pub fn output_readable_animation(&self) -> Arc<dyn Animation> {
+        Arc::clone(&self.0.output_animation.1)
+    }
+
     ///
     /// Retrieves the edit buffer set in this state
     ///
@@ -134,14 +163,16 @@ impl CommandState {
     ///
     /// Updates the output animation for this state
     ///
-    pub fn set_output_animation<Anim: 'static+EditableAnimation>(&self, description: StorageDescriptor, animation: Arc<Anim>) -> CommandState {
+    /// The storage connection is used by commands that need to send requests directly to the storage for the animation
+    ///
+    pub fn set_output_animation<Anim: 'static+EditableAnimation>(&self, description: StorageDescriptor, animation: Arc<Anim>, storage: Option<StorageConnection>) -> CommandState {
         CommandState(Arc::new(StateValue {
             file_manager:       self.0.file_manager.clone(),
             input_animation:    self.0.input_animation.clone(),
             edit_buffer:        self.0.edit_buffer.clone(),
             frame:              self.0.frame.clone(),
 
-            output_animation:   AnimationState(description, animation.clone(), animation.clone()),
+            output_animation:   AnimationState(description, animation.clone(), animation.clone(), storage),
         }))
     }
 
@@ -149,8 +180,10 @@ impl CommandState {
     /// Returns this state modified to have a new input file loaded from the specified storage descriptor (None if the file cannot be loaded)
     ///
     pub fn load_input_file(&self, input: StorageDescriptor) -> Option<CommandState> {
-        // Ask the descriptor to open the animation it's referencing
-        let new_input = input.open_animation(&self.file_manager())?;
+        // Ask the descriptor to open the storage it's referencing, and create an animation from it
+        let storage     = input.open_storage(&self.file_manager())?;
+        let storage     = Arc::new(move |commands: BoxStream<'static, Vec<StorageCommand>>| storage.get_responses(commands).boxed()) as StorageConnection;
+        let new_input   = { let storage = Arc::clone(&storage); Arc::new(create_animation_editor(move |commands| (*storage)(commands))) };
 
         // Return a state with the new animation as the input
         Some(CommandState(Arc::new(StateValue {
@@ -159,7 +192,7 @@ impl CommandState {
             edit_buffer:        self.0.edit_buffer.clone(),
             frame:              self.0.frame.clone(),
 
-            input_animation:    AnimationState(input, new_input.clone(), new_input.clone()),
+            input_animation:    AnimationState(input, new_input.clone(), new_input.clone(), Some(storage)),
         })))
     }
 
@@ -177,6 +210,20 @@ impl CommandState {
         }))
     }
 
+    ///
+    /// Makes the current 'read' animation the target for writing as well
+    ///
+    pub fn write_to_read_side(&self) -> CommandState {
+        CommandState(Arc::new(StateValue {
+            file_manager:       self.0.file_manager.clone(),
+            input_animation:    self.0.input_animation.clone(),
+            edit_buffer:        self.0.edit_buffer.clone(),
+            frame:              self.0.frame.clone(),
+
+            output_animation:   self.0.input_animation.clone()
+        }))
+    }
+
     ///
     /// Sets the edit buffer to a new value
     ///
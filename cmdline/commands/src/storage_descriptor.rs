@@ -46,35 +46,73 @@ impl StorageDescriptor {
     /// Opens the animation that this storage descriptor references, using the specified file manager
     ///
     pub fn open_animation(&self, file_manager: &Arc<dyn FileManager>) -> Option<Arc<impl EditableAnimation>> {
-        let storage = match self {
+        let storage     = self.open_storage(file_manager);
+        let animation   = storage.map(|storage| Arc::new(create_animation_editor(move |commands| storage.get_responses(commands).boxed())));
+        animation
+    }
+
+    ///
+    /// Opens the animation that this storage descriptor references without changing the file (eg, to copy data from it)
+    ///
+    /// Unlike `open_animation()`, this won't upgrade the file format or store any caches in the file. Files that are in an
+    /// older format can't be read this way.
+    ///
+    pub fn open_animation_read_only(&self, file_manager: &Arc<dyn FileManager>) -> Option<Arc<impl Animation>> {
+        let storage     = match self {
+            StorageDescriptor::InMemory                 => SqliteAnimationStorage::new_in_memory().ok(),
+            StorageDescriptor::File(filename)           => SqliteAnimationStorage::open_file_read_only(&PathBuf::from(filename)).ok(),
+
+            StorageDescriptor::CatalogNumber(_)         |
+            StorageDescriptor::CatalogName(_)           => {
+                let path = self.catalog_path(file_manager)?;
+                SqliteAnimationStorage::open_file_read_only(path.as_path()).ok()
+            }
+        };
+        let animation   = storage.map(|storage| Arc::new(create_animation_editor(move |commands| storage.get_responses(commands).boxed())));
+        animation
+    }
+
+    ///
+    /// Opens the storage for the animation that this storage descriptor references
+    ///
+    pub fn open_storage(&self, file_manager: &Arc<dyn FileManager>) -> Option<SqliteAnimationStorage> {
+        match self {
             StorageDescriptor::InMemory                 => SqliteAnimationStorage::new_in_memory().ok(),
             StorageDescriptor::File(filename)           => SqliteAnimationStorage::open_file(&PathBuf::from(filename)).ok(),
 
+            StorageDescriptor::CatalogNumber(_)         |
+            StorageDescriptor::CatalogName(_)           => {
+                let path = self.catalog_path(file_manager)?;
+                SqliteAnimationStorage::open_file(path.as_path()).ok()
+            }
+        }
+    }
+
+    ///
+    /// Returns the path of the catalog file that this storage descriptor references (None if it does not refer to an item in the catalog)
+    ///
+    pub fn catalog_path(&self, file_manager: &Arc<dyn FileManager>) -> Option<PathBuf> {
+        match self {
+            StorageDescriptor::InMemory                 |
+            StorageDescriptor::File(_)                  => None,
+
             StorageDescriptor::CatalogNumber(num)       => {
                 let all_files       = file_manager.get_all_files();
-                let requested_file  = all_files.into_iter().nth(*num)?;
-                SqliteAnimationStorage::open_file(requested_file.as_path()).ok()
+                all_files.into_iter().nth(*num)
             }
 
             StorageDescriptor::CatalogName(filename)    => {
                 let all_files       = file_manager.get_all_files();
                 let filename        = filename.to_lowercase();
-                let mut result      = None;
 
-                for file in all_files {
-                    let full_name = file_manager.display_name_for_path(file.as_path()).unwrap_or("<untitled>".to_string());
-                    if full_name.to_lowercase() == filename {
-                        result = SqliteAnimationStorage::open_file(file.as_path()).ok();
-                        break;
-                    }
-                }
-
-                result
+                all_files.into_iter()
+                    .filter(|file| {
+                        let full_name = file_manager.display_name_for_path(file.as_path()).unwrap_or("<untitled>".to_string());
+                        full_name.to_lowercase() == filename
+                    })
+                    .nth(0)
             }
-        };
-
-        let animation   = storage.map(|storage| Arc::new(create_animation_editor(move |commands| storage.get_responses(commands).boxed())));
-        animation
+        }
     }
 
     ///
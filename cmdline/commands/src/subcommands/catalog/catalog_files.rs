@@ -0,0 +1,145 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+use crate::storage_descriptor::*;
+
+use flo_stream::*;
+use flo_ui_files::*;
+
+use futures::prelude::*;
+use serde_json::json;
+
+use std::fs;
+use std::sync::*;
+use std::path::{Path, PathBuf};
+
+///
+/// Finds the path of an animation in the catalog
+///
+pub (crate) fn find_in_catalog(file_manager: &Arc<dyn FileManager>, animation: &StorageDescriptor) -> Result<PathBuf, CommandError> {
+    animation.catalog_path(file_manager)
+        .ok_or_else(|| CommandError::NotInCatalog(format!("{}", animation)))
+}
+
+///
+/// Copies a file, converting any error into a command error
+///
+fn copy_file(from: &Path, to: &Path) -> Result<(), CommandError> {
+    fs::copy(from, to)
+        .map(|_| ())
+        .map_err(|err| CommandError::FileOperationFailed(format!("Could not copy '{}' to '{}': {}", from.display(), to.display(), err)))
+}
+
+///
+/// Creates a copy of an animation in the catalog
+///
+pub fn duplicate_animation<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, animation: StorageDescriptor, new_name: Option<String>) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let file_manager    = state.file_manager();
+        let source_path     = find_in_catalog(&file_manager, &animation)?;
+        let source_name     = file_manager.display_name_for_path(source_path.as_path()).unwrap_or("<untitled>".to_string());
+        let new_name        = new_name.unwrap_or_else(|| format!("{} (copy)", source_name));
+
+        // Copy the file to a new path in the catalog
+        let new_path        = file_manager.create_new_path();
+        if let Err(err) = copy_file(source_path.as_path(), new_path.as_path()) {
+            file_manager.delete_path(new_path.as_path());
+            return Err(err);
+        }
+
+        // The copy has the same tags as the original
+        file_manager.set_display_name_for_path(new_path.as_path(), new_name.clone());
+        file_manager.set_tags_for_path(new_path.as_path(), file_manager.tags_for_path(source_path.as_path()));
+
+        output.publish(FloCommandOutput::Message(format!("Copied '{}' to '{}'", source_name, new_name))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "copied_file", "from": source_name, "name": new_name }))).await;
+
+        Ok(())
+    }
+}
+
+///
+/// Changes the name of an animation in the catalog
+///
+pub fn rename_animation<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, animation: StorageDescriptor, new_name: String) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let file_manager    = state.file_manager();
+        let path            = find_in_catalog(&file_manager, &animation)?;
+        let old_name        = file_manager.display_name_for_path(path.as_path()).unwrap_or("<untitled>".to_string());
+
+        file_manager.set_display_name_for_path(path.as_path(), new_name.clone());
+
+        output.publish(FloCommandOutput::Message(format!("Renamed '{}' to '{}'", old_name, new_name))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "renamed_file", "from": old_name, "name": new_name }))).await;
+
+        Ok(())
+    }
+}
+
+///
+/// Removes an animation from the catalog (and deletes its file)
+///
+pub fn delete_animation<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, animation: StorageDescriptor) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let file_manager    = state.file_manager();
+        let path            = find_in_catalog(&file_manager, &animation)?;
+        let name            = file_manager.display_name_for_path(path.as_path()).unwrap_or("<untitled>".to_string());
+
+        file_manager.delete_path(path.as_path());
+
+        output.publish(FloCommandOutput::Message(format!("Deleted '{}'", name))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "deleted_file", "name": name }))).await;
+
+        Ok(())
+    }
+}
+
+///
+/// Copies an animation from the catalog to a file outside of it
+///
+pub fn export_animation<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, animation: StorageDescriptor, target_file: String) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let file_manager    = state.file_manager();
+        let path            = find_in_catalog(&file_manager, &animation)?;
+        let name            = file_manager.display_name_for_path(path.as_path()).unwrap_or("<untitled>".to_string());
+
+        copy_file(path.as_path(), &PathBuf::from(&target_file))?;
+
+        output.publish(FloCommandOutput::Message(format!("Exported '{}' to '{}'", name, target_file))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "exported_file", "name": name, "file": target_file }))).await;
+
+        Ok(())
+    }
+}
+
+///
+/// Copies an animation file into the catalog
+///
+/// If no name is supplied, the animation is named after the file it was imported from
+///
+pub fn import_animation<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, source_file: String, name: Option<String>) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let file_manager    = state.file_manager();
+        let source_path     = PathBuf::from(&source_file);
+
+        if !source_path.is_file() {
+            return Err(CommandError::CouldNotOpenAnimation(source_file));
+        }
+
+        let name            = name.unwrap_or_else(|| source_path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_else(|| "Imported".to_string()));
+
+        // Copy the file into the catalog
+        let new_path        = file_manager.create_new_path();
+        if let Err(err) = copy_file(source_path.as_path(), new_path.as_path()) {
+            file_manager.delete_path(new_path.as_path());
+            return Err(err);
+        }
+
+        file_manager.set_display_name_for_path(new_path.as_path(), name.clone());
+
+        output.publish(FloCommandOutput::Message(format!("Imported '{}' as '{}'", source_file, name))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "imported_file", "file": source_file, "name": name }))).await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use super::catalog_files::*;
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+use crate::storage_descriptor::*;
+
+use flo_stream::*;
+
+use futures::prelude::*;
+use serde_json::json;
+
+///
+/// Adds tags to an animation in the catalog
+///
+pub fn add_tags<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, animation: StorageDescriptor, tags: Vec<String>) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let file_manager    = state.file_manager();
+        let path            = find_in_catalog(&file_manager, &animation)?;
+        let name            = file_manager.display_name_for_path(path.as_path()).unwrap_or("<untitled>".to_string());
+
+        let mut new_tags    = file_manager.tags_for_path(path.as_path());
+        for tag in tags {
+            if !new_tags.contains(&tag) {
+                new_tags.push(tag);
+            }
+        }
+
+        file_manager.set_tags_for_path(path.as_path(), new_tags);
+        let new_tags        = file_manager.tags_for_path(path.as_path());
+
+        output.publish(FloCommandOutput::Message(format!("'{}' is tagged: {}", name, new_tags.join(", ")))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "file_tags", "name": name, "tags": new_tags }))).await;
+
+        Ok(())
+    }
+}
+
+///
+/// Removes tags from an animation in the catalog
+///
+pub fn remove_tags<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, animation: StorageDescriptor, tags: Vec<String>) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let file_manager    = state.file_manager();
+        let path            = find_in_catalog(&file_manager, &animation)?;
+        let name            = file_manager.display_name_for_path(path.as_path()).unwrap_or("<untitled>".to_string());
+
+        let new_tags        = file_manager.tags_for_path(path.as_path())
+            .into_iter()
+            .filter(|tag| !tags.contains(tag))
+            .collect::<Vec<_>>();
+
+        file_manager.set_tags_for_path(path.as_path(), new_tags.clone());
+
+        output.publish(FloCommandOutput::Message(format!("'{}' is tagged: {}", name, new_tags.join(", ")))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "file_tags", "name": name, "tags": new_tags }))).await;
+
+        Ok(())
+    }
+}
+
+///
+/// Lists the animations in the catalog whose name or tags contain the specified text
+///
+pub fn search_catalog<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, text: String) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        use self::FloCommandOutput::*;
+
+        let file_manager    = state.file_manager();
+        let all_files       = file_manager.get_all_files();
+        let matches         = file_manager.search(&text);
+
+        output.publish(Message(format!("{} files matching '{}'", matches.len(), text))).await;
+        output.publish(Message("".to_string())).await;
+
+        for file in matches {
+            // Files are identified by their index in the full catalog so they can be used with the other commands
+            let index       = all_files.iter().position(|path| path == &file).unwrap_or(0);
+            let full_name   = file_manager.display_name_for_path(file.as_path()).unwrap_or("<untitled>".to_string());
+            let tags        = file_manager.tags_for_path(file.as_path());
+
+            if tags.is_empty() {
+                output.publish(Output(format!("#{}#: {}\n", index, full_name))).await;
+            } else {
+                output.publish(Output(format!("#{}#: {} [{}]\n", index, full_name, tags.join(", ")))).await;
+            }
+            output.publish(Data(json!({ "type": "file", "index": index, "name": full_name, "tags": tags }))).await;
+        }
+
+        Ok(())
+    }
+}
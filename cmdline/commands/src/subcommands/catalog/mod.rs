@@ -0,0 +1,5 @@
+mod catalog_files;
+mod catalog_tags;
+
+pub use self::catalog_files::*;
+pub use self::catalog_tags::*;
@@ -0,0 +1,445 @@
+use crate::subcommands::list::*;
+
+use flo_animation::*;
+
+use std::sync::*;
+use std::time::{Duration};
+use std::collections::{BTreeMap, BTreeSet};
+
+///
+/// Describes how an item differs between two versions of an animation
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiffKind {
+    /// The item only exists in the 'after' animation
+    Added,
+
+    /// The item only exists in the 'before' animation
+    Removed,
+
+    /// The item exists in both animations but is different
+    Changed
+}
+
+///
+/// A difference between the layers of two animations
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerDiff {
+    pub kind:           DiffKind,
+    pub layer_id:       u64,
+    pub name_before:    Option<String>,
+    pub name_after:     Option<String>
+}
+
+///
+/// A keyframe that was added or removed between two animations
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyFrameDiff {
+    pub kind:       DiffKind,
+    pub layer_id:   u64,
+    pub when:       Duration
+}
+
+///
+/// Summarises the geometry of an element
+///
+#[derive(Clone, Debug)]
+pub struct ElementGeometry {
+    /// The type of the element (as reported by ls-elements)
+    pub element_type: &'static str,
+
+    /// The time when the element first appears
+    pub start_time: Duration,
+
+    /// The bounding box of the element
+    pub bounds: Rect,
+
+    /// The number of points in the element (brush points for brush strokes, path components for paths)
+    pub num_points: usize,
+
+    /// The paths that make up this element
+    pub paths: Vec<Path>
+}
+
+///
+/// An element that differs between two animations
+///
+#[derive(Clone, Debug)]
+pub struct ElementDiff {
+    pub kind:       DiffKind,
+    pub layer_id:   u64,
+    pub keyframe:   Duration,
+    pub element_id: ElementId,
+    pub before:     Option<ElementGeometry>,
+    pub after:      Option<ElementGeometry>
+}
+
+///
+/// The differences between two animations
+///
+#[derive(Clone, Debug)]
+pub struct AnimationDiff {
+    pub layers:     Vec<LayerDiff>,
+    pub keyframes:  Vec<KeyFrameDiff>,
+    pub elements:   Vec<ElementDiff>
+}
+
+///
+/// An element read from an animation, along with its serialized form (used to determine if it has changed)
+///
+struct ElementSnapshot {
+    serialized: String,
+    geometry:   ElementGeometry
+}
+
+impl ElementGeometry {
+    ///
+    /// Reads the geometry of an element in a frame
+    ///
+    fn from_element(frame: &dyn Frame, element: &Vector, start_time: Duration) -> ElementGeometry {
+        let properties  = frame.apply_properties_for_element(element, Arc::new(VectorProperties::default()));
+        let paths       = element.to_path(&*properties, PathConversion::Fastest).unwrap_or_else(|| vec![]);
+        let bounds      = paths.iter().fold(Rect::empty(), |bounds, path| bounds.union(Rect::from(path)));
+
+        ElementGeometry {
+            element_type:   vector_type(element),
+            start_time:     start_time,
+            bounds:         bounds,
+            num_points:     Self::count_points(element),
+            paths:          paths
+        }
+    }
+
+    ///
+    /// Counts the number of points that define an element
+    ///
+    fn count_points(element: &Vector) -> usize {
+        match element {
+            Vector::BrushStroke(brush_stroke)   => brush_stroke.points().len(),
+            Vector::Path(path)                  => path.path().elements().count(),
+            Vector::Group(group)                => group.elements().map(|element| Self::count_points(element)).sum(),
+            Vector::Transformed(transformed)    => Self::count_points(&*transformed.without_transformations()),
+            _                                   => 0
+        }
+    }
+
+    ///
+    /// Describes how the geometry of an element changed
+    ///
+    pub fn describe_change(&self, after: &ElementGeometry) -> String {
+        let mut changes = vec![];
+
+        let (before_center, after_center) = (self.bounds.center(), after.bounds.center());
+        let (dx, dy) = (after_center.0 - before_center.0, after_center.1 - before_center.1);
+        if dx != 0.0 || dy != 0.0 {
+            changes.push(format!("moved by ({:.1}, {:.1})", dx, dy));
+        }
+
+        let (dw, dh) = (after.bounds.width() - self.bounds.width(), after.bounds.height() - self.bounds.height());
+        if dw != 0.0 || dh != 0.0 {
+            changes.push(format!("resized by ({:.1}, {:.1})", dw, dh));
+        }
+
+        if self.num_points != after.num_points {
+            changes.push(format!("{} -> {} points", self.num_points, after.num_points));
+        }
+
+        if self.start_time != after.start_time {
+            changes.push(format!("appears at {}ms -> {}ms", self.start_time.as_millis(), after.start_time.as_millis()));
+        }
+
+        if self.element_type != after.element_type {
+            changes.push(format!("{} -> {}", self.element_type, after.element_type));
+        }
+
+        if changes.len() == 0 {
+            changes.push("properties changed".to_string());
+        }
+
+        changes.join(", ")
+    }
+}
+
+///
+/// Reads the elements from every keyframe of an animation, indexed by layer, keyframe and element ID
+///
+/// Elements can appear part-way through a keyframe, so each element is read from the frame at its own start time
+///
+fn read_elements(animation: &dyn Animation) -> BTreeMap<(u64, Duration, i64), ElementSnapshot> {
+    let mut elements = BTreeMap::new();
+
+    for layer_id in animation.get_layer_ids() {
+        let layer       = match animation.get_layer_with_id(layer_id) { Some(layer) => layer, None => { continue; } };
+        let keyframes   = layer.get_key_frames().collect::<Vec<_>>();
+
+        for (index, keyframe) in keyframes.iter().enumerate() {
+            // The last frame in the keyframe contains every element that's in it
+            let last_time   = keyframes.get(index+1).map(|next_keyframe| *next_keyframe - Duration::from_micros(1)).unwrap_or_else(|| Duration::from_secs(u32::MAX as u64));
+            let last_frame  = layer.get_frame_at_time(last_time);
+            let vectors     = last_frame.vector_elements().map(|elements| elements.collect::<Vec<_>>()).unwrap_or_else(|| vec![]);
+            let mut frames  = BTreeMap::new();
+
+            for element in vectors {
+                // Unassigned elements can't be matched up between animations
+                let element_id      = match element.id().id() { Some(id) => id, None => { continue; } };
+
+                // Read the element from the frame where it first appears
+                let start_time      = last_frame.element_start_time(element.id()).unwrap_or(*keyframe);
+                let frame           = frames.entry(start_time).or_insert_with(|| layer.get_frame_at_time(start_time));
+                let element         = frame.element_with_id(element.id()).unwrap_or(element);
+
+                let mut serialized  = String::new();
+                element.serialize(&mut serialized);
+
+                elements.insert((layer_id, *keyframe, element_id), ElementSnapshot {
+                    serialized: serialized,
+                    geometry:   ElementGeometry::from_element(&**frame, &element, start_time)
+                });
+            }
+        }
+    }
+
+    elements
+}
+
+///
+/// Reads the keyframes of the layers in an animation
+///
+fn read_keyframes(animation: &dyn Animation) -> BTreeSet<(u64, Duration)> {
+    animation.get_layer_ids().into_iter()
+        .flat_map(|layer_id| animation.get_layer_with_id(layer_id))
+        .flat_map(|layer| {
+            let layer_id = layer.id();
+            layer.get_key_frames().map(move |keyframe| (layer_id, keyframe)).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+///
+/// Finds the differences between two animations
+///
+/// Layers and elements are matched up by ID, so this works best on two versions of the same animation (such as
+/// two different points in its edit log). Elements are compared keyframe by keyframe: an element that moved to a
+/// different keyframe is reported as removed from one and added to the other.
+///
+pub fn diff_animations(before: &dyn Animation, after: &dyn Animation) -> AnimationDiff {
+    // Compare the layers
+    let layers_before   = before.get_layer_ids().into_iter().flat_map(|layer_id| before.get_layer_with_id(layer_id)).map(|layer| (layer.id(), layer.name())).collect::<BTreeMap<_, _>>();
+    let layers_after    = after.get_layer_ids().into_iter().flat_map(|layer_id| after.get_layer_with_id(layer_id)).map(|layer| (layer.id(), layer.name())).collect::<BTreeMap<_, _>>();
+    let all_layer_ids   = layers_before.keys().chain(layers_after.keys()).cloned().collect::<BTreeSet<_>>();
+
+    let layers          = all_layer_ids.into_iter()
+        .flat_map(|layer_id| {
+            let kind = match (layers_before.get(&layer_id), layers_after.get(&layer_id)) {
+                (None, Some(_))                                 => DiffKind::Added,
+                (Some(_), None)                                 => DiffKind::Removed,
+                (Some(before), Some(after)) if before != after  => DiffKind::Changed,
+                _                                               => { return None; }
+            };
+
+            Some(LayerDiff {
+                kind:           kind,
+                layer_id:       layer_id,
+                name_before:    layers_before.get(&layer_id).cloned().flatten(),
+                name_after:     layers_after.get(&layer_id).cloned().flatten()
+            })
+        })
+        .collect();
+
+    // Compare the keyframes
+    let keyframes_before    = read_keyframes(before);
+    let keyframes_after     = read_keyframes(after);
+    let keyframes           = keyframes_before.union(&keyframes_after)
+        .flat_map(|(layer_id, when)| {
+            let kind = match (keyframes_before.contains(&(*layer_id, *when)), keyframes_after.contains(&(*layer_id, *when))) {
+                (false, true)   => DiffKind::Added,
+                (true, false)   => DiffKind::Removed,
+                _               => { return None; }
+            };
+
+            Some(KeyFrameDiff { kind: kind, layer_id: *layer_id, when: *when })
+        })
+        .collect();
+
+    // Compare the elements
+    let mut elements_before = read_elements(before);
+    let mut elements_after  = read_elements(after);
+    let all_elements        = elements_before.keys().chain(elements_after.keys()).cloned().collect::<BTreeSet<_>>();
+
+    let elements            = all_elements.into_iter()
+        .flat_map(|(layer_id, keyframe, element_id)| {
+            let before  = elements_before.remove(&(layer_id, keyframe, element_id));
+            let after   = elements_after.remove(&(layer_id, keyframe, element_id));
+
+            let kind    = match (&before, &after) {
+                (None, Some(_))                                                         => DiffKind::Added,
+                (Some(_), None)                                                         => DiffKind::Removed,
+                (Some(before), Some(after)) if before.serialized != after.serialized    => DiffKind::Changed,
+                (Some(before), Some(after)) if before.geometry.start_time != after.geometry.start_time
+                                                                                        => DiffKind::Changed,
+                _                                                                       => { return None; }
+            };
+
+            Some(ElementDiff {
+                kind:       kind,
+                layer_id:   layer_id,
+                keyframe:   keyframe,
+                element_id: ElementId::Assigned(element_id),
+                before:     before.map(|before| before.geometry),
+                after:      after.map(|after| after.geometry)
+            })
+        })
+        .collect();
+
+    AnimationDiff {
+        layers:     layers,
+        keyframes:  keyframes,
+        elements:   elements
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flo_animation::storage::*;
+
+    use futures::prelude::*;
+
+    fn create_animation() -> impl EditableAnimation {
+        let in_memory_store = InMemoryStorage::new();
+        create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed())
+    }
+
+    fn add_path(anim: &impl EditableAnimation, element_id: i64, offset: f64) {
+        add_path_at(anim, element_id, offset, Duration::from_millis(0));
+    }
+
+    fn add_path_at(anim: &impl EditableAnimation, element_id: i64, offset: f64, when: Duration) {
+        use self::LayerEdit::*;
+
+        anim.perform_edits(vec![
+            AnimationEdit::Layer(1, Path(when,
+                PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, Path(when,
+                PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(1, Path(when,
+                PathEdit::CreatePath(ElementId::Assigned(element_id), Arc::new(vec![
+                    PathComponent::Move(PathPoint::new(10.0+offset, 20.0)),
+                    PathComponent::Line(PathPoint::new(20.0+offset, 30.0)),
+                    PathComponent::Line(PathPoint::new(30.0+offset, 20.0)),
+                    PathComponent::Close
+                ]))))
+        ]);
+    }
+
+    fn create_base_animation() -> impl EditableAnimation {
+        let anim = create_animation();
+
+        anim.perform_edits(vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+        ]);
+        add_path(&anim, 100, 0.0);
+
+        anim
+    }
+
+    #[test]
+    fn identical_animations_have_no_differences() {
+        let before  = create_base_animation();
+        let after   = create_base_animation();
+        let diff    = diff_animations(&before, &after);
+
+        assert!(diff.layers.len() == 0);
+        assert!(diff.keyframes.len() == 0);
+        assert!(diff.elements.len() == 0);
+    }
+
+    #[test]
+    fn find_added_layer_and_keyframe() {
+        let before  = create_base_animation();
+        let after   = create_base_animation();
+
+        after.perform_edits(vec![
+            AnimationEdit::AddNewLayer(2),
+            AnimationEdit::Layer(2, LayerEdit::SetName("New layer".to_string())),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(100)))
+        ]);
+
+        let diff    = diff_animations(&before, &after);
+
+        assert!(diff.layers == vec![LayerDiff { kind: DiffKind::Added, layer_id: 2, name_before: None, name_after: Some("New layer".to_string()) }]);
+        assert!(diff.keyframes.iter().any(|keyframe| keyframe == &KeyFrameDiff { kind: DiffKind::Added, layer_id: 1, when: Duration::from_millis(100) }));
+    }
+
+    #[test]
+    fn find_changed_element() {
+        let before  = create_animation();
+        let after   = create_animation();
+
+        for anim in vec![&before, &after] {
+            anim.perform_edits(vec![
+                AnimationEdit::AddNewLayer(1),
+                AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+            ]);
+        }
+
+        add_path(&before, 100, 0.0);
+        add_path(&after, 100, 5.0);
+
+        let diff    = diff_animations(&before, &after);
+        let changed = diff.elements.iter().filter(|element| element.element_id == ElementId::Assigned(100)).collect::<Vec<_>>();
+
+        assert!(changed.len() == 1);
+        assert!(changed[0].kind == DiffKind::Changed);
+        assert!(changed[0].before.as_ref().unwrap().describe_change(changed[0].after.as_ref().unwrap()).contains("moved by (5.0, 0.0)"));
+    }
+
+    #[test]
+    fn find_added_and_removed_elements() {
+        let before  = create_base_animation();
+        let after   = create_base_animation();
+
+        after.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Delete)]);
+        add_path(&after, 200, 0.0);
+
+        let diff    = diff_animations(&before, &after);
+
+        assert!(diff.elements.iter().any(|element| element.element_id == ElementId::Assigned(100) && element.kind == DiffKind::Removed));
+        assert!(diff.elements.iter().any(|element| element.element_id == ElementId::Assigned(200) && element.kind == DiffKind::Added));
+    }
+
+    #[test]
+    fn find_element_added_part_way_through_keyframe() {
+        let before  = create_base_animation();
+        let after   = create_base_animation();
+
+        add_path_at(&after, 200, 0.0, Duration::from_millis(500));
+
+        let diff    = diff_animations(&before, &after);
+        let added   = diff.elements.iter().filter(|element| element.element_id == ElementId::Assigned(200)).collect::<Vec<_>>();
+
+        assert!(added.len() == 1);
+        assert!(added[0].kind == DiffKind::Added);
+        assert!(added[0].keyframe == Duration::from_millis(0));
+        assert!(added[0].after.as_ref().unwrap().start_time == Duration::from_millis(500));
+    }
+
+    #[test]
+    fn find_element_with_changed_start_time() {
+        let before  = create_base_animation();
+        let after   = create_base_animation();
+
+        add_path_at(&before, 200, 0.0, Duration::from_millis(500));
+        add_path_at(&after, 200, 0.0, Duration::from_millis(300));
+
+        let diff    = diff_animations(&before, &after);
+        let changed = diff.elements.iter().filter(|element| element.element_id == ElementId::Assigned(200)).collect::<Vec<_>>();
+
+        assert!(changed.len() == 1);
+        assert!(changed[0].kind == DiffKind::Changed);
+        assert!(changed[0].before.as_ref().unwrap().describe_change(changed[0].after.as_ref().unwrap()).contains("appears at 500ms -> 300ms"));
+    }
+}
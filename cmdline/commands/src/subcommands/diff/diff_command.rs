@@ -0,0 +1,158 @@
+use super::diff_svg::*;
+use super::animation_diff::*;
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+use crate::animation_version::*;
+
+use flo_stream::*;
+use flo_animation::*;
+use flo_animation::storage::*;
+
+use futures::prelude::*;
+use serde_json::json;
+
+use std::sync::*;
+use std::time::{Duration};
+
+///
+/// Opens a version of an animation so it can be compared with another version
+///
+async fn open_version(state: &CommandState, version: &AnimationVersion) -> Result<Arc<dyn Animation>, CommandError> {
+    match version {
+        AnimationVersion::Input                 => Ok(state.input_animation()),
+        AnimationVersion::Output                => Ok(state.output_readable_animation()),
+
+        AnimationVersion::Storage(storage)      => {
+            let animation: Arc<dyn Animation> = storage.open_animation(&state.file_manager())
+                .ok_or_else(|| CommandError::CouldNotOpenAnimation(format!("{}", storage)))?;
+            Ok(animation)
+        }
+
+        AnimationVersion::InputAtEdit(num_edits) => {
+            // Read the edits up to the requested point from the input animation
+            let input           = state.input_animation();
+            let total_edits     = input.get_num_edits();
+            if *num_edits > total_edits {
+                return Err(CommandError::EditIndexOutOfRange(*num_edits, total_edits));
+            }
+
+            let edits           = input.read_edit_log(0..*num_edits).collect::<Vec<_>>().await;
+
+            // Replay them into an animation in memory
+            let in_memory_store = InMemoryStorage::new();
+            let animation       = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+            animation.perform_edits(edits);
+
+            // Reading the number of edits waits for the edits to be stored
+            animation.get_num_edits();
+
+            let animation: Arc<dyn Animation> = Arc::new(animation);
+            Ok(animation)
+        }
+    }
+}
+
+///
+/// Returns the name of a kind of difference
+///
+fn kind_name(kind: DiffKind) -> &'static str {
+    match kind {
+        DiffKind::Added     => "added",
+        DiffKind::Removed   => "removed",
+        DiffKind::Changed   => "changed"
+    }
+}
+
+///
+/// Converts a duration to milliseconds for display
+///
+fn millis(when: Duration) -> f64 {
+    when.as_secs_f64() * 1000.0
+}
+
+///
+/// Generates the JSON representation of the geometry of an element
+///
+fn geometry_data(geometry: &Option<ElementGeometry>) -> serde_json::Value {
+    match geometry {
+        Some(geometry)  => json!({
+            "element_type": geometry.element_type,
+            "start_time":   millis(geometry.start_time),
+            "bounds":       [geometry.bounds.x1, geometry.bounds.y1, geometry.bounds.x2, geometry.bounds.y2],
+            "points":       geometry.num_points
+        }),
+        None            => serde_json::Value::Null
+    }
+}
+
+///
+/// Reports the differences between two versions of an animation, optionally writing an SVG file that highlights the elements that changed
+///
+pub fn diff_animations_command<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, before: AnimationVersion, after: AnimationVersion, svg_filename: Option<String>) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        use self::FloCommandOutput::*;
+
+        // Open the two versions of the animation
+        let before_animation    = open_version(state, &before).await?;
+        let after_animation     = open_version(state, &after).await?;
+
+        output.publish(StartTask(format!("Compare '{}' with '{}'", before, after))).await;
+        let diff                = diff_animations(&*before_animation, &*after_animation);
+        output.publish(FinishTask).await;
+
+        // Report on the layers
+        for layer in diff.layers.iter() {
+            let name = layer.name_after.clone().or(layer.name_before.clone()).unwrap_or_else(|| "<unnamed>".to_string());
+
+            match layer.kind {
+                DiffKind::Changed   => output.publish(Message(format!("Layer {} renamed from '{}' to '{}'", layer.layer_id, layer.name_before.clone().unwrap_or_default(), name))).await,
+                kind                => output.publish(Message(format!("Layer {} {} ('{}')", layer.layer_id, kind_name(kind), name))).await
+            }
+
+            output.publish(Data(json!({ "type": "layer_diff", "change": kind_name(layer.kind), "layer": layer.layer_id, "name_before": layer.name_before, "name_after": layer.name_after }))).await;
+        }
+
+        // Report on the keyframes
+        for keyframe in diff.keyframes.iter() {
+            output.publish(Message(format!("Keyframe {} on layer {} at {}ms", kind_name(keyframe.kind), keyframe.layer_id, millis(keyframe.when)))).await;
+            output.publish(Data(json!({ "type": "keyframe_diff", "change": kind_name(keyframe.kind), "layer": keyframe.layer_id, "when_millis": millis(keyframe.when) }))).await;
+        }
+
+        // Report on the elements
+        for element in diff.elements.iter() {
+            let element_id  = element.element_id.id().unwrap_or(-1);
+            let description = match (&element.before, &element.after) {
+                (Some(before), Some(after)) => format!("{}: {}", after.element_type, before.describe_change(after)),
+                (Some(geometry), None)      |
+                (None, Some(geometry))      => format!("{}, {} points", geometry.element_type, geometry.num_points),
+                (None, None)                => String::new()
+            };
+
+            output.publish(Message(format!("Element {:04} {} on layer {} at {}ms ({})", element_id, kind_name(element.kind), element.layer_id, millis(element.keyframe), description))).await;
+            output.publish(Data(json!({
+                "type":         "element_diff",
+                "change":       kind_name(element.kind),
+                "id":           element_id,
+                "layer":        element.layer_id,
+                "when_millis":  millis(element.keyframe),
+                "before":       geometry_data(&element.before),
+                "after":        geometry_data(&element.after)
+            }))).await;
+        }
+
+        output.publish(Message(format!("{} layer, {} keyframe and {} element differences", diff.layers.len(), diff.keyframes.len(), diff.elements.len()))).await;
+        output.publish(Data(json!({ "type": "diff_summary", "layers": diff.layers.len(), "keyframes": diff.keyframes.len(), "elements": diff.elements.len() }))).await;
+
+        // Write the overlay SVG if requested
+        if let Some(svg_filename) = svg_filename {
+            let svg = diff_overlay_svg(&diff, after_animation.size());
+
+            output.publish(Message(format!("Writing {}", svg_filename))).await;
+            output.publish(BeginOutput(svg_filename)).await;
+            output.publish(Output(svg)).await;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use super::animation_diff::*;
+
+use flo_animation::*;
+
+use std::fmt::Write;
+
+///
+/// Converts a path to SVG path data
+///
+/// FlowBetween's coordinates have the origin at the bottom-left, so the y axis is flipped to match SVG's coordinates
+///
+fn svg_path_data(path: &Path, height: f64) -> String {
+    let mut data    = String::new();
+    let point       = |point: &PathPoint| (point.position.0, height - point.position.1);
+
+    for component in path.elements_ref() {
+        match component {
+            PathComponent::Move(pos)                => { let (x, y) = point(pos); write!(data, "M {:.2} {:.2} ", x, y).ok(); }
+            PathComponent::Line(pos)                => { let (x, y) = point(pos); write!(data, "L {:.2} {:.2} ", x, y).ok(); }
+            PathComponent::Bezier(pos, cp1, cp2)    => {
+                let ((x, y), (x1, y1), (x2, y2)) = (point(pos), point(cp1), point(cp2));
+                write!(data, "C {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} ", x1, y1, x2, y2, x, y).ok();
+            }
+            PathComponent::Close                    => { data.push_str("Z "); }
+        }
+    }
+
+    data
+}
+
+///
+/// Writes the paths for an element using the specified style
+///
+fn svg_element(svg: &mut String, geometry: &ElementGeometry, height: f64, style: &str) {
+    for path in geometry.paths.iter() {
+        writeln!(svg, "  <path d=\"{}\" style=\"{}\" />", svg_path_data(path, height).trim_end(), style).ok();
+    }
+}
+
+///
+/// Generates an SVG file that overlays the elements that changed between two animations
+///
+/// Removed elements are drawn in red and added elements in green. Changed elements are drawn with their original
+/// shape as a dashed orange outline and their new shape in blue.
+///
+pub fn diff_overlay_svg(diff: &AnimationDiff, size: (f64, f64)) -> String {
+    let (width, height) = size;
+    let mut svg         = String::new();
+
+    svg.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n");
+    svg.push_str("<!DOCTYPE svg PUBLIC \"-//W3C//DTD SVG 1.1//EN\" \"http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd\">\n");
+    writeln!(svg, "<svg width=\"100%\" height=\"100%\" viewBox=\"0 0 {} {}\" version=\"1.1\" xmlns=\"http://www.w3.org/2000/svg\" xml:space=\"preserve\" style=\"fill-rule:evenodd;clip-rule:evenodd;stroke-linecap:round;stroke-miterlimit:8;\">", width, height).ok();
+    writeln!(svg, "  <rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" style=\"fill:none;stroke:#888888;stroke-width:1\" />", width, height).ok();
+
+    for element in diff.elements.iter() {
+        match element.kind {
+            DiffKind::Added     => {
+                if let Some(after) = &element.after     { svg_element(&mut svg, after, height, "fill:#00c000;fill-opacity:0.4;stroke:#008000;stroke-width:1"); }
+            }
+
+            DiffKind::Removed   => {
+                if let Some(before) = &element.before   { svg_element(&mut svg, before, height, "fill:#e00000;fill-opacity:0.4;stroke:#a00000;stroke-width:1"); }
+            }
+
+            DiffKind::Changed   => {
+                if let Some(before) = &element.before   { svg_element(&mut svg, before, height, "fill:none;stroke:#ff8000;stroke-width:1;stroke-dasharray:4,2"); }
+                if let Some(after) = &element.after     { svg_element(&mut svg, after, height, "fill:#0060ff;fill-opacity:0.3;stroke:#0040c0;stroke-width:1"); }
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+
+    svg
+}
@@ -0,0 +1,7 @@
+mod animation_diff;
+mod diff_svg;
+mod diff_command;
+
+pub use self::animation_diff::*;
+pub use self::diff_svg::*;
+pub use self::diff_command::*;
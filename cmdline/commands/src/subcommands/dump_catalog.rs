@@ -6,6 +6,7 @@ use futures::prelude::*;
 
 use flo_stream::*;
 use flo_animation::*;
+use serde_json::json;
 
 ///
 /// Implementation of the list_files command
@@ -49,7 +50,7 @@ pub fn dump_catalog_as_edits<'a>(output: &'a mut Publisher<FloCommandOutput>, st
                 }
             }).collect::<String>();
             let output_file = format!("{} - {}.edits.flo", index, full_name);
-            output.publish(BeginOutput(output_file)).await;
+            output.publish(BeginOutput(output_file.clone())).await;
 
             // Fill the output file with the edits from this animation
             let num_edits       = file.get_num_edits();
@@ -74,6 +75,7 @@ pub fn dump_catalog_as_edits<'a>(output: &'a mut Publisher<FloCommandOutput>, st
             }
 
             output.publish(FloCommandOutput::FinishTask).await;
+            output.publish(Data(json!({ "type": "dumped_file", "index": index, "file": output_file, "edits": edit_count }))).await;
 
             index += 1;
         }
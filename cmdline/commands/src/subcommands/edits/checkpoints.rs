@@ -0,0 +1,65 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::storage::*;
+
+use futures::prelude::*;
+use serde_json::json;
+
+///
+/// Writes out the named checkpoints in the input animation
+///
+pub fn list_checkpoints<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let input_animation = state.input_animation();
+        let checkpoints     = input_animation.get_checkpoints();
+
+        if checkpoints.len() == 0 {
+            output.publish(FloCommandOutput::Message("No checkpoints".to_string())).await;
+        }
+
+        for checkpoint in checkpoints {
+            output.publish(FloCommandOutput::Message(format!("{}: {}", checkpoint.edit_index, checkpoint.name))).await;
+            output.publish(FloCommandOutput::Data(json!({ "type": "checkpoint", "name": checkpoint.name, "edit_index": checkpoint.edit_index }))).await;
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// Creates a named checkpoint at the end of the edit log of the input animation
+///
+pub fn add_checkpoint<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, name: String) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let input_animation = state.input_editable_animation();
+
+        input_animation.set_checkpoint(&name);
+        let edit_index      = input_animation.get_num_edits();
+        output.publish(FloCommandOutput::Message(format!("Created checkpoint '{}' at edit {}", name, edit_index))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "checkpoint", "name": name, "edit_index": edit_index }))).await;
+
+        Ok(())
+    }
+}
+
+///
+/// Restores the input animation to the state it was in at a named checkpoint
+///
+pub fn restore_checkpoint<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, name: String) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let input_animation = state.input_editable_animation();
+
+        output.publish(FloCommandOutput::StartTask(format!("Restore checkpoint '{}'", name))).await;
+        let num_edits       = restore_to_checkpoint(&*input_animation, &name).await;
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        let num_edits       = num_edits.ok_or_else(|| CommandError::CheckpointNotFound(name.clone()))?;
+        output.publish(FloCommandOutput::Message(format!("Restored checkpoint '{}' ({} edits added to the log)", name, num_edits))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "restored_checkpoint", "name": name, "edits_added": num_edits }))).await;
+
+        Ok(())
+    }
+}
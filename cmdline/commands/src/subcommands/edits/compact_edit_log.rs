@@ -0,0 +1,48 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::storage::*;
+
+use futures::prelude::*;
+use serde_json::json;
+
+///
+/// Compacts the edit log of the input animation, keeping the specified number of recent edits
+///
+pub fn compact_input_edit_log<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, retain_edits: usize) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let input_animation = state.input_editable_animation();
+
+        // Rewrite the log
+        output.publish(FloCommandOutput::StartTask("Compact edit log".to_string())).await;
+        let original_size   = input_animation.get_edit_log_size();
+        let compaction      = compact_edit_log(&*input_animation, retain_edits).await;
+        let compacted_size  = input_animation.get_edit_log_size();
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        // Report on the result
+        if compaction.compacted {
+            let edits_msg   = format!("Compacted {} edit operations to {} ({} recent edits retained)", compaction.original_length, compaction.compacted_length, compaction.retained_edits);
+            let size_msg    = format!("Edit log size reduced from {} to {} bytes", original_size, compacted_size);
+            output.publish(FloCommandOutput::Message(edits_msg)).await;
+            output.publish(FloCommandOutput::Message(size_msg)).await;
+        } else {
+            let edits_msg   = format!("Edit log was left unchanged ({} edit operations): a snapshot would not be shorter or would not recreate the animation exactly", compaction.original_length);
+            output.publish(FloCommandOutput::Message(edits_msg)).await;
+        }
+
+        output.publish(FloCommandOutput::Data(json!({
+            "type":             "compaction",
+            "compacted":        compaction.compacted,
+            "original_length":  compaction.original_length,
+            "compacted_length": compaction.compacted_length,
+            "retained_edits":   compaction.retained_edits,
+            "original_bytes":   original_size,
+            "compacted_bytes":  compacted_size
+        }))).await;
+
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ use flo_stream::*;
 use flo_animation::*;
 
 use futures::prelude::*;
+use serde_json::json;
 use std::marker::{Unpin};
 
 ///
@@ -72,9 +73,11 @@ where SourceStream: 'a+Unpin+Send+Stream<Item=char> {
         }
 
         // Update the edit buffer with the values we just read
+        let num_edits = edits.len();
         *state = state.set_edit_buffer(edits);
 
         output.publish(FloCommandOutput::FinishTask).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "edit_buffer", "edits": num_edits }))).await;
 
         Ok(())
     }
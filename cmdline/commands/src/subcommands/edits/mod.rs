@@ -3,9 +3,15 @@ mod write_all_edits;
 mod serialize_edits;
 mod deserialize_edits;
 mod summarize_edit_log;
+mod show_edit_log_size;
+mod compact_edit_log;
+mod checkpoints;
 
 pub use self::read_all_edits::*;
 pub use self::write_all_edits::*;
 pub use self::serialize_edits::*;
 pub use self::deserialize_edits::*;
 pub use self::summarize_edit_log::*;
+pub use self::show_edit_log_size::*;
+pub use self::compact_edit_log::*;
+pub use self::checkpoints::*;
@@ -6,6 +6,7 @@ use flo_stream::*;
 use ::desync::*;
 
 use futures::prelude::*;
+use serde_json::json;
 
 ///
 /// The read_all_edits command loads all of the edits from the input animation and stores them in the state buffer
@@ -40,7 +41,10 @@ pub fn read_all_edits<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'
         }).await;
 
         // Update the edit buffer
-        *state = state.set_edit_buffer(edits.unwrap());
+        let edits = edits.unwrap();
+        output.publish(FloCommandOutput::Data(json!({ "type": "edit_buffer", "edits": edits.len() }))).await;
+
+        *state = state.set_edit_buffer(edits);
 
         Ok(())
     }
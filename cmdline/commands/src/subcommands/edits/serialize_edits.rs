@@ -6,6 +6,7 @@ use flo_stream::*;
 use flo_animation::serializer::*;
 
 use futures::prelude::*;
+use serde_json::json;
 
 ///
 /// Serializes the edits to the output
@@ -17,7 +18,8 @@ pub fn serialize_edits<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &
         serialize_animation_as_edits(&mut result, state.edit_buffer(), "FlowBetween Animation");
 
         // Send to the output
-        output.publish(FloCommandOutput::Output(result)).await;
+        output.publish(FloCommandOutput::Output(result.clone())).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "serialized_edits", "edits": state.edit_buffer().len(), "text": result }))).await;
 
         Ok(())
     }
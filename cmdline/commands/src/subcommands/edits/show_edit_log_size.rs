@@ -0,0 +1,26 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+
+use futures::prelude::*;
+use serde_json::json;
+
+///
+/// Displays the number of edits and the total size of the edit log for the input animation
+///
+pub fn show_edit_log_size<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let input_animation = state.input_animation();
+
+        let num_edits       = input_animation.get_num_edits();
+        let log_size        = input_animation.get_edit_log_size();
+
+        let size_msg        = format!("Edit log: {} edit operations ({} bytes)", num_edits, log_size);
+        output.publish(FloCommandOutput::Message(size_msg)).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "edit_log_size", "edits": num_edits, "bytes": log_size }))).await;
+
+        Ok(())
+    }
+}
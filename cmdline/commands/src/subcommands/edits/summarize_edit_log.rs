@@ -3,8 +3,29 @@ use crate::error::*;
 use crate::output::*;
 
 use flo_stream::*;
+use flo_animation::*;
 
 use futures::prelude::*;
+use serde_json::json;
+
+use std::collections::{BTreeMap};
+
+///
+/// Returns a name for the type of an edit
+///
+fn edit_type(edit: &AnimationEdit) -> &'static str {
+    use self::AnimationEdit::*;
+
+    match edit {
+        Layer(_, _)         => "layer",
+        Element(_, _)       => "element",
+        Motion(_, _)        => "motion",
+        SetSize(_, _)       => "set_size",
+        SetPreference(_, _) => "set_preference",
+        AddNewLayer(_)      => "add_new_layer",
+        RemoveLayer(_)      => "remove_layer"
+    }
+}
 
 ///
 /// Displays a summary of the contents of the edit log buffer
@@ -23,7 +44,17 @@ pub fn summarize_edit_log<'a>(output: &'a mut Publisher<FloCommandOutput>, state
         let total_number_of_items_msg = format!("Total: {} edit operations", edit_log.len());
         output.publish(FloCommandOutput::Message(total_number_of_items_msg)).await;
 
-        // TODO: item breakdown
+        // Breakdown by edit type
+        let mut by_type = BTreeMap::new();
+        for edit in edit_log.iter() {
+            *by_type.entry(edit_type(edit)).or_insert(0usize) += 1;
+        }
+
+        for (kind, count) in by_type.iter() {
+            output.publish(FloCommandOutput::Message(format!("  {}: {}", kind, count))).await;
+        }
+
+        output.publish(FloCommandOutput::Data(json!({ "type": "edit_summary", "total": edit_log.len(), "by_type": by_type }))).await;
 
         Ok(())
     }
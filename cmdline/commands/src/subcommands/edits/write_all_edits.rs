@@ -6,6 +6,7 @@ use flo_stream::*;
 use ::desync::*;
 
 use futures::prelude::*;
+use serde_json::json;
 use std::sync::*;
 
 ///
@@ -38,6 +39,7 @@ pub fn write_all_edits<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &
 
                 let finish_message = format!("Wrote {} edits to the output animation", edits.len());
                 edit_output.publish(FloCommandOutput::Message(finish_message)).await;
+                edit_output.publish(FloCommandOutput::Data(json!({ "type": "wrote_edits", "edits": edits.len() }))).await;
 
                 edits
             }.boxed()
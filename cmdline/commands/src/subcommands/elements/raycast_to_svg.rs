@@ -8,6 +8,7 @@ use flo_stream::*;
 use flo_animation::*;
 
 use futures::prelude::*;
+use serde_json::json;
 
 use std::sync::*;
 
@@ -47,6 +48,7 @@ pub fn raycast_to_svg<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'
         };
 
         output.publish(FloCommandOutput::Message(format!("Adding {} paths", paths.len()))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "raycast", "element": element_id.id(), "paths": paths.len() }))).await;
 
         // The way grouping works is to remove interior points and then combine the paths with a rule, we'll simulate that here and
         // use flo_curve's debugging function to generate a set of SVG files
@@ -70,6 +72,7 @@ pub fn raycast_to_svg<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'
             let svg                         = graph_path_svg_string(&remove_interior, vec![]);
             output.publish(FloCommandOutput::Message(format!("  Writing {}", remove_interior_filename))).await;
 
+            output.publish(FloCommandOutput::Data(json!({ "type": "raycast_svg", "path": path_num, "stage": "remove_interior", "file": remove_interior_filename }))).await;
             output.publish(FloCommandOutput::BeginOutput(remove_interior_filename)).await;
             output.publish(FloCommandOutput::Output("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>
                 <!DOCTYPE svg PUBLIC \"-//W3C//DTD SVG 1.1//EN\" \"http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd\">
@@ -98,6 +101,7 @@ pub fn raycast_to_svg<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'
                 let svg                         = graph_path_svg_string(&combined_path, vec![]);
                 output.publish(FloCommandOutput::Message(format!("  Writing {}", combined_filename))).await;
 
+                output.publish(FloCommandOutput::Data(json!({ "type": "raycast_svg", "path": path_num, "stage": "combined", "file": combined_filename }))).await;
                 output.publish(FloCommandOutput::BeginOutput(combined_filename)).await;
                 output.publish(FloCommandOutput::Output("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>
                     <!DOCTYPE svg PUBLIC \"-//W3C//DTD SVG 1.1//EN\" \"http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd\">
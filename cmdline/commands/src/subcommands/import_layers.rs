@@ -0,0 +1,56 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+use crate::storage_descriptor::*;
+
+use flo_stream::*;
+use flo_animation::*;
+
+use futures::prelude::*;
+use serde_json::json;
+
+use std::sync::*;
+
+///
+/// Copies layers from another animation into the output animation
+///
+/// All of the layers are imported if the list of layers is empty. If `retime` is set, the times in the imported layers
+/// are adjusted so that they match up with the frames of the output animation.
+///
+pub fn import_layers<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, source: StorageDescriptor, layer_ids: Vec<u64>, retime: bool) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        // Open the animation to import from (this is only read from, so the file is left unchanged)
+        let source_animation    = source.open_animation_read_only(&state.file_manager())
+            .ok_or_else(|| CommandError::CouldNotOpenAnimation(format!("{}", source)))?;
+
+        let layer_ids           = if layer_ids.len() == 0 { source_animation.get_layer_ids() } else { layer_ids };
+        let source_layers       = source_animation.get_layer_ids();
+        if let Some(missing_layer) = layer_ids.iter().filter(|layer_id| !source_layers.contains(layer_id)).nth(0) {
+            return Err(CommandError::LayerNotFound(*missing_layer));
+        }
+
+        // Generate the edits to copy the layers into the output animation
+        let output_animation    = state.output_animation();
+        let existing_layers     = output_animation.get_layer_ids();
+
+        output.publish(FloCommandOutput::StartTask(format!("Import layers from '{}'", source))).await;
+        let edits               = import_layer_edits(&*source_animation, &*output_animation, &layer_ids, retime);
+
+        let mut edit_sink       = output_animation.edit();
+        edit_sink.publish(Arc::new(edits)).await;
+        edit_sink.when_empty().await;
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        // Report on the layers that were imported
+        let new_layers          = output_animation.get_layer_ids().into_iter()
+            .filter(|layer_id| !existing_layers.contains(layer_id))
+            .collect::<Vec<_>>();
+
+        for (source_layer_id, new_layer_id) in layer_ids.iter().zip(new_layers.iter()) {
+            output.publish(FloCommandOutput::Message(format!("Imported layer {} as layer {}", source_layer_id, new_layer_id))).await;
+            output.publish(FloCommandOutput::Data(json!({ "type": "imported_layer", "source_layer": source_layer_id, "layer": new_layer_id }))).await;
+        }
+
+        Ok(())
+    }
+}
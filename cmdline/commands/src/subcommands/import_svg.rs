@@ -0,0 +1,53 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::*;
+use flo_animation::svg::*;
+
+use futures::prelude::*;
+use serde_json::json;
+
+use std::fs;
+use std::sync::*;
+
+///
+/// Adds the shapes from an SVG file to the keyframe at the specified layer and frame of the output animation
+///
+pub fn import_svg<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, svg_file: String, layer_id: u64, frame_number: usize) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        // Read the SVG file
+        let svg                 = fs::read_to_string(&svg_file)
+            .map_err(|err| CommandError::FileOperationFailed(format!("Could not read '{}': {}", svg_file, err)))?;
+
+        // Find the layer to import into
+        let output_animation    = state.output_animation();
+        if output_animation.get_layer_with_id(layer_id).is_none() {
+            return Err(CommandError::LayerNotFound(layer_id));
+        }
+
+        let when                = output_animation.frame_length() * (frame_number as u32);
+
+        // Generate and send the edits
+        let edits               = svg_import_edits(&svg, &*output_animation, layer_id, when)
+            .map_err(|err| CommandError::CannotImportSvg(format!("Could not import '{}': {}", svg_file, err)))?;
+        let num_paths           = edits.iter()
+            .filter(|edit| match edit {
+                AnimationEdit::Layer(_, LayerEdit::Path(_, PathEdit::CreatePath(_, _)))    => true,
+                _                                                                       => false
+            })
+            .count();
+
+        output.publish(FloCommandOutput::StartTask(format!("Import '{}'", svg_file))).await;
+        let mut edit_sink       = output_animation.edit();
+        edit_sink.publish(Arc::new(edits)).await;
+        edit_sink.when_empty().await;
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        output.publish(FloCommandOutput::Message(format!("Imported {} paths from '{}' into frame {}:{}", num_paths, svg_file, layer_id, frame_number))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "imported_svg", "file": svg_file, "layer": layer_id, "frame": frame_number, "paths": num_paths }))).await;
+
+        Ok(())
+    }
+}
@@ -6,6 +6,9 @@ use flo_animation::*;
 
 use futures::prelude::*;
 use itertools::*;
+use serde_json::json;
+
+use std::sync::*;
 
 ///
 /// Generates a short summary of a vector
@@ -35,6 +38,52 @@ fn describe_vector(vec: &Vector) -> String {
     }
 }
 
+///
+/// Returns a name for the type of a vector element
+///
+pub (crate) fn vector_type(vec: &Vector) -> &'static str {
+    use self::Vector::*;
+
+    match vec {
+        Transformed(_transformed)       => "transformed",
+        BrushDefinition(_definition)    => "brush_definition",
+        BrushProperties(_props)         => "brush_properties",
+        BrushStroke(_brush_stroke)      => "brush_stroke",
+        Path(_path)                     => "path",
+        Motion(_motion)                 => "motion",
+        Transformation(_transform)      => "transformation",
+        Error                           => "error",
+        Group(_group)                   => "group"
+    }
+}
+
+///
+/// Generates a machine-readable description of a vector element
+///
+fn element_data(frame: &dyn Frame, element: &Vector) -> serde_json::Value {
+    // Work out the properties and the bounds of this element
+    let properties          = frame.apply_properties_for_element(element, Arc::new(VectorProperties::default()));
+    let bounds              = element.to_path(&*properties, PathConversion::Fastest)
+        .unwrap_or_else(|| vec![])
+        .iter()
+        .fold(Rect::empty(), |bounds, path| bounds.union(Rect::from(path)));
+
+    let brush               = &properties.brush_properties;
+    let (r, g, b, a)        = brush.color.to_rgba_components();
+
+    json!({
+        "type":         "element",
+        "id":           element.id().id(),
+        "element_type": vector_type(element),
+        "bounds":       [bounds.x1, bounds.y1, bounds.x2, bounds.y2],
+        "brush":        {
+            "size":     brush.size,
+            "opacity":  brush.opacity,
+            "color":    [r, g, b, a]
+        }
+    })
+}
+
 ///
 /// Lists all of the elements in the current frame
 ///
@@ -49,6 +98,9 @@ pub fn list_elements<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a
 
             let elements = frame.vector_elements().unwrap().collect::<Vec<_>>();
             for element in elements {
+                // Generate the data for this element
+                let data            = element_data(&*frame, &element);
+
                 // Get the ID of this element
                 let element_id      = element.id().id()
                     .map(|id| format!("{:04}", id))
@@ -64,6 +116,7 @@ pub fn list_elements<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a
                 // Write it out to the output
                 let element = format!("{} : {}", element_id, description);
                 output.publish(Output(element)).await;
+                output.publish(Data(data)).await;
             }
 
         } else {
@@ -4,6 +4,7 @@ use crate::output::*;
 use futures::prelude::*;
 
 use flo_stream::*;
+use serde_json::json;
 
 ///
 /// Implementation of the list_files command
@@ -23,9 +24,10 @@ pub fn list_files<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mu
         let mut index = 0;
         for file in all_files {
             let full_name = file_manager.display_name_for_path(file.as_path()).unwrap_or("<untitled>".to_string());
-            let file_name = format!("#{}#: {}", index, full_name);
-            output.publish(Output(file_name)).await;
-            output.publish(Output("\n".to_string())).await;
+            let tags      = file_manager.tags_for_path(file.as_path());
+            let file_name = if tags.is_empty() { format!("#{}#: {}", index, full_name) } else { format!("#{}#: {} [{}]", index, full_name, tags.join(", ")) };
+            output.publish(Output(format!("{}\n", file_name))).await;
+            output.publish(Data(json!({ "type": "file", "index": index, "name": full_name, "tags": tags }))).await;
 
             index += 1;
         }
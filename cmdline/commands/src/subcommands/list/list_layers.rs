@@ -4,6 +4,7 @@ use crate::output::*;
 use futures::prelude::*;
 
 use flo_stream::*;
+use serde_json::json;
 
 ///
 /// Writes out a list of layers to the output
@@ -19,11 +20,12 @@ pub fn list_layers<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a m
 
         output.publish(Message("Animation layers:".to_string())).await;
 
-        for layer_id in layer_ids {
+        for (index, layer_id) in layer_ids.into_iter().enumerate() {
             if let Some(layer) = input_animation.get_layer_with_id(layer_id) {
                 // Display information on this layer
                 let layer_info = format!("  Layer ({:02}): {}", layer_id, layer.name().unwrap_or("No name".to_string()));
                 output.publish(Message(layer_info)).await;
+                output.publish(Data(json!({ "type": "layer", "id": layer_id, "name": layer.name(), "index": index }))).await;
             } else {
                 // Layer is in the list but not actually present in the animation
                 let layer_info = format!("  Layer ({:02}) - missing", layer_id);
@@ -0,0 +1,67 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::*;
+use flo_animation::lottie::*;
+
+use futures::prelude::*;
+use serde_json::json;
+
+use std::fs;
+use std::sync::*;
+
+///
+/// Writes the input animation to a Lottie JSON file
+///
+pub fn export_lottie<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, target_file: String) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let input_animation = state.input_animation();
+
+        output.publish(FloCommandOutput::StartTask(format!("Export '{}'", target_file))).await;
+        let lottie          = lottie_export(&*input_animation);
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        fs::write(&target_file, lottie)
+            .map_err(|err| CommandError::FileOperationFailed(format!("Could not write '{}': {}", target_file, err)))?;
+
+        output.publish(FloCommandOutput::Message(format!("Exported the animation to '{}'", target_file))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "exported_lottie", "file": target_file }))).await;
+
+        Ok(())
+    }
+}
+
+///
+/// Adds the shape layers from a Lottie JSON file to the output animation as new layers
+///
+pub fn import_lottie<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, lottie_file: String) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        // Read the Lottie file
+        let lottie              = fs::read_to_string(&lottie_file)
+            .map_err(|err| CommandError::FileOperationFailed(format!("Could not read '{}': {}", lottie_file, err)))?;
+
+        // Generate and send the edits
+        let output_animation    = state.output_animation();
+        let existing_layers     = output_animation.get_layer_ids();
+        let edits               = lottie_import_edits(&lottie, &*output_animation)
+            .map_err(|err| CommandError::CannotImportLottie(format!("Could not import '{}': {}", lottie_file, err)))?;
+
+        output.publish(FloCommandOutput::StartTask(format!("Import '{}'", lottie_file))).await;
+        let mut edit_sink       = output_animation.edit();
+        edit_sink.publish(Arc::new(edits)).await;
+        edit_sink.when_empty().await;
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        // Report on the layers that were created
+        let new_layers          = output_animation.get_layer_ids().into_iter()
+            .filter(|layer_id| !existing_layers.contains(layer_id))
+            .collect::<Vec<_>>();
+
+        output.publish(FloCommandOutput::Message(format!("Imported {} layers from '{}'", new_layers.len(), lottie_file))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "imported_lottie", "file": lottie_file, "layers": new_layers }))).await;
+
+        Ok(())
+    }
+}
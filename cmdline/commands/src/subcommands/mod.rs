@@ -1,17 +1,29 @@
 mod list;
+mod catalog;
+mod diff;
 mod edits;
 mod elements;
 mod read_from;
 mod dump_catalog;
+mod import_layers;
+mod import_svg;
+mod lottie;
 mod select_frame;
 mod write_to_catalog;
 mod set_catalog_folder;
+mod validate_animation;
 
 pub (super) use self::list::*;
+pub (super) use self::catalog::*;
+pub (super) use self::diff::*;
 pub (super) use self::edits::*;
 pub (super) use self::elements::*;
 pub (super) use self::read_from::*;
 pub (super) use self::dump_catalog::*;
+pub (super) use self::import_layers::*;
+pub (super) use self::import_svg::*;
+pub (super) use self::lottie::*;
 pub (super) use self::select_frame::*;
 pub (super) use self::write_to_catalog::*;
 pub (super) use self::set_catalog_folder::*;
+pub (super) use self::validate_animation::*;
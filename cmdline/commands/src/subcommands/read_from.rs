@@ -6,6 +6,7 @@ use crate::storage_descriptor::*;
 use flo_stream::*;
 
 use futures::prelude::*;
+use serde_json::json;
 
 ///
 /// The read_from command: changes the input to the location specified by the storage descriptor
@@ -13,7 +14,8 @@ use futures::prelude::*;
 pub fn read_from<'a>(location: StorageDescriptor, output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
     async move {
         // Generate a message we'll use when the file opens
-        let msg = format!("Opening '{}' for input", location);
+        let msg         = format!("Opening '{}' for input", location);
+        let description = format!("{}", location);
 
         // Load the file using the current state (and update to a new state)
         *state = state.load_input_file(location.clone())
@@ -24,6 +26,7 @@ pub fn read_from<'a>(location: StorageDescriptor, output: &'a mut Publisher<FloC
 
         // Display the success message when the file is opened
         output.publish(FloCommandOutput::Message(msg)).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "input", "location": description }))).await;
 
         Ok(())
     }
@@ -4,6 +4,7 @@ use crate::output::*;
 use flo_stream::*;
 
 use futures::prelude::*;
+use serde_json::json;
 
 ///
 /// Updates the selected frame to the frame found at the specified time in the specified layer of the input animation
@@ -22,6 +23,7 @@ pub fn select_frame<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a
         if frame.is_some() {
             let msg = format!("Frame {}:{} (at T+{}ms)", layer_id, frame_number, frame_time.as_millis());
             output.publish(Message(msg)).await;
+            output.publish(Data(json!({ "type": "frame", "layer": layer_id, "frame": frame_number, "time_ms": frame_time.as_millis() as u64 }))).await;
         } else {
             let msg = format!("Frame {}:{} was not found", layer_id, frame_number);
             output.publish(Error(msg)).await;
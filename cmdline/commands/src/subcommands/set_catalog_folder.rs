@@ -6,6 +6,7 @@ use flo_stream::*;
 use flo_ui_files::sqlite::*;
 
 use futures::prelude::*;
+use serde_json::json;
 use std::sync::*;
 
 ///
@@ -22,6 +23,7 @@ pub fn set_catalog_folder<'a>(new_folder: &'a str, output: &'a mut Publisher<Flo
         // Notify the user
         let msg = format!("Set catalog folder to '{}'", new_folder);
         output.publish(FloCommandOutput::Message(msg)).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "catalog_folder", "folder": new_folder }))).await;
 
         Ok(())
     }
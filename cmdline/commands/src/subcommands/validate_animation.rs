@@ -0,0 +1,117 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::*;
+use flo_animation::storage::*;
+
+use futures::prelude::*;
+use serde_json::json;
+
+use std::sync::*;
+
+///
+/// Generates a description of an element ID
+///
+fn describe_id(element_id: &ElementId) -> String {
+    element_id.id().map(|id| id.to_string()).unwrap_or_else(|| "<unassigned>".to_string())
+}
+
+///
+/// Generates a description of an issue found in an animation
+///
+fn describe_issue(issue: &AnimationIssue) -> String {
+    use self::AnimationIssue::*;
+
+    match issue {
+        UnreadableElement(element_id)                           => format!("Element {} cannot be read", describe_id(element_id)),
+        DanglingAttachment(element_id, layer_id, when)          => format!("Deleted element {} is still attached to layer {} at T+{}ms", describe_id(element_id), layer_id, when.as_millis()),
+        ElementOnMissingLayer(element_id, layer_id)             => format!("Element {} is attached to missing layer {}", describe_id(element_id), layer_id),
+        ElementOnMissingKeyFrame(element_id, layer_id, when)    => format!("Element {} is attached to a missing keyframe in layer {} at T+{}ms", describe_id(element_id), layer_id, when.as_millis()),
+        MissingAttachment(element_id, attachment_id)            => format!("Element {} has missing attachment {}", describe_id(element_id), describe_id(attachment_id)),
+        MotionForMissingElement(motion_id, element_id)          => format!("Motion {} is attached to missing element {}", describe_id(motion_id), describe_id(element_id)),
+        OrphanedMotion(motion_id)                               => format!("Motion {} is only attached to missing elements", describe_id(motion_id))
+    }
+}
+
+///
+/// Generates a name for the type of an issue
+///
+fn issue_type(issue: &AnimationIssue) -> &'static str {
+    use self::AnimationIssue::*;
+
+    match issue {
+        UnreadableElement(_)                => "unreadable_element",
+        DanglingAttachment(_, _, _)         => "dangling_attachment",
+        ElementOnMissingLayer(_, _)         => "element_on_missing_layer",
+        ElementOnMissingKeyFrame(_, _, _)   => "element_on_missing_keyframe",
+        MissingAttachment(_, _)             => "missing_attachment",
+        MotionForMissingElement(_, _)       => "motion_for_missing_element",
+        OrphanedMotion(_)                   => "orphaned_motion"
+    }
+}
+
+///
+/// Checks the storage of the input animation for problems and writes them to the output
+///
+fn find_issues<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<Vec<AnimationIssue>, CommandError>>+Send+'a {
+    async move {
+        let input_storage   = state.input_storage().ok_or(CommandError::StorageNotAvailable)?;
+
+        output.publish(FloCommandOutput::StartTask("Validate animation".to_string())).await;
+        let issues          = validate_animation_storage(move |commands| (*input_storage)(commands)).await;
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        // Report on the issues that were found
+        if issues.len() == 0 {
+            output.publish(FloCommandOutput::Message("No issues found".to_string())).await;
+        } else {
+            output.publish(FloCommandOutput::Message(format!("{} issue{} found", issues.len(), if issues.len() == 1 { "" } else { "s" }))).await;
+        }
+
+        for issue in issues.iter() {
+            output.publish(FloCommandOutput::Message(format!("  {}", describe_issue(issue)))).await;
+            output.publish(FloCommandOutput::Data(json!({ "type": "issue", "issue_type": issue_type(issue), "description": describe_issue(issue) }))).await;
+        }
+        output.publish(FloCommandOutput::Data(json!({ "type": "validation", "issues": issues.len() }))).await;
+
+        Ok(issues)
+    }
+}
+
+///
+/// Checks the storage of the input animation for problems
+///
+pub fn validate_animation<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        find_issues(output, state).await?;
+
+        Ok(())
+    }
+}
+
+///
+/// Checks the storage of the input animation for problems, and writes the edits that will fix them to the output animation
+///
+/// The output animation should be the same as the input animation or a copy of it (eg, generated by reading and writing
+/// all of the edits) for the repair edits to make sense.
+///
+pub fn repair_animation<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let issues          = find_issues(output, state).await?;
+        let repair_edits    = repair_edits_for_issues(&issues);
+        let num_edits       = repair_edits.len();
+
+        // Write the edits to the output animation
+        let output_animation = state.output_animation();
+        let mut edit_sink    = output_animation.edit();
+        edit_sink.publish(Arc::new(repair_edits)).await;
+        edit_sink.when_empty().await;
+
+        output.publish(FloCommandOutput::Message(format!("Wrote {} repair edit{} to the output animation", num_edits, if num_edits == 1 { "" } else { "s" }))).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "repair", "issues": issues.len(), "edits": num_edits }))).await;
+
+        Ok(())
+    }
+}
@@ -8,6 +8,8 @@ use flo_animation::storage::*;
 use flo_sqlite_storage::*;
 
 use futures::prelude::*;
+use futures::stream::{BoxStream};
+use serde_json::json;
 use std::sync::*;
 
 ///
@@ -25,14 +27,16 @@ pub fn write_to_catalog<'a>(name: String, output: &'a mut Publisher<FloCommandOu
         let file_manager    = state.file_manager();
         let new_path        = file_manager.create_new_path();
         let storage         = SqliteAnimationStorage::new_with_file(&new_path.clone()).map_err(|_| CommandError::CouldNotCreateAnimation(name.clone()))?;
-        let animation       = create_animation_editor(move |commands| storage.get_responses(commands).boxed());
+        let storage         = Arc::new(move |commands: BoxStream<'static, Vec<StorageCommand>>| storage.get_responses(commands).boxed()) as StorageConnection;
+        let animation       = { let storage = Arc::clone(&storage); create_animation_editor(move |commands| (*storage)(commands)) };
         file_manager.set_display_name_for_path(new_path.as_path(), name.clone());
 
         // Update the state to point at it
-        *state = state.set_output_animation(StorageDescriptor::CatalogName(name), Arc::new(animation));
+        *state = state.set_output_animation(StorageDescriptor::CatalogName(name.clone()), Arc::new(animation), Some(storage));
 
         // Display the success message when the file is opened
         output.publish(FloCommandOutput::Message(msg)).await;
+        output.publish(FloCommandOutput::Data(json!({ "type": "output", "name": name }))).await;
 
         Ok(())
     }
@@ -8,10 +8,90 @@ use futures::prelude::*;
 
 use std::path::*;
 
+///
+/// The formats that the console can write command output in
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ConsoleFormat {
+    /// Human-readable text
+    Text,
+
+    /// One JSON record per line
+    Json
+}
+
 ///
 /// Sends the command output to the console
 ///
-pub fn run_console<InputStream>(command_output: InputStream) -> impl Future<Output=()>+Send
+pub fn run_console<InputStream>(command_output: InputStream, format: ConsoleFormat) -> impl Future<Output=()>+Send
+where InputStream: Stream<Item=FloCommandOutput>+Send+Unpin {
+    async move {
+        match format {
+            ConsoleFormat::Text => run_text_console(command_output).await,
+            ConsoleFormat::Json => run_json_console(command_output).await
+        }
+    }
+}
+
+///
+/// Writes a string to an output stream
+///
+async fn write_string<Target: AsyncWrite+Unpin+?Sized>(target: &mut Target, output: &str) {
+    let bytes   = output.as_bytes();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let remaining_bytes = &bytes[pos..bytes.len()];
+
+        let num_written     = target.write(remaining_bytes).await.unwrap();
+        pos                 += num_written;
+    }
+}
+
+///
+/// Sends the command output to the console as JSON records, one per line
+///
+/// Output that's redirected to a file is written unchanged. The text messages and any output that isn't redirected are
+/// left out: every command reports what it did as `Data` records, so the records written to stdout are all JSON.
+///
+fn run_json_console<InputStream>(command_output: InputStream) -> impl Future<Output=()>+Send
+where InputStream: Stream<Item=FloCommandOutput>+Send+Unpin {
+    async move {
+        let mut command_output  = command_output;
+        let mut record_stream   = stdout();
+        let mut output_file     = None;
+
+        while let Some(input) = command_output.next().await {
+            use self::FloCommandOutput::*;
+
+            match input {
+                BeginOutput(filename)   => {
+                    output_file = Some(fs::File::create(PathBuf::from(filename.clone())).await.unwrap());
+                    write_string(&mut record_stream, &format!("{}\n", BeginOutput(filename).to_json().unwrap())).await;
+                }
+
+                Output(output)          => {
+                    if let Some(output_file) = output_file.as_mut() {
+                        write_string(output_file, &output).await;
+                    }
+                }
+
+                Message(_msg)           => { }
+
+                other                   => {
+                    if let Some(record) = other.to_json() {
+                        write_string(&mut record_stream, &format!("{}\n", record)).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+///
+/// Sends the command output to the console as text
+///
+fn run_text_console<InputStream>(command_output: InputStream) -> impl Future<Output=()>+Send
 where InputStream: Stream<Item=FloCommandOutput>+Send+Unpin {
     async move {
         let mut command_output = command_output;
@@ -26,6 +106,7 @@ where InputStream: Stream<Item=FloCommandOutput>+Send+Unpin {
             match input {
                 BeginCommand(_cmd)              => { }
                 Message(msg)                    => { message_stream.write(msg.as_bytes()).await.unwrap(); message_stream.write("\n".as_bytes()).await.unwrap(); }
+                Data(_data)                     => { }
                 BeginOutput(filename)           => { output_stream = Box::new(fs::File::create(PathBuf::from(filename)).await.unwrap()); }
                 Error(err)                      => { message_stream.write(err.as_bytes()).await.unwrap(); message_stream.write("\n".as_bytes()).await.unwrap(); }
                 State(_state)                   => { }
@@ -38,17 +119,7 @@ where InputStream: Stream<Item=FloCommandOutput>+Send+Unpin {
                     message_stream.write(msg.as_bytes()).await.unwrap();
                 }
 
-                Output(output)                  => {
-                    let bytes   = output.as_bytes();
-                    let mut pos = 0;
-
-                    while pos < bytes.len() {
-                        let remaining_bytes = &bytes[pos..bytes.len()];
-
-                        let num_written     = output_stream.write(remaining_bytes).await.unwrap();
-                        pos                 += num_written;
-                    }
-                }
+                Output(output)                  => { write_string(&mut output_stream, &output).await; }
             }
         }
     }
@@ -2,9 +2,10 @@ use flo_commands::*;
 use flo_animation::*;
 
 use tokio::prelude::*;
-use tokio::io::{stdin, stderr};
+use tokio::io::{stdin, stderr, AsyncBufRead, BufReader};
 use tokio::fs;
 use futures::prelude::*;
+use futures::stream;
 use clap::{App, Arg, SubCommand};
 
 mod console;
@@ -54,14 +55,172 @@ async fn main() {
             .short("F")
             .takes_value(true)
             .help("Specifies the layer and frame to apply the operation to (eg: -F 3:5 selects layer 3, frame 5)"))
+        .arg(Arg::with_name("continue-on-error")
+            .long("continue-on-error")
+            .help("Keeps running commands after one of them fails"))
+        .arg(Arg::with_name("json")
+            .long("json")
+            .help("Writes the results of the commands as JSON records, one per line"))
         .subcommand(SubCommand::with_name("ls")
             .about("Lists animations in the main index"))
         .subcommand(SubCommand::with_name("ls-layers")
             .about("Lists the layers defined in the input animation"))
+        .subcommand(SubCommand::with_name("duplicate")
+            .arg(Arg::with_name("ANIMATION")
+                .index(1)
+                .required(true)
+                .help("The catalog name or #number# of the animation to copy"))
+            .arg(Arg::with_name("NAME")
+                .index(2)
+                .help("The name of the copy (defaults to the original name followed by '(copy)')"))
+            .about("Makes a copy of an animation in the catalog"))
+        .subcommand(SubCommand::with_name("rename")
+            .arg(Arg::with_name("ANIMATION")
+                .index(1)
+                .required(true)
+                .help("The catalog name or #number# of the animation to rename"))
+            .arg(Arg::with_name("NAME")
+                .index(2)
+                .required(true)
+                .help("The new name for the animation"))
+            .about("Changes the name of an animation in the catalog"))
+        .subcommand(SubCommand::with_name("delete")
+            .arg(Arg::with_name("ANIMATION")
+                .index(1)
+                .required(true)
+                .help("The catalog name or #number# of the animation to delete"))
+            .about("Removes an animation from the catalog"))
+        .subcommand(SubCommand::with_name("export")
+            .arg(Arg::with_name("ANIMATION")
+                .index(1)
+                .required(true)
+                .help("The catalog name or #number# of the animation to export"))
+            .arg(Arg::with_name("FILE")
+                .index(2)
+                .required(true)
+                .help("The file to write the animation to"))
+            .about("Copies an animation from the catalog to a file"))
+        .subcommand(SubCommand::with_name("import")
+            .arg(Arg::with_name("FILE")
+                .index(1)
+                .required(true)
+                .help("The animation file to import"))
+            .arg(Arg::with_name("NAME")
+                .index(2)
+                .help("The name of the animation in the catalog (defaults to the name of the file)"))
+            .about("Copies an animation file into the catalog"))
+        .subcommand(SubCommand::with_name("tag")
+            .arg(Arg::with_name("ANIMATION")
+                .index(1)
+                .required(true)
+                .help("The catalog name or #number# of the animation to tag"))
+            .arg(Arg::with_name("TAG")
+                .index(2)
+                .required(true)
+                .multiple(true)
+                .help("The tags to add to the animation"))
+            .about("Adds tags to an animation in the catalog"))
+        .subcommand(SubCommand::with_name("untag")
+            .arg(Arg::with_name("ANIMATION")
+                .index(1)
+                .required(true)
+                .help("The catalog name or #number# of the animation to remove tags from"))
+            .arg(Arg::with_name("TAG")
+                .index(2)
+                .required(true)
+                .multiple(true)
+                .help("The tags to remove from the animation"))
+            .about("Removes tags from an animation in the catalog"))
+        .subcommand(SubCommand::with_name("search")
+            .arg(Arg::with_name("TEXT")
+                .index(1)
+                .required(true)
+                .help("The text to search for"))
+            .about("Lists the animations in the catalog whose name or tags contain some text"))
         .subcommand(SubCommand::with_name("ls-elements")
             .about("Lists all of the elements in the selected frame"))
         .subcommand(SubCommand::with_name("summarize-edits")
             .about("Reads all of the edits in the input animation and shows a summary of them"))
+        .subcommand(SubCommand::with_name("edit-log-size")
+            .about("Shows the number of edits and the size of the edit log in the input animation"))
+        .subcommand(SubCommand::with_name("compact-edits")
+            .arg(Arg::with_name("retain")
+                .long("retain")
+                .short("r")
+                .takes_value(true)
+                .help("The number of recent edits to keep in the edit log (default 0)"))
+            .about("Replaces the edit log of the input animation with a shorter log that produces the same animation"))
+        .subcommand(SubCommand::with_name("ls-checkpoints")
+            .about("Lists the named checkpoints in the input animation"))
+        .subcommand(SubCommand::with_name("checkpoint")
+            .arg(Arg::with_name("NAME")
+                .index(1)
+                .required(true)
+                .help("The name of the checkpoint to create"))
+            .about("Creates a named checkpoint at the current end of the edit log of the input animation"))
+        .subcommand(SubCommand::with_name("restore-checkpoint")
+            .arg(Arg::with_name("NAME")
+                .index(1)
+                .required(true)
+                .help("The name of the checkpoint to restore"))
+            .about("Restores the input animation to the state it was in at a named checkpoint, keeping the edit history"))
+        .subcommand(SubCommand::with_name("validate")
+            .about("Checks the input animation for problems such as elements that cannot be read or which are attached to missing layers"))
+        .subcommand(SubCommand::with_name("repair")
+            .about("Checks the input animation for problems and fixes them (the input animation is updated unless an output animation is specified)"))
+        .subcommand(SubCommand::with_name("import-layers")
+            .arg(Arg::with_name("SOURCE")
+                .index(1)
+                .required(true)
+                .help("The file containing the animation to import layers from"))
+            .arg(Arg::with_name("LAYER")
+                .index(2)
+                .multiple(true)
+                .help("The IDs of the layers to import (all layers are imported if none are specified)"))
+            .arg(Arg::with_name("retime")
+                .long("retime")
+                .help("Adjusts the timing of the imported layers so their frames line up with the frames of the input animation"))
+            .about("Copies layers from another animation into the input animation (or into the output animation if one is specified)"))
+        .subcommand(SubCommand::with_name("import-svg")
+            .arg(Arg::with_name("SVG")
+                .index(1)
+                .required(true)
+                .help("The SVG file to import"))
+            .arg(Arg::with_name("LAYER")
+                .index(2)
+                .required(true)
+                .help("The ID of the layer to add the shapes to"))
+            .arg(Arg::with_name("FRAME")
+                .index(3)
+                .required(false)
+                .help("The frame to add the shapes to (defaults to the first frame)"))
+            .about("Adds the shapes from an SVG file to a layer of the input animation (or of the output animation if one is specified)"))
+        .subcommand(SubCommand::with_name("export-lottie")
+            .arg(Arg::with_name("FILE")
+                .index(1)
+                .required(true)
+                .help("The Lottie JSON file to write"))
+            .about("Writes the input animation to a Lottie JSON file"))
+        .subcommand(SubCommand::with_name("import-lottie")
+            .arg(Arg::with_name("FILE")
+                .index(1)
+                .required(true)
+                .help("The Lottie JSON file to import"))
+            .about("Adds the shape layers from a Lottie JSON file to the input animation (or to the output animation if one is specified)"))
+        .subcommand(SubCommand::with_name("diff")
+            .arg(Arg::with_name("BEFORE")
+                .index(1)
+                .required(true)
+                .help("The original version of the animation ('input', 'output', '@<edit_number>' for the input animation after that many edits, or a catalog name)"))
+            .arg(Arg::with_name("AFTER")
+                .index(2)
+                .required(false)
+                .help("The updated version of the animation (defaults to the input animation)"))
+            .arg(Arg::with_name("svg")
+                .long("svg")
+                .takes_value(true)
+                .help("Writes an SVG file to the output folder highlighting the elements that changed"))
+            .about("Reports the layers, keyframes and elements that differ between two versions of an animation"))
         .subcommand(SubCommand::with_name("rewrite-edits")
             .about("Reads all of the edits in the input animation and writes them to the output animation"))
         .subcommand(SubCommand::with_name("serialize-edits")
@@ -74,6 +233,19 @@ async fn main() {
             .about("Reads a file (or standard input if no file is specified) containing serialized edits and writes them to the output animation"))
         .subcommand(SubCommand::with_name("dump-all-catalog-edits")
             .about("Writes out the entire catalog as a set of edit logs"))
+        .subcommand(SubCommand::with_name("script")
+            .arg(Arg::with_name("SCRIPT")
+                .help("The script file to run")
+                .required(false)
+                .index(1))
+            .arg(Arg::with_name("var")
+                .long("var")
+                .short("D")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Sets a variable for the script (eg: -D name=value)"))
+            .about("Runs a script containing one command per line from a file (or standard input if no file is specified)"))
         .subcommand(SubCommand::with_name("debug-raycasting")
             .about("Writes out a series of SVG files showing the raycasting used for a particular element")
             .arg(Arg::with_name("ELEMENT")
@@ -90,6 +262,10 @@ async fn main() {
             input.push(FloCommand::Version)
         }
 
+        if params.is_present("continue-on-error") {
+            input.push(FloCommand::ContinueOnError(true));
+        }
+
         // Set the catalog folder if one is specified
         if let Some(catalog_folder) = params.value_of("catalog") {
             input.push(FloCommand::SetCatalogFolder(catalog_folder.to_string()));
@@ -136,6 +312,41 @@ async fn main() {
             input.push(FloCommand::ListAnimations);
         }
 
+        // Catalog management commands
+        if let Some(duplicate) = params.subcommand_matches("duplicate") {
+            input.push(FloCommand::DuplicateAnimation(StorageDescriptor::parse_catalog_string(duplicate.value_of("ANIMATION").unwrap()), duplicate.value_of("NAME").map(|name| name.to_string())));
+        }
+
+        if let Some(rename) = params.subcommand_matches("rename") {
+            input.push(FloCommand::RenameAnimation(StorageDescriptor::parse_catalog_string(rename.value_of("ANIMATION").unwrap()), rename.value_of("NAME").unwrap().to_string()));
+        }
+
+        if let Some(delete) = params.subcommand_matches("delete") {
+            input.push(FloCommand::DeleteAnimation(StorageDescriptor::parse_catalog_string(delete.value_of("ANIMATION").unwrap())));
+        }
+
+        if let Some(export) = params.subcommand_matches("export") {
+            input.push(FloCommand::ExportAnimation(StorageDescriptor::parse_catalog_string(export.value_of("ANIMATION").unwrap()), export.value_of("FILE").unwrap().to_string()));
+        }
+
+        if let Some(import) = params.subcommand_matches("import") {
+            input.push(FloCommand::ImportAnimation(import.value_of("FILE").unwrap().to_string(), import.value_of("NAME").map(|name| name.to_string())));
+        }
+
+        if let Some(tag) = params.subcommand_matches("tag") {
+            let tags = tag.values_of("TAG").into_iter().flatten().map(|tag| tag.to_string()).collect();
+            input.push(FloCommand::TagAnimation(StorageDescriptor::parse_catalog_string(tag.value_of("ANIMATION").unwrap()), tags));
+        }
+
+        if let Some(untag) = params.subcommand_matches("untag") {
+            let tags = untag.values_of("TAG").into_iter().flatten().map(|tag| tag.to_string()).collect();
+            input.push(FloCommand::UntagAnimation(StorageDescriptor::parse_catalog_string(untag.value_of("ANIMATION").unwrap()), tags));
+        }
+
+        if let Some(search) = params.subcommand_matches("search") {
+            input.push(FloCommand::SearchCatalog(search.value_of("TEXT").unwrap().to_string()));
+        }
+
         // Ls-layers command
         if let Some(_ls_params) = params.subcommand_matches("ls-layers") {
             input.push(FloCommand::ListLayers);
@@ -152,6 +363,117 @@ async fn main() {
             input.push(FloCommand::SummarizeEdits);
         }
 
+        // Edit log size command
+        if let Some(_) = params.subcommand_matches("edit-log-size") {
+            input.push(FloCommand::ShowEditLogSize);
+        }
+
+        // Compact edits command
+        if let Some(compact_edits) = params.subcommand_matches("compact-edits") {
+            // Parse the number of edits to retain
+            let retain = compact_edits.value_of("retain").unwrap_or("0");
+            let retain = match usize::from_str(retain) {
+                Ok(retain)  => retain,
+                Err(_)      => {
+                    stderr().write(format!("'{}' is not a valid number of edits to retain\n\n", retain).as_bytes()).await.unwrap();
+                    return;
+                }
+            };
+
+            input.push(FloCommand::ShowEditLogSize);
+            input.push(FloCommand::CompactEditLog(retain));
+        }
+
+        // Checkpoint commands
+        if let Some(_) = params.subcommand_matches("ls-checkpoints") {
+            input.push(FloCommand::ListCheckpoints);
+        }
+
+        if let Some(checkpoint) = params.subcommand_matches("checkpoint") {
+            input.push(FloCommand::AddCheckpoint(checkpoint.value_of("NAME").unwrap().to_string()));
+        }
+
+        if let Some(restore_checkpoint) = params.subcommand_matches("restore-checkpoint") {
+            input.push(FloCommand::RestoreCheckpoint(restore_checkpoint.value_of("NAME").unwrap().to_string()));
+        }
+
+        // Validation commands
+        if let Some(_) = params.subcommand_matches("validate") {
+            input.push(FloCommand::ValidateAnimation);
+        }
+
+        if let Some(_) = params.subcommand_matches("repair") {
+            if !params.is_present("output-to-catalog") {
+                input.push(FloCommand::WriteToReadAnimation);
+            }
+
+            input.push(FloCommand::RepairAnimation);
+        }
+
+        // Import layers command
+        if let Some(import_layers) = params.subcommand_matches("import-layers") {
+            let source      = StorageDescriptor::File(import_layers.value_of("SOURCE").unwrap().to_string());
+            let mut layers  = vec![];
+
+            for layer in import_layers.values_of("LAYER").into_iter().flatten() {
+                match u64::from_str(layer) {
+                    Ok(layer_id)    => { layers.push(layer_id); }
+                    Err(_)          => {
+                        stderr().write(format!("'{}' is not a valid layer ID\n\n", layer).as_bytes()).await.unwrap();
+                        return;
+                    }
+                }
+            }
+
+            if !params.is_present("output-to-catalog") {
+                input.push(FloCommand::WriteToReadAnimation);
+            }
+
+            input.push(FloCommand::ImportLayers(source, layers, import_layers.is_present("retime")));
+        }
+
+        // Import SVG command
+        if let Some(import_svg) = params.subcommand_matches("import-svg") {
+            let svg_file    = import_svg.value_of("SVG").unwrap().to_string();
+            let layer       = import_svg.value_of("LAYER").unwrap();
+            let frame       = import_svg.value_of("FRAME").unwrap_or("0");
+
+            let (layer, frame) = match (u64::from_str(layer), usize::from_str(frame)) {
+                (Ok(layer), Ok(frame))  => (layer, frame),
+                _                       => {
+                    stderr().write(format!("'{} {}' is not a valid layer and frame\n\n", layer, frame).as_bytes()).await.unwrap();
+                    return;
+                }
+            };
+
+            if !params.is_present("output-to-catalog") {
+                input.push(FloCommand::WriteToReadAnimation);
+            }
+
+            input.push(FloCommand::ImportSvg(svg_file, layer, frame));
+        }
+
+        // Lottie commands
+        if let Some(export_lottie) = params.subcommand_matches("export-lottie") {
+            input.push(FloCommand::ExportLottie(export_lottie.value_of("FILE").unwrap().to_string()));
+        }
+
+        if let Some(import_lottie) = params.subcommand_matches("import-lottie") {
+            if !params.is_present("output-to-catalog") {
+                input.push(FloCommand::WriteToReadAnimation);
+            }
+
+            input.push(FloCommand::ImportLottie(import_lottie.value_of("FILE").unwrap().to_string()));
+        }
+
+        // Diff command
+        if let Some(diff) = params.subcommand_matches("diff") {
+            let before  = AnimationVersion::parse_version_string(diff.value_of("BEFORE").unwrap());
+            let after   = AnimationVersion::parse_version_string(diff.value_of("AFTER").unwrap_or("input"));
+
+            input.push(FloCommand::DiffAnimations(before, after, diff.value_of("svg").map(|svg| svg.to_string())));
+        }
+
         // Write edits command
         if let Some(_) = params.subcommand_matches("rewrite-edits") {
             input.push(FloCommand::ReadAllEdits);
@@ -209,14 +531,65 @@ async fn main() {
             input.push(FloCommand::RayCastToSvg(element_id));
         }
         
+        // Script command
+        let script = if let Some(script) = params.subcommand_matches("script") {
+            // Set up the variables that were passed in on the command line
+            let mut parser = ScriptParser::new();
+
+            if let Some(input) = params.value_of("input-from-catalog") {
+                parser.set_input(StorageDescriptor::parse_catalog_string(input));
+            }
+            if let Some(input) = params.value_of("input-from-file") {
+                parser.set_input(StorageDescriptor::File(input.to_string()));
+            }
+            if let Some(output) = params.value_of("output-to-catalog") {
+                parser.set_variable("output", output);
+            }
+            if let Some(frame) = params.value_of("frame") {
+                parser.set_variable("frame", frame);
+            }
+
+            for variable in script.values_of("var").into_iter().flatten() {
+                match variable.find('=') {
+                    Some(sep_pos)   => parser.set_variable(&variable[0..sep_pos], &variable[sep_pos+1..]),
+                    None            => {
+                        stderr().write(format!("'{}' is not a valid variable. Variables must be of the format <name>=<value>\n\n", variable).as_bytes()).await.unwrap();
+                        return;
+                    }
+                }
+            }
+
+            // Read the script a line at a time, so commands from standard input are run as they're typed
+            let reader: Box<dyn AsyncBufRead+Send+Unpin> = if let Some(script_file) = script.value_of("SCRIPT") {
+                match fs::File::open(script_file).await {
+                    Ok(file)    => Box::new(BufReader::new(file)),
+                    Err(err)    => {
+                        stderr().write(format!("Could not open '{}': {}\n\n", script_file, err).as_bytes()).await.unwrap();
+                        return;
+                    }
+                }
+            } else {
+                Box::new(BufReader::new(stdin()))
+            };
+
+            let lines = stream::unfold(reader.lines(), |mut lines| async move {
+                lines.next_line().await.ok().flatten().map(move |line| (line, lines))
+            }).boxed();
+
+            flo_script_commands(lines, parser).boxed()
+        } else {
+            stream::empty().boxed()
+        };
+
         // Prepare as a stream as input to the command line
-        let input       = stream::iter(input);
+        let input       = stream::iter(input).chain(script);
 
         // Basic loop with a character output
         let mut stderr  = stderr();
 
         // Write the output to the stream
-        run_console(flo_run_commands(input)).await;
+        let format      = if params.is_present("json") { ConsoleFormat::Json } else { ConsoleFormat::Text };
+        run_console(flo_run_commands(input), format).await;
 
         // Always finish with a newline
         stderr.write(&[10u8]).await.unwrap();
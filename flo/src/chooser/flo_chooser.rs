@@ -30,8 +30,9 @@ where Loader::NewAnimation: 'static+EditableAnimation {
         // Create the file manager (we use a single default user by default)
         let file_manager = Arc::new(SqliteFileManager::new(APP_NAME, DEFAULT_USER_FOLDER));
 
-        // Create the file store
-        let file_store = Arc::new(OpenFileStore::new(loader));
+        // Create the file store (the file manager is used to find the animations that can be imported into the one being edited)
+        let loader      = FloLoader { loader: loader, file_manager: Arc::clone(&file_manager) as Arc<dyn FileManager> };
+        let file_store  = Arc::new(OpenFileStore::new(Arc::new(loader)));
 
         // Put everything together
         FloChooser {
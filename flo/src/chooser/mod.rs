@@ -2,3 +2,4 @@ mod flo_chooser;
 mod consts;
 
 pub use self::flo_chooser::*;
+pub use self::consts::*;
@@ -187,7 +187,7 @@ impl HsluvPickerController {
                     .with((ActionTrigger::EditValue, "SetLum"))
                     .with((ActionTrigger::SetValue, "SetLum")),
                 Control::label()
-                    .with("Luminance")
+                    .with(localized("Luminance"))
                     .with(TextAlign::Center)
                     .with(Font::Size(12.0))
                     .with(Bounds::next_vert(16.0))
@@ -202,7 +202,7 @@ impl HsluvPickerController {
                     .with((ActionTrigger::EditValue, "SetSat"))
                     .with((ActionTrigger::SetValue, "SetSat")),
                 Control::label()
-                    .with("Saturation")
+                    .with(localized("Saturation"))
                     .with(TextAlign::Center)
                     .with(Font::Size(12.0))
                     .with(Bounds::next_vert(16.0))
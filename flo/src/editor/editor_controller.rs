@@ -3,8 +3,11 @@ use super::canvas_controller::*;
 use super::toolbox_controller::*;
 use super::timeline_controller::*;
 use super::controlbar_controller::*;
+use super::history_controller::*;
+use super::editor_panels::*;
 use super::super::model::*;
 use super::super::style::*;
+use super::super::MESSAGES;
 
 use flo_ui::*;
 use flo_ui_files::ui::*;
@@ -23,7 +26,10 @@ enum SubController {
     Menu,
     ControlBar,
     Timeline,
-    Toolbox
+    Toolbox,
+    History,
+    ImportLayers,
+    ImportSvg
 }
 
 ///
@@ -55,11 +61,22 @@ where Loader::NewAnimation: 'static+EditableAnimation {
     /// Creates a new editor controller from a model
     ///
     pub fn from_model(animation: FloModel<Loader::NewAnimation>) -> EditorController<Loader> {
+        // Make sure the translations for the editor are available
+        MESSAGES.load();
+
+        let panels      = EditorPanels::new();
+
         let canvas      = Arc::new(CanvasController::new(&animation));
-        let menu        = Arc::new(MenuController::new(&animation));
+        let menu        = Arc::new(MenuController::new(&animation, &panels));
         let timeline    = Arc::new(TimelineController::new(&animation));
         let toolbox     = Arc::new(ToolboxController::new(&animation));
         let control_bar = Arc::new(ControlBarController::new(&animation));
+        let history     = DockPanelController::new(HistoryController::new(&animation), computed(|| localized("History")), &panels.history);
+        let history     = Arc::new(history.with_size(&(320, 300)));
+        let import      = DockPanelController::new(ImportLayersController::new(&animation), computed(|| localized("Import layers")), &panels.import_layers);
+        let import      = Arc::new(import.with_size(&(280, 300)));
+        let import_svg  = DockPanelController::new(ImportSvgController::new(&animation), computed(|| localized("Import SVG")), &panels.import_svg);
+        let import_svg  = Arc::new(import_svg.with_size(&(280, 100)));
 
         let ui          = bind(Self::ui());
         let mut subcontrollers: HashMap<SubController, Arc<dyn Controller>> = HashMap::new();
@@ -69,6 +86,9 @@ where Loader::NewAnimation: 'static+EditableAnimation {
         subcontrollers.insert(SubController::Timeline,      timeline);
         subcontrollers.insert(SubController::Toolbox,       toolbox);
         subcontrollers.insert(SubController::ControlBar,    control_bar);
+        subcontrollers.insert(SubController::History,       history);
+        subcontrollers.insert(SubController::ImportLayers,  import);
+        subcontrollers.insert(SubController::ImportSvg,     import_svg);
 
         EditorController {
             anim:           PhantomData,
@@ -153,6 +173,36 @@ where Loader::NewAnimation: 'static+EditableAnimation {
             .with_controller(&serde_json::to_string(&SubController::ControlBar).unwrap())
     }
 
+    ///
+    /// Creates the container for the history panel
+    ///
+    pub fn history_panel() -> Control {
+        Control::container()
+            .with(DockEdge::Right)
+            .with(LayoutMode::Column)
+            .with_controller(&serde_json::to_string(&SubController::History).unwrap())
+    }
+
+    ///
+    /// Creates the container for the import layers panel
+    ///
+    pub fn import_layers_panel() -> Control {
+        Control::container()
+            .with(DockEdge::Right)
+            .with(LayoutMode::Column)
+            .with_controller(&serde_json::to_string(&SubController::ImportLayers).unwrap())
+    }
+
+    ///
+    /// Creates the container for the import SVG panel
+    ///
+    pub fn import_svg_panel() -> Control {
+        Control::container()
+            .with(DockEdge::Right)
+            .with(LayoutMode::Column)
+            .with_controller(&serde_json::to_string(&SubController::ImportSvg).unwrap())
+    }
+
     ///
     /// Creates the UI tree for this controller
     ///
@@ -164,14 +214,24 @@ where Loader::NewAnimation: 'static+EditableAnimation {
         let toolbar     = Self::toolbox();
         let canvas      = Self::canvas();
         let control_bar = Self::control_bar();
+        let history     = Self::history_panel();
+        let import      = Self::import_layers_panel();
+        let import_svg  = Self::import_svg_panel();
 
         Control::container()
             .with(Bounds::fill_all())
             .with(vec![
                 menu_bar,
                 Control::container()
-                    .with((vec![toolbar, canvas],
-                        Bounds { x1: Start, y1: After, x2: End, y2: Stretch(1.0) })),
+                    .with(Bounds { x1: Start, y1: After, x2: End, y2: Stretch(1.0) })
+                    .with(LayoutMode::Dock)
+                    .with(vec![
+                        history,
+                        import,
+                        import_svg,
+                        Control::container()
+                            .with(vec![toolbar, canvas])
+                    ]),
                 Control::empty()
                     .with(Bounds::next_vert(1.0))
                     .with(Appearance::Background(TIMESCALE_BORDER)),
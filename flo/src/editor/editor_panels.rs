@@ -0,0 +1,40 @@
+use flo_ui::*;
+use flo_binding::*;
+
+///
+/// Where the dockable panels of the editor are displayed
+///
+#[derive(Clone)]
+pub struct EditorPanels {
+    /// The panel that lists the checkpoints in the history of the animation
+    pub history: Binding<DockPosition>,
+
+    /// The panel used to import layers from other animations
+    pub import_layers: Binding<DockPosition>,
+
+    /// The panel used to import SVG files into the selected layer
+    pub import_svg: Binding<DockPosition>
+}
+
+impl EditorPanels {
+    ///
+    /// Creates a new set of panels, all of which are hidden
+    ///
+    pub fn new() -> EditorPanels {
+        EditorPanels {
+            history:        bind(DockPosition::Hidden),
+            import_layers:  bind(DockPosition::Hidden),
+            import_svg:     bind(DockPosition::Hidden)
+        }
+    }
+
+    ///
+    /// Shows a panel if it's hidden, or hides it if it's displayed
+    ///
+    pub fn toggle(panel: &Binding<DockPosition>) {
+        match panel.get() {
+            DockPosition::Hidden    => panel.set(DockPosition::Docked),
+            _                       => panel.set(DockPosition::Hidden)
+        }
+    }
+}
@@ -102,7 +102,14 @@ impl<Anim: 'static+Animation+EditableAnimation> FrameControlsController<Anim> {
                     let frame       = micros / duration;
                     let frame       = (frame % fps)+1;
 
-                    format!("T+{}:{:02}.{:02}", minutes, seconds, frame)
+                    // Numbers are written using the conventions of the current locale
+                    let numbers     = localization().number_format();
+
+                    localized_format("T+{minutes}:{seconds}.{frame}", &[
+                        ("minutes", numbers.format_integer(minutes as i64, 1)),
+                        ("seconds", numbers.format_integer(seconds as i64, 2)),
+                        ("frame",   numbers.format_integer(frame as i64, 2))
+                    ])
                 }
 
                 FrameDisplayStyle::FrameNumber => {
@@ -112,7 +119,9 @@ impl<Anim: 'static+Animation+EditableAnimation> FrameControlsController<Anim> {
 
                     let frame       = micros/duration + 1;
 
-                    format!("F {}", frame)
+                    let numbers     = localization().number_format();
+
+                    localized_format("F {frame}", &[("frame", numbers.format_integer(frame as i64, 1))])
                 }
             }
         }))
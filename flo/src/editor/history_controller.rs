@@ -0,0 +1,164 @@
+use super::super::model::*;
+use super::super::style::*;
+
+use flo_ui::*;
+use flo_binding::*;
+use flo_animation::*;
+
+///
+/// The history controller lists the checkpoints in the edit log of an animation, and can create new checkpoints or restore old ones
+///
+pub struct HistoryController<Anim: 'static+Animation+EditableAnimation> {
+    /// The UI for this controller
+    ui: BindRef<Control>,
+
+    /// The model for the animation
+    model: FloModel<Anim>,
+
+    /// The name of the checkpoint to create
+    checkpoint_name: Binding<String>
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> HistoryController<Anim> {
+    ///
+    /// Creates a new history controller
+    ///
+    pub fn new(model: &FloModel<Anim>) -> HistoryController<Anim> {
+        let checkpoint_name = bind(String::new());
+        let ui              = Self::ui(model.checkpoints(), BindRef::from(checkpoint_name.clone()));
+
+        HistoryController {
+            ui:                 ui,
+            model:              model.clone(),
+            checkpoint_name:    checkpoint_name
+        }
+    }
+
+    ///
+    /// Creates the row displaying a single checkpoint
+    ///
+    fn checkpoint_row(index: usize, checkpoint: &AnimationCheckpoint) -> Control {
+        let num_edits = localization().number_format().format_integer(checkpoint.edit_index as i64, 0);
+
+        Control::container()
+            .with(Bounds::next_vert(22.0))
+            .with(ControlAttribute::Padding((4, 1), (4, 1)))
+            .with(vec![
+                Control::label()
+                    .with(checkpoint.name.clone())
+                    .with(Bounds::stretch_horiz(1.0)),
+                Control::label()
+                    .with(localized_format("{count} edits", &[("count", num_edits)]))
+                    .with(Appearance::Foreground(TIMESCALE_MAINTICK))
+                    .with(Bounds::next_horiz(70.0)),
+                Control::button()
+                    .with(localized("Restore"))
+                    .with((ActionTrigger::Click, format!("RestoreCheckpoint-{}", index)))
+                    .with(Bounds::next_horiz(60.0)),
+                Control::empty()
+                    .with(Bounds::next_horiz(2.0)),
+                Control::button()
+                    .with(localized("Remove"))
+                    .with((ActionTrigger::Click, format!("RemoveCheckpoint-{}", index)))
+                    .with(Bounds::next_horiz(60.0))
+            ])
+    }
+
+    ///
+    /// Creates the UI binding for this controller
+    ///
+    fn ui(checkpoints: BindRef<Vec<AnimationCheckpoint>>, checkpoint_name: BindRef<String>) -> BindRef<Control> {
+        BindRef::from(computed(move || {
+            let checkpoints     = checkpoints.get();
+            let checkpoint_name = checkpoint_name.get();
+
+            // The new checkpoint controls are followed by the existing checkpoints
+            let mut controls = vec![
+                Control::container()
+                    .with(Bounds::next_vert(24.0))
+                    .with(ControlAttribute::Padding((4, 2), (4, 2)))
+                    .with(vec![
+                        Control::text_box()
+                            .with(checkpoint_name.clone())
+                            .with(Bounds::stretch_horiz(1.0))
+                            .with((ActionTrigger::EditValue, "SetCheckpointName"))
+                            .with((ActionTrigger::SetValue, "AddCheckpoint")),
+                        Control::empty()
+                            .with(Bounds::next_horiz(2.0)),
+                        Control::button()
+                            .with(localized("Add checkpoint"))
+                            .with(State::Enabled(Property::Bool(!checkpoint_name.trim().is_empty())))
+                            .with((ActionTrigger::Click, "AddCheckpoint"))
+                            .with(Bounds::next_horiz(100.0))
+                    ]),
+                Control::empty()
+                    .with(Appearance::Background(TIMESCALE_BORDER))
+                    .with(Bounds::next_vert(1.0))
+            ];
+
+            if checkpoints.len() == 0 {
+                controls.push(Control::label()
+                    .with(localized("No checkpoints"))
+                    .with(TextAlign::Center)
+                    .with(Bounds::next_vert(22.0)));
+            } else {
+                controls.extend(checkpoints.iter()
+                    .enumerate()
+                    .map(|(index, checkpoint)| Self::checkpoint_row(index, checkpoint)));
+            }
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(Font::Size(11.0))
+                .with(controls)
+        }))
+    }
+
+    ///
+    /// Creates a checkpoint at the current end of the edit log
+    ///
+    fn add_checkpoint(&self, name: &str) {
+        let name = name.trim();
+
+        if name.len() > 0 {
+            self.model.set_checkpoint(name);
+            self.checkpoint_name.set(String::new());
+        }
+    }
+
+    ///
+    /// Retrieves the checkpoint displayed at the specified index
+    ///
+    fn checkpoint_at_index(&self, index: &str) -> Option<AnimationCheckpoint> {
+        let index = usize::from_str_radix(index, 10).ok()?;
+
+        self.model.checkpoints().get().into_iter().nth(index)
+    }
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> Controller for HistoryController<Anim> {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        match (action_id, action_parameter) {
+            ("SetCheckpointName", ActionParameter::Value(PropertyValue::String(name))) => self.checkpoint_name.set(name.clone()),
+            ("AddCheckpoint", ActionParameter::Value(PropertyValue::String(name)))     => self.add_checkpoint(name),
+            ("AddCheckpoint", _)                                                        => self.add_checkpoint(&self.checkpoint_name.get()),
+
+            _ => {
+                // 'RestoreCheckpoint-x' restores checkpoint 'x', 'RemoveCheckpoint-x' removes it
+                if action_id.starts_with("RestoreCheckpoint-") {
+                    if let Some(checkpoint) = self.checkpoint_at_index(&action_id["RestoreCheckpoint-".len()..]) {
+                        self.model.restore_checkpoint(&checkpoint.name);
+                    }
+                } else if action_id.starts_with("RemoveCheckpoint-") {
+                    if let Some(checkpoint) = self.checkpoint_at_index(&action_id["RemoveCheckpoint-".len()..]) {
+                        self.model.remove_checkpoint(&checkpoint.name);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use super::super::model::*;
+use super::super::style::*;
+
+use flo_ui::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use std::sync::*;
+use std::path::{PathBuf};
+
+///
+/// The import layers controller lets the user choose another animation and copy some of its layers into the animation being edited
+///
+pub struct ImportLayersController<Anim: 'static+Animation+EditableAnimation> {
+    /// The UI for this controller
+    ui: BindRef<Control>,
+
+    /// The model for the animation
+    model: FloModel<Anim>,
+
+    /// The animations that layers can be imported from
+    animations: Binding<Vec<(PathBuf, String)>>,
+
+    /// The animation that the user has chosen to import from
+    source_path: Binding<Option<PathBuf>>,
+
+    /// The animation that the layers are being imported from
+    source: Mutex<Option<Arc<dyn Animation>>>,
+
+    /// The IDs and names of the layers in the source animation
+    source_layers: Binding<Vec<(u64, String)>>,
+
+    /// The layers that the user has chosen to import
+    selected_layers: Binding<Vec<u64>>,
+
+    /// True if the keyframes of the imported layers should be adjusted to match the frames of this animation
+    retime: Binding<bool>,
+
+    /// Set to a description of the problem if the chosen animation could not be read
+    error: Binding<Option<String>>
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> ImportLayersController<Anim> {
+    ///
+    /// Creates a new import layers controller
+    ///
+    pub fn new(model: &FloModel<Anim>) -> ImportLayersController<Anim> {
+        let animations      = bind(model.library().map(|library| library.animations()).unwrap_or(vec![]));
+        let source_path     = bind(None);
+        let source_layers   = bind(vec![]);
+        let selected_layers = bind(vec![]);
+        let retime          = bind(true);
+        let error           = bind(None);
+
+        let ui              = Self::ui(BindRef::from(animations.clone()), BindRef::from(source_path.clone()), BindRef::from(source_layers.clone()),
+            BindRef::from(selected_layers.clone()), BindRef::from(retime.clone()), BindRef::from(error.clone()));
+
+        ImportLayersController {
+            ui:                 ui,
+            model:              model.clone(),
+            animations:         animations,
+            source_path:        source_path,
+            source:             Mutex::new(None),
+            source_layers:      source_layers,
+            selected_layers:    selected_layers,
+            retime:             retime,
+            error:              error
+        }
+    }
+
+    ///
+    /// Creates a row with a checkbox and a label
+    ///
+    fn check_row(label: String, selected: bool, action: String) -> Control {
+        Control::container()
+            .with(Bounds::next_vert(20.0))
+            .with(ControlAttribute::Padding((4, 1), (4, 1)))
+            .with(vec![
+                Control::check_box()
+                    .with(State::Value(Property::Bool(selected)))
+                    .with((ActionTrigger::SetValue, action))
+                    .with(Bounds::next_horiz(18.0)),
+                Control::empty()
+                    .with(Bounds::next_horiz(4.0)),
+                Control::label()
+                    .with(label)
+                    .with(Bounds::stretch_horiz(1.0))
+            ])
+    }
+
+    ///
+    /// Creates the UI binding for this controller
+    ///
+    fn ui(animations: BindRef<Vec<(PathBuf, String)>>, source_path: BindRef<Option<PathBuf>>, source_layers: BindRef<Vec<(u64, String)>>,
+        selected_layers: BindRef<Vec<u64>>, retime: BindRef<bool>, error: BindRef<Option<String>>) -> BindRef<Control> {
+        BindRef::from(computed(move || {
+            let animations      = animations.get();
+            let source_path     = source_path.get();
+            let source_layers   = source_layers.get();
+            let selected_layers = selected_layers.get();
+            let retime          = retime.get();
+            let error           = error.get();
+
+            // The list of animations comes first
+            let mut controls = vec![];
+
+            if animations.len() == 0 {
+                controls.push(Control::label()
+                    .with(localized("No other animations"))
+                    .with(TextAlign::Center)
+                    .with(Bounds::next_vert(22.0)));
+            } else {
+                controls.extend(animations.iter()
+                    .enumerate()
+                    .map(|(index, (path, name))| {
+                        Control::button()
+                            .with(name.clone())
+                            .with(State::Selected(Property::Bool(source_path.as_ref() == Some(path))))
+                            .with((ActionTrigger::Click, format!("ChooseAnimation-{}", index)))
+                            .with(ControlAttribute::Padding((4, 1), (4, 1)))
+                            .with(Bounds::next_vert(22.0))
+                    }));
+            }
+
+            controls.push(Control::empty()
+                .with(Appearance::Background(TIMESCALE_BORDER))
+                .with(Bounds::next_vert(1.0)));
+
+            // Followed by the layers in the chosen animation
+            if let Some(error) = error {
+                controls.push(Control::label()
+                    .with(error)
+                    .with(TextAlign::Center)
+                    .with(Bounds::next_vert(22.0)));
+            } else if source_path.is_some() {
+                if source_layers.len() == 0 {
+                    controls.push(Control::label()
+                        .with(localized("No layers"))
+                        .with(TextAlign::Center)
+                        .with(Bounds::next_vert(22.0)));
+                }
+
+                controls.extend(source_layers.iter()
+                    .enumerate()
+                    .map(|(index, (layer_id, name))| Self::check_row(name.clone(), selected_layers.contains(layer_id), format!("SelectLayer-{}", index))));
+            }
+
+            // Finally, the import options
+            controls.push(Control::empty()
+                .with(Appearance::Background(TIMESCALE_BORDER))
+                .with(Bounds::next_vert(1.0)));
+            controls.push(Self::check_row(localized("Match frame timing"), retime, "SetRetime".to_string()));
+            controls.push(Control::container()
+                .with(Bounds::next_vert(24.0))
+                .with(ControlAttribute::Padding((4, 2), (4, 2)))
+                .with(vec![
+                    Control::empty()
+                        .with(Bounds::stretch_horiz(1.0)),
+                    Control::button()
+                        .with(localized("Import layers"))
+                        .with(State::Enabled(Property::Bool(selected_layers.len() > 0)))
+                        .with((ActionTrigger::Click, "ImportLayers"))
+                        .with(Bounds::next_horiz(100.0))
+                ]));
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(Font::Size(11.0))
+                .with(controls)
+        }))
+    }
+
+    ///
+    /// Opens the animation at the specified index in the list of animations and lists its layers
+    ///
+    fn choose_animation(&self, index: &str) {
+        let index   = match usize::from_str_radix(index, 10) { Ok(index) => index, Err(_) => { return; } };
+        let path    = match self.animations.get().into_iter().nth(index) { Some((path, _name)) => path, None => { return; } };
+        let library = match self.model.library() { Some(library) => library, None => { return; } };
+
+        // Open the animation and read the names of its layers
+        let source  = library.open_animation(path.as_path());
+        let layers  = source.as_ref()
+            .map(|source| source.get_layer_ids()
+                .into_iter()
+                .filter_map(|layer_id| source.get_layer_with_id(layer_id))
+                .map(|layer| (layer.id(), layer.name().unwrap_or_else(|| format!("Layer {}", layer.id()))))
+                .collect())
+            .unwrap_or(vec![]);
+
+        match &source {
+            Ok(_)   => self.error.set(None),
+            Err(_)  => self.error.set(Some(localized("This animation could not be read")))
+        }
+
+        *self.source.lock().unwrap() = source.ok();
+        self.source_path.set(Some(path));
+        self.source_layers.set(layers);
+        self.selected_layers.set(vec![]);
+    }
+
+    ///
+    /// Adds or removes the layer at the specified index in the source animation from the layers to import
+    ///
+    fn select_layer(&self, index: &str, selected: bool) {
+        let index       = match usize::from_str_radix(index, 10) { Ok(index) => index, Err(_) => { return; } };
+        let layer_id    = match self.source_layers.get().into_iter().nth(index) { Some((layer_id, _name)) => layer_id, None => { return; } };
+
+        let mut selected_layers = self.selected_layers.get();
+        selected_layers.retain(|selected_id| *selected_id != layer_id);
+        if selected { selected_layers.push(layer_id); }
+
+        self.selected_layers.set(selected_layers);
+    }
+
+    ///
+    /// Imports the selected layers into the animation
+    ///
+    fn import_layers(&self) {
+        let source = self.source.lock().unwrap().clone();
+
+        if let Some(source) = source {
+            // Import the layers in the order they appear in the source animation
+            let selected_layers = self.selected_layers.get();
+            let layer_ids       = self.source_layers.get()
+                .into_iter()
+                .map(|(layer_id, _name)| layer_id)
+                .filter(|layer_id| selected_layers.contains(layer_id))
+                .collect::<Vec<_>>();
+
+            self.model.import_layers(&*source, &layer_ids, self.retime.get());
+            self.selected_layers.set(vec![]);
+        }
+    }
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> Controller for ImportLayersController<Anim> {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        match (action_id, action_parameter) {
+            ("SetRetime", ActionParameter::Value(PropertyValue::Bool(retime)))  => self.retime.set(*retime),
+            ("ImportLayers", _)                                                 => self.import_layers(),
+
+            _ => {
+                // 'ChooseAnimation-x' opens animation 'x', 'SelectLayer-x' changes whether or not layer 'x' is imported
+                if action_id.starts_with("ChooseAnimation-") {
+                    self.choose_animation(&action_id["ChooseAnimation-".len()..]);
+                } else if action_id.starts_with("SelectLayer-") {
+                    if let ActionParameter::Value(PropertyValue::Bool(selected)) = action_parameter {
+                        self.select_layer(&action_id["SelectLayer-".len()..], *selected);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use super::super::model::*;
+use super::super::style::*;
+
+use flo_ui::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use std::fs;
+
+///
+/// The import SVG controller reads an SVG file from disk and adds its contents to the selected layer at the current time
+///
+pub struct ImportSvgController<Anim: 'static+Animation+EditableAnimation> {
+    /// The UI for this controller
+    ui: BindRef<Control>,
+
+    /// The model for the animation
+    model: FloModel<Anim>,
+
+    /// The path of the file to import
+    svg_path: Binding<String>,
+
+    /// The result of the last import (a description of the problem if it failed)
+    status: Binding<Option<String>>
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> ImportSvgController<Anim> {
+    ///
+    /// Creates a new import SVG controller
+    ///
+    pub fn new(model: &FloModel<Anim>) -> ImportSvgController<Anim> {
+        let svg_path    = bind(String::new());
+        let status      = bind(None);
+        let has_layer   = model.timeline().selected_layer.clone();
+        let has_layer   = BindRef::from(computed(move || has_layer.get().is_some()));
+        let ui          = Self::ui(BindRef::from(svg_path.clone()), BindRef::from(status.clone()), has_layer);
+
+        ImportSvgController {
+            ui:         ui,
+            model:      model.clone(),
+            svg_path:   svg_path,
+            status:     status
+        }
+    }
+
+    ///
+    /// Creates the UI binding for this controller
+    ///
+    fn ui(svg_path: BindRef<String>, status: BindRef<Option<String>>, has_layer: BindRef<bool>) -> BindRef<Control> {
+        BindRef::from(computed(move || {
+            let svg_path    = svg_path.get();
+            let status      = status.get();
+            let has_layer   = has_layer.get();
+
+            let mut controls = vec![
+                Control::label()
+                    .with(localized("SVG file"))
+                    .with(ControlAttribute::Padding((4, 2), (4, 0)))
+                    .with(Bounds::next_vert(18.0)),
+                Control::container()
+                    .with(Bounds::next_vert(24.0))
+                    .with(ControlAttribute::Padding((4, 2), (4, 2)))
+                    .with(vec![
+                        Control::text_box()
+                            .with(svg_path.clone())
+                            .with(Bounds::stretch_horiz(1.0))
+                            .with((ActionTrigger::EditValue, "SetSvgPath"))
+                            .with((ActionTrigger::SetValue, "ImportSvg")),
+                        Control::empty()
+                            .with(Bounds::next_horiz(2.0)),
+                        Control::button()
+                            .with(localized("Import"))
+                            .with(State::Enabled(Property::Bool(has_layer && !svg_path.trim().is_empty())))
+                            .with((ActionTrigger::Click, "ImportSvg"))
+                            .with(Bounds::next_horiz(60.0))
+                    ])
+            ];
+
+            if let Some(status) = status {
+                controls.push(Control::label()
+                    .with(status)
+                    .with(Appearance::Foreground(TIMESCALE_MAINTICK))
+                    .with(ControlAttribute::Padding((4, 0), (4, 0)))
+                    .with(Bounds::next_vert(18.0)));
+            } else if !has_layer {
+                controls.push(Control::label()
+                    .with(localized("Select a layer to import into"))
+                    .with(Appearance::Foreground(TIMESCALE_MAINTICK))
+                    .with(ControlAttribute::Padding((4, 0), (4, 0)))
+                    .with(Bounds::next_vert(18.0)));
+            }
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(Font::Size(11.0))
+                .with(controls)
+        }))
+    }
+
+    ///
+    /// Imports the SVG file at the specified path into the selected layer
+    ///
+    fn import_svg(&self, path: &str) {
+        let path = path.trim();
+        if path.len() == 0 { return; }
+
+        let svg = match fs::read_to_string(path) {
+            Ok(svg)     => svg,
+            Err(err)    => { self.status.set(Some(err.to_string())); return; }
+        };
+
+        match self.model.import_svg(&svg) {
+            Ok(Some(_element_id))   => { self.status.set(None); self.svg_path.set(String::new()); }
+            Ok(None)                => { self.status.set(Some(localized("Select a layer to import into"))); }
+            Err(err)                => { self.status.set(Some(err.to_string())); }
+        }
+    }
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> Controller for ImportSvgController<Anim> {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        match (action_id, action_parameter) {
+            ("SetSvgPath", ActionParameter::Value(PropertyValue::String(path)))    => self.svg_path.set(path.clone()),
+            ("ImportSvg", ActionParameter::Value(PropertyValue::String(path)))     => self.import_svg(path),
+            ("ImportSvg", _)                                                        => self.import_svg(&self.svg_path.get()),
+
+            _ => { }
+        }
+    }
+}
@@ -42,7 +42,11 @@ pub struct KeyFrameControlsController<Anim: 'static+Animation+EditableAnimation>
     /// The edit sink for the animation
     edit_sink: Desync<Publisher<Arc<Vec<AnimationEdit>>>>,
 
-    debug_model: FloModel<Anim>
+    /// True if the onion skin settings are stored in the animation rather than the user's preferences
+    onion_skins_saved_with_animation: Binding<bool>,
+
+    /// The model for the animation being edited
+    model: FloModel<Anim>
 }
 
 impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim> {
@@ -62,9 +66,12 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
         let keyframe_selected       = frame.keyframe_selected.clone();
         let prev_next_1             = frame.previous_and_next_keyframe.clone();
         let prev_next_2             = frame.previous_and_next_keyframe.clone();
+        let saved_with_animation    = bind(model.onion_skin_preferences_overridden());
+        let saved_with_animation_2  = saved_with_animation.clone();
 
         view_model.set_computed("CreateKeyFrameOnDrawSelected", move || PropertyValue::Bool(create_keyframe_on_draw.get()));
         view_model.set_computed("ShowOnionSkinsSelected",       move || PropertyValue::Bool(show_onion_skins.get()));
+        view_model.set_computed("OnionSkinsSavedWithAnimation", move || PropertyValue::Bool(saved_with_animation_2.get()));
         view_model.set_computed("CanCreateKeyFrame",            move || PropertyValue::Bool(selected_layer.get().is_some() && !keyframe_selected.get()));
         view_model.set_computed("CanMoveToPreviousKeyFrame",    move || PropertyValue::Bool(prev_next_1.get().0.is_some()));
         view_model.set_computed("CanMoveToNextKeyFrame",        move || PropertyValue::Bool(prev_next_2.get().1.is_some()));
@@ -86,7 +93,9 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
             current_time:   timeline.current_time.clone(),
             selected_layer: timeline.selected_layer.clone(),
             edit_sink:      Desync::new(edit_sink),
-            debug_model:    model.clone()
+
+            onion_skins_saved_with_animation:   saved_with_animation,
+            model:                              model.clone()
         }
     }
 
@@ -119,7 +128,7 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
                     Control::empty()
                         .with(Bounds::next_horiz(3.0)),
                     Control::label()
-                        .with("Keyframes:")
+                        .with(localized("Keyframes:"))
                         .with(TextAlign::Right)
                         .with(Bounds::stretch_horiz(1.0)),
                     Control::empty()
@@ -147,6 +156,12 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
                                 .with(State::Selected(Property::bound("ShowOnionSkinsSelected")))
                                 .with(State::Enabled(Property::Bool(true)))
                                 .with((ActionTrigger::Click, "ToggleShowOnionSkins"))
+                                .with(Menu::Context(vec![
+                                    MenuCommand::new("SaveOnionSkinsWithAnimation", &localized("Save onion skin settings with this animation"))
+                                        .with_checked(Property::bound("OnionSkinsSavedWithAnimation"))
+                                        .into()
+                                ]))
+                                .with((ActionTrigger::SelectMenuItem, "OnionSkinMenu"))
                                 .with(Bounds::next_horiz(22.0)),
 
                             Control::button()
@@ -209,7 +224,7 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for KeyFrameControlsC
         Some(self.view_model.clone())
     }
 
-    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
         match action_id {
             "ToggleCreateKeyFrameOnDraw" => {
                 let current_value = self.frame.create_keyframe_on_draw.get();
@@ -225,13 +240,25 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for KeyFrameControlsC
 
                 // Debugging: remove the cache for the current frame if there is one
                 if let Some(selected_layer) = selected_layer {
-                    self.debug_model.get_layer_with_id(selected_layer)
+                    self.model.get_layer_with_id(selected_layer)
                         .map(|layer| layer
                             .get_canvas_cache_at_time(current_time)
                             .invalidate(CacheType::OnionSkinLayer));
                 }
             },
 
+            "OnionSkinMenu" => {
+                if let ActionParameter::MenuItem(command_id) = action_parameter {
+                    if command_id == "SaveOnionSkinsWithAnimation" {
+                        // Move the onion skin settings between the animation and the user's preferences
+                        let saved_with_animation = !self.onion_skins_saved_with_animation.get();
+
+                        self.model.override_onion_skin_preferences(saved_with_animation);
+                        self.onion_skins_saved_with_animation.set(saved_with_animation);
+                    }
+                }
+            },
+
             "MoveToPreviousKeyFrame" => {
                 let previous_frame = self.frame.previous_and_next_keyframe.get().0;
                 if let Some(previous_frame) = previous_frame {
@@ -1,7 +1,9 @@
+use super::editor_panels::*;
 use super::super::menu::*;
 use super::super::style::*;
 use super::super::model::*;
 use super::super::tools::*;
+use super::super::preferences::*;
 
 use flo_ui::*;
 use flo_binding::*;
@@ -15,6 +17,7 @@ use std::collections::HashMap;
 ///
 pub struct MenuController<Anim: Animation> {
     anim_model:         Arc<FloModel<Anim>>,
+    panels:             EditorPanels,
     ui:                 BindRef<Control>,
     tool_controllers:   Mutex<HashMap<String, Arc<dyn Controller>>>,
 
@@ -25,16 +28,17 @@ impl<Anim: 'static+Animation> MenuController<Anim> {
     ///
     /// Creates a new menu controller
     ///
-    pub fn new(anim_model: &FloModel<Anim>) -> MenuController<Anim> {
+    pub fn new(anim_model: &FloModel<Anim>, panels: &EditorPanels) -> MenuController<Anim> {
         // Create the UI
         let effective_tool  = anim_model.tools().effective_tool.clone();
         let tool_controller = BindRef::from(computed(move || format!("Tool_{}", effective_tool.get().map(|tool| tool.tool_name()).unwrap_or(String::new()))));
-        let ui              = Self::create_ui(&tool_controller);
+        let ui              = Self::create_ui(&tool_controller, panels);
         let empty_menu      = Arc::new(EmptyMenuController::new());
 
         // Create the controller
         MenuController {
             anim_model:         Arc::new(anim_model.clone()),
+            panels:             panels.clone(),
             ui:                 BindRef::from(ui),
             tool_controllers:   Mutex::new(HashMap::new()),
 
@@ -45,12 +49,18 @@ impl<Anim: 'static+Animation> MenuController<Anim> {
     ///
     /// Creates the UI binding for this controller
     ///
-    fn create_ui(tool_controller: &BindRef<String>) -> BindRef<Control> {
+    fn create_ui(tool_controller: &BindRef<String>, panels: &EditorPanels) -> BindRef<Control> {
         let tool_controller = tool_controller.clone();
+        let history         = panels.history.clone();
+        let import_layers   = panels.import_layers.clone();
+        let import_svg      = panels.import_svg.clone();
 
         BindRef::from(computed(move || {
             // Get properties
             let tool_controller = tool_controller.get();
+            let show_history    = history.get() != DockPosition::Hidden;
+            let show_import     = import_layers.get() != DockPosition::Hidden;
+            let show_svg        = import_svg.get() != DockPosition::Hidden;
 
             // The control tree for the menu
             Control::empty()
@@ -71,6 +81,36 @@ impl<Anim: 'static+Animation> MenuController<Anim> {
                         .with(Bounds::stretch_horiz(1.0))
                         .with(Font::Size(12.0))
                         .with_controller(&tool_controller),
+
+                    Control::button()
+                        .with(localized("Thumbnail"))
+                        .with(Font::Size(12.0))
+                        .with((ActionTrigger::Click, "SetThumbnailFrame"))
+                        .with(ControlAttribute::Padding((0, 4), (0, 4)))
+                        .with(Bounds::next_horiz(80.0)),
+                    Control::button()
+                        .with(localized("Import"))
+                        .with(Font::Size(12.0))
+                        .with(State::Selected(Property::Bool(show_import)))
+                        .with((ActionTrigger::Click, "ToggleImportLayers"))
+                        .with(ControlAttribute::Padding((0, 4), (0, 4)))
+                        .with(Bounds::next_horiz(80.0)),
+                    Control::button()
+                        .with(localized("SVG"))
+                        .with(Font::Size(12.0))
+                        .with(State::Selected(Property::Bool(show_svg)))
+                        .with((ActionTrigger::Click, "ToggleImportSvg"))
+                        .with(ControlAttribute::Padding((0, 4), (0, 4)))
+                        .with(Bounds::next_horiz(80.0)),
+                    Control::button()
+                        .with(localized("History"))
+                        .with(Font::Size(12.0))
+                        .with(State::Selected(Property::Bool(show_history)))
+                        .with((ActionTrigger::Click, "ToggleHistory"))
+                        .with(ControlAttribute::Padding((0, 4), (0, 4)))
+                        .with(Bounds::next_horiz(80.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0))
                 ])
                 .with(Appearance::Background(MENU_BACKGROUND))
         }))
@@ -87,6 +127,21 @@ impl<Anim: 'static+Animation> MenuController<Anim> {
             .filter(|tool|  format!("Tool_{}", tool.tool_name()) == controller_name)
             .nth(0)
     }
+
+    ///
+    /// Makes the current frame the one shown in the thumbnail for this animation
+    ///
+    fn set_thumbnail_frame(&self) {
+        let timeline            = self.anim_model.timeline();
+        let current_time        = timeline.current_time.get();
+        let frame_duration      = timeline.frame_duration.get();
+
+        let current_time_ns     = current_time.as_secs() * 1_000_000_000 + (current_time.subsec_nanos() as u64);
+        let frame_duration_ns   = frame_duration.as_secs() * 1_000_000_000 + (frame_duration.subsec_nanos() as u64);
+        let frame               = (current_time_ns+(frame_duration_ns/2)) / frame_duration_ns;
+
+        self.anim_model.preferences().set_override(&THUMBNAIL_FRAME, &(frame as u32));
+    }
 }
 
 impl<Anim: EditableAnimation+Animation+'static> Controller for MenuController<Anim>  {
@@ -94,6 +149,16 @@ impl<Anim: EditableAnimation+Animation+'static> Controller for MenuController<An
         BindRef::clone(&self.ui)
     }
 
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        match action_id {
+            "ToggleHistory"         => EditorPanels::toggle(&self.panels.history),
+            "ToggleImportLayers"    => EditorPanels::toggle(&self.panels.import_layers),
+            "ToggleImportSvg"       => EditorPanels::toggle(&self.panels.import_svg),
+            "SetThumbnailFrame"     => self.set_thumbnail_frame(),
+            _                       => { }
+        }
+    }
+
     fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
         use std::collections::hash_map::Entry::*;
 
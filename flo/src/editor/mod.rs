@@ -8,9 +8,17 @@ mod controlbar_controller;
 mod frame_controls_controller;
 mod keyframe_controls_controller;
 mod toolbox_controller;
+mod history_controller;
+mod import_layers_controller;
+mod import_svg_controller;
+mod editor_panels;
 
 pub use self::editor_controller::*;
 pub use self::canvas_controller::*;
 pub use self::menu_controller::*;
 pub use self::timeline_controller::*;
 pub use self::toolbox_controller::*;
+pub use self::history_controller::*;
+pub use self::import_layers_controller::*;
+pub use self::import_svg_controller::*;
+pub use self::editor_panels::*;
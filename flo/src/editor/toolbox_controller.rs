@@ -1,6 +1,7 @@
 use super::super::tools::*;
 use super::super::style::*;
 use super::super::model::*;
+use super::super::preferences::*;
 
 use flo_ui::*;
 use flo_binding::*;
@@ -27,8 +28,9 @@ impl<Anim: 'static+EditableAnimation+Animation> ToolboxController<Anim> {
         // Create the viewmodel
         let viewmodel = Arc::new(DynamicViewModel::new());
 
-        // There's a 'SelectedTool' key that describes the currently selected tool
-        viewmodel.set_property("SelectedTool", PropertyValue::String("Ink".to_string()));
+        // There's a 'SelectedTool' key that describes the currently selected tool (the tool the user last picked, or the ink tool)
+        let selected_tool = anim_model.preferences().get(&SELECTED_TOOL).unwrap_or_else(|| "Ink".to_string());
+        viewmodel.set_property("SelectedTool", PropertyValue::String(selected_tool));
 
         // Update the viewmodel whenever the effective tool changes
         let effective_tool = anim_model.tools().effective_tool.clone();
@@ -162,6 +164,9 @@ impl<Anim: 'static+EditableAnimation+Animation> ToolboxController<Anim> {
             .with(State::Selected(Property::Bind(selected_property_name)))
             .with(Bounds::next_vert(48.0))
             .with(Hint::Class("tool-button".to_string()))
+            .with(Accessibility::Tooltip(Property::String(localized(name))))
+            .with(Accessibility::Label(Property::String(localized(name))))
+            .with(Accessibility::Role(AccessibilityRole::ToggleButton))
             .with(vec![
                 Control::empty()
                     .with(Bounds::fill_all())
@@ -181,6 +186,9 @@ impl<Anim: 'static+EditableAnimation+Animation> Controller for ToolboxController
 
         // Update the animation view model with the newly selected tool
         self.anim_model.tools().choose_tool_with_name(action_id);
+
+        // Remember the tool for the next time the editor is opened
+        self.anim_model.preferences().set(&SELECTED_TOOL, &action_id.to_string());
     }
 
     fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
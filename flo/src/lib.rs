@@ -26,7 +26,26 @@ pub mod tools;
 pub mod standard_tools;
 pub mod menu;
 pub mod color;
+pub mod preferences;
 
 mod model;
 
 pub use self::editor::*;
+
+///
+/// The translations of the messages displayed by the editor
+///
+pub static MESSAGES: flo_ui::EmbeddedCatalogs = flo_ui::EmbeddedCatalogs::new(&[
+    ("ja", include_str!("../locale/ja.po")),
+    ("es", include_str!("../locale/es.po"))
+]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn message_catalogs_are_valid() {
+        assert!(MESSAGES.check().is_ok());
+    }
+}
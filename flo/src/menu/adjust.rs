@@ -26,14 +26,14 @@ impl AdjustMenuController {
     /// Creates the UI for the adjust menu controller
     ///
     fn ui() -> BindRef<Control> {
-        let ui = bind(Control::container()
+        let ui = computed(move || Control::container()
                     .with(Bounds::fill_all())
                     .with(ControlAttribute::Padding((0, 3), (0, 3)))
                     .with(vec![
                         controls::divider(),
 
                         Control::label()
-                            .with("Adjust:")
+                            .with(localized("Adjust:"))
                             .with(FontWeight::Light)
                             .with(TextAlign::Right)
                             .with(Font::Size(14.0))
@@ -63,14 +63,14 @@ impl EraserMenuController {
         canvases.assign_name(&opacity_preview_large, "OpacityPreview2");
 
         // Generate the UI
-        let ui = BindRef::from(bind(Control::container()
+        let ui = BindRef::from(computed(move || Control::container()
                 .with(Bounds::fill_all())
                 .with(ControlAttribute::Padding((0, 3), (0, 3)))
                 .with(vec![
                     controls::divider(),
 
                     Control::label()
-                        .with("Eraser:")
+                        .with(localized("Eraser:"))
                         .with(FontWeight::Light)
                         .with(TextAlign::Right)
                         .with(Font::Size(14.0))
@@ -78,13 +78,13 @@ impl EraserMenuController {
                     Control::empty()
                         .with(Bounds::next_horiz(8.0)),
                     Control::canvas()
-                        .with(brush_preview)
+                        .with(brush_preview.clone())
                         .with(Bounds::next_horiz(64.0)),
 
                     controls::divider(),
 
                     Control::label()
-                        .with("Size:")
+                        .with(localized("Size:"))
                         .with(TextAlign::Right)
                         .with(Bounds::next_horiz(36.0)),
                     Control::empty().with(Bounds::next_horiz(6.0)),
@@ -96,7 +96,7 @@ impl EraserMenuController {
                         .with((ActionTrigger::SetValue, "ChangeSizeSet".to_string())),
                     Control::empty().with(Bounds::next_horiz(4.0)),
                     Control::canvas()
-                        .with(size_preview)
+                        .with(size_preview.clone())
                         .with(Bounds::next_horiz(32.0))
                         .with(vec![
                             Control::popup()
@@ -108,14 +108,14 @@ impl EraserMenuController {
                                 .with(vec![
                                     Control::canvas()
                                         .with(Bounds::fill_all())
-                                        .with(size_preview_large)
+                                        .with(size_preview_large.clone())
                                 ])
                         ]),
 
                     controls::divider(),
 
                     Control::label()
-                        .with("Opacity:")
+                        .with(localized("Opacity:"))
                         .with(TextAlign::Right)
                         .with(Bounds::next_horiz(56.0)),
                     Control::empty().with(Bounds::next_horiz(6.0)),
@@ -127,7 +127,7 @@ impl EraserMenuController {
                         .with((ActionTrigger::SetValue, "ChangeOpacitySet".to_string())),
                     Control::empty().with(Bounds::next_horiz(4.0)),
                     Control::canvas()
-                        .with(opacity_preview)
+                        .with(opacity_preview.clone())
                         .with(Bounds::next_horiz(32.0))
                         .with(vec![
                             Control::popup()
@@ -140,7 +140,7 @@ impl EraserMenuController {
                                 .with(vec![
                                     Control::canvas()
                                         .with(Bounds::fill_all())
-                                        .with(opacity_preview_large)
+                                        .with(opacity_preview_large.clone())
                                 ])
                         ]),
 
@@ -123,7 +123,7 @@ impl FloodFillMenuController {
                     controls::divider(),
 
                     Control::label()
-                        .with("Flood fill:")
+                        .with(localized("Flood fill:"))
                         .with(FontWeight::Light)
                         .with(TextAlign::Right)
                         .with(Font::Size(14.0))
@@ -132,7 +132,7 @@ impl FloodFillMenuController {
                         .with(Bounds::next_horiz(8.0)),
 
                     Control::label()
-                        .with("Color:")
+                        .with(localized("Color:"))
                         .with(TextAlign::Right)
                         .with(Bounds::next_horiz(40.0)),
                     Control::empty().with(Bounds::next_horiz(4.0)),
@@ -146,7 +146,7 @@ impl FloodFillMenuController {
                     controls::divider(),
 
                     Control::label()
-                        .with("Opacity:")
+                        .with(localized("Opacity:"))
                         .with(TextAlign::Right)
                         .with(Bounds::next_horiz(56.0)),
                     Control::empty().with(Bounds::next_horiz(6.0)),
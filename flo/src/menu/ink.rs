@@ -205,7 +205,7 @@ impl InkMenuController {
                     controls::divider(),
 
                     Control::label()
-                        .with("Brush:")
+                        .with(localized("Brush:"))
                         .with(FontWeight::Light)
                         .with(TextAlign::Right)
                         .with(Font::Size(14.0))
@@ -219,7 +219,7 @@ impl InkMenuController {
                     controls::divider(),
 
                     Control::label()
-                        .with("Color:")
+                        .with(localized("Color:"))
                         .with(TextAlign::Right)
                         .with(Bounds::next_horiz(40.0)),
                     Control::empty().with(Bounds::next_horiz(4.0)),
@@ -233,7 +233,7 @@ impl InkMenuController {
                     controls::divider(),
 
                     Control::label()
-                        .with("Size:")
+                        .with(localized("Size:"))
                         .with(TextAlign::Right)
                         .with(Bounds::next_horiz(36.0)),
                     Control::empty().with(Bounds::next_horiz(6.0)),
@@ -264,7 +264,7 @@ impl InkMenuController {
                     controls::divider(),
 
                     Control::label()
-                        .with("Opacity:")
+                        .with(localized("Opacity:"))
                         .with(TextAlign::Right)
                         .with(Bounds::next_horiz(56.0)),
                     Control::empty().with(Bounds::next_horiz(6.0)),
@@ -327,7 +327,7 @@ impl InkMenuController {
                                                         .with(Bounds::next_vert(3.0)),
                                                     Control::label()
                                                         .with(Bounds::next_vert(26.0))
-                                                        .with("Settings")
+                                                        .with(localized("Settings"))
                                                         .with(Font::Size(15.0))
                                                         .with(Font::Align(TextAlign::Center))
                                                         .with(Font::Weight(FontWeight::Light)),
@@ -352,7 +352,7 @@ impl InkMenuController {
                                                             Control::label()
                                                                 .with(Bounds::fill_horiz())
                                                                 .with(Font::Size(11.0))
-                                                                .with(modification_text)
+                                                                .with(localized(modification_text))
                                                         ]),
                                                     Control::empty()
                                                         .with(Bounds::next_vert(3.0)),
@@ -370,7 +370,7 @@ impl InkMenuController {
                                                             Control::label()
                                                                 .with(Bounds::fill_horiz())
                                                                 .with(Font::Size(11.0))
-                                                                .with(representation_text)
+                                                                .with(localized(representation_text))
                                                         ]),
                                                     Control::empty()
                                                         .with(Bounds::next_vert(3.0)),
@@ -143,9 +143,9 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                 // Number of things selected
                 let num_selected        = num_selected.get();
                 let num_selected_text   = match num_selected {
-                    0 => String::from("Nothing"),
-                    1 => String::from("1 item"),
-                    _ => format!("{} items", num_selected)
+                    0 => localized("Nothing"),
+                    1 => localized("1 item"),
+                    _ => localized_format("{count} items", &[("count", localization().number_format().format_integer(num_selected as i64, 0))])
                 };
 
                 let anything_selected   = anything_selected.get();
@@ -157,7 +157,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                         controls::divider(),
 
                         Control::label()
-                            .with("Order:")
+                            .with(localized("Order:"))
                             .with(TextAlign::Right)
                             .with(Font::Size(13.0))
                             .with(Bounds::next_horiz(48.0)),
@@ -204,7 +204,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                         controls::divider(),
 
                         Control::label()
-                            .with("Align:")
+                            .with(localized("Align:"))
                             .with(TextAlign::Right)
                             .with(Font::Size(13.0))
                             .with(Bounds::next_horiz(48.0)),
@@ -302,7 +302,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                         controls::divider(),
 
                         Control::label()
-                            .with("Group:")
+                            .with(localized("Group:"))
                             .with(TextAlign::Right)
                             .with(Font::Size(13.0))
                             .with(Bounds::next_horiz(48.0)),
@@ -358,7 +358,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                         controls::divider(),
 
                         Control::label()
-                            .with("Select:")
+                            .with(localized("Select:"))
                             .with(FontWeight::Light)
                             .with(TextAlign::Right)
                             .with(Font::Size(14.0))
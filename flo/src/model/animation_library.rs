@@ -0,0 +1,66 @@
+use flo_ui::*;
+use flo_animation::*;
+use flo_animation::storage::*;
+use flo_ui_files::*;
+
+use std::sync::*;
+use std::path::{Path, PathBuf};
+
+///
+/// Provides access to the other animations that the user has created, so that their contents can be imported
+///
+pub trait AnimationLibrary : Send+Sync {
+    ///
+    /// Lists the animations in the library, as their path and display name
+    ///
+    fn animations(&self) -> Vec<(PathBuf, String)>;
+
+    ///
+    /// Opens an animation from the library for reading
+    ///
+    fn open_animation(&self, path: &Path) -> Result<Arc<dyn Animation>, StorageError>;
+}
+
+///
+/// An animation library containing the files in a file manager, apart from the animation that is being edited
+///
+pub struct FileManagerLibrary<Loader: FileAnimation> {
+    /// The loader used to open the animations
+    loader: Arc<Loader>,
+
+    /// The file manager that lists the animations
+    file_manager: Arc<dyn FileManager>,
+
+    /// The path of the animation that's being edited
+    editing_path: PathBuf
+}
+
+impl<Loader: FileAnimation> FileManagerLibrary<Loader> {
+    ///
+    /// Creates a library for the animation that is being edited at a particular path
+    ///
+    pub fn new(loader: Arc<Loader>, file_manager: Arc<dyn FileManager>, editing_path: &Path) -> FileManagerLibrary<Loader> {
+        FileManagerLibrary {
+            loader:         loader,
+            file_manager:   file_manager,
+            editing_path:   PathBuf::from(editing_path)
+        }
+    }
+}
+
+impl<Loader: FileAnimation> AnimationLibrary for FileManagerLibrary<Loader> {
+    fn animations(&self) -> Vec<(PathBuf, String)> {
+        self.file_manager.get_all_files()
+            .into_iter()
+            .filter(|path| path != &self.editing_path)
+            .map(|path| {
+                let name = self.file_manager.display_name_for_path(path.as_path()).unwrap_or_else(|| localized("Untitled"));
+                (path, name)
+            })
+            .collect()
+    }
+
+    fn open_animation(&self, path: &Path) -> Result<Arc<dyn Animation>, StorageError> {
+        self.loader.open_read_only(path)
+    }
+}
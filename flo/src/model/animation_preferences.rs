@@ -0,0 +1,117 @@
+use flo_stream::*;
+use flo_animation::*;
+use flo_ui_files::preferences::*;
+use ::desync::*;
+
+use std::sync::*;
+use std::collections::HashMap;
+
+///
+/// Preference store that keeps its values in an animation file (used for the preferences that an animation overrides)
+///
+pub struct AnimationPreferenceStore {
+    /// The preferences that are currently overridden by the animation
+    values: Mutex<HashMap<String, String>>,
+
+    /// The preferences that have changed but which have not been sent to the animation yet
+    pending: Arc<Mutex<HashMap<String, Option<String>>>>,
+
+    /// Publisher where the edits that update the preferences are sent
+    edit_publisher: Arc<Desync<Publisher<Arc<Vec<AnimationEdit>>>>>
+}
+
+impl AnimationPreferenceStore {
+    ///
+    /// Creates a new preference store for an animation, which will send its updates to the specified edit publisher
+    ///
+    pub fn new<Anim: Animation>(animation: &Anim, edit_publisher: Arc<Desync<Publisher<Arc<Vec<AnimationEdit>>>>>) -> AnimationPreferenceStore {
+        AnimationPreferenceStore {
+            values:         Mutex::new(animation.preference_overrides()),
+            pending:        Arc::new(Mutex::new(HashMap::new())),
+            edit_publisher: edit_publisher
+        }
+    }
+}
+
+impl PreferenceStore for AnimationPreferenceStore {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.lock().unwrap().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: Option<String>) {
+        // Update the values we know about (nothing needs to be written if the value is unchanged)
+        {
+            let mut values = self.values.lock().unwrap();
+
+            if values.get(key) == value.as_ref() {
+                return;
+            }
+
+            match value.as_ref() {
+                Some(value) => { values.insert(key.to_string(), value.clone()); }
+                None        => { values.remove(key); }
+            }
+        }
+
+        // Values that change several times before the edit publisher gets to them (eg, while a slider is dragged) are only stored once
+        let needs_publish = {
+            let mut pending     = self.pending.lock().unwrap();
+            let was_empty       = pending.is_empty();
+
+            pending.insert(key.to_string(), value);
+            was_empty
+        };
+
+        // Store the latest values of the pending preferences in the animation
+        if needs_publish {
+            let pending = Arc::clone(&self.pending);
+
+            let _ = self.edit_publisher.future(move |animation| {
+                let edits = pending.lock().unwrap()
+                    .drain()
+                    .map(|(key, value)| AnimationEdit::SetPreference(key, value))
+                    .collect::<Vec<_>>();
+
+                animation.publish(Arc::new(edits))
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use futures::prelude::*;
+    use futures::executor;
+
+    use std::sync::mpsc;
+
+    #[test]
+    fn repeated_changes_are_published_once() {
+        let edit_publisher  = Arc::new(Desync::new(Publisher::new(10)));
+        let mut edits       = edit_publisher.sync(|publisher| publisher.subscribe());
+        let store           = AnimationPreferenceStore {
+            values:         Mutex::new(HashMap::new()),
+            pending:        Arc::new(Mutex::new(HashMap::new())),
+            edit_publisher: Arc::clone(&edit_publisher)
+        };
+
+        // Hold up the publisher while the preference is changed
+        let (release, wait_for_release) = mpsc::channel::<()>();
+        edit_publisher.desync(move |_| { wait_for_release.recv().ok(); });
+
+        store.set("test.size", Some("1".to_string()));
+        store.set("test.size", Some("2".to_string()));
+        store.set("test.size", Some("3".to_string()));
+        store.set("test.size", Some("3".to_string()));
+
+        release.send(()).unwrap();
+        edit_publisher.sync(|_| { });
+
+        // Only the final value should be sent to the animation
+        let published = executor::block_on(async { edits.next().await });
+        assert!(published == Some(Arc::new(vec![AnimationEdit::SetPreference("test.size".to_string(), Some("3".to_string()))])));
+        assert!(store.get("test.size") == Some("3".to_string()));
+    }
+}
@@ -3,17 +3,26 @@ use super::frame::*;
 use super::timeline::*;
 use super::selection::*;
 use super::onion_skin::*;
+use super::animation_preferences::*;
+use super::animation_library::*;
+use super::super::preferences::*;
 
 use flo_stream::*;
 use flo_binding::*;
 use flo_animation::*;
+use flo_animation::svg::*;
+use flo_animation::storage::restore_edits;
+use flo_ui_files::preferences::*;
 use futures::*;
 use futures::stream::{BoxStream};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use ::desync::*;
 
 use std::ops::Range;
 use std::time::Duration;
 use std::sync::*;
+use std::collections::HashMap;
 
 ///
 /// The model for the animation editor
@@ -37,9 +46,18 @@ pub struct FloModel<Anim: Animation> {
     /// The onion skin model
     onion_skin: OnionSkinModel<Anim>,
 
+    /// The preferences for the editor (the user's preferences, with any overrides stored in the animation)
+    preferences: Arc<Preferences>,
+
     /// The size of the animation
     pub size: BindRef<(f64, f64)>,
 
+    /// The checkpoints in the edit log of the animation
+    checkpoints: Binding<Vec<AnimationCheckpoint>>,
+
+    /// The other animations that can be imported into this one
+    library: Option<Arc<dyn AnimationLibrary>>,
+
     /// The underlying size binding
     size_binding: Binding<(f64, f64)>,
 
@@ -54,7 +72,16 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
     ///
     /// Creates a new model
     ///
+    /// The user's preferences are not loaded or stored by this model (use `new_with_preferences` to specify where they are kept)
+    ///
     pub fn new(animation: Anim) -> FloModel<Anim> {
+        Self::new_with_preferences(animation, Arc::new(InMemoryPreferenceStore::new()))
+    }
+
+    ///
+    /// Creates a new model, which will load the settings for the tools and onion skins from (and save them to) a preference store
+    ///
+    pub fn new_with_preferences(animation: Anim, user_preferences: Arc<dyn PreferenceStore>) -> FloModel<Anim> {
         let mut edit_publisher  = animation.edit();
         let animation           = Arc::new(animation);
         let tools               = ToolModel::new();
@@ -65,8 +92,13 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
         let onion_skin          = OnionSkinModel::new(Arc::clone(&animation), &timeline);
 
         let size_binding        = bind(animation.size());
+        let checkpoints         = bind(animation.get_checkpoints());
         let edit_publisher      = Arc::new(Desync::new(edit_publisher));
 
+        let overrides           = Arc::new(AnimationPreferenceStore::new(&*animation, Arc::clone(&edit_publisher)));
+        let preferences         = Arc::new(Preferences::with_overrides(user_preferences, overrides));
+        Self::bind_onion_skin_preferences(&preferences, &onion_skin);
+
         let mut model           = FloModel {
             animation:          animation,
             tools:              tools,
@@ -75,9 +107,12 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
             frame:              frame,
             selection:          selection,
             onion_skin:         onion_skin,
+            preferences:        preferences,
 
             size:               BindRef::from(size_binding.clone()),
             size_binding:       size_binding,
+            checkpoints:        checkpoints,
+            library:            None,
 
             edit_publisher:     edit_publisher
         };
@@ -87,6 +122,41 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
         model
     }
 
+    ///
+    /// Sets the library containing the other animations that can be imported into this one
+    ///
+    pub fn with_library(mut self, library: Arc<dyn AnimationLibrary>) -> FloModel<Anim> {
+        self.library = Some(library);
+        self
+    }
+
+    ///
+    /// Loads the onion skin settings from the preferences, and saves them when they change
+    ///
+    fn bind_onion_skin_preferences(preferences: &Preferences, onion_skin: &OnionSkinModel<Anim>) {
+        preferences.bind(&SHOW_ONION_SKINS, &onion_skin.show_onion_skins);
+        preferences.bind(&ONION_SKIN_FRAMES_BEFORE, &onion_skin.frames_before);
+        preferences.bind(&ONION_SKIN_FRAMES_AFTER, &onion_skin.frames_after);
+        preferences.bind(&ONION_SKIN_PAST_COLOR, &onion_skin.past_color);
+        preferences.bind(&ONION_SKIN_FUTURE_COLOR, &onion_skin.future_color);
+    }
+
+    ///
+    /// Moves a bound preference into the animation's overrides, or returns it to the value stored in the user's preferences
+    ///
+    fn override_preference<T>(preferences: &Preferences, key: &PreferenceKey<T>, binding: &Binding<T>, override_preference: bool)
+    where T: 'static+Clone+PartialEq+Send+Serialize+DeserializeOwned {
+        if override_preference {
+            preferences.set_override(key, &binding.get());
+        } else {
+            preferences.remove_override(key);
+
+            if let Some(value) = preferences.get(key) {
+                binding.set(value);
+            }
+        }
+    }
+
     ///
     /// Updates the model based on edits made to the animation
     ///
@@ -123,6 +193,8 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
                     advance_edit_counter = true;
                 },
 
+                SetPreference(_, _)         => { }
+
                 AddNewLayer(_)              |
                 RemoveLayer(_)              |
                 Element(_, _)               |
@@ -156,6 +228,124 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
         }
     }
 
+    ///
+    /// Copies layers from another animation into this one, returning the IDs of the new layers
+    ///
+    /// The first of the imported layers is selected once the import is complete. If `retime` is true, the keyframes
+    /// of the imported layers are adjusted so that they match up with the frames of this animation.
+    ///
+    pub fn import_layers<Source: Animation+?Sized>(&self, source: &Source, layer_ids: &[u64], retime: bool) -> Vec<u64> {
+        // Generate the edits for the new layers
+        let edits           = import_layer_edits(source, self, layer_ids, retime);
+        let new_layer_ids   = edits.iter()
+            .filter_map(|edit| match edit {
+                AnimationEdit::AddNewLayer(layer_id)    => Some(*layer_id),
+                _                                       => None
+            })
+            .collect::<Vec<_>>();
+
+        // Send to the animation
+        let _ = self.edit_publisher.future(move |animation| {
+            animation.publish(Arc::new(edits))
+        });
+        self.edit_publisher.sync(|_| {});
+
+        // Select the first new layer
+        if let Some(first_layer_id) = new_layer_ids.iter().nth(0) {
+            self.timeline.selected_layer.set(Some(*first_layer_id));
+        }
+
+        // Update the model
+        self.timeline.update_keyframe_bindings();
+        self.timeline.invalidate_canvas();
+
+        new_layer_ids
+    }
+
+    ///
+    /// Imports the contents of an SVG document into the selected layer at the current time, returning the ID of the imported element
+    ///
+    /// The imported element (a group if the document contains more than one shape) is selected once the import is complete.
+    /// A keyframe is created if the layer has none at the current time. Nothing is imported if there's no selected layer.
+    ///
+    pub fn import_svg(&self, svg: &str) -> Result<Option<ElementId>, SvgImportError> {
+        let layer_id = match self.timeline.selected_layer.get() {
+            Some(layer_id)  => layer_id,
+            None            => { return Ok(None); }
+        };
+        let when = self.timeline.current_time.get();
+
+        // Generate the edits for the document
+        let edits = svg_import_edits(svg, self, layer_id, when)?;
+
+        // The top-level element is always the last one to be created
+        let element_id = edits.iter().rev()
+            .filter_map(|edit| match edit {
+                AnimationEdit::Element(_, ElementEdit::Group(group_id, _))                  => Some(*group_id),
+                AnimationEdit::Layer(_, LayerEdit::Path(_, PathEdit::CreatePath(path_id, _)))  => Some(*path_id),
+                _                                                                           => None
+            })
+            .nth(0);
+
+        // Send to the animation
+        let _ = self.edit_publisher.future(move |animation| {
+            animation.publish(Arc::new(edits))
+        });
+        self.edit_publisher.sync(|_| {});
+
+        // Update the model
+        self.timeline.update_keyframe_bindings();
+        self.timeline.invalidate_canvas();
+
+        // Select the imported element
+        if let Some(element_id) = element_id {
+            self.selection.clear_selection();
+            self.selection.select(element_id);
+        }
+
+        Ok(element_id)
+    }
+
+    ///
+    /// Restores the animation to the state it was in at the named checkpoint, returning false if the checkpoint doesn't exist
+    ///
+    /// The history of the animation is kept: the edits that recreate the animation as it was at the checkpoint are added
+    /// to the end of the edit log, so it's possible to restore to a later checkpoint afterwards.
+    ///
+    pub fn restore_checkpoint(&self, name: &str) -> bool {
+        let checkpoint = self.animation.get_checkpoints()
+            .into_iter()
+            .filter(|checkpoint| checkpoint.name == name)
+            .nth(0);
+        let checkpoint = match checkpoint {
+            Some(checkpoint)    => checkpoint,
+            None                => { return false; }
+        };
+
+        // Generate the restore edits and send them to the animation
+        let animation = Arc::clone(&self.animation);
+        let _ = self.edit_publisher.future(move |publisher| {
+            async move {
+                let edits = restore_edits(&*animation, checkpoint.edit_index).await;
+                publisher.publish(Arc::new(edits)).await;
+            }.boxed()
+        });
+        self.edit_publisher.sync(|_| {});
+
+        // The selected layer may no longer exist
+        let layer_ids = self.animation.get_layer_ids();
+        if !self.timeline.selected_layer.get().map(|layer_id| layer_ids.contains(&layer_id)).unwrap_or(false) {
+            self.timeline.selected_layer.set(layer_ids.iter().nth(0).cloned());
+        }
+
+        // Update the model
+        self.timeline.update_keyframe_bindings();
+        self.timeline.invalidate_canvas();
+        self.selection.clear_selection();
+
+        true
+    }
+
     ///
     /// Returns a future that indicates when all of the pending edits have been processed
     ///
@@ -209,6 +399,48 @@ impl<Anim: Animation+'static> FloModel<Anim> {
         &self.onion_skin
     }
 
+    ///
+    /// Retrieves the preferences for this animation
+    ///
+    pub fn preferences(&self) -> &Preferences {
+        &*self.preferences
+    }
+
+    ///
+    /// True if the onion skin settings are stored in this animation instead of the user's preferences
+    ///
+    pub fn onion_skin_preferences_overridden(&self) -> bool {
+        self.preferences.is_overridden(&SHOW_ONION_SKINS)
+    }
+
+    ///
+    /// Stores the current onion skin settings in this animation, or goes back to using the settings from the user's preferences
+    ///
+    pub fn override_onion_skin_preferences(&self, override_preferences: bool) {
+        let preferences = &*self.preferences;
+        let onion_skin  = &self.onion_skin;
+
+        Self::override_preference(preferences, &SHOW_ONION_SKINS, &onion_skin.show_onion_skins, override_preferences);
+        Self::override_preference(preferences, &ONION_SKIN_FRAMES_BEFORE, &onion_skin.frames_before, override_preferences);
+        Self::override_preference(preferences, &ONION_SKIN_FRAMES_AFTER, &onion_skin.frames_after, override_preferences);
+        Self::override_preference(preferences, &ONION_SKIN_PAST_COLOR, &onion_skin.past_color, override_preferences);
+        Self::override_preference(preferences, &ONION_SKIN_FUTURE_COLOR, &onion_skin.future_color, override_preferences);
+    }
+
+    ///
+    /// Retrieves the library of other animations that can be imported into this one, if there is one
+    ///
+    pub fn library(&self) -> Option<Arc<dyn AnimationLibrary>> {
+        self.library.clone()
+    }
+
+    ///
+    /// Retrieves the checkpoints in the edit log of this animation
+    ///
+    pub fn checkpoints(&self) -> BindRef<Vec<AnimationCheckpoint>> {
+        BindRef::from(self.checkpoints.clone())
+    }
+
     ///
     /// Retrieves the frame update binding for this animation
     ///
@@ -235,9 +467,12 @@ impl<Anim: Animation> Clone for FloModel<Anim> {
             frame:              self.frame.clone(),
             selection:          self.selection.clone(),
             onion_skin:         self.onion_skin.clone(),
+            preferences:        self.preferences.clone(),
 
             size:               self.size.clone(),
             size_binding:       self.size_binding.clone(),
+            checkpoints:        self.checkpoints.clone(),
+            library:            self.library.clone(),
 
             edit_publisher:     self.edit_publisher.clone()
         }
@@ -266,6 +501,13 @@ impl<Anim: Animation> Animation for FloModel<Anim> {
         self.animation.frame_length()
     }
 
+    ///
+    /// Retrieves the preferences that are overridden by this animation
+    ///
+    fn preference_overrides(&self) -> HashMap<String, String> {
+        self.animation.preference_overrides()
+    }
+
     ///
     /// Retrieves the IDs of the layers in this object
     ///
@@ -287,6 +529,13 @@ impl<Anim: Animation> Animation for FloModel<Anim> {
         self.animation.get_num_edits()
     }
 
+    ///
+    /// Retrieves the size of the edit log for this animation (the total length of the serialized edits)
+    ///
+    fn get_edit_log_size(&self) -> usize {
+        self.animation.get_edit_log_size()
+    }
+
     ///
     /// Reads from the edit log for this animation
     ///
@@ -294,6 +543,13 @@ impl<Anim: Animation> Animation for FloModel<Anim> {
         self.animation.read_edit_log(range)
     }
 
+    ///
+    /// Retrieves the named checkpoints in the edit log for this animation
+    ///
+    fn get_checkpoints(&self) -> Vec<AnimationCheckpoint> {
+        self.animation.get_checkpoints()
+    }
+
     ///
     /// Supplies a reference which can be used to find the motions associated with this animation
     ///
@@ -356,6 +612,29 @@ impl<Anim: 'static+Animation+EditableAnimation> EditableAnimation for FloModel<A
     fn flush_caches(&self) {
         self.animation.flush_caches()
     }
+
+    ///
+    /// Replaces the first `num_edits` edits in the edit log with a new set of edits
+    ///
+    fn replace_edit_log(&self, num_edits: usize, new_edits: Vec<AnimationEdit>) {
+        self.animation.replace_edit_log(num_edits, new_edits)
+    }
+
+    ///
+    /// Creates a checkpoint with the specified name at the current end of the edit log
+    ///
+    fn set_checkpoint(&self, name: &str) {
+        self.animation.set_checkpoint(name);
+        self.checkpoints.set(self.animation.get_checkpoints());
+    }
+
+    ///
+    /// Removes the checkpoint with the specified name
+    ///
+    fn remove_checkpoint(&self, name: &str) {
+        self.animation.remove_checkpoint(name);
+        self.checkpoints.set(self.animation.get_checkpoints());
+    }
 }
 
 #[cfg(test)]
@@ -386,4 +665,102 @@ mod test {
         assert!(model.size()        == (800.0, 600.0));
         assert!(model.size.get()    == (800.0, 600.0));
     }
+
+    #[test]
+    fn onion_skin_settings_are_loaded_from_preferences() {
+        let user_preferences    = Arc::new(InMemoryPreferenceStore::new());
+
+        // Change the onion skin settings in one model
+        let in_memory_store     = InMemoryStorage::new();
+        let animation           = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+        let model               = FloModel::new_with_preferences(animation, user_preferences.clone());
+
+        model.onion_skin().frames_before.set(7);
+
+        // New models should use the same settings
+        let in_memory_store     = InMemoryStorage::new();
+        let animation           = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+        let model               = FloModel::new_with_preferences(animation, user_preferences.clone());
+
+        assert!(model.onion_skin().frames_before.get() == 7);
+        assert!(model.onion_skin().frames_after.get() == 3);
+    }
+
+    #[test]
+    fn overridden_preferences_are_stored_in_the_animation() {
+        let user_preferences    = Arc::new(InMemoryPreferenceStore::new());
+        let in_memory_store     = InMemoryStorage::new();
+        let animation           = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+        let model               = FloModel::new_with_preferences(animation, user_preferences.clone());
+
+        // Override the number of onion skins for this animation
+        model.preferences().set_override(&ONION_SKIN_FRAMES_BEFORE, &2);
+        model.onion_skin().frames_before.set(5);
+
+        executor::block_on(async { model.when_complete().await; });
+
+        // The override should be updated, leaving the user's preferences alone
+        assert!(model.preference_overrides().get(ONION_SKIN_FRAMES_BEFORE.name()) == Some(&"5".to_string()));
+        assert!(user_preferences.get(ONION_SKIN_FRAMES_BEFORE.name()) == None);
+    }
+
+    #[test]
+    fn onion_skin_settings_can_be_stored_in_the_animation() {
+        let user_preferences    = Arc::new(InMemoryPreferenceStore::new());
+        let in_memory_store     = InMemoryStorage::new();
+        let animation           = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+        let model               = FloModel::new_with_preferences(animation, user_preferences.clone());
+
+        model.onion_skin().frames_before.set(4);
+        model.override_onion_skin_preferences(true);
+        model.onion_skin().frames_before.set(6);
+
+        executor::block_on(async { model.when_complete().await; });
+
+        assert!(model.onion_skin_preferences_overridden());
+        assert!(model.preference_overrides().get(ONION_SKIN_FRAMES_BEFORE.name()) == Some(&"6".to_string()));
+        assert!(user_preferences.get(ONION_SKIN_FRAMES_BEFORE.name()) == Some("4".to_string()));
+
+        // Removing the override should go back to the user's settings
+        model.override_onion_skin_preferences(false);
+        executor::block_on(async { model.when_complete().await; });
+
+        assert!(!model.onion_skin_preferences_overridden());
+        assert!(model.onion_skin().frames_before.get() == 4);
+        assert!(model.preference_overrides().get(ONION_SKIN_FRAMES_BEFORE.name()) == None);
+    }
+
+    #[test]
+    fn restore_checkpoint_recreates_layers() {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+        let model           = FloModel::new(animation);
+
+        executor::block_on(async {
+            let mut edit_log = model.edit();
+            edit_log.publish(Arc::new(vec![AnimationEdit::AddNewLayer(1)])).await;
+            edit_log.when_empty().await;
+            model.when_complete().await;
+        });
+
+        model.set_checkpoint("one layer");
+        assert!(model.checkpoints().get().iter().map(|checkpoint| checkpoint.name.clone()).collect::<Vec<_>>() == vec!["one layer".to_string()]);
+
+        executor::block_on(async {
+            let mut edit_log = model.edit();
+            edit_log.publish(Arc::new(vec![AnimationEdit::AddNewLayer(2), AnimationEdit::SetSize(800.0, 600.0)])).await;
+            edit_log.when_empty().await;
+            model.when_complete().await;
+        });
+
+        assert!(model.get_layer_ids() == vec![1, 2]);
+
+        // Restoring should remove the second layer and put the size back
+        assert!(model.restore_checkpoint("one layer"));
+        executor::block_on(async { model.when_complete().await; });
+
+        assert!(model.get_layer_ids() == vec![1]);
+        assert!(model.size.get() == (1920.0, 1080.0));
+        assert!(!model.restore_checkpoint("does not exist"));
+    }
 }
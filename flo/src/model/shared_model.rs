@@ -1,17 +1,31 @@
 use super::flo_model::*;
+use super::animation_library::*;
+use super::super::preferences::*;
 
+use flo_canvas::*;
 use flo_animation::*;
 use flo_ui_files::*;
 
 use std::sync::*;
 use std::path::{Path, PathBuf};
 
+///
+/// Loads FlowBetween animations, along with the file manager that lists the other animations that can be imported into them
+///
+pub struct FloLoader<Loader> {
+    /// The loader that opens animation files
+    pub loader: Arc<Loader>,
+
+    /// The file manager listing the animations that the user has created
+    pub file_manager: Arc<dyn FileManager>
+}
+
 ///
 /// Represents the file model for FlowBetween animations
 ///
 pub struct FloSharedModel<Loader> {
     /// The loader that can load in a new copy of the animation
-    loader: Arc<Loader>,
+    loader: Arc<FloLoader<Loader>>,
 
     /// The path where the animation can be opened from
     path: PathBuf
@@ -21,13 +35,13 @@ impl<Loader: FileAnimation+Send+Sync+'static> FileModel for FloSharedModel<Loade
 where Loader::NewAnimation: 'static+EditableAnimation {
     // TODO: we should probably actually share the file between instances :-)
     type InstanceModel  = FloModel<Loader::NewAnimation>;
-    type Loader         = Loader;
+    type Loader         = FloLoader<Loader>;
 
     ///
     /// Opens the file found at a particular path, returning the model shared across all instances
     /// of this file. This is shared across all controllers using the same file.
     ///
-    fn open(loader: Arc<Loader>, path: &Path) -> FloSharedModel<Loader> {
+    fn open(loader: Arc<FloLoader<Loader>>, path: &Path) -> FloSharedModel<Loader> {
         FloSharedModel {
             loader: loader,
             path:   PathBuf::from(path),
@@ -38,8 +52,73 @@ where Loader::NewAnimation: 'static+EditableAnimation {
     /// Creates a new instance model from the shared model. This is used for a single session.
     ///
     fn new_instance(&self) -> FloModel<Loader::NewAnimation> {
-        let animation = self.loader.open(self.path.as_path());
+        let animation   = self.loader.loader.open(self.path.as_path());
+        let library     = FileManagerLibrary::new(Arc::clone(&self.loader.loader), Arc::clone(&self.loader.file_manager), self.path.as_path());
+
+        FloModel::new_with_preferences(animation, user_preferences())
+            .with_library(Arc::new(library))
+    }
+
+    ///
+    /// Returns true if the animation at the specified path was not closed cleanly
+    ///
+    fn needs_recovery(loader: &Arc<FloLoader<Loader>>, path: &Path) -> bool {
+        loader.loader.needs_recovery(path)
+    }
+
+    ///
+    /// Recovers the edits that were lost when the animation at the specified path was not closed cleanly
+    ///
+    fn recover(loader: &Arc<FloLoader<Loader>>, path: &Path) -> Result<(), RecoveryError> {
+        loader.loader.recover(path)
+            .map_err(|err| RecoveryError::CannotOpenFile(format!("{:?}", err)))
+    }
+
+    ///
+    /// Discards the edits that could have been recovered for the animation at the specified path
+    ///
+    fn discard_recovery(loader: &Arc<FloLoader<Loader>>, path: &Path) {
+        loader.loader.discard_recovery(path)
+    }
+
+    ///
+    /// Renders the thumbnail frame of the animation at the specified path (the first frame if no thumbnail frame is set)
+    ///
+    /// The animation is opened read-only, so this doesn't start a session or a journal for the file. The thumbnail frame is
+    /// stored in the animation, so choosing a new one changes the file and makes any cached thumbnail out of date.
+    ///
+    fn render_thumbnail(loader: &Arc<FloLoader<Loader>>, path: &Path) -> Result<Vec<Draw>, ThumbnailError> {
+        if !path.exists() { return Err(ThumbnailError::CannotReadFile(format!("{} does not exist", path.to_string_lossy()))); }
+
+        let animation       = loader.loader.open_read_only(path)
+            .map_err(|err| ThumbnailError::CannotReadFile(format!("{:?}", err)))?;
+        let (width, height) = animation.size();
+        let (width, height) = (width as f32, height as f32);
+        let mut drawing     = vec![];
+
+        let thumbnail_frame = animation.preference_overrides().get(THUMBNAIL_FRAME.name())
+            .and_then(|frame| serde_json::from_str::<u32>(frame).ok())
+            .unwrap_or(0);
+        let when            = animation.frame_length() * thumbnail_frame;
+
+        // Show the whole of the animation, with a bit of a border
+        drawing.canvas_height(height*1.05);
+        drawing.center_region(0.0, 0.0, width, height);
+
+        // Draw the canvas background
+        drawing.fill_color(Color::Rgba(1.0, 1.0, 1.0, 1.0));
+        drawing.new_path();
+        drawing.rect(0.0, 0.0, width, height);
+        drawing.fill();
+
+        // Draw the thumbnail frame of each layer on top of the background
+        for layer_id in animation.get_layer_ids() {
+            if let Some(layer) = animation.get_layer_with_id(layer_id) {
+                let frame = layer.get_frame_at_time(when);
+                frame.render_to(&mut drawing);
+            }
+        }
 
-        FloModel::new(animation)
+        Ok(drawing)
     }
 }
@@ -0,0 +1,81 @@
+//!
+//! # Preferences
+//!
+//! The keys for the preferences used by FlowBetween, and the store where the user's preferences are kept
+//!
+
+use super::chooser::*;
+
+use flo_canvas::*;
+use flo_ui_files::preferences::*;
+use flo_ui_files::sqlite::*;
+
+use std::sync::*;
+
+/// The size of the ink brush
+pub const INK_SIZE: PreferenceKey<f32>                      = PreferenceKey::new("tools.ink.size");
+
+/// The opacity of the ink brush
+pub const INK_OPACITY: PreferenceKey<f32>                   = PreferenceKey::new("tools.ink.opacity");
+
+/// The colour of the ink brush
+pub const INK_COLOR: PreferenceKey<Color>                   = PreferenceKey::new("tools.ink.color");
+
+/// The size of the eraser
+pub const ERASER_SIZE: PreferenceKey<f32>                   = PreferenceKey::new("tools.eraser.size");
+
+/// The opacity of the eraser
+pub const ERASER_OPACITY: PreferenceKey<f32>                = PreferenceKey::new("tools.eraser.opacity");
+
+/// The colour used by the flood fill tool
+pub const FLOOD_FILL_COLOR: PreferenceKey<Color>            = PreferenceKey::new("tools.flood_fill.color");
+
+/// The opacity used by the flood fill tool
+pub const FLOOD_FILL_OPACITY: PreferenceKey<f32>            = PreferenceKey::new("tools.flood_fill.opacity");
+
+/// The name of the selected tool
+pub const SELECTED_TOOL: PreferenceKey<String>              = PreferenceKey::new("tools.selected");
+
+/// Whether or not the onion skins are displayed
+pub const SHOW_ONION_SKINS: PreferenceKey<bool>             = PreferenceKey::new("onion_skin.show");
+
+/// The number of onion skins to show before the current frame
+pub const ONION_SKIN_FRAMES_BEFORE: PreferenceKey<usize>    = PreferenceKey::new("onion_skin.frames_before");
+
+/// The number of onion skins to show after the current frame
+pub const ONION_SKIN_FRAMES_AFTER: PreferenceKey<usize>     = PreferenceKey::new("onion_skin.frames_after");
+
+/// The colour of the onion skins before the current frame
+pub const ONION_SKIN_PAST_COLOR: PreferenceKey<Color>       = PreferenceKey::new("onion_skin.past_color");
+
+/// The colour of the onion skins after the current frame
+pub const ONION_SKIN_FUTURE_COLOR: PreferenceKey<Color>     = PreferenceKey::new("onion_skin.future_color");
+
+/// The frame shown in the thumbnail for an animation (stored as an override in the animation itself)
+pub const THUMBNAIL_FRAME: PreferenceKey<u32>               = PreferenceKey::new("thumbnail.frame");
+
+/// The size of the main window
+pub const WINDOW_SIZE: PreferenceKey<(i32, i32)>            = PreferenceKey::new("window.size");
+
+lazy_static! {
+    static ref USER_PREFERENCES: Arc<dyn PreferenceStore> = open_user_preferences();
+}
+
+///
+/// Opens the store for the default user's preferences
+///
+fn open_user_preferences() -> Arc<dyn PreferenceStore> {
+    match SqlitePreferenceStore::new(APP_NAME, DEFAULT_USER_FOLDER) {
+        Ok(store)   => Arc::new(store),
+
+        // Preferences are only kept for this session if they can't be stored
+        Err(_)      => Arc::new(InMemoryPreferenceStore::new())
+    }
+}
+
+///
+/// Returns the store containing the default user's preferences
+///
+pub fn user_preferences() -> Arc<dyn PreferenceStore> {
+    Arc::clone(&USER_PREFERENCES)
+}
@@ -2,6 +2,7 @@ use super::ink::*;
 use super::super::menu::*;
 use super::super::tools::*;
 use super::super::model::*;
+use super::super::preferences::*;
 
 use flo_ui::*;
 use flo_binding::*;
@@ -47,11 +48,15 @@ impl<Anim: Animation+'static> Tool<Anim> for Eraser {
 
     fn image_name(&self) -> String { "eraser".to_string() }
 
-    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> InkModel {
-        let model = InkModel::new();
+    fn create_model(&self, flo_model: Arc<FloModel<Anim>>) -> InkModel {
+        let model       = InkModel::new();
+        let preferences = flo_model.preferences();
 
         model.size.set(10.0);
 
+        preferences.bind(&ERASER_SIZE, &model.size);
+        preferences.bind(&ERASER_OPACITY, &model.opacity);
+
         model
     }
 
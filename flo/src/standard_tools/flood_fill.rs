@@ -1,6 +1,7 @@
 use super::super::menu::*;
 use super::super::tools::*;
 use super::super::model::*;
+use super::super::preferences::*;
 
 use flo_ui::*;
 use flo_canvas::*;
@@ -111,8 +112,14 @@ impl<Anim: 'static+Animation> Tool<Anim> for FloodFill {
 
     fn image_name(&self) -> String { "floodfill".to_string() }
 
-    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> FloodFillModel {
-        FloodFillModel::new()
+    fn create_model(&self, flo_model: Arc<FloModel<Anim>>) -> FloodFillModel {
+        let model       = FloodFillModel::new();
+        let preferences = flo_model.preferences();
+
+        preferences.bind(&FLOOD_FILL_COLOR, &model.color);
+        preferences.bind(&FLOOD_FILL_OPACITY, &model.opacity);
+
+        model
     }
 
     fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &FloodFillModel) -> Option<Arc<dyn Controller>> {
@@ -1,6 +1,7 @@
 use super::super::menu::*;
 use super::super::tools::*;
 use super::super::model::*;
+use super::super::preferences::*;
 
 use flo_ui::*;
 use flo_canvas::*;
@@ -116,8 +117,15 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
     ///
     /// Creates a new instance of the UI model for this tool
     ///
-    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> InkModel {
-        InkModel::new()
+    fn create_model(&self, flo_model: Arc<FloModel<Anim>>) -> InkModel {
+        let model       = InkModel::new();
+        let preferences = flo_model.preferences();
+
+        preferences.bind(&INK_SIZE, &model.size);
+        preferences.bind(&INK_OPACITY, &model.opacity);
+        preferences.bind(&INK_COLOR, &model.color);
+
+        model
     }
 
     ///
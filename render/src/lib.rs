@@ -1,7 +1,9 @@
 mod action;
 mod buffer;
 mod gl_renderer;
+mod software_renderer;
 
 pub use self::action::*;
 pub use self::buffer::*;
 pub use self::gl_renderer::{GlRenderer};
+pub use self::software_renderer::*;
@@ -0,0 +1,6 @@
+mod renderer;
+mod render_target;
+mod rasterize;
+
+pub use self::renderer::*;
+pub use self::render_target::*;
@@ -0,0 +1,165 @@
+use super::render_target::*;
+use crate::action::*;
+
+///
+/// The factors that can be applied to the source or destination colour when blending
+///
+/// These correspond to the factors that the OpenGL renderer uses for its blend modes
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum BlendFactor {
+    Zero,
+    One,
+    OneMinusSrcColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    OneMinusDstColor,
+    DstAlpha,
+    OneMinusDstAlpha
+}
+
+impl BlendFactor {
+    ///
+    /// Computes the value of this factor for a particular channel
+    ///
+    #[inline]
+    fn value(&self, src: &[f32; 4], dst: &[f32; 4], channel: usize) -> f32 {
+        use self::BlendFactor::*;
+
+        match self {
+            Zero                => 0.0,
+            One                 => 1.0,
+            OneMinusSrcColor    => 1.0 - src[channel],
+            SrcAlpha            => src[3],
+            OneMinusSrcAlpha    => 1.0 - src[3],
+            OneMinusDstColor    => 1.0 - dst[channel],
+            DstAlpha            => dst[3],
+            OneMinusDstAlpha    => 1.0 - dst[3]
+        }
+    }
+}
+
+///
+/// Returns the source colour, destination colour, source alpha and destination alpha factors for a blend mode
+///
+fn blend_factors(blend_mode: BlendMode) -> (BlendFactor, BlendFactor, BlendFactor, BlendFactor) {
+    use self::BlendMode::*;
+    use self::BlendFactor::*;
+
+    match blend_mode {
+        SourceOver                      => (SrcAlpha, OneMinusSrcAlpha, One, OneMinusSrcAlpha),
+        DestinationOver                 => (OneMinusDstAlpha, DstAlpha, OneMinusDstAlpha, One),
+        SourceIn                        => (DstAlpha, Zero, DstAlpha, Zero),
+        DestinationIn                   => (Zero, SrcAlpha, Zero, SrcAlpha),
+        SourceOut                       => (Zero, OneMinusDstAlpha, Zero, OneMinusDstAlpha),
+        DestinationOut                  => (Zero, OneMinusSrcAlpha, Zero, OneMinusSrcAlpha),
+        SourceATop                      => (OneMinusDstAlpha, SrcAlpha, OneMinusDstAlpha, SrcAlpha),
+        DestinationATop                 => (OneMinusDstAlpha, OneMinusSrcAlpha, OneMinusDstAlpha, OneMinusSrcAlpha),
+
+        AllChannelAlphaSourceOver       => (One, OneMinusSrcColor, One, OneMinusSrcAlpha),
+        AllChannelAlphaDestinationOver  => (OneMinusDstColor, One, OneMinusDstAlpha, One)
+    }
+}
+
+///
+/// Blends a source colour onto a destination colour using the specified blend mode
+///
+#[inline]
+pub (super) fn blend(blend_mode: BlendMode, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+    let (src_rgb, dst_rgb, src_alpha, dst_alpha) = blend_factors(blend_mode);
+    let mut result = [0.0; 4];
+
+    for channel in 0..3 {
+        result[channel] = src[channel]*src_rgb.value(&src, &dst, channel) + dst[channel]*dst_rgb.value(&src, &dst, channel);
+    }
+    result[3] = src[3]*src_alpha.value(&src, &dst, 3) + dst[3]*dst_alpha.value(&src, &dst, 3);
+
+    // Clamp to the range that a framebuffer can store
+    for channel in 0..4 {
+        result[channel] = result[channel].max(0.0).min(1.0);
+    }
+
+    result
+}
+
+///
+/// Edge function: positive if p is to the left of the line from a to b
+///
+#[inline]
+fn edge(a: (f32, f32), b: (f32, f32), p: (f32, f32)) -> f32 {
+    (b.0-a.0)*(p.1-a.1) - (b.1-a.1)*(p.0-a.0)
+}
+
+///
+/// True if the edge from a to b (in a counter-clockwise triangle) is a top or a left edge
+///
+/// Pixels whose centres lie exactly on a top or left edge are considered to be inside the triangle, so pixels that lie on
+/// an edge shared between two triangles are only drawn once.
+///
+#[inline]
+fn is_top_left(a: (f32, f32), b: (f32, f32)) -> bool {
+    (a.1 == b.1 && b.0 < a.0) || (b.1 < a.1)
+}
+
+///
+/// Fills a triangle in a render target
+///
+/// The points are specified in pixel coordinates. The shader function receives the pixel position and the interpolated vertex
+/// colour and returns the colour to blend into the render target.
+///
+pub (super) fn fill_triangle<ShaderFn>(target: &mut SoftwareRenderTarget, points: [(f32, f32); 3], colors: [[f32; 4]; 3], blend_mode: BlendMode, shader: ShaderFn)
+where ShaderFn: Fn(usize, usize, [f32; 4]) -> [f32; 4] {
+    let (width, height) = target.get_size();
+
+    // Arrange the points so the triangle is counter-clockwise
+    let (p0, mut p1, mut p2)        = (points[0], points[1], points[2]);
+    let (c0, mut c1, mut c2)        = (colors[0], colors[1], colors[2]);
+    let mut area                    = edge(p0, p1, p2);
+
+    if area == 0.0 || !area.is_finite() {
+        // Degenerate triangle
+        return;
+    }
+
+    if area < 0.0 {
+        ::std::mem::swap(&mut p1, &mut p2);
+        ::std::mem::swap(&mut c1, &mut c2);
+        area = -area;
+    }
+
+    // Work out the pixels that the triangle covers
+    let min_x = p0.0.min(p1.0).min(p2.0).floor().max(0.0) as usize;
+    let min_y = p0.1.min(p1.1).min(p2.1).floor().max(0.0) as usize;
+    let max_x = (p0.0.max(p1.0).max(p2.0).ceil().max(0.0) as usize).min(width);
+    let max_y = (p0.1.max(p1.1).max(p2.1).ceil().max(0.0) as usize).min(height);
+
+    let top_left = [is_top_left(p1, p2), is_top_left(p2, p0), is_top_left(p0, p1)];
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            // Sample at the center of the pixel
+            let pos     = (x as f32 + 0.5, y as f32 + 0.5);
+            let weights = [edge(p1, p2, pos), edge(p2, p0, pos), edge(p0, p1, pos)];
+
+            let inside  = weights.iter()
+                .zip(top_left.iter())
+                .all(|(weight, top_left)| *weight > 0.0 || (*weight == 0.0 && *top_left));
+
+            if !inside { continue; }
+
+            // Interpolate the colour
+            let (w0, w1, w2)    = (weights[0]/area, weights[1]/area, weights[2]/area);
+            let mut color       = [0.0; 4];
+            for channel in 0..4 {
+                color[channel] = c0[channel]*w0 + c1[channel]*w1 + c2[channel]*w2;
+            }
+
+            // Shade and blend the pixel
+            let color           = shader(x, y, color);
+            let dst             = target.pixel(x, y);
+            let blended         = target.mask_channels(blend(blend_mode, color, dst));
+
+            target.pixels_mut()[x + y*width] = blended;
+        }
+    }
+}
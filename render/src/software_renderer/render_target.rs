@@ -0,0 +1,135 @@
+use crate::action::*;
+
+///
+/// A render target for the software renderer
+///
+/// Pixels are stored as floating-point RGBA values, with the first row representing the bottom of the image (matching
+/// the OpenGL convention)
+///
+#[derive(Clone)]
+pub struct SoftwareRenderTarget {
+    /// The width of this render target in pixels
+    width: usize,
+
+    /// The height of this render target in pixels
+    height: usize,
+
+    /// The type of this render target
+    render_type: RenderTargetType,
+
+    /// The pixels in this render target
+    pixels: Vec<[f32; 4]>
+}
+
+impl SoftwareRenderTarget {
+    ///
+    /// Creates a new render target, with all of its pixels set to transparent
+    ///
+    pub fn new(width: usize, height: usize, render_type: RenderTargetType) -> SoftwareRenderTarget {
+        SoftwareRenderTarget {
+            width:          width,
+            height:         height,
+            render_type:    render_type,
+            pixels:         vec![[0.0, 0.0, 0.0, 0.0]; width*height]
+        }
+    }
+
+    ///
+    /// Retrieves the size of this render target
+    ///
+    pub fn get_size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    ///
+    /// Retrieves the type of this render target
+    ///
+    pub fn render_type(&self) -> RenderTargetType {
+        self.render_type
+    }
+
+    ///
+    /// Retrieves the pixels for this render target
+    ///
+    pub fn pixels(&self) -> &[[f32; 4]] {
+        &self.pixels
+    }
+
+    ///
+    /// Retrieves the pixels for this render target for editing
+    ///
+    pub (crate) fn pixels_mut(&mut self) -> &mut [[f32; 4]] {
+        &mut self.pixels
+    }
+
+    ///
+    /// Retrieves the pixel at the specified position
+    ///
+    #[inline]
+    pub fn pixel(&self, x: usize, y: usize) -> [f32; 4] {
+        self.pixels[x + y*self.width]
+    }
+
+    ///
+    /// Sets every pixel in this render target to the specified colour
+    ///
+    pub fn clear(&mut self, color: [f32; 4]) {
+        let color = self.mask_channels(color);
+
+        for pixel in self.pixels.iter_mut() {
+            *pixel = color;
+        }
+    }
+
+    ///
+    /// Removes any channels that are not written by this render target type
+    ///
+    #[inline]
+    pub (crate) fn mask_channels(&self, color: [f32; 4]) -> [f32; 4] {
+        match self.render_type {
+            RenderTargetType::Monochrome                    |
+            RenderTargetType::MonochromeMultisampledTexture => [color[0], 0.0, 0.0, 0.0],
+            _                                               => color
+        }
+    }
+
+    ///
+    /// Copies the pixels from another render target into this one at the specified position (clipping to the bounds of this target)
+    ///
+    pub fn blit_from(&mut self, source: &SoftwareRenderTarget, x: i32, y: i32) {
+        for source_y in 0..source.height {
+            let target_y = source_y as i32 + y;
+            if target_y < 0 || target_y >= self.height as i32 { continue; }
+
+            for source_x in 0..source.width {
+                let target_x = source_x as i32 + x;
+                if target_x < 0 || target_x >= self.width as i32 { continue; }
+
+                let target_idx = (target_x as usize) + (target_y as usize)*self.width;
+                self.pixels[target_idx] = self.mask_channels(source.pixel(source_x, source_y));
+            }
+        }
+    }
+
+    ///
+    /// Reads the pixels in this render target as 8-bit RGBA values, with the top row first
+    ///
+    /// The colour channels are stored premultiplied by alpha (as they are after blending with `SourceOver`), so they're
+    /// divided by the alpha value here. Pixels with no alpha (including every pixel in a monochrome target) are written
+    /// unchanged.
+    ///
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(self.width*self.height*4);
+
+        for y in (0..self.height).rev() {
+            for x in 0..self.width {
+                let [r, g, b, a]    = self.pixel(x, y);
+                let (r, g, b)       = if a > 0.0 { (r/a, g/a, b/a) } else { (r, g, b) };
+
+                result.extend([r, g, b, a].iter().map(|component| (component.max(0.0).min(1.0) * 255.0).round() as u8));
+            }
+        }
+
+        result
+    }
+}
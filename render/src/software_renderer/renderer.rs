@@ -0,0 +1,291 @@
+use super::rasterize::*;
+use super::render_target::*;
+
+use crate::action::*;
+use crate::buffer::*;
+
+use std::mem;
+use std::ops::{Range};
+
+///
+/// Renderer that performs rendering actions on the CPU
+///
+/// This is much slower than the OpenGL renderer, but doesn't require a graphics context, so it's useful for generating
+/// images off-screen (for exports, thumbnails or tests). Each render target has a single sample per pixel: anti-aliasing
+/// can be performed by rendering at a higher resolution and downsampling the result.
+///
+pub struct SoftwareRenderer {
+    /// The vertex buffers allocated to this renderer
+    buffers: Vec<Option<Vec<Vertex2D>>>,
+
+    /// The index buffers allocated to this renderer
+    index_buffers: Vec<Option<Vec<u16>>>,
+
+    /// The 'main' render target that represents the output for this renderer
+    frame_buffer: SoftwareRenderTarget,
+
+    /// The render targets assigned to this renderer
+    render_targets: Vec<Option<SoftwareRenderTarget>>,
+
+    /// The render target that each texture is attached to
+    textures: Vec<Option<RenderTargetId>>,
+
+    /// The render target that's currently selected (None for the main frame buffer)
+    current_target: Option<RenderTargetId>,
+
+    /// The active transformation matrix
+    transform: Matrix,
+
+    /// The active blend mode
+    blend_mode: BlendMode,
+
+    /// The active shader
+    shader: ShaderType
+}
+
+impl SoftwareRenderer {
+    ///
+    /// Creates a new software renderer with a frame buffer of the specified size
+    ///
+    pub fn new(width: usize, height: usize) -> SoftwareRenderer {
+        SoftwareRenderer {
+            buffers:        vec![],
+            index_buffers:  vec![],
+            frame_buffer:   SoftwareRenderTarget::new(width, height, RenderTargetType::Standard),
+            render_targets: vec![],
+            textures:       vec![],
+            current_target: None,
+            transform:      Matrix::identity(),
+            blend_mode:     BlendMode::SourceOver,
+            shader:         ShaderType::Simple { erase_texture: None }
+        }
+    }
+
+    ///
+    /// Retrieves the main frame buffer for this renderer
+    ///
+    pub fn frame_buffer(&self) -> &SoftwareRenderTarget {
+        &self.frame_buffer
+    }
+
+    ///
+    /// Retrieves the render target with the specified ID, if it exists
+    ///
+    pub fn render_target(&self, RenderTargetId(render_id): RenderTargetId) -> Option<&SoftwareRenderTarget> {
+        self.render_targets.get(render_id).and_then(|target| target.as_ref())
+    }
+
+    ///
+    /// Performs rendering of the specified actions to this renderer
+    ///
+    pub fn render<Actions: IntoIterator<Item=RenderAction>>(&mut self, actions: Actions) {
+        for action in actions {
+            use self::RenderAction::*;
+
+            match action {
+                SetTransform(matrix)                                                    => { self.transform = matrix; }
+                CreateVertex2DBuffer(VertexBufferId(id), vertices)                      => { Self::store(&mut self.buffers, id, vertices); }
+                CreateIndexBuffer(IndexBufferId(id), indices)                           => { Self::store(&mut self.index_buffers, id, indices); }
+                FreeVertexBuffer(VertexBufferId(id))                                    => { self.buffers.get_mut(id).map(|buffer| *buffer = None); }
+                BlendMode(blend_mode)                                                   => { self.blend_mode = blend_mode; }
+                CreateRenderTarget(render_id, texture_id, width, height, render_type)   => { self.create_render_target(render_id, texture_id, width, height, render_type); }
+                FreeRenderTarget(RenderTargetId(render_id))                             => { self.render_targets.get_mut(render_id).map(|target| *target = None); }
+                SelectRenderTarget(render_id)                                           => { self.current_target = Some(render_id); }
+                RenderToFrameBuffer                                                     => { self.current_target = None; }
+                DrawFrameBuffer(render_id, x, y)                                        => { self.draw_frame_buffer(render_id, x, y); }
+                ShowFrameBuffer                                                         => { /* Rendering is always to memory so there's nothing to show */ }
+                CreateTextureBgra(_texture_id, _width, _height)                         => { /* Textures are only supported as attachments to render targets */ }
+                FreeTexture(TextureId(texture_id))                                      => { self.textures.get_mut(texture_id).map(|texture| *texture = None); }
+                Clear(color)                                                            => { self.clear(color); }
+                UseShader(shader_type)                                                  => { self.shader = shader_type; }
+                DrawTriangles(buffer_id, buffer_range)                                  => { self.draw_triangles(buffer_id, buffer_range); }
+                DrawIndexedTriangles(vertex_buffer, index_buffer, num_vertices)         => { self.draw_indexed_triangles(vertex_buffer, index_buffer, num_vertices); }
+            }
+        }
+    }
+
+    ///
+    /// Stores an item in a list of buffers, extending the list as needed
+    ///
+    fn store<T>(buffers: &mut Vec<Option<T>>, id: usize, item: T) {
+        while buffers.len() <= id {
+            buffers.push(None);
+        }
+
+        buffers[id] = Some(item);
+    }
+
+    ///
+    /// Creates a new render target
+    ///
+    fn create_render_target(&mut self, render_id: RenderTargetId, TextureId(texture_id): TextureId, width: usize, height: usize, render_type: RenderTargetType) {
+        let RenderTargetId(render_index) = render_id;
+
+        Self::store(&mut self.render_targets, render_index, SoftwareRenderTarget::new(width, height, render_type));
+        Self::store(&mut self.textures, texture_id, render_id);
+    }
+
+    ///
+    /// Retrieves the render target that drawing instructions are currently being sent to
+    ///
+    fn current_target(&mut self) -> Option<&mut SoftwareRenderTarget> {
+        match self.current_target {
+            None                            => Some(&mut self.frame_buffer),
+            Some(RenderTargetId(render_id)) => self.render_targets.get_mut(render_id).and_then(|target| target.as_mut())
+        }
+    }
+
+    ///
+    /// Clears the current render target
+    ///
+    fn clear(&mut self, Rgba8([r, g, b, a]): Rgba8) {
+        let color = [(r as f32)/255.0, (g as f32)/255.0, (b as f32)/255.0, (a as f32)/255.0];
+
+        self.current_target().map(|target| target.clear(color));
+    }
+
+    ///
+    /// Draws a frame buffer at a location
+    ///
+    fn draw_frame_buffer(&mut self, RenderTargetId(source_buffer): RenderTargetId, x: i32, y: i32) {
+        // Take the source buffer so the target can be borrowed mutably
+        let source = self.render_targets.get_mut(source_buffer).and_then(|target| target.take());
+
+        if let Some(source) = source {
+            self.current_target().map(|target| target.blit_from(&source, x, y));
+            self.render_targets[source_buffer] = Some(source);
+        }
+    }
+
+    ///
+    /// Draw triangles from a buffer
+    ///
+    fn draw_triangles(&mut self, VertexBufferId(buffer_id): VertexBufferId, buffer_range: Range<usize>) {
+        let vertices = self.buffers.get(buffer_id).and_then(|buffer| buffer.as_ref())
+            .map(|vertices| {
+                let end = buffer_range.end.min(vertices.len());
+                let start = buffer_range.start.min(end);
+
+                vertices[start..end].to_vec()
+            });
+
+        if let Some(vertices) = vertices {
+            self.draw_vertices(vertices.into_iter());
+        }
+    }
+
+    ///
+    /// Draw triangles using an index buffer
+    ///
+    fn draw_indexed_triangles(&mut self, VertexBufferId(vertex_buffer): VertexBufferId, IndexBufferId(index_buffer): IndexBufferId, num_vertices: usize) {
+        let vertices = self.buffers.get(vertex_buffer).and_then(|buffer| buffer.as_ref());
+        let indices  = self.index_buffers.get(index_buffer).and_then(|buffer| buffer.as_ref());
+
+        let vertices = if let (Some(vertices), Some(indices)) = (vertices, indices) {
+            indices.iter()
+                .take(num_vertices)
+                .filter_map(|index| vertices.get(*index as usize).cloned())
+                .collect::<Vec<_>>()
+        } else {
+            return;
+        };
+
+        self.draw_vertices(vertices.into_iter());
+    }
+
+    ///
+    /// Draws a list of vertices as triangles to the current render target
+    ///
+    fn draw_vertices<VertexIter: Iterator<Item=Vertex2D>>(&mut self, vertices: VertexIter) {
+        // Take the target we're drawing to so we can read from the erase texture while drawing
+        let mut target = match self.current_target {
+            None                            => mem::replace(&mut self.frame_buffer, SoftwareRenderTarget::new(0, 0, RenderTargetType::Standard)),
+            Some(RenderTargetId(render_id)) => match self.render_targets.get_mut(render_id).and_then(|target| target.take()) {
+                Some(target)    => target,
+                None            => { return; }
+            }
+        };
+
+        // Find the erase texture, if there is one
+        let erase_target = match self.shader {
+            ShaderType::Simple { erase_texture: Some(TextureId(texture_id)) } => {
+                self.textures.get(texture_id)
+                    .and_then(|render_id| render_id.as_ref())
+                    .and_then(|RenderTargetId(render_id)| self.render_targets.get(*render_id))
+                    .and_then(|target| target.as_ref())
+            }
+
+            _ => None
+        };
+
+        // The shader reduces the alpha of anything that's drawn by the value in the erase texture
+        let (width, height) = target.get_size();
+        let shader = |x: usize, y: usize, color: [f32; 4]| {
+            if let Some(erase_target) = erase_target {
+                let (erase_width, erase_height) = erase_target.get_size();
+                let erase_x                     = (x * erase_width) / width.max(1);
+                let erase_y                     = (y * erase_height) / height.max(1);
+
+                if erase_x < erase_width && erase_y < erase_height {
+                    let erase = 1.0 - erase_target.pixel(erase_x, erase_y)[0];
+                    [color[0]*erase, color[1]*erase, color[2]*erase, color[3]*erase]
+                } else {
+                    color
+                }
+            } else {
+                color
+            }
+        };
+
+        // Draw the triangles
+        let vertices    = vertices.collect::<Vec<_>>();
+        let transform   = self.transform;
+        let blend_mode  = self.blend_mode;
+
+        for triangle in vertices.chunks_exact(3) {
+            let points = [
+                Self::to_pixel(&transform, &triangle[0], width, height),
+                Self::to_pixel(&transform, &triangle[1], width, height),
+                Self::to_pixel(&transform, &triangle[2], width, height)
+            ];
+            let colors = [
+                Self::to_color(&triangle[0]),
+                Self::to_color(&triangle[1]),
+                Self::to_color(&triangle[2])
+            ];
+
+            fill_triangle(&mut target, points, colors, blend_mode, &shader);
+        }
+
+        // Return the target to where it came from
+        match self.current_target {
+            None                            => { self.frame_buffer = target; }
+            Some(RenderTargetId(render_id)) => { self.render_targets[render_id] = Some(target); }
+        }
+    }
+
+    ///
+    /// Converts a vertex position to pixel coordinates using a transformation matrix
+    ///
+    #[inline]
+    fn to_pixel(Matrix(matrix): &Matrix, vertex: &Vertex2D, width: usize, height: usize) -> (f32, f32) {
+        let pos     = vertex.pos;
+        let (x, y)  = (pos[0], pos[1]);
+
+        let ndc_x   = matrix[0][0]*x + matrix[0][1]*y + matrix[0][3];
+        let ndc_y   = matrix[1][0]*x + matrix[1][1]*y + matrix[1][3];
+
+        ((ndc_x+1.0)/2.0 * (width as f32), (ndc_y+1.0)/2.0 * (height as f32))
+    }
+
+    ///
+    /// Converts the colour of a vertex to floating point components
+    ///
+    #[inline]
+    fn to_color(vertex: &Vertex2D) -> [f32; 4] {
+        let color           = vertex.color;
+        let [r, g, b, a]    = color;
+
+        [(r as f32)/255.0, (g as f32)/255.0, (b as f32)/255.0, (a as f32)/255.0]
+    }
+}
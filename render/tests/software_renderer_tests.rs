@@ -0,0 +1,138 @@
+use flo_render::*;
+
+///
+/// Creates a vertex with the specified position and colour
+///
+fn vertex(x: f32, y: f32, color: [u8; 4]) -> Vertex2D {
+    Vertex2D { pos: [x, y], tex_coord: [0.0, 0.0], color: color }
+}
+
+#[test]
+fn fill_triangle_covering_viewport() {
+    let mut renderer = SoftwareRenderer::new(4, 4);
+
+    renderer.render(vec![
+        RenderAction::Clear(Rgba8([0, 0, 0, 0])),
+        RenderAction::BlendMode(BlendMode::SourceOver),
+        RenderAction::CreateVertex2DBuffer(VertexBufferId(0), vec![
+            vertex(-1.0, -1.0, [255, 0, 0, 255]),
+            vertex(3.0, -1.0, [255, 0, 0, 255]),
+            vertex(-1.0, 3.0, [255, 0, 0, 255])
+        ]),
+        RenderAction::DrawTriangles(VertexBufferId(0), 0..3)
+    ]);
+
+    let pixels = renderer.frame_buffer().to_rgba8();
+
+    assert!(pixels.len() == 4*4*4);
+    assert!(pixels.chunks(4).all(|pixel| pixel == &[255, 0, 0, 255]));
+}
+
+#[test]
+fn fill_half_of_viewport() {
+    let mut renderer = SoftwareRenderer::new(4, 4);
+
+    // Triangle covering the bottom half of the viewport
+    renderer.render(vec![
+        RenderAction::BlendMode(BlendMode::SourceOver),
+        RenderAction::CreateVertex2DBuffer(VertexBufferId(0), vec![
+            vertex(-1.0, -1.0, [0, 0, 255, 255]),
+            vertex(3.0, -1.0, [0, 0, 255, 255]),
+            vertex(-1.0, 0.0, [0, 0, 255, 255]),
+            vertex(3.0, 0.0, [0, 0, 255, 255])
+        ]),
+        RenderAction::CreateIndexBuffer(IndexBufferId(0), vec![0, 1, 3, 0, 3, 2]),
+        RenderAction::DrawIndexedTriangles(VertexBufferId(0), IndexBufferId(0), 6)
+    ]);
+
+    let target = renderer.frame_buffer();
+
+    // Bottom row is first in the render target
+    assert!(target.pixel(1, 0) == [0.0, 0.0, 1.0, 1.0]);
+    assert!(target.pixel(1, 1) == [0.0, 0.0, 1.0, 1.0]);
+    assert!(target.pixel(1, 2) == [0.0, 0.0, 0.0, 0.0]);
+    assert!(target.pixel(1, 3) == [0.0, 0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn shared_edges_are_drawn_once() {
+    let mut renderer = SoftwareRenderer::new(8, 8);
+
+    // Two translucent triangles that share a diagonal edge: blending twice would make the edge darker
+    renderer.render(vec![
+        RenderAction::BlendMode(BlendMode::SourceOver),
+        RenderAction::CreateVertex2DBuffer(VertexBufferId(0), vec![
+            vertex(-1.0, -1.0, [255, 255, 255, 128]),
+            vertex(1.0, -1.0, [255, 255, 255, 128]),
+            vertex(1.0, 1.0, [255, 255, 255, 128]),
+            vertex(-1.0, 1.0, [255, 255, 255, 128])
+        ]),
+        RenderAction::CreateIndexBuffer(IndexBufferId(0), vec![0, 1, 2, 0, 2, 3]),
+        RenderAction::DrawIndexedTriangles(VertexBufferId(0), IndexBufferId(0), 6)
+    ]);
+
+    let target      = renderer.frame_buffer();
+    let first_alpha = target.pixel(0, 0)[3];
+
+    for y in 0..8 {
+        for x in 0..8 {
+            assert!((target.pixel(x, y)[3] - first_alpha).abs() < 0.001);
+        }
+    }
+}
+
+#[test]
+fn erase_texture_removes_drawing() {
+    let mut renderer = SoftwareRenderer::new(4, 4);
+
+    let full_screen = vec![
+        vertex(-1.0, -1.0, [255, 255, 255, 255]),
+        vertex(3.0, -1.0, [255, 255, 255, 255]),
+        vertex(-1.0, 3.0, [255, 255, 255, 255])
+    ];
+
+    renderer.render(vec![
+        RenderAction::CreateRenderTarget(RenderTargetId(0), TextureId(0), 4, 4, RenderTargetType::Multisampled),
+        RenderAction::CreateRenderTarget(RenderTargetId(1), TextureId(1), 4, 4, RenderTargetType::MonochromeMultisampledTexture),
+        RenderAction::CreateVertex2DBuffer(VertexBufferId(0), full_screen),
+
+        // Fill the erase texture
+        RenderAction::SelectRenderTarget(RenderTargetId(1)),
+        RenderAction::BlendMode(BlendMode::AllChannelAlphaDestinationOver),
+        RenderAction::DrawTriangles(VertexBufferId(0), 0..3),
+
+        // Draw using the erase texture
+        RenderAction::SelectRenderTarget(RenderTargetId(0)),
+        RenderAction::UseShader(ShaderType::Simple { erase_texture: Some(TextureId(1)) }),
+        RenderAction::BlendMode(BlendMode::DestinationOver),
+        RenderAction::DrawTriangles(VertexBufferId(0), 0..3),
+
+        // Copy to the frame buffer
+        RenderAction::RenderToFrameBuffer,
+        RenderAction::DrawFrameBuffer(RenderTargetId(0), 0, 0)
+    ]);
+
+    let pixels = renderer.frame_buffer().to_rgba8();
+    assert!(pixels.chunks(4).all(|pixel| pixel[3] == 0));
+}
+
+#[test]
+fn translucent_pixels_are_not_darkened() {
+    let mut renderer = SoftwareRenderer::new(4, 4);
+
+    renderer.render(vec![
+        RenderAction::Clear(Rgba8([0, 0, 0, 0])),
+        RenderAction::BlendMode(BlendMode::SourceOver),
+        RenderAction::CreateVertex2DBuffer(VertexBufferId(0), vec![
+            vertex(-1.0, -1.0, [255, 0, 0, 128]),
+            vertex(3.0, -1.0, [255, 0, 0, 128]),
+            vertex(-1.0, 3.0, [255, 0, 0, 128])
+        ]),
+        RenderAction::DrawTriangles(VertexBufferId(0), 0..3)
+    ]);
+
+    // The frame buffer is premultiplied, but the RGBA8 output should not be
+    let pixels = renderer.frame_buffer().to_rgba8();
+
+    assert!(pixels.chunks(4).all(|pixel| pixel == &[255, 0, 0, 128]));
+}
@@ -0,0 +1,153 @@
+use super::canvas_renderer::*;
+
+use flo_render as render;
+use flo_canvas as canvas;
+use flo_canvas::{GraphicsContext};
+
+use futures::prelude::*;
+
+///
+/// Describes how an image should be rendered off-screen
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct OffscreenRenderSettings {
+    /// The size of the canvas being rendered (in canvas units: for an animation this is its size)
+    pub canvas_size: (f64, f64),
+
+    /// The width and height of the resulting image in pixels
+    pub resolution: (usize, usize),
+
+    /// The number of samples to take along each axis of each pixel (1 disables anti-aliasing, 4 generates 16 samples per pixel)
+    pub supersampling: usize,
+
+    /// The colour to render behind the image
+    pub background: canvas::Color
+}
+
+impl OffscreenRenderSettings {
+    ///
+    /// Creates the default settings for rendering a canvas of the specified size at 1:1 scale
+    ///
+    pub fn new(canvas_size: (f64, f64)) -> OffscreenRenderSettings {
+        OffscreenRenderSettings {
+            canvas_size:    canvas_size,
+            resolution:     (canvas_size.0.ceil().max(1.0) as usize, canvas_size.1.ceil().max(1.0) as usize),
+            supersampling:  4,
+            background:     canvas::Color::Rgba(1.0, 1.0, 1.0, 1.0)
+        }
+    }
+
+    ///
+    /// Sets the resolution of the resulting image
+    ///
+    pub fn with_resolution(mut self, width: usize, height: usize) -> OffscreenRenderSettings {
+        self.resolution = (width, height);
+        self
+    }
+
+    ///
+    /// Sets the supersampling factor to use when rendering
+    ///
+    pub fn with_supersampling(mut self, supersampling: usize) -> OffscreenRenderSettings {
+        self.supersampling = supersampling;
+        self
+    }
+
+    ///
+    /// Sets the background colour of the resulting image
+    ///
+    pub fn with_background(mut self, background: canvas::Color) -> OffscreenRenderSettings {
+        self.background = background;
+        self
+    }
+}
+
+///
+/// Renders a set of canvas drawing instructions off-screen, returning the result as 8-bit RGBA pixels, with the top row first
+///
+/// The drawing is scaled so that the canvas (with (0,0) at the bottom-left) fills the image. Animation frames can be
+/// rendered by passing in the drawing generated by `Frame::render_to`.
+///
+pub fn render_drawing_offscreen<'a>(drawing: Vec<canvas::Draw>, settings: OffscreenRenderSettings) -> impl 'a+Future<Output=Vec<u8>> {
+    async move {
+        let (width, height)     = settings.resolution;
+        let supersampling       = settings.supersampling.max(1);
+        let (canvas_width, canvas_height) = settings.canvas_size;
+
+        if width == 0 || height == 0 {
+            return vec![];
+        }
+
+        // Render at a higher resolution so we can downsample the result
+        let sample_width        = width * supersampling;
+        let sample_height       = height * supersampling;
+
+        // Set up the canvas so it fills the render target
+        let mut preamble: Vec<canvas::Draw> = vec![];
+        preamble.canvas_height(canvas_height as f32);
+        preamble.center_region(0.0, 0.0, canvas_width as f32, canvas_height as f32);
+
+        let drawing             = preamble.into_iter().chain(drawing.into_iter());
+
+        // Tessellate the drawing
+        let mut canvas_renderer = CanvasRenderer::new();
+        canvas_renderer.set_viewport(0.0..(sample_width as f32), 0.0..(sample_height as f32), sample_width as f32, sample_height as f32);
+
+        let actions             = canvas_renderer.draw(drawing).collect::<Vec<_>>().await;
+
+        // Rasterize the result
+        let mut renderer        = render::SoftwareRenderer::new(sample_width, sample_height);
+        renderer.render(actions);
+
+        downsample(renderer.frame_buffer(), supersampling, settings.background)
+    }
+}
+
+///
+/// Averages the samples in a render target to generate the final image, composited over a background colour
+///
+/// The samples are premultiplied by alpha (which is how `SourceOver` blending leaves them), and the result is
+/// converted back to straight alpha once it's been composited.
+///
+fn downsample(samples: &render::SoftwareRenderTarget, supersampling: usize, background: canvas::Color) -> Vec<u8> {
+    let (sample_width, sample_height)   = samples.get_size();
+    let width                           = sample_width / supersampling;
+    let height                          = sample_height / supersampling;
+    let num_samples                     = (supersampling*supersampling) as f32;
+
+    let (bg_r, bg_g, bg_b, bg_a)        = background.to_rgba_components();
+    let mut result                      = Vec::with_capacity(width*height*4);
+
+    // Render targets store the bottom row first
+    for y in (0..height).rev() {
+        for x in 0..width {
+            // Average the samples (which are already premultiplied)
+            let mut pixel = [0.0f32; 4];
+
+            for sample_y in (y*supersampling)..((y+1)*supersampling) {
+                for sample_x in (x*supersampling)..((x+1)*supersampling) {
+                    let [r, g, b, a] = samples.pixel(sample_x, sample_y);
+
+                    pixel[0] += r;
+                    pixel[1] += g;
+                    pixel[2] += b;
+                    pixel[3] += a;
+                }
+            }
+
+            let [r, g, b, a] = [pixel[0]/num_samples, pixel[1]/num_samples, pixel[2]/num_samples, pixel[3]/num_samples];
+
+            // Composite over the background, then un-premultiply the result
+            let out_a   = a + bg_a*(1.0-a);
+            let (r, g, b) = if out_a > 0.0 {
+                ((r + bg_r*bg_a*(1.0-a))/out_a, (g + bg_g*bg_a*(1.0-a))/out_a, (b + bg_b*bg_a*(1.0-a))/out_a)
+            } else {
+                (0.0, 0.0, 0.0)
+            };
+
+            result.extend([r, g, b, out_a].iter().map(|component| (component.max(0.0).min(1.0) * 255.0).round() as u8));
+        }
+    }
+
+    result
+}
@@ -0,0 +1,57 @@
+use flo_render_canvas::*;
+use flo_canvas::*;
+
+use futures::executor;
+
+#[test]
+fn render_circle_offscreen() {
+    // Red circle in the middle of a 100x100 canvas
+    let mut draw_circle = vec![];
+    draw_circle.new_path();
+    draw_circle.circle(50.0, 50.0, 25.0);
+    draw_circle.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+    draw_circle.fill();
+
+    let settings    = OffscreenRenderSettings::new((100.0, 100.0))
+        .with_resolution(20, 20)
+        .with_supersampling(2)
+        .with_background(Color::Rgba(0.0, 0.0, 1.0, 1.0));
+    let pixels      = executor::block_on(render_drawing_offscreen(draw_circle, settings));
+
+    assert!(pixels.len() == 20*20*4);
+
+    // Center should be the circle, corners should be the background
+    let pixel_at    = |x: usize, y: usize| { let idx = (x + y*20)*4; [pixels[idx], pixels[idx+1], pixels[idx+2], pixels[idx+3]] };
+
+    assert!(pixel_at(10, 10) == [255, 0, 0, 255]);
+    assert!(pixel_at(0, 0) == [0, 0, 255, 255]);
+    assert!(pixel_at(19, 19) == [0, 0, 255, 255]);
+}
+
+#[test]
+fn render_translucent_fill_offscreen() {
+    // Half-transparent red rectangle covering the whole canvas
+    let mut draw_rect = vec![];
+    draw_rect.new_path();
+    draw_rect.rect(-10.0, -10.0, 110.0, 110.0);
+    draw_rect.fill_color(Color::Rgba(1.0, 0.0, 0.0, 0.5));
+    draw_rect.fill();
+
+    let pixel_at    = |pixels: &Vec<u8>, x: usize, y: usize| { let idx = (x + y*10)*4; [pixels[idx], pixels[idx+1], pixels[idx+2], pixels[idx+3]] };
+    let is_near     = |pixel: [u8; 4], expected: [u8; 4]| pixel.iter().zip(expected.iter()).all(|(a, b)| (*a as i32 - *b as i32).abs() <= 1);
+
+    // Over a transparent background, the colour should be unchanged and the alpha should be half
+    let settings    = OffscreenRenderSettings::new((100.0, 100.0))
+        .with_resolution(10, 10)
+        .with_supersampling(2)
+        .with_background(Color::Rgba(0.0, 0.0, 0.0, 0.0));
+    let pixels      = executor::block_on(render_drawing_offscreen(draw_rect.clone(), settings));
+
+    assert!(is_near(pixel_at(&pixels, 5, 5), [255, 0, 0, 128]));
+
+    // Over white, the result should be pink rather than a darkened red
+    let settings    = settings.with_background(Color::Rgba(1.0, 1.0, 1.0, 1.0));
+    let pixels      = executor::block_on(render_drawing_offscreen(draw_rect, settings));
+
+    assert!(is_near(pixel_at(&pixels, 5, 5), [255, 128, 128, 255]));
+}
@@ -1,12 +1,26 @@
 /***
  **
- ** FlowBetween File format version 4
+ ** FlowBetween File format version 7
  **
  **   V4 of the file format moves the bulk of the work of data representation into the animation and its serialization
  **   format, which greatly simplifies the content of the database.
  **
+ **   V5 adds a version number table so that older files can be upgraded when the format changes.
+ **
+ **   V6 adds named checkpoints in the edit log.
+ **
+ **   V7 adds session markers, used to detect when a file was not closed cleanly.
+ **
  ***/
 
+/**
+ * The version number of the file format (used for upgrading)
+ */
+CREATE TABLE FloStorageVersion (
+    VersionNumber INTEGER NOT NULL
+);
+INSERT INTO FloStorageVersion (VersionNumber) VALUES (7);
+
 /**
  * Represents the global properties for the animation
  */
@@ -23,6 +37,23 @@ CREATE TABLE EditLog (
     Edit TEXT NOT NULL
 );
 
+/**
+ * Named points in the edit log
+ */
+CREATE TABLE Checkpoints (
+    Name TEXT NOT NULL PRIMARY KEY,
+    EditIndex INTEGER NOT NULL
+) WITHOUT ROWID;
+
+/**
+ * The sessions that currently have this file open (rows left behind after a crash indicate an unclean shutdown)
+ */
+CREATE TABLE Sessions (
+    SessionId INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    ProcessId INTEGER NOT NULL,
+    OpenedAt INTEGER NOT NULL
+);
+
 /**
  * An element definition
  */
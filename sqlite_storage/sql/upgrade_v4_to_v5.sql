@@ -0,0 +1,12 @@
+/***
+ **
+ ** Upgrades FlowBetween file format version 4 to version 5
+ **
+ **   Version 4 files have no version number table: this adds one. The version number itself is written
+ **   once the upgrade has completed.
+ **
+ ***/
+
+CREATE TABLE FloStorageVersion (
+    VersionNumber INTEGER NOT NULL
+);
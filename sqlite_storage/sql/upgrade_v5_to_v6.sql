@@ -0,0 +1,12 @@
+/***
+ **
+ ** Upgrades FlowBetween file format version 5 to version 6
+ **
+ **   Version 6 adds named checkpoints to the edit log.
+ **
+ ***/
+
+CREATE TABLE Checkpoints (
+    Name TEXT NOT NULL PRIMARY KEY,
+    EditIndex INTEGER NOT NULL
+) WITHOUT ROWID;
@@ -0,0 +1,13 @@
+/***
+ **
+ ** Upgrades FlowBetween file format version 6 to version 7
+ **
+ **   Version 7 adds session markers, used to detect when a file was not closed cleanly.
+ **
+ ***/
+
+CREATE TABLE Sessions (
+    SessionId INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    ProcessId INTEGER NOT NULL,
+    OpenedAt INTEGER NOT NULL
+);
@@ -3,9 +3,12 @@
 mod sqlite_core;
 mod sqlite_storage;
 mod sqlite_loader;
+mod sqlite_upgrade;
+mod sqlite_session;
 
 #[cfg(test)] mod sqlite_core_tests;
 #[cfg(test)] mod round_trip_tests;
 
 pub use self::sqlite_storage::*;
 pub use self::sqlite_loader::*;
+pub use self::sqlite_upgrade::{FILE_FORMAT_VERSION};
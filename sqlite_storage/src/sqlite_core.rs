@@ -1,3 +1,6 @@
+use super::sqlite_upgrade;
+use super::sqlite_session;
+
 use flo_animation::storage::*;
 
 use rusqlite;
@@ -18,6 +21,15 @@ pub (super) struct SqliteCore {
 
     /// If the core has encountered an error it can't recover from, this is what it is
     error: Option<(StorageError, String)>,
+
+    /// The ID of the session marker written by this core, if it has started a session
+    session_id: Option<i64>,
+
+    /// The lock indicating to other processes that the session is still live
+    session_lock: Option<sqlite_session::SessionLock>,
+
+    /// True if the connection is read-only (layer caches are not written back, and sessions cannot be started)
+    read_only: bool
 }
 
 impl SqliteCore {
@@ -26,8 +38,24 @@ impl SqliteCore {
     ///
     pub fn new(connection: rusqlite::Connection) -> SqliteCore {
         SqliteCore {
-            connection: connection,
-            error:      None
+            connection:     connection,
+            error:          None,
+            session_id:     None,
+            session_lock:   None,
+            read_only:      false
+        }
+    }
+
+    ///
+    /// Creates a new core from a read-only SQLite connection
+    ///
+    pub fn new_read_only(connection: rusqlite::Connection) -> SqliteCore {
+        SqliteCore {
+            connection:     connection,
+            error:          None,
+            session_id:     None,
+            session_lock:   None,
+            read_only:      true
         }
     }
 
@@ -54,13 +82,115 @@ impl SqliteCore {
         self.check_error(self.connection.execute_batch(&defn))
     }
 
+    ///
+    /// Creates a new animation in a blank database
+    ///
+    /// Databases that already contain any tables are left untouched and the core is put into an error state
+    ///
+    pub fn create_new(&mut self) -> Result<(), StorageError> {
+        if !sqlite_upgrade::is_blank(&self.connection) {
+            self.error = Some((StorageError::FailedToInitialise, "Cannot create a new animation in a database that already contains data".to_string()));
+            return Err(StorageError::FailedToInitialise);
+        }
+
+        match self.initialize() {
+            Ok(())  => Ok(()),
+            Err(_)  => Err(StorageError::FailedToInitialise)
+        }
+    }
+
+    ///
+    /// Upgrades an existing animation to the latest version of the file format
+    ///
+    /// If the database is blank, is not an animation, is from a newer version of FlowBetween or can't be upgraded, the core
+    /// will be put into an error state
+    ///
+    pub fn upgrade_to_latest(&mut self) -> Result<(), StorageError> {
+        match sqlite_upgrade::upgrade_to_latest(&mut self.connection) {
+            Ok(())              => Ok(()),
+            Err((err, msg))     => {
+                self.error = Some((err.clone(), msg));
+                Err(err)
+            }
+        }
+    }
+
+    ///
+    /// Checks that the database is an animation in the latest file format, for databases that can't be upgraded
+    ///
+    /// If it's not, the core will be put into an error state
+    ///
+    pub fn check_is_latest(&mut self) -> Result<(), StorageError> {
+        match sqlite_upgrade::check_is_latest(&self.connection) {
+            Ok(())              => Ok(()),
+            Err((err, msg))     => {
+                self.error = Some((err.clone(), msg));
+                Err(err)
+            }
+        }
+    }
+
+    ///
+    /// Retrieves the file format version of the database attached to this core
+    ///
+    pub fn file_format_version(&self) -> Option<i64> {
+        sqlite_upgrade::file_format_version(&self.connection)
+    }
+
+    ///
+    /// Marks the database as being open in this process. The marker is removed when the core is dropped, so a marker that is
+    /// still present when the file is next opened indicates that it was not closed cleanly.
+    ///
+    pub fn begin_session(&mut self) -> Result<(), rusqlite::Error> {
+        if self.session_id.is_none() && self.error.is_none() && !self.read_only {
+            let session_id  = sqlite_session::begin_session(&self.connection);
+            let session_id  = self.check_error(session_id)?;
+
+            self.session_id     = Some(session_id);
+            self.session_lock   = sqlite_session::lock_session(&self.connection, session_id).ok().and_then(|lock| lock);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Returns true if the database contains session markers left behind by another process
+    ///
+    pub fn has_unclean_session(&self) -> bool {
+        sqlite_session::count_unclean_sessions(&self.connection).unwrap_or(0) > 0
+    }
+
+    ///
+    /// Returns true if another process that is still running has the database open
+    ///
+    pub fn is_open_elsewhere(&self) -> bool {
+        sqlite_session::count_live_sessions(&self.connection).unwrap_or(0) > 0
+    }
+
+    ///
+    /// Removes any session markers left behind by other processes
+    ///
+    pub fn clear_unclean_sessions(&mut self) -> Result<(), rusqlite::Error> {
+        let result = sqlite_session::clear_unclean_sessions(&self.connection);
+        self.check_error(result)
+    }
+
     ///
     /// Runs some commands on this storage database
     ///
     pub fn run_commands(&mut self, commands: Vec<StorageCommand>) -> Vec<StorageResponse> {
         // If we're in an error state, then the result is just to indicate that we can't continue
-        if let Some((_err, msg)) = self.error.as_ref() {
-            return vec![StorageResponse::Error(StorageError::CannotContinueAfterError, msg.clone())];
+        if let Some((err, msg)) = self.error.as_ref() {
+            // Errors from opening the file are reported as-is, other errors just stop the storage from continuing
+            let err = match err {
+                StorageError::FileFormatTooNew          |
+                StorageError::CannotUpgradeFileFormat   |
+                StorageError::NotAnAnimation            |
+                StorageError::FailedToInitialise        => err.clone(),
+                _                                       => StorageError::CannotContinueAfterError
+            };
+
+            return vec![StorageResponse::Error(err, msg.clone())];
         }
 
         // Process each of the commands in turn and flatten to a single response
@@ -88,6 +218,11 @@ impl SqliteCore {
             ReadHighestUnusedElementId                          => { self.read_highest_unused_element_id() },
             ReadEditLogLength                                   => { self.read_edit_log_length() },
             ReadEdits(edit_range)                               => { self.read_edits(edit_range) },
+            ReadEditLogSize                                     => { self.read_edit_log_size() },
+            ReplaceEdits(num_edits, new_edits)                  => { self.replace_edits(num_edits, new_edits) },
+            WriteCheckpoint(name, edit_index)                   => { self.write_checkpoint(name, edit_index) },
+            DeleteCheckpoint(name)                              => { self.delete_checkpoint(name) },
+            ReadCheckpoints                                     => { self.read_checkpoints() },
             WriteElement(element_id, value)                     => { self.write_element(element_id, value) },
             ReadElement(element_id)                             => { self.read_element(element_id) },
             DeleteElement(element_id)                           => { self.delete_element(element_id) },
@@ -103,6 +238,8 @@ impl SqliteCore {
             DetachElementFromLayer(element_id)                  => { self.detach_element_from_layer(element_id) },
             ReadElementAttachments(element_id)                  => { self.read_element_attachments(element_id) },
             ReadElementsForKeyFrame(layer_id, when)             => { self.read_elements_for_key_frame(layer_id, when) },
+            WriteLayerCache(_, _, _, _) if self.read_only       => { Ok(vec![StorageResponse::Updated]) },
+            DeleteLayerCache(_, _, _) if self.read_only         => { Ok(vec![StorageResponse::Updated]) },
             WriteLayerCache(layer_id, when, cache_type, value)  => { self.write_layer_cache(layer_id, when, cache_type, value) },
             DeleteLayerCache(layer_id, when, cache_type)        => { self.delete_layer_cache(layer_id, when, cache_type) },
             ReadLayerCache(layer_id, when, cache_type)          => { self.read_layer_cache(layer_id, when, cache_type) },
@@ -201,6 +338,83 @@ impl SqliteCore {
         Ok(edits.collect::<Result<_, _>>()?)
     }
 
+    ///
+    /// Reads the number of edits and the total size of the serialized edits in the edit log
+    ///
+    fn read_edit_log_size(&mut self) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut read            = self.connection.prepare_cached("SELECT COALESCE(MAX(EditId), 0), COALESCE(SUM(LENGTH(CAST(Edit AS BLOB))), 0) FROM EditLog;")?;
+        let (count, length)     = read.query_row(NO_PARAMS, |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?;
+
+        Ok(vec![StorageResponse::EditLogSize(count as usize, length as usize)])
+    }
+
+    ///
+    /// Replaces the first few edits in the edit log with a new set of edits, renumbering the remaining edits to follow on from them
+    ///
+    fn replace_edits(&mut self, num_edits: usize, new_edits: Vec<String>) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let transaction = self.connection.transaction()?;
+
+        {
+            // Read the edits that are being kept
+            let mut read        = transaction.prepare_cached("SELECT Edit FROM EditLog WHERE EditId > ? ORDER BY EditId ASC;")?;
+            let retained_edits  = read.query_map(&[num_edits as i64], |row| row.get::<_, String>(0))?;
+            let retained_edits  = retained_edits.collect::<Result<Vec<_>, _>>()?;
+
+            // Clear the existing log and reset the edit IDs so they start at 1 again
+            transaction.execute("DELETE FROM EditLog;", NO_PARAMS)?;
+            transaction.execute("DELETE FROM sqlite_sequence WHERE name = 'EditLog';", NO_PARAMS)?;
+
+            // Checkpoints in the replaced part of the log are removed, and the rest are moved to follow the new edits
+            let num_new_edits   = new_edits.len() as i64;
+            transaction.execute("DELETE FROM Checkpoints WHERE EditIndex < ?;", &[num_edits as i64])?;
+            transaction.execute("UPDATE Checkpoints SET EditIndex = EditIndex - ? + ?;", &[num_edits as i64, num_new_edits])?;
+
+            // Write the new edits followed by the ones that were retained
+            let mut write       = transaction.prepare_cached("INSERT INTO EditLog (EditId, Edit) VALUES (?, ?);")?;
+            for (index, edit) in new_edits.into_iter().chain(retained_edits).enumerate() {
+                write.execute(params![(index as i64)+1, edit])?;
+            }
+        }
+
+        transaction.commit()?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Sets a named checkpoint in the edit log
+    ///
+    fn write_checkpoint(&mut self, name: String, edit_index: usize) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut write   = self.connection.prepare_cached("INSERT OR REPLACE INTO Checkpoints (Name, EditIndex) VALUES (?, ?);")?;
+        write.execute(params![name, edit_index as i64])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Removes a named checkpoint from the edit log
+    ///
+    fn delete_checkpoint(&mut self, name: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut delete  = self.connection.prepare_cached("DELETE FROM Checkpoints WHERE Name = ?;")?;
+        let num_deleted = delete.execute(&[name])?;
+
+        if num_deleted > 0 {
+            Ok(vec![StorageResponse::Updated])
+        } else {
+            Ok(vec![StorageResponse::NotFound])
+        }
+    }
+
+    ///
+    /// Reads the checkpoints in the edit log
+    ///
+    fn read_checkpoints(&mut self) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut read    = self.connection.prepare_cached("SELECT Name, EditIndex FROM Checkpoints ORDER BY EditIndex ASC, Name ASC;")?;
+        let checkpoints = read.query_map(NO_PARAMS, |row| Ok(StorageResponse::Checkpoint(row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize)))?;
+
+        Ok(checkpoints.collect::<Result<_, _>>()?)
+    }
+
     ///
     /// Writes data for an element
     ///
@@ -544,3 +758,15 @@ impl SqliteCore {
         }
     }
 }
+
+impl Drop for SqliteCore {
+    fn drop(&mut self) {
+        // Remove the session marker to indicate that the file was closed cleanly
+        if let Some(session_id) = self.session_id.take() {
+            sqlite_session::end_session(&self.connection, session_id).ok();
+        }
+
+        // Release the lock once the marker is gone
+        self.session_lock.take();
+    }
+}
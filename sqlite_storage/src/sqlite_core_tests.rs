@@ -2,6 +2,10 @@ use flo_animation::storage::*;
 
 use rusqlite;
 use super::sqlite_core::*;
+use super::sqlite_upgrade::*;
+use super::sqlite_loader::*;
+
+use flo_animation::{FileAnimation};
 
 use std::i64;
 use std::time::{Duration};
@@ -16,6 +20,157 @@ fn initialize_database() {
     assert!(result.is_ok());
 }
 
+///
+/// Creates a connection to a database in the version 4 file format (which has no version number table)
+///
+fn v4_database() -> rusqlite::Connection {
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(include_str!["../sql/flo_storage.sql"]).unwrap();
+    connection.execute_batch("DROP TABLE FloStorageVersion; DROP TABLE Checkpoints; DROP TABLE Sessions;").unwrap();
+
+    connection
+}
+
+///
+/// Creates a connection to a database in the version 5 file format (which has no checkpoints table)
+///
+fn v5_database() -> rusqlite::Connection {
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(include_str!["../sql/flo_storage.sql"]).unwrap();
+    connection.execute_batch("DROP TABLE Checkpoints; DROP TABLE Sessions; UPDATE FloStorageVersion SET VersionNumber = 5;").unwrap();
+
+    connection
+}
+
+///
+/// Creates a connection to a database in the version 6 file format (which has no sessions table)
+///
+fn v6_database() -> rusqlite::Connection {
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(include_str!["../sql/flo_storage.sql"]).unwrap();
+    connection.execute_batch("DROP TABLE Sessions; UPDATE FloStorageVersion SET VersionNumber = 6;").unwrap();
+
+    connection
+}
+
+#[test]
+fn new_database_has_current_version() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.file_format_version() == Some(FILE_FORMAT_VERSION));
+}
+
+#[test]
+fn blank_database_is_not_initialised_by_upgrade() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+
+    assert!(core.file_format_version() == None);
+    assert!(core.upgrade_to_latest() == Err(StorageError::NotAnAnimation));
+    assert!(core.file_format_version() == None);
+
+    let result      = core.run_commands(vec![StorageCommand::ReadEditLogLength]);
+    assert!(match &result[0] { StorageResponse::Error(StorageError::NotAnAnimation, _) => true, _ => false });
+}
+
+#[test]
+fn foreign_database_is_rejected() {
+    let connection  = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch("CREATE TABLE Notes (Id INTEGER PRIMARY KEY, Text TEXT);").unwrap();
+
+    let mut core    = SqliteCore::new(connection);
+
+    assert!(core.upgrade_to_latest() == Err(StorageError::NotAnAnimation));
+    assert!(core.file_format_version() == None);
+}
+
+#[test]
+fn create_new_initialises_blank_database() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+
+    assert!(core.create_new().is_ok());
+    assert!(core.file_format_version() == Some(FILE_FORMAT_VERSION));
+    assert!(core.run_commands(vec![StorageCommand::ReadEditLogLength]) == vec![StorageResponse::NumberOfEdits(0)]);
+}
+
+#[test]
+fn create_new_leaves_foreign_database_alone() {
+    let connection  = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch("CREATE TABLE Notes (Id INTEGER PRIMARY KEY, Text TEXT);").unwrap();
+
+    let mut core    = SqliteCore::new(connection);
+
+    assert!(core.create_new() == Err(StorageError::FailedToInitialise));
+    assert!(core.file_format_version() == None);
+
+    let result      = core.run_commands(vec![StorageCommand::ReadEditLogLength]);
+    assert!(match &result[0] { StorageResponse::Error(StorageError::FailedToInitialise, _) => true, _ => false });
+}
+
+#[test]
+fn upgrade_v4_database() {
+    let mut core    = SqliteCore::new(v4_database());
+
+    assert!(core.file_format_version() == Some(4));
+    assert!(core.upgrade_to_latest().is_ok());
+    assert!(core.file_format_version() == Some(FILE_FORMAT_VERSION));
+}
+
+#[test]
+fn upgrade_v5_database() {
+    let mut core    = SqliteCore::new(v5_database());
+
+    assert!(core.file_format_version() == Some(5));
+    assert!(core.upgrade_to_latest().is_ok());
+    assert!(core.file_format_version() == Some(FILE_FORMAT_VERSION));
+    assert!(core.run_commands(vec![StorageCommand::ReadCheckpoints]) == vec![]);
+}
+
+#[test]
+fn upgrade_v6_database() {
+    let mut core    = SqliteCore::new(v6_database());
+
+    assert!(core.file_format_version() == Some(6));
+    assert!(core.upgrade_to_latest().is_ok());
+    assert!(core.file_format_version() == Some(FILE_FORMAT_VERSION));
+    assert!(!core.has_unclean_session());
+    assert!(core.begin_session().is_ok());
+}
+
+#[test]
+fn upgrade_preserves_edits() {
+    let connection  = v4_database();
+    connection.execute_batch("INSERT INTO EditLog (Edit) VALUES ('Test1'); INSERT INTO EditLog (Edit) VALUES ('Test2');").unwrap();
+
+    let mut core    = SqliteCore::new(connection);
+    core.upgrade_to_latest().unwrap();
+
+    assert!(core.run_commands(vec![StorageCommand::ReadEdits(0..2)]) == vec![StorageResponse::Edit(0, "Test1".to_string()), StorageResponse::Edit(1, "Test2".to_string())]);
+}
+
+#[test]
+fn upgrading_latest_version_does_nothing() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.upgrade_to_latest().is_ok());
+    assert!(core.file_format_version() == Some(FILE_FORMAT_VERSION));
+}
+
+#[test]
+fn newer_file_format_is_rejected() {
+    let connection  = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(include_str!["../sql/flo_storage.sql"]).unwrap();
+    connection.execute("UPDATE FloStorageVersion SET VersionNumber = ?", &[FILE_FORMAT_VERSION+1]).unwrap();
+
+    let mut core    = SqliteCore::new(connection);
+
+    assert!(core.upgrade_to_latest() == Err(StorageError::FileFormatTooNew));
+
+    let result      = core.run_commands(vec![StorageCommand::ReadEditLogLength]);
+    assert!(match &result[0] { StorageResponse::Error(StorageError::FileFormatTooNew, _) => true, _ => false });
+}
+
 #[test]
 fn read_no_properties() {
     let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
@@ -71,6 +226,85 @@ fn read_all_edits() {
     assert!(core.run_commands(vec![StorageCommand::ReadEdits(0..2)]) == vec![StorageResponse::Edit(0, "Test1".to_string()), StorageResponse::Edit(1, "Test2".to_string())]);
 }
 
+#[test]
+fn read_edit_log_size() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    core.run_commands(vec![
+            StorageCommand::WriteEdit("Test1".to_string()), 
+            StorageCommand::WriteEdit("Test22".to_string())
+        ]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadEditLogSize]) == vec![StorageResponse::EditLogSize(2, 11)]);
+}
+
+#[test]
+fn replace_edits() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    core.run_commands(vec![
+            StorageCommand::WriteEdit("Test1".to_string()), 
+            StorageCommand::WriteEdit("Test2".to_string()),
+            StorageCommand::WriteEdit("Test3".to_string()),
+            StorageCommand::WriteEdit("Test4".to_string())
+        ]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReplaceEdits(3, vec!["Compacted".to_string()])]) == vec![StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadEditLogLength]) == vec![StorageResponse::NumberOfEdits(2)]);
+    assert!(core.run_commands(vec![StorageCommand::ReadEdits(0..2)]) == vec![StorageResponse::Edit(0, "Compacted".to_string()), StorageResponse::Edit(1, "Test4".to_string())]);
+
+    // New edits follow on from the replaced ones
+    core.run_commands(vec![StorageCommand::WriteEdit("Test5".to_string())]);
+    assert!(core.run_commands(vec![StorageCommand::ReadEdits(2..3)]) == vec![StorageResponse::Edit(2, "Test5".to_string())]);
+}
+
+#[test]
+fn write_and_read_checkpoints() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::WriteCheckpoint("Second".to_string(), 4),
+            StorageCommand::WriteCheckpoint("First".to_string(), 2)
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadCheckpoints]) == vec![StorageResponse::Checkpoint("First".to_string(), 2), StorageResponse::Checkpoint("Second".to_string(), 4)]);
+}
+
+#[test]
+fn delete_checkpoint() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    core.run_commands(vec![StorageCommand::WriteCheckpoint("First".to_string(), 2)]);
+
+    assert!(core.run_commands(vec![StorageCommand::DeleteCheckpoint("First".to_string())]) == vec![StorageResponse::Updated]);
+    assert!(core.run_commands(vec![StorageCommand::DeleteCheckpoint("First".to_string())]) == vec![StorageResponse::NotFound]);
+    assert!(core.run_commands(vec![StorageCommand::ReadCheckpoints]) == vec![]);
+}
+
+#[test]
+fn replace_edits_moves_checkpoints() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    core.run_commands(vec![
+            StorageCommand::WriteEdit("Test1".to_string()), 
+            StorageCommand::WriteEdit("Test2".to_string()),
+            StorageCommand::WriteEdit("Test3".to_string()),
+            StorageCommand::WriteEdit("Test4".to_string()),
+            StorageCommand::WriteCheckpoint("Replaced".to_string(), 1),
+            StorageCommand::WriteCheckpoint("Retained".to_string(), 4)
+        ]);
+
+    core.run_commands(vec![StorageCommand::ReplaceEdits(3, vec!["Compacted".to_string()])]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadCheckpoints]) == vec![StorageResponse::Checkpoint("Retained".to_string(), 2)]);
+}
+
 #[test]
 fn highest_unused_element_id_is_0_with_no_elements() {
     let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
@@ -586,3 +820,127 @@ fn delete_from_layer_cache() {
     assert!(core.run_commands(vec![StorageCommand::ReadLayerCache(1, Duration::from_millis(500), "Type".to_string())]) ==
         vec![StorageResponse::LayerCache("Cache2".to_string())]);
 }
+
+#[test]
+fn own_session_is_not_unclean() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+    core.begin_session().unwrap();
+
+    assert!(!core.has_unclean_session());
+}
+
+#[test]
+fn session_from_other_process_is_unclean() {
+    let connection  = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(include_str!["../sql/flo_storage.sql"]).unwrap();
+    connection.execute("INSERT INTO Sessions (ProcessId, OpenedAt) VALUES (?, 0)", &[std::process::id() as i64 + 1]).unwrap();
+
+    let mut core    = SqliteCore::new(connection);
+    core.begin_session().unwrap();
+
+    assert!(core.has_unclean_session());
+
+    core.clear_unclean_sessions().unwrap();
+    assert!(!core.has_unclean_session());
+}
+
+#[test]
+fn live_session_from_other_process_is_not_unclean() {
+    let path = std::env::temp_dir().join(format!("flo-live-session-test-{}.flo", std::process::id()));
+    std::fs::remove_file(&path).ok();
+
+    {
+        // Start a session and pretend that it belongs to another process
+        let mut live_core   = SqliteCore::new(rusqlite::Connection::open(&path).unwrap());
+        live_core.initialize().unwrap();
+        live_core.begin_session().unwrap();
+
+        let connection      = rusqlite::Connection::open(&path).unwrap();
+        connection.execute("UPDATE Sessions SET ProcessId = ?", &[std::process::id() as i64 + 1]).unwrap();
+
+        // The session is still live, so it should not need recovering or be cleared
+        let mut core        = SqliteCore::new(rusqlite::Connection::open(&path).unwrap());
+        assert!(core.is_open_elsewhere());
+        assert!(!core.has_unclean_session());
+
+        core.clear_unclean_sessions().unwrap();
+        assert!(core.is_open_elsewhere());
+
+        // A session that was left behind by a process that's no longer running is unclean
+        connection.execute("INSERT INTO Sessions (ProcessId, OpenedAt) VALUES (?, 0)", &[std::process::id() as i64 + 2]).unwrap();
+        assert!(core.has_unclean_session());
+
+        core.clear_unclean_sessions().unwrap();
+        assert!(!core.has_unclean_session());
+        assert!(core.is_open_elsewhere());
+    }
+
+    // Once the live session is closed, the file is no longer open elsewhere
+    let core = SqliteCore::new(rusqlite::Connection::open(&path).unwrap());
+    assert!(!core.is_open_elsewhere());
+    assert!(!core.has_unclean_session());
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn read_only_core_does_not_write_to_the_file() {
+    let path = std::env::temp_dir().join(format!("flo-read-only-test-{}.flo", std::process::id()));
+    std::fs::remove_file(&path).ok();
+
+    {
+        let mut core = SqliteCore::new(rusqlite::Connection::open(&path).unwrap());
+        core.initialize().unwrap();
+        core.run_commands(vec![StorageCommand::AddLayer(1, "Layer".to_string())]);
+    }
+
+    {
+        let connection  = rusqlite::Connection::open_with_flags(&path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY).unwrap();
+        let mut core    = SqliteCore::new_read_only(connection);
+
+        assert!(core.check_is_latest().is_ok());
+        core.begin_session().unwrap();
+
+        // Layer caches are silently discarded, and other reads carry on working
+        let result = core.run_commands(vec![StorageCommand::WriteLayerCache(1, Duration::from_millis(0), "Test".to_string(), "Cache".to_string())]);
+        assert!(result == vec![StorageResponse::Updated]);
+
+        let result = core.run_commands(vec![StorageCommand::ReadLayers]);
+        assert!(match &result[0] { StorageResponse::LayerProperties(1, _) => true, _ => false });
+    }
+
+    // No session was started by the read-only core
+    let core = SqliteCore::new(rusqlite::Connection::open(&path).unwrap());
+    assert!(!core.is_open_elsewhere());
+    assert!(!core.has_unclean_session());
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn read_only_core_rejects_older_file_format() {
+    let mut core = SqliteCore::new_read_only(v6_database());
+
+    assert!(core.check_is_latest() == Err(StorageError::CannotUpgradeFileFormat));
+    assert!(core.file_format_version() == Some(6));
+}
+
+#[test]
+fn open_read_only_rejects_missing_files() {
+    let path = std::env::temp_dir().join(format!("flo-missing-read-only-test-{}.flo", std::process::id()));
+    std::fs::remove_file(&path).ok();
+
+    assert!(sqlite_animation_loader().open_read_only(&path).is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn open_read_only_rejects_files_that_are_not_animations() {
+    let path = std::env::temp_dir().join(format!("flo-not-an-animation-test-{}.flo", std::process::id()));
+    std::fs::write(&path, "Not an animation").unwrap();
+
+    assert!(sqlite_animation_loader().open_read_only(&path).is_err());
+
+    std::fs::remove_file(&path).ok();
+}
@@ -6,23 +6,128 @@ use flo_animation::storage::*;
 use futures::prelude::*;
 use rusqlite::{Connection, OpenFlags};
 
+use std::sync::*;
+use std::path::Path;
+
+///
+/// Loads animations stored in SQLite files
+///
+/// Animations opened by this loader keep a journal of the edits that are waiting to be stored, and mark the file as open
+/// while they are being edited, so that edits can be recovered if FlowBetween exits unexpectedly.
+///
+struct SqliteAnimationLoader<TFn>(TFn);
+
+///
+/// Opens the storage for an animation, creating a new file if necessary
+///
+fn open_storage(path: &Path) -> SqliteAnimationStorage {
+    if path.exists() {
+        // Open/restore an existing animation
+        let connection  = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE).unwrap();
+        SqliteAnimationStorage::from_connection(connection)
+    } else {
+        // Create a new animation
+        let connection  = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE).unwrap();
+        SqliteAnimationStorage::new_from_connection(connection)
+    }
+}
+
+///
+/// Opens an animation for editing
+///
+fn open_animation(path: &Path) -> impl EditableAnimation {
+    // Connect to the database and mark it as open
+    let storage = open_storage(path);
+    storage.begin_session();
+
+    // Create the editor for this animation, using a journal if one can be created
+    let journal = EditJournal::open(&EditJournal::path_for_file(path)).ok();
+    create_animation_editor_with_journal(move |commands| storage.get_responses(commands).boxed(), journal)
+}
+
+impl<TFn, TAnim> FileAnimation for SqliteAnimationLoader<TFn>
+where TFn: Send+Sync+Fn(&Path) -> TAnim,
+TAnim: EditableAnimation {
+    type NewAnimation = TAnim;
+
+    ///
+    /// Opens an animation from a file on disk
+    ///
+    fn open(&self, path: &Path) -> Self::NewAnimation {
+        self.0(path)
+    }
+
+    ///
+    /// Opens an animation for reading without starting a session or a journal
+    ///
+    fn open_read_only(&self, path: &Path) -> Result<Arc<dyn Animation>, StorageError> {
+        let storage     = SqliteAnimationStorage::open_file_read_only(path)?;
+        let animation   = create_animation_editor(move |commands| storage.get_responses(commands).boxed());
+
+        Ok(Arc::new(animation))
+    }
+
+    ///
+    /// Returns true if the animation at the specified path was not closed cleanly
+    ///
+    /// Files that another copy of FlowBetween is still editing don't need recovering, even though they have a journal and a
+    /// session marker.
+    ///
+    fn needs_recovery(&self, path: &Path) -> bool {
+        if SqliteAnimationStorage::file_is_open_elsewhere(path) { return false; }
+
+        let journal_path    = EditJournal::path_for_file(path);
+        let pending_edits   = EditJournal::read_pending_edits(&journal_path).map(|edits| edits.len()).unwrap_or(0);
+
+        pending_edits > 0 || SqliteAnimationStorage::file_has_unclean_session(path)
+    }
+
+    ///
+    /// Writes any edits that were lost when the animation was not closed cleanly back to the file
+    ///
+    fn recover(&self, path: &Path) -> Result<(), StorageError> {
+        // The journal of a file that's open elsewhere belongs to the process that's editing it
+        if SqliteAnimationStorage::file_is_open_elsewhere(path) { return Ok(()); }
+
+        let journal_path    = EditJournal::path_for_file(path);
+        let pending_edits   = EditJournal::read_pending_edits(&journal_path).unwrap_or_else(|_| vec![]);
+
+        {
+            // Only existing files can be recovered
+            let storage         = SqliteAnimationStorage::open_file(path).map_err(|_| StorageError::General)?;
+            storage.clear_unclean_sessions();
+
+            // Replay the edits that never reached the file
+            let animation       = create_animation_editor(move |commands| storage.get_responses(commands).boxed());
+            animation.perform_edits(pending_edits);
+
+            // Reading the number of edits waits for the edits to be stored
+            animation.get_num_edits();
+        }
+
+        EditJournal::remove(&journal_path).ok();
+
+        Ok(())
+    }
+
+    ///
+    /// Throws away any edits that could be recovered
+    ///
+    fn discard_recovery(&self, path: &Path) {
+        if SqliteAnimationStorage::file_is_open_elsewhere(path) { return; }
+
+        if path.exists() {
+            let storage = open_storage(path);
+            storage.clear_unclean_sessions();
+        }
+
+        EditJournal::remove(&EditJournal::path_for_file(path)).ok();
+    }
+}
+
 ///
 /// Creates a loader for loading animations stored in SQLite files
 ///
 pub fn sqlite_animation_loader() -> impl FileAnimation {
-    AnimationLoader(|path| {
-        // Connect to the database
-        let storage = if path.exists() {
-            // Open/restore an existing animation
-            let connection  = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE).unwrap();
-            SqliteAnimationStorage::from_connection(connection)
-        } else {
-            // Create a new animation
-            let connection  = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE).unwrap();
-            SqliteAnimationStorage::new_from_connection(connection)
-        };
-
-        // Create the editor for this animation
-        create_animation_editor(move |commands| storage.get_responses(commands).boxed())
-    })
+    SqliteAnimationLoader(open_animation)
 }
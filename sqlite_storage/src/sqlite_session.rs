@@ -0,0 +1,161 @@
+use rusqlite;
+use rusqlite::{NO_PARAMS, ErrorCode};
+
+use std::fs;
+use std::process;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+///
+/// Lock held by a process for as long as one of its sessions is live
+///
+/// The lock is an exclusive SQLite lock on a small file next to the database, named after the session. The operating system
+/// releases file locks when a process exits, so a session whose lock file can be read belongs to a process that has gone away.
+///
+pub (super) struct SessionLock {
+    /// The connection holding the lock
+    connection: Option<rusqlite::Connection>,
+
+    /// The path of the lock file
+    path: PathBuf
+}
+
+impl Drop for SessionLock {
+    fn drop(&mut self) {
+        // Release the lock, then tidy up the file
+        self.connection.take();
+        fs::remove_file(&self.path).ok();
+    }
+}
+
+///
+/// Retrieves the path of the lock file for a session, or `None` if the database is not stored in a file
+///
+fn lock_path(connection: &rusqlite::Connection, session_id: i64) -> Option<PathBuf> {
+    let database_path   = connection.query_row("PRAGMA database_list", NO_PARAMS, |row| row.get::<_, String>(2)).ok()?;
+    if database_path.is_empty() { return None; }
+
+    let mut lock_path   = Path::new(&database_path).as_os_str().to_owned();
+    lock_path.push(format!(".session-{}", session_id));
+
+    Some(PathBuf::from(lock_path))
+}
+
+///
+/// Takes the lock that indicates that a session is live, returning `None` if the database is not stored in a file
+///
+pub (super) fn lock_session(connection: &rusqlite::Connection, session_id: i64) -> Result<Option<SessionLock>, rusqlite::Error> {
+    let path = match lock_path(connection, session_id) {
+        Some(path)  => path,
+        None        => { return Ok(None); }
+    };
+
+    // In exclusive locking mode, SQLite keeps the lock from the first write until the connection is closed
+    let lock = rusqlite::Connection::open(&path)?;
+    lock.execute_batch("PRAGMA locking_mode = EXCLUSIVE; BEGIN EXCLUSIVE; COMMIT;")?;
+
+    Ok(Some(SessionLock { connection: Some(lock), path: path }))
+}
+
+///
+/// Returns true if the process that started a session still has it open
+///
+fn session_is_live(connection: &rusqlite::Connection, session_id: i64) -> bool {
+    let path = match lock_path(connection, session_id) {
+        Some(path)  => path,
+        None        => { return false; }
+    };
+
+    // Sessions without a lock file were not closed cleanly (or were started before the lock files were added)
+    if !path.exists() { return false; }
+
+    let lock = match rusqlite::Connection::open_with_flags(&path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY) {
+        Ok(lock)    => lock,
+        Err(_)      => { return false; }
+    };
+    lock.busy_timeout(Duration::from_millis(0)).ok();
+
+    // Reading the file will fail with a 'busy' error while the session's process is holding its lock
+    match lock.query_row("SELECT COUNT(*) FROM sqlite_master", NO_PARAMS, |row| row.get::<_, i64>(0)) {
+        Err(rusqlite::Error::SqliteFailure(err, _)) => err.code == ErrorCode::DatabaseBusy,
+        _                                           => false
+    }
+}
+
+///
+/// Retrieves the IDs of the sessions started by other processes, along with whether or not they're still live
+///
+fn other_sessions(connection: &rusqlite::Connection) -> Result<Vec<(i64, bool)>, rusqlite::Error> {
+    let process_id  = process::id() as i64;
+
+    let mut select  = connection.prepare_cached("SELECT SessionId FROM Sessions WHERE ProcessId <> ?;")?;
+    let sessions    = select.query_map(&[process_id], |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(sessions.into_iter()
+        .map(|session_id| (session_id, session_is_live(connection, session_id)))
+        .collect())
+}
+
+///
+/// Adds a marker to a database indicating that the current process has it open, returning the ID of the new session
+///
+/// The marker should be removed with `end_session` when the file is closed: any markers that are left behind indicate
+/// that the file was not closed cleanly.
+///
+pub (super) fn begin_session(connection: &rusqlite::Connection) -> Result<i64, rusqlite::Error> {
+    let opened_at   = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs() as i64).unwrap_or(0);
+    let process_id  = process::id() as i64;
+
+    let mut insert  = connection.prepare_cached("INSERT INTO Sessions (ProcessId, OpenedAt) VALUES (?, ?);")?;
+    insert.execute(&[process_id, opened_at])?;
+
+    Ok(connection.last_insert_rowid())
+}
+
+///
+/// Removes the marker for a session started by `begin_session`
+///
+pub (super) fn end_session(connection: &rusqlite::Connection, session_id: i64) -> Result<(), rusqlite::Error> {
+    let mut delete  = connection.prepare_cached("DELETE FROM Sessions WHERE SessionId = ?;")?;
+    delete.execute(&[session_id])?;
+
+    Ok(())
+}
+
+///
+/// Returns the number of sessions left in the database by other processes that are no longer running
+///
+/// These are sessions which were not closed cleanly. Files from before the session table was added have no sessions.
+///
+pub (super) fn count_unclean_sessions(connection: &rusqlite::Connection) -> Result<usize, rusqlite::Error> {
+    Ok(other_sessions(connection)?.into_iter().filter(|(_, is_live)| !is_live).count())
+}
+
+///
+/// Returns the number of sessions in the database that belong to other processes that still have the file open
+///
+pub (super) fn count_live_sessions(connection: &rusqlite::Connection) -> Result<usize, rusqlite::Error> {
+    Ok(other_sessions(connection)?.into_iter().filter(|(_, is_live)| *is_live).count())
+}
+
+///
+/// Removes the sessions left behind by other processes, indicating that any recovery from an unclean shutdown is complete
+///
+/// Sessions belonging to processes that still have the file open are left alone.
+///
+pub (super) fn clear_unclean_sessions(connection: &rusqlite::Connection) -> Result<(), rusqlite::Error> {
+    let unclean     = other_sessions(connection)?.into_iter().filter(|(_, is_live)| !is_live).map(|(session_id, _)| session_id);
+
+    let mut delete  = connection.prepare_cached("DELETE FROM Sessions WHERE SessionId = ?;")?;
+    for session_id in unclean {
+        delete.execute(&[session_id])?;
+
+        // Tidy up any lock file left behind by the process that crashed
+        if let Some(lock_path) = lock_path(connection, session_id) {
+            fs::remove_file(lock_path).ok();
+        }
+    }
+
+    Ok(())
+}
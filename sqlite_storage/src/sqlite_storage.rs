@@ -1,4 +1,5 @@
 use super::sqlite_core::*;
+use super::sqlite_session;
 
 use flo_animation::storage::*;
 
@@ -19,9 +20,10 @@ pub struct SqliteAnimationStorage {
 
 impl SqliteAnimationStorage {
     ///
-    /// Creates a new SQLite database from an existing connection
+    /// Creates a new animation in a blank SQLite database
     /// 
-    /// This will initialise the database, so use `from_connection` for the case where the core already exists
+    /// This will initialise the database, so use `from_connection` for the case where the animation already exists. If the
+    /// database is not blank, it's left untouched and all commands sent to the storage will return an error.
     ///
     pub fn new_from_connection(connection: rusqlite::Connection) -> SqliteAnimationStorage {
         // Create the core with the connection
@@ -29,7 +31,7 @@ impl SqliteAnimationStorage {
         let core    = Arc::new(Desync::new(core));
 
         // Initialise it (in the background)
-        core.desync(|core| { core.initialize().ok(); });
+        core.desync(|core| { core.create_new().ok(); });
 
         // Create the storage object
         SqliteAnimationStorage {
@@ -40,11 +42,18 @@ impl SqliteAnimationStorage {
     ///
     /// Creates a SQLite storage from an existing database connection, which should already be initialised
     ///
+    /// Databases from older versions of FlowBetween are upgraded to the current file format. If the database is
+    /// from a newer version, all commands sent to the storage will return a `StorageError::FileFormatTooNew` error,
+    /// and if it's blank or isn't an animation at all, they will return `StorageError::NotAnAnimation`.
+    ///
     pub fn from_connection(connection: rusqlite::Connection) -> SqliteAnimationStorage {
         // Create the core with the connection
         let core    = SqliteCore::new(connection);
         let core    = Arc::new(Desync::new(core));
 
+        // Upgrade it to the latest version if needed (in the background)
+        core.desync(|core| { core.upgrade_to_latest().ok(); });
+
         // Create the storage object
         SqliteAnimationStorage {
             core:   core
@@ -59,6 +68,24 @@ impl SqliteAnimationStorage {
         Ok(Self::from_connection(connection))
     }
 
+    ///
+    /// Opens an existing database file for reading only
+    ///
+    /// Nothing is written to the file: no session is started, layer caches are not stored and any edits will fail. Files
+    /// that are not animations, or that are in a format other than the current one, return an error.
+    ///
+    pub fn open_file_read_only(path: &Path) -> Result<SqliteAnimationStorage, StorageError> {
+        let connection  = rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|_| StorageError::General)?;
+        let mut core    = SqliteCore::new_read_only(connection);
+
+        core.check_is_latest()?;
+
+        Ok(SqliteAnimationStorage {
+            core: Arc::new(Desync::new(core))
+        })
+    }
+
     ///
     /// Creates a new animation at the specified path
     ///
@@ -74,6 +101,69 @@ impl SqliteAnimationStorage {
         Ok(Self::new_from_connection(rusqlite::Connection::open_in_memory()?))
     }
 
+    ///
+    /// Retrieves the file format version of the database (None if the database is not initialised)
+    ///
+    pub fn file_format_version(&self) -> Option<i64> {
+        self.core.sync(|core| core.file_format_version())
+    }
+
+    ///
+    /// Marks the file as being open in this process until the storage is closed
+    ///
+    /// If the process exits without closing the storage, the marker is left behind: `has_unclean_session()` can be used to detect
+    /// this the next time that the file is opened.
+    ///
+    pub fn begin_session(&self) {
+        self.core.desync(|core| { core.begin_session().ok(); });
+    }
+
+    ///
+    /// Returns true if the file contains session markers left behind by another process that is no longer running (indicating
+    /// that it was not closed cleanly)
+    ///
+    pub fn has_unclean_session(&self) -> bool {
+        self.core.sync(|core| core.has_unclean_session())
+    }
+
+    ///
+    /// Returns true if another process that is still running has this file open
+    ///
+    pub fn is_open_elsewhere(&self) -> bool {
+        self.core.sync(|core| core.is_open_elsewhere())
+    }
+
+    ///
+    /// Removes any session markers left behind by other processes that are no longer running, once the file has been recovered
+    ///
+    pub fn clear_unclean_sessions(&self) {
+        self.core.sync(|core| { core.clear_unclean_sessions().ok(); });
+    }
+
+    ///
+    /// Checks a file for session markers left behind by other processes without opening it for editing
+    ///
+    pub fn file_has_unclean_session(path: &Path) -> bool {
+        let connection = rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY);
+
+        match connection {
+            Ok(connection)  => sqlite_session::count_unclean_sessions(&connection).unwrap_or(0) > 0,
+            Err(_)          => false
+        }
+    }
+
+    ///
+    /// Checks whether or not another process that is still running has a file open, without opening it for editing
+    ///
+    pub fn file_is_open_elsewhere(path: &Path) -> bool {
+        let connection = rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY);
+
+        match connection {
+            Ok(connection)  => sqlite_session::count_live_sessions(&connection).unwrap_or(0) > 0,
+            Err(_)          => false
+        }
+    }
+
     ///
     /// Returns the responses for a stream of commands
     ///
@@ -0,0 +1,129 @@
+use flo_animation::storage::*;
+
+use rusqlite;
+use rusqlite::{NO_PARAMS};
+
+/// The version of the file format written by this version of the storage code
+pub const FILE_FORMAT_VERSION: i64          = 7;
+
+/// Files with no version table but with an edit log are from version 4 of the file format
+const UNVERSIONED_FILE_FORMAT_VERSION: i64  = 4;
+
+///
+/// A step that upgrades a file from one version of the file format to the next
+///
+struct Upgrade {
+    /// The version that this upgrade applies to
+    from_version: i64,
+
+    /// The SQL script that performs the upgrade (the version number is updated separately)
+    script: &'static [u8]
+}
+
+/// The upgrade steps, in order
+const UPGRADES: &[Upgrade] = &[
+    Upgrade { from_version: 4, script: include_bytes!["../sql/upgrade_v4_to_v5.sql"] },
+    Upgrade { from_version: 5, script: include_bytes!["../sql/upgrade_v5_to_v6.sql"] },
+    Upgrade { from_version: 6, script: include_bytes!["../sql/upgrade_v6_to_v7.sql"] }
+];
+
+///
+/// Retrieves the file format version of a database, or None if the database is blank
+///
+pub (super) fn file_format_version(connection: &rusqlite::Connection) -> Option<i64> {
+    // Try to read from the version table
+    let version_number  = connection.prepare("SELECT MAX(VersionNumber) FROM FloStorageVersion");
+    let version_number  = version_number.and_then(|mut version_number| version_number.query_row(NO_PARAMS, |row| row.get::<_, Option<i64>>(0)));
+
+    if let Ok(version_number) = version_number {
+        // Database has a version number in it (if the table is empty, treat it as an unversioned file)
+        Some(version_number.unwrap_or(UNVERSIONED_FILE_FORMAT_VERSION))
+    } else {
+        // Version 4 had no version number table
+        let edit_log = connection.prepare("SELECT COUNT(*) FROM EditLog");
+
+        if edit_log.and_then(|mut edit_log| edit_log.query_row::<i64, _, _>(NO_PARAMS, |row| row.get(0))).is_ok() {
+            Some(UNVERSIONED_FILE_FORMAT_VERSION)
+        } else {
+            // Not a FlowBetween animation
+            None
+        }
+    }
+}
+
+///
+/// Returns true if a database contains no tables at all
+///
+pub (super) fn is_blank(connection: &rusqlite::Connection) -> bool {
+    let num_tables = connection.prepare("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'");
+    let num_tables = num_tables.and_then(|mut num_tables| num_tables.query_row::<i64, _, _>(NO_PARAMS, |row| row.get(0)));
+
+    num_tables.map(|num_tables| num_tables == 0).unwrap_or(false)
+}
+
+///
+/// Performs a single upgrade step, returning the new version number
+///
+fn perform_upgrade(connection: &mut rusqlite::Connection, upgrade: &Upgrade) -> Result<i64, rusqlite::Error> {
+    let new_version = upgrade.from_version + 1;
+    let transaction = connection.transaction()?;
+
+    // Run the upgrade script
+    transaction.execute_batch(&String::from_utf8_lossy(upgrade.script))?;
+
+    // Record the new version
+    transaction.execute("DELETE FROM FloStorageVersion", NO_PARAMS)?;
+    transaction.execute("INSERT INTO FloStorageVersion (VersionNumber) VALUES (?)", &[new_version])?;
+
+    transaction.commit()?;
+
+    Ok(new_version)
+}
+
+///
+/// Checks that a database is an animation in the latest version of the file format without changing it
+///
+/// Databases that are opened read-only can't be upgraded, so files in older formats are rejected with
+/// `StorageError::CannotUpgradeFileFormat` instead.
+///
+pub (super) fn check_is_latest(connection: &rusqlite::Connection) -> Result<(), (StorageError, String)> {
+    match file_format_version(connection) {
+        None                                            => Err((StorageError::NotAnAnimation, "File is not a FlowBetween animation".to_string())),
+        Some(version) if version > FILE_FORMAT_VERSION  => Err((StorageError::FileFormatTooNew, format!("File format version {} is newer than the latest supported version ({}): the file was created by a newer version of FlowBetween", version, FILE_FORMAT_VERSION))),
+        Some(version) if version < FILE_FORMAT_VERSION  => Err((StorageError::CannotUpgradeFileFormat, format!("File format version {} must be upgraded before it can be read, which can't be done when the file is opened read-only", version))),
+        Some(_)                                         => Ok(())
+    }
+}
+
+///
+/// Upgrades a database to the latest version of the file format
+///
+/// Databases that are blank or that are not FlowBetween animations are rejected with `StorageError::NotAnAnimation`: use the
+/// file definition to initialise a new database instead. On failure, returns the storage error and a description of what
+/// went wrong.
+///
+pub (super) fn upgrade_to_latest(connection: &mut rusqlite::Connection) -> Result<(), (StorageError, String)> {
+    let mut version = match file_format_version(connection) {
+        Some(version)   => version,
+        None            => { return Err((StorageError::NotAnAnimation, "File is not a FlowBetween animation".to_string())); }
+    };
+
+    // Files from newer versions can't be upgraded
+    if version > FILE_FORMAT_VERSION {
+        return Err((StorageError::FileFormatTooNew, format!("File format version {} is newer than the latest supported version ({}): the file was created by a newer version of FlowBetween", version, FILE_FORMAT_VERSION)));
+    }
+
+    // Apply the upgrade steps until the file is at the latest version
+    while version < FILE_FORMAT_VERSION {
+        let upgrade = UPGRADES.iter().find(|upgrade| upgrade.from_version == version);
+        let upgrade = match upgrade {
+            Some(upgrade)   => upgrade,
+            None            => { return Err((StorageError::CannotUpgradeFileFormat, format!("No upgrade is available for file format version {}", version))); }
+        };
+
+        version = perform_upgrade(connection, upgrade)
+            .map_err(|err| (StorageError::CannotUpgradeFileFormat, format!("Failed to upgrade from file format version {}: {}", version, err)))?;
+    }
+
+    Ok(())
+}
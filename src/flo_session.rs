@@ -12,6 +12,23 @@ use flo::chooser::*;
 use std::sync::*;
 use serde_json;
 
+///
+/// The languages that the user can choose from, as language tags and the name of the language in that language
+///
+const LANGUAGES: [(&str, &str); 3] = [
+    ("en", "English"),
+    ("ja", "日本語"),
+    ("es", "Español")
+];
+
+///
+/// The translations of the messages displayed by the session
+///
+static MESSAGES: EmbeddedCatalogs = EmbeddedCatalogs::new(&[
+    ("ja", include_str!("../locale/ja.po")),
+    ("es", include_str!("../locale/es.po"))
+]);
+
 ///
 /// Possible subcontrollers of the main flowbetween controller
 ///
@@ -24,9 +41,11 @@ enum SubController {
 /// The main flowbetween session object
 ///
 pub struct FlowBetweenSession {
-    ui:         Binding<Control>,
+    ui:         BindRef<Control>,
     editor:     Arc<dyn Controller>,
-    images:     Arc<ResourceManager<Image>>
+    images:     Arc<ResourceManager<Image>>,
+    dark_mode:  Binding<bool>,
+    theme:      BindRef<Theme>
 }
 
 impl FlowBetweenSession {
@@ -46,18 +65,71 @@ impl FlowBetweenSession {
 
         file_chooser.set_background(FILE_CHOOSER_BACKGROUND);
 
+        // Use the user's language if we can
+        let localization = localization();
+        MESSAGES.load();
+
+        if let Some(locale) = Localization::locale_from_environment() {
+            localization.set_locale(&locale);
+        }
+
+        // The dark theme is used by default
+        let dark_mode   = bind(true);
+        let theme       = Self::create_theme(&dark_mode);
+        let ui          = Self::create_ui(&dark_mode);
+
         // Create the session
         FlowBetweenSession {
-            ui:         bind(Control::container()
-                            .with(Bounds::fill_all())
-                            .with(Appearance::Foreground(DEFAULT_TEXT))
-                            .with(Appearance::Background(MAIN_BACKGROUND))
-                            .with_controller(&serde_json::to_string(&SubController::Editor).unwrap())),
+            ui:         ui,
             editor:     Arc::new(file_chooser),
-            images:     images
+            images:     images,
+            dark_mode:  dark_mode,
+            theme:      theme
         }
     }
 
+    ///
+    /// Creates the theme binding for this session
+    ///
+    fn create_theme(dark_mode: &Binding<bool>) -> BindRef<Theme> {
+        let dark_mode = dark_mode.clone();
+
+        BindRef::from(computed(move || if dark_mode.get() { Theme::dark() } else { Theme::light() }))
+    }
+
+    ///
+    /// Creates the UI binding for this session
+    ///
+    fn create_ui(dark_mode: &Binding<bool>) -> BindRef<Control> {
+        let dark_mode   = dark_mode.clone();
+        let locale      = localization().locale();
+
+        BindRef::from(computed(move || {
+            let dark_mode   = dark_mode.get();
+            let locale      = locale.get();
+            let language    = locale.split('-').nth(0).unwrap_or("").to_string();
+
+            let languages   = LANGUAGES.iter()
+                .map(|(tag, name)| MenuItem::from(MenuCommand::new(&format!("Locale-{}", tag), *name).with_checked(Property::Bool(language == *tag))))
+                .collect::<Vec<_>>();
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(Appearance::Foreground(DEFAULT_TEXT))
+                .with(Appearance::Background(MAIN_BACKGROUND))
+                .with(Menu::Bar(vec![
+                    MenuItem::sub_menu(&localized("View"), vec![
+                        MenuCommand::new("LightTheme", &localized("Light theme")).with_checked(Property::Bool(!dark_mode)).into(),
+                        MenuCommand::new("DarkTheme", &localized("Dark theme")).with_checked(Property::Bool(dark_mode)).into(),
+                        MenuItem::Separator,
+                        MenuItem::sub_menu(&localized("Language"), languages)
+                    ])
+                ]))
+                .with((ActionTrigger::SelectMenuItem, "Menu"))
+                .with_controller(&serde_json::to_string(&SubController::Editor).unwrap())
+        }))
+    }
+
     /*
     fn create_inmemory_animation() -> SqliteAnimation {
         // Create a new animation
@@ -84,7 +156,7 @@ impl FlowBetweenSession {
 
 impl Controller for FlowBetweenSession {
     fn ui(&self) -> BindRef<Control> {
-        BindRef::new(&self.ui)
+        BindRef::clone(&self.ui)
     }
 
     fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
@@ -102,4 +174,24 @@ impl Controller for FlowBetweenSession {
     fn get_image_resources(&self) -> Option<Arc<ResourceManager<Image>>> {
         Some(self.images.clone())
     }
+
+    fn get_theme(&self) -> Option<BindRef<Theme>> {
+        Some(BindRef::clone(&self.theme))
+    }
+
+    fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        match (action_id, action_data) {
+            ("Menu", ActionParameter::MenuItem(command_id)) => match command_id.as_str() {
+                "LightTheme"    => self.dark_mode.set(false),
+                "DarkTheme"     => self.dark_mode.set(true),
+                other           => {
+                    if other.starts_with("Locale-") {
+                        localization().set_locale(&other["Locale-".len()..]);
+                    }
+                }
+            },
+
+            _                                               => { }
+        }
+    }
 }
@@ -41,6 +41,9 @@ use flo_logging::*;
 #[cfg(feature="http")]  use actix_rt;
 #[cfg(feature="gtk")]   use flo_ui::session::*;
 #[cfg(feature="gtk")]   use flo_gtk_ui::*;
+#[cfg(feature="gtk")]   use flo_binding::*;
+#[cfg(feature="gtk")]   use flo_ui_files::preferences::*;
+#[cfg(feature="gtk")]   use flo::preferences::*;
 #[cfg(feature="gtk")]   use futures::executor;
 #[cfg(feature="gtk")]   use futures::prelude::*;
 
@@ -98,10 +101,15 @@ fn main_actix() -> Option<JoinHandle<()>> {
 #[cfg(feature="gtk")]
 fn main_gtk() -> Option<JoinHandle<()>> {
     Some(thread::spawn(|| {
+        // The window size is remembered between sessions
+        let preferences             = Preferences::new(user_preferences());
+        let window_size             = bind((1920, 1080));
+        preferences.bind(&WINDOW_SIZE, &window_size);
+
         // Create a GTK session
         let (gtk_ui, gtk_run_loop)  = GtkUserInterface::new();
         let (session, ui_run_loop)  = UiSession::new(FlowBetweenSession::new());
-        let gtk_session             = GtkSession::new(session, gtk_ui);
+        let gtk_session             = GtkSession::new_with_window_size(session, gtk_ui, window_size);
 
         let run_session             = gtk_session.run();
         let run_loop                = future::select(gtk_run_loop.boxed(), ui_run_loop.boxed());
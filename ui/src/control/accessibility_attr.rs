@@ -0,0 +1,84 @@
+use super::*;
+use super::super::property::*;
+
+use ::modifier::*;
+
+///
+/// The role of a control, as reported to assistive technologies such as screen readers
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum AccessibilityRole {
+    Button,
+    ToggleButton,
+    CheckBox,
+    Slider,
+    TextBox,
+    Label,
+    Image,
+    Canvas,
+    ComboBox,
+    List,
+    ListItem,
+    Tree,
+    TreeItem,
+    Toolbar,
+    Group
+}
+
+///
+/// Attributes describing a control to the user and to assistive technologies
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Accessibility {
+    /// Text displayed when the user hovers the pointer over this control
+    Tooltip(Property),
+
+    /// The name of this control, as read out by a screen reader (controls that display text are named by that text if this isn't set)
+    Label(Property),
+
+    /// A longer description of what this control does
+    Description(Property),
+
+    /// What kind of control this is (the default role is determined by the control type)
+    Role(AccessibilityRole),
+
+    /// The position of this control in the keyboard focus order amongst its siblings (lower values are focused first)
+    ///
+    /// `State::FocusPriority` decides which control is focused when a new control appears: this decides where focus
+    /// moves to when the user presses tab.
+    FocusOrder(u32)
+}
+
+impl AccessibilityRole {
+    ///
+    /// Returns the role that a control of the specified type has by default
+    ///
+    pub fn for_control_type(control_type: ControlType) -> Option<AccessibilityRole> {
+        use self::ControlType::*;
+
+        match control_type {
+            Button              => Some(AccessibilityRole::Button),
+            CheckBox            => Some(AccessibilityRole::CheckBox),
+            Slider | Rotor      => Some(AccessibilityRole::Slider),
+            TextBox             => Some(AccessibilityRole::TextBox),
+            Label               => Some(AccessibilityRole::Label),
+            Canvas              => Some(AccessibilityRole::Canvas),
+            ComboBox            => Some(AccessibilityRole::ComboBox),
+            ListView            => Some(AccessibilityRole::List),
+            TreeView            => Some(AccessibilityRole::Tree),
+            _                   => None
+        }
+    }
+}
+
+impl Modifier<Control> for Accessibility {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::AccessibilityAttr(self))
+    }
+}
+
+impl<'a> Modifier<Control> for &'a Accessibility {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::AccessibilityAttr(self.clone()))
+    }
+}
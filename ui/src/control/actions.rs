@@ -9,7 +9,7 @@ pub enum ActionTrigger {
     /// User clicked this item (pressed down and released while over the same item)
     Click,
 
-    /// User began an interaction outside of this item (usually means that a popup should be dismissed)
+    /// User began an interaction outside of this item (usually means that a popup should be dismissed), or closed a window
     Dismiss,
 
     /// Tracks all user clicks and drags over this item with a particular device
@@ -34,7 +34,16 @@ pub enum ActionTrigger {
     CancelEdit,
 
     /// Divides a scrollable region into a grid, and generates an event whenever the region in the top-left corner changes
-    VirtualScroll(f32, f32)
+    VirtualScroll(f32, f32),
+
+    /// The user has changed which items are selected in a list or tree view
+    SelectItems,
+
+    /// The user has expanded or collapsed an item in a tree view
+    ExpandItem,
+
+    /// The user has picked a command from one of the menus attached to this control
+    SelectMenuItem
 }
 
 ///
@@ -73,5 +82,14 @@ pub enum ActionParameter {
     /// of 3, 2 in the second would indicate that the client area of the scroll
     /// region is 1536x1024 (ie, you need to draw 3 512x512 squares horizontally
     /// and 2 vertically in order to cover everything the user can currently see)
-    VirtualScroll((u32, u32), (u32, u32))
+    VirtualScroll((u32, u32), (u32, u32)),
+
+    /// The indexes of the items that are now selected in a list or tree view
+    Selection(Vec<u32>),
+
+    /// The index of an item in a tree view, and whether or not it should now be expanded
+    Expanded(u32, bool),
+
+    /// The ID of the menu command that was picked
+    MenuItem(String)
 }
@@ -3,8 +3,14 @@ use super::control::*;
 use super::actions::*;
 use super::font_attr::*;
 use super::hint_attr::*;
+use super::items_attr::*;
+use super::menu_attr::*;
+use super::layout_attr::*;
+use super::accessibility_attr::*;
 use super::state_attr::*;
 use super::popup_attr::*;
+use super::window_attr::*;
+use super::transition_attr::*;
 use super::scroll_attr::*;
 use super::appearance_attr::*;
 
@@ -20,7 +26,6 @@ use ::modifier::*;
 ///
 #[derive(Clone, PartialEq, Debug)]
 pub enum ControlAttribute {
-    // TODO: layout attribute for bounding box, zindex and padding
     /// The bounding box for this control
     BoundingBox(Bounds),
 
@@ -42,6 +47,12 @@ pub enum ControlAttribute {
     /// Specifies the popup state of this control (meaningless if this is not a popup control)
     PopupAttr(Popup),
 
+    /// Specifies the window state of this control (meaningless if this is not a window control)
+    WindowAttr(Window),
+
+    /// Specifies how changes to the layout of this control are animated
+    TransitionAttr(Transition),
+
     /// Specifies the appearance of this control
     AppearanceAttr(Appearance),
 
@@ -51,9 +62,21 @@ pub enum ControlAttribute {
     /// Specifies a hint on how this control should be treated
     HintAttr(Hint),
 
+    /// Specifies how the items in a combo box, list view or tree view are arranged
+    ItemsAttr(Items),
+
+    /// Specifies a menu attached to this control
+    MenuAttr(Menu),
+
+    /// Describes this control to the user and to assistive technologies
+    AccessibilityAttr(Accessibility),
+
     /// The unique ID for this control
     Id(String),
 
+    /// Specifies how this control and its subcomponents are laid out
+    LayoutAttr(Layout),
+
     /// Subcomponents of this control
     SubComponents(Vec<Control>),
 
@@ -180,6 +203,26 @@ impl ControlAttribute {
         }
     }
 
+    ///
+    /// The window attribute represented by this attribute
+    ///
+    pub fn window<'a>(&'a self) -> Option<&'a Window> {
+        match self {
+            &WindowAttr(ref window) => Some(window),
+            _                       => None
+        }
+    }
+
+    ///
+    /// The transition attribute represented by this attribute
+    ///
+    pub fn transition<'a>(&'a self) -> Option<&'a Transition> {
+        match self {
+            &TransitionAttr(ref transition) => Some(transition),
+            _                               => None
+        }
+    }
+
     ///
     /// The canvas resource represented by this attribute, if there is one
     ///
@@ -230,6 +273,46 @@ impl ControlAttribute {
         }
     }
 
+    ///
+    /// If this is an items attribute, returns the attribute, otherwise returns nothing
+    ///
+    pub fn items<'a>(&'a self) -> Option<&'a Items> {
+        match self {
+            &ItemsAttr(ref items)   => Some(items),
+            _                       => None
+        }
+    }
+
+    ///
+    /// If this is a menu attribute, returns the menu, otherwise returns nothing
+    ///
+    pub fn menu<'a>(&'a self) -> Option<&'a Menu> {
+        match self {
+            &MenuAttr(ref menu) => Some(menu),
+            _                   => None
+        }
+    }
+
+    ///
+    /// If this is an accessibility attribute, returns it, otherwise returns nothing
+    ///
+    pub fn accessibility<'a>(&'a self) -> Option<&'a Accessibility> {
+        match self {
+            &AccessibilityAttr(ref accessibility)   => Some(accessibility),
+            _                                       => None
+        }
+    }
+
+    ///
+    /// If this is a layout attribute, returns it, otherwise returns nothing
+    ///
+    pub fn layout<'a>(&'a self) -> Option<&'a Layout> {
+        match self {
+            &LayoutAttr(ref layout) => Some(layout),
+            _                       => None
+        }
+    }
+
     ///
     /// Returns true if this attribute is different from another one
     /// (non-recursively, so this won't check subcomoponents)
@@ -246,10 +329,16 @@ impl ControlAttribute {
             &Action(ref trigger, ref action)    => Some((trigger, action)) != compare_to.action(),
             &StateAttr(ref state)               => Some(state) != compare_to.state(),
             &PopupAttr(ref popup)               => Some(popup) != compare_to.popup(),
+            &WindowAttr(ref window)             => Some(window) != compare_to.window(),
+            &TransitionAttr(ref transition)     => Some(transition) != compare_to.transition(),
             &Canvas(ref canvas_resource)        => Some(canvas_resource) != compare_to.canvas(),
             &AppearanceAttr(ref appearance)     => Some(appearance) != compare_to.appearance(),
             &ScrollAttr(ref scroll)             => Some(scroll) != compare_to.scroll(),
             &HintAttr(ref hint)                 => Some(hint) != compare_to.hint(),
+            &ItemsAttr(ref items)               => Some(items) != compare_to.items(),
+            &MenuAttr(ref menu)                 => Some(menu) != compare_to.menu(),
+            &AccessibilityAttr(ref access)      => Some(access) != compare_to.accessibility(),
+            &LayoutAttr(ref layout)             => Some(layout) != compare_to.layout(),
 
             // For the subcomponents we only care about the number as we don't want to recurse
             &SubComponents(ref components)      => Some(components.len()) != compare_to.subcomponents().map(|components| components.len())
@@ -50,6 +50,11 @@ impl Control {
         Self::new(Popup)
     }
 
+    /// Creates a new window control
+    pub fn window() -> Control {
+        Self::new(Window)
+    }
+
     /// Creates a new button control
     pub fn button() -> Control {
         Self::new(Button)
@@ -70,6 +75,21 @@ impl Control {
         Self::new(CheckBox)
     }
 
+    /// Creates a new combo box control
+    pub fn combo_box() -> Control {
+        Self::new(ComboBox)
+    }
+
+    /// Creates a new list view control
+    pub fn list_view() -> Control {
+        Self::new(ListView)
+    }
+
+    /// Creates a new tree view control
+    pub fn tree_view() -> Control {
+        Self::new(TreeView)
+    }
+
     /// Create a new empty control
     pub fn empty() -> Control {
         Self::new(Empty)
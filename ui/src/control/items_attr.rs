@@ -0,0 +1,68 @@
+use super::*;
+use super::super::property::*;
+
+use ::modifier::*;
+
+/// The height of a row in a list or tree view that doesn't specify one
+pub const DEFAULT_ROW_HEIGHT: f32 = 24.0;
+
+///
+/// How many items can be selected at once in a list or tree view
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum SelectionMode {
+    /// Items can't be selected
+    None,
+
+    /// At most one item can be selected at once
+    Single,
+
+    /// Any number of items can be selected at once
+    Multiple
+}
+
+///
+/// Attributes describing the items in a combo box, list view or tree view
+///
+/// The items in these controls are their subcomponents, which are laid out one per row (for list and tree views)
+/// or displayed as the options in a drop-down (for combo boxes). Items are selected using the `Selected` state,
+/// except in a combo box, where the `Value` state is the index of the selected item.
+///
+/// List and tree views are virtualised: the `Count` attribute specifies how many items there are in total, and
+/// the subcomponents are just the items from `FirstIndex` onwards. A `VirtualScroll` action with a grid height
+/// matching the row height can be used to find out which items are visible. Tree views are displayed as a
+/// flattened list of their visible items, with the `Level` and `Expanded` attributes on each item describing
+/// where it is in the hierarchy.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Items {
+    /// How many items can be selected at once in a list or tree view
+    Selection(SelectionMode),
+
+    /// The height of each row in a list or tree view
+    RowHeight(f32),
+
+    /// The total number of items in a list or tree view (if not set, this is the number of subcomponents)
+    Count(Property),
+
+    /// The index of the item represented by the first subcomponent of a list or tree view
+    FirstIndex(Property),
+
+    /// Set on an item in a tree view to indicate how deeply it is nested in the hierarchy
+    Level(u32),
+
+    /// Set on an item in a tree view to indicate that it has child items, and whether or not they're displayed
+    Expanded(Property)
+}
+
+impl Modifier<Control> for Items {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::ItemsAttr(self))
+    }
+}
+
+impl<'a> Modifier<Control> for &'a Items {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::ItemsAttr(self.clone()))
+    }
+}
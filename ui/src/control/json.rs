@@ -23,11 +23,17 @@ impl ToJsonValue for ControlAttribute {
             StateAttr(Enabled(property))            => json!({ "Enabled": property }),
             StateAttr(FocusPriority(property))      => json!({ "FocusPriority": property }),
             PopupAttr(popup)                        => json!({ "Popup": popup }),
+            WindowAttr(window)                      => json!({ "Window": window }),
+            TransitionAttr(transition)              => json!({ "Transition": transition }),
             ScrollAttr(scroll)                      => json!({ "Scroll": scroll }),
             Id(id)                                  => json!({ "Id": id }),
             Controller(name)                        => json!({ "Controller": name }),
             Action(trigger, action)                 => json!({ "Action": (trigger, action) }),
             HintAttr(hint)                          => json!({ "Hint": hint }),
+            ItemsAttr(items)                        => json!({ "Items": items }),
+            MenuAttr(menu)                          => json!({ "Menu": menu }),
+            AccessibilityAttr(accessibility)        => json!({ "Accessibility": accessibility }),
+            LayoutAttr(layout)                      => json!({ "Layout": layout }),
 
             SubComponents(components)               => {
                 let json_components: Vec<_> = components.iter()
@@ -0,0 +1,133 @@
+use super::*;
+
+use ::modifier::*;
+
+///
+/// How the subcomponents of a control are arranged
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum LayoutMode {
+    /// Subcomponents are placed according to their bounding boxes (this is the default)
+    Bounds,
+
+    /// Subcomponents are placed in a single row, from left to right
+    Row,
+
+    /// Subcomponents are placed in a single column, from top to bottom
+    Column,
+
+    /// Subcomponents are placed from left to right, starting a new row whenever there's no more space in the current one
+    WrapRow,
+
+    /// Subcomponents are placed in the cells of a grid with the specified number of columns
+    Grid(u32),
+
+    /// Subcomponents with a `Dock` attribute are placed against the edges of the control, in order, and the remaining
+    /// subcomponents fill the space that's left in the middle
+    Dock
+}
+
+///
+/// The edge of a container that a docked control is placed against
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum DockEdge {
+    Left,
+    Top,
+    Right,
+    Bottom
+}
+
+///
+/// How items are aligned within the space available to them
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Alignment {
+    /// Items are placed at the start of the space (ie, left or top)
+    Start,
+
+    /// Items are centred in the space
+    Centre,
+
+    /// Items are placed at the end of the space (ie, right or bottom)
+    End,
+
+    /// Items are stretched to fill the space
+    Stretch
+}
+
+///
+/// Attributes describing how a control and its subcomponents are laid out
+///
+/// The `Mode`, `Gap`, `Justify` and `AlignItems` attributes apply to the subcomponents of the control they're
+/// attached to. The remaining attributes describe how the control itself is placed by its container: the size
+/// attributes apply in every mode but the alignment, grid and dock attributes only apply when the container has a
+/// matching mode.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Layout {
+    /// How the subcomponents of this control are arranged
+    Mode(LayoutMode),
+
+    /// The space to leave between subcomponents (and between rows of subcomponents when they wrap)
+    Gap(f32),
+
+    /// How subcomponents are placed along the direction of a row or column when there's space left over
+    ///
+    /// `Stretch` shares the remaining space out between all of the subcomponents.
+    Justify(Alignment),
+
+    /// How subcomponents are aligned across a row or column, or within a grid cell (subcomponents are stretched by default)
+    AlignItems(Alignment),
+
+    /// Overrides the `AlignItems` setting of the container for this control
+    AlignSelf(Alignment),
+
+    /// The space to leave around this control. Values are 'left top' and 'right bottom'.
+    Margin((u32, u32), (u32, u32)),
+
+    /// The minimum width and height of this control
+    MinSize(f32, f32),
+
+    /// The maximum width and height of this control
+    MaxSize(f32, f32),
+
+    /// The size of the content of this control (if not set, this is estimated from the text or the subcomponents of the control)
+    ContentSize(f32, f32),
+
+    /// The proportion of the space left over in a row or column that's added to the size of this control
+    Grow(f32),
+
+    /// The column and row of the grid cell that this control is placed in (by default, controls fill the next free cell)
+    GridCell(u32, u32),
+
+    /// The number of columns and rows of the grid that this control covers
+    GridSpan(u32, u32),
+
+    /// The edge of the container that this control is docked against
+    Dock(DockEdge)
+}
+
+impl Modifier<Control> for Layout {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::LayoutAttr(self))
+    }
+}
+
+impl<'a> Modifier<Control> for &'a Layout {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::LayoutAttr(self.clone()))
+    }
+}
+
+impl Modifier<Control> for LayoutMode {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::LayoutAttr(Layout::Mode(self)))
+    }
+}
+
+impl Modifier<Control> for DockEdge {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::LayoutAttr(Layout::Dock(self)))
+    }
+}
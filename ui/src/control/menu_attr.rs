@@ -0,0 +1,147 @@
+use super::*;
+use super::super::property::*;
+
+use ::modifier::*;
+
+///
+/// A command that can be picked from a menu
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MenuCommand {
+    /// Identifies this command: this is the parameter sent with the `SelectMenuItem` action when it's picked
+    pub id: String,
+
+    /// The text displayed for this command
+    pub label: Property,
+
+    /// Text describing the keyboard shortcut for this command (eg, 'Ctrl+Z'), if it has one
+    pub shortcut: Option<String>,
+
+    /// Whether or not this command can be picked
+    pub enabled: Property,
+
+    /// If this command can be checked, whether or not it is currently checked
+    pub checked: Option<Property>
+}
+
+///
+/// An item in a menu
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum MenuItem {
+    /// A command that sends an action when it's picked
+    Command(MenuCommand),
+
+    /// A line separating groups of items
+    Separator,
+
+    /// A submenu, with a label and a list of items
+    SubMenu(Property, Vec<MenuItem>)
+}
+
+///
+/// Attributes describing the menus attached to a control
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Menu {
+    /// The menu bar for the window this control is displayed in (this is normally attached to the root control of a UI)
+    Bar(Vec<MenuItem>),
+
+    /// The menu displayed when the user right-clicks on this control
+    Context(Vec<MenuItem>)
+}
+
+impl MenuCommand {
+    ///
+    /// Creates a new enabled menu command with the specified ID and label
+    ///
+    pub fn new<Label: ToProperty>(id: &str, label: Label) -> MenuCommand {
+        MenuCommand {
+            id:         id.to_string(),
+            label:      label.to_property(),
+            shortcut:   None,
+            enabled:    Property::Bool(true),
+            checked:    None
+        }
+    }
+
+    ///
+    /// Adds a description of the keyboard shortcut to this command
+    ///
+    pub fn with_shortcut(mut self, shortcut: &str) -> MenuCommand {
+        self.shortcut = Some(shortcut.to_string());
+        self
+    }
+
+    ///
+    /// Sets whether or not this command can be picked
+    ///
+    pub fn with_enabled(mut self, enabled: Property) -> MenuCommand {
+        self.enabled = enabled;
+        self
+    }
+
+    ///
+    /// Makes this command checkable, and sets whether or not it's checked
+    ///
+    pub fn with_checked(mut self, checked: Property) -> MenuCommand {
+        self.checked = Some(checked);
+        self
+    }
+}
+
+impl MenuItem {
+    ///
+    /// Creates a submenu
+    ///
+    pub fn sub_menu<Label: ToProperty>(label: Label, items: Vec<MenuItem>) -> MenuItem {
+        MenuItem::SubMenu(label.to_property(), items)
+    }
+
+    ///
+    /// Returns every command in a list of menu items, including those in submenus
+    ///
+    pub fn all_commands<'a>(items: &'a Vec<MenuItem>) -> Vec<&'a MenuCommand> {
+        let mut result = vec![];
+
+        for item in items.iter() {
+            match item {
+                MenuItem::Command(command)          => result.push(command),
+                MenuItem::Separator                 => { },
+                MenuItem::SubMenu(_, sub_items)     => result.extend(MenuItem::all_commands(sub_items))
+            }
+        }
+
+        result
+    }
+}
+
+impl From<MenuCommand> for MenuItem {
+    fn from(command: MenuCommand) -> MenuItem {
+        MenuItem::Command(command)
+    }
+}
+
+impl Menu {
+    ///
+    /// Retrieves the items in this menu
+    ///
+    pub fn items<'a>(&'a self) -> &'a Vec<MenuItem> {
+        match self {
+            Menu::Bar(items)        => items,
+            Menu::Context(items)    => items
+        }
+    }
+}
+
+impl Modifier<Control> for Menu {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::MenuAttr(self))
+    }
+}
+
+impl<'a> Modifier<Control> for &'a Menu {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::MenuAttr(self.clone()))
+    }
+}
@@ -9,11 +9,17 @@ mod modifier;
 mod position;
 mod font_attr;
 mod hint_attr;
+mod items_attr;
+mod menu_attr;
+mod layout_attr;
 mod state_attr;
 mod popup_attr;
+mod window_attr;
+mod transition_attr;
 mod attributes;
 mod scroll_attr;
 mod appearance_attr;
+mod accessibility_attr;
 
 pub use self::json::*;
 pub use self::types::*;
@@ -26,11 +32,17 @@ pub use self::modifier::*;
 pub use self::position::*;
 pub use self::font_attr::*;
 pub use self::hint_attr::*;
+pub use self::items_attr::*;
+pub use self::menu_attr::*;
+pub use self::layout_attr::*;
 pub use self::state_attr::*;
 pub use self::popup_attr::*;
+pub use self::window_attr::*;
+pub use self::transition_attr::*;
 pub use self::attributes::*;
 pub use self::scroll_attr::*;
 pub use self::appearance_attr::*;
+pub use self::accessibility_attr::*;
 
 #[cfg(test)]
 mod test {
@@ -77,6 +89,32 @@ mod test {
         assert!(container.attributes().any(|attr| attr == &ControlAttribute::SubComponents(vec![Control::label().with("Hello")])));
     }
 
+    #[test]
+    fn can_create_list_view_with_items() {
+        let list_view = Control::list_view()
+            .with(Items::Selection(SelectionMode::Multiple))
+            .with(vec![Control::label().with("Item")]);
+
+        assert!(list_view.control_type() == ControlType::ListView);
+        assert!(list_view.attributes().any(|attr| attr.items() == Some(&Items::Selection(SelectionMode::Multiple))));
+    }
+
+    #[test]
+    fn can_find_commands_in_submenus() {
+        let menu = Menu::Context(vec![
+            MenuCommand::new("Cut", "Cut").with_shortcut("Ctrl+X").into(),
+            MenuItem::Separator,
+            MenuItem::sub_menu("Arrange", vec![
+                MenuCommand::new("BringToFront", "Bring to front").into()
+            ])
+        ]);
+        let label = Control::label().with(&menu);
+
+        let commands = MenuItem::all_commands(menu.items());
+        assert!(commands.iter().map(|command| command.id.clone()).collect::<Vec<_>>() == vec!["Cut".to_string(), "BringToFront".to_string()]);
+        assert!(label.attributes().any(|attr| attr.menu() == Some(&menu)));
+    }
+
     #[test]
     fn can_find_all_subcontrollers() {
         let container = Control::container()
@@ -0,0 +1,104 @@
+use super::*;
+
+use ::modifier::*;
+
+///
+/// Describes how a transition progresses from its start to its end
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum TransitionEasing {
+    /// Moves at a constant speed
+    Linear,
+
+    /// Starts slowly and speeds up towards the end
+    EaseIn,
+
+    /// Starts quickly and slows down towards the end
+    EaseOut,
+
+    /// Starts and ends slowly
+    EaseInOut
+}
+
+///
+/// Describes how changes to the layout of a control are animated
+///
+/// Durations are in milliseconds. Controls with no transition attributes snap to their new layout.
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Transition {
+    /// When the control moves, it travels from its old position to its new one
+    Position(u32, TransitionEasing),
+
+    /// When the control is resized, it grows or shrinks from its old size to its new one
+    Size(u32, TransitionEasing),
+
+    /// When the control first appears, it fades in
+    Opacity(u32, TransitionEasing)
+}
+
+impl TransitionEasing {
+    ///
+    /// Given the proportion of the duration that has elapsed (0.0-1.0), returns the proportion of the way between
+    /// the start and the end value that the transition has reached
+    ///
+    pub fn ease(&self, t: f64) -> f64 {
+        use self::TransitionEasing::*;
+
+        let t = t.max(0.0).min(1.0);
+
+        match self {
+            Linear      => t,
+            EaseIn      => t*t*t,
+            EaseOut     => { let inv = 1.0-t; 1.0 - inv*inv*inv },
+            EaseInOut   => {
+                if t < 0.5 {
+                    4.0*t*t*t
+                } else {
+                    let inv = 2.0-2.0*t;
+                    1.0 - (inv*inv*inv)/2.0
+                }
+            }
+        }
+    }
+}
+
+impl Transition {
+    ///
+    /// The duration of this transition in milliseconds
+    ///
+    pub fn duration(&self) -> u32 {
+        use self::Transition::*;
+
+        match self {
+            Position(duration, _)   => *duration,
+            Size(duration, _)       => *duration,
+            Opacity(duration, _)    => *duration
+        }
+    }
+
+    ///
+    /// The easing function for this transition
+    ///
+    pub fn easing(&self) -> TransitionEasing {
+        use self::Transition::*;
+
+        match self {
+            Position(_, easing)     => *easing,
+            Size(_, easing)         => *easing,
+            Opacity(_, easing)      => *easing
+        }
+    }
+}
+
+impl Modifier<Control> for Transition {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::TransitionAttr(self))
+    }
+}
+
+impl<'a> Modifier<Control> for &'a Transition {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::TransitionAttr(self.clone()))
+    }
+}
@@ -38,5 +38,17 @@ pub enum ControlType {
     TextBox,
 
     /// A checkbox that can be turned on or off, with optional label
-    CheckBox
+    CheckBox,
+
+    /// A drop-down for choosing one of the options supplied as its subcomponents
+    ComboBox,
+
+    /// A scrolling list of items, which can be selected
+    ListView,
+
+    /// A scrolling list of items arranged in a hierarchy, which can be expanded or collapsed
+    TreeView,
+
+    /// A separate top-level window, which displays the subcomponents of this control
+    Window
 }
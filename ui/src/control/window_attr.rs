@@ -0,0 +1,55 @@
+use super::*;
+use super::super::property::*;
+
+use ::modifier::*;
+
+/// The size of the content of a window that doesn't specify one
+pub const DEFAULT_WINDOW_SIZE: (u32, u32) = (400, 300);
+
+///
+/// How a window is presented relative to the main window
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum WindowKind {
+    /// A standard window, which can be moved behind the main window
+    Normal,
+
+    /// A utility window (such as a floating colour picker or palette), which stays above the main window
+    Utility
+}
+
+///
+/// Attributes associated with a window control
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Window {
+    /// The title of this window
+    Title(Property),
+
+    /// Whether or not this window is open (windows are open by default)
+    IsOpen(Property),
+
+    /// How this window is presented
+    Kind(WindowKind),
+
+    /// The initial size in pixels of the content of this window (`DEFAULT_WINDOW_SIZE` if not set)
+    Size(u32, u32)
+}
+
+impl Modifier<Control> for Window {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::WindowAttr(self))
+    }
+}
+
+impl Modifier<Control> for WindowKind {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::WindowAttr(Window::Kind(self)))
+    }
+}
+
+impl<'a> Modifier<Control> for &'a Window {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::WindowAttr(self.clone()))
+    }
+}
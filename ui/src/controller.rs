@@ -3,6 +3,7 @@ use flo_binding::binding_context::*;
 
 use super::image::*;
 use super::control::*;
+use super::theme::*;
 use super::viewmodel::*;
 use super::binding_canvas::*;
 use super::resource_manager::*;
@@ -50,6 +51,9 @@ pub trait Controller : Send+Sync {
     /// Retrieves a resource manager containing the canvases used in the UI for this controller
     fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> { None }
 
+    /// Retrieves the theme used to style the UI (only the theme of the root controller of a session is used)
+    fn get_theme(&self) -> Option<BindRef<Theme>> { None }
+
     /// Called just before an update is processed
     ///
     /// This is called for every controller every time after processing any actions
@@ -0,0 +1,187 @@
+use super::super::*;
+
+use flo_binding::*;
+use std::sync::*;
+
+///
+/// Where a dockable panel is displayed
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum DockPosition {
+    /// The panel is docked against the edge of its container
+    Docked,
+
+    /// The panel is displayed in a window of its own
+    Floating,
+
+    /// The panel is not displayed
+    Hidden
+}
+
+///
+/// Controller that provides standard behaviour for dockable panels.
+///
+/// When docked, the content is displayed underneath a title bar with a button that
+/// moves the panel into a floating window. Closing the floating window docks the
+/// panel again. The container that this controller is attached to should have a
+/// `Layout::Dock` attribute and the `LayoutMode::Column` mode (so it's sized to fit
+/// the panel), and be in a container with the `LayoutMode::Dock` mode: it takes up
+/// no space while the panel is floating or hidden.
+///
+pub struct DockPanelController<ContentController: Controller> {
+    /// Controller that provides the content of the panel
+    content_controller: ContentController,
+
+    /// The title of the panel
+    title: BindRef<String>,
+
+    /// The size of the content of the panel
+    panel_size: BindRef<(u32, u32)>,
+
+    /// Where the panel is displayed
+    position: Binding<DockPosition>,
+
+    /// User interface for this controller
+    ui: BindRef<Control>
+}
+
+impl<ContentController: Controller> DockPanelController<ContentController> {
+    ///
+    /// Creates a new dock panel controller.
+    ///
+    /// Default settings are a size of 200,300
+    ///
+    pub fn new<T: Into<BindRef<String>>>(controller: ContentController, title: T, position: &Binding<DockPosition>) -> DockPanelController<ContentController> {
+        // Create the initial set of bindings
+        let content_controller  = controller;
+        let title               = title.into();
+        let position            = position.clone();
+        let panel_size          = BindRef::from(&(200, 300));
+        let content             = content_controller.ui();
+
+        // Derive the basic UI binding
+        let ui                  = Self::create_ui(&content, &title, &panel_size, &position);
+
+        DockPanelController {
+            content_controller: content_controller,
+            title:              title,
+            panel_size:         panel_size,
+            position:           position,
+            ui:                 ui
+        }
+    }
+
+    ///
+    /// Returns a modified controller with a different size
+    ///
+    pub fn with_size<T: Into<BindRef<(u32, u32)>>>(mut self, size: T) -> DockPanelController<ContentController> {
+        self.panel_size = size.into();
+        self.regenerate_ui()
+    }
+
+    ///
+    /// Regenerates the UI field from the current bindings
+    ///
+    fn regenerate_ui(mut self) -> DockPanelController<ContentController> {
+        self.ui = Self::create_ui(&self.content_controller.ui(), &self.title, &self.panel_size, &self.position);
+        self
+    }
+
+    ///
+    /// Creates the UI binding for this controller
+    ///
+    fn create_ui(content: &BindRef<Control>, title: &BindRef<String>, size: &BindRef<(u32, u32)>, position: &Binding<DockPosition>) -> BindRef<Control> {
+        // Clone the model bits
+        let content     = content.clone();
+        let title       = title.clone();
+        let size        = size.clone();
+        let position    = position.clone();
+
+        // Compute the UI
+        BindRef::from(computed(move || {
+            let title           = title.get();
+            let (width, height) = size.get();
+
+            match position.get() {
+                DockPosition::Docked => {
+                    Control::container()
+                        .with(Bounds::fill_all())
+                        .with(LayoutMode::Column)
+                        .with(Layout::ContentSize(width as f32, height as f32))
+                        .with(Layout::Grow(1.0))
+                        .with(vec![
+                            Control::container()
+                                .with(LayoutMode::Row)
+                                .with(Layout::AlignItems(Alignment::Centre))
+                                .with(ControlAttribute::Padding((4, 2), (4, 2)))
+                                .with(vec![
+                                    Control::label()
+                                        .with(title)
+                                        .with(Layout::Grow(1.0)),
+                                    Control::button()
+                                        .with(&localized("Float"))
+                                        .with((ActionTrigger::Click, "FloatPanel"))
+                                ]),
+                            Control::container()
+                                .with(Layout::Grow(1.0))
+                                .with(vec![
+                                    content.get()
+                                ])
+                        ])
+                },
+
+                DockPosition::Floating => {
+                    Control::window()
+                        .with(Window::Title(Property::String(title)))
+                        .with(Window::Size(width, height))
+                        .with(WindowKind::Utility)
+                        .with((ActionTrigger::Dismiss, "DockPanel"))
+                        .with(vec![
+                            content.get()
+                        ])
+                },
+
+                DockPosition::Hidden => {
+                    // Hidden panels are closed windows, which take up no space in their container
+                    Control::window()
+                        .with(Window::IsOpen(Property::Bool(false)))
+                        .with(Window::Title(Property::String(title)))
+                }
+            }
+        }))
+    }
+}
+
+impl<ContentController: Controller> Controller for DockPanelController<ContentController> {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        self.content_controller.get_viewmodel()
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        self.content_controller.get_subcontroller(id)
+    }
+
+    fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        // Move the panel between the dock and its window
+        match action_id {
+            "FloatPanel"    => self.position.set(DockPosition::Floating),
+            "DockPanel"     => self.position.set(DockPosition::Docked),
+            _               => { }
+        }
+
+        // Pass the action on to the main controller
+        self.content_controller.action(action_id, action_data);
+    }
+
+    fn get_image_resources(&self) -> Option<Arc<ResourceManager<Image>>> {
+        self.content_controller.get_image_resources()
+    }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        self.content_controller.get_canvas_resources()
+    }
+}
@@ -1,7 +1,11 @@
 mod popup_controller;
+mod window_controller;
+mod dock_panel_controller;
 mod virtual_canvas;
 mod createable_controller;
 
 pub use self::popup_controller::*;
+pub use self::window_controller::*;
+pub use self::dock_panel_controller::*;
 pub use self::virtual_canvas::*;
 pub use self::createable_controller::*;
@@ -0,0 +1,159 @@
+use super::super::*;
+
+use flo_binding::*;
+use std::sync::*;
+
+///
+/// Controller that displays the UI of another controller in a separate window.
+/// Supply this as a subcontroller to open windows such as floating palettes or
+/// previews. The content UI is suppressed while the window is closed.
+///
+pub struct WindowController<ContentController: Controller> {
+    /// Controller that provides the content of the window
+    content_controller: ContentController,
+
+    /// The title of the window
+    title: BindRef<String>,
+
+    /// The size of the content of the window
+    window_size: BindRef<(u32, u32)>,
+
+    /// How the window is presented
+    kind: BindRef<WindowKind>,
+
+    /// Binding that specifies whether or not the window is open
+    /// (This will set to false if the user closes the window)
+    open: Binding<bool>,
+
+    /// User interface for this controller
+    ui: BindRef<Control>
+}
+
+impl<ContentController: Controller> WindowController<ContentController> {
+    ///
+    /// Creates a new window controller.
+    ///
+    /// Default settings are an untitled, normal window with the default window size
+    ///
+    pub fn new(controller: ContentController, is_open: &Binding<bool>) -> WindowController<ContentController> {
+        // Create the initial set of bindings
+        let content_controller  = controller;
+        let open                = is_open.clone();
+        let title               = BindRef::from(&String::new());
+        let window_size         = BindRef::from(&DEFAULT_WINDOW_SIZE);
+        let kind                = BindRef::from(&WindowKind::Normal);
+        let content             = content_controller.ui();
+
+        // Derive the basic UI binding
+        let ui                  = Self::create_ui(&content, &open, &title, &window_size, &kind);
+
+        WindowController {
+            content_controller: content_controller,
+            title:              title,
+            window_size:        window_size,
+            kind:               kind,
+            open:               open,
+            ui:                 ui
+        }
+    }
+
+    ///
+    /// Returns a modified controller with a different title
+    ///
+    pub fn with_title<T: Into<BindRef<String>>>(mut self, title: T) -> WindowController<ContentController> {
+        self.title = title.into();
+        self.regenerate_ui()
+    }
+
+    ///
+    /// Returns a modified controller with a different size
+    ///
+    pub fn with_size<T: Into<BindRef<(u32, u32)>>>(mut self, size: T) -> WindowController<ContentController> {
+        self.window_size = size.into();
+        self.regenerate_ui()
+    }
+
+    ///
+    /// Returns a modified controller with a different kind of window
+    ///
+    pub fn with_kind<T: Into<BindRef<WindowKind>>>(mut self, kind: T) -> WindowController<ContentController> {
+        self.kind = kind.into();
+        self.regenerate_ui()
+    }
+
+    ///
+    /// Regenerates the UI field from the current bindings
+    ///
+    fn regenerate_ui(mut self) -> WindowController<ContentController> {
+        self.ui = Self::create_ui(&self.content_controller.ui(), &self.open, &self.title, &self.window_size, &self.kind);
+        self
+    }
+
+    ///
+    /// Creates the UI binding for this controller
+    ///
+    fn create_ui(content: &BindRef<Control>, open: &Binding<bool>, title: &BindRef<String>, size: &BindRef<(u32, u32)>, kind: &BindRef<WindowKind>) -> BindRef<Control> {
+        // Clone the model bits
+        let content     = content.clone();
+        let open        = open.clone();
+        let title       = title.clone();
+        let size        = size.clone();
+        let kind        = kind.clone();
+
+        // Compute the UI
+        BindRef::from(computed(move || {
+            let open            = open.get();
+            let title           = title.get();
+            let (width, height) = size.get();
+            let kind            = kind.get();
+
+            let window          = Control::window()
+                .with(Window::IsOpen(Property::Bool(open)))
+                .with(Window::Title(Property::String(title)))
+                .with(Window::Size(width, height))
+                .with(kind)
+                .with((ActionTrigger::Dismiss, "CloseWindow"));
+
+            if !open {
+                // Not binding the UI here so if the controller updates while the window is closed, nothing happens
+                window
+            } else {
+                window.with(vec![
+                    content.get()
+                ])
+            }
+        }))
+    }
+}
+
+impl<ContentController: Controller> Controller for WindowController<ContentController> {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        self.content_controller.get_viewmodel()
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        self.content_controller.get_subcontroller(id)
+    }
+
+    fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        // Close the window if our CloseWindow action is fired
+        if action_id == "CloseWindow" {
+            self.open.set(false);
+        }
+
+        // Pass the action on to the main controller
+        self.content_controller.action(action_id, action_data);
+    }
+
+    fn get_image_resources(&self) -> Option<Arc<ResourceManager<Image>>> {
+        self.content_controller.get_image_resources()
+    }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        self.content_controller.get_canvas_resources()
+    }
+}
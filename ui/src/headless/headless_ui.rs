@@ -0,0 +1,486 @@
+use super::state::*;
+use super::super::theme::*;
+use super::super::control::*;
+use super::super::property::*;
+use super::super::session::*;
+use super::super::controller::*;
+use super::super::user_interface::*;
+
+use flo_canvas::*;
+use flo_stream::*;
+use flo_binding::*;
+
+use futures::*;
+use futures::future::{Either};
+use futures::executor;
+use futures::channel::oneshot;
+
+use std::thread;
+use std::sync::*;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long to wait for the UI to respond to a set of events
+const RESPONSE_TIMEOUT: Duration = Duration::from_millis(2000);
+
+///
+/// A user interface that runs a UI session without displaying anything
+///
+/// The headless UI maintains the control tree, viewmodel and canvas state from the updates generated by the session, and
+/// lays out the controls in a window of a fixed size. Controls can be found by ID, type, class or text, and driven by
+/// clicking, dragging, painting, typing on them, selecting items in them or picking commands from their menus, which makes it possible to test controllers end-to-end without a
+/// real user interface.
+///
+/// Unlike a real user interface, the headless UI only reads updates from the session when it's asked to: the driver
+/// functions wait for the session to respond before returning, and `settle()` can be used to read any updates caused
+/// by something other than an event (such as a change to a binding used by a controller). The session has responded
+/// once it has processed a tick sent after the events: ticks are always processed last and always generate an update.
+///
+pub struct HeadlessUi<CoreController: Controller> {
+    /// The session that this is running
+    session: UiSession<CoreController>,
+
+    /// The thread pool running the session's event loop
+    _thread_pool: executor::ThreadPool,
+
+    /// The updates from the session
+    updates: UiUpdateStream,
+
+    /// The timer used to stop waiting for a session that isn't responding
+    timer: Timer,
+
+    /// The state of the UI built up from the updates so far
+    state: HeadlessState
+}
+
+///
+/// Completes futures after a timeout, using a single thread for every timeout
+///
+struct Timer {
+    /// Sends the deadlines to the timer thread, along with the channel to signal when they pass
+    requests: mpsc::Sender<(Instant, oneshot::Sender<()>)>
+}
+
+impl Timer {
+    ///
+    /// Starts a new timer thread (which stops when the timer is dropped)
+    ///
+    fn new() -> Timer {
+        let (requests, pending_requests) = mpsc::channel::<(Instant, oneshot::Sender<()>)>();
+
+        thread::spawn(move || {
+            let mut waiting: Vec<(Instant, oneshot::Sender<()>)> = vec![];
+
+            loop {
+                // Signal any timeouts whose deadline has passed
+                let now                         = Instant::now();
+                let (expired, still_waiting)    = waiting.into_iter().partition::<Vec<_>, _>(|(deadline, _)| *deadline <= now);
+                waiting                         = still_waiting;
+
+                expired.into_iter().for_each(|(_, timeout_send)| { timeout_send.send(()).ok(); });
+
+                // Wait for a new request or for the next deadline to pass
+                let next_request = match waiting.iter().map(|(deadline, _)| *deadline).min() {
+                    Some(deadline)  => pending_requests.recv_timeout(deadline.saturating_duration_since(now)),
+                    None            => pending_requests.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected)
+                };
+
+                match next_request {
+                    Ok(request)                                 => waiting.push(request),
+                    Err(mpsc::RecvTimeoutError::Timeout)        => { }
+                    Err(mpsc::RecvTimeoutError::Disconnected)   => { break; }
+                }
+            }
+        });
+
+        Timer {
+            requests: requests
+        }
+    }
+
+    ///
+    /// Creates a future that completes after a timeout
+    ///
+    fn timeout(&self, duration: Duration) -> oneshot::Receiver<()> {
+        let (timeout_send, timeout_recv) = oneshot::channel::<()>();
+
+        self.requests.send((Instant::now() + duration, timeout_send)).ok();
+
+        timeout_recv
+    }
+}
+
+impl<CoreController: 'static+Controller> HeadlessUi<CoreController> {
+    ///
+    /// Starts a headless UI for a controller, laid out in a window of the specified size
+    ///
+    /// This waits for the initial state of the UI to arrive before returning.
+    ///
+    pub fn new(controller: CoreController, width: f32, height: f32) -> HeadlessUi<CoreController> {
+        let thread_pool         = executor::ThreadPool::new().unwrap();
+        let (session, run_loop) = UiSession::new(controller);
+        thread_pool.spawn_ok(run_loop);
+
+        let updates             = session.get_updates();
+        let mut headless_ui     = HeadlessUi {
+            session:        session,
+            _thread_pool:   thread_pool,
+            updates:        updates,
+            timer:          Timer::new(),
+            state:          HeadlessState::new(width, height)
+        };
+
+        headless_ui.settle();
+        headless_ui
+    }
+
+    ///
+    /// Reads updates from the session until the update generated by a tick arrives
+    ///
+    /// The update stream returns an empty set of updates for a tick once it has returned every other pending update, so
+    /// an empty update means that every event sent before the tick has been processed. This gives up if the session
+    /// hasn't responded within `RESPONSE_TIMEOUT`.
+    ///
+    fn wait_for_tick(&mut self) {
+        let mut timeout = self.timer.timeout(RESPONSE_TIMEOUT);
+
+        loop {
+            let next_update = executor::block_on(future::select(self.updates.next(), &mut timeout));
+
+            match next_update {
+                Either::Left((Some(Ok(updates)), _timeout)) => {
+                    if updates.len() == 0 { break; }
+
+                    self.state.apply_updates(updates);
+                }
+
+                _ => { break; }
+            }
+        }
+    }
+
+    ///
+    /// Reads any updates that the session has generated since the last time the UI was updated
+    ///
+    pub fn settle(&mut self) {
+        self.send_events(vec![]);
+    }
+
+    ///
+    /// Sends some events to the session and waits for it to process them
+    ///
+    pub fn send_events(&mut self, events: Vec<UiEvent>) {
+        // The tick is processed after the other events, and always generates an update
+        let mut events = events;
+        events.push(UiEvent::Tick);
+
+        let mut input_sink = self.session.get_input_sink();
+        executor::block_on(input_sink.publish(events));
+
+        self.wait_for_tick();
+    }
+
+    ///
+    /// Changes the size of the window that the UI is laid out in
+    ///
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.state.set_size(width, height);
+    }
+
+    ///
+    /// Retrieves the current state of the UI
+    ///
+    pub fn state(&self) -> &HeadlessState {
+        &self.state
+    }
+
+    ///
+    /// Returns every control in the UI, laid out in the window, in the order they appear in the UI tree
+    ///
+    pub fn controls(&self) -> Vec<HeadlessControl> {
+        self.state.controls()
+    }
+
+    ///
+    /// Finds the control with the specified ID
+    ///
+    pub fn control_with_id(&self, id: &str) -> Option<HeadlessControl> {
+        self.controls().into_iter()
+            .find(|control| control.control.attributes().any(|attr| attr.id().map(|control_id| control_id == id).unwrap_or(false)))
+    }
+
+    ///
+    /// Finds all of the controls of a particular type
+    ///
+    pub fn controls_with_type(&self, control_type: ControlType) -> Vec<HeadlessControl> {
+        self.controls().into_iter()
+            .filter(|control| control.control.control_type() == control_type)
+            .collect()
+    }
+
+    ///
+    /// Finds all of the controls with a particular class
+    ///
+    /// The class of a control is the name of its controller (this is the class used for styling controls by the GTK
+    /// and HTTP user interfaces)
+    ///
+    pub fn controls_with_class(&self, class: &str) -> Vec<HeadlessControl> {
+        self.controls().into_iter()
+            .filter(|control| control.control.controller() == Some(class))
+            .collect()
+    }
+
+    ///
+    /// Finds the first control displaying the specified text
+    ///
+    pub fn control_with_text(&self, text: &str) -> Option<HeadlessControl> {
+        self.controls().into_iter()
+            .find(|control| self.state.text(control).map(|control_text| control_text == text).unwrap_or(false))
+    }
+
+    ///
+    /// Finds the first control with the specified accessibility label (or displaying the specified text, if it has no label)
+    ///
+    /// This finds controls the way a screen reader user would, which makes it possible to find icon-only buttons without
+    /// giving them an ID.
+    ///
+    pub fn control_with_label(&self, label: &str) -> Option<HeadlessControl> {
+        self.controls().into_iter()
+            .find(|control| self.state.accessible_label(control).map(|control_label| control_label == label).unwrap_or(false))
+    }
+
+    ///
+    /// Finds the topmost control at the specified point in the main window
+    ///
+    pub fn control_at(&self, x: f32, y: f32) -> Option<HeadlessControl> {
+        self.controls().into_iter()
+            .filter(|control| control.window.is_empty())
+            .filter(|control| control.contains(x, y))
+            .last()
+    }
+
+    ///
+    /// Returns the window controls for the windows that are currently open, besides the main window
+    ///
+    pub fn windows(&self) -> Vec<HeadlessControl> {
+        self.controls().into_iter()
+            .filter(|control| control.control.control_type() == ControlType::Window)
+            .filter(|window| self.state.window_is_open(window))
+            .collect()
+    }
+
+    ///
+    /// Returns the controls displayed in a window (not including the window control itself)
+    ///
+    pub fn controls_in_window(&self, window: &HeadlessControl) -> Vec<HeadlessControl> {
+        self.controls().into_iter()
+            .filter(|control| control.window == window.address)
+            .collect()
+    }
+
+    ///
+    /// Retrieves the text displayed by a control
+    ///
+    pub fn text(&self, control: &HeadlessControl) -> Option<String> {
+        self.state.text(control)
+    }
+
+    ///
+    /// Retrieves the tooltip for a control
+    ///
+    pub fn tooltip(&self, control: &HeadlessControl) -> Option<String> {
+        self.state.tooltip(control)
+    }
+
+    ///
+    /// Retrieves the properties that the current theme sets for a control
+    ///
+    pub fn style(&self, control: &HeadlessControl) -> Vec<StyleProperty> {
+        self.state.style(control)
+    }
+
+    ///
+    /// Retrieves the value of a property in the viewmodel of the controller at the specified path
+    ///
+    pub fn viewmodel_value(&self, controller_path: &Vec<String>, property_name: &str) -> Option<PropertyValue> {
+        self.state.viewmodel_value(controller_path, property_name)
+    }
+
+    ///
+    /// Retrieves the drawing instructions that have been sent to the canvas of a control since it was last cleared
+    ///
+    pub fn canvas_drawing(&self, control: &HeadlessControl) -> Vec<Draw> {
+        let canvas_name = control.control.canvas_resource()
+            .map(|canvas| canvas.name().unwrap_or_else(|| canvas.id().to_string()));
+
+        match canvas_name {
+            Some(canvas_name)   => self.state.canvas_drawing(&control.controller_path, &canvas_name),
+            None                => vec![]
+        }
+    }
+
+    ///
+    /// Sends the action for a trigger on a control to its controller once for each of a set of parameters
+    ///
+    /// Returns false (and sends nothing) if the control has no action for the trigger
+    ///
+    fn send_action(&mut self, control: &HeadlessControl, trigger: ActionTrigger, parameters: Vec<ActionParameter>) -> bool {
+        let action_name = match control.action_name(&trigger) {
+            Some(action_name)   => action_name.to_string(),
+            None                => { return false; }
+        };
+
+        let events = parameters.into_iter()
+            .map(|parameter| UiEvent::Action(control.controller_path.clone(), action_name.clone(), parameter))
+            .collect();
+        self.send_events(events);
+
+        true
+    }
+
+    ///
+    /// Clicks on a control, returning false if it has no click action
+    ///
+    pub fn click(&mut self, control: &HeadlessControl) -> bool {
+        self.send_action(control, ActionTrigger::Click, vec![ActionParameter::None])
+    }
+
+    ///
+    /// Dismisses a popup or closes a window, returning false if it has no dismiss action
+    ///
+    pub fn dismiss(&mut self, control: &HeadlessControl) -> bool {
+        self.send_action(control, ActionTrigger::Dismiss, vec![ActionParameter::None])
+    }
+
+    ///
+    /// Drags across a control from one point to another, returning false if it has no drag action
+    ///
+    pub fn drag(&mut self, control: &HeadlessControl, from: (f32, f32), to: (f32, f32)) -> bool {
+        self.send_action(control, ActionTrigger::Drag, vec![
+            ActionParameter::Drag(DragAction::Start, from, from),
+            ActionParameter::Drag(DragAction::Drag, from, to),
+            ActionParameter::Drag(DragAction::Finish, from, to)
+        ])
+    }
+
+    ///
+    /// Sends some painting events to a control, returning false if the control does not accept painting from the device
+    ///
+    pub fn paint(&mut self, control: &HeadlessControl, device: PaintDevice, painting: Vec<Painting>) -> bool {
+        self.send_action(control, ActionTrigger::Paint(device), vec![ActionParameter::Paint(device, painting)])
+    }
+
+    ///
+    /// Paints a stroke through a set of points (relative to the control) at full pressure
+    ///
+    pub fn paint_stroke(&mut self, control: &HeadlessControl, device: PaintDevice, points: &[(f32, f32)]) -> bool {
+        let painting = points.iter()
+            .enumerate()
+            .map(|(index, location)| Painting {
+                action:     if index == 0 { PaintAction::Start } else { PaintAction::Continue },
+                pointer_id: 0,
+                location:   *location,
+                pressure:   1.0,
+                tilt_x:     0.0,
+                tilt_y:     0.0
+            })
+            .chain(points.last().map(|location| Painting {
+                action:     PaintAction::Finish,
+                pointer_id: 0,
+                location:   *location,
+                pressure:   1.0,
+                tilt_x:     0.0,
+                tilt_y:     0.0
+            }))
+            .collect();
+
+        self.paint(control, device, painting)
+    }
+
+    ///
+    /// Sets the value of a control (such as a slider or a checkbox), returning false if it has no action for setting its value
+    ///
+    pub fn set_value(&mut self, control: &HeadlessControl, value: PropertyValue) -> bool {
+        self.send_action(control, ActionTrigger::SetValue, vec![ActionParameter::Value(value)])
+    }
+
+    ///
+    /// Selects a set of items (by index) in a list or tree view, returning false if it has no action for changing the selection
+    ///
+    pub fn select_items(&mut self, control: &HeadlessControl, items: Vec<u32>) -> bool {
+        self.send_action(control, ActionTrigger::SelectItems, vec![ActionParameter::Selection(items)])
+    }
+
+    ///
+    /// Expands or collapses an item in a tree view, returning false if it has no action for expanding items
+    ///
+    pub fn expand_item(&mut self, control: &HeadlessControl, item: u32, expanded: bool) -> bool {
+        self.send_action(control, ActionTrigger::ExpandItem, vec![ActionParameter::Expanded(item, expanded)])
+    }
+
+    ///
+    /// Picks a command from one of the menus attached to a control, returning false if the control has no action for
+    /// menu items or has no enabled command with the specified ID
+    ///
+    pub fn select_menu_item(&mut self, control: &HeadlessControl, command_id: &str) -> bool {
+        let enabled = {
+            let state   = &self.state;
+            let command = control.control.attributes()
+                .filter_map(|attr| attr.menu())
+                .flat_map(|menu| MenuItem::all_commands(menu.items()))
+                .find(|command| command.id == command_id);
+
+            command.map(|command| state.property_value(&control.controller_path, &command.enabled).to_bool().unwrap_or(false))
+                .unwrap_or(false)
+        };
+
+        if !enabled {
+            return false;
+        }
+
+        self.send_action(control, ActionTrigger::SelectMenuItem, vec![ActionParameter::MenuItem(command_id.to_string())])
+    }
+
+    ///
+    /// Types some text into a control, returning false if it has no action for setting its value
+    ///
+    /// The text is sent as an edit followed by setting the final value, as would happen if the user typed the text and
+    /// then pressed return.
+    ///
+    pub fn type_text(&mut self, control: &HeadlessControl, text: &str) -> bool {
+        if control.action_name(&ActionTrigger::SetValue).is_none() {
+            return false;
+        }
+
+        let value = PropertyValue::String(text.to_string());
+        self.send_action(control, ActionTrigger::EditValue, vec![ActionParameter::Value(value.clone())]);
+        self.send_action(control, ActionTrigger::SetValue, vec![ActionParameter::Value(value)])
+    }
+}
+
+impl<CoreController: 'static+Controller> UserInterface<Vec<UiEvent>, Vec<UiUpdate>, ()> for HeadlessUi<CoreController> {
+    /// The type of the update stream for this UI
+    type UpdateStream = UiUpdateStream;
+
+    /// Retrieves an input event sink for this user interface
+    fn get_input_sink(&self) -> WeakPublisher<Vec<UiEvent>> {
+        self.session.get_input_sink()
+    }
+
+    /// Retrieves a view onto the update stream for this user interface
+    fn get_updates(&self) -> UiUpdateStream {
+        self.session.get_updates()
+    }
+}
+
+impl<CoreController: 'static+Controller> CoreUserInterface for HeadlessUi<CoreController> {
+    type CoreController = CoreController;
+
+    fn ui_tree(&self) -> BindRef<Control> {
+        self.session.ui_tree()
+    }
+
+    fn controller(&self) -> Arc<CoreController> {
+        self.session.controller()
+    }
+}
@@ -0,0 +1,15 @@
+//!
+//! # Headless user interface
+//!
+//! The headless UI runs a UI session for a controller without displaying anything. It tracks the state of the
+//! UI from the updates generated by the session and provides functions for finding and interacting with the
+//! controls, so controllers can be tested end-to-end without a GTK or browser front-end.
+//!
+
+mod state;
+mod headless_ui;
+
+pub use self::state::*;
+pub use self::headless_ui::*;
+
+#[cfg(test)] mod tests;
@@ -0,0 +1,433 @@
+use super::super::control::*;
+use super::super::layout::*;
+use super::super::theme::*;
+use super::super::property::*;
+use super::super::viewmodel::*;
+use super::super::viewmodel_update::*;
+use super::super::session::*;
+
+use flo_canvas::*;
+
+use std::collections::HashMap;
+
+///
+/// A control found in the UI tree of a headless UI, along with where it is and where it was laid out
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct HeadlessControl {
+    /// The address of this control in the UI tree (the indexes of the subcomponents leading to it)
+    pub address: Vec<u32>,
+
+    /// The path to the controller that owns this control (and which will receive its actions)
+    pub controller_path: Vec<String>,
+
+    /// The control itself
+    pub control: Control,
+
+    /// The address of the window control that displays this control (empty for controls in the main window)
+    pub window: Vec<u32>,
+
+    /// The bounds of this control in the coordinates of the window it's displayed in
+    pub bounds: ((f32, f32), (f32, f32))
+}
+
+impl HeadlessControl {
+    ///
+    /// Returns the name of the action that's generated by this control for a particular trigger
+    ///
+    pub fn action_name(&self, trigger: &ActionTrigger) -> Option<&str> {
+        self.control.attributes()
+            .filter_map(|attr| attr.action())
+            .filter(|(action_trigger, _name)| *action_trigger == trigger)
+            .map(|(_trigger, name)| &**name)
+            .nth(0)
+    }
+
+    ///
+    /// True if the specified point (in window coordinates) is within this control
+    ///
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let ((x1, y1), (x2, y2)) = self.bounds;
+
+        x >= x1 && x < x2 && y >= y1 && y < y2
+    }
+}
+
+///
+/// The state of a headless user interface, built up from the updates generated by a UI session
+///
+#[derive(Clone, Debug)]
+pub struct HeadlessState {
+    /// The size of the window that the UI is laid out in
+    size: (f32, f32),
+
+    /// The current UI tree
+    ui: Control,
+
+    /// The theme used to style the UI
+    theme: Option<Theme>,
+
+    /// The values in the viewmodel for each controller
+    viewmodels: HashMap<Vec<String>, HashMap<String, PropertyValue>>,
+
+    /// The drawing instructions for each canvas (since the last time it was cleared), indexed by controller path and canvas name
+    canvases: HashMap<(Vec<String>, String), Vec<Draw>>
+}
+
+impl HeadlessState {
+    ///
+    /// Creates a new headless state with an empty UI
+    ///
+    pub fn new(width: f32, height: f32) -> HeadlessState {
+        HeadlessState {
+            size:       (width, height),
+            ui:         Control::empty(),
+            theme:      None,
+            viewmodels: HashMap::new(),
+            canvases:   HashMap::new()
+        }
+    }
+
+    ///
+    /// Changes the size of the window that the UI is laid out in
+    ///
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.size = (width, height);
+    }
+
+    ///
+    /// Retrieves the current UI tree
+    ///
+    pub fn ui(&self) -> &Control {
+        &self.ui
+    }
+
+    ///
+    /// Retrieves the theme that the session has set for the UI, if there is one
+    ///
+    pub fn theme(&self) -> Option<&Theme> {
+        self.theme.as_ref()
+    }
+
+    ///
+    /// Returns the properties that the current theme sets for a control
+    ///
+    pub fn style(&self, control: &HeadlessControl) -> Vec<StyleProperty> {
+        self.theme.as_ref()
+            .map(|theme| theme.properties_for_control(&control.control))
+            .unwrap_or_else(|| vec![])
+    }
+
+    ///
+    /// Updates this state from a set of UI updates
+    ///
+    pub fn apply_updates(&mut self, updates: Vec<UiUpdate>) {
+        for update in updates {
+            match update {
+                UiUpdate::Start                     => { }
+                UiUpdate::UpdateUi(diffs)           => { diffs.into_iter().for_each(|diff| self.apply_ui_diff(diff)); }
+                UiUpdate::UpdateCanvas(diffs)       => { diffs.into_iter().for_each(|diff| self.apply_canvas_diff(diff)); }
+                UiUpdate::UpdateViewModel(updates)  => { updates.into_iter().for_each(|update| self.apply_viewmodel_update(update)); }
+                UiUpdate::UpdateTheme(theme)        => { self.theme = Some(theme); }
+            }
+        }
+    }
+
+    ///
+    /// Replaces the part of the UI tree at the address in a diff
+    ///
+    fn apply_ui_diff(&mut self, diff: UiDiff) {
+        fn replace(control: &Control, address: &[u32], new_ui: Control) -> Control {
+            if address.len() == 0 {
+                return new_ui;
+            }
+
+            // Rebuild the control, replacing the subcomponent at the next index along the address
+            let mut new_control = Control::new(control.control_type());
+            let mut new_ui      = Some(new_ui);
+
+            for attribute in control.attributes() {
+                match attribute {
+                    ControlAttribute::SubComponents(subcomponents) => {
+                        let subcomponents = subcomponents.iter()
+                            .enumerate()
+                            .map(|(index, subcomponent)| {
+                                if index as u32 == address[0] {
+                                    new_ui.take().map(|new_ui| replace(subcomponent, &address[1..], new_ui)).unwrap_or_else(|| subcomponent.clone())
+                                } else {
+                                    subcomponent.clone()
+                                }
+                            })
+                            .collect();
+
+                        new_control.add_attribute(ControlAttribute::SubComponents(subcomponents));
+                    }
+
+                    other => new_control.add_attribute(other.clone())
+                }
+            }
+
+            new_control
+        }
+
+        self.ui = replace(&self.ui, &diff.address, diff.new_ui);
+    }
+
+    ///
+    /// Adds the drawing instructions in a canvas diff to the canvas that they're for
+    ///
+    fn apply_canvas_diff(&mut self, diff: CanvasDiff) {
+        let drawing = self.canvases.entry((diff.controller, diff.canvas_name)).or_insert_with(|| vec![]);
+
+        for draw in diff.updates {
+            // Clearing the canvas discards everything that was drawn before
+            if draw == Draw::ClearCanvas {
+                drawing.clear();
+            }
+
+            drawing.push(draw);
+        }
+    }
+
+    ///
+    /// Updates the values stored for a viewmodel
+    ///
+    fn apply_viewmodel_update(&mut self, update: ViewModelUpdate) {
+        let values = self.viewmodels.entry(update.controller_path().clone()).or_insert_with(|| HashMap::new());
+
+        for change in update.updates() {
+            match change {
+                ViewModelChange::NewProperty(name, value)       |
+                ViewModelChange::PropertyChanged(name, value)   => { values.insert(name.clone(), value.clone()); }
+            }
+        }
+    }
+
+    ///
+    /// Retrieves the value of a property in the viewmodel of a controller
+    ///
+    pub fn viewmodel_value(&self, controller_path: &Vec<String>, property_name: &str) -> Option<PropertyValue> {
+        self.viewmodels.get(controller_path)
+            .and_then(|values| values.get(property_name))
+            .cloned()
+    }
+
+    ///
+    /// Retrieves the value of a property belonging to a control owned by a particular controller
+    ///
+    pub fn property_value(&self, controller_path: &Vec<String>, property: &Property) -> PropertyValue {
+        match property {
+            Property::Nothing               => PropertyValue::Nothing,
+            Property::Bool(value)           => PropertyValue::Bool(*value),
+            Property::Int(value)            => PropertyValue::Int(*value),
+            Property::Float(value)          => PropertyValue::Float(*value),
+            Property::String(value)         => PropertyValue::String(value.clone()),
+            Property::Bind(property_name)   => self.viewmodel_value(controller_path, property_name).unwrap_or(PropertyValue::Nothing)
+        }
+    }
+
+    ///
+    /// Retrieves the text of a control, if it has any
+    ///
+    pub fn text(&self, control: &HeadlessControl) -> Option<String> {
+        control.control.text()
+            .and_then(|text| self.string_value(&control.controller_path, text))
+    }
+
+    ///
+    /// Retrieves the tooltip for a control, if it has one
+    ///
+    pub fn tooltip(&self, control: &HeadlessControl) -> Option<String> {
+        control.control.attributes()
+            .filter_map(|attr| match attr.accessibility() { Some(Accessibility::Tooltip(tooltip)) => Some(tooltip), _ => None })
+            .nth(0)
+            .and_then(|tooltip| self.string_value(&control.controller_path, tooltip))
+    }
+
+    ///
+    /// Retrieves the name that a screen reader would use for a control (its accessibility label, or its text if it has no label)
+    ///
+    pub fn accessible_label(&self, control: &HeadlessControl) -> Option<String> {
+        control.control.attributes()
+            .filter_map(|attr| match attr.accessibility() { Some(Accessibility::Label(label)) => Some(label), _ => None })
+            .nth(0)
+            .and_then(|label| self.string_value(&control.controller_path, label))
+            .or_else(|| self.text(control))
+    }
+
+    ///
+    /// Retrieves the value of a property as a string
+    ///
+    fn string_value(&self, controller_path: &Vec<String>, property: &Property) -> Option<String> {
+        match self.property_value(controller_path, property) {
+            PropertyValue::Nothing          => None,
+            PropertyValue::Bool(value)      => Some(value.to_string()),
+            PropertyValue::Int(value)       => Some(value.to_string()),
+            PropertyValue::Float(value)     => Some(value.to_string()),
+            PropertyValue::String(value)    => Some(value)
+        }
+    }
+
+    ///
+    /// Retrieves the drawing instructions sent to a canvas since it was last cleared
+    ///
+    pub fn canvas_drawing(&self, controller_path: &Vec<String>, canvas_name: &str) -> Vec<Draw> {
+        self.canvases.get(&(controller_path.clone(), canvas_name.to_string()))
+            .cloned()
+            .unwrap_or_else(|| vec![])
+    }
+
+    ///
+    /// Lays out the UI tree and returns every control in it, in the order that they appear in the tree
+    ///
+    /// Positions are laid out using `ControlLayout`, in the same way as a real UI: floating positions use their current
+    /// value from the viewmodel. The items in list and tree views are laid out in rows, without any scrolling.
+    /// Window controls are laid out at their initial size in their own coordinates, and the content of closed
+    /// windows is left out.
+    ///
+    pub fn controls(&self) -> Vec<HeadlessControl> {
+        let mut result = vec![];
+        let (width, height) = self.size;
+
+        self.lay_out(&self.ui, vec![], vec![], vec![], ((0.0, 0.0), (width, height)), &mut result);
+
+        result
+    }
+
+    ///
+    /// Returns true if a window control is open
+    ///
+    pub fn window_is_open(&self, window: &HeadlessControl) -> bool {
+        let is_open = window.control.attributes()
+            .filter_map(|attr| match attr.window() { Some(Window::IsOpen(is_open)) => Some(is_open), _ => None })
+            .nth(0);
+
+        match is_open.map(|is_open| self.property_value(&window.controller_path, is_open)) {
+            Some(PropertyValue::Bool(false))    => false,
+            _                                   => true
+        }
+    }
+
+    ///
+    /// Lays out a control and its subcomponents within the specified bounds
+    ///
+    fn lay_out(&self, control: &Control, address: Vec<u32>, controller_path: Vec<String>, window: Vec<u32>, bounds: ((f32, f32), (f32, f32)), result: &mut Vec<HeadlessControl>) {
+        // Windows are laid out in their own coordinates, and display their subcomponents instead of the window they're in
+        let is_window               = control.control_type() == ControlType::Window;
+        let bounds                  = if is_window {
+            let (width, height) = control.attributes()
+                .filter_map(|attr| match attr.window() { Some(Window::Size(width, height)) => Some((*width, *height)), _ => None })
+                .nth(0)
+                .unwrap_or(DEFAULT_WINDOW_SIZE);
+
+            ((0.0, 0.0), (width as f32, height as f32))
+        } else {
+            bounds
+        };
+        let subcomponent_window     = if is_window { address.clone() } else { window.clone() };
+
+        // Subcomponents are laid out in the control after its padding is applied
+        let ((x1, y1), (x2, y2))    = bounds;
+        let ((left, top), (right, bottom)) = control.attributes()
+            .filter_map(|attr| attr.padding())
+            .nth(0)
+            .unwrap_or(((0, 0), (0, 0)));
+        let (inner_x, inner_y)      = (x1 + left as f32, y1 + top as f32);
+        let inner_width             = (x2 - x1 - (left + right) as f32).max(0.0);
+        let inner_height            = (y2 - y1 - (top + bottom) as f32).max(0.0);
+
+        // Subcomponents belong to this control's controller, if it has one
+        let mut subcomponent_path   = controller_path.clone();
+        if let Some(controller) = control.controller() {
+            subcomponent_path.push(controller.to_string());
+        }
+
+        let headless_control = HeadlessControl {
+            address:            address.clone(),
+            controller_path:    controller_path,
+            control:            control.clone(),
+            window:             window,
+            bounds:             bounds
+        };
+
+        // Closed windows don't display their content
+        let is_closed = is_window && !self.window_is_open(&headless_control);
+        result.push(headless_control);
+
+        if is_closed {
+            return;
+        }
+
+        if let Some(subcomponents) = control.subcomponents() {
+            // The items in list and tree views are stacked in rows, starting at the row of the first item
+            let control_type = control.control_type();
+            if control_type == ControlType::ListView || control_type == ControlType::TreeView {
+                let row_height  = control.attributes()
+                    .filter_map(|attr| match attr.items() { Some(Items::RowHeight(height)) => Some(*height), _ => None })
+                    .nth(0)
+                    .unwrap_or(DEFAULT_ROW_HEIGHT);
+                let first_index = control.attributes()
+                    .filter_map(|attr| match attr.items() { Some(Items::FirstIndex(index)) => Some(self.property_value(&subcomponent_path, index)), _ => None })
+                    .nth(0)
+                    .and_then(|index| match index { PropertyValue::Int(index) => Some(index as f32), _ => None })
+                    .unwrap_or(0.0);
+
+                for (index, subcomponent) in subcomponents.iter().enumerate() {
+                    let row_y = inner_y + (first_index + index as f32) * row_height;
+
+                    let mut subcomponent_address = address.clone();
+                    subcomponent_address.push(index as u32);
+
+                    self.lay_out(subcomponent, subcomponent_address, subcomponent_path.clone(), subcomponent_window.clone(), ((inner_x, row_y), (inner_x + inner_width, row_y + row_height)), result);
+                }
+
+                return;
+            }
+
+            let layout  = ControlLayout::for_control(control);
+            let items   = subcomponents.iter()
+                .map(|subcomponent| {
+                    let mut item    = LayoutItem::from_control(subcomponent);
+                    item.bounds     = self.resolve_floating(&subcomponent_path, item.bounds);
+                    item
+                })
+                .collect::<Vec<_>>();
+            let positions = layout.lay_out(&items, inner_width, inner_height);
+
+            for (index, (subcomponent, position)) in subcomponents.iter().zip(positions.into_iter()).enumerate() {
+                let ((sx1, sy1), (sx2, sy2)) = position;
+
+                let mut subcomponent_address = address.clone();
+                subcomponent_address.push(index as u32);
+
+                self.lay_out(subcomponent, subcomponent_address, subcomponent_path.clone(), subcomponent_window.clone(), ((inner_x + sx1, inner_y + sy1), (inner_x + sx2, inner_y + sy2)), result);
+            }
+        }
+    }
+
+    ///
+    /// Replaces any bound floating positions in a set of bounds with their current values
+    ///
+    fn resolve_floating(&self, controller_path: &Vec<String>, bounds: Bounds) -> Bounds {
+        let resolve = |position: Position| match position {
+            Position::Floating(property, offset) => {
+                let value = match self.property_value(controller_path, &property) {
+                    PropertyValue::Float(value) => value,
+                    PropertyValue::Int(value)   => value as f64,
+                    _                           => 0.0
+                };
+
+                Position::Floating(Property::Float(value), offset)
+            }
+
+            other => other
+        };
+
+        Bounds {
+            x1: resolve(bounds.x1),
+            y1: resolve(bounds.y1),
+            x2: resolve(bounds.x2),
+            y2: resolve(bounds.y2)
+        }
+    }
+}
@@ -0,0 +1,546 @@
+use super::*;
+use super::super::*;
+
+use flo_canvas::*;
+use flo_binding::*;
+
+use std::sync::*;
+
+///
+/// Controller with a button that counts clicks, a text box and a canvas that can be painted on
+///
+struct TestController {
+    ui: BindRef<Control>,
+    clicks: Binding<i32>,
+    viewmodel: Arc<DynamicViewModel>,
+    canvases: Arc<ResourceManager<BindingCanvas>>,
+    canvas: Resource<BindingCanvas>
+}
+
+impl TestController {
+    fn new() -> TestController {
+        let clicks      = bind(0);
+        let viewmodel   = Arc::new(DynamicViewModel::new());
+        let canvases    = Arc::new(ResourceManager::new());
+        let canvas      = canvases.register(BindingCanvas::new());
+        canvases.assign_name(&canvas, "Drawing");
+
+        viewmodel.set_property("Text", PropertyValue::String("Initial".to_string()));
+
+        let ui_clicks   = clicks.clone();
+        let ui_canvas   = canvas.clone();
+        let ui          = computed(move || {
+            let clicks = ui_clicks.get();
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(vec![
+                    Control::button()
+                        .with(ControlAttribute::Id("Counter".to_string()))
+                        .with(format!("Clicked {}", clicks))
+                        .with((ActionTrigger::Click, "Count"))
+                        .with(Accessibility::Label("Count clicks".to_property()))
+                        .with(Accessibility::Tooltip("Counts the number of times it's clicked".to_property()))
+                        .with(Bounds::next_horiz(100.0)),
+                    Control::text_box()
+                        .with(ControlAttribute::Text(Property::bound("Text")))
+                        .with((ActionTrigger::SetValue, "SetText"))
+                        .with(Bounds::next_horiz(100.0)),
+                    Control::canvas()
+                        .with(ui_canvas.clone())
+                        .with((ActionTrigger::Paint(PaintDevice::Pen), "Paint"))
+                        .with(Bounds::fill_horiz())
+                ])
+        });
+
+        TestController {
+            ui:         BindRef::from(ui),
+            clicks:     clicks,
+            viewmodel:  viewmodel,
+            canvases:   canvases,
+            canvas:     canvas
+        }
+    }
+}
+
+impl Controller for TestController {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.viewmodel.clone())
+    }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        Some(self.canvases.clone())
+    }
+
+    fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        match (action_id, action_data) {
+            ("Count", _)                                    => self.clicks.set(self.clicks.get() + 1),
+            ("SetText", ActionParameter::Value(value))      => self.viewmodel.set_property("Text", value.clone()),
+            ("Paint", ActionParameter::Paint(_, painting))  => {
+                let painting = painting.clone();
+
+                self.canvas.draw(move |gc| {
+                    gc.new_path();
+                    painting.iter().for_each(|paint| gc.line_to(paint.location.0, paint.location.1));
+                });
+            }
+
+            _                                               => { }
+        }
+    }
+}
+
+#[test]
+fn find_control_by_id_and_click() {
+    let mut ui  = HeadlessUi::new(TestController::new(), 400.0, 100.0);
+    let counter = ui.control_with_id("Counter").unwrap();
+
+    assert!(ui.text(&counter) == Some("Clicked 0".to_string()));
+    assert!(ui.click(&counter));
+
+    let counter = ui.control_with_id("Counter").unwrap();
+    assert!(ui.text(&counter) == Some("Clicked 1".to_string()));
+    assert!(ui.control_with_text("Clicked 1").is_some());
+}
+
+#[test]
+fn find_control_by_label() {
+    let mut ui  = HeadlessUi::new(TestController::new(), 400.0, 100.0);
+    let counter = ui.control_with_label("Count clicks").unwrap();
+
+    assert!(ui.tooltip(&counter) == Some("Counts the number of times it's clicked".to_string()));
+    assert!(ui.click(&counter));
+    assert!(ui.control_with_text("Clicked 1").is_some());
+
+    // Controls without a label are found by their text
+    assert!(ui.control_with_label("Initial").map(|text_box| text_box.control.control_type()) == Some(ControlType::TextBox));
+}
+
+#[test]
+fn controls_are_laid_out() {
+    let ui      = HeadlessUi::new(TestController::new(), 400.0, 100.0);
+    let canvas  = ui.controls_with_type(ControlType::Canvas);
+
+    assert!(canvas.len() == 1);
+    assert!(canvas[0].bounds == ((200.0, 0.0), (400.0, 100.0)));
+
+    assert!(ui.control_at(150.0, 50.0).map(|control| control.control.control_type()) == Some(ControlType::TextBox));
+}
+
+#[test]
+fn type_into_text_box() {
+    let mut ui      = HeadlessUi::new(TestController::new(), 400.0, 100.0);
+    let text_box    = ui.control_with_text("Initial").unwrap();
+
+    assert!(text_box.control.control_type() == ControlType::TextBox);
+    assert!(ui.type_text(&text_box, "Typed"));
+
+    assert!(ui.viewmodel_value(&vec![], "Text") == Some(PropertyValue::String("Typed".to_string())));
+    assert!(ui.control_with_text("Typed").is_some());
+}
+
+#[test]
+fn paint_on_canvas() {
+    let mut ui  = HeadlessUi::new(TestController::new(), 400.0, 100.0);
+    let canvas  = ui.controls_with_type(ControlType::Canvas)[0].clone();
+
+    assert!(!ui.paint_stroke(&canvas, PaintDevice::Mouse(MouseButton::Left), &[(10.0, 10.0), (20.0, 20.0)]));
+    assert!(ui.paint_stroke(&canvas, PaintDevice::Pen, &[(10.0, 10.0), (20.0, 20.0)]));
+
+    let drawing = ui.canvas_drawing(&canvas);
+    assert!(drawing.contains(&Draw::NewPath));
+    assert!(drawing.contains(&Draw::Line(20.0, 20.0)));
+}
+
+///
+/// Controller with a list of items that can be selected
+///
+struct ListController {
+    ui: BindRef<Control>,
+    selection: Binding<Vec<u32>>
+}
+
+impl ListController {
+    fn new() -> ListController {
+        let selection       = bind(vec![]);
+        let ui_selection    = selection.clone();
+
+        let ui = computed(move || {
+            let selection = ui_selection.get();
+            let items     = (0..5u32)
+                .map(|index| Control::label()
+                    .with(format!("Item {}", index))
+                    .with(State::Selected(Property::Bool(selection.contains(&index)))))
+                .collect::<Vec<_>>();
+
+            Control::list_view()
+                .with(Bounds::fill_all())
+                .with(Items::Selection(SelectionMode::Multiple))
+                .with(Items::RowHeight(20.0))
+                .with(Items::Count(Property::Int(5)))
+                .with(items)
+                .with((ActionTrigger::SelectItems, "Select"))
+        });
+
+        ListController {
+            ui:         BindRef::from(ui),
+            selection:  selection
+        }
+    }
+}
+
+impl Controller for ListController {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        match (action_id, action_data) {
+            ("Select", ActionParameter::Selection(items))   => self.selection.set(items.clone()),
+            _                                               => { }
+        }
+    }
+}
+
+#[test]
+fn list_items_are_laid_out_in_rows() {
+    let ui      = HeadlessUi::new(ListController::new(), 200.0, 200.0);
+    let item    = ui.control_with_text("Item 2").unwrap();
+
+    assert!(item.bounds == ((0.0, 40.0), (200.0, 60.0)));
+}
+
+#[test]
+fn select_list_items() {
+    let mut ui  = HeadlessUi::new(ListController::new(), 200.0, 200.0);
+    let list    = ui.controls_with_type(ControlType::ListView)[0].clone();
+
+    assert!(ui.select_items(&list, vec![1, 3]));
+
+    let item1   = ui.control_with_text("Item 1").unwrap();
+    let item2   = ui.control_with_text("Item 2").unwrap();
+    assert!(item1.control.attributes().any(|attr| attr == &ControlAttribute::StateAttr(State::Selected(Property::Bool(true)))));
+    assert!(item2.control.attributes().any(|attr| attr == &ControlAttribute::StateAttr(State::Selected(Property::Bool(false)))));
+}
+
+///
+/// Controller with an edit menu in its menu bar
+///
+struct MenuController {
+    ui: BindRef<Control>,
+    viewmodel: Arc<DynamicViewModel>,
+    commands: Binding<Vec<String>>
+}
+
+impl MenuController {
+    fn new() -> MenuController {
+        let viewmodel   = Arc::new(DynamicViewModel::new());
+        let commands    = bind(vec![]);
+
+        viewmodel.set_property("CanRedo", PropertyValue::Bool(false));
+
+        let ui = computed(move || {
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(Menu::Bar(vec![
+                    MenuItem::sub_menu("Edit", vec![
+                        MenuCommand::new("Undo", "Undo").with_shortcut("Ctrl+Z").into(),
+                        MenuCommand::new("Redo", "Redo").with_enabled(Property::bound("CanRedo")).into(),
+                        MenuItem::Separator,
+                        MenuCommand::new("Snap", "Snap to grid").with_checked(Property::Bool(true)).into()
+                    ])
+                ]))
+                .with((ActionTrigger::SelectMenuItem, "Menu"))
+        });
+
+        MenuController {
+            ui:         BindRef::from(ui),
+            viewmodel:  viewmodel,
+            commands:   commands
+        }
+    }
+}
+
+impl Controller for MenuController {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.viewmodel.clone())
+    }
+
+    fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        match (action_id, action_data) {
+            ("Menu", ActionParameter::MenuItem(command_id)) => {
+                let mut commands = self.commands.get();
+                commands.push(command_id.clone());
+                self.commands.set(commands);
+            },
+            _                                               => { }
+        }
+    }
+}
+
+#[test]
+fn select_menu_items() {
+    let controller  = MenuController::new();
+    let commands    = controller.commands.clone();
+    let mut ui      = HeadlessUi::new(controller, 200.0, 200.0);
+    let root        = ui.controls_with_type(ControlType::Container)[0].clone();
+
+    assert!(ui.select_menu_item(&root, "Undo"));
+    assert!(!ui.select_menu_item(&root, "Redo"));
+    assert!(!ui.select_menu_item(&root, "NotACommand"));
+
+    assert!(commands.get() == vec!["Undo".to_string()]);
+}
+
+///
+/// Controller that switches between the light and dark themes when its button is clicked
+///
+struct ThemeController {
+    ui: BindRef<Control>,
+    dark_mode: Binding<bool>,
+    theme: BindRef<Theme>
+}
+
+impl ThemeController {
+    fn new() -> ThemeController {
+        let dark_mode   = bind(false);
+        let theme_dark  = dark_mode.clone();
+        let theme       = computed(move || if theme_dark.get() { Theme::dark() } else { Theme::light() });
+
+        let ui = computed(move || {
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(vec![
+                    Control::button()
+                        .with(ControlAttribute::Id("DarkMode".to_string()))
+                        .with("Dark mode")
+                        .with((ActionTrigger::Click, "ToggleDarkMode"))
+                        .with(Bounds::next_horiz(100.0))
+                ])
+        });
+
+        ThemeController {
+            ui:         BindRef::from(ui),
+            dark_mode:  dark_mode,
+            theme:      BindRef::from(theme)
+        }
+    }
+}
+
+impl Controller for ThemeController {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn get_theme(&self) -> Option<BindRef<Theme>> {
+        Some(BindRef::clone(&self.theme))
+    }
+
+    fn action(&self, action_id: &str, _action_data: &ActionParameter) {
+        match action_id {
+            "ToggleDarkMode"    => self.dark_mode.set(!self.dark_mode.get()),
+            _                   => { }
+        }
+    }
+}
+
+#[test]
+fn switch_theme_at_runtime() {
+    let mut ui  = HeadlessUi::new(ThemeController::new(), 200.0, 100.0);
+    let button  = ui.control_with_id("DarkMode").unwrap();
+
+    assert!(ui.state().theme().map(|theme| theme.name.clone()) == Some("light".to_string()));
+    assert!(ui.style(&button).contains(&StyleProperty::CornerRadius(3.0)));
+
+    assert!(ui.click(&button));
+
+    assert!(ui.state().theme().map(|theme| theme.name.clone()) == Some("dark".to_string()));
+    assert!(ui.style(&button) == Theme::dark().properties_for_control(&button.control));
+}
+
+///
+/// Controller for the content of a secondary window or panel, with a button that sets a colour
+///
+struct SwatchController {
+    ui: BindRef<Control>,
+    color: Binding<String>
+}
+
+impl SwatchController {
+    fn new(color: &Binding<String>) -> SwatchController {
+        let ui = Control::container()
+            .with(Bounds::fill_all())
+            .with(vec![
+                Control::button()
+                    .with(ControlAttribute::Id("Red".to_string()))
+                    .with("Red")
+                    .with((ActionTrigger::Click, "PickRed"))
+                    .with(Bounds::next_horiz(50.0))
+            ]);
+
+        SwatchController {
+            ui:     BindRef::from(&ui),
+            color:  color.clone()
+        }
+    }
+}
+
+impl Controller for SwatchController {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn action(&self, action_id: &str, _action_data: &ActionParameter) {
+        match action_id {
+            "PickRed"   => self.color.set("Red".to_string()),
+            _           => { }
+        }
+    }
+}
+
+///
+/// Controller with a button that opens a colour picker window, and a dockable swatch panel
+///
+struct WindowsController {
+    ui: BindRef<Control>,
+    picker_open: Binding<bool>,
+    picker: Arc<WindowController<SwatchController>>,
+    panel: Arc<DockPanelController<SwatchController>>
+}
+
+impl WindowsController {
+    fn new(picker_color: &Binding<String>, panel_color: &Binding<String>, panel_position: &Binding<DockPosition>) -> WindowsController {
+        let picker_open = bind(false);
+        let picker      = WindowController::new(SwatchController::new(picker_color), &picker_open)
+            .with_title(&"Colours".to_string())
+            .with_kind(&WindowKind::Utility)
+            .with_size(&(200, 100));
+        let panel       = DockPanelController::new(SwatchController::new(panel_color), &"Swatches".to_string(), panel_position)
+            .with_size(&(80, 100));
+
+        let ui = Control::container()
+            .with(Bounds::fill_all())
+            .with(LayoutMode::Dock)
+            .with(vec![
+                Control::container()
+                    .with(DockEdge::Left)
+                    .with(LayoutMode::Column)
+                    .with_controller("Panel"),
+                Control::container()
+                    .with(vec![
+                        Control::button()
+                            .with(ControlAttribute::Id("OpenPicker".to_string()))
+                            .with("Colours...")
+                            .with((ActionTrigger::Click, "OpenPicker"))
+                            .with(Bounds::next_horiz(100.0))
+                    ]),
+                Control::container()
+                    .with_controller("Picker")
+            ]);
+
+        WindowsController {
+            ui:             BindRef::from(&ui),
+            picker_open:    picker_open,
+            picker:         Arc::new(picker),
+            panel:          Arc::new(panel)
+        }
+    }
+}
+
+impl Controller for WindowsController {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        match id {
+            "Picker"    => Some(self.picker.clone()),
+            "Panel"     => Some(self.panel.clone()),
+            _           => None
+        }
+    }
+
+    fn action(&self, action_id: &str, _action_data: &ActionParameter) {
+        match action_id {
+            "OpenPicker"    => self.picker_open.set(true),
+            _               => { }
+        }
+    }
+}
+
+#[test]
+fn open_secondary_window_and_route_its_actions() {
+    let picker_color    = bind("None".to_string());
+    let mut ui          = HeadlessUi::new(WindowsController::new(&picker_color, &bind("None".to_string()), &bind(DockPosition::Hidden)), 400.0, 300.0);
+
+    assert!(ui.windows().len() == 0);
+    assert!(ui.control_with_id("Red").is_none());
+
+    let open_picker = ui.control_with_id("OpenPicker").unwrap();
+    assert!(ui.click(&open_picker));
+
+    // The picker is displayed in its own window, in its own coordinates
+    let windows = ui.windows();
+    assert!(windows.len() == 1);
+    assert!(windows[0].bounds == ((0.0, 0.0), (200.0, 100.0)));
+
+    let red = ui.control_with_id("Red").unwrap();
+    assert!(red.window == windows[0].address);
+    assert!(red.bounds == ((0.0, 0.0), (50.0, 100.0)));
+    assert!(ui.controls_in_window(&windows[0]).contains(&red));
+    assert!(ui.control_at(25.0, 50.0) != Some(red.clone()));
+
+    // Actions from the window go to the window's controller
+    assert!(ui.click(&red));
+    assert!(picker_color.get() == "Red".to_string());
+
+    // Closing the window removes its content
+    assert!(ui.dismiss(&windows[0]));
+    assert!(ui.windows().len() == 0);
+    assert!(ui.control_with_id("Red").is_none());
+}
+
+#[test]
+fn dock_panel_can_float_and_return() {
+    let panel_color     = bind("None".to_string());
+    let panel_position  = bind(DockPosition::Docked);
+    let mut ui          = HeadlessUi::new(WindowsController::new(&bind("None".to_string()), &panel_color, &panel_position), 400.0, 300.0);
+
+    // While docked, the panel takes space from the left of the window
+    let open_picker = ui.control_with_id("OpenPicker").unwrap();
+    let red         = ui.control_with_id("Red").unwrap();
+
+    assert!(ui.windows().len() == 0);
+    assert!(red.window == vec![]);
+    assert!(red.bounds.0 .0 < 80.0);
+    assert!(open_picker.bounds.0 == (80.0, 0.0));
+
+    assert!(ui.click(&red));
+    assert!(panel_color.get() == "Red".to_string());
+
+    // Floating the panel moves it into a window and gives its space back to the rest of the UI
+    let float = ui.control_with_text("Float").unwrap();
+    assert!(ui.click(&float));
+
+    let windows     = ui.windows();
+    let open_picker = ui.control_with_id("OpenPicker").unwrap();
+    assert!(panel_position.get() == DockPosition::Floating);
+    assert!(windows.len() == 1);
+    assert!(ui.control_with_id("Red").unwrap().window == windows[0].address);
+    assert!(open_picker.bounds.0 == (0.0, 0.0));
+
+    // Closing the window docks the panel again
+    assert!(ui.dismiss(&windows[0]));
+
+    assert!(panel_position.get() == DockPosition::Docked);
+    assert!(ui.windows().len() == 0);
+    assert!(ui.control_with_id("OpenPicker").unwrap().bounds.0 == (80.0, 0.0));
+}
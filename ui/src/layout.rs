@@ -1,6 +1,17 @@
 use super::control::*;
 use super::property::*;
 
+use std::collections::HashSet;
+
+/// The font size assumed when estimating the size of the text in a control that doesn't specify one
+pub const DEFAULT_FONT_SIZE: f32 = 13.0;
+
+/// The width of a character relative to the font size, used when estimating the size of text
+const CHARACTER_WIDTH: f32 = 0.6;
+
+/// The height of a line of text relative to the font size
+const LINE_HEIGHT: f32 = 1.4;
+
 ///
 /// Converts Positions to actual coordinates
 ///
@@ -81,6 +92,736 @@ impl BoundsLayout {
     }
 }
 
+///
+/// Describes a control to be placed by a `ControlLayout`
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct LayoutItem {
+    /// The bounding box of this item (used when the container has the `LayoutMode::Bounds` mode)
+    pub bounds: Bounds,
+
+    /// The layout attributes attached to this item
+    pub layout: Vec<Layout>,
+
+    /// The size of the content of this item
+    pub content_size: (f32, f32),
+
+    /// True if this item is displayed outside of its container (eg, a window), so it takes up no space there
+    pub detached: bool
+}
+
+impl LayoutItem {
+    ///
+    /// Creates a new layout item
+    ///
+    pub fn new(bounds: Bounds, layout: Vec<Layout>, content_size: (f32, f32)) -> LayoutItem {
+        LayoutItem { bounds, layout, content_size, detached: false }
+    }
+
+    ///
+    /// Creates the layout item for a control, estimating the size of its content if it doesn't specify one
+    ///
+    /// Text is measured by assuming every character has the same width. A bounding box with an `Offset` as its
+    /// second coordinate fixes the size of the content along that axis.
+    ///
+    pub fn from_control(control: &Control) -> LayoutItem {
+        use self::Position::*;
+
+        let bounds          = control.bounding_box().cloned().unwrap_or_else(|| Bounds::fill_all());
+        let layout: Vec<_>  = control.attributes().filter_map(|attr| attr.layout()).cloned().collect();
+
+        let explicit_size   = layout.iter()
+            .filter_map(|layout| match layout { Layout::ContentSize(width, height) => Some((*width, *height)), _ => None })
+            .nth(0);
+
+        let content_size    = if let Some(explicit_size) = explicit_size {
+            explicit_size
+        } else {
+            let (estimated_width, estimated_height) = Self::estimate_content_size(control);
+            let width   = match bounds.x2 { Offset(width) => width, _ => estimated_width };
+            let height  = match bounds.y2 { Offset(height) => height, _ => estimated_height };
+
+            (width, height)
+        };
+
+        let detached        = control.control_type() == ControlType::Window;
+
+        LayoutItem { bounds, layout, content_size, detached }
+    }
+
+    ///
+    /// Estimates the size of the content of a control from its subcomponents or its text
+    ///
+    fn estimate_content_size(control: &Control) -> (f32, f32) {
+        let ((left, top), (right, bottom)) = control.attributes()
+            .filter_map(|attr| attr.padding())
+            .nth(0)
+            .unwrap_or(((0, 0), (0, 0)));
+
+        let (width, height) = match (control.subcomponents(), control.text()) {
+            (Some(subcomponents), _) if subcomponents.len() > 0 => {
+                let items: Vec<_> = subcomponents.iter().map(|subcomponent| LayoutItem::from_control(subcomponent)).collect();
+                ControlLayout::for_control(control).content_size(&items)
+            },
+
+            (_, Some(Property::String(text))) => {
+                let font_size = control.attributes()
+                    .filter_map(|attr| match attr.font() { Some(Font::Size(size)) => Some(*size), _ => None })
+                    .nth(0)
+                    .unwrap_or(DEFAULT_FONT_SIZE);
+
+                (text.chars().count() as f32 * font_size * CHARACTER_WIDTH, font_size * LINE_HEIGHT)
+            },
+
+            _ => (0.0, 0.0)
+        };
+
+        (width + (left + right) as f32, height + (top + bottom) as f32)
+    }
+
+    ///
+    /// The margins around this item, as (left, top, right, bottom)
+    ///
+    fn margin(&self) -> (f32, f32, f32, f32) {
+        self.layout.iter()
+            .filter_map(|layout| match layout { Layout::Margin((left, top), (right, bottom)) => Some((*left as f32, *top as f32, *right as f32, *bottom as f32)), _ => None })
+            .nth(0)
+            .unwrap_or((0.0, 0.0, 0.0, 0.0))
+    }
+
+    ///
+    /// The minimum size of this item
+    ///
+    fn min_size(&self) -> (f32, f32) {
+        self.layout.iter()
+            .filter_map(|layout| match layout { Layout::MinSize(width, height) => Some((*width, *height)), _ => None })
+            .nth(0)
+            .unwrap_or((0.0, 0.0))
+    }
+
+    ///
+    /// The maximum size of this item
+    ///
+    fn max_size(&self) -> (f32, f32) {
+        self.layout.iter()
+            .filter_map(|layout| match layout { Layout::MaxSize(width, height) => Some((*width, *height)), _ => None })
+            .nth(0)
+            .unwrap_or((std::f32::INFINITY, std::f32::INFINITY))
+    }
+
+    ///
+    /// The proportion of the remaining space in a row or column that this item grows into
+    ///
+    fn grow(&self) -> f32 {
+        self.layout.iter()
+            .filter_map(|layout| match layout { Layout::Grow(grow) => Some(*grow), _ => None })
+            .nth(0)
+            .unwrap_or(0.0)
+    }
+
+    ///
+    /// The alignment that this item overrides its container's alignment with
+    ///
+    fn align_self(&self) -> Option<Alignment> {
+        self.layout.iter()
+            .filter_map(|layout| match layout { Layout::AlignSelf(align) => Some(*align), _ => None })
+            .nth(0)
+    }
+
+    ///
+    /// The grid cell that this item is placed in, if it's not placed automatically
+    ///
+    fn grid_cell(&self) -> Option<(usize, usize)> {
+        self.layout.iter()
+            .filter_map(|layout| match layout { Layout::GridCell(column, row) => Some((*column as usize, *row as usize)), _ => None })
+            .nth(0)
+    }
+
+    ///
+    /// The number of columns and rows that this item covers in a grid
+    ///
+    fn grid_span(&self) -> (usize, usize) {
+        self.layout.iter()
+            .filter_map(|layout| match layout { Layout::GridSpan(columns, rows) => Some(((*columns as usize).max(1), (*rows as usize).max(1))), _ => None })
+            .nth(0)
+            .unwrap_or((1, 1))
+    }
+
+    ///
+    /// The edge that this item is docked against, if it's docked
+    ///
+    fn dock_edge(&self) -> Option<DockEdge> {
+        self.layout.iter()
+            .filter_map(|layout| match layout { Layout::Dock(edge) => Some(*edge), _ => None })
+            .nth(0)
+    }
+
+    ///
+    /// Constrains a size to the minimum and maximum sizes of this item
+    ///
+    fn clamp(&self, size: (f32, f32)) -> (f32, f32) {
+        let (min_width, min_height) = self.min_size();
+        let (max_width, max_height) = self.max_size();
+
+        (size.0.min(max_width).max(min_width), size.1.min(max_height).max(min_height))
+    }
+
+    ///
+    /// The size that this item would like to be
+    ///
+    pub fn preferred_size(&self) -> (f32, f32) {
+        self.clamp(self.content_size)
+    }
+
+    ///
+    /// The size that this item would like to be, including its margins
+    ///
+    fn outer_size(&self) -> (f32, f32) {
+        let (width, height)                 = self.preferred_size();
+        let (left, top, right, bottom)      = self.margin();
+
+        (width + left + right, height + top + bottom)
+    }
+}
+
+///
+/// Converts a (width, height) pair to a (main, cross) pair for a row (horizontal) or column (vertical), or back again
+///
+#[inline]
+fn along(horizontal: bool, (x, y): (f32, f32)) -> (f32, f32) {
+    if horizontal { (x, y) } else { (y, x) }
+}
+
+///
+/// Places the subcomponents of a control according to its layout attributes
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct ControlLayout {
+    /// How the items are arranged
+    mode: LayoutMode,
+
+    /// The space between items
+    gap: f32,
+
+    /// How items are placed along a row or column when there's space left over
+    justify: Alignment,
+
+    /// How items are aligned across a row or column
+    align_items: Alignment
+}
+
+impl ControlLayout {
+    ///
+    /// Creates a control layout from the layout attributes of a container
+    ///
+    pub fn new<'a, LayoutIter: IntoIterator<Item=&'a Layout>>(layout: LayoutIter) -> ControlLayout {
+        let mut result = ControlLayout {
+            mode:           LayoutMode::Bounds,
+            gap:            0.0,
+            justify:        Alignment::Start,
+            align_items:    Alignment::Stretch
+        };
+
+        for attr in layout {
+            match attr {
+                Layout::Mode(mode)          => result.mode = *mode,
+                Layout::Gap(gap)            => result.gap = *gap,
+                Layout::Justify(justify)    => result.justify = *justify,
+                Layout::AlignItems(align)   => result.align_items = *align,
+                _                           => ()
+            }
+        }
+
+        result
+    }
+
+    ///
+    /// Creates the layout for the subcomponents of a control
+    ///
+    pub fn for_control(control: &Control) -> ControlLayout {
+        ControlLayout::new(control.attributes().filter_map(|attr| attr.layout()))
+    }
+
+    ///
+    /// Lays out some items in an area of the specified size, returning their bounds in the same order as the items
+    ///
+    /// Detached items take up no space, and are given empty bounds at the origin.
+    ///
+    pub fn lay_out(&self, items: &[LayoutItem], width: f32, height: f32) -> Vec<((f32, f32), (f32, f32))> {
+        if items.iter().any(|item| item.detached) {
+            let attached: Vec<_>    = items.iter().filter(|item| !item.detached).cloned().collect();
+            let mut positions       = self.lay_out(&attached, width, height).into_iter();
+
+            return items.iter()
+                .map(|item| if item.detached { None } else { positions.next() })
+                .map(|position| position.unwrap_or(((0.0, 0.0), (0.0, 0.0))))
+                .collect();
+        }
+
+        match self.mode {
+            LayoutMode::Bounds          => self.lay_out_bounds(items, width, height),
+            LayoutMode::Row             => self.lay_out_line(&items.iter().collect::<Vec<_>>(), true, (0.0, width), (0.0, height)),
+            LayoutMode::Column          => self.lay_out_line(&items.iter().collect::<Vec<_>>(), false, (0.0, height), (0.0, width)),
+            LayoutMode::WrapRow         => self.lay_out_wrapped(items, width),
+            LayoutMode::Grid(columns)   => self.lay_out_grid(items, columns, width, height),
+            LayoutMode::Dock            => self.lay_out_dock(items, width, height)
+        }
+    }
+
+    ///
+    /// Returns the size needed to show all of the specified items at their preferred size
+    ///
+    pub fn content_size(&self, items: &[LayoutItem]) -> (f32, f32) {
+        if items.iter().any(|item| item.detached) {
+            let attached: Vec<_> = items.iter().filter(|item| !item.detached).cloned().collect();
+            return self.content_size(&attached);
+        }
+
+        let gaps = self.gap * (items.len().max(1) - 1) as f32;
+
+        match self.mode {
+            LayoutMode::Bounds => {
+                // Items that are sized relative to the container have no size when the container has no size
+                self.lay_out_bounds(items, 0.0, 0.0).into_iter()
+                    .fold((0.0, 0.0), |(width, height), ((_x1, _y1), (x2, y2))| (width.max(x2), height.max(y2)))
+            },
+
+            LayoutMode::Row | LayoutMode::WrapRow => {
+                items.iter()
+                    .map(|item| item.outer_size())
+                    .fold((gaps, 0.0), |(width, height), (item_width, item_height)| (width + item_width, height.max(item_height)))
+            },
+
+            LayoutMode::Column => {
+                items.iter()
+                    .map(|item| item.outer_size())
+                    .fold((0.0, gaps), |(width, height), (item_width, item_height)| (width.max(item_width), height + item_height))
+            },
+
+            LayoutMode::Grid(columns) => {
+                let columns         = (columns as usize).max(1);
+                let cells           = Self::grid_cells(items, columns);
+                let row_heights     = Self::grid_row_heights(items, &cells);
+                let column_width    = items.iter().zip(cells.iter())
+                    .filter(|(_item, (_column, _row, column_span, _row_span))| *column_span == 1)
+                    .map(|(item, _cell)| item.outer_size().0)
+                    .fold(0.0, f32::max);
+
+                let width           = column_width * columns as f32 + self.gap * (columns - 1) as f32;
+                let height          = row_heights.iter().sum::<f32>() + self.gap * (row_heights.len().max(1) - 1) as f32;
+
+                (width, height)
+            },
+
+            LayoutMode::Dock => {
+                // Work outwards from the items that fill the middle
+                let centre = items.iter()
+                    .filter(|item| item.dock_edge().is_none())
+                    .map(|item| item.outer_size())
+                    .fold((0.0, 0.0), |(width, height), (item_width, item_height)| (f32::max(width, item_width), f32::max(height, item_height)));
+
+                items.iter().rev()
+                    .filter_map(|item| item.dock_edge().map(|edge| (edge, item.outer_size())))
+                    .fold(centre, |(width, height), (edge, (item_width, item_height))| match edge {
+                        DockEdge::Left | DockEdge::Right    => (width + item_width + self.gap, f32::max(height, item_height)),
+                        DockEdge::Top | DockEdge::Bottom    => (f32::max(width, item_width), height + item_height + self.gap)
+                    })
+            }
+        }
+    }
+
+    ///
+    /// Turns a Position into an absolute position
+    ///
+    fn layout_position(last_pos: f32, next_pos: &Position, max_pos: f32, stretch_area: f32, total_stretch: f32) -> f32 {
+        use self::Position::*;
+
+        match next_pos {
+            &At(pos)                            => pos,
+            &Floating(Property::Float(pos), offset) => (pos as f32) + offset,
+            &Floating(_, offset)                => offset,
+            &Offset(offset)                     => last_pos + offset,
+            &Stretch(portion)                   => last_pos + stretch_area * (portion/total_stretch),
+            &Start                              => 0.0,
+            &End                                => max_pos,
+            &After                              => last_pos
+        }
+    }
+
+    ///
+    /// Returns the amount of stretch in a position
+    ///
+    fn stretch(pos: &Position) -> f32 {
+        if let &Position::Stretch(portion) = pos {
+            portion
+        } else {
+            0.0
+        }
+    }
+
+    ///
+    /// Lays out items according to their bounding boxes
+    ///
+    /// Stretched items share the space that's left over after all of the other items have been placed.
+    ///
+    fn lay_out_bounds(&self, items: &[LayoutItem], width: f32, height: f32) -> Vec<((f32, f32), (f32, f32))> {
+        // First pass: lay out the items without stretching them to find out how much space is left over
+        let mut xpos            = 0.0;
+        let mut ypos            = 0.0;
+        let mut total_stretch_x = 0.0;
+        let mut total_stretch_y = 0.0;
+
+        for item in items.iter() {
+            let bounds  = &item.bounds;
+
+            let x1      = Self::layout_position(xpos, &bounds.x1, width, 0.0, 1.0);
+            let x2      = Self::layout_position(x1, &bounds.x2, width, 0.0, 1.0);
+            let y1      = Self::layout_position(ypos, &bounds.y1, height, 0.0, 1.0);
+            let y2      = Self::layout_position(y1, &bounds.y2, height, 0.0, 1.0);
+
+            total_stretch_x += Self::stretch(&bounds.x1) + Self::stretch(&bounds.x2);
+            total_stretch_y += Self::stretch(&bounds.y1) + Self::stretch(&bounds.y2);
+
+            xpos = x2;
+            ypos = y2;
+        }
+
+        // Second pass: share out the remaining space between the stretched items
+        if total_stretch_x == 0.0 { total_stretch_x = 1.0; }
+        if total_stretch_y == 0.0 { total_stretch_y = 1.0; }
+
+        let stretch_x   = width - xpos;
+        let stretch_y   = height - ypos;
+
+        xpos            = 0.0;
+        ypos            = 0.0;
+
+        items.iter()
+            .map(|item| {
+                let bounds  = &item.bounds;
+
+                let x1      = Self::layout_position(xpos, &bounds.x1, width, stretch_x, total_stretch_x);
+                let x2      = Self::layout_position(x1, &bounds.x2, width, stretch_x, total_stretch_x);
+                let y1      = Self::layout_position(ypos, &bounds.y1, height, stretch_y, total_stretch_y);
+                let y2      = Self::layout_position(y1, &bounds.y2, height, stretch_y, total_stretch_y);
+
+                // The size constraints are applied after the bounds are decided
+                let (item_width, item_height)   = item.clamp((x2-x1, y2-y1));
+                let (x2, y2)                    = (x1+item_width, y1+item_height);
+
+                xpos = x2;
+                ypos = y2;
+
+                // Margins are taken out of the area reserved by the bounding box
+                let (left, top, right, bottom)  = item.margin();
+                ((x1+left, y1+top), ((x2-right).max(x1+left), (y2-bottom).max(y1+top)))
+            })
+            .collect()
+    }
+
+    ///
+    /// Lays out items in a single row (horizontal) or column (vertical)
+    ///
+    /// The main extent is the (start, length) of the area along the row or column, and the cross extent is the (start, length)
+    /// of the area across it.
+    ///
+    fn lay_out_line(&self, items: &[&LayoutItem], horizontal: bool, main_extent: (f32, f32), cross_extent: (f32, f32)) -> Vec<((f32, f32), (f32, f32))> {
+        let (main_start, main_length)   = main_extent;
+        let (cross_start, cross_length) = cross_extent;
+
+        // Margins as ((main_before, main_after), (cross_before, cross_after))
+        let margins: Vec<_> = items.iter()
+            .map(|item| {
+                let (left, top, right, bottom) = item.margin();
+                if horizontal { ((left, right), (top, bottom)) } else { ((top, bottom), (left, right)) }
+            })
+            .collect();
+
+        // Items start out at their preferred size along the main axis
+        let mut sizes: Vec<_>   = items.iter().map(|item| along(horizontal, item.preferred_size()).0).collect();
+        let gaps                = self.gap * (items.len().max(1) - 1) as f32;
+        let used                = sizes.iter().sum::<f32>() + margins.iter().map(|((before, after), _)| before + after).sum::<f32>() + gaps;
+        let mut free            = main_length - used;
+
+        let clamp_main          = |item: &LayoutItem, size: f32| {
+            along(horizontal, item.clamp(along(horizontal, (size, 0.0)))).0
+        };
+
+        if free > 0.0 {
+            // Items grow into the space that's left over
+            let total_grow  = items.iter().map(|item| item.grow()).sum::<f32>();
+            let grow: Vec<_> = if total_grow > 0.0 {
+                items.iter().map(|item| item.grow()).collect()
+            } else if self.justify == Alignment::Stretch {
+                items.iter().map(|_| 1.0).collect()
+            } else {
+                items.iter().map(|_| 0.0).collect()
+            };
+            let total_grow  = grow.iter().sum::<f32>();
+
+            if total_grow > 0.0 {
+                let available = free;
+
+                for index in 0..items.len() {
+                    let new_size    = clamp_main(items[index], sizes[index] + available * (grow[index]/total_grow));
+                    free            -= new_size - sizes[index];
+                    sizes[index]    = new_size;
+                }
+            }
+        } else if free < 0.0 {
+            // Items shrink in proportion to their size when there's not enough space, but not below their minimum size
+            let total_size = sizes.iter().sum::<f32>();
+
+            if total_size > 0.0 {
+                let overflow = -free;
+
+                for index in 0..items.len() {
+                    let new_size    = clamp_main(items[index], sizes[index] - overflow * (sizes[index]/total_size));
+                    free            += sizes[index] - new_size;
+                    sizes[index]    = new_size;
+                }
+            }
+        }
+
+        // Any remaining space decides where the first item goes
+        let free        = free.max(0.0);
+        let mut pos     = main_start + match self.justify {
+            Alignment::Start | Alignment::Stretch   => 0.0,
+            Alignment::Centre                       => free / 2.0,
+            Alignment::End                          => free
+        };
+
+        items.iter().enumerate()
+            .map(|(index, item)| {
+                let ((main_before, main_after), (cross_before, cross_after)) = margins[index];
+
+                // Position along the line
+                let main1       = pos + main_before;
+                let main2       = main1 + sizes[index];
+                pos             = main2 + main_after + self.gap;
+
+                // Position across the line
+                let available   = (cross_length - cross_before - cross_after).max(0.0);
+                let (_, cross)  = along(horizontal, item.preferred_size());
+                let align       = item.align_self().unwrap_or(self.align_items);
+                let cross_size  = match align {
+                    Alignment::Stretch  => along(horizontal, item.clamp(along(horizontal, (0.0, available)))).1,
+                    _                   => cross.min(available)
+                };
+                let cross1      = cross_start + cross_before + match align {
+                    Alignment::Start | Alignment::Stretch   => 0.0,
+                    Alignment::Centre                       => (available - cross_size) / 2.0,
+                    Alignment::End                          => available - cross_size
+                };
+                let cross2      = cross1 + cross_size;
+
+                if horizontal {
+                    ((main1, cross1), (main2, cross2))
+                } else {
+                    ((cross1, main1), (cross2, main2))
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Lays out items in rows, starting a new row whenever an item won't fit in the current one
+    ///
+    fn lay_out_wrapped(&self, items: &[LayoutItem], width: f32) -> Vec<((f32, f32), (f32, f32))> {
+        // Split the items into rows
+        let mut rows        = vec![];
+        let mut row         = vec![];
+        let mut row_width   = 0.0;
+
+        for item in items.iter() {
+            let (item_width, _) = item.outer_size();
+
+            if row.len() > 0 && row_width + self.gap + item_width > width {
+                rows.push(row);
+                row         = vec![];
+                row_width   = 0.0;
+            }
+
+            row_width += if row.len() > 0 { self.gap + item_width } else { item_width };
+            row.push(item);
+        }
+
+        if row.len() > 0 {
+            rows.push(row);
+        }
+
+        // Each row is as tall as its tallest item
+        let mut result  = vec![];
+        let mut ypos    = 0.0;
+
+        for row in rows {
+            let row_height = row.iter().map(|item| item.outer_size().1).fold(0.0, f32::max);
+
+            result.extend(self.lay_out_line(&row, true, (0.0, width), (ypos, row_height)));
+            ypos += row_height + self.gap;
+        }
+
+        result
+    }
+
+    ///
+    /// Decides which grid cell each item goes in, as (column, row, column_span, row_span)
+    ///
+    fn grid_cells(items: &[LayoutItem], columns: usize) -> Vec<(usize, usize, usize, usize)> {
+        let mut occupied    = HashSet::new();
+        let mut next_cell   = 0;
+        let mut cells       = vec![];
+
+        for item in items.iter() {
+            let (column_span, row_span) = item.grid_span();
+            let column_span             = column_span.min(columns);
+
+            let is_free = |occupied: &HashSet<(usize, usize)>, column: usize, row: usize| {
+                column + column_span <= columns
+                    && (column..(column+column_span)).all(|column| (row..(row+row_span)).all(|row| !occupied.contains(&(column, row))))
+            };
+
+            // Items without a cell go in the next cell that has space for them
+            let (column, row) = if let Some((column, row)) = item.grid_cell() {
+                (column.min(columns - column_span), row)
+            } else {
+                while !is_free(&occupied, next_cell % columns, next_cell / columns) {
+                    next_cell += 1;
+                }
+
+                (next_cell % columns, next_cell / columns)
+            };
+
+            for covered_column in column..(column+column_span) {
+                for covered_row in row..(row+row_span) {
+                    occupied.insert((covered_column, covered_row));
+                }
+            }
+
+            cells.push((column, row, column_span, row_span));
+        }
+
+        cells
+    }
+
+    ///
+    /// Works out the height of each row in a grid from the items that cover a single row
+    ///
+    fn grid_row_heights(items: &[LayoutItem], cells: &[(usize, usize, usize, usize)]) -> Vec<f32> {
+        let num_rows            = cells.iter().map(|(_column, row, _column_span, row_span)| row + row_span).max().unwrap_or(0);
+        let mut row_heights     = vec![0.0; num_rows];
+
+        for (item, (_column, row, _column_span, row_span)) in items.iter().zip(cells.iter()) {
+            if *row_span == 1 {
+                row_heights[*row] = f32::max(row_heights[*row], item.outer_size().1);
+            }
+        }
+
+        row_heights
+    }
+
+    ///
+    /// Lays out items in the cells of a grid
+    ///
+    /// The columns share the width of the area equally and each row is as tall as the tallest item in it. The rows
+    /// are placed according to the `Justify` setting: `Stretch` shares any remaining height between them.
+    ///
+    fn lay_out_grid(&self, items: &[LayoutItem], columns: u32, width: f32, height: f32) -> Vec<((f32, f32), (f32, f32))> {
+        let columns             = (columns as usize).max(1);
+        let cells               = Self::grid_cells(items, columns);
+        let mut row_heights     = Self::grid_row_heights(items, &cells);
+
+        let column_width        = ((width - self.gap * (columns - 1) as f32) / columns as f32).max(0.0);
+        let grid_height         = row_heights.iter().sum::<f32>() + self.gap * (row_heights.len().max(1) - 1) as f32;
+        let free                = (height - grid_height).max(0.0);
+
+        let mut ypos            = match self.justify {
+            Alignment::Start    => 0.0,
+            Alignment::Centre   => free / 2.0,
+            Alignment::End      => free,
+            Alignment::Stretch  => {
+                let extra = free / (row_heights.len().max(1) as f32);
+                row_heights.iter_mut().for_each(|row_height| *row_height += extra);
+                0.0
+            }
+        };
+
+        let mut row_tops        = vec![];
+        for row_height in row_heights.iter() {
+            row_tops.push(ypos);
+            ypos += row_height + self.gap;
+        }
+
+        items.iter().zip(cells.iter())
+            .map(|(item, (column, row, column_span, row_span))| {
+                // Work out the area covered by this item's cells
+                let x1                          = (*column as f32) * (column_width + self.gap);
+                let x2                          = x1 + (*column_span as f32) * column_width + ((column_span - 1) as f32) * self.gap;
+                let y1                          = row_tops[*row];
+                let y2                          = row_tops[row + row_span - 1] + row_heights[row + row_span - 1];
+
+                // Align the item within the area
+                let (left, top, right, bottom)  = item.margin();
+                let available                   = ((x2 - x1 - left - right).max(0.0), (y2 - y1 - top - bottom).max(0.0));
+                let align                       = item.align_self().unwrap_or(self.align_items);
+                let (item_width, item_height)   = match align {
+                    Alignment::Stretch  => item.clamp(available),
+                    _                   => { let (width, height) = item.preferred_size(); (width.min(available.0), height.min(available.1)) }
+                };
+                let (offset_x, offset_y)        = match align {
+                    Alignment::Start | Alignment::Stretch   => (0.0, 0.0),
+                    Alignment::Centre                       => ((available.0 - item_width) / 2.0, (available.1 - item_height) / 2.0),
+                    Alignment::End                          => (available.0 - item_width, available.1 - item_height)
+                };
+
+                let (x1, y1)                    = (x1 + left + offset_x, y1 + top + offset_y);
+                ((x1, y1), (x1 + item_width, y1 + item_height))
+            })
+            .collect()
+    }
+
+    ///
+    /// Lays out items against the edges of the area, with the undocked items filling the space that's left over
+    ///
+    /// Docked items take their preferred size away from the edge and are stretched along it. Each item is docked
+    /// within the space left by the items before it.
+    ///
+    fn lay_out_dock(&self, items: &[LayoutItem], width: f32, height: f32) -> Vec<((f32, f32), (f32, f32))> {
+        // The area that hasn't been taken by a docked item yet
+        let (mut left, mut top, mut right, mut bottom) = (0.0, 0.0, width, height);
+
+        let docked: Vec<_> = items.iter()
+            .map(|item| {
+                item.dock_edge().map(|edge| {
+                    let (item_width, item_height)   = item.outer_size();
+                    let item_width                  = item_width.min((right - left).max(0.0));
+                    let item_height                 = item_height.min((bottom - top).max(0.0));
+
+                    match edge {
+                        DockEdge::Left      => { let area = ((left, top), (left + item_width, bottom)); left += item_width + self.gap; area },
+                        DockEdge::Right     => { let area = ((right - item_width, top), (right, bottom)); right -= item_width + self.gap; area },
+                        DockEdge::Top       => { let area = ((left, top), (right, top + item_height)); top += item_height + self.gap; area },
+                        DockEdge::Bottom    => { let area = ((left, bottom - item_height), (right, bottom)); bottom -= item_height + self.gap; area }
+                    }
+                })
+            })
+            .collect();
+
+        // Everything else fills the middle
+        let centre = ((left, top), (f32::max(right, left), f32::max(bottom, top)));
+
+        items.iter().zip(docked.into_iter())
+            .map(|(item, area)| {
+                let ((x1, y1), (x2, y2))        = area.unwrap_or(centre);
+                let (left, top, right, bottom)  = item.margin();
+                let (item_width, item_height)   = item.clamp(((x2 - x1 - left - right).max(0.0), (y2 - y1 - top - bottom).max(0.0)));
+
+                ((x1 + left, y1 + top), (x1 + left + item_width, y1 + top + item_height))
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -179,4 +920,155 @@ mod test {
 
         assert!(layout.to_abs_bounds(&Bounds::fill_horiz()) == ((60.0, 0.0), (200.0, 20.0)));
     }
+
+    fn item(width: f32, height: f32) -> LayoutItem {
+        LayoutItem::new(Bounds::fill_all(), vec![], (width, height))
+    }
+
+    #[test]
+    pub fn control_layout_shares_stretch_between_bounds() {
+        let layout  = ControlLayout::new(&vec![]);
+        let items   = vec![
+            LayoutItem::new(Bounds::next_vert(32.0), vec![], (0.0, 0.0)),
+            LayoutItem::new(Bounds::stretch_vert(1.0), vec![], (0.0, 0.0)),
+            LayoutItem::new(Bounds::next_vert(256.0), vec![], (0.0, 0.0))
+        ];
+
+        let positions = layout.lay_out(&items, 1920.0, 1080.0);
+
+        assert!(positions == vec![
+            ((0.0, 0.0), (1920.0, 32.0)),
+            ((0.0, 32.0), (1920.0, 824.0)),
+            ((0.0, 824.0), (1920.0, 1080.0))
+        ]);
+    }
+
+    #[test]
+    pub fn row_places_items_at_their_preferred_size() {
+        let layout      = ControlLayout::new(&vec![Layout::Mode(LayoutMode::Row), Layout::Gap(10.0)]);
+        let positions   = layout.lay_out(&vec![item(20.0, 10.0), item(30.0, 10.0)], 100.0, 40.0);
+
+        assert!(positions == vec![((0.0, 0.0), (20.0, 40.0)), ((30.0, 0.0), (60.0, 40.0))]);
+    }
+
+    #[test]
+    pub fn row_items_grow_into_remaining_space() {
+        let layout      = ControlLayout::new(&vec![Layout::Mode(LayoutMode::Row)]);
+        let growing     = LayoutItem::new(Bounds::fill_all(), vec![Layout::Grow(1.0)], (20.0, 10.0));
+        let positions   = layout.lay_out(&vec![growing, item(20.0, 10.0)], 100.0, 40.0);
+
+        assert!(positions == vec![((0.0, 0.0), (80.0, 40.0)), ((80.0, 0.0), (100.0, 40.0))]);
+    }
+
+    #[test]
+    pub fn stretched_row_items_stop_at_their_max_size() {
+        let layout      = ControlLayout::new(&vec![Layout::Mode(LayoutMode::Row), Layout::Justify(Alignment::Stretch)]);
+        let limited     = LayoutItem::new(Bounds::fill_all(), vec![Layout::MaxSize(20.0, 100.0)], (10.0, 10.0));
+        let positions   = layout.lay_out(&vec![limited, item(10.0, 10.0)], 100.0, 40.0);
+
+        assert!(positions == vec![((0.0, 0.0), (20.0, 40.0)), ((20.0, 0.0), (70.0, 40.0))]);
+    }
+
+    #[test]
+    pub fn row_items_are_surrounded_by_margins() {
+        let layout      = ControlLayout::new(&vec![Layout::Mode(LayoutMode::Row)]);
+        let with_margin = LayoutItem::new(Bounds::fill_all(), vec![Layout::Margin((5, 5), (5, 5))], (10.0, 10.0));
+        let positions   = layout.lay_out(&vec![with_margin, item(10.0, 10.0)], 100.0, 40.0);
+
+        assert!(positions == vec![((5.0, 5.0), (15.0, 35.0)), ((20.0, 0.0), (30.0, 40.0))]);
+    }
+
+    #[test]
+    pub fn column_can_centre_items() {
+        let layout      = ControlLayout::new(&vec![Layout::Mode(LayoutMode::Column), Layout::Justify(Alignment::Centre), Layout::AlignItems(Alignment::Centre)]);
+        let positions   = layout.lay_out(&vec![item(40.0, 20.0)], 100.0, 100.0);
+
+        assert!(positions == vec![((30.0, 40.0), (70.0, 60.0))]);
+    }
+
+    #[test]
+    pub fn wrapped_row_starts_new_row_when_full() {
+        let layout      = ControlLayout::new(&vec![Layout::Mode(LayoutMode::WrapRow)]);
+        let positions   = layout.lay_out(&vec![item(40.0, 10.0), item(40.0, 20.0), item(40.0, 10.0)], 100.0, 100.0);
+
+        assert!(positions == vec![
+            ((0.0, 0.0), (40.0, 20.0)),
+            ((40.0, 0.0), (80.0, 20.0)),
+            ((0.0, 20.0), (40.0, 30.0))
+        ]);
+    }
+
+    #[test]
+    pub fn grid_places_items_in_cells() {
+        let layout      = ControlLayout::new(&vec![Layout::Mode(LayoutMode::Grid(2))]);
+        let positions   = layout.lay_out(&vec![item(10.0, 10.0), item(10.0, 20.0), item(10.0, 10.0)], 100.0, 100.0);
+
+        assert!(positions == vec![
+            ((0.0, 0.0), (50.0, 20.0)),
+            ((50.0, 0.0), (100.0, 20.0)),
+            ((0.0, 20.0), (50.0, 30.0))
+        ]);
+    }
+
+    #[test]
+    pub fn grid_items_can_span_columns() {
+        let layout      = ControlLayout::new(&vec![Layout::Mode(LayoutMode::Grid(2))]);
+        let spanning    = LayoutItem::new(Bounds::fill_all(), vec![Layout::GridSpan(2, 1)], (10.0, 10.0));
+        let positions   = layout.lay_out(&vec![spanning, item(10.0, 10.0)], 100.0, 100.0);
+
+        assert!(positions == vec![((0.0, 0.0), (100.0, 10.0)), ((0.0, 10.0), (50.0, 20.0))]);
+    }
+
+    #[test]
+    pub fn content_size_of_row_includes_text_and_fixed_sizes() {
+        let row = Control::container()
+            .with(LayoutMode::Row)
+            .with(Layout::Gap(4.0))
+            .with(vec![
+                Control::label().with("Hello").with(Font::Size(10.0)),
+                Control::label().with(Bounds::next_horiz(20.0))
+            ]);
+
+        let (width, height) = LayoutItem::from_control(&row).content_size;
+
+        assert!((width - 54.0).abs() < 0.01);
+        assert!((height - 14.0).abs() < 0.01);
+    }
+
+    #[test]
+    pub fn dock_places_items_against_edges() {
+        let layout      = ControlLayout::new(&vec![Layout::Mode(LayoutMode::Dock)]);
+        let left        = LayoutItem::new(Bounds::fill_all(), vec![Layout::Dock(DockEdge::Left)], (20.0, 10.0));
+        let bottom      = LayoutItem::new(Bounds::fill_all(), vec![Layout::Dock(DockEdge::Bottom)], (10.0, 30.0));
+        let positions   = layout.lay_out(&vec![left, bottom, item(10.0, 10.0)], 100.0, 100.0);
+
+        assert!(positions == vec![
+            ((0.0, 0.0), (20.0, 100.0)),
+            ((20.0, 70.0), (100.0, 100.0)),
+            ((20.0, 0.0), (100.0, 70.0))
+        ]);
+    }
+
+    #[test]
+    pub fn windows_take_no_space_in_a_row() {
+        let row = Control::container()
+            .with(LayoutMode::Row)
+            .with(Layout::Gap(4.0))
+            .with(vec![
+                Control::label().with(Bounds::next_horiz(20.0)),
+                Control::window().with(Window::Size(200, 100)).with(vec![Control::label().with(Bounds::next_horiz(50.0))]),
+                Control::label().with(Bounds::next_horiz(30.0))
+            ]);
+
+        let items: Vec<_>   = row.subcomponents().unwrap().iter().map(|control| LayoutItem::from_control(control)).collect();
+        let positions       = ControlLayout::for_control(&row).lay_out(&items, 100.0, 10.0);
+        let (width, _)      = LayoutItem::from_control(&row).content_size;
+
+        assert!(positions == vec![
+            ((0.0, 0.0), (20.0, 10.0)),
+            ((0.0, 0.0), (0.0, 0.0)),
+            ((24.0, 0.0), (54.0, 10.0))
+        ]);
+        assert!((width - 54.0).abs() < 0.01);
+    }
 }
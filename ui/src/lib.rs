@@ -19,6 +19,7 @@ extern crate desync;
 mod user_interface;
 mod json;
 mod layout;
+mod transition;
 mod diff;
 mod controller;
 mod property;
@@ -32,11 +33,15 @@ pub mod control;
 pub mod image;
 pub mod controllers;
 pub mod session;
+pub mod headless;
+pub mod theme;
+pub mod localization;
 
 pub use user_interface::*;
 pub use self::json::*;
 pub use self::control::*;
 pub use self::layout::*;
+pub use self::transition::*;
 pub use self::diff::*;
 pub use self::controller::*;
 pub use self::property::*;
@@ -47,3 +52,5 @@ pub use self::resource_manager::*;
 pub use self::binding_canvas::*;
 pub use self::image::*;
 pub use self::controllers::*;
+pub use self::theme::*;
+pub use self::localization::*;
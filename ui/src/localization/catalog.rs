@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+///
+/// Error that can occur while parsing a message catalog
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum CatalogError {
+    /// A line could not be understood (the line number starts at 1)
+    BadLine(usize),
+
+    /// A `msgid` was not followed by a `msgstr` (the line number is of the `msgid`)
+    MissingTranslation(usize),
+
+    /// A string was not terminated or contained an unknown escape sequence
+    BadString(usize)
+}
+
+///
+/// A set of translations for the messages displayed by a user interface
+///
+/// Catalogs are read from a subset of the gettext `.po` format:
+///
+/// ```text
+/// # Comments start with a '#'
+/// msgid "New file"
+/// msgstr "Nuevo archivo"
+/// ```
+///
+/// Messages can contain placeholders like `{frame}`, which are filled in by `Localization::format()`.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct MessageCatalog {
+    /// Maps message IDs to their translations
+    messages: HashMap<String, String>
+}
+
+///
+/// Parses a quoted string from a .po file
+///
+fn parse_string(text: &str, line_number: usize) -> Result<String, CatalogError> {
+    let text = text.trim();
+
+    if !text.starts_with('"') || !text.ends_with('"') || text.len() < 2 {
+        return Err(CatalogError::BadString(line_number));
+    }
+
+    let mut result  = String::new();
+    let mut chars   = text[1..text.len()-1].chars();
+
+    while let Some(chr) = chars.next() {
+        match chr {
+            '\\' => match chars.next() {
+                Some('n')   => result.push('\n'),
+                Some('t')   => result.push('\t'),
+                Some('"')   => result.push('"'),
+                Some('\\')  => result.push('\\'),
+                _           => { return Err(CatalogError::BadString(line_number)); }
+            },
+            '"'     => { return Err(CatalogError::BadString(line_number)); }
+            chr     => result.push(chr)
+        }
+    }
+
+    Ok(result)
+}
+
+impl MessageCatalog {
+    ///
+    /// Creates a new, empty message catalog
+    ///
+    pub fn new() -> MessageCatalog {
+        MessageCatalog {
+            messages: HashMap::new()
+        }
+    }
+
+    ///
+    /// Reads a message catalog from the text of a .po file
+    ///
+    pub fn from_po(po_file: &str) -> Result<MessageCatalog, CatalogError> {
+        // The section of the entry that continuation lines (lines that are just a string) add to
+        enum Section { None, Id, Translation }
+
+        let mut catalog         = MessageCatalog::new();
+        let mut section         = Section::None;
+        let mut id              = String::new();
+        let mut id_line         = 0;
+        let mut translation     = None;
+
+        for (line_index, line) in po_file.lines().enumerate() {
+            let line_number = line_index + 1;
+            let line        = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                // Blank lines and comments are ignored
+                continue;
+            } else if line.starts_with("msgid ") {
+                // Start of a new message: finish the old one
+                if let Section::Id = section { return Err(CatalogError::MissingTranslation(id_line)); }
+                if let Some(translation) = translation.take() { catalog.add_message(&id, translation); }
+
+                id          = parse_string(&line[6..], line_number)?;
+                id_line     = line_number;
+                section     = Section::Id;
+            } else if line.starts_with("msgstr ") {
+                if let Section::Id = section { } else { return Err(CatalogError::BadLine(line_number)); }
+
+                translation = Some(parse_string(&line[7..], line_number)?);
+                section     = Section::Translation;
+            } else if line.starts_with('"') {
+                // Long strings can be continued on the following lines
+                let continuation = parse_string(line, line_number)?;
+
+                match section {
+                    Section::None           => { return Err(CatalogError::BadLine(line_number)); }
+                    Section::Id             => id.push_str(&continuation),
+                    Section::Translation    => translation.as_mut().map(|translation| translation.push_str(&continuation)).unwrap_or(())
+                }
+            } else {
+                return Err(CatalogError::BadLine(line_number));
+            }
+        }
+
+        // Finish the last message
+        if let Section::Id = section { return Err(CatalogError::MissingTranslation(id_line)); }
+        if let Some(translation) = translation.take() { catalog.add_message(&id, translation); }
+
+        Ok(catalog)
+    }
+
+    ///
+    /// Adds a translation to this catalog
+    ///
+    /// Empty IDs (the header of a .po file) and empty translations (untranslated messages) are ignored
+    ///
+    pub fn add_message(&mut self, id: &str, translation: String) {
+        if !id.is_empty() && !translation.is_empty() {
+            self.messages.insert(id.to_string(), translation);
+        }
+    }
+
+    ///
+    /// Adds all of the messages from another catalog to this one
+    ///
+    pub fn merge(&mut self, catalog: MessageCatalog) {
+        self.messages.extend(catalog.messages);
+    }
+
+    ///
+    /// Retrieves the translation of a message, if this catalog contains one
+    ///
+    pub fn message<'a>(&'a self, id: &str) -> Option<&'a str> {
+        self.messages.get(id).map(|translation| translation.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_po_file() {
+        let catalog = MessageCatalog::from_po(r#"
+# Header
+msgid ""
+msgstr "Content-Type: text/plain; charset=UTF-8\n"
+
+msgid "New file"
+msgstr "Nuevo archivo"
+
+msgid "Some recent changes may have been lost: "
+"do you want to try to recover them?"
+msgstr "Es posible que se hayan perdido cambios recientes: "
+"¿quiere intentar recuperarlos?"
+
+msgid "Untranslated"
+msgstr ""
+"#).unwrap();
+
+        assert!(catalog.message("New file") == Some("Nuevo archivo"));
+        assert!(catalog.message("Some recent changes may have been lost: do you want to try to recover them?") == Some("Es posible que se hayan perdido cambios recientes: ¿quiere intentar recuperarlos?"));
+        assert!(catalog.message("Untranslated") == None);
+        assert!(catalog.message("") == None);
+    }
+
+    #[test]
+    fn missing_translation_is_an_error() {
+        assert!(MessageCatalog::from_po("msgid \"One\"\nmsgid \"Two\"\nmsgstr \"Dos\"\n") == Err(CatalogError::MissingTranslation(1)));
+    }
+}
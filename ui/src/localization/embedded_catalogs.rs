@@ -0,0 +1,82 @@
+use super::catalog::*;
+use super::localization::*;
+
+use std::sync::Once;
+
+///
+/// The message catalogs that a crate compiles in, as pairs of language tags and the contents of `.po` files
+///
+/// Crates that display text declare their catalogs in a static and call `load()` before creating their UI:
+///
+/// ```ignore
+/// pub static MESSAGES: EmbeddedCatalogs = EmbeddedCatalogs::new(&[
+///     ("ja", include_str!("../locale/ja.po")),
+///     ("es", include_str!("../locale/es.po"))
+/// ]);
+/// ```
+///
+pub struct EmbeddedCatalogs {
+    /// Used to make sure the catalogs are only added to the shared localization once
+    loaded: Once,
+
+    /// The language tags and .po files for each catalog
+    catalogs: &'static [(&'static str, &'static str)]
+}
+
+impl EmbeddedCatalogs {
+    ///
+    /// Declares a set of embedded catalogs
+    ///
+    pub const fn new(catalogs: &'static [(&'static str, &'static str)]) -> EmbeddedCatalogs {
+        EmbeddedCatalogs {
+            loaded:     Once::new(),
+            catalogs:   catalogs
+        }
+    }
+
+    ///
+    /// Adds these catalogs to the shared localization
+    ///
+    /// The catalogs are only loaded once, no matter how many times this is called.
+    ///
+    pub fn load(&self) {
+        self.loaded.call_once(|| {
+            let localization = localization();
+
+            for (locale, po_file) in self.catalogs.iter() {
+                localization.add_catalog(locale, MessageCatalog::from_po(po_file).unwrap());
+            }
+        });
+    }
+
+    ///
+    /// Checks that all of these catalogs can be parsed, without loading them
+    ///
+    pub fn check(&self) -> Result<(), CatalogError> {
+        for (_locale, po_file) in self.catalogs.iter() {
+            MessageCatalog::from_po(po_file)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static GOOD_CATALOGS: EmbeddedCatalogs = EmbeddedCatalogs::new(&[
+        ("es", "msgid \"New file\"\nmsgstr \"Nuevo archivo\"\n")
+    ]);
+
+    static BAD_CATALOGS: EmbeddedCatalogs = EmbeddedCatalogs::new(&[
+        ("es", "msgid \"New file\"\nmsgstr \"Nuevo archivo\"\n"),
+        ("ja", "msgid \"New file\"\n")
+    ]);
+
+    #[test]
+    fn can_check_catalogs() {
+        assert!(GOOD_CATALOGS.check().is_ok());
+        assert!(BAD_CATALOGS.check() == Err(CatalogError::MissingTranslation(1)));
+    }
+}
@@ -0,0 +1,189 @@
+use super::catalog::*;
+use super::number_format::*;
+
+use flo_binding::*;
+use lazy_static::lazy_static;
+
+use std::env;
+use std::sync::*;
+use std::collections::HashMap;
+
+lazy_static! {
+    /// The localization shared by every controller in this process
+    static ref SHARED_LOCALIZATION: Arc<Localization> = Arc::new(Localization::new());
+}
+
+///
+/// Returns the localization shared by every controller in this process
+///
+pub fn localization() -> Arc<Localization> {
+    Arc::clone(&SHARED_LOCALIZATION)
+}
+
+///
+/// Translates a message into the language of the shared localization
+///
+/// When this is called while computing a binding (such as the UI for a controller), the binding will be
+/// updated whenever the locale is changed.
+///
+pub fn localized(message: &str) -> String {
+    SHARED_LOCALIZATION.text(message)
+}
+
+///
+/// Translates a message into the language of the shared localization, and fills in its placeholders
+///
+pub fn localized_format(message: &str, arguments: &[(&str, String)]) -> String {
+    SHARED_LOCALIZATION.format(message, arguments)
+}
+
+///
+/// Provides translations of the text displayed in a user interface for the current locale
+///
+pub struct Localization {
+    /// The language tag for the current locale (eg, `en`, `ja` or `es-MX`)
+    locale: Binding<String>,
+
+    /// The message catalogs for each language tag
+    catalogs: RwLock<HashMap<String, MessageCatalog>>
+}
+
+impl Localization {
+    ///
+    /// Creates a new localization, with the locale initially set to English
+    ///
+    pub fn new() -> Localization {
+        Localization {
+            locale:     bind("en".to_string()),
+            catalogs:   RwLock::new(HashMap::new())
+        }
+    }
+
+    ///
+    /// Reads the language tag for the user's locale from the environment (eg, `ja` if `LANG` is set to `ja_JP.UTF-8`)
+    ///
+    pub fn locale_from_environment() -> Option<String> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|variable| env::var(variable).ok())
+            .filter(|value| !value.is_empty() && value != "C" && value != "POSIX")
+            .map(|value| value.split('.').nth(0).unwrap_or("").replace('_', "-"))
+            .nth(0)
+    }
+
+    ///
+    /// Adds translations for a locale (messages that are already translated are replaced)
+    ///
+    pub fn add_catalog(&self, locale: &str, catalog: MessageCatalog) {
+        self.catalogs.write().unwrap()
+            .entry(locale.to_string())
+            .or_insert_with(|| MessageCatalog::new())
+            .merge(catalog);
+    }
+
+    ///
+    /// Changes the current locale
+    ///
+    pub fn set_locale(&self, locale: &str) {
+        self.locale.set(locale.to_string());
+    }
+
+    ///
+    /// Returns a binding for the current locale
+    ///
+    pub fn locale(&self) -> BindRef<String> {
+        BindRef::new(&self.locale)
+    }
+
+    ///
+    /// Looks up the translation of a message for a locale
+    ///
+    /// Messages are looked up in the catalog for the full language tag (eg, `es-MX`) and then for the
+    /// language (`es`). The message itself is returned if there is no translation.
+    ///
+    fn translate(&self, locale: &str, message: &str) -> String {
+        let catalogs = self.catalogs.read().unwrap();
+        let language = locale.split('-').nth(0).unwrap_or(locale);
+
+        catalogs.get(locale)
+            .and_then(|catalog| catalog.message(message))
+            .or_else(|| catalogs.get(language).and_then(|catalog| catalog.message(message)))
+            .unwrap_or(message)
+            .to_string()
+    }
+
+    ///
+    /// Translates a message into the current language
+    ///
+    pub fn text(&self, message: &str) -> String {
+        self.translate(&self.locale.get(), message)
+    }
+
+    ///
+    /// Returns a binding that translates a message into the current language
+    ///
+    pub fn bind_text(self: &Arc<Self>, message: &str) -> BindRef<String> {
+        let localization    = Arc::clone(self);
+        let message         = message.to_string();
+
+        BindRef::from(computed(move || localization.text(&message)))
+    }
+
+    ///
+    /// Translates a message into the current language and replaces any placeholders (like `{frame}`) with their values
+    ///
+    pub fn format(&self, message: &str, arguments: &[(&str, String)]) -> String {
+        let mut result = self.text(message);
+
+        for (name, value) in arguments.iter() {
+            result = result.replace(&format!("{{{}}}", name), value);
+        }
+
+        result
+    }
+
+    ///
+    /// Returns the format for numbers in the current locale
+    ///
+    pub fn number_format(&self) -> NumberFormat {
+        NumberFormat::for_locale(&self.locale.get())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn spanish() -> MessageCatalog {
+        MessageCatalog::from_po("msgid \"Frame {frame}\"\nmsgstr \"Fotograma {frame}\"\n").unwrap()
+    }
+
+    #[test]
+    fn untranslated_messages_are_unchanged() {
+        let localization = Localization::new();
+        localization.add_catalog("es", spanish());
+
+        assert!(localization.text("Frame {frame}") == "Frame {frame}");
+        assert!(localization.text("Not in the catalog") == "Not in the catalog");
+    }
+
+    #[test]
+    fn regional_locale_uses_language_catalog() {
+        let localization = Localization::new();
+        localization.add_catalog("es", spanish());
+        localization.set_locale("es-MX");
+
+        assert!(localization.format("Frame {frame}", &[("frame", "12".to_string())]) == "Fotograma 12");
+    }
+
+    #[test]
+    fn changing_locale_updates_bindings() {
+        let localization = Arc::new(Localization::new());
+        localization.add_catalog("es", spanish());
+
+        let text = localization.bind_text("Frame {frame}");
+        assert!(text.get() == "Frame {frame}");
+
+        localization.set_locale("es");
+        assert!(text.get() == "Fotograma {frame}");
+    }
+}
@@ -0,0 +1,17 @@
+//!
+//! Localization of the text displayed in a user interface
+//!
+//! Messages are identified by their English text (as with gettext), so a message that has not been
+//! translated is displayed in English. Translations are loaded from message catalogs, which use a
+//! subset of the gettext `.po` format.
+//!
+
+mod catalog;
+mod number_format;
+mod localization;
+mod embedded_catalogs;
+
+pub use self::catalog::*;
+pub use self::number_format::*;
+pub use self::localization::*;
+pub use self::embedded_catalogs::*;
@@ -0,0 +1,104 @@
+///
+/// Describes how numbers are written in a particular locale
+///
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct NumberFormat {
+    /// The character that separates the integer and fractional parts of a number
+    pub decimal_separator: char,
+
+    /// The character that separates groups of thousands, if numbers are grouped
+    pub group_separator: Option<char>,
+
+    /// The minimum number of digits in the integer part of a number before the digits are grouped
+    pub min_grouping_digits: usize
+}
+
+impl NumberFormat {
+    ///
+    /// Returns the number format for a language tag (eg, `en`, `ja` or `es-MX`)
+    ///
+    pub fn for_locale(locale: &str) -> NumberFormat {
+        let language = locale.split(|chr| chr == '-' || chr == '_').nth(0).unwrap_or("");
+
+        match language {
+            // Spanish uses a decimal comma, and doesn't group numbers with only four digits
+            "es"    => NumberFormat { decimal_separator: ',', group_separator: Some('.'), min_grouping_digits: 5 },
+
+            "de"    => NumberFormat { decimal_separator: ',', group_separator: Some('.'), min_grouping_digits: 4 },
+            "fr"    => NumberFormat { decimal_separator: ',', group_separator: Some('\u{202f}'), min_grouping_digits: 4 },
+
+            _       => NumberFormat { decimal_separator: '.', group_separator: Some(','), min_grouping_digits: 4 }
+        }
+    }
+
+    ///
+    /// Adds group separators to a string of digits
+    ///
+    fn group_digits(&self, digits: &str) -> String {
+        match self.group_separator {
+            Some(separator) if digits.len() >= self.min_grouping_digits => {
+                let mut result = String::new();
+
+                for (index, digit) in digits.chars().enumerate() {
+                    if index > 0 && (digits.len()-index)%3 == 0 {
+                        result.push(separator);
+                    }
+                    result.push(digit);
+                }
+
+                result
+            }
+
+            _ => digits.to_string()
+        }
+    }
+
+    ///
+    /// Formats an integer, padding it with zeros so it has at least `min_digits` digits
+    ///
+    pub fn format_integer(&self, value: i64, min_digits: usize) -> String {
+        let digits = format!("{:0width$}", value.abs(), width=min_digits);
+        let digits = self.group_digits(&digits);
+
+        if value < 0 { format!("-{}", digits) } else { digits }
+    }
+
+    ///
+    /// Formats a number with a fixed number of decimal places
+    ///
+    pub fn format_decimal(&self, value: f64, decimal_places: usize) -> String {
+        let formatted           = format!("{:.places$}", value.abs(), places=decimal_places);
+        let mut parts           = formatted.split('.');
+        let integer_part        = self.group_digits(parts.next().unwrap_or("0"));
+        let fraction_part       = parts.next();
+        let sign                = if value < 0.0 && formatted.chars().any(|chr| chr != '0' && chr != '.') { "-" } else { "" };
+
+        match fraction_part {
+            Some(fraction_part) => format!("{}{}{}{}", sign, integer_part, self.decimal_separator, fraction_part),
+            None                => format!("{}{}", sign, integer_part)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_english_numbers() {
+        let format = NumberFormat::for_locale("en-GB");
+
+        assert!(format.format_integer(1234567, 0) == "1,234,567");
+        assert!(format.format_integer(7, 2) == "07");
+        assert!(format.format_decimal(-1234.5, 2) == "-1,234.50");
+    }
+
+    #[test]
+    fn format_spanish_numbers() {
+        let format = NumberFormat::for_locale("es");
+
+        assert!(format.format_integer(1234, 0) == "1234");
+        assert!(format.format_integer(12345, 0) == "12.345");
+        assert!(format.format_decimal(0.25, 2) == "0,25");
+    }
+}
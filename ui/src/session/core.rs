@@ -231,20 +231,30 @@ impl UiSessionCore {
     ///
     /// Sends ticks to the specified controller and all its subcontrollers
     ///
+    fn tick_controllers(controller: &dyn Controller) {
+        // Send ticks to the subcontrollers first
+        let ui              = controller.ui().get();
+        let subcontrollers  = ui.all_controllers();
+        for subcontroller_name in subcontrollers {
+            if let Some(subcontroller) = controller.get_subcontroller(&subcontroller_name) {
+                Self::tick_controllers(&*subcontroller);
+            }
+        }
+
+        // Send the tick to the controller
+        controller.tick();
+    }
+
+    ///
+    /// Sends ticks to the specified controller and all its subcontrollers, then notifies the update stream
+    ///
+    /// The update stream is notified once for every tick event, after every controller has been ticked, which makes
+    /// the tick usable as a marker for when the events before it have been processed.
+    ///
     #[must_use]
     fn dispatch_tick<'a>(&'a mut self, controller: &'a dyn Controller) -> BoxFuture<'a, ()> {
         async move {
-            // Send ticks to the subcontrollers first
-            let ui              = controller.ui().get();
-            let subcontrollers  = ui.all_controllers();
-            for subcontroller_name in subcontrollers {
-                if let Some(subcontroller) = controller.get_subcontroller(&subcontroller_name) {
-                    self.dispatch_tick(&*subcontroller).await;
-                }
-            }
-
-            // Send the tick to the controller
-            controller.tick();
+            Self::tick_controllers(controller);
 
             self.tick.publish(()).await;
         }.boxed()
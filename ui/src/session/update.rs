@@ -1,3 +1,4 @@
+use super::super::theme::*;
 use super::super::control::*;
 use super::super::viewmodel_update::*;
 
@@ -45,5 +46,8 @@ pub enum UiUpdate {
     UpdateCanvas(Vec<CanvasDiff>),
 
     /// Represents an update to the viewmodel
-    UpdateViewModel(Vec<ViewModelUpdate>)
+    UpdateViewModel(Vec<ViewModelUpdate>),
+
+    /// The theme used to style the UI has changed (this is sent before any UI updates that should use the new theme)
+    UpdateTheme(Theme)
 }
@@ -2,6 +2,7 @@ use super::update::*;
 use super::canvas_stream::*;
 use super::viewmodel_stream::*;
 use super::super::diff::*;
+use super::super::theme::*;
 use super::super::control::*;
 use super::super::controller::*;
 
@@ -40,6 +41,9 @@ pub struct UiUpdateStream {
     /// The updates for the UI tree
     ui_updates: FollowStream<Control, BindRef<Control>>,
 
+    /// The updates for the theme of the root controller, if it has one
+    theme_updates: Option<FollowStream<Theme, BindRef<Theme>>>,
+
     /// The viewmodel updates
     viewmodel_updates: ViewModelUpdateStream,
 
@@ -69,6 +73,9 @@ impl UiUpdateStream {
         let ui_tree             = assemble_ui(Arc::clone(&controller));
         let ui_updates          = follow(ui_tree.clone());
 
+        // Stream from the theme
+        let theme_updates       = controller.get_theme().map(|theme| follow(theme));
+
         // Stream from the viewmodel
         let viewmodel_updates   = ViewModelUpdateStream::new(Arc::clone(&controller));
 
@@ -79,6 +86,7 @@ impl UiUpdateStream {
         let new_stream = UiUpdateStream {
             _ui_tree:           ui_tree,
             ui_updates:         ui_updates,
+            theme_updates:      theme_updates,
             last_ui:            None,
             update_suspend:     update_suspend,
             viewmodel_updates:  viewmodel_updates,
@@ -138,6 +146,26 @@ impl UiUpdateStream {
         }
     }
 
+    ///
+    /// Pulls any theme changes into the pending UI stream
+    ///
+    fn pull_theme_events(&mut self, context: &mut Context) {
+        if let Some(theme_updates) = self.theme_updates.as_mut() {
+            // Only the most recent theme matters
+            let mut new_theme = None;
+
+            while let Poll::Ready(Some(theme)) = theme_updates.poll_next_unpin(context) {
+                new_theme = Some(theme);
+            }
+
+            if let Some(new_theme) = new_theme {
+                self.pending_ui.lock().unwrap()
+                    .get_or_insert_with(|| vec![])
+                    .push(UiUpdate::UpdateTheme(new_theme));
+            }
+        }
+    }
+
     ///
     /// Pulls any viewmodel events into the pending stream
     ///
@@ -199,6 +227,8 @@ impl Stream for UiUpdateStream {
             self_ref.pull_viewmodel_events(context);
 
             // UI events are polled last but we return them first (this way the viewmodel and canvas updates apply to the current UI)
+            // The theme is updated before the UI so that any new controls are created with the new theme
+            self_ref.pull_theme_events(context);
             self_ref.pull_ui_events(context);
 
             // Try to read the pending update, if there is one
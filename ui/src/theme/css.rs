@@ -0,0 +1,98 @@
+use super::style::*;
+use super::theme::*;
+use super::super::control::*;
+
+use flo_canvas::Color;
+
+use std::fmt::Write;
+
+///
+/// The flavour of CSS to generate for a theme
+///
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CssDialect {
+    /// CSS for the GTK+ toolkit
+    Gtk,
+
+    /// CSS for a web browser
+    Html
+}
+
+///
+/// Formats a colour as a CSS rgba() value
+///
+fn css_color(color: &Color) -> String {
+    let (r, g, b, a)    = color.to_rgba_components();
+    let (r, g, b)       = ((r*255.0).floor() as i32, (g*255.0).floor() as i32, (b*255.0).floor() as i32);
+
+    format!("rgba({}, {}, {}, {})", r, g, b, a)
+}
+
+impl StyleProperty {
+    ///
+    /// Returns the CSS declarations for this property
+    ///
+    pub fn to_css(&self, dialect: CssDialect) -> String {
+        use self::StyleProperty::*;
+
+        match self {
+            Foreground(color)       => format!("color: {};", css_color(color)),
+            Background(color)       => match dialect {
+                // GTK themes often draw backgrounds using images, which hide the background colour
+                CssDialect::Gtk     => format!("background-color: {}; background-image: none;", css_color(color)),
+                CssDialect::Html    => format!("background-color: {};", css_color(color))
+            },
+            FontSize(size)          => format!("font-size: {}px;", size),
+            FontWeight(weight)      => format!("font-weight: {};", *weight as u32),
+            Border(width, color)    => format!("border: {}px solid {};", width, css_color(color)),
+            CornerRadius(radius)    => format!("border-radius: {}px;", radius)
+        }
+    }
+}
+
+impl Theme {
+    ///
+    /// Generates a style sheet for this theme
+    ///
+    /// The type selector function returns the CSS selector that matches controls of a particular type (eg, `.flo-button`
+    /// for GTK or `flo-button` for HTML). Classes set with `Hint::Class` become CSS classes.
+    ///
+    pub fn to_css<TypeSelector: Fn(ControlType) -> String>(&self, dialect: CssDialect, type_selector: TypeSelector) -> String {
+        let mut css = String::new();
+
+        // Rules are written in order of specificity so that GTK (where every selector is a class) applies them in the same order as a browser
+        for style in self.ordered_styles() {
+            let selector = match &style.selector {
+                StyleSelector::All                                          => "*".to_string(),
+                StyleSelector::ControlType(control_type)                    => type_selector(*control_type),
+                StyleSelector::Class(class_name)                            => format!(".{}", class_name),
+                StyleSelector::ControlTypeWithClass(control_type, class_name) => format!("{}.{}", type_selector(*control_type), class_name)
+            };
+
+            let declarations = style.properties.iter()
+                .map(|property| property.to_css(dialect))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            writeln!(css, "{} {{ {} }}", selector, declarations).ok();
+        }
+
+        css
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_html_css() {
+        let theme = Theme::new("test")
+            .with_style(StyleSelector::Class("toolbox".to_string()), vec![StyleProperty::Background(Color::Rgba(0.0, 0.0, 0.0, 1.0))])
+            .with_style(StyleSelector::ControlType(ControlType::Button), vec![StyleProperty::CornerRadius(4.0), StyleProperty::FontWeight(FontWeight::Bold)]);
+
+        let css = theme.to_css(CssDialect::Html, |_control_type| "flo-button".to_string());
+
+        assert!(css == "flo-button { border-radius: 4px; font-weight: 700; }\n.toolbox { background-color: rgba(0, 0, 0, 1); }\n");
+    }
+}
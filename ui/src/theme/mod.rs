@@ -0,0 +1,7 @@
+mod css;
+mod style;
+mod theme;
+
+pub use self::css::*;
+pub use self::style::*;
+pub use self::theme::*;
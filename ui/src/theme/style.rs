@@ -0,0 +1,122 @@
+use super::super::control::*;
+
+use flo_canvas::Color;
+
+///
+/// A property that can be set by a style
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum StyleProperty {
+    /// The foreground (text) colour of a control
+    Foreground(Color),
+
+    /// The background colour of a control
+    Background(Color),
+
+    /// The font size in pixels
+    FontSize(f32),
+
+    /// The font weight
+    FontWeight(FontWeight),
+
+    /// The width and colour of the border around a control
+    Border(f32, Color),
+
+    /// The radius of the corners of a control
+    CornerRadius(f32)
+}
+
+///
+/// Describes which controls a style applies to
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum StyleSelector {
+    /// Every control
+    All,
+
+    /// Controls of a particular type
+    ControlType(ControlType),
+
+    /// Controls with a particular class (assigned using `Hint::Class`)
+    Class(String),
+
+    /// Controls of a particular type that also have a particular class
+    ControlTypeWithClass(ControlType, String)
+}
+
+///
+/// A named style class: a set of properties applied to the controls matching a selector
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Style {
+    /// The controls this style applies to
+    pub selector: StyleSelector,
+
+    /// The properties set by this style
+    pub properties: Vec<StyleProperty>
+}
+
+impl StyleProperty {
+    ///
+    /// True if this property sets the same thing as another property (ie, is the same kind of property)
+    ///
+    pub fn is_same_kind(&self, other: &StyleProperty) -> bool {
+        use std::mem;
+
+        mem::discriminant(self) == mem::discriminant(other)
+    }
+
+    ///
+    /// True if a control overrides this property with one of its own attributes
+    ///
+    pub fn is_overridden_by(&self, control: &Control) -> bool {
+        use self::StyleProperty::*;
+
+        control.attributes().any(|attr| match (self, attr) {
+            (Foreground(_), ControlAttribute::AppearanceAttr(Appearance::Foreground(_)))    => true,
+            (Background(_), ControlAttribute::AppearanceAttr(Appearance::Background(_)))    => true,
+            (FontSize(_), ControlAttribute::FontAttr(Font::Size(_)))                        => true,
+            (FontWeight(_), ControlAttribute::FontAttr(Font::Weight(_)))                    => true,
+            _                                                                               => false
+        })
+    }
+}
+
+impl StyleSelector {
+    ///
+    /// Returns how specific this selector is: styles with more specific selectors override less specific ones
+    ///
+    pub fn specificity(&self) -> u32 {
+        use self::StyleSelector::*;
+
+        match self {
+            All                             => 0,
+            ControlType(_)                  => 1,
+            Class(_)                        => 2,
+            ControlTypeWithClass(_, _)      => 3
+        }
+    }
+
+    ///
+    /// True if this selector matches a control with the specified type and classes
+    ///
+    pub fn matches(&self, control_type: ControlType, classes: &[&str]) -> bool {
+        use self::StyleSelector::*;
+
+        match self {
+            All                                                 => true,
+            ControlType(selector_type)                          => *selector_type == control_type,
+            Class(class_name)                                   => classes.contains(&class_name.as_str()),
+            ControlTypeWithClass(selector_type, class_name)     => *selector_type == control_type && classes.contains(&class_name.as_str())
+        }
+    }
+}
+
+impl Style {
+    ///
+    /// Creates a new style
+    ///
+    pub fn new(selector: StyleSelector, properties: Vec<StyleProperty>) -> Style {
+        Style { selector, properties }
+    }
+}
@@ -0,0 +1,165 @@
+use super::style::*;
+use super::super::control::*;
+
+use flo_canvas::Color;
+
+///
+/// A theme describes the colours, fonts, borders and corner radii used for the controls in a user interface
+///
+/// Styles are applied in order of how specific their selectors are (see `StyleSelector::specificity()`), and
+/// then in the order they were added to the theme. Attributes set directly on a control, such as
+/// `Appearance::Foreground` or `Font::Size`, always take priority over the theme.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Theme {
+    /// The name of this theme
+    pub name: String,
+
+    /// The styles that make up this theme
+    pub styles: Vec<Style>
+}
+
+impl Theme {
+    ///
+    /// Creates a new theme with no styles
+    ///
+    pub fn new(name: &str) -> Theme {
+        Theme {
+            name:   name.to_string(),
+            styles: vec![]
+        }
+    }
+
+    ///
+    /// Adds a style to this theme
+    ///
+    pub fn with_style(mut self, selector: StyleSelector, properties: Vec<StyleProperty>) -> Theme {
+        self.styles.push(Style::new(selector, properties));
+        self
+    }
+
+    ///
+    /// Returns the styles in this theme, in the order that they should be applied
+    ///
+    pub fn ordered_styles(&self) -> Vec<&Style> {
+        let mut styles = self.styles.iter().collect::<Vec<_>>();
+
+        // sort_by_key is stable so styles with the same specificity stay in the order they were added
+        styles.sort_by_key(|style| style.selector.specificity());
+        styles
+    }
+
+    ///
+    /// Returns the style properties that apply to a control (not including its subcomponents)
+    ///
+    /// Properties that the control sets using its own attributes are left out.
+    ///
+    pub fn properties_for_control(&self, control: &Control) -> Vec<StyleProperty> {
+        let control_type    = control.control_type();
+        let classes         = control.attributes()
+            .filter_map(|attr| match attr.hint() { Some(Hint::Class(class_name)) => Some(class_name.as_str()), _ => None })
+            .collect::<Vec<_>>();
+
+        let mut result: Vec<StyleProperty> = vec![];
+
+        for style in self.ordered_styles() {
+            if style.selector.matches(control_type, &classes) {
+                for property in style.properties.iter() {
+                    result.retain(|existing| !existing.is_same_kind(property));
+                    result.push(property.clone());
+                }
+            }
+        }
+
+        result.retain(|property| !property.is_overridden_by(control));
+        result
+    }
+
+    ///
+    /// The standard light theme
+    ///
+    pub fn light() -> Theme {
+        use self::StyleProperty::*;
+        use self::StyleSelector::ControlType as Type;
+
+        let text        = Color::Rgba(0.1, 0.1, 0.12, 1.0);
+        let window      = Color::Rgba(0.93, 0.93, 0.94, 1.0);
+        let field       = Color::Rgba(1.0, 1.0, 1.0, 1.0);
+        let button      = Color::Rgba(0.86, 0.86, 0.88, 1.0);
+        let border      = Color::Rgba(0.7, 0.7, 0.72, 1.0);
+
+        Theme::new("light")
+            .with_style(StyleSelector::All, vec![Foreground(text), FontSize(13.0)])
+            .with_style(Type(ControlType::CroppingContainer), vec![Background(window)])
+            .with_style(Type(ControlType::Popup), vec![Background(window), Border(1.0, border), CornerRadius(4.0)])
+            .with_style(Type(ControlType::Window), vec![Background(window)])
+            .with_style(Type(ControlType::Button), vec![Background(button), Border(1.0, border), CornerRadius(3.0)])
+            .with_style(Type(ControlType::TextBox), vec![Background(field), Border(1.0, border), CornerRadius(2.0)])
+            .with_style(Type(ControlType::ComboBox), vec![Background(field), Border(1.0, border), CornerRadius(2.0)])
+            .with_style(Type(ControlType::ListView), vec![Background(field)])
+            .with_style(Type(ControlType::TreeView), vec![Background(field)])
+    }
+
+    ///
+    /// The standard dark theme, for working in dim surroundings
+    ///
+    pub fn dark() -> Theme {
+        use self::StyleProperty::*;
+        use self::StyleSelector::ControlType as Type;
+
+        let text        = Color::Rgba(0.86, 0.86, 0.88, 1.0);
+        let window      = Color::Rgba(0.15, 0.15, 0.17, 1.0);
+        let field       = Color::Rgba(0.1, 0.1, 0.11, 1.0);
+        let button      = Color::Rgba(0.24, 0.24, 0.27, 1.0);
+        let border      = Color::Rgba(0.32, 0.32, 0.35, 1.0);
+
+        Theme::new("dark")
+            .with_style(StyleSelector::All, vec![Foreground(text), FontSize(13.0)])
+            .with_style(Type(ControlType::CroppingContainer), vec![Background(window)])
+            .with_style(Type(ControlType::Popup), vec![Background(window), Border(1.0, border), CornerRadius(4.0)])
+            .with_style(Type(ControlType::Window), vec![Background(window)])
+            .with_style(Type(ControlType::Button), vec![Background(button), Border(1.0, border), CornerRadius(3.0)])
+            .with_style(Type(ControlType::TextBox), vec![Background(field), Border(1.0, border), CornerRadius(2.0)])
+            .with_style(Type(ControlType::ComboBox), vec![Background(field), Border(1.0, border), CornerRadius(2.0)])
+            .with_style(Type(ControlType::ListView), vec![Background(field)])
+            .with_style(Type(ControlType::TreeView), vec![Background(field)])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn class_styles_override_type_styles() {
+        let theme = Theme::new("test")
+            .with_style(StyleSelector::Class("tool".to_string()), vec![StyleProperty::CornerRadius(8.0)])
+            .with_style(StyleSelector::ControlType(ControlType::Button), vec![StyleProperty::CornerRadius(2.0), StyleProperty::FontSize(10.0)]);
+
+        let button      = Control::button().with(Hint::Class("tool".to_string()));
+        let properties  = theme.properties_for_control(&button);
+
+        assert!(properties == vec![StyleProperty::FontSize(10.0), StyleProperty::CornerRadius(8.0)]);
+    }
+
+    #[test]
+    fn control_attributes_override_theme() {
+        let theme       = Theme::dark();
+        let label       = Control::label().with(Appearance::Foreground(Color::Rgba(1.0, 0.0, 0.0, 1.0)));
+        let properties  = theme.properties_for_control(&label);
+
+        assert!(properties == vec![StyleProperty::FontSize(13.0)]);
+    }
+
+    #[test]
+    fn light_and_dark_themes_style_the_same_properties() {
+        let light = Theme::light();
+        let dark  = Theme::dark();
+
+        assert!(light.styles.len() == dark.styles.len());
+        for (light_style, dark_style) in light.styles.iter().zip(dark.styles.iter()) {
+            assert!(light_style.selector == dark_style.selector);
+            assert!(light_style.properties.iter().zip(dark_style.properties.iter()).all(|(light_prop, dark_prop)| light_prop.is_same_kind(dark_prop)));
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use super::control::*;
+
+///
+/// Interpolates a control between two layouts according to its transition attributes
+///
+/// Positions are in the same form as the ones returned by `ControlLayout::lay_out()`. A transition with no starting
+/// position describes a control that has just appeared: it starts at its final position and fades in if it has an
+/// opacity transition.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct LayoutTransition {
+    /// Where the control was laid out before the change (None if it has just appeared)
+    from: Option<((f32, f32), (f32, f32))>,
+
+    /// Where the control is laid out after the change
+    to: ((f32, f32), (f32, f32)),
+
+    /// The duration and easing for changes to the position of the control
+    position: Option<(u32, TransitionEasing)>,
+
+    /// The duration and easing for changes to the size of the control
+    size: Option<(u32, TransitionEasing)>,
+
+    /// The duration and easing for the control fading in
+    opacity: Option<(u32, TransitionEasing)>
+}
+
+impl LayoutTransition {
+    ///
+    /// Creates a transition for a control moving from one layout position to another
+    ///
+    pub fn new<'a, TransitionIter: IntoIterator<Item=&'a Transition>>(transitions: TransitionIter, from: Option<((f32, f32), (f32, f32))>, to: ((f32, f32), (f32, f32))) -> LayoutTransition {
+        let mut position    = None;
+        let mut size        = None;
+        let mut opacity     = None;
+
+        // The last attribute of each kind is the one that's used
+        for transition in transitions {
+            match transition {
+                Transition::Position(duration, easing)  => position = Some((*duration, *easing)),
+                Transition::Size(duration, easing)      => size = Some((*duration, *easing)),
+                Transition::Opacity(duration, easing)   => opacity = Some((*duration, *easing))
+            }
+        }
+
+        LayoutTransition { from, to, position, size, opacity }
+    }
+
+    ///
+    /// Creates a transition from the transition attributes of a control
+    ///
+    pub fn from_control(control: &Control, from: Option<((f32, f32), (f32, f32))>, to: ((f32, f32), (f32, f32))) -> LayoutTransition {
+        Self::new(control.attributes().filter_map(|attr| attr.transition()), from, to)
+    }
+
+    ///
+    /// The layout position that this transition finishes at
+    ///
+    pub fn target(&self) -> ((f32, f32), (f32, f32)) {
+        self.to
+    }
+
+    ///
+    /// True if this transition changes the control at all (false if it should just snap to its final position)
+    ///
+    pub fn is_animated(&self) -> bool {
+        self.duration() > 0
+    }
+
+    ///
+    /// The time in milliseconds that this transition takes to complete
+    ///
+    pub fn duration(&self) -> u32 {
+        let changed = |transition: Option<(u32, TransitionEasing)>, has_changed: bool| {
+            if has_changed { transition.map(|(duration, _)| duration).unwrap_or(0) } else { 0 }
+        };
+
+        match self.from {
+            None        => changed(self.opacity, true),
+            Some(from)  => {
+                let ((from_x1, from_y1), (from_x2, from_y2))    = from;
+                let ((to_x1, to_y1), (to_x2, to_y2))            = self.to;
+
+                let moved   = from_x1 != to_x1 || from_y1 != to_y1;
+                let resized = (from_x2-from_x1) != (to_x2-to_x1) || (from_y2-from_y1) != (to_y2-to_y1);
+
+                changed(self.position, moved).max(changed(self.size, resized))
+            }
+        }
+    }
+
+    ///
+    /// True if this transition has finished after the specified number of milliseconds
+    ///
+    pub fn is_finished(&self, elapsed_ms: f64) -> bool {
+        elapsed_ms >= self.duration() as f64
+    }
+
+    ///
+    /// Returns the proportion of the way through a particular transition after the specified number of milliseconds
+    ///
+    fn progress(transition: Option<(u32, TransitionEasing)>, elapsed_ms: f64) -> f32 {
+        match transition {
+            None                    => 1.0,
+            Some((0, _))            => 1.0,
+            Some((duration, easing))=> easing.ease(elapsed_ms / (duration as f64)) as f32
+        }
+    }
+
+    ///
+    /// Retrieves the position of the control after the specified number of milliseconds
+    ///
+    pub fn bounds_at(&self, elapsed_ms: f64) -> ((f32, f32), (f32, f32)) {
+        match self.from {
+            None        => self.to,
+            Some(from)  => {
+                let ((from_x1, from_y1), (from_x2, from_y2))    = from;
+                let ((to_x1, to_y1), (to_x2, to_y2))            = self.to;
+
+                let position_t  = Self::progress(self.position, elapsed_ms);
+                let size_t      = Self::progress(self.size, elapsed_ms);
+
+                // Position and size are interpolated separately
+                let x1          = from_x1 + (to_x1-from_x1)*position_t;
+                let y1          = from_y1 + (to_y1-from_y1)*position_t;
+                let width       = (from_x2-from_x1) + ((to_x2-to_x1)-(from_x2-from_x1))*size_t;
+                let height      = (from_y2-from_y1) + ((to_y2-to_y1)-(from_y2-from_y1))*size_t;
+
+                ((x1, y1), (x1+width, y1+height))
+            }
+        }
+    }
+
+    ///
+    /// Retrieves the opacity of the control (0.0-1.0) after the specified number of milliseconds
+    ///
+    pub fn opacity_at(&self, elapsed_ms: f64) -> f64 {
+        match self.from {
+            None    => Self::progress(self.opacity, elapsed_ms) as f64,
+            Some(_) => 1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn easing_starts_and_ends_at_the_endpoints() {
+        for easing in vec![TransitionEasing::Linear, TransitionEasing::EaseIn, TransitionEasing::EaseOut, TransitionEasing::EaseInOut] {
+            assert!(easing.ease(0.0) == 0.0);
+            assert!((easing.ease(1.0)-1.0).abs() < 0.0001);
+            assert!(easing.ease(2.0) == easing.ease(1.0));
+        }
+
+        assert!(TransitionEasing::EaseIn.ease(0.5) < 0.5);
+        assert!(TransitionEasing::EaseOut.ease(0.5) > 0.5);
+        assert!((TransitionEasing::EaseInOut.ease(0.5)-0.5).abs() < 0.0001);
+    }
+
+    #[test]
+    fn position_moves_while_size_snaps() {
+        let transition = LayoutTransition::new(&[Transition::Position(100, TransitionEasing::Linear)], Some(((0.0, 0.0), (10.0, 10.0))), ((100.0, 50.0), (120.0, 70.0)));
+
+        assert!(transition.duration() == 100);
+        assert!(transition.bounds_at(0.0) == ((0.0, 0.0), (20.0, 20.0)));
+        assert!(transition.bounds_at(50.0) == ((50.0, 25.0), (70.0, 45.0)));
+        assert!(transition.bounds_at(100.0) == ((100.0, 50.0), (120.0, 70.0)));
+        assert!(transition.is_finished(100.0));
+        assert!(transition.opacity_at(0.0) == 1.0);
+    }
+
+    #[test]
+    fn size_grows_from_the_old_size() {
+        let transition = LayoutTransition::new(&[Transition::Size(200, TransitionEasing::Linear)], Some(((0.0, 0.0), (100.0, 20.0))), ((0.0, 0.0), (300.0, 20.0)));
+
+        assert!(transition.duration() == 200);
+        assert!(transition.bounds_at(100.0) == ((0.0, 0.0), (200.0, 20.0)));
+    }
+
+    #[test]
+    fn new_controls_fade_in() {
+        let transition = LayoutTransition::new(&[Transition::Position(100, TransitionEasing::Linear), Transition::Opacity(200, TransitionEasing::Linear)], None, ((10.0, 10.0), (20.0, 20.0)));
+
+        assert!(transition.duration() == 200);
+        assert!(transition.bounds_at(0.0) == ((10.0, 10.0), (20.0, 20.0)));
+        assert!(transition.opacity_at(0.0) == 0.0);
+        assert!(transition.opacity_at(100.0) == 0.5);
+        assert!(transition.opacity_at(200.0) == 1.0);
+    }
+
+    #[test]
+    fn unchanged_controls_are_not_animated() {
+        let control     = Control::label().with(Transition::Position(100, TransitionEasing::EaseOut));
+        let transition  = LayoutTransition::from_control(&control, Some(((0.0, 0.0), (10.0, 10.0))), ((0.0, 0.0), (10.0, 10.0)));
+
+        assert!(!transition.is_animated());
+    }
+}
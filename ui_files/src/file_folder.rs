@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+
+///
+/// Identifies a folder that files can be organised into
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FolderId(pub i64);
+
+///
+/// An item that can be found in a folder
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum FolderEntry {
+    /// A file with the specified path
+    File(PathBuf),
+
+    /// A folder
+    Folder(FolderId)
+}
@@ -1,5 +1,7 @@
+use super::file_folder::*;
 use super::file_update::*;
 
+use flo_canvas::*;
 use futures::stream::{BoxStream};
 
 use std::path::{Path, PathBuf};
@@ -30,7 +32,9 @@ pub trait FileManager : Send+Sync {
     fn delete_path(&self, path: &Path);
 
     ///
-    /// Re-orders the files so that `path` is displayed after `after` (or at the beginning if `after` is `None`)
+    /// Re-orders the files so that `path` is displayed after `after` (or at the beginning of its folder if `after` is `None`)
+    ///
+    /// If `after` is in a different folder, `path` is moved into that folder.
     ///
     fn order_path_after(&self, path: &Path, after: Option<&Path>);
 
@@ -41,6 +45,69 @@ pub trait FileManager : Send+Sync {
     ///
     fn set_display_name_for_path(&self, path: &Path, display_name: String);
 
+    ///
+    /// Returns the tags that have been assigned to a particular path
+    ///
+    fn tags_for_path(&self, path: &Path) -> Vec<String>;
+
+    ///
+    /// Replaces the tags assigned to a particular path
+    ///
+    fn set_tags_for_path(&self, path: &Path, tags: Vec<String>);
+
+    ///
+    /// Returns the paths whose display name or tags contain the specified text (ignoring case), in the same order
+    /// as they're returned by `get_all_files()`
+    ///
+    fn search(&self, text: &str) -> Vec<PathBuf>;
+
+    ///
+    /// Creates a new folder inside a parent folder (or at the top level if the parent is `None`)
+    ///
+    fn create_folder(&self, parent: Option<FolderId>, display_name: String) -> FolderId;
+
+    ///
+    /// Returns the display name for a folder
+    ///
+    fn folder_name(&self, folder: FolderId) -> Option<String>;
+
+    ///
+    /// Changes the display name for a folder
+    ///
+    fn set_folder_name(&self, folder: FolderId, display_name: String);
+
+    ///
+    /// Returns the folder that contains a folder (or `None` if it's at the top level)
+    ///
+    fn parent_folder(&self, folder: FolderId) -> Option<FolderId>;
+
+    ///
+    /// Returns the folder that contains a path (or `None` if it's at the top level)
+    ///
+    fn folder_for_path(&self, path: &Path) -> Option<FolderId>;
+
+    ///
+    /// Moves a path to the start of a folder (or to the top level if the folder is `None`)
+    ///
+    fn move_path_to_folder(&self, path: &Path, folder: Option<FolderId>);
+
+    ///
+    /// Returns the files and folders in a folder (or at the top level if the folder is `None`), in display order
+    ///
+    /// `get_all_files()` returns the files in every folder, while this only returns the items directly in the folder.
+    ///
+    fn folder_contents(&self, folder: Option<FolderId>) -> Vec<FolderEntry>;
+
+    ///
+    /// Returns the thumbnail stored for a path, or `None` if there's no thumbnail or the file has changed since it was stored
+    ///
+    fn cached_thumbnail(&self, path: &Path) -> Option<Vec<Draw>>;
+
+    ///
+    /// Stores a thumbnail for a path, which will be returned by `cached_thumbnail` until the file is changed
+    ///
+    fn set_cached_thumbnail(&self, path: &Path, thumbnail: Vec<Draw>);
+
     ///
     /// Returns a stream of updates indicating changes made to the file manager
     ///
@@ -1,6 +1,29 @@
+use flo_canvas::*;
+
 use std::path::Path;
 use std::sync::*;
 
+///
+/// The reasons that a thumbnail could not be rendered for a file
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum ThumbnailError {
+    /// This type of file can't be previewed
+    NotSupported,
+
+    /// The file could not be read (with a description of why)
+    CannotReadFile(String)
+}
+
+///
+/// The reasons that the lost changes to a file could not be recovered
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum RecoveryError {
+    /// The file could not be opened (with a description of why)
+    CannotOpenFile(String)
+}
+
 ///
 /// Trait implemented by model objects that represent open files
 ///
@@ -21,4 +44,26 @@ pub trait FileModel : Send+Sync {
     /// Creates a new instance model from the shared model. This is used for a single session.
     ///
     fn new_instance(&self) -> Self::InstanceModel;
+
+    ///
+    /// Returns true if the file at the specified path was not closed cleanly and has changes that can be recovered
+    ///
+    fn needs_recovery(_loader: &Arc<Self::Loader>, _path: &Path) -> bool { false }
+
+    ///
+    /// Recovers the changes that were lost when the file at the specified path was not closed cleanly
+    ///
+    fn recover(_loader: &Arc<Self::Loader>, _path: &Path) -> Result<(), RecoveryError> { Ok(()) }
+
+    ///
+    /// Discards the changes that could have been recovered for the file at the specified path
+    ///
+    fn discard_recovery(_loader: &Arc<Self::Loader>, _path: &Path) { }
+
+    ///
+    /// Renders a small preview of the file at the specified path
+    ///
+    /// The file should be read without changing it, as it might be open for editing elsewhere
+    ///
+    fn render_thumbnail(_loader: &Arc<Self::Loader>, _path: &Path) -> Result<Vec<Draw>, ThumbnailError> { Err(ThumbnailError::NotSupported) }
 }
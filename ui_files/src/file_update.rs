@@ -1,3 +1,5 @@
+use super::file_folder::*;
+
 use std::path::PathBuf;
 
 ///
@@ -11,9 +13,21 @@ pub enum FileUpdate {
     /// Indicates that the display name for the specified file has changed
     SetDisplayName(PathBuf, String),
 
+    /// The tags for the specified file have changed
+    SetTags(PathBuf, Vec<String>),
+
     /// The file with the specified path has been removed
     RemovedFile(PathBuf),
 
     /// The file with the specified path has been moved so that it's after the specified file, or at the beginning if that file does not exist
-    ChangedOrder(PathBuf, Option<PathBuf>)
+    ChangedOrder(PathBuf, Option<PathBuf>),
+
+    /// A new folder has been created inside the specified parent folder (None for the top level), with the specified name
+    NewFolder(FolderId, Option<FolderId>, String),
+
+    /// The display name for the specified folder has changed
+    SetFolderName(FolderId, String),
+
+    /// The file with the specified path has been moved to the start of the specified folder (None for the top level)
+    MovedToFolder(PathBuf, Option<FolderId>)
 }
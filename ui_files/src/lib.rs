@@ -11,16 +11,39 @@ extern crate uuid;
 extern crate desync;
 extern crate futures;
 extern crate rusqlite;
+extern crate serde;
+extern crate serde_json;
 #[macro_use] extern crate lazy_static;
 
 mod file_model;
+mod file_folder;
 mod open_file_store;
 mod file_manager;
 mod file_update;
 pub mod ui;
 pub mod sqlite;
+pub mod preferences;
 
 pub use self::file_model::*;
+pub use self::file_folder::*;
 pub use self::open_file_store::*;
 pub use self::file_manager::*;
 pub use self::file_update::*;
+
+///
+/// The translations of the messages displayed by the file chooser
+///
+pub static MESSAGES: flo_ui::EmbeddedCatalogs = flo_ui::EmbeddedCatalogs::new(&[
+    ("ja", include_str!("../locale/ja.po")),
+    ("es", include_str!("../locale/es.po"))
+]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn message_catalogs_are_valid() {
+        assert!(MESSAGES.check().is_ok());
+    }
+}
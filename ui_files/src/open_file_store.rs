@@ -1,5 +1,7 @@
 use super::file_model::*;
 
+use flo_canvas::*;
+
 use ::desync::*;
 
 use std::sync::*;
@@ -53,6 +55,44 @@ impl<Model: 'static+FileModel> OpenFileStore<Model> {
         shared
     }
 
+    ///
+    /// Returns true if the file at the specified path needs to be recovered before it can be opened
+    ///
+    /// Files that are already open in this store never need recovery.
+    ///
+    pub fn needs_recovery(&self, path: &Path) -> bool {
+        let (loader, is_open) = self.core.sync(|core| (Arc::clone(&core.loader), core.open_files.contains_key(path)));
+
+        !is_open && Model::needs_recovery(&loader, path)
+    }
+
+    ///
+    /// Recovers any changes that were lost when the file at the specified path was last closed
+    ///
+    pub fn recover(&self, path: &Path) -> Result<(), RecoveryError> {
+        let loader = self.core.sync(|core| Arc::clone(&core.loader));
+
+        Model::recover(&loader, path)
+    }
+
+    ///
+    /// Discards any changes that could have been recovered for the file at the specified path
+    ///
+    pub fn discard_recovery(&self, path: &Path) {
+        let loader = self.core.sync(|core| Arc::clone(&core.loader));
+
+        Model::discard_recovery(&loader, path);
+    }
+
+    ///
+    /// Renders a preview of the file at the specified path
+    ///
+    pub fn render_thumbnail(&self, path: &Path) -> Result<Vec<Draw>, ThumbnailError> {
+        let loader = self.core.sync(|core| Arc::clone(&core.loader));
+
+        Model::render_thumbnail(&loader, path)
+    }
+
     ///
     /// Removes the shared data for a file if there are no remaining references
     ///
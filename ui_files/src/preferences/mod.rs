@@ -0,0 +1,18 @@
+//!
+//! # Preferences
+//!
+//! Preferences are values that should be remembered between sessions, such as the settings for the tools. Each preference
+//! is identified by a typed `PreferenceKey`, and its value is serialized as JSON in a `PreferenceStore`.
+//!
+//! The `Preferences` object can bind a key to a `Binding`, which loads the stored value into the binding and then stores
+//! the new value whenever it changes. It can also be given a second store containing overrides: this is used so that an
+//! animation can record its own values for some preferences, which take precedence over the user's values while it's open.
+//!
+
+mod preference_key;
+mod preference_store;
+mod preferences;
+
+pub use self::preference_key::*;
+pub use self::preference_store::*;
+pub use self::preferences::*;
@@ -0,0 +1,36 @@
+use std::marker::PhantomData;
+
+///
+/// Identifies a preference with a value of type `T`
+///
+pub struct PreferenceKey<T> {
+    /// The name that the preference is stored under
+    name: &'static str,
+
+    value: PhantomData<T>
+}
+
+impl<T> PreferenceKey<T> {
+    ///
+    /// Creates a new preference key
+    ///
+    pub const fn new(name: &'static str) -> PreferenceKey<T> {
+        PreferenceKey {
+            name:   name,
+            value:  PhantomData
+        }
+    }
+
+    ///
+    /// The name that this preference is stored under
+    ///
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Clone for PreferenceKey<T> {
+    fn clone(&self) -> PreferenceKey<T> {
+        PreferenceKey::new(self.name)
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::*;
+use std::collections::HashMap;
+
+///
+/// A preference store stores the serialized values of some preferences
+///
+pub trait PreferenceStore : Send+Sync {
+    ///
+    /// Retrieves the serialized value of a preference, if it has been stored
+    ///
+    fn get(&self, key: &str) -> Option<String>;
+
+    ///
+    /// Stores the serialized value of a preference (or removes it if the value is `None`)
+    ///
+    fn set(&self, key: &str, value: Option<String>);
+}
+
+///
+/// A preference store that keeps its values in memory (and so forgets them when it's dropped)
+///
+pub struct InMemoryPreferenceStore {
+    /// The values in this store
+    values: Mutex<HashMap<String, String>>
+}
+
+impl InMemoryPreferenceStore {
+    ///
+    /// Creates a new, empty, preference store
+    ///
+    pub fn new() -> InMemoryPreferenceStore {
+        InMemoryPreferenceStore {
+            values: Mutex::new(HashMap::new())
+        }
+    }
+}
+
+impl PreferenceStore for InMemoryPreferenceStore {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.lock().unwrap().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: Option<String>) {
+        let mut values = self.values.lock().unwrap();
+
+        if let Some(value) = value {
+            values.insert(key.to_string(), value);
+        } else {
+            values.remove(key);
+        }
+    }
+}
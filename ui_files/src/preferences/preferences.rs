@@ -0,0 +1,246 @@
+use super::preference_key::*;
+use super::preference_store::*;
+
+use flo_binding::*;
+
+use serde::{Serialize};
+use serde::de::{DeserializeOwned};
+use serde_json;
+
+use std::sync::*;
+
+///
+/// The stores that a set of preferences are read from and written to
+///
+struct PreferenceLayers {
+    /// The store containing the user's preferences
+    user: Arc<dyn PreferenceStore>,
+
+    /// The store containing the preferences that override the user's preferences (eg, the ones stored with an animation)
+    overrides: Option<Arc<dyn PreferenceStore>>
+}
+
+impl PreferenceLayers {
+    ///
+    /// Reads the serialized value of a preference, preferring the overridden value if there is one
+    ///
+    fn get(&self, key: &str) -> Option<String> {
+        self.overrides.as_ref()
+            .and_then(|overrides| overrides.get(key))
+            .or_else(|| self.user.get(key))
+    }
+
+    ///
+    /// Writes the serialized value of a preference to the store it's currently read from
+    ///
+    fn set(&self, key: &str, value: String) {
+        match self.overrides.as_ref() {
+            Some(overrides) if overrides.get(key).is_some() => overrides.set(key, Some(value)),
+            _                                               => self.user.set(key, Some(value))
+        }
+    }
+}
+
+///
+/// Reads and writes typed preferences, and keeps bindings in sync with them
+///
+pub struct Preferences {
+    /// Where the preferences are stored
+    layers: Arc<PreferenceLayers>,
+
+    /// The lifetimes of the bindings that are saved whenever they change
+    bindings: Mutex<Vec<Box<dyn Releasable>>>
+}
+
+impl Preferences {
+    ///
+    /// Creates a new set of preferences that are read from and written to a store
+    ///
+    pub fn new(user: Arc<dyn PreferenceStore>) -> Preferences {
+        Preferences {
+            layers:     Arc::new(PreferenceLayers { user: user, overrides: None }),
+            bindings:   Mutex::new(vec![])
+        }
+    }
+
+    ///
+    /// Creates a new set of preferences, where the values found in the overrides store take precedence
+    /// over the values in the user store
+    ///
+    pub fn with_overrides(user: Arc<dyn PreferenceStore>, overrides: Arc<dyn PreferenceStore>) -> Preferences {
+        Preferences {
+            layers:     Arc::new(PreferenceLayers { user: user, overrides: Some(overrides) }),
+            bindings:   Mutex::new(vec![])
+        }
+    }
+
+    ///
+    /// Retrieves the value of a preference (or `None` if it has never been set)
+    ///
+    pub fn get<T: DeserializeOwned>(&self, key: &PreferenceKey<T>) -> Option<T> {
+        self.layers.get(key.name())
+            .and_then(|value| serde_json::from_str(&value).ok())
+    }
+
+    ///
+    /// Sets the value of a preference
+    ///
+    /// If the preference is overridden, the override is updated, otherwise the user's preference is updated
+    ///
+    pub fn set<T: Serialize>(&self, key: &PreferenceKey<T>, value: &T) {
+        if let Ok(value) = serde_json::to_string(value) {
+            self.layers.set(key.name(), value);
+        }
+    }
+
+    ///
+    /// Returns true if the specified preference is overridden
+    ///
+    pub fn is_overridden<T>(&self, key: &PreferenceKey<T>) -> bool {
+        self.layers.overrides.as_ref()
+            .map(|overrides| overrides.get(key.name()).is_some())
+            .unwrap_or(false)
+    }
+
+    ///
+    /// Overrides the user's value for a preference. Future changes to the preference will update the override and
+    /// leave the user's value alone.
+    ///
+    /// This has no effect if there is no overrides store.
+    ///
+    pub fn set_override<T: Serialize>(&self, key: &PreferenceKey<T>, value: &T) {
+        if let (Some(overrides), Ok(value)) = (self.layers.overrides.as_ref(), serde_json::to_string(value)) {
+            overrides.set(key.name(), Some(value));
+        }
+    }
+
+    ///
+    /// Removes the override for a preference, so future changes will update the user's value again
+    ///
+    pub fn remove_override<T>(&self, key: &PreferenceKey<T>) {
+        if let Some(overrides) = self.layers.overrides.as_ref() {
+            overrides.set(key.name(), None);
+        }
+    }
+
+    ///
+    /// Binds a preference to a binding. The binding is set to the stored value of the preference (if there is one), and
+    /// the preference is updated whenever the binding changes, for as long as this object exists.
+    ///
+    pub fn bind<T>(&self, key: &PreferenceKey<T>, binding: &Binding<T>)
+    where T: 'static+Clone+PartialEq+Send+Serialize+DeserializeOwned {
+        // Load the current value of the preference
+        if let Some(value) = self.get(key) {
+            binding.set(value);
+        }
+
+        // Store the value whenever the binding changes
+        let layers      = Arc::clone(&self.layers);
+        let name        = key.name();
+        let value       = binding.clone();
+        let lifetime    = binding.when_changed(notify(move || {
+            if let Ok(value) = serde_json::to_string(&value.get()) {
+                layers.set(name, value);
+            }
+        }));
+
+        self.bindings.lock().unwrap().push(lifetime);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SIZE: PreferenceKey<f32>          = PreferenceKey::new("test.size");
+    const NAME: PreferenceKey<String>       = PreferenceKey::new("test.name");
+
+    #[test]
+    fn read_unset_preference() {
+        let preferences = Preferences::new(Arc::new(InMemoryPreferenceStore::new()));
+
+        assert!(preferences.get(&SIZE) == None);
+    }
+
+    #[test]
+    fn set_and_get_preference() {
+        let store       = Arc::new(InMemoryPreferenceStore::new());
+        let preferences = Preferences::new(store.clone());
+
+        preferences.set(&SIZE, &12.0);
+        preferences.set(&NAME, &"Ink".to_string());
+
+        assert!(preferences.get(&SIZE) == Some(12.0));
+        assert!(preferences.get(&NAME) == Some("Ink".to_string()));
+        assert!(store.get("test.size") == Some("12.0".to_string()));
+    }
+
+    #[test]
+    fn binding_loads_stored_value() {
+        let store       = Arc::new(InMemoryPreferenceStore::new());
+        store.set("test.size", Some("20.0".to_string()));
+
+        let preferences = Preferences::new(store);
+        let size        = bind(5.0);
+
+        preferences.bind(&SIZE, &size);
+
+        assert!(size.get() == 20.0);
+    }
+
+    #[test]
+    fn binding_saves_new_values() {
+        let store       = Arc::new(InMemoryPreferenceStore::new());
+        let preferences = Preferences::new(store.clone());
+        let size        = bind(5.0);
+
+        preferences.bind(&SIZE, &size);
+        assert!(store.get("test.size") == None);
+
+        size.set(7.0);
+
+        assert!(preferences.get(&SIZE) == Some(7.0));
+
+        // Values should persist in a new set of preferences
+        let new_preferences = Preferences::new(store);
+        assert!(new_preferences.get(&SIZE) == Some(7.0));
+    }
+
+    #[test]
+    fn overrides_take_precedence() {
+        let user        = Arc::new(InMemoryPreferenceStore::new());
+        let overrides   = Arc::new(InMemoryPreferenceStore::new());
+        let preferences = Preferences::with_overrides(user.clone(), overrides.clone());
+
+        preferences.set(&SIZE, &10.0);
+        preferences.set_override(&SIZE, &3.0);
+
+        assert!(preferences.is_overridden(&SIZE));
+        assert!(preferences.get(&SIZE) == Some(3.0));
+        assert!(Preferences::new(user.clone()).get(&SIZE) == Some(10.0));
+    }
+
+    #[test]
+    fn overridden_bindings_update_the_override() {
+        let user        = Arc::new(InMemoryPreferenceStore::new());
+        let overrides   = Arc::new(InMemoryPreferenceStore::new());
+        let preferences = Preferences::with_overrides(user.clone(), overrides.clone());
+        let size        = bind(5.0);
+
+        preferences.set(&SIZE, &10.0);
+        preferences.set_override(&SIZE, &3.0);
+        preferences.bind(&SIZE, &size);
+
+        assert!(size.get() == 3.0);
+
+        size.set(4.0);
+        assert!(overrides.get("test.size") == Some("4.0".to_string()));
+        assert!(user.get("test.size") == Some("10.0".to_string()));
+
+        // Once the override is removed, changes go back to the user's preferences
+        preferences.remove_override(&SIZE);
+        size.set(6.0);
+        assert!(overrides.get("test.size") == None);
+        assert!(user.get("test.size") == Some("6.0".to_string()));
+    }
+}
@@ -1,4 +1,5 @@
 use super::file_error::*;
+use super::super::file_folder::*;
 
 use flo_logging::*;
 
@@ -8,10 +9,10 @@ use std::result;
 use std::path::{Path, PathBuf};
 
 /// The definition file for the latest version of the database
-const DEFINITION: &[u8]         = include_bytes!["../../sql/file_list_v2.sqlite"];
+const DEFINITION: &[u8]         = include_bytes!["../../sql/file_list_v5.sqlite"];
 
 /// The maximum supported version number
-const MAX_VERSION: i64      = 2;
+const MAX_VERSION: i64      = 5;
 
 /// The ID of the root entity (where the standard file directory is located)
 const ROOT_ENTITY: i64      = -1;
@@ -81,6 +82,9 @@ impl FileList {
         match connection_version {
             None                => { self.initialize()?; },
             Some(1)             => { Self::upgrade_v1_to_v2(&self.log, &mut self.connection)?; self.upgrade_to_latest()?; }
+            Some(2)             => { Self::upgrade_v2_to_v3(&self.log, &mut self.connection)?; self.upgrade_to_latest()?; }
+            Some(3)             => { Self::upgrade_v3_to_v4(&self.log, &mut self.connection)?; self.upgrade_to_latest()?; }
+            Some(4)             => { Self::upgrade_v4_to_v5(&self.log, &mut self.connection)?; self.upgrade_to_latest()?; }
             Some(MAX_VERSION)   => { }
 
             _                   => { return result::Result::Err(FileListError::CannotUpgradeVersion); }
@@ -201,6 +205,8 @@ impl FileList {
 
         // Remove from the file list
         let path_string = Self::string_for_path(path);
+        transaction.execute("DELETE FROM Flo_File_Tags WHERE RelativePath = ?", &[&path_string])?;
+        transaction.execute("DELETE FROM Flo_Thumbnails WHERE RelativePath = ?", &[&path_string])?;
         transaction.execute("DELETE FROM Flo_Files WHERE RelativePath = ?", &[&path_string]).unwrap();
 
         // Remove from the entity list
@@ -214,34 +220,66 @@ impl FileList {
     ///
     /// Lists the paths in the database
     ///
+    /// The files in each folder are listed at the position of the folder
+    ///
     pub fn list_paths(&self) -> result::Result<Vec<PathBuf>, FileListError> {
-        let mut select_paths    = self.connection.prepare("
-            WITH RECURSIVE RootEntities AS (
+        let mut paths = vec![];
+        self.append_paths_in_folder(None, &mut paths)?;
+
+        Ok(paths)
+    }
+
+    ///
+    /// Appends the paths in a folder and its subfolders to a list
+    ///
+    fn append_paths_in_folder(&self, folder: Option<FolderId>, paths: &mut Vec<PathBuf>) -> result::Result<(), FileListError> {
+        for entry in self.list_folder(folder)? {
+            match entry {
+                FolderEntry::File(path)         => { paths.push(path); }
+                FolderEntry::Folder(subfolder)  => { self.append_paths_in_folder(Some(subfolder), paths)?; }
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Lists the files and folders directly inside a folder (or at the top level if the folder is None)
+    ///
+    pub fn list_folder(&self, folder: Option<FolderId>) -> result::Result<Vec<FolderEntry>, FileListError> {
+        let parent_entity_id    = folder.map(|FolderId(entity_id)| entity_id).unwrap_or(ROOT_ENTITY);
+        let mut select_entries  = self.connection.prepare("
+            WITH RECURSIVE FolderEntities AS (
                 SELECT 0 AS idx, EntityId, NextEntity
                     FROM    Flo_Entity_Ordering
                     WHERE   ParentEntityId = ? AND EntityId NOT IN (SELECT NextEntity FROM Flo_Entity_Ordering)
                 UNION
-                SELECT RootEntities.idx+1, Flo_Entity_Ordering.EntityId, Flo_Entity_Ordering.NextEntity
-                    FROM    Flo_Entity_Ordering, RootEntities
-                    WHERE   Flo_Entity_Ordering.EntityId = RootEntities.NextEntity
+                SELECT FolderEntities.idx+1, Flo_Entity_Ordering.EntityId, Flo_Entity_Ordering.NextEntity
+                    FROM    Flo_Entity_Ordering, FolderEntities
+                    WHERE   Flo_Entity_Ordering.EntityId = FolderEntities.NextEntity
                     AND     Flo_Entity_Ordering.EntityId != -1
             )
-            SELECT RelativePath FROM Flo_Files
-            INNER JOIN RootEntities ON Flo_Files.EntityId = RootEntities.EntityId
-            ORDER BY RootEntities.idx
+            SELECT FolderEntities.EntityId, Flo_Files.RelativePath, Flo_Folders.EntityId FROM FolderEntities
+            LEFT OUTER JOIN Flo_Files ON Flo_Files.EntityId = FolderEntities.EntityId
+            LEFT OUTER JOIN Flo_Folders ON Flo_Folders.EntityId = FolderEntities.EntityId
+            ORDER BY FolderEntities.idx
         ")?;
-        let paths               = select_paths
-            .query_map(&[&ROOT_ENTITY], |row| {
-                let path_string = row.get::<_, String>(0)?;
-                let mut path    = PathBuf::new();
-                path.push(path_string);
-
-                Ok(path)
+        let entries             = select_entries
+            .query_map(&[&parent_entity_id], |row| {
+                let path_string = row.get::<_, Option<String>>(1)?;
+                let folder_id   = row.get::<_, Option<i64>>(2)?;
+
+                Ok(match (path_string, folder_id) {
+                    (Some(path_string), _)  => Some(FolderEntry::File(PathBuf::from(path_string))),
+                    (None, Some(folder_id)) => Some(FolderEntry::Folder(FolderId(folder_id))),
+                    (None, None)            => None
+                })
             })?
             .filter_map(|row| row.ok())
+            .flatten()
             .collect();
 
-        Ok(paths)
+        Ok(entries)
     }
 
     ///
@@ -252,6 +290,33 @@ impl FileList {
         Ok(entity_id)
     }
 
+    ///
+    /// Retrieves the entity for a folder (ROOT_ENTITY for the top level), checking that the folder exists
+    ///
+    fn entity_id_for_folder(transaction: &Transaction, folder: Option<FolderId>) -> result::Result<i64, FileListError> {
+        match folder {
+            None                    => Ok(ROOT_ENTITY),
+            Some(FolderId(folder))  => Ok(transaction.query_row("SELECT EntityId FROM Flo_Folders WHERE EntityId = ?", &[&folder], |row| row.get(0))?)
+        }
+    }
+
+    ///
+    /// Retrieve the parent entity ID for a given entity
+    ///
+    fn get_parent_entity_id(transaction: &Transaction, entity_id: i64) -> result::Result<i64, FileListError> {
+        let parent_entity_id = transaction.query_row("SELECT ParentEntityId FROM Flo_Entity_Ordering WHERE EntityId = ?", &[&entity_id], |row| row.get(0))?;
+
+        Ok(parent_entity_id)
+    }
+
+    ///
+    /// Sets the parent entity value for a particular entity
+    ///
+    fn set_parent_entity(transaction: &Transaction, entity_id: i64, parent_entity_id: i64) -> result::Result<(), FileListError> {
+        transaction.execute("UPDATE Flo_Entity_Ordering SET ParentEntityId = ? WHERE EntityId = ?", &[&parent_entity_id, &entity_id])?;
+        Ok(())
+    }
+
     ///
     /// Retrieve the following entity ID for a given entity
     ///
@@ -321,8 +386,9 @@ impl FileList {
 
         // Move to the beginning if 'after' is the root entity
         if after == ROOT_ENTITY {
-            // Get the first entity in the list
-            let first_entity_id = Self::get_first_entity_id(transaction, ROOT_ENTITY)?;
+            // Get the first entity in the list containing this entity
+            let parent_entity_id    = Self::get_parent_entity_id(transaction, entity_id)?;
+            let first_entity_id     = Self::get_first_entity_id(transaction, parent_entity_id)?;
 
             if first_entity_id != entity_id {
                 // Remove the entity from the list
@@ -335,6 +401,10 @@ impl FileList {
             // Remove the entity from the list
             Self::unlink_entity(transaction, entity_id)?;
 
+            // The entity moves into the same folder as the entity it's being placed after
+            let parent_entity_id = Self::get_parent_entity_id(transaction, after)?;
+            Self::set_parent_entity(transaction, entity_id, parent_entity_id)?;
+
             // Make the next entity be the same as the one currently after
             let next_after = Self::get_next_entity_id(transaction, after)?;
             Self::set_next_entity(transaction, entity_id, next_after)?;
@@ -387,6 +457,170 @@ impl FileList {
 
         self.connection.query_row("SELECT DisplayName FROM Flo_Files WHERE RelativePath = ?", &[&path_string], |row| row.get(0)).ok().and_then(|name| name)
     }
+
+    ///
+    /// Creates a new folder at the start of a parent folder (or the top level if the parent is None)
+    ///
+    pub fn create_folder(&mut self, parent: Option<FolderId>, display_name: &str) -> result::Result<FolderId, FileListError> {
+        let transaction = self.connection.transaction()?;
+
+        // Create an entity for the folder inside its parent
+        let parent_id   = Self::entity_id_for_folder(&transaction, parent)?;
+        let entity_id   = Self::add_entity(&transaction)?;
+        Self::set_parent_entity(&transaction, entity_id, parent_id)?;
+
+        // Create the folder
+        transaction.execute::<&[&dyn ToSql]>("INSERT INTO Flo_Folders (EntityId, DisplayName) VALUES (?, ?)", &[&entity_id, &display_name])?;
+
+        // Folders are added at the start of their parent folder, the same as files
+        Self::make_first_entity(&transaction, entity_id, parent_id)?;
+
+        transaction.commit()?;
+
+        Ok(FolderId(entity_id))
+    }
+
+    ///
+    /// Updates the display name for a folder
+    ///
+    pub fn set_folder_name(&self, folder: FolderId, display_name: &str) -> result::Result<(), FileListError> {
+        let FolderId(entity_id) = folder;
+
+        self.connection.execute::<&[&dyn ToSql]>("UPDATE Flo_Folders SET DisplayName = ? WHERE EntityId = ?", &[&display_name, &entity_id])?;
+
+        Ok(())
+    }
+
+    ///
+    /// Retrieves the display name for a folder
+    ///
+    pub fn folder_name(&self, folder: FolderId) -> Option<String> {
+        let FolderId(entity_id) = folder;
+
+        self.connection.query_row("SELECT DisplayName FROM Flo_Folders WHERE EntityId = ?", &[&entity_id], |row| row.get(0)).ok()
+    }
+
+    ///
+    /// Retrieves the folder that contains a folder (None if it's at the top level)
+    ///
+    pub fn parent_folder(&self, folder: FolderId) -> result::Result<Option<FolderId>, FileListError> {
+        let FolderId(entity_id) = folder;
+        let parent_id           = self.connection.query_row("SELECT ParentEntityId FROM Flo_Entity_Ordering WHERE EntityId = ?", &[&entity_id], |row| row.get::<_, i64>(0))?;
+
+        Ok(if parent_id == ROOT_ENTITY { None } else { Some(FolderId(parent_id)) })
+    }
+
+    ///
+    /// Retrieves the folder that contains a path (None if it's at the top level)
+    ///
+    pub fn folder_for_path(&self, path: &Path) -> result::Result<Option<FolderId>, FileListError> {
+        let path_string = Self::string_for_path(path);
+        let parent_id   = self.connection.query_row("
+                SELECT Flo_Entity_Ordering.ParentEntityId FROM Flo_Files
+                INNER JOIN Flo_Entity_Ordering ON Flo_Entity_Ordering.EntityId = Flo_Files.EntityId
+                WHERE Flo_Files.RelativePath = ?
+            ", &[&path_string], |row| row.get::<_, i64>(0))?;
+
+        Ok(if parent_id == ROOT_ENTITY { None } else { Some(FolderId(parent_id)) })
+    }
+
+    ///
+    /// Moves a path to the start of a folder (or the top level if the folder is None)
+    ///
+    pub fn move_path_to_folder(&mut self, path: &Path, folder: Option<FolderId>) -> result::Result<(), FileListError> {
+        let transaction = self.connection.transaction()?;
+
+        let entity_id   = Self::entity_id_for_path(&transaction, path)?;
+        let parent_id   = Self::entity_id_for_folder(&transaction, folder)?;
+
+        // Remove from the current folder and add at the start of the new one
+        Self::unlink_entity(&transaction, entity_id)?;
+        Self::set_parent_entity(&transaction, entity_id, parent_id)?;
+        Self::make_first_entity(&transaction, entity_id, parent_id)?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Replaces the tags for a path
+    ///
+    /// Tags are trimmed and empty tags are ignored. Tags are case-sensitive, but duplicates are only stored once.
+    ///
+    pub fn set_tags_for_path(&mut self, path: &Path, tags: &[String]) -> result::Result<(), FileListError> {
+        let path_string = Self::string_for_path(path);
+        let transaction = self.connection.transaction()?;
+
+        transaction.execute("DELETE FROM Flo_File_Tags WHERE RelativePath = ?", &[&path_string])?;
+
+        {
+            let mut add_tag = transaction.prepare("INSERT OR IGNORE INTO Flo_File_Tags (RelativePath, Tag) VALUES (?, ?)")?;
+            for tag in tags.iter().map(|tag| tag.trim()).filter(|tag| tag.len() > 0) {
+                add_tag.execute::<&[&dyn ToSql]>(&[&path_string, &tag])?;
+            }
+        }
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Retrieves the tags for a path, in alphabetical order
+    ///
+    pub fn tags_for_path(&self, path: &Path) -> result::Result<Vec<String>, FileListError> {
+        let path_string = Self::string_for_path(path);
+        let mut select  = self.connection.prepare("SELECT Tag FROM Flo_File_Tags WHERE RelativePath = ? ORDER BY Tag")?;
+        let tags        = select.query_map(&[&path_string], |row| row.get(0))?
+            .filter_map(|tag| tag.ok())
+            .collect();
+
+        Ok(tags)
+    }
+
+    ///
+    /// Stores the thumbnail for a path, along with the modification time of the file it was generated from
+    ///
+    pub fn set_thumbnail_for_path(&self, path: &Path, modified_time: i64, drawing: &str) -> result::Result<(), FileListError> {
+        let path_string = Self::string_for_path(path);
+
+        self.connection.execute::<&[&dyn ToSql]>("INSERT OR REPLACE INTO Flo_Thumbnails (RelativePath, ModifiedTime, Drawing) VALUES (?, ?, ?)", &[&path_string, &modified_time, &drawing])?;
+
+        Ok(())
+    }
+
+    ///
+    /// Retrieves the thumbnail for a path and the modification time of the file it was generated from
+    ///
+    pub fn thumbnail_for_path(&self, path: &Path) -> Option<(i64, String)> {
+        let path_string = Self::string_for_path(path);
+
+        self.connection.query_row("SELECT ModifiedTime, Drawing FROM Flo_Thumbnails WHERE RelativePath = ?", &[&path_string], |row| Ok((row.get(0)?, row.get(1)?))).ok()
+    }
+
+    ///
+    /// Finds the paths whose display name or tags contain the specified text (ignoring case)
+    ///
+    /// The paths are returned in no particular order.
+    ///
+    pub fn search_paths(&self, text: &str) -> result::Result<Vec<PathBuf>, FileListError> {
+        // Escape the wildcard characters in the search text
+        let pattern     = text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let pattern     = format!("%{}%", pattern);
+
+        let mut select  = self.connection.prepare("
+            SELECT RelativePath FROM Flo_Files WHERE DisplayName LIKE ?1 ESCAPE '\\'
+            UNION
+            SELECT RelativePath FROM Flo_File_Tags WHERE Tag LIKE ?1 ESCAPE '\\'
+        ")?;
+        let paths       = select.query_map(&[&pattern], |row| row.get::<_, String>(0))?
+            .filter_map(|path| path.ok())
+            .map(|path| PathBuf::from(path))
+            .collect();
+
+        Ok(paths)
+    }
 }
 
 #[cfg(test)]
@@ -577,6 +811,225 @@ mod test {
         assert!(file_list.display_name_for_path(&PathBuf::from("test").as_path()) == Some("TestDisplayName".to_string()));
     }
 
+    #[test]
+    pub fn set_tags() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test").as_path()).unwrap();
+        file_list.set_tags_for_path(&PathBuf::from("test").as_path(), &vec!["walk cycle".to_string(), " Background ".to_string(), "".to_string()]).unwrap();
+
+        assert!(file_list.tags_for_path(&PathBuf::from("test").as_path()).unwrap() == vec!["Background".to_string(), "walk cycle".to_string()]);
+    }
+
+    #[test]
+    pub fn removing_path_removes_tags() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test").as_path()).unwrap();
+        file_list.set_tags_for_path(&PathBuf::from("test").as_path(), &vec!["shot".to_string()]).unwrap();
+        file_list.remove_path(&PathBuf::from("test").as_path()).unwrap();
+
+        assert!(file_list.tags_for_path(&PathBuf::from("test").as_path()).unwrap().len() == 0);
+    }
+
+    #[test]
+    pub fn search_by_name_and_tag() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test1").as_path()).unwrap();
+        file_list.add_path(&PathBuf::from("test2").as_path()).unwrap();
+        file_list.add_path(&PathBuf::from("test3").as_path()).unwrap();
+        file_list.set_display_name_for_path(&PathBuf::from("test1").as_path(), "Opening shot").unwrap();
+        file_list.set_tags_for_path(&PathBuf::from("test2").as_path(), &vec!["Shot 2".to_string()]).unwrap();
+        file_list.set_display_name_for_path(&PathBuf::from("test3").as_path(), "100% done").unwrap();
+
+        let mut found = file_list.search_paths("SHOT").unwrap();
+        found.sort();
+        assert!(found == vec![PathBuf::from("test1"), PathBuf::from("test2")]);
+
+        assert!(file_list.search_paths("0%").unwrap() == vec![PathBuf::from("test3")]);
+        assert!(file_list.search_paths("_").unwrap().len() == 0);
+    }
+
+    #[test]
+    pub fn set_thumbnail() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test").as_path()).unwrap();
+        assert!(file_list.thumbnail_for_path(&PathBuf::from("test").as_path()) == None);
+
+        file_list.set_thumbnail_for_path(&PathBuf::from("test").as_path(), 42, "drawing").unwrap();
+        assert!(file_list.thumbnail_for_path(&PathBuf::from("test").as_path()) == Some((42, "drawing".to_string())));
+
+        file_list.set_thumbnail_for_path(&PathBuf::from("test").as_path(), 43, "new drawing").unwrap();
+        assert!(file_list.thumbnail_for_path(&PathBuf::from("test").as_path()) == Some((43, "new drawing".to_string())));
+    }
+
+    #[test]
+    pub fn removing_path_removes_thumbnail() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test").as_path()).unwrap();
+        file_list.set_thumbnail_for_path(&PathBuf::from("test").as_path(), 42, "drawing").unwrap();
+        file_list.remove_path(&PathBuf::from("test").as_path()).unwrap();
+
+        assert!(file_list.thumbnail_for_path(&PathBuf::from("test").as_path()) == None);
+    }
+
+    #[test]
+    pub fn create_folder() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test1").as_path()).unwrap();
+        let folder          = file_list.create_folder(None, "Episode 1").unwrap();
+
+        assert!(file_list.folder_name(folder) == Some("Episode 1".to_string()));
+        assert!(file_list.parent_folder(folder).unwrap() == None);
+        assert!(file_list.list_folder(None).unwrap() == vec![FolderEntry::Folder(folder), FolderEntry::File(PathBuf::from("test1"))]);
+        assert!(file_list.list_folder(Some(folder)).unwrap().len() == 0);
+    }
+
+    #[test]
+    pub fn rename_folder() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        let folder          = file_list.create_folder(None, "Episode 1").unwrap();
+        file_list.set_folder_name(folder, "Pilot").unwrap();
+
+        assert!(file_list.folder_name(folder) == Some("Pilot".to_string()));
+    }
+
+    #[test]
+    pub fn create_subfolder() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        let folder          = file_list.create_folder(None, "Episode 1").unwrap();
+        let subfolder       = file_list.create_folder(Some(folder), "Scene 1").unwrap();
+
+        assert!(file_list.parent_folder(subfolder).unwrap() == Some(folder));
+        assert!(file_list.list_folder(None).unwrap() == vec![FolderEntry::Folder(folder)]);
+        assert!(file_list.list_folder(Some(folder)).unwrap() == vec![FolderEntry::Folder(subfolder)]);
+    }
+
+    #[test]
+    pub fn cannot_create_folder_in_missing_folder() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        assert!(file_list.create_folder(Some(FolderId(42)), "Episode 1").is_err());
+    }
+
+    #[test]
+    pub fn move_path_to_folder() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test1").as_path()).unwrap();
+        file_list.add_path(&PathBuf::from("test2").as_path()).unwrap();
+        file_list.add_path(&PathBuf::from("test3").as_path()).unwrap();
+        let folder          = file_list.create_folder(None, "Episode 1").unwrap();
+
+        file_list.move_path_to_folder(&PathBuf::from("test1").as_path(), Some(folder)).unwrap();
+        file_list.move_path_to_folder(&PathBuf::from("test3").as_path(), Some(folder)).unwrap();
+
+        assert!(file_list.folder_for_path(&PathBuf::from("test1").as_path()).unwrap() == Some(folder));
+        assert!(file_list.folder_for_path(&PathBuf::from("test2").as_path()).unwrap() == None);
+        assert!(file_list.list_folder(None).unwrap() == vec![FolderEntry::Folder(folder), FolderEntry::File(PathBuf::from("test2"))]);
+        assert!(file_list.list_folder(Some(folder)).unwrap() == vec![FolderEntry::File(PathBuf::from("test3")), FolderEntry::File(PathBuf::from("test1"))]);
+    }
+
+    #[test]
+    pub fn move_path_back_to_top_level() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test1").as_path()).unwrap();
+        file_list.add_path(&PathBuf::from("test2").as_path()).unwrap();
+        let folder          = file_list.create_folder(None, "Episode 1").unwrap();
+
+        file_list.move_path_to_folder(&PathBuf::from("test1").as_path(), Some(folder)).unwrap();
+        file_list.move_path_to_folder(&PathBuf::from("test1").as_path(), None).unwrap();
+
+        assert!(file_list.list_folder(None).unwrap() == vec![FolderEntry::File(PathBuf::from("test1")), FolderEntry::Folder(folder), FolderEntry::File(PathBuf::from("test2"))]);
+        assert!(file_list.list_folder(Some(folder)).unwrap().len() == 0);
+    }
+
+    #[test]
+    pub fn list_paths_includes_paths_in_folders() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test1").as_path()).unwrap();
+        file_list.add_path(&PathBuf::from("test2").as_path()).unwrap();
+        let folder          = file_list.create_folder(None, "Episode 1").unwrap();
+        file_list.add_path(&PathBuf::from("test3").as_path()).unwrap();
+
+        file_list.move_path_to_folder(&PathBuf::from("test1").as_path(), Some(folder)).unwrap();
+
+        let paths = file_list.list_paths().unwrap();
+        let paths = paths.into_iter().map(|path_buf| path_buf.to_str().unwrap().to_string()).collect::<Vec<_>>();
+
+        assert!(paths == vec![
+            "test3".to_string(),
+            "test1".to_string(),
+            "test2".to_string()
+        ]);
+    }
+
+    #[test]
+    pub fn ordering_after_path_in_folder_moves_to_folder() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test1").as_path()).unwrap();
+        file_list.add_path(&PathBuf::from("test2").as_path()).unwrap();
+        let folder          = file_list.create_folder(None, "Episode 1").unwrap();
+
+        file_list.move_path_to_folder(&PathBuf::from("test1").as_path(), Some(folder)).unwrap();
+        file_list.order_path_after(&PathBuf::from("test2").as_path(), Some(&PathBuf::from("test1").as_path())).unwrap();
+
+        assert!(file_list.folder_for_path(&PathBuf::from("test2").as_path()).unwrap() == Some(folder));
+        assert!(file_list.list_folder(None).unwrap() == vec![FolderEntry::Folder(folder)]);
+        assert!(file_list.list_folder(Some(folder)).unwrap() == vec![FolderEntry::File(PathBuf::from("test1")), FolderEntry::File(PathBuf::from("test2"))]);
+    }
+
+    #[test]
+    pub fn move_path_to_start_of_folder() {
+        let db              = Connection::open_in_memory().unwrap();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test1").as_path()).unwrap();
+        file_list.add_path(&PathBuf::from("test2").as_path()).unwrap();
+        let folder          = file_list.create_folder(None, "Episode 1").unwrap();
+
+        file_list.move_path_to_folder(&PathBuf::from("test1").as_path(), Some(folder)).unwrap();
+        file_list.move_path_to_folder(&PathBuf::from("test2").as_path(), Some(folder)).unwrap();
+        file_list.order_path_after(&PathBuf::from("test1").as_path(), None).unwrap();
+
+        assert!(file_list.list_folder(None).unwrap() == vec![FolderEntry::Folder(folder)]);
+        assert!(file_list.list_folder(Some(folder)).unwrap() == vec![FolderEntry::File(PathBuf::from("test1")), FolderEntry::File(PathBuf::from("test2"))]);
+    }
+
+    #[test]
+    fn upgrade_v1_path_can_be_moved_to_folder() {
+        let db              = v1_database();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test").as_path()).unwrap();
+        let folder          = file_list.create_folder(None, "Folder").unwrap();
+        file_list.move_path_to_folder(&PathBuf::from("test").as_path(), Some(folder)).unwrap();
+
+        assert!(file_list.list_folder(Some(folder)).unwrap() == vec![FolderEntry::File(PathBuf::from("test"))]);
+    }
+
     #[test]
     fn get_version_uninitialized() {
         let db          = Connection::open_in_memory().unwrap();
@@ -599,6 +1052,17 @@ mod test {
         assert!(FileList::version_number(&file_list.connection) == Some(MAX_VERSION));
     }
 
+    #[test]
+    fn upgrade_v1_path_can_be_tagged() {
+        let db              = v1_database();
+        let mut file_list   = FileList::new(db).unwrap();
+
+        file_list.add_path(&PathBuf::from("test").as_path()).unwrap();
+        file_list.set_tags_for_path(&PathBuf::from("test").as_path(), &vec!["tag".to_string()]).unwrap();
+
+        assert!(file_list.tags_for_path(&PathBuf::from("test").as_path()).unwrap() == vec!["tag".to_string()]);
+    }
+
     #[test]
     fn get_version_latest() {
         let db          = Connection::open_in_memory().unwrap();
@@ -11,6 +11,15 @@ use std::result;
 /// Performs the v1 to v2 upgrade steps
 const UPGRADE_V1_TO_V2: &[u8]   = include_bytes!["../../sql/file_list_v1_to_v2.sqlite"];
 
+/// Performs the v2 to v3 upgrade steps
+const UPGRADE_V2_TO_V3: &[u8]   = include_bytes!["../../sql/file_list_v2_to_v3.sqlite"];
+
+/// Performs the v3 to v4 upgrade steps
+const UPGRADE_V3_TO_V4: &[u8]   = include_bytes!["../../sql/file_list_v3_to_v4.sqlite"];
+
+/// Performs the v4 to v5 upgrade steps
+const UPGRADE_V4_TO_V5: &[u8]   = include_bytes!["../../sql/file_list_v4_to_v5.sqlite"];
+
 impl FileList {
     ///
     /// Upgrades from version 1 of the database to version 2
@@ -60,4 +69,46 @@ impl FileList {
         // Upgrade was successful
         Ok(())
     }
+
+    ///
+    /// Upgrades from version 2 of the database to version 3 (which adds tags)
+    ///
+    pub (crate) fn upgrade_v2_to_v3(log: &LogPublisher, connection: &mut Connection) -> result::Result<(), FileListError> {
+        log.log((Level::Info, "Upgrading file list from v2 to v3"));
+
+        // Perform the upgrade in a transaction
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(&String::from_utf8_lossy(UPGRADE_V2_TO_V3))?;
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Upgrades from version 3 of the database to version 4 (which adds folders)
+    ///
+    pub (crate) fn upgrade_v3_to_v4(log: &LogPublisher, connection: &mut Connection) -> result::Result<(), FileListError> {
+        log.log((Level::Info, "Upgrading file list from v3 to v4"));
+
+        // Perform the upgrade in a transaction
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(&String::from_utf8_lossy(UPGRADE_V3_TO_V4))?;
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Upgrades from version 4 of the database to version 5 (which adds thumbnails)
+    ///
+    pub (crate) fn upgrade_v4_to_v5(log: &LogPublisher, connection: &mut Connection) -> result::Result<(), FileListError> {
+        log.log((Level::Info, "Upgrading file list from v4 to v5"));
+
+        // Perform the upgrade in a transaction
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(&String::from_utf8_lossy(UPGRADE_V4_TO_V5))?;
+        transaction.commit()?;
+
+        Ok(())
+    }
 }
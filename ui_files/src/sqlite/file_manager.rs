@@ -1,8 +1,10 @@
 use super::file_list::*;
+use super::super::file_folder::*;
 use super::super::file_update::*;
 use super::super::file_manager::*;
 
 use flo_stream::*;
+use flo_canvas::*;
 use flo_logging::*;
 
 use dirs;
@@ -14,8 +16,9 @@ use futures::stream::{BoxStream};
 
 use std::fs;
 use std::sync::*;
+use std::time::{UNIX_EPOCH};
 use std::path::{Path, PathBuf};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 const FILES_DB: &str = "files.db";
 const DATA_DIR: &str = "data";
@@ -50,6 +53,26 @@ pub struct SqliteFileManager {
     core: Arc<Desync<SqliteFileManagerCore>>
 }
 
+///
+/// Returns the directory where the data for a particular application path and sub-path is stored, creating it if it
+/// does not already exist
+///
+pub (crate) fn user_data_path(application_path: &str, sub_path: &str) -> PathBuf {
+    // Start at the user's data directory
+    let mut root_path = dirs::data_local_dir()
+        .or_else(|| dirs::data_dir())
+        .unwrap();
+
+    // Append the path components
+    root_path.push(application_path);
+    root_path.push(sub_path);
+
+    // Create the data directory if it does not exist
+    fs::create_dir_all(root_path.as_path()).unwrap();
+
+    root_path
+}
+
 impl SqliteFileManagerCore {
     ///
     /// Sends an update to everything that's listening for them
@@ -69,16 +92,7 @@ impl SqliteFileManager {
         let log         = LogPublisher::new(module_path!());
 
         // This will be the 'root' data directory for the user
-        let mut root_path = dirs::data_local_dir()
-            .or_else(|| dirs::data_dir())
-            .unwrap();
-
-        // Append the path components
-        root_path.push(application_path);
-        root_path.push(sub_path);
-
-        // Create the data directory if it does not exist
-        fs::create_dir_all(root_path.as_path()).unwrap();
+        let root_path = user_data_path(application_path, sub_path);
 
         // Create the subdirectories too
         let mut data_dir = root_path.clone();
@@ -171,6 +185,38 @@ impl SqliteFileManager {
             None
         }
     }
+
+    ///
+    /// Finds the full path of a path returned by the file list
+    ///
+    fn full_path(&self, file_list_path: PathBuf) -> PathBuf {
+        let mut full_path = self.root_path.clone();
+        full_path.push(DATA_DIR);
+        full_path.push(file_list_path);
+        full_path
+    }
+
+    ///
+    /// Returns the time in milliseconds that a file was last modified
+    ///
+    /// Animations are written through a write-ahead log, so this also checks the `-wal` file next to the path
+    ///
+    fn modified_time(full_path: &Path) -> Option<i64> {
+        let mut wal_path = full_path.as_os_str().to_owned();
+        wal_path.push("-wal");
+
+        let modified_time = |path: &Path| {
+            fs::metadata(path).ok()
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map(|since_epoch| since_epoch.as_millis() as i64)
+        };
+
+        let file_time   = modified_time(full_path)?;
+        let wal_time    = modified_time(Path::new(&wal_path));
+
+        Some(wal_time.map(|wal_time| wal_time.max(file_time)).unwrap_or(file_time))
+    }
 }
 
 impl FileManager for SqliteFileManager {
@@ -181,12 +227,7 @@ impl FileManager for SqliteFileManager {
         // Retrieve from the file list and append the folder we're using
         self.core.sync(|core| core.file_list.list_paths().unwrap())
             .into_iter()
-            .map(|last_component| {
-                let mut full_path = self.root_path.clone();
-                full_path.push(DATA_DIR);
-                full_path.push(last_component);
-                full_path
-            })
+            .map(|last_component| self.full_path(last_component))
             .collect()
     }
 
@@ -276,6 +317,170 @@ impl FileManager for SqliteFileManager {
         }
     }
 
+    ///
+    /// Returns the tags that have been assigned to a particular path
+    ///
+    fn tags_for_path(&self, full_path: &Path) -> Vec<String> {
+        let path = self.file_list_path(full_path);
+
+        if let Some(path) = path {
+            self.core.sync(|core| core.file_list.tags_for_path(path.as_path()).unwrap_or_else(|_| vec![]))
+        } else {
+            vec![]
+        }
+    }
+
+    ///
+    /// Replaces the tags assigned to a particular path
+    ///
+    fn set_tags_for_path(&self, full_path: &Path, tags: Vec<String>) {
+        let path = self.file_list_path(full_path);
+
+        if let Some(path) = path {
+            let full_path = PathBuf::from(full_path);
+
+            let _ = self.core.future(move |core| {
+                core.file_list.set_tags_for_path(path.as_path(), &tags).unwrap();
+
+                // The update contains the tags as they were stored
+                let tags    = core.file_list.tags_for_path(path.as_path()).unwrap_or_else(|_| vec![]);
+                let update  = FileUpdate::SetTags(full_path, tags);
+                Box::pin(core.send_update(update))
+            });
+        }
+    }
+
+    ///
+    /// Returns the paths whose display name or tags contain the specified text
+    ///
+    fn search(&self, text: &str) -> Vec<PathBuf> {
+        let text    = text.to_string();
+        let found   = self.core.sync(move |core| core.file_list.search_paths(&text).unwrap_or_else(|_| vec![]))
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        // Return the paths in the same order as get_all_files
+        self.get_all_files().into_iter()
+            .filter(|full_path| self.file_list_path(full_path.as_path()).map(|path| found.contains(&path)).unwrap_or(false))
+            .collect()
+    }
+
+    ///
+    /// Creates a new folder inside a parent folder (or at the top level if the parent is `None`)
+    ///
+    fn create_folder(&self, parent: Option<FolderId>, display_name: String) -> FolderId {
+        let folder = self.core.sync(|core| {
+            core.log.log((Level::Info, format!("Creating folder `{}`", display_name)));
+            core.file_list.create_folder(parent, &display_name).unwrap()
+        });
+
+        let update = FileUpdate::NewFolder(folder, parent, display_name);
+        let _ = self.core.future(move |core| Box::pin(core.send_update(update)));
+
+        folder
+    }
+
+    ///
+    /// Returns the display name for a folder
+    ///
+    fn folder_name(&self, folder: FolderId) -> Option<String> {
+        self.core.sync(|core| core.file_list.folder_name(folder))
+    }
+
+    ///
+    /// Changes the display name for a folder
+    ///
+    fn set_folder_name(&self, folder: FolderId, display_name: String) {
+        let update = FileUpdate::SetFolderName(folder, display_name.clone());
+
+        let _ = self.core.future(move |core| {
+            core.file_list.set_folder_name(folder, &display_name).unwrap();
+            Box::pin(core.send_update(update))
+        });
+    }
+
+    ///
+    /// Returns the folder that contains a folder (or `None` if it's at the top level)
+    ///
+    fn parent_folder(&self, folder: FolderId) -> Option<FolderId> {
+        self.core.sync(|core| core.file_list.parent_folder(folder).unwrap_or(None))
+    }
+
+    ///
+    /// Returns the folder that contains a path (or `None` if it's at the top level)
+    ///
+    fn folder_for_path(&self, full_path: &Path) -> Option<FolderId> {
+        let path = self.file_list_path(full_path);
+
+        if let Some(path) = path {
+            self.core.sync(|core| core.file_list.folder_for_path(path.as_path()).unwrap_or(None))
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Moves a path to the start of a folder (or to the top level if the folder is `None`)
+    ///
+    fn move_path_to_folder(&self, full_path: &Path, folder: Option<FolderId>) {
+        let path = self.file_list_path(full_path);
+
+        if let Some(path) = path {
+            let update = FileUpdate::MovedToFolder(PathBuf::from(full_path), folder);
+
+            let _ = self.core.future(move |core| {
+                core.file_list.move_path_to_folder(path.as_path(), folder).unwrap();
+                Box::pin(core.send_update(update))
+            });
+        }
+    }
+
+    ///
+    /// Returns the files and folders in a folder (or at the top level if the folder is `None`), in display order
+    ///
+    fn folder_contents(&self, folder: Option<FolderId>) -> Vec<FolderEntry> {
+        self.core.sync(|core| core.file_list.list_folder(folder).unwrap_or_else(|_| vec![]))
+            .into_iter()
+            .map(|entry| match entry {
+                FolderEntry::File(path)     => FolderEntry::File(self.full_path(path)),
+                FolderEntry::Folder(folder) => FolderEntry::Folder(folder)
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns the thumbnail stored for a path, or `None` if there's no thumbnail or the file has changed since it was stored
+    ///
+    fn cached_thumbnail(&self, full_path: &Path) -> Option<Vec<Draw>> {
+        let path            = self.file_list_path(full_path)?;
+        let modified_time   = Self::modified_time(full_path)?;
+
+        let (thumbnail_time, drawing) = self.core.sync(|core| core.file_list.thumbnail_for_path(path.as_path()))?;
+
+        if thumbnail_time == modified_time {
+            decode_drawing(drawing.chars()).collect::<Result<Vec<_>, _>>().ok()
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Stores a thumbnail for a path, which will be returned by `cached_thumbnail` until the file is changed
+    ///
+    fn set_cached_thumbnail(&self, full_path: &Path, thumbnail: Vec<Draw>) {
+        let path            = self.file_list_path(full_path);
+        let modified_time   = Self::modified_time(full_path);
+
+        if let (Some(path), Some(modified_time)) = (path, modified_time) {
+            let mut drawing = String::new();
+            thumbnail.encode_canvas(&mut drawing);
+
+            self.core.desync(move |core| {
+                core.file_list.set_thumbnail_for_path(path.as_path(), modified_time, &drawing).unwrap();
+            });
+        }
+    }
+
     ///
     /// Returns a stream of updates indicating changes made to the file manager
     ///
@@ -404,6 +609,78 @@ mod test {
         assert!(test_files.display_name_for_path(new_path.as_path()) == Some("Test display name".to_string()));
     }
 
+    #[test]
+    fn set_tags() {
+        let test_files  = SqliteFileManager::new("app.flowbetween.test", "set_tags");
+        let new_path    = test_files.create_new_path();
+
+        test_files.set_tags_for_path(new_path.as_path(), vec!["Scene 1".to_string(), "Rough".to_string()]);
+        assert!(test_files.tags_for_path(new_path.as_path()) == vec!["Rough".to_string(), "Scene 1".to_string()]);
+    }
+
+    #[test]
+    fn search_by_tag() {
+        let test_files  = SqliteFileManager::new("app.flowbetween.test", "search_by_tag");
+        let new_path1   = test_files.create_new_path();
+        let new_path2   = test_files.create_new_path();
+        let tag         = format!("tag-{}", new_path1.file_name().unwrap().to_string_lossy());
+
+        test_files.set_tags_for_path(new_path1.as_path(), vec![tag.clone()]);
+        test_files.set_display_name_for_path(new_path2.as_path(), format!("Named {}", tag));
+
+        assert!(test_files.search(&tag) == vec![new_path2, new_path1]);
+    }
+
+    #[test]
+    fn move_path_to_folder() {
+        let test_files  = SqliteFileManager::new("app.flowbetween.test", "move_path_to_folder");
+        let new_path1   = test_files.create_new_path();
+        let new_path2   = test_files.create_new_path();
+        let folder      = test_files.create_folder(None, "Episode 1".to_string());
+
+        test_files.move_path_to_folder(new_path1.as_path(), Some(folder));
+
+        assert!(test_files.folder_name(folder) == Some("Episode 1".to_string()));
+        assert!(test_files.folder_for_path(new_path1.as_path()) == Some(folder));
+        assert!(test_files.folder_for_path(new_path2.as_path()) == None);
+        assert!(test_files.folder_contents(Some(folder)) == vec![FolderEntry::File(new_path1.clone())]);
+        assert!(test_files.get_all_files().contains(&new_path1));
+    }
+
+    #[test]
+    fn store_thumbnail() {
+        let test_files  = SqliteFileManager::new("app.flowbetween.test", "store_thumbnail");
+        let new_path    = test_files.create_new_path();
+
+        fs::write(new_path.as_path(), "Not really an animation").unwrap();
+
+        let thumbnail   = vec![Draw::NewPath, Draw::Move(10.0, 20.0), Draw::Line(30.0, 40.0), Draw::Fill];
+        assert!(test_files.cached_thumbnail(new_path.as_path()) == None);
+
+        test_files.set_cached_thumbnail(new_path.as_path(), thumbnail.clone());
+        assert!(test_files.cached_thumbnail(new_path.as_path()) == Some(thumbnail));
+
+        test_files.delete_path(new_path.as_path());
+    }
+
+    #[test]
+    fn will_send_folder_updates_to_stream() {
+        let test_files          = SqliteFileManager::new("app.flowbetween.test", "will_send_folder_updates_to_stream");
+        let mut update_stream   = test_files.update_stream();
+
+        let new_path            = test_files.create_new_path();
+        let folder              = test_files.create_folder(None, "Episode 1".to_string());
+        test_files.move_path_to_folder(new_path.as_path(), Some(folder));
+        test_files.set_folder_name(folder, "Pilot".to_string());
+
+        executor::block_on(async {
+            assert!(update_stream.next().await == Some(FileUpdate::NewFile(new_path.clone())));
+            assert!(update_stream.next().await == Some(FileUpdate::NewFolder(folder, None, "Episode 1".to_string())));
+            assert!(update_stream.next().await == Some(FileUpdate::MovedToFolder(new_path.clone(), Some(folder))));
+            assert!(update_stream.next().await == Some(FileUpdate::SetFolderName(folder, "Pilot".to_string())));
+        })
+    }
+
     #[test]
     fn will_send_updates_to_stream() {
         let test_files          = SqliteFileManager::new("app.flowbetween.test", "will_send_updates_to_stream");
@@ -2,6 +2,8 @@ mod file_manager;
 mod file_list;
 mod file_list_legacy;
 mod file_error;
+mod preference_store;
 
 pub use self::file_manager::*;
 pub use self::file_error::*;
+pub use self::preference_store::*;
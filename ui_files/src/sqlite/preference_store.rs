@@ -0,0 +1,105 @@
+use super::file_manager::*;
+use super::super::preferences::*;
+
+use flo_logging::*;
+
+use ::desync::*;
+use rusqlite::*;
+
+/// The name of the database where the preferences are stored (this is kept next to the database containing the file list)
+const PREFERENCES_DB: &str = "preferences.db";
+
+/// The definition of the preferences database
+const DEFINITION: &str = "CREATE TABLE IF NOT EXISTS Flo_Preferences (Key TEXT NOT NULL PRIMARY KEY, Value TEXT NOT NULL);";
+
+struct SqlitePreferenceStoreCore {
+    /// The log for this preference store
+    log: LogPublisher,
+
+    /// The connection to the preferences database
+    connection: Connection
+}
+
+///
+/// A preference store that keeps its values in a Sqlite database
+///
+pub struct SqlitePreferenceStore {
+    /// The core of the store, where the database connection is kept
+    core: Desync<SqlitePreferenceStoreCore>
+}
+
+impl SqlitePreferenceStore {
+    ///
+    /// Opens the preferences for an application path and sub-path, stored alongside the files
+    /// managed by a `SqliteFileManager` with the same paths
+    ///
+    pub fn new(application_path: &str, sub_path: &str) -> Result<SqlitePreferenceStore> {
+        let mut database_file = user_data_path(application_path, sub_path);
+        database_file.push(PREFERENCES_DB);
+
+        Self::from_connection(Connection::open(database_file.as_path())?)
+    }
+
+    ///
+    /// Creates a preference store using an existing database connection
+    ///
+    pub fn from_connection(connection: Connection) -> Result<SqlitePreferenceStore> {
+        connection.execute_batch(DEFINITION)?;
+
+        let core = SqlitePreferenceStoreCore {
+            log:        LogPublisher::new(module_path!()),
+            connection: connection
+        };
+
+        Ok(SqlitePreferenceStore {
+            core: Desync::new(core)
+        })
+    }
+}
+
+impl PreferenceStore for SqlitePreferenceStore {
+    fn get(&self, key: &str) -> Option<String> {
+        let key = key.to_string();
+
+        self.core.sync(move |core| {
+            core.connection.query_row("SELECT Value FROM Flo_Preferences WHERE Key = ?", &[&key], |row| row.get(0)).ok()
+        })
+    }
+
+    fn set(&self, key: &str, value: Option<String>) {
+        let key = key.to_string();
+
+        // Preferences are written in the background, as they can change rapidly (eg, while a slider is dragged)
+        self.core.desync(move |core| {
+            let result = match value {
+                Some(value) => core.connection.execute("INSERT OR REPLACE INTO Flo_Preferences (Key, Value) VALUES (?, ?)", &[&key, &value]),
+                None        => core.connection.execute("DELETE FROM Flo_Preferences WHERE Key = ?", &[&key])
+            };
+
+            if let Err(err) = result {
+                core.log.log((Level::Warn, format!("Could not store preference `{}`: {:?}", key, err)));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn store_and_retrieve_preference() {
+        let store = SqlitePreferenceStore::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+
+        assert!(store.get("ink.size") == None);
+
+        store.set("ink.size", Some("12.0".to_string()));
+        assert!(store.get("ink.size") == Some("12.0".to_string()));
+
+        store.set("ink.size", Some("8.0".to_string()));
+        assert!(store.get("ink.size") == Some("8.0".to_string()));
+
+        store.set("ink.size", None);
+        assert!(store.get("ink.size") == None);
+    }
+}
@@ -2,15 +2,20 @@ use super::file_chooser::*;
 use super::file_chooser_model::*;
 use super::file_controller::*;
 use super::super::file_model::*;
+use super::super::file_folder::*;
 use super::super::file_manager::*;
+use super::super::file_update::*;
 use super::super::open_file_store::*;
+use super::super::MESSAGES;
 
 use flo_ui::*;
 use flo_canvas::*;
 use flo_binding::*;
+use ::desync::*;
+use futures::prelude::*;
 
 use std::sync::*;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::collections::HashSet;
 
 const LOGO_HEIGHT: f32      = 256.0;
@@ -18,6 +23,9 @@ const NUM_COLUMNS: u32      = 3;
 const FILE_WIDTH: f32       = 256.0;
 const FILE_HEIGHT: f32      = 180.0;
 const VIRTUAL_HEIGHT: f32   = 512.0;
+const FOLDER_COLUMNS: u32   = 6;
+const FOLDER_WIDTH: f32     = 128.0;
+const FOLDER_HEIGHT: f32    = 64.0;
 
 ///
 /// The file chooser controller can be used as a front-end for tablet or web-style applications
@@ -44,6 +52,9 @@ pub struct FileChooserController<Chooser: FileChooser> {
 
     /// The cache of open files
     open_file_store: Arc<OpenFileStore<<Chooser::Controller as FileController>::Model>>,
+
+    /// Queue used to render thumbnails in the background
+    thumbnail_queue: Arc<Desync<()>>
 }
 
 impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
@@ -53,6 +64,9 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
     pub fn new<LogoController: Controller+'static>(chooser: Chooser, logo_controller: LogoController) -> FileChooserController<Chooser> {
         let logo_controller     = Arc::new(logo_controller);
 
+        // Make sure the translations for the file chooser are available
+        MESSAGES.load();
+
         // Fetch the file manager and file store from the chooser
         let file_manager        = chooser.get_file_manager();
         let open_file_store     = chooser.get_file_store();
@@ -75,17 +89,80 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
         let ui                  = Self::ui(&model, BindRef::from(background_color.clone()), Arc::clone(&viewmodel));
 
         // Create the chooser controller
-        FileChooserController {
+        let controller = FileChooserController {
             model:              model,
             viewmodel:          viewmodel,
             logo_controller:    logo_controller,
             ui:                 ui,
             file_manager:       file_manager,
             background_color:   background_color,
-            open_file_store:    open_file_store
+            open_file_store:    open_file_store,
+            thumbnail_queue:    Arc::new(Desync::new(()))
+        };
+
+        // Generate thumbnails for any file that doesn't have an up-to-date one, and for new files as they're added
+        controller.refresh_thumbnails();
+        controller.follow_file_updates();
+
+        controller
+    }
+
+    ///
+    /// Renders the thumbnail for a file and stores it in the file manager's cache and the file's model
+    ///
+    fn render_thumbnail(path: &Path, file_manager: &Arc<Chooser::FileManager>, open_file_store: &Arc<OpenFileStore<<Chooser::Controller as FileController>::Model>>, all_files: &BindRef<Arc<Vec<FileUiModel>>>, thumbnail_canvases: &Arc<ResourceManager<BindingCanvas>>) {
+        // Files that need recovering are left alone until the user decides what to do with them
+        if open_file_store.needs_recovery(path) { return; }
+
+        // Files that can't be read are left without a thumbnail
+        if let Ok(drawing) = open_file_store.render_thumbnail(path) {
+            file_manager.set_cached_thumbnail(path, drawing.clone());
+
+            // Files added since the list was read will pick up the cached thumbnail when their model is created
+            for file in all_files.get().iter().filter(|file| file.path.get().as_path() == path) {
+                file.thumbnail.set(Some(FileChooserModel::<Chooser>::thumbnail_canvas(thumbnail_canvases, drawing.clone())));
+            }
         }
     }
 
+    ///
+    /// Renders thumbnails in the background for any file that doesn't have one
+    ///
+    fn refresh_thumbnails(&self) {
+        for file in self.model.all_files.get().iter() {
+            if file.thumbnail.get().is_some() { continue; }
+
+            let path                = file.path.get();
+            let file_manager        = Arc::clone(&self.file_manager);
+            let open_file_store     = Arc::clone(&self.open_file_store);
+            let all_files           = self.model.all_files.clone();
+            let thumbnail_canvases  = Arc::clone(&self.model.thumbnail_canvases);
+
+            self.thumbnail_queue.desync(move |_| {
+                Self::render_thumbnail(path.as_path(), &file_manager, &open_file_store, &all_files, &thumbnail_canvases);
+            });
+        }
+    }
+
+    ///
+    /// Renders thumbnails in the background for files as they're added to the file manager
+    ///
+    fn follow_file_updates(&self) {
+        let file_manager        = Arc::clone(&self.file_manager);
+        let open_file_store     = Arc::clone(&self.open_file_store);
+        let all_files           = self.model.all_files.clone();
+        let thumbnail_canvases  = Arc::clone(&self.model.thumbnail_canvases);
+        let updates             = self.file_manager.update_stream();
+
+        pipe_in(Arc::clone(&self.thumbnail_queue), updates, move |_, update| {
+            if let FileUpdate::NewFile(path) = update {
+                Self::render_thumbnail(path.as_path(), &file_manager, &open_file_store, &all_files, &thumbnail_canvases);
+            }
+
+            future::ready(()).boxed()
+        });
+    }
+
     ///
     /// Converts a path to a string
     ///
@@ -162,12 +239,20 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
         // Make sure the file is created in the viewmodel
         Self::create_viewmodel_for_file(viewmodel, file);
 
+        // The preview shows the thumbnail once it's been rendered
+        let preview = if let Some(thumbnail) = file.thumbnail.get() {
+            Control::canvas()
+                .with(thumbnail)
+        } else {
+            Control::empty()
+                .with(Appearance::Background(Color::Rgba(0.0, 0.6, 0.9, 1.0)))
+        };
+
         // Control consists of a panel showing a preview of the file and a label showing the 'filename'
         Control::container()
             .with(vec![
-                Control::empty()
+                preview
                     .with(Bounds::stretch_vert(1.0))
-                    .with(Appearance::Background(Color::Rgba(0.0, 0.6, 0.9, 1.0)))
                     .with((ActionTrigger::Click, format!("Open-{}", index)))
                     .with((ActionTrigger::Drag, format!("Drag-{}", index))),
                 Control::empty()
@@ -182,6 +267,62 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
             .with(ControlAttribute::Padding((2, 2), (2, 2)))
     }
 
+    ///
+    /// Creates a control representing a folder
+    ///
+    /// Clicking the folder opens it and clicking the name edits it (if there's an edit action)
+    ///
+    fn folder_ui(name: String, open_action: String, edit_action: Option<String>, editing: bool, drop_target: bool) -> Control {
+        // If the user is editing the folder name, then use a textbox instead of the label
+        let label = if editing {
+            Control::text_box()
+                .with(TextAlign::Center)
+                .with(FontWeight::Normal)
+                .with(State::FocusPriority(Property::from(128.0)))
+                .with(Bounds::next_vert(22.0))
+                .with((ActionTrigger::Click, "DoNotClickThrough"))
+                .with((ActionTrigger::EditValue, "SetEditedFolderName"))
+                .with((ActionTrigger::CancelEdit, "CancelEditingFolderName"))
+                .with((ActionTrigger::Dismiss, "StopEditingFolderName"))
+                .with((ActionTrigger::SetValue, "StopEditingFolderName"))
+                .with(name)
+        } else {
+            Control::label()
+                .with(TextAlign::Center)
+                .with(FontWeight::Normal)
+                .with(Bounds::next_vert(22.0))
+                .with(name)
+                .with((ActionTrigger::Click, edit_action.unwrap_or_else(|| open_action.clone())))
+        };
+
+        // Folders are highlighted when a file is dragged over them
+        let background = if drop_target { Color::Rgba(0.0, 0.8, 1.0, 1.0) } else { Color::Rgba(0.9, 0.7, 0.2, 1.0) };
+
+        Control::container()
+            .with(vec![
+                Control::empty()
+                    .with(Bounds::stretch_vert(1.0))
+                    .with(Appearance::Background(background))
+                    .with((ActionTrigger::Click, open_action)),
+                Control::empty()
+                    .with(Bounds::next_vert(2.0)),
+                label
+            ])
+            .with(ControlAttribute::Padding((2, 2), (2, 2)))
+    }
+
+    ///
+    /// Returns the height of the grid of folder tiles above the files
+    ///
+    fn folder_grid_height(num_tiles: usize) -> f32 {
+        if num_tiles == 0 {
+            0.0
+        } else {
+            let num_rows = ((num_tiles as u32)-1) / FOLDER_COLUMNS + 1;
+            (num_rows as f32) * FOLDER_HEIGHT + 8.0
+        }
+    }
+
     ///
     /// Creates the UI binding from the model
     ///
@@ -195,6 +336,13 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
         let editing_filename_index  = model.editing_filename_index.clone();
         let selected_file_count     = model.selected_file_count.clone();
         let confirming_deletion     = model.confirming_deletion.clone();
+        let recovering_file         = model.recovering_file.clone();
+        let all_tags                = model.all_tags.clone();
+        let tag_filter              = model.tag_filter.clone();
+        let current_folder          = model.current_folder.clone();
+        let folder_list             = model.folder_list.clone();
+        let editing_folder_index    = model.editing_folder_index.clone();
+        let drag_into_folder        = model.drag_into_folder.clone();
 
         // Generate the UI
         let ui = computed(move || {
@@ -215,6 +363,34 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                 let dragging_file       = dragging_file.get();
                 let drag_after_index    = drag_after_index.get();
 
+                // The folders are displayed as tiles above the files, with a tile for moving up to the parent folder if we're not at the top level
+                let current_folder          = current_folder.get();
+                let folder_list             = folder_list.get();
+                let editing_folder_index    = editing_folder_index.get();
+                let drag_into_folder        = drag_into_folder.get();
+
+                let parent_tile             = current_folder.map(|_| (localized("Up"), "OpenParentFolder".to_string(), None, false));
+                let folder_tiles            = folder_list.iter().enumerate()
+                    .map(|(folder_index, folder)| (folder.name.get(), format!("OpenFolder-{}", folder_index), Some(format!("EditFolderName-{}", folder_index)), editing_folder_index == Some(folder_index)));
+                let folder_tiles            = parent_tile.into_iter().chain(folder_tiles).collect::<Vec<_>>();
+                let folder_height           = Self::folder_grid_height(folder_tiles.len());
+
+                let folders                 = folder_tiles.into_iter().enumerate()
+                    .map(|(tile_index, (name, open_action, edit_action, editing))| {
+                        let row     = (tile_index as u32) / FOLDER_COLUMNS;
+                        let column  = (tile_index as u32) % FOLDER_COLUMNS;
+                        let x       = (column as f32) * FOLDER_WIDTH;
+                        let y       = (row as f32) * FOLDER_HEIGHT;
+
+                        Self::folder_ui(name, open_action, edit_action, editing, drag_into_folder == Some(tile_index))
+                            .with(Bounds {
+                                x1: Position::At(x),
+                                y1: Position::At(y),
+                                x2: Position::At(x+FOLDER_WIDTH),
+                                y2: Position::At(y+FOLDER_HEIGHT) })
+                            .with(ControlAttribute::ZIndex(0))
+                    });
+
                 let mut files           = file_range.into_iter()
                     .filter_map(|file_index| file_list.get(file_index as usize).map(|file| (file_index, file)))
                     .flat_map(|(file_index, file_model)| {
@@ -223,7 +399,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                         let row     = file_index / NUM_COLUMNS;
                         let column  = file_index % NUM_COLUMNS;
                         let x       = (column as f32) * FILE_WIDTH;
-                        let y       = (row as f32) * FILE_HEIGHT;
+                        let y       = (row as f32) * FILE_HEIGHT + folder_height;
 
                         if dragging_file == Some(file_index as usize) {
                             // File that is being dragged has a high z-index and moves with the drag position
@@ -289,7 +465,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                     let row         = file_index / NUM_COLUMNS;
                     let column      = file_index % NUM_COLUMNS;
                     let x           = (column as f32) * FILE_WIDTH;
-                    let y           = (row as f32) * FILE_HEIGHT;
+                    let y           = (row as f32) * FILE_HEIGHT + folder_height;
 
                     files.push(Control::empty()
                         .with(Bounds {
@@ -322,7 +498,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                                     .with(vec![Control::label()
                                         .with(Bounds::fill_all())
                                         .with(TextAlign::Center)
-                                        .with("Clear selection")
+                                        .with(localized("Clear selection"))
                                     ])
                                     .with((ActionTrigger::Click, "ClearSelection")),
 
@@ -360,9 +536,112 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                     vec![]
                 };
 
+                // If a file was not closed cleanly, ask the user whether or not to recover it
+                let recovery_controls = if let Some(recovering_file) = recovering_file.get() {
+                    let file_name = file_list.iter()
+                        .filter(|file| file.path.get() == recovering_file)
+                        .map(|file| file.name.get())
+                        .nth(0)
+                        .unwrap_or_else(|| localized("Untitled"));
+
+                    vec![
+                        Control::container()
+                            .with(Bounds {
+                                x1: Position::Start,
+                                x2: Position::End,
+                                y1: Position::At(8.0),
+                                y2: Position::At(128.0)
+                            })
+                            .with(ControlAttribute::Padding((64, 8), (64, 8)))
+                            .with(vec![
+                                Control::label()
+                                    .with(Bounds::next_vert(24.0))
+                                    .with(TextAlign::Center)
+                                    .with(format!("'{}' was not closed properly", file_name)),
+                                Control::label()
+                                    .with(Bounds::next_vert(24.0))
+                                    .with(TextAlign::Center)
+                                    .with(Font::Size(11.0))
+                                    .with(localized("Some recent changes may have been lost: do you want to try to recover them?")),
+
+                                Control::empty()
+                                    .with(Bounds::next_vert(8.0)),
+
+                                Control::container()
+                                    .with(Bounds::next_vert(32.0))
+                                    .with(vec![
+                                        Control::empty()
+                                            .with(Bounds::stretch_horiz(1.0)),
+                                        Control::button()
+                                            .with(Bounds::next_horiz(160.0))
+                                            .with(vec![Control::label()
+                                                .with(Bounds::fill_all())
+                                                .with(TextAlign::Center)
+                                                .with(localized("Recover changes"))
+                                            ])
+                                            .with((ActionTrigger::Click, "RecoverFile")),
+                                        Control::empty()
+                                            .with(Bounds::next_horiz(8.0)),
+                                        Control::button()
+                                            .with(Bounds::next_horiz(160.0))
+                                            .with(vec![Control::label()
+                                                .with(Bounds::fill_all())
+                                                .with(TextAlign::Center)
+                                                .with(localized("Discard changes"))
+                                            ])
+                                            .with((ActionTrigger::Click, "DiscardRecovery")),
+                                        Control::empty()
+                                            .with(Bounds::stretch_horiz(1.0))
+                                    ])
+                            ])
+                            .with(Appearance::Background(Color::Rgba(0.0, 0.0, 0.0, 0.6)))
+                            .with(Scroll::Fix(FixedAxis::Vertical))
+                            .with((ActionTrigger::Dismiss, "CancelRecovery"))
+                            .with(ControlAttribute::ZIndex(6))
+                    ]
+                } else {
+                    vec![]
+                };
+
+                // If any files are tagged, the user can choose a tag to filter the files by
+                let all_tags        = all_tags.get();
+                let tag_filter      = tag_filter.get();
+                let tag_controls    = if all_tags.len() > 0 {
+                    let tag_button  = |label: &str, action: String, selected: bool| {
+                        Control::button()
+                            .with(Bounds::next_horiz(96.0))
+                            .with(vec![Control::label()
+                                .with(Bounds::fill_all())
+                                .with(TextAlign::Center)
+                                .with(label)
+                            ])
+                            .with(State::Selected(Property::Bool(selected)))
+                            .with((ActionTrigger::Click, action))
+                    };
+
+                    let buttons = vec![tag_button(&localized("All files"), "ClearTagFilter".to_string(), tag_filter.is_none())].into_iter()
+                        .chain(all_tags.iter().enumerate().map(|(index, tag)| tag_button(tag, format!("FilterTag-{}", index), tag_filter.as_ref() == Some(tag))))
+                        .flat_map(|button| vec![button, Control::empty().with(Bounds::next_horiz(4.0))]);
+
+                    vec![
+                        Control::container()
+                            .with(vec![Control::empty().with(Bounds::stretch_horiz(1.0))].into_iter()
+                                .chain(buttons)
+                                .chain(vec![Control::empty().with(Bounds::stretch_horiz(1.0))])
+                                .collect::<Vec<_>>())
+                            .with(Font::Size(11.0))
+                            .with(Bounds::next_vert(24.0)),
+                        Control::empty()
+                            .with(Bounds::next_vert(4.0))
+                    ]
+                } else {
+                    vec![]
+                };
+                let tag_height      = if tag_controls.len() > 0 { 28.0 } else { 0.0 };
+
                 // Work out the height of the container
                 let num_rows    = ((file_list.len() as i32)-1) / (NUM_COLUMNS as i32) + 1;
-                let height      = LOGO_HEIGHT + 8.0 + 24.0 + FILE_HEIGHT * (num_rows as f32);
+                let height      = LOGO_HEIGHT + 8.0 + 24.0 + tag_height + folder_height + FILE_HEIGHT * (num_rows as f32);
 
                 // The UI allows the user to pick a file
                 Control::scrolling_container()
@@ -396,16 +675,28 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                                     .with(vec![Control::label()
                                         .with(Bounds::fill_all())
                                         .with(TextAlign::Center)
-                                        .with("+ New file")])
+                                        .with(localized("+ New file"))])
                                         .with((ActionTrigger::Click, "CreateNewFile")),
+                                Control::empty()
+                                    .with(Bounds::next_horiz(8.0)),
+                                Control::button()
+                                    .with(Bounds::next_horiz(120.0))
+                                    .with(vec![Control::label()
+                                        .with(Bounds::fill_all())
+                                        .with(TextAlign::Center)
+                                        .with(localized("+ New folder"))])
+                                        .with((ActionTrigger::Click, "CreateNewFolder")),
                                 Control::empty()
                                     .with(Bounds::stretch_horiz(1.0))
                             ])
                             .with(Bounds::next_vert(32.0)),
 
                         Control::empty()
-                            .with(Bounds::next_vert(4.0)),
-
+                            .with(Bounds::next_vert(4.0))
+                    ]
+                    .into_iter()
+                    .chain(tag_controls)
+                    .chain(vec![
                         // Actual files
                         Control::container()
                             .with(Bounds::fill_vert())
@@ -414,16 +705,16 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                                     .with(Bounds::stretch_horiz(1.0)),
                                 Control::container()
                                     .with(Bounds::next_horiz(FILE_WIDTH * (NUM_COLUMNS as f32)))
-                                    .with(files)
+                                    .with(folders.chain(files).collect::<Vec<_>>())
                                     .with(Appearance::Foreground(Color::Rgba(1.0, 1.0, 1.0, 1.0)))
                                     .with(Font::Size(11.0))
                                     .with(Font::Weight(FontWeight::Light)),
                                 Control::empty()
                                     .with(Bounds::stretch_horiz(1.0)),
                             ])
-                    ]
-                    .into_iter()
+                    ])
                     .chain(selected_file_controls)
+                    .chain(recovery_controls)
                     .collect::<Vec<_>>()
                     )
 
@@ -451,6 +742,78 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
             self.file_manager.set_display_name_for_path(&file_path.as_path(), new_filename);
         }
     }
+
+    ///
+    /// Deselects all of the files
+    ///
+    fn clear_selection(&self) {
+        self.model.confirming_deletion.set(false);
+        self.model.all_files.get()
+            .iter()
+            .for_each(|file_model| file_model.selected.set(false));
+    }
+
+    ///
+    /// Stops the folder name editing process and updates the name of the folder
+    ///
+    pub fn stop_editing_folder_name(&self) {
+        if let Some(edited_folder_index) = self.model.editing_folder_index.get() {
+            // Stop editing the folder name
+            self.model.editing_folder_index.set(None);
+
+            // Send the new name to the file manager
+            if let Some(folder_model) = self.model.folder_list.get().get(edited_folder_index) {
+                self.file_manager.set_folder_name(folder_model.id, self.model.edited_folder_name.get());
+            }
+        }
+    }
+
+    ///
+    /// Displays the contents of a folder (or the top level if the folder is None)
+    ///
+    fn open_folder(&self, folder: Option<FolderId>) {
+        // The indexes of any selected or edited files will change
+        self.stop_editing_filename();
+        self.stop_editing_folder_name();
+        self.clear_selection();
+
+        self.model.current_folder.set(folder);
+    }
+
+    ///
+    /// Returns the folder that each of the folder tiles will move a file into when the file is dropped on it
+    ///
+    fn folder_tile_targets(&self) -> Vec<Option<FolderId>> {
+        let all_folders     = self.model.all_folders.get();
+        let current_folder  = self.model.current_folder.get();
+
+        // If we're not at the top level, the first tile moves files into the parent folder
+        let parent_tile     = current_folder.map(|current_folder| all_folders.iter()
+            .filter(|folder| folder.id == current_folder)
+            .map(|folder| folder.parent.get())
+            .nth(0)
+            .unwrap_or(None));
+
+        parent_tile.into_iter()
+            .chain(self.model.folder_list.get().iter().map(|folder| Some(folder.id)))
+            .collect()
+    }
+
+    ///
+    /// Opens the file at the specified path
+    ///
+    fn open_file(&self, path: PathBuf) {
+        // Create a new controller for the file
+        let shared_state    = self.open_file_store.open_shared(path.as_path());
+        let instance_state  = shared_state.new_instance();
+        let new_controller  = Chooser::Controller::open(instance_state);
+        let new_controller  = Arc::new(new_controller);
+
+        // Set as the main controller
+        *self.model.shared_state.lock().unwrap() = Some(shared_state);
+        self.model.open_file.set(Some(path));
+        self.model.active_controller.set(Some(new_controller));
+    }
 }
 
 impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser> {
@@ -492,9 +855,10 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                 let top     = (*y as f32) * VIRTUAL_HEIGHT;
                 let bottom  = ((y+height) as f32) * VIRTUAL_HEIGHT;
 
-                // Correct for logo position
-                let top     = top - LOGO_HEIGHT;
-                let bottom  = bottom - LOGO_HEIGHT;
+                // Correct for logo and folder positions
+                let offset  = LOGO_HEIGHT + Self::folder_grid_height(self.folder_tile_targets().len());
+                let top     = top - offset;
+                let bottom  = bottom - offset;
 
                 // Get the file range
                 let top     = (top/FILE_HEIGHT - 1.0).floor().max(0.0);
@@ -511,20 +875,24 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
             ("CreateNewFile", _) => {
                 // Stop any editing
                 self.stop_editing_filename();
+                self.stop_editing_folder_name();
 
-                // Create a new file in the file manager
+                // Create a new file in the file manager (in the folder the user is looking at)
                 let new_file = self.file_manager.create_new_path();
+                if let Some(current_folder) = self.model.current_folder.get() {
+                    self.file_manager.move_path_to_folder(new_file.as_path(), Some(current_folder));
+                }
 
                 // Give it a unique name
                 let all_files       = self.file_manager.get_all_files();
                 let used_names      = all_files.into_iter().filter_map(|path| self.file_manager.display_name_for_path(path.as_path())).collect::<HashSet<_>>();
 
-                let mut new_name    = String::from("New file");
+                let mut new_name    = localized("New file");
                 let mut name_index  = 0;
 
                 while used_names.contains(&new_name) {
                     name_index += 1;
-                    new_name = format!("New file ({})", name_index);
+                    new_name = localized_format("New file ({index})", &[("index", name_index.to_string())]);
                 }
 
                 self.file_manager.set_display_name_for_path(new_file.as_path(), new_name.clone());
@@ -534,11 +902,55 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                 self.model.editing_filename_index.set(Some(0));
             },
 
+            ("CreateNewFolder", _) => {
+                // Stop any editing
+                self.stop_editing_filename();
+                self.stop_editing_folder_name();
+
+                // Give the folder a unique name
+                let used_names      = self.model.all_folders.get().iter().map(|folder| folder.name.get()).collect::<HashSet<_>>();
+
+                let mut new_name    = localized("New folder");
+                let mut name_index  = 0;
+
+                while used_names.contains(&new_name) {
+                    name_index += 1;
+                    new_name = localized_format("New folder ({index})", &[("index", name_index.to_string())]);
+                }
+
+                // Create the folder in the current folder
+                self.file_manager.create_folder(self.model.current_folder.get(), new_name.clone());
+
+                // Edit the name (new folders are displayed first)
+                self.model.edited_folder_name.set(new_name);
+                self.model.editing_folder_index.set(Some(0));
+            },
+
+            ("OpenParentFolder", _) => {
+                let parent_folder = self.model.current_folder.get().and_then(|current_folder| self.file_manager.parent_folder(current_folder));
+                self.open_folder(parent_folder);
+            },
+
+            ("CancelEditingFolderName", _) => {
+                self.model.editing_folder_index.set(None);
+            },
+
+            ("StopEditingFolderName", _) => {
+                self.stop_editing_folder_name();
+            },
+
+            ("SetEditedFolderName", ActionParameter::Value(PropertyValue::String(new_name))) => {
+                self.model.edited_folder_name.set(new_name.clone());
+            },
+
+            ("ClearTagFilter", _) => {
+                self.stop_editing_filename();
+                self.clear_selection();
+                self.model.tag_filter.set(None);
+            },
+
             ("ClearSelection", _) => {
-                self.model.confirming_deletion.set(false);
-                self.model.file_list.get()
-                    .iter()
-                    .for_each(|file_model| file_model.selected.set(false));
+                self.clear_selection();
             },
 
             ("ConfirmDeleteSelectedFiles", _) => {
@@ -568,6 +980,29 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                 self.model.confirming_deletion.set(false);
             },
 
+            ("RecoverFile", _) => {
+                if let Some(path) = self.model.recovering_file.get() {
+                    // Recover the lost changes, then open the file as normal (files that can't be recovered are left closed)
+                    self.model.recovering_file.set(None);
+                    if self.open_file_store.recover(path.as_path()).is_ok() {
+                        self.open_file(path);
+                    }
+                }
+            },
+
+            ("DiscardRecovery", _) => {
+                if let Some(path) = self.model.recovering_file.get() {
+                    // Throw away the lost changes, then open the file as normal
+                    self.model.recovering_file.set(None);
+                    self.open_file_store.discard_recovery(path.as_path());
+                    self.open_file(path);
+                }
+            },
+
+            ("CancelRecovery", _) => {
+                self.model.recovering_file.set(None);
+            },
+
             ("CancelEditingFilename", _) => {
                 // Just unset the editing index without storing the edited value
                 self.model.editing_filename_index.set(None);
@@ -592,21 +1027,50 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                     // Get the index of the file being opened
                     let (_, file_index) = action.split_at("Open-".len());
                     let file_index      = usize::from_str_radix(file_index, 10).unwrap();
+                    let path            = self.model.file_list.get()[file_index].path.get();
 
-                    // ... and the file itself
-                    let file_model      = &self.model.file_list.get()[file_index];
-                    let path            = file_model.path.get();
+                    if self.open_file_store.needs_recovery(path.as_path()) {
+                        // Ask the user whether or not to recover the file before opening it (the file list may change while we're asking)
+                        self.model.recovering_file.set(Some(path));
+                    } else {
+                        self.open_file(path);
+                    }
+
+                } else if action.starts_with("OpenFolder-") {
 
-                    // Create a new controller for the file
-                    let shared_state    = self.open_file_store.open_shared(path.as_path());
-                    let instance_state  = shared_state.new_instance();
-                    let new_controller  = Chooser::Controller::open(instance_state);
-                    let new_controller  = Arc::new(new_controller);
+                    // Get the index of the folder being opened
+                    let (_, folder_index)   = action.split_at("OpenFolder-".len());
+                    let folder_index        = usize::from_str_radix(folder_index, 10).unwrap();
 
-                    // Set as the main controller
-                    *self.model.shared_state.lock().unwrap() = Some(shared_state);
-                    self.model.open_file.set(Some(path));
-                    self.model.active_controller.set(Some(new_controller));
+                    if let Some(folder) = self.model.folder_list.get().get(folder_index) {
+                        self.open_folder(Some(folder.id));
+                    }
+
+                } else if action.starts_with("EditFolderName-") {
+
+                    // Get the index of the folder being edited
+                    let (_, folder_index)   = action.split_at("EditFolderName-".len());
+                    let folder_index        = usize::from_str_radix(folder_index, 10).unwrap();
+
+                    if let Some(folder) = self.model.folder_list.get().get(folder_index) {
+                        self.stop_editing_filename();
+
+                        self.model.edited_folder_name.set(folder.name.get());
+                        self.model.editing_folder_index.set(Some(folder_index));
+                    }
+
+                } else if action.starts_with("FilterTag-") {
+
+                    // Get the index of the tag to filter by
+                    let (_, tag_index)  = action.split_at("FilterTag-".len());
+                    let tag_index       = usize::from_str_radix(tag_index, 10).unwrap();
+
+                    if let Some(tag) = self.model.all_tags.get().get(tag_index) {
+                        // Only the files with this tag will be displayed, so the indexes of any selected or edited files will change
+                        self.stop_editing_filename();
+                        self.clear_selection();
+                        self.model.tag_filter.set(Some(tag.clone()));
+                    }
 
                 } else if action.starts_with("SetSelect-") {
 
@@ -649,8 +1113,15 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                         ActionParameter::Drag(DragAction::Finish, _, _) => {
                             let drag_after_index = self.model.drag_after_index.get();
 
-                            // Move the file if there's a drag index
-                            if let Some(drag_after_index) = drag_after_index {
+                            if let Some(folder_tile) = self.model.drag_into_folder.get() {
+                                // Move the file into the folder it was dropped on
+                                let src_path = self.model.file_list.get()[file_index].path.get();
+
+                                if let Some(target_folder) = self.folder_tile_targets().get(folder_tile) {
+                                    self.file_manager.move_path_to_folder(src_path.as_path(), *target_folder);
+                                }
+                            } else if let Some(drag_after_index) = drag_after_index {
+                                // Move the file if there's a drag index
                                 let files       = self.model.file_list.get();
                                 let src_path    = files[file_index].path.get();
 
@@ -667,11 +1138,13 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                             // Clear the drag operation
                             self.model.dragging_file.set(None);
                             self.model.drag_after_index.set(None);
+                            self.model.drag_into_folder.set(None);
                         },
 
                         ActionParameter::Drag(DragAction::Cancel, _, _) => {
                             self.model.dragging_file.set(None);
                             self.model.drag_after_index.set(None);
+                            self.model.drag_into_folder.set(None);
                         },
 
                         ActionParameter::Drag(DragAction::Drag, (from_x, from_y), (to_x, to_y)) => {
@@ -684,6 +1157,31 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                             let origin_col  = (file_index % (NUM_COLUMNS as usize)) as i64;
                             let origin_row  = (file_index / (NUM_COLUMNS as usize)) as i64;
 
+                            // Files dragged above the first row are over the folder tiles
+                            let folder_targets  = self.folder_tile_targets();
+                            let grid_x          = (origin_col as f32)*FILE_WIDTH + to_x;
+                            let grid_y          = (origin_row as f32)*FILE_HEIGHT + to_y;
+
+                            if grid_y < 0.0 && folder_targets.len() > 0 {
+                                let folder_y    = grid_y + Self::folder_grid_height(folder_targets.len());
+                                let folder_col  = (grid_x / FOLDER_WIDTH).floor() as i64;
+                                let folder_row  = (folder_y / FOLDER_HEIGHT).floor() as i64;
+                                let folder_idx  = folder_col + folder_row*(FOLDER_COLUMNS as i64);
+
+                                self.model.drag_after_index.set(None);
+
+                                if folder_y >= 0.0 && folder_col >= 0 && folder_col < (FOLDER_COLUMNS as i64) && folder_idx < (folder_targets.len() as i64) {
+                                    // Dropping the file will move it into this folder
+                                    self.model.drag_into_folder.set(Some(folder_idx as usize));
+                                } else {
+                                    self.model.drag_into_folder.set(None);
+                                }
+
+                                return;
+                            }
+
+                            self.model.drag_into_folder.set(None);
+
                             let offset_x    = to_x-(FILE_WIDTH/2.0);
                             let offset_y    = to_y;
 
@@ -717,4 +1215,9 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
     fn get_image_resources(&self) -> Option<Arc<ResourceManager<Image>>> {
         None
     }
+
+    /// Retrieves a resource manager containing the canvases used in the UI for this controller
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        Some(Arc::clone(&self.model.thumbnail_canvases))
+    }
 }
@@ -1,13 +1,17 @@
 use super::file_chooser::*;
 use super::file_controller::*;
+use super::super::file_folder::*;
 use super::super::file_update::*;
 use super::super::file_manager::*;
 
+use flo_ui::*;
+use flo_canvas::*;
 use flo_binding::*;
 
 use std::sync::*;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::collections::{BTreeSet};
 
 ///
 /// Model for a file chooser file
@@ -20,13 +24,43 @@ pub struct FileUiModel {
     /// The name of this file
     pub name: Binding<String>,
 
+    /// The tags assigned to this file
+    pub tags: Binding<Vec<String>>,
+
+    /// The folder containing this file (None if it's at the top level)
+    pub folder: Binding<Option<FolderId>>,
+
+    /// The canvas containing the preview of this file (None if the thumbnail hasn't been rendered yet)
+    pub thumbnail: Binding<Option<Resource<BindingCanvas>>>,
+
     /// Whether or not this file is selected
     pub selected: Binding<bool>
 }
 
 impl PartialEq for FileUiModel {
     fn eq(&self, other: &FileUiModel) -> bool {
-        self.path.get() == other.path.get() && self.name.get() == other.name.get()
+        self.path.get() == other.path.get() && self.name.get() == other.name.get() && self.tags.get() == other.tags.get() && self.folder.get() == other.folder.get()
+    }
+}
+
+///
+/// Model for a file chooser folder
+///
+#[derive(Clone)]
+pub struct FolderUiModel {
+    /// The ID of this folder
+    pub id: FolderId,
+
+    /// The folder containing this folder (None if it's at the top level)
+    pub parent: Binding<Option<FolderId>>,
+
+    /// The name of this folder
+    pub name: Binding<String>
+}
+
+impl PartialEq for FolderUiModel {
+    fn eq(&self, other: &FolderUiModel) -> bool {
+        self.id == other.id && self.parent.get() == other.parent.get() && self.name.get() == other.name.get()
     }
 }
 
@@ -43,9 +77,39 @@ pub struct FileChooserModel<Chooser: FileChooser> {
     /// The path of the currently open file
     pub open_file: Binding<Option<PathBuf>>,
 
-    /// The list of files to choose from
+    /// The canvases used to display the file thumbnails
+    pub thumbnail_canvases: Arc<ResourceManager<BindingCanvas>>,
+
+    /// All of the files known to the file manager
+    pub all_files: BindRef<Arc<Vec<FileUiModel>>>,
+
+    /// The list of files to choose from (the files in the current folder matching the tag filter)
     pub file_list: BindRef<Arc<Vec<FileUiModel>>>,
 
+    /// The folder whose contents are being displayed (None for the top level)
+    pub current_folder: Binding<Option<FolderId>>,
+
+    /// All of the folders known to the file manager
+    pub all_folders: BindRef<Arc<Vec<FolderUiModel>>>,
+
+    /// The folders in the current folder
+    pub folder_list: BindRef<Arc<Vec<FolderUiModel>>>,
+
+    /// The index in the folder list of the folder whose name is being edited
+    pub editing_folder_index: Binding<Option<usize>>,
+
+    /// The current name of the folder after editing
+    pub edited_folder_name: Binding<String>,
+
+    /// The index of the folder tile that the dragged file will be moved into if it is dropped here
+    pub drag_into_folder: Binding<Option<usize>>,
+
+    /// The tag that files must have to be displayed in the file list (or None to display all files)
+    pub tag_filter: Binding<Option<String>>,
+
+    /// The tags that have been assigned to at least one file, in alphabetical order
+    pub all_tags: BindRef<Vec<String>>,
+
     /// The index of the file whose name is being edited
     pub editing_filename_index: Binding<Option<usize>>,
 
@@ -68,7 +132,10 @@ pub struct FileChooserModel<Chooser: FileChooser> {
     pub selected_file_count: BindRef<usize>,
 
     /// True if we're confirming a deletion request
-    pub confirming_deletion: Binding<bool>
+    pub confirming_deletion: Binding<bool>,
+
+    /// The path of the file that was not closed cleanly, if we're asking the user whether or not to recover it
+    pub recovering_file: Binding<Option<PathBuf>>
 }
 
 impl<Chooser: 'static+FileChooser> FileChooserModel<Chooser> {
@@ -81,7 +148,16 @@ impl<Chooser: 'static+FileChooser> FileChooserModel<Chooser> {
         let active_controller   = bind(None);
 
         // Create the actual file list model
-        let file_list           = Self::file_list(chooser.get_file_manager());
+        let thumbnail_canvases  = Arc::new(ResourceManager::new());
+        let all_files           = Self::file_list(chooser.get_file_manager(), Arc::clone(&thumbnail_canvases));
+        let tag_filter          = bind(None);
+        let current_folder      = bind(None);
+        let file_list           = Self::filtered_file_list(all_files.clone(), BindRef::from(tag_filter.clone()), BindRef::from(current_folder.clone()));
+        let all_tags            = Self::all_tags(all_files.clone());
+
+        // ... and the folders
+        let all_folders         = Self::folder_list(chooser.get_file_manager());
+        let folder_list         = Self::folders_in_current_folder(all_folders.clone(), BindRef::from(current_folder.clone()));
 
         // ... and the value indicating if any file is selected
         let selected_file_count = Self::selected_file_count(file_list.clone());
@@ -96,36 +172,92 @@ impl<Chooser: 'static+FileChooser> FileChooserModel<Chooser> {
             edited_filename:        bind(String::from("")),
             dragging_offset:        bind((0.0, 0.0)),
             open_file:              open_file,
+            thumbnail_canvases:     thumbnail_canvases,
+            all_files:              all_files,
             file_list:              file_list,
+            tag_filter:             tag_filter,
+            all_tags:               all_tags,
+            current_folder:         current_folder,
+            all_folders:            all_folders,
+            folder_list:            folder_list,
+            editing_folder_index:   bind(None),
+            edited_folder_name:     bind(String::from("")),
+            drag_into_folder:       bind(None),
             file_range:             bind(0..0),
             selected_file_count:    selected_file_count,
-            confirming_deletion:    bind(false)
+            confirming_deletion:    bind(false),
+            recovering_file:        bind(None)
         }
     }
 
     ///
     /// Creates the file model for a particular path
     ///
-    fn model_for_path(file_manager: &Arc<Chooser::FileManager>, path: &Path) -> FileUiModel {
-        let name = file_manager.display_name_for_path(path).unwrap_or("Untitled".to_string());
+    fn model_for_path(file_manager: &Arc<Chooser::FileManager>, thumbnail_canvases: &Arc<ResourceManager<BindingCanvas>>, path: &Path) -> FileUiModel {
+        let name        = file_manager.display_name_for_path(path).unwrap_or_else(|| localized("Untitled"));
+        let tags        = file_manager.tags_for_path(path);
+        let folder      = file_manager.folder_for_path(path);
+        let thumbnail   = file_manager.cached_thumbnail(path).map(|drawing| Self::thumbnail_canvas(thumbnail_canvases, drawing));
 
         FileUiModel {
             path:       bind(PathBuf::from(path)),
             name:       bind(name),
+            tags:       bind(tags),
+            folder:     bind(folder),
+            thumbnail:  bind(thumbnail),
             selected:   bind(false)
         }
     }
 
+    ///
+    /// Creates a canvas resource displaying a thumbnail
+    ///
+    pub fn thumbnail_canvas(thumbnail_canvases: &Arc<ResourceManager<BindingCanvas>>, drawing: Vec<Draw>) -> Resource<BindingCanvas> {
+        let canvas = BindingCanvas::new();
+
+        canvas.draw(move |gc| {
+            gc.clear_canvas();
+            gc.draw_list(Box::new(drawing.into_iter()));
+        });
+
+        thumbnail_canvases.register(canvas)
+    }
+
+    ///
+    /// Creates the folder model for a particular folder
+    ///
+    fn model_for_folder(file_manager: &Arc<Chooser::FileManager>, folder: FolderId, parent: Option<FolderId>) -> FolderUiModel {
+        let name = file_manager.folder_name(folder).unwrap_or_else(|| localized("Untitled"));
+
+        FolderUiModel {
+            id:         folder,
+            parent:     bind(parent),
+            name:       bind(name)
+        }
+    }
+
+    ///
+    /// Adds the models for the folders inside a folder (and their subfolders) to a list
+    ///
+    fn append_folder_models(file_manager: &Arc<Chooser::FileManager>, parent: Option<FolderId>, folders: &mut Vec<FolderUiModel>) {
+        for entry in file_manager.folder_contents(parent) {
+            if let FolderEntry::Folder(folder) = entry {
+                folders.push(Self::model_for_folder(file_manager, folder, parent));
+                Self::append_folder_models(file_manager, Some(folder), folders);
+            }
+        }
+    }
+
     ///
     /// Creates the file list binding from a file manager
     ///
-    fn file_list(file_manager: Arc<Chooser::FileManager>) -> BindRef<Arc<Vec<FileUiModel>>> {
+    fn file_list(file_manager: Arc<Chooser::FileManager>, thumbnail_canvases: Arc<ResourceManager<BindingCanvas>>) -> BindRef<Arc<Vec<FileUiModel>>> {
         // Get all of the files from the file manager
         let files = file_manager.get_all_files();
 
         // Create the file models from the paths
         let files: Vec<_> = files.into_iter()
-            .map(|path| Self::model_for_path(&file_manager, path.as_path()))
+            .map(|path| Self::model_for_path(&file_manager, &thumbnail_canvases, path.as_path()))
             .collect();
         let files = Arc::new(files);
 
@@ -136,7 +268,7 @@ impl<Chooser: 'static+FileChooser> FileChooserModel<Chooser> {
 
             match update {
                 FileUpdate::NewFile(path) => {
-                    files.insert(0, Self::model_for_path(&file_manager, path.as_path()))
+                    files.insert(0, Self::model_for_path(&file_manager, &thumbnail_canvases, path.as_path()))
                 },
 
                 FileUpdate::RemovedFile(path) => {
@@ -151,6 +283,14 @@ impl<Chooser: 'static+FileChooser> FileChooserModel<Chooser> {
                     })
                 },
 
+                FileUpdate::SetTags(path, new_tags) => {
+                    files.iter_mut().for_each(|model| {
+                        if model.path.get() == path {
+                            model.tags.set(new_tags.clone());
+                        }
+                    })
+                },
+
                 FileUpdate::ChangedOrder(path, after) => {
                     // Find the file being moved
                     let file_idx = files.iter().enumerate()
@@ -169,14 +309,32 @@ impl<Chooser: 'static+FileChooser> FileChooserModel<Chooser> {
                             .nth(0));
 
                         if let Some(after_idx) = after_idx {
-                            // Move after the 'after' file
+                            // Move after the 'after' file (and into the same folder)
+                            moving_file.folder.set(files[after_idx].folder.get());
                             files.insert(after_idx+1, moving_file);
                         } else {
                             // Move to the beginning
                             files.insert(0, moving_file);
                         }
                     }
-                }
+                },
+
+                FileUpdate::MovedToFolder(path, folder) => {
+                    // Files moved to a folder become the first file in that folder
+                    let file_idx = files.iter().enumerate()
+                        .filter(|(_idx, model)| model.path.get() == path)
+                        .map(|(idx, _model)| idx)
+                        .nth(0);
+
+                    if let Some(file_idx) = file_idx {
+                        let moving_file = files.remove(file_idx);
+                        moving_file.folder.set(folder);
+                        files.insert(0, moving_file);
+                    }
+                },
+
+                FileUpdate::NewFolder(_, _, _)      |
+                FileUpdate::SetFolderName(_, _)     => { }
             }
 
             Arc::new(files)
@@ -186,6 +344,95 @@ impl<Chooser: 'static+FileChooser> FileChooserModel<Chooser> {
         BindRef::from(files)
     }
 
+    ///
+    /// Creates the folder list binding from a file manager
+    ///
+    fn folder_list(file_manager: Arc<Chooser::FileManager>) -> BindRef<Arc<Vec<FolderUiModel>>> {
+        // Get all of the folders from the file manager
+        let mut folders = vec![];
+        Self::append_folder_models(&file_manager, None, &mut folders);
+        let folders     = Arc::new(folders);
+
+        // Bind to updates from the file manager
+        let updates = file_manager.update_stream();
+        let folders = bind_stream(updates, folders, move |folders, update| {
+            match update {
+                FileUpdate::NewFolder(folder, parent, name) => {
+                    // New folders are displayed first
+                    let mut folders = (*folders).clone();
+                    folders.insert(0, FolderUiModel { id: folder, parent: bind(parent), name: bind(name) });
+
+                    Arc::new(folders)
+                },
+
+                FileUpdate::SetFolderName(folder, new_name) => {
+                    folders.iter().for_each(|model| {
+                        if model.id == folder {
+                            model.name.set(new_name.clone());
+                        }
+                    });
+
+                    folders
+                },
+
+                _ => folders
+            }
+        });
+
+        BindRef::from(folders)
+    }
+
+    ///
+    /// Creates the list of files that are in the current folder and match the tag filter
+    ///
+    fn filtered_file_list(all_files: BindRef<Arc<Vec<FileUiModel>>>, tag_filter: BindRef<Option<String>>, current_folder: BindRef<Option<FolderId>>) -> BindRef<Arc<Vec<FileUiModel>>> {
+        let file_list = computed(move || {
+            let all_files       = all_files.get();
+            let current_folder  = current_folder.get();
+            let tag_filter      = tag_filter.get();
+
+            Arc::new(all_files.iter()
+                .filter(|file| file.folder.get() == current_folder)
+                .filter(|file| tag_filter.as_ref().map(|tag| file.tags.get().contains(tag)).unwrap_or(true))
+                .cloned()
+                .collect())
+        });
+
+        BindRef::from(file_list)
+    }
+
+    ///
+    /// Creates the list of folders that are in the current folder
+    ///
+    fn folders_in_current_folder(all_folders: BindRef<Arc<Vec<FolderUiModel>>>, current_folder: BindRef<Option<FolderId>>) -> BindRef<Arc<Vec<FolderUiModel>>> {
+        let folder_list = computed(move || {
+            let all_folders     = all_folders.get();
+            let current_folder  = current_folder.get();
+
+            Arc::new(all_folders.iter()
+                .filter(|folder| folder.parent.get() == current_folder)
+                .cloned()
+                .collect())
+        });
+
+        BindRef::from(folder_list)
+    }
+
+    ///
+    /// Creates a binding containing all of the tags assigned to the files in a file list
+    ///
+    fn all_tags(all_files: BindRef<Arc<Vec<FileUiModel>>>) -> BindRef<Vec<String>> {
+        let all_tags = computed(move || {
+            let all_tags = all_files.get().iter()
+                .flat_map(|file| file.tags.get())
+                .collect::<BTreeSet<_>>();
+
+            all_tags.into_iter().collect::<Vec<_>>()
+        });
+
+        BindRef::from(all_tags)
+    }
+
     ///
     /// Returns a binding that sets itself to true if any file is selected in the file list
     ///
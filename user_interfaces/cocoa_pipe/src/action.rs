@@ -117,6 +117,12 @@ pub enum ViewAction {
     /// Sets the background colour of this view
     SetBackgroundColor(Color),
 
+    /// Sets the width and colour of the border around this view
+    SetBorder(f64, Color),
+
+    /// Sets the radius of the corners of this view
+    SetCornerRadius(f64),
+
     /// Sets the text to display in a control
     SetText(AppProperty),
 
@@ -144,6 +150,15 @@ pub enum ViewAction {
     /// Specifies the padding around the view
     SetPadding(f64, f64, f64, f64),
 
+    /// Sets how the view lays out its subviews, and how it is placed by its container
+    SetLayout(ControlLayout, LayoutItem),
+
+    /// Animates changes to the layout of this view
+    AddTransition(Transition),
+
+    /// This view is replacing the view with the specified ID, so its transitions start from where that view was
+    ReplacesView(usize),
+
     /// Sets the ID for this view
     SetId(String),
 
@@ -154,7 +169,22 @@ pub enum ViewAction {
     SetState(ViewStateUpdate),
 
     /// Performs an action that relates to a pop-up view
-    Popup(ViewPopupAction)
+    Popup(ViewPopupAction),
+
+    /// Performs an action that relates to a window view
+    Window(ViewWindowAction),
+
+    /// Updates how the items in a list are displayed (or how this view is displayed as an item)
+    SetItems(ViewItemsUpdate),
+
+    /// Sets the menu bar to display while this view's window is active
+    SetMenuBar(Vec<AppMenuItem>),
+
+    /// Sets the menu to display when this view is right-clicked
+    SetContextMenu(Vec<AppMenuItem>),
+
+    /// Updates the tooltip or accessibility information for this view
+    SetAccessibility(ViewAccessibilityUpdate)
 }
 
 ///
@@ -187,6 +217,66 @@ pub enum ViewStateUpdate {
     AddClass(String)
 }
 
+///
+/// Actions that update how a combo box, list view or tree view displays its items, or how a view is displayed as an item
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum ViewItemsUpdate {
+    /// How many items can be selected at once
+    SelectionMode(SelectionMode),
+
+    /// The height of each row in a list
+    RowHeight(f64),
+
+    /// Property indicating the total number of items in a list
+    Count(AppProperty),
+
+    /// Property indicating the index of the item represented by the first subview
+    FirstIndex(AppProperty),
+
+    /// How deeply an item is nested in a tree
+    Level(f64),
+
+    /// Property indicating whether or not an item in a tree is expanded
+    Expanded(AppProperty)
+}
+
+///
+/// Actions that update how a view is described to the user and to assistive technologies
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum ViewAccessibilityUpdate {
+    /// Property indicating the tooltip to display when the pointer hovers over this view
+    Tooltip(AppProperty),
+
+    /// Property indicating the name of this view, as read by a screen reader
+    Label(AppProperty),
+
+    /// Property indicating a longer description of what this view does
+    Description(AppProperty),
+
+    /// The role of this view
+    Role(AccessibilityRole),
+
+    /// The position of this view in the keyboard focus order amongst its siblings
+    FocusOrder(f64)
+}
+
+///
+/// Represents an item in a menu
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum AppMenuItem {
+    /// A command with an ID, a label, a shortcut description, an enabled property and a checked property (for checkable items)
+    Command(String, AppProperty, Option<String>, AppProperty, Option<AppProperty>),
+
+    /// A line separating groups of items
+    Separator,
+
+    /// A submenu with a label and a list of items
+    SubMenu(AppProperty, Vec<AppMenuItem>)
+}
+
 ///
 /// Actions relating to a pop-up view
 ///
@@ -205,6 +295,24 @@ pub enum ViewPopupAction {
     SetOffset(f64)
 }
 
+///
+/// Actions relating to a window view
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum ViewWindowAction {
+    /// Updates the property that specifies whether or not this window is on screen
+    Open(AppProperty),
+
+    /// Sets the title of the window
+    SetTitle(AppProperty),
+
+    /// Sets how the window is presented relative to the main window
+    SetKind(WindowKind),
+
+    /// Sets the initial size of the content of the window in pixels
+    SetSize(f64, f64)
+}
+
 #[derive(Clone, Copy, PartialEq, Debug, FromPrimitive, ToPrimitive)]
 pub enum AppPaintDevice {
     MouseLeft       = 0,
@@ -261,7 +369,16 @@ pub enum ViewEvent {
     SetValue,
 
     /// Event sent when some EditValues were sent but the editing was cancelled
-    CancelEdit
+    CancelEdit,
+
+    /// Event sent when the user changes which items are selected in a list
+    SelectItems,
+
+    /// Event sent when the user expands or collapses an item in a tree
+    ExpandItem,
+
+    /// Event sent when the user picks a command from one of the menus attached to this view
+    SelectMenuItem
 }
 
 ///
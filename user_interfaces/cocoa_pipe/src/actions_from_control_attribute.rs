@@ -34,9 +34,13 @@ impl ActionsFrom<ViewAction> for ControlAttribute {
             FontAttr(font_attr)                     => font_attr.actions_from(bind_property),
             StateAttr(state_attr)                   => state_attr.actions_from(bind_property),
             PopupAttr(popup_attr)                   => popup_attr.actions_from(bind_property),
+            WindowAttr(window_attr)                 => window_attr.actions_from(bind_property),
             AppearanceAttr(appearance_attr)         => appearance_attr.actions_from(bind_property),
             ScrollAttr(scroll_attr)                 => scroll_attr.actions_from(bind_property),
             HintAttr(hint_attr)                     => hint_attr.actions_from(bind_property),
+            ItemsAttr(items_attr)                   => items_attr.actions_from(bind_property),
+            MenuAttr(menu_attr)                     => menu_attr.actions_from(bind_property),
+            AccessibilityAttr(accessibility_attr)   => accessibility_attr.actions_from(bind_property),
 
             BoundingBox(bounds)                     => vec![ViewAction::SetBounds(make_app_bounds(bounds, bind_property))],
             ZIndex(z_index)                         => vec![ViewAction::SetZIndex(*z_index as f64)],
@@ -47,7 +51,10 @@ impl ActionsFrom<ViewAction> for ControlAttribute {
             Action(trigger, name)                   => event_actions(trigger, name),
             Canvas(canvas_resource)                 => vec![ViewAction::Draw(canvas_resource.get_drawing())],
 
-            SubComponents(_components)              => vec![]               // Handled separately by ViewState
+            SubComponents(_components)              => vec![],              // Handled separately by ViewState
+
+            LayoutAttr(_layout)                     => vec![],              // Handled separately by ViewState
+            TransitionAttr(transition)              => vec![ViewAction::AddTransition(*transition)]
         }
     }
 }
@@ -80,6 +87,9 @@ fn event_actions(trigger: &ActionTrigger, name: &String) -> Vec<ViewAction> {
         SetValue                        => vec![ViewAction::RequestEvent(ViewEvent::SetValue, name.clone())],
         CancelEdit                      => vec![ViewAction::RequestEvent(ViewEvent::CancelEdit, name.clone())],
         VirtualScroll(width, height)    => vec![ViewAction::RequestEvent(ViewEvent::VirtualScroll(*width as f64, *height as f64), name.clone())],
+        SelectItems                     => vec![ViewAction::RequestEvent(ViewEvent::SelectItems, name.clone())],
+        ExpandItem                      => vec![ViewAction::RequestEvent(ViewEvent::ExpandItem, name.clone())],
+        SelectMenuItem                  => vec![ViewAction::RequestEvent(ViewEvent::SelectMenuItem, name.clone())],
     }
 }
 
@@ -123,6 +133,19 @@ impl ActionsFrom<ViewAction> for Popup {
     }
 }
 
+impl ActionsFrom<ViewAction> for Window {
+    fn actions_from<BindProperty: FnMut(Property) -> AppProperty>(&self, bind_property: &mut BindProperty) -> Vec<ViewAction> {
+        use self::Window::*;
+
+        match self {
+            IsOpen(property)        => vec![ViewAction::Window(ViewWindowAction::Open(bind_property(property.clone())))],
+            Title(title)            => vec![ViewAction::Window(ViewWindowAction::SetTitle(bind_property(title.clone())))],
+            Kind(kind)              => vec![ViewAction::Window(ViewWindowAction::SetKind(*kind))],
+            Size(width, height)     => vec![ViewAction::Window(ViewWindowAction::SetSize(*width as f64, *height as f64))]
+        }
+    }
+}
+
 impl ActionsFrom<ViewAction> for Appearance {
     fn actions_from<BindProperty: FnMut(Property) -> AppProperty>(&self, _bind_property: &mut BindProperty) -> Vec<ViewAction> {
         use self::Appearance::*;
@@ -148,6 +171,57 @@ impl ActionsFrom<ViewAction> for Scroll {
     }
 }
 
+impl ActionsFrom<ViewAction> for Items {
+    fn actions_from<BindProperty: FnMut(Property) -> AppProperty>(&self, bind_property: &mut BindProperty) -> Vec<ViewAction> {
+        use self::Items::*;
+
+        match self {
+            Selection(mode)         => vec![ViewAction::SetItems(ViewItemsUpdate::SelectionMode(*mode))],
+            RowHeight(height)       => vec![ViewAction::SetItems(ViewItemsUpdate::RowHeight(*height as f64))],
+            Count(property)         => vec![ViewAction::SetItems(ViewItemsUpdate::Count(bind_property(property.clone())))],
+            FirstIndex(property)    => vec![ViewAction::SetItems(ViewItemsUpdate::FirstIndex(bind_property(property.clone())))],
+            Level(level)            => vec![ViewAction::SetItems(ViewItemsUpdate::Level(*level as f64))],
+            Expanded(property)      => vec![ViewAction::SetItems(ViewItemsUpdate::Expanded(bind_property(property.clone())))]
+        }
+    }
+}
+
+///
+/// Converts a list of menu items to app menu items, binding their properties
+///
+fn make_app_menu_items<BindProperty: FnMut(Property) -> AppProperty>(items: &Vec<MenuItem>, bind_property: &mut BindProperty) -> Vec<AppMenuItem> {
+    items.iter()
+        .map(|item| match item {
+            MenuItem::Command(command)          => AppMenuItem::Command(command.id.clone(), bind_property(command.label.clone()), command.shortcut.clone(), bind_property(command.enabled.clone()), command.checked.clone().map(|checked| bind_property(checked))),
+            MenuItem::Separator                 => AppMenuItem::Separator,
+            MenuItem::SubMenu(label, sub_items) => AppMenuItem::SubMenu(bind_property(label.clone()), make_app_menu_items(sub_items, bind_property))
+        })
+        .collect()
+}
+
+impl ActionsFrom<ViewAction> for Menu {
+    fn actions_from<BindProperty: FnMut(Property) -> AppProperty>(&self, bind_property: &mut BindProperty) -> Vec<ViewAction> {
+        match self {
+            Menu::Bar(items)        => vec![ViewAction::SetMenuBar(make_app_menu_items(items, bind_property))],
+            Menu::Context(items)    => vec![ViewAction::SetContextMenu(make_app_menu_items(items, bind_property))]
+        }
+    }
+}
+
+impl ActionsFrom<ViewAction> for Accessibility {
+    fn actions_from<BindProperty: FnMut(Property) -> AppProperty>(&self, bind_property: &mut BindProperty) -> Vec<ViewAction> {
+        use self::Accessibility::*;
+
+        match self {
+            Tooltip(tooltip)            => vec![ViewAction::SetAccessibility(ViewAccessibilityUpdate::Tooltip(bind_property(tooltip.clone())))],
+            Label(label)                => vec![ViewAction::SetAccessibility(ViewAccessibilityUpdate::Label(bind_property(label.clone())))],
+            Description(description)    => vec![ViewAction::SetAccessibility(ViewAccessibilityUpdate::Description(bind_property(description.clone())))],
+            Role(role)                  => vec![ViewAction::SetAccessibility(ViewAccessibilityUpdate::Role(*role))],
+            FocusOrder(order)           => vec![ViewAction::SetAccessibility(ViewAccessibilityUpdate::FocusOrder(*order as f64))]
+        }
+    }
+}
+
 impl ActionsFrom<ViewAction> for Hint {
     fn actions_from<BindProperty: FnMut(Property) -> AppProperty>(&self, _bind_property: &mut BindProperty) -> Vec<ViewAction> {
         use self::Hint::*;
@@ -0,0 +1,19 @@
+use super::action::*;
+use super::actions_from::*;
+
+use flo_ui::*;
+
+impl ActionsFrom<ViewAction> for StyleProperty {
+    fn actions_from<BindProperty: FnMut(Property) -> AppProperty>(&self, _bind_property: &mut BindProperty) -> Vec<ViewAction> {
+        use self::StyleProperty::*;
+
+        match self {
+            Foreground(color)       => vec![ViewAction::SetForegroundColor(*color)],
+            Background(color)       => vec![ViewAction::SetBackgroundColor(*color)],
+            FontSize(size)          => vec![ViewAction::SetFontSize(*size as f64)],
+            FontWeight(weight)      => vec![ViewAction::SetFontWeight(*weight as u32 as f64)],
+            Border(width, color)    => vec![ViewAction::SetBorder(*width as f64, *color)],
+            CornerRadius(radius)    => vec![ViewAction::SetCornerRadius(*radius as f64)]
+        }
+    }
+}
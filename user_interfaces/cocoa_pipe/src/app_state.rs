@@ -16,6 +16,9 @@ pub struct AppState {
     /// The root view
     root_view: Option<ViewState>,
 
+    /// The theme used to style the views
+    theme: Option<Theme>,
+
     /// The ID that will be assigned to the next view we create
     next_view_id: usize,
 
@@ -58,6 +61,7 @@ impl AppState {
     pub fn new() -> AppState {
         AppState {
             root_view:                  None,
+            theme:                      None,
             view_models:                HashMap::new(),
             canvas_models:              HashMap::new(),
             view_model_properties:      HashMap::new(),
@@ -80,6 +84,7 @@ impl AppState {
             UpdateUi(differences)       => { self.update_ui(differences) }
             UpdateCanvas(differences)   => { self.update_canvas(differences) }
             UpdateViewModel(updates)    => { self.update_viewmodel(updates) }
+            UpdateTheme(theme)          => { self.update_theme(theme) }
         }
     }
 
@@ -100,6 +105,9 @@ impl AppState {
             EditValue(view_id, name, _action, property)         => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Value(property))],
 
             VirtualScroll(view_id, name, top_left, size)        => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::VirtualScroll(top_left, size))],
+            SelectItems(view_id, name, selection)               => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Selection(selection))],
+            ExpandItem(view_id, name, index, expanded)          => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Expanded(index, expanded))],
+            SelectMenuItem(view_id, name, command_id)           => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::MenuItem(command_id))],
 
             Drag(view_id, name, DragAction::Start, from, to)    => {
                 self.activate_view(view_id);
@@ -130,6 +138,25 @@ impl AppState {
         ]
     }
 
+    ///
+    /// Applies a new theme to all of the existing views
+    ///
+    /// Views are only updated with the properties set by the new theme, so themes are expected to set the
+    /// same properties for the same controls (as the standard light and dark themes do)
+    ///
+    fn update_theme(&mut self, theme: Theme) -> Vec<AppAction> {
+        let mut actions         = vec![];
+        let mut views_to_style  = self.root_view.iter().collect::<Vec<_>>();
+
+        while let Some(view) = views_to_style.pop() {
+            actions.extend(view.theme_actions(&theme));
+            views_to_style.extend(view.subviews());
+        }
+
+        self.theme = Some(theme);
+        actions
+    }
+
     ///
     /// Marks a particular view ID as being active
     ///
@@ -214,6 +241,25 @@ impl AppState {
         address_for_view.remove(&view_state.id());
     }
 
+    ///
+    /// Returns the actions that tell the views with transitions in a new view tree which view they're replacing in the old tree
+    ///
+    fn replacement_actions(old_view: &ViewState, new_view: &ViewState, new_ui: &Control) -> Vec<AppAction> {
+        let mut actions = vec![];
+
+        if new_ui.attributes().any(|attr| attr.transition().is_some()) {
+            actions.push(AppAction::View(new_view.id(), ViewAction::ReplacesView(old_view.id())));
+        }
+
+        // Subviews are matched up by their index
+        let no_subcomponents = vec![];
+        for ((old_subview, new_subview), subview_ui) in old_view.subviews().zip(new_view.subviews()).zip(new_ui.subcomponents().unwrap_or(&no_subcomponents).iter()) {
+            actions.extend(Self::replacement_actions(old_subview, new_subview, subview_ui));
+        }
+
+        actions
+    }
+
     ///
     /// Returns the actions required to perform a single UI diff
     ///
@@ -232,6 +278,9 @@ impl AppState {
         let address_for_view    = &mut self.address_for_view;
         let view_to_replace     = root_view.as_ref().and_then(|root_view| root_view.get_state_at_address(&difference.address));
 
+        // Any transitions in the new view start from where the existing view was (this must be done before it's removed)
+        actions.extend(view_to_replace.map(|view_to_replace| Self::replacement_actions(view_to_replace, &view_state, &difference.new_ui)).unwrap_or(vec![]));
+
         // Generate the actions to remove the existing view
         actions.extend(view_to_replace.map(|view_to_replace| view_to_replace.destroy_subtree_actions()).unwrap_or(vec![]));
 
@@ -323,8 +372,13 @@ impl AppState {
             property
         });
 
+        // Style the view using the theme
+        view_state.set_styled_control(control);
+        let theme_actions           = self.theme.as_ref().map(|theme| view_state.theme_actions(theme)).unwrap_or(vec![]);
+
         // Property setup actions need to occur before all the other actions associated with this control's setup
         property_actions.extend(setup_actions);
+        property_actions.extend(theme_actions);
         let mut setup_actions = property_actions;
 
         // Work out the controller path for the subcomponents. If the view state has a controller, then add it to the existing path, otherwise keep the existing path
@@ -62,6 +62,15 @@ pub enum AppEvent {
     /// The scrolling region has changed
     VirtualScroll(usize, String, (u32, u32), (u32, u32)),
 
+    /// The user has changed which items are selected in a list
+    SelectItems(usize, String, Vec<u32>),
+
+    /// The user has expanded or collapsed an item in a tree
+    ExpandItem(usize, String, u32, bool),
+
+    /// The user has picked the command with the specified ID from a menu
+    SelectMenuItem(usize, String, String),
+
     /// Indicates that a point has been dragged to another location
     Drag(usize, String, DragAction, (f64, f64), (f64, f64)),
 
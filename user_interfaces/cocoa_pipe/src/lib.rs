@@ -16,6 +16,7 @@ mod view_state;
 mod canvas_model;
 mod actions_from;
 mod actions_from_control_attribute;
+mod actions_from_style_property;
 
 pub use self::action::*;
 pub use self::event::*;
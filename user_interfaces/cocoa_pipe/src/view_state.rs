@@ -20,7 +20,10 @@ pub struct ViewState {
     child_views: Vec<ViewState>,
 
     /// The name of the canvas this view is using, if it has one
-    canvas_name: Option<String>
+    canvas_name: Option<String>,
+
+    /// The control this view was set up from, without its subcomponents (used to apply the theme to the view)
+    styled_control: Control
 }
 
 impl ViewState {
@@ -32,7 +35,8 @@ impl ViewState {
             view_id:            view_id,
             subview_controller: None,
             child_views:        vec![],
-            canvas_name:        None
+            canvas_name:        None,
+            styled_control:     Control::empty()
         }
     }
 
@@ -50,6 +54,33 @@ impl ViewState {
         self.canvas_name.as_ref()
     }
 
+    ///
+    /// Stores the control that this view was set up from, so that it can be styled by a theme
+    ///
+    pub fn set_styled_control(&mut self, control: &Control) {
+        let mut styled_control = Control::new(control.control_type());
+
+        control.attributes()
+            .filter(|attribute| attribute.subcomponents().is_none())
+            .for_each(|attribute| styled_control.add_attribute(attribute.clone()));
+
+        self.styled_control = styled_control;
+    }
+
+    ///
+    /// Returns the actions required to style this view using a theme (not including its subviews)
+    ///
+    pub fn theme_actions(&self, theme: &Theme) -> Vec<AppAction> {
+        // Style properties never refer to the viewmodel
+        let mut no_binding = |_property: Property| AppProperty::Nothing;
+
+        theme.properties_for_control(&self.styled_control)
+            .into_iter()
+            .flat_map(|property| property.actions_from(&mut no_binding))
+            .map(|view_action| AppAction::View(self.view_id, view_action))
+            .collect()
+    }
+
     ///
     /// Retrieves the child state at the specified address
     ///
@@ -255,6 +286,9 @@ impl ViewState {
             .map(|view_action| AppAction::View(self.view_id, view_action));
         set_up_steps.extend(view_set_up);
 
+        // The subviews are placed by flo_ui's layout, so the view needs to know how it's laid out
+        set_up_steps.push(AppAction::View(self.view_id, ViewAction::SetLayout(ControlLayout::for_control(control), LayoutItem::from_control(control))));
+
         // Perform drawing/etc actions after the size has been set up
         let canvas_set_up = control.attributes()
             .filter(|attribute| if let ControlAttribute::Canvas(_) = attribute { true } else { false })
@@ -30,7 +30,19 @@ pub enum ViewType {
     Scrolling,
 
     /// A view that displays a popup window
-    Popup
+    Popup,
+
+    /// A view that lets the user pick one of several options from a drop-down
+    ComboBox,
+
+    /// A view that displays a scrolling list of items
+    ListView,
+
+    /// A view that displays a scrolling hierarchy of items
+    TreeView,
+
+    /// A view that displays its content in a separate window
+    Window
 }
 
 impl From<&Control> for ViewType {
@@ -55,7 +67,11 @@ impl From<ControlType> for ViewType {
             Slider                  => ViewType::Slider,
             Rotor                   => ViewType::Rotor,
             TextBox                 => ViewType::TextBox,
-            CheckBox                => ViewType::CheckBox
+            CheckBox                => ViewType::CheckBox,
+            ComboBox                => ViewType::ComboBox,
+            ListView                => ViewType::ListView,
+            TreeView                => ViewType::TreeView,
+            Window                  => ViewType::Window
         }
     }
 }
@@ -54,7 +54,10 @@ pub struct FloEvents {
     queued_update: bool,
 
     /// The events that are waiting to be sent
-    pending_events: Vec<AppEvent>
+    pending_events: Vec<AppEvent>,
+
+    /// How the view lays out its subviews, and how it's placed by its container
+    layout: Option<(ControlLayout, LayoutItem)>
 }
 
 impl FloEvents {
@@ -67,7 +70,8 @@ impl FloEvents {
             session_id:         session_id,
             events_publisher:   Desync::new(publisher),
             queued_update:      false,
-            pending_events:     vec![]
+            pending_events:     vec![],
+            layout:             None
         }
     }
 
@@ -106,6 +110,20 @@ impl FloEvents {
         let events = FloEvents::init(publisher, session_id, view_id);
         Self::object_from_events(Arc::new(Mutex::new(events)))
     }
+
+    ///
+    /// Sets the layout for the view that a FloEvents object is for
+    ///
+    pub fn set_layout(events: &StrongPtr, layout: ControlLayout, item: LayoutItem) {
+        unsafe {
+            let events_id   = (***events).get_ivar::<usize>("_eventsId");
+            let flo_events  = FLO_EVENTS_STORE.lock().unwrap().get(events_id).cloned();
+
+            if let Some(flo_events) = flo_events {
+                flo_events.lock().unwrap().layout = Some((layout, item));
+            }
+        }
+    }
 }
 
 ///
@@ -168,6 +186,13 @@ pub fn declare_flo_events_class() -> &'static Class {
             flo_events.map(|flo_events| flo_events.lock().unwrap().view_id)
         }
 
+        // Retrieves the layout for an object
+        unsafe fn get_layout(this: &mut Object) -> Option<(ControlLayout, LayoutItem)> {
+            let events_id   = (*this).get_ivar::<usize>("_eventsId");
+            let flo_events  = FLO_EVENTS_STORE.lock().unwrap().get(events_id).cloned();
+            flo_events.and_then(|flo_events| flo_events.lock().unwrap().layout.clone())
+        }
+
         // Retrieves the view ID for an object
         unsafe fn get_session_id(this: &mut Object) -> Option<usize> {
             let events_id   = (*this).get_ivar::<usize>("_eventsId");
@@ -265,6 +290,50 @@ pub fn declare_flo_events_class() -> &'static Class {
             }
         }
 
+        // Sends the 'select items' event (the indexes are an NSArray of NSNumbers)
+        extern fn send_select_items(this: &mut Object, _sel: Sel, name: *mut Object, indexes: *mut Object) {
+            unsafe {
+                let view_id = get_view_id(this);
+                let name    = name_for_name(&mut *name);
+
+                let count: usize    = msg_send!(indexes, count);
+                let selection       = (0..count).map(|index| {
+                    let number: *mut Object = msg_send!(indexes, objectAtIndex: index);
+                    let value: u32          = msg_send!(number, unsignedIntValue);
+                    value
+                }).collect();
+
+                if let Some(view_id) = view_id {
+                    send_event(this, AppEvent::SelectItems(view_id, name, selection));
+                }
+            }
+        }
+
+        // Sends the 'expand item' event
+        extern fn send_expand_item(this: &mut Object, _sel: Sel, name: *mut Object, index: u32, expanded: bool) {
+            unsafe {
+                let view_id = get_view_id(this);
+                let name    = name_for_name(&mut *name);
+
+                if let Some(view_id) = view_id {
+                    send_event(this, AppEvent::ExpandItem(view_id, name, index, expanded));
+                }
+            }
+        }
+
+        // Sends the 'select menu item' event
+        extern fn send_select_menu_item(this: &mut Object, _sel: Sel, name: *mut Object, command_id: *mut Object) {
+            unsafe {
+                let view_id     = get_view_id(this);
+                let name        = name_for_name(&mut *name);
+                let command_id  = name_for_name(&mut *command_id);
+
+                if let Some(view_id) = view_id {
+                    send_event(this, AppEvent::SelectMenuItem(view_id, name, command_id));
+                }
+            }
+        }
+
         // Sends the 'drag' event
         extern fn send_drag(this: &mut Object, _sel: Sel, name: *mut Object, drag_action: u32, from_x: f64, from_y: f64, to_x: f64, to_y: f64) {
             unsafe {
@@ -350,6 +419,31 @@ pub fn declare_flo_events_class() -> &'static Class {
             }
         }
 
+        // Works out where the subviews of a view go in an area of a particular size (the subviews are an NSArray of their FloEvents objects, and the result is an NSArray of NSValue rects)
+        extern fn frames_for_subviews(this: &mut Object, _sel: Sel, subviews: *mut Object, size: CGSize) -> *mut Object {
+            unsafe {
+                let layout          = get_layout(this).map(|(layout, _item)| layout).unwrap_or_else(|| ControlLayout::new(&[]));
+
+                let count: usize    = msg_send!(subviews, count);
+                let items: Vec<_>   = (0..count).map(|index| {
+                    let subview: *mut Object = msg_send!(subviews, objectAtIndex: index);
+                    get_layout(&mut *subview)
+                        .map(|(_layout, item)| item)
+                        .unwrap_or_else(|| LayoutItem::new(Bounds::fill_all(), vec![], (0.0, 0.0)))
+                }).collect();
+
+                let frames: *mut Object = msg_send!(class!(NSMutableArray), array);
+
+                for ((x1, y1), (x2, y2)) in layout.lay_out(&items, size.width as f32, size.height as f32) {
+                    let frame               = CGRect { origin: CGPoint { x: x1 as CGFloat, y: y1 as CGFloat }, size: CGSize { width: (x2-x1) as CGFloat, height: (y2-y1) as CGFloat } };
+                    let frame: *mut Object  = msg_send!(class!(NSValue), valueWithRect: frame);
+                    let _: () = msg_send!(frames, addObject: frame);
+                }
+
+                frames
+            }
+        }
+
         // Clears the list of pending events
         extern fn finish_sending_events(this: &mut Object, _sel: Sel) {
             unsafe {
@@ -394,12 +488,16 @@ pub fn declare_flo_events_class() -> &'static Class {
         flo_events.add_method(sel!(sendChangeValue:isSet:withDouble:), send_change_value_double as extern fn(&mut Object, Sel, *mut Object, bool, f64));
         flo_events.add_method(sel!(sendChangeValue:isSet:withString:), send_change_value_string as extern fn(&mut Object, Sel, *mut Object, bool, *mut Object));
         flo_events.add_method(sel!(sendVirtualScroll:left:top:width:height:), send_virtual_scroll as extern fn(&mut Object, Sel, *mut Object, u32, u32, u32, u32));
+        flo_events.add_method(sel!(sendSelectItems:withIndexes:), send_select_items as extern fn(&mut Object, Sel, *mut Object, *mut Object));
+        flo_events.add_method(sel!(sendExpandItem:index:expanded:), send_expand_item as extern fn(&mut Object, Sel, *mut Object, u32, bool));
+        flo_events.add_method(sel!(sendSelectMenuItem:withId:), send_select_menu_item as extern fn(&mut Object, Sel, *mut Object, *mut Object));
         flo_events.add_method(sel!(sendDrag:dragAction:fromX:fromY:toX:toY:), send_drag as extern fn(&mut Object, Sel, *mut Object, u32, f64, f64, f64, f64));
         flo_events.add_method(sel!(sendPaintStartForDevice:name:action:), send_paint_start as extern fn(&mut Object, Sel, u32, *mut Object, AppPainting));
         flo_events.add_method(sel!(sendPaintContinueForDevice:name:action:), send_paint_continue as extern fn(&mut Object, Sel, u32, *mut Object, AppPainting));
         flo_events.add_method(sel!(sendPaintFinishForDevice:name:action:), send_paint_finish as extern fn(&mut Object, Sel, u32, *mut Object, AppPainting));
         flo_events.add_method(sel!(sendPaintCancelForDevice:name:action:), send_paint_cancel as extern fn(&mut Object, Sel, u32, *mut Object, AppPainting));
         flo_events.add_method(sel!(redrawCanvasWithSize:viewport:), redraw_canvas as extern fn(&mut Object, Sel, CGSize, CGRect));
+        flo_events.add_method(sel!(framesForSubviews:withSize:), frames_for_subviews as extern fn(&mut Object, Sel, *mut Object, CGSize) -> *mut Object);
     }
 
     // Finalize the class
@@ -258,6 +258,10 @@ impl CocoaSession {
                     CheckBox        => { msg_send!(*view_class, createAsCheckBox) }
                     Scrolling       => { msg_send!(*view_class, createAsScrolling) }
                     Popup           => { msg_send!(*view_class, createAsPopup) }
+                    ComboBox        => { msg_send!(*view_class, createAsComboBox) }
+                    ListView        => { msg_send!(*view_class, createAsListView) }
+                    TreeView        => { msg_send!(*view_class, createAsTreeView) }
+                    Window          => { msg_send!(*view_class, createAsWindow) }
                 };
 
                 let view = StrongPtr::retain(view);
@@ -317,9 +321,14 @@ impl CocoaSession {
                     InsertSubView(view_id, index)           => { self.views.get(&view_id).cloned().map(|subview| { let _: () = msg_send!((**view), viewInsertSubView: *subview atIndex: index as u32); }); }
                     SetBounds(bounds)                       => { self.set_bounds(view, bounds); }
                     SetPadding(left, top, right, bottom)    => { self.set_padding(view, left, top, right, bottom); }
+                    SetLayout(layout, item)                 => { self.set_layout(view_id, layout, item); }
+                    AddTransition(transition)               => { self.add_transition(view, transition); }
+                    ReplacesView(old_view_id)               => { self.views.get(&old_view_id).cloned().map(|old_view| { let _: () = msg_send!((**view), viewReplacesView: *old_view); }); }
                     SetZIndex(z_index)                      => { let _: () = msg_send!(**view, viewSetZIndex: z_index); }
                     SetForegroundColor(col)                 => { let (r, g, b, a) = col.to_rgba_components(); let _: () = msg_send!(**view, viewSetForegroundRed: r as f64 green: g as f64 blue: b as f64 alpha: a as f64); }
                     SetBackgroundColor(col)                 => { let (r, g, b, a) = col.to_rgba_components(); let _: () = msg_send!(**view, viewSetBackgroundRed: r as f64 green: g as f64 blue: b as f64 alpha: a as f64); }
+                    SetBorder(width, col)                   => { let (r, g, b, a) = col.to_rgba_components(); let _: () = msg_send!(**view, viewSetBorderWidth: width red: r as f64 green: g as f64 blue: b as f64 alpha: a as f64); }
+                    SetCornerRadius(radius)                 => { let _: () = msg_send!(**view, viewSetCornerRadius: radius); }
 
                     SetId(_id)                              => { /* TODO? */ }
                     SetText(property)                       => { let _: () = msg_send!(**view, viewSetText: *self.flo_property(property)); }
@@ -331,6 +340,11 @@ impl CocoaSession {
                     SetState(view_state)                    => { self.set_view_state(view, view_state); },
 
                     Popup(action)                           => { self.pop_up_action(view, action); }
+                    Window(action)                          => { self.window_action(view, action); }
+                    SetItems(items)                         => { self.set_view_items(view, items); }
+                    SetMenuBar(items)                       => { let _: () = msg_send!(**view, viewBeginMenuBar); self.add_menu_items(view, items); let _: () = msg_send!(**view, viewEndMenu); }
+                    SetContextMenu(items)                   => { let _: () = msg_send!(**view, viewBeginContextMenu); self.add_menu_items(view, items); let _: () = msg_send!(**view, viewEndMenu); }
+                    SetAccessibility(accessibility)         => { self.set_view_accessibility(view, accessibility); }
 
                     SetScrollMinimumSize(width, height)     => { let _: () = msg_send!(**view, viewSetScrollMinimumSizeWithWidth: width height: height); }
                     SetHorizontalScrollBar(visibility)      => { let _: () = msg_send!(**view, viewSetHorizontalScrollVisibility: Self::scroll_visibility_value(visibility)); },
@@ -361,6 +375,32 @@ impl CocoaSession {
         }
     }
 
+    ///
+    /// Sends a window action to a view
+    ///
+    fn window_action(&self, view: &StrongPtr, action: ViewWindowAction) {
+        use self::ViewWindowAction::*;
+
+        unsafe {
+            match action {
+                Open(property)          => { let _: () = msg_send!(**view, viewSetWindowOpen: *self.flo_property(property)); },
+                SetTitle(property)      => { let _: () = msg_send!(**view, viewSetWindowTitle: *self.flo_property(property)); },
+                SetKind(kind)           => { let _: () = msg_send!(**view, viewSetWindowKind: Self::window_kind_value(kind)); },
+                SetSize(width, height)  => { let _: () = msg_send!(**view, viewSetWindowSizeWithWidth: width height: height); }
+            }
+        }
+    }
+
+    ///
+    /// Converts a window kind to an integer value
+    ///
+    fn window_kind_value(kind: WindowKind) -> u32 {
+        match kind {
+            WindowKind::Normal  => 0,
+            WindowKind::Utility => 1
+        }
+    }
+
     ///
     /// Converts a popup direction to an integer value
     ///
@@ -398,6 +438,109 @@ impl CocoaSession {
         }
     }
 
+    ///
+    /// Updates how a view displays its items
+    ///
+    fn set_view_items(&self, view: &StrongPtr, items: ViewItemsUpdate) {
+        use self::ViewItemsUpdate::*;
+
+        unsafe {
+            match items {
+                SelectionMode(mode)         => { let _: () = msg_send!(**view, viewSetSelectionMode: self.id_for_selection_mode(mode)); },
+                RowHeight(height)           => { let _: () = msg_send!(**view, viewSetRowHeight: height); },
+                Count(property)             => { let _: () = msg_send!(**view, viewSetItemCount: *self.flo_property(property)); },
+                FirstIndex(property)        => { let _: () = msg_send!(**view, viewSetFirstItemIndex: *self.flo_property(property)); },
+                Level(level)                => { let _: () = msg_send!(**view, viewSetItemLevel: level); },
+                Expanded(property)          => { let _: () = msg_send!(**view, viewSetItemExpanded: *self.flo_property(property)); }
+            }
+        }
+    }
+
+    ///
+    /// Updates the tooltip or accessibility information for a view
+    ///
+    fn set_view_accessibility(&self, view: &StrongPtr, accessibility: ViewAccessibilityUpdate) {
+        use self::ViewAccessibilityUpdate::*;
+
+        unsafe {
+            match accessibility {
+                Tooltip(property)           => { let _: () = msg_send!(**view, viewSetTooltip: *self.flo_property(property)); },
+                Label(property)             => { let _: () = msg_send!(**view, viewSetAccessibilityLabel: *self.flo_property(property)); },
+                Description(property)       => { let _: () = msg_send!(**view, viewSetAccessibilityHelp: *self.flo_property(property)); },
+                Role(role)                  => { let _: () = msg_send!(**view, viewSetAccessibilityRole: self.id_for_accessibility_role(role)); },
+                FocusOrder(order)           => { let _: () = msg_send!(**view, viewSetFocusOrder: order); }
+            }
+        }
+    }
+
+    ///
+    /// Adds some items to the menu that's being built for a view
+    ///
+    fn add_menu_items(&self, view: &StrongPtr, items: Vec<AppMenuItem>) {
+        unsafe {
+            for item in items {
+                match item {
+                    AppMenuItem::Command(command_id, label, shortcut, enabled, checked) => {
+                        let command_id  = StrongPtr::new(NSString::alloc(nil).init_str(&command_id));
+                        let shortcut    = StrongPtr::new(NSString::alloc(nil).init_str(&shortcut.unwrap_or_else(|| String::new())));
+                        let label       = self.flo_property(label);
+                        let enabled     = self.flo_property(enabled);
+                        let checked     = checked.map(|checked| self.flo_property(checked));
+                        let checked: id = checked.as_ref().map(|checked| **checked).unwrap_or(nil);
+
+                        let _: () = msg_send!(**view, viewAddMenuCommand: *command_id label: *label shortcut: *shortcut enabled: *enabled checked: checked);
+                    },
+
+                    AppMenuItem::Separator                      => {
+                        let _: () = msg_send!(**view, viewAddMenuSeparator);
+                    },
+
+                    AppMenuItem::SubMenu(label, sub_items)      => {
+                        let _: () = msg_send!(**view, viewBeginSubMenu: *self.flo_property(label));
+                        self.add_menu_items(view, sub_items);
+                        let _: () = msg_send!(**view, viewEndSubMenu);
+                    }
+                }
+            }
+        }
+    }
+
+    ///
+    /// Converts a selection mode to an ID to pass to the Swift side
+    ///
+    fn id_for_selection_mode(&self, mode: SelectionMode) -> u32 {
+        match mode {
+            SelectionMode::None     => 0,
+            SelectionMode::Single   => 1,
+            SelectionMode::Multiple => 2
+        }
+    }
+
+    ///
+    /// Converts an accessibility role to an ID to pass to the Swift side
+    ///
+    fn id_for_accessibility_role(&self, role: AccessibilityRole) -> u32 {
+        use self::AccessibilityRole::*;
+
+        match role {
+            Button          => 0,
+            ToggleButton    => 1,
+            CheckBox        => 2,
+            Slider          => 3,
+            TextBox         => 4,
+            Label           => 5,
+            Image           => 6,
+            Canvas          => 7,
+            ComboBox        => 8,
+            List            => 9,
+            ListItem        => 10,
+            Tree            => 11,
+            TreeItem        => 12,
+            Toolbar         => 13,
+            Group           => 14
+        }
+    }
+
     ///
     /// Converts a scroll axis to an ID to pass to the Swift side
     ///
@@ -527,6 +670,9 @@ impl CocoaSession {
                     EditValue                       => { let _: () = msg_send!(**view, requestEditValue: *flo_events withName: *name); }
                     SetValue                        => { let _: () = msg_send!(**view, requestSetValue: *flo_events withName: *name); }
                     CancelEdit                      => { let _: () = msg_send!(**view, requestCancelEdit: *flo_events withName: *name); }
+                    SelectItems                     => { let _: () = msg_send!(**view, requestSelectItems: *flo_events withName: *name); }
+                    ExpandItem                      => { let _: () = msg_send!(**view, requestExpandItem: *flo_events withName: *name); }
+                    SelectMenuItem                  => { let _: () = msg_send!(**view, requestSelectMenuItem: *flo_events withName: *name); }
                 }
             }
         }
@@ -617,6 +763,49 @@ impl CocoaSession {
         }
     }
 
+    ///
+    /// Converts a transition easing function to an integer value
+    ///
+    fn transition_easing_value(easing: TransitionEasing) -> u32 {
+        use self::TransitionEasing::*;
+
+        match easing {
+            Linear      => 0,
+            EaseIn      => 1,
+            EaseOut     => 2,
+            EaseInOut   => 3
+        }
+    }
+
+    ///
+    /// Sends a transition attribute to a view
+    ///
+    fn add_transition(&self, view: &StrongPtr, transition: Transition) {
+        use self::Transition::*;
+
+        let kind = match transition {
+            Position(_, _)  => 0,
+            Size(_, _)      => 1,
+            Opacity(_, _)   => 2
+        };
+        let duration    = transition.duration() as f64;
+        let easing      = Self::transition_easing_value(transition.easing());
+
+        unsafe { let _: () = msg_send!(**view, viewAddTransition: kind as u32 duration: duration easing: easing); }
+    }
+
+    ///
+    /// Stores how a view is laid out and passes its events object on so it can ask where its subviews go
+    ///
+    fn set_layout(&mut self, view_id: usize, layout: ControlLayout, item: LayoutItem) {
+        let events = self.events_for_view(view_id);
+        FloEvents::set_layout(&events, layout, item);
+
+        if let Some(view) = self.views.get(&view_id) {
+            unsafe { let _: () = msg_send!(**view, viewSetLayout: *events); }
+        }
+    }
+
     ///
     /// Sets a request to set the position of a side of a view
     ///
@@ -49,6 +49,9 @@ pub enum GtkWidgetType {
     Scale,
     ScrollArea,
     Popover,
+    Window,
+    ComboBox,
+    ListView,
 
     Overlay,
 
@@ -99,6 +102,18 @@ pub enum GtkWidgetAction {
     /// Controls the popup attributes of this widget
     Popup(WidgetPopup),
 
+    /// Controls the window attributes of this widget
+    Window(WidgetWindow),
+
+    /// Updates how this widget displays its items (or how it's displayed as an item)
+    Items(WidgetItems),
+
+    /// Updates the menus attached to this widget
+    Menu(WidgetMenu),
+
+    /// Updates how this widget is described to the user and to assistive technologies
+    Accessibility(WidgetAccessibility),
+
     /// Deletes this widget (and any child widgets it may contain)
     Delete
 }
@@ -145,7 +160,19 @@ pub enum WidgetLayout {
     ZIndex(u32),
 
     /// Specifies the padding for this widget
-    Padding((u32, u32), (u32, u32))
+    Padding((u32, u32), (u32, u32)),
+
+    /// Specifies how this widget and its children are laid out
+    Layout(ui::Layout),
+
+    /// This widget is displayed outside of its parent (eg, in a window of its own) so it takes up no space there
+    Detached,
+
+    /// Specifies how changes to the layout of this widget are animated
+    Transition(ui::Transition),
+
+    /// This widget is replacing the specified widget (so any transitions start from where that widget was laid out)
+    Replaces(WidgetId)
 }
 
 impl From<WidgetLayout> for GtkWidgetAction {
@@ -217,6 +244,114 @@ impl From<WidgetPopup> for GtkWidgetAction {
     }
 }
 
+///
+/// Actions for widgets that display their content in a separate window
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum WidgetWindow {
+    /// Sets the title of this window
+    SetTitle(String),
+
+    /// Sets how this window is presented
+    SetKind(ui::WindowKind),
+
+    /// Sets the initial size of the content of this window
+    SetSize(u32, u32),
+
+    /// Sets whether or not this window is open
+    SetOpen(bool)
+}
+
+impl From<WidgetWindow> for GtkWidgetAction {
+    fn from (item: WidgetWindow) -> GtkWidgetAction {
+        GtkWidgetAction::Window(item)
+    }
+}
+
+///
+/// Actions for widgets that display a set of items, and for the items themselves
+///
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum WidgetItems {
+    /// Sets how many items can be selected at once
+    SetSelectionMode(ui::SelectionMode),
+
+    /// Sets the height of each row
+    SetRowHeight(f64),
+
+    /// Sets the total number of items in a virtualised list
+    SetCount(u32),
+
+    /// Sets the index of the first item in the list of children
+    SetFirstIndex(u32),
+
+    /// Sets the indentation level of an item
+    SetLevel(u32),
+
+    /// Sets whether or not an item is expanded (items that are never set as expanded or collapsed have no children)
+    SetExpanded(bool)
+}
+
+impl From<WidgetItems> for GtkWidgetAction {
+    fn from(item: WidgetItems) -> GtkWidgetAction {
+        GtkWidgetAction::Items(item)
+    }
+}
+
+///
+/// Actions for the menus attached to a widget
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum WidgetMenu {
+    /// Sets the items in the menu bar of the window containing this widget
+    SetMenuBar(Vec<ui::MenuItem>),
+
+    /// Sets the items in the menu displayed when this widget is right-clicked
+    SetContextMenu(Vec<ui::MenuItem>),
+
+    /// Sets the label of the command with the specified ID
+    SetCommandLabel(String, String),
+
+    /// Sets whether or not the command with the specified ID can be picked
+    SetCommandEnabled(String, bool),
+
+    /// Sets whether or not the command with the specified ID is checked
+    SetCommandChecked(String, bool)
+}
+
+impl From<WidgetMenu> for GtkWidgetAction {
+    fn from(item: WidgetMenu) -> GtkWidgetAction {
+        GtkWidgetAction::Menu(item)
+    }
+}
+
+///
+/// Actions that describe a widget to the user and to assistive technologies
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum WidgetAccessibility {
+    /// Sets the tooltip for this widget (an empty string removes the tooltip)
+    SetTooltip(String),
+
+    /// Sets the name reported for this widget
+    SetLabel(String),
+
+    /// Sets the description reported for this widget
+    SetDescription(String),
+
+    /// Sets the role reported for this widget
+    SetRole(ui::AccessibilityRole),
+
+    /// Sets the position of this widget in its parent's keyboard focus order
+    SetFocusOrder(u32)
+}
+
+impl From<WidgetAccessibility> for GtkWidgetAction {
+    fn from(item: WidgetAccessibility) -> GtkWidgetAction {
+        GtkWidgetAction::Accessibility(item)
+    }
+}
+
 impl From<ui::Font> for GtkWidgetAction {
     fn from(item: ui::Font) -> GtkWidgetAction {
         GtkWidgetAction::Font(item)
@@ -247,7 +382,10 @@ pub enum GtkAction {
     Window(WindowId, Vec<GtkWindowAction>),
 
     /// Performs some actions on a widget
-    Widget(WidgetId, Vec<GtkWidgetAction>)
+    Widget(WidgetId, Vec<GtkWidgetAction>),
+
+    /// Replaces the style sheet used to theme every widget
+    SetStyleSheet(String)
 }
 
 impl GtkAction {
@@ -15,6 +15,9 @@ pub enum GtkEvent {
     /// A window was closed by the user
     CloseWindow(WindowId),
 
+    /// A window was resized (new width and height)
+    ResizeWindow(WindowId, i32, i32),
+
     /// Registered event has occurred on a widget
     Event(WidgetId, String, GtkEventParameter)
 }
@@ -46,7 +46,19 @@ pub enum GtkEventParameter {
     DragFinish((f64, f64), (f64, f64)),
 
     /// Virtual scroll region has moved (tuples are the x and y coordinates and the width and height of the grid)
-    VirtualScroll((u32, u32), (u32, u32))
+    VirtualScroll((u32, u32), (u32, u32)),
+
+    /// The user picked the item with the specified index
+    SelectedIndex(u32),
+
+    /// The set of selected items has changed
+    SelectedItems(Vec<u32>),
+
+    /// The item with the specified index has been expanded or collapsed
+    ItemExpanded(u32, bool),
+
+    /// The menu command with the specified ID was picked
+    MenuItem(String)
 }
 
 ///
@@ -74,7 +86,11 @@ impl From<GtkEventParameter> for ActionParameter {
             GtkEventParameter::DragStart(x, y)                              => ActionParameter::Drag(DragAction::Start, (x as f32, y as f32), (x as f32, y as f32)),
             GtkEventParameter::DragContinue((from_x, from_y), (to_x, to_y)) => ActionParameter::Drag(DragAction::Drag, (from_x as f32, from_y as f32), (to_x as f32, to_y as f32)),
             GtkEventParameter::DragFinish((from_x, from_y), (to_x, to_y))   => ActionParameter::Drag(DragAction::Finish, (from_x as f32, from_y as f32), (to_x as f32, to_y as f32)),
-            GtkEventParameter::VirtualScroll(top_left, size)                => ActionParameter::VirtualScroll(top_left, size)
+            GtkEventParameter::VirtualScroll(top_left, size)                => ActionParameter::VirtualScroll(top_left, size),
+            GtkEventParameter::SelectedIndex(index)                         => ActionParameter::Value(PropertyValue::Int(index as i32)),
+            GtkEventParameter::SelectedItems(items)                         => ActionParameter::Selection(items),
+            GtkEventParameter::ItemExpanded(index, expanded)                => ActionParameter::Expanded(index, expanded),
+            GtkEventParameter::MenuItem(command_id)                         => ActionParameter::MenuItem(command_id)
         }
     }
 }
@@ -7,6 +7,8 @@ use super::super::widgets::*;
 use flo_stream::*;
 
 use gtk;
+use gtk::CssProviderExt;
+use gdk;
 use glib;
 use futures::prelude::*;
 use futures::stream::{BoxStream};
@@ -55,7 +57,10 @@ pub struct FloGtk {
     event_sink: GtkEventSink,
 
     /// The style provider for the widgets and windows created for this Gtk instance
-    style_provider: gtk::CssProvider
+    style_provider: gtk::CssProvider,
+
+    /// The style provider for the theme, once it has been added to the screen
+    theme_provider: Option<gtk::CssProvider>
 }
 
 impl MessageQueue {
@@ -158,7 +163,8 @@ impl FloGtk {
             windows:            HashMap::new(),
             widget_data:        Rc::new(WidgetData::new()),
             event_sink:         event_sink,
-            style_provider:     gtk::CssProvider::new()
+            style_provider:     gtk::CssProvider::new(),
+            theme_provider:     None
         }
     }
 
@@ -185,6 +191,25 @@ impl FloGtk {
         &mut self.style_provider
     }
 
+    ///
+    /// Replaces the style sheet used to theme every widget on the screen
+    ///
+    pub fn set_style_sheet(&mut self, style_sheet: &str) {
+        // The theme provider is created the first time a style sheet is set (the screen isn't available until GTK is running)
+        let theme_provider = self.theme_provider.get_or_insert_with(|| {
+            let theme_provider = gtk::CssProvider::new();
+
+            if let Some(screen) = gdk::Screen::get_default() {
+                // Custom styles on individual widgets use the application priority, so the theme goes just below them
+                gtk::StyleContext::add_provider_for_screen(&screen, &theme_provider, gtk::STYLE_PROVIDER_PRIORITY_APPLICATION-1);
+            }
+
+            theme_provider
+        });
+
+        theme_provider.load_from_data(style_sheet.as_bytes()).ok();
+    }
+
     ///
     /// Associates a window with an ID
     ///
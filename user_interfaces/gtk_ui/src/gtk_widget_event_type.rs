@@ -66,7 +66,16 @@ pub enum GtkWidgetEventType {
     VirtualScroll(f32, f32),
 
     /// User has interacted outside of this widget
-    Dismiss
+    Dismiss,
+
+    /// User has changed which items are selected
+    SelectItems,
+
+    /// User has expanded or collapsed an item
+    ExpandItem,
+
+    /// User has picked a command from a menu
+    SelectMenuItem
 }
 
 impl From<PaintDevice> for GtkPaintDevice {
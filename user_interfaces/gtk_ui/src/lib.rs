@@ -8,12 +8,14 @@
 
 extern crate flo_ui;
 extern crate flo_canvas;
+extern crate flo_binding;
 
 extern crate gtk;
 extern crate gdk;
 extern crate gdk_sys;
 extern crate gio;
 extern crate gdk_pixbuf;
+extern crate atk;
 extern crate cairo;
 extern crate glib;
 extern crate anymap;
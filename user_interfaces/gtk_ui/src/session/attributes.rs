@@ -95,6 +95,9 @@ fn needs_event_box(control: &Control) -> bool {
             &Action(ActionTrigger::Click, _)            => true,
             &Action(ActionTrigger::Drag, _)             => true,
 
+            // Controls with a context menu need an event box to receive right-clicks
+            &MenuAttr(Menu::Context(_))                 => true,
+
             // Other controls do not need an event box
             _                                           => false
         }
@@ -104,6 +107,32 @@ fn needs_event_box(control: &Control) -> bool {
 ///
 /// Creates the actions required to instantiate a control - without its subcomponents
 ///
+///
+/// Returns the style class used for the GTK widgets created for a particular type of control
+///
+pub fn widget_class_for_control_type(control_type: ControlType) -> &'static str {
+    use self::ControlType::*;
+
+    match control_type {
+        Empty               => "flo-empty",
+        Container           => "flo-container",
+        CroppingContainer   => "flo-cropping-container",
+        ScrollingContainer  => "flo-scrolling-container",
+        Popup               => "flo-popup",
+        Button              => "flo-button",
+        Label               => "flo-label",
+        Canvas              => "flo-canvas",
+        Slider              => "flo-slider",
+        Rotor               => "flo-rotor",
+        TextBox             => "flo-textbox",
+        CheckBox            => "flo-checkbox",
+        ComboBox            => "flo-combobox",
+        ListView            => "flo-listview",
+        TreeView            => "flo-treeview",
+        Window              => "flo-window"
+    }
+}
+
 impl ToGtkActions for Control {
     fn to_gtk_actions(&self) -> Vec<PropertyWidgetAction> {
         use self::ControlType::*;
@@ -122,24 +151,15 @@ impl ToGtkActions for Control {
             Slider              => New(GtkWidgetType::Scale),
             Rotor               => New(GtkWidgetType::Rotor),
             TextBox             => New(GtkWidgetType::TextBox),
-            CheckBox            => New(GtkWidgetType::CheckBox)
+            CheckBox            => New(GtkWidgetType::CheckBox),
+            ComboBox            => New(GtkWidgetType::ComboBox),
+            ListView            => New(GtkWidgetType::ListView),
+            TreeView            => New(GtkWidgetType::ListView),
+            Window              => New(GtkWidgetType::Window)
         };
 
         // The widget class allows the style sheet to specifically target Flo widgets
-        let widget_class = match self.control_type() {
-            Empty               => "flo-empty",
-            Container           => "flo-container",
-            CroppingContainer   => "flo-cropping-container",
-            ScrollingContainer  => "flo-scrolling-container",
-            Popup               => "flo-popup",
-            Button              => "flo-button",
-            Label               => "flo-label",
-            Canvas              => "flo-canvas",
-            Slider              => "flo-slider",
-            Rotor               => "flo-rotor",
-            TextBox             => "flo-textbox",
-            CheckBox            => "flo-checkbox"
-        };
+        let widget_class = widget_class_for_control_type(self.control_type());
 
         // Build into the 'create control' action
         let mut create_control = vec![
@@ -151,6 +171,11 @@ impl ToGtkActions for Control {
             create_control.push(GtkWidgetAction::IntoEventBox.into());
         }
 
+        // Windows take up no space in their parent
+        if self.control_type() == ControlType::Window {
+            create_control.push(GtkWidgetAction::from(WidgetLayout::Detached).into());
+        }
+
         // Controls have their own class for styling and are displayed by default
         create_control.extend(vec![
             GtkWidgetAction::Content(WidgetContent::AddClass(widget_class.to_string())).into(),
@@ -175,15 +200,21 @@ impl ToGtkActions for ControlAttribute {
             &BoundingBox(ref bounds)                => bounds.to_gtk_actions(),
             &ZIndex(zindex)                         => vec![ WidgetLayout::ZIndex(zindex).into() ].into_actions(),
             &Padding((left, top), (right, bottom))  => vec![ WidgetLayout::Padding((left, top), (right, bottom)).into() ].into_actions(),
+            &LayoutAttr(ref layout)                 => vec![ WidgetLayout::Layout(layout.clone()).into() ].into_actions(),
+            &TransitionAttr(ref transition)         => vec![ WidgetLayout::Transition(transition.clone()).into() ].into_actions(),
 
             &Text(ref text)                         => vec![ PropertyAction::from_property(text.clone(), |text| vec![ WidgetContent::SetText(text.to_string()).into() ]) ],
 
             &FontAttr(ref font)                     => font.to_gtk_actions(),
             &StateAttr(ref state)                   => state.to_gtk_actions(),
             &PopupAttr(ref popup)                   => popup.to_gtk_actions(),
+            &WindowAttr(ref window)                 => window.to_gtk_actions(),
             &AppearanceAttr(ref appearance)         => appearance.to_gtk_actions(),
             &ScrollAttr(ref scroll)                 => scroll.to_gtk_actions(),
             &HintAttr(ref hint)                     => hint.to_gtk_actions(),
+            &ItemsAttr(ref items)                   => items.to_gtk_actions(),
+            &MenuAttr(ref menu)                     => menu.to_gtk_actions(),
+            &AccessibilityAttr(ref accessibility)   => accessibility.to_gtk_actions(),
 
             &Id(ref id)                             => vec![ WidgetContent::AddClass(id.clone()).into() ].into_actions(),
             &Action(ref _trigger, ref _action_name) => vec![],
@@ -211,6 +242,62 @@ impl ToGtkActions for Hint {
     }
 }
 
+impl ToGtkActions for Items {
+    fn to_gtk_actions(&self) -> Vec<PropertyWidgetAction> {
+        use self::Items::*;
+
+        match self {
+            &Selection(mode)            => vec![ WidgetItems::SetSelectionMode(mode).into() ].into_actions(),
+            &RowHeight(height)          => vec![ WidgetItems::SetRowHeight(height as f64).into() ].into_actions(),
+            &Count(ref count)           => vec![ PropertyAction::from_property(count.clone(), |count| vec![ WidgetItems::SetCount(count.to_f64().unwrap_or(0.0) as u32).into() ]) ],
+            &FirstIndex(ref index)      => vec![ PropertyAction::from_property(index.clone(), |index| vec![ WidgetItems::SetFirstIndex(index.to_f64().unwrap_or(0.0) as u32).into() ]) ],
+            &Level(level)               => vec![ WidgetItems::SetLevel(level).into() ].into_actions(),
+            &Expanded(ref expanded)     => vec![ PropertyAction::from_property(expanded.clone(), |expanded| vec![ WidgetItems::SetExpanded(expanded.to_bool().unwrap_or(false)).into() ]) ]
+        }
+    }
+}
+
+impl ToGtkActions for Menu {
+    fn to_gtk_actions(&self) -> Vec<PropertyWidgetAction> {
+        // Create the structure of the menu first
+        let create_menu = match self {
+            Menu::Bar(items)        => WidgetMenu::SetMenuBar(items.clone()),
+            Menu::Context(items)    => WidgetMenu::SetContextMenu(items.clone())
+        };
+        let mut result = vec![ GtkWidgetAction::Menu(create_menu) ].into_actions();
+
+        // The labels and states of the commands can be bound to properties
+        for command in MenuItem::all_commands(self.items()) {
+            let label_id    = command.id.clone();
+            let enabled_id  = command.id.clone();
+
+            result.push(PropertyAction::from_property(command.label.clone(), move |label| vec![ WidgetMenu::SetCommandLabel(label_id.clone(), label.to_string()).into() ]));
+            result.push(PropertyAction::from_property(command.enabled.clone(), move |enabled| vec![ WidgetMenu::SetCommandEnabled(enabled_id.clone(), enabled.to_bool().unwrap_or(false)).into() ]));
+
+            if let Some(ref checked) = command.checked {
+                let checked_id = command.id.clone();
+                result.push(PropertyAction::from_property(checked.clone(), move |checked| vec![ WidgetMenu::SetCommandChecked(checked_id.clone(), checked.to_bool().unwrap_or(false)).into() ]));
+            }
+        }
+
+        result
+    }
+}
+
+impl ToGtkActions for Accessibility {
+    fn to_gtk_actions(&self) -> Vec<PropertyWidgetAction> {
+        use self::Accessibility::*;
+
+        match self {
+            &Tooltip(ref tooltip)           => vec![ PropertyAction::from_property(tooltip.clone(), |tooltip| vec![ WidgetAccessibility::SetTooltip(tooltip.to_string()).into() ]) ],
+            &Label(ref label)               => vec![ PropertyAction::from_property(label.clone(), |label| vec![ WidgetAccessibility::SetLabel(label.to_string()).into() ]) ],
+            &Description(ref description)   => vec![ PropertyAction::from_property(description.clone(), |description| vec![ WidgetAccessibility::SetDescription(description.to_string()).into() ]) ],
+            &Role(role)                     => vec![ WidgetAccessibility::SetRole(role).into() ].into_actions(),
+            &FocusOrder(order)              => vec![ WidgetAccessibility::SetFocusOrder(order).into() ].into_actions()
+        }
+    }
+}
+
 impl ToGtkActions for Bounds {
     fn to_gtk_actions(&self) -> Vec<PropertyWidgetAction> {
         // We always start by setting the bounding box to the active value
@@ -295,6 +382,19 @@ impl ToGtkActions for Popup {
     }
 }
 
+impl ToGtkActions for Window {
+    fn to_gtk_actions(&self) -> Vec<PropertyWidgetAction> {
+        use self::Window::*;
+
+        match self {
+            &Title(ref title)           => vec![ PropertyAction::from_property(title.clone(), |title| vec![ WidgetWindow::SetTitle(title.to_string()).into() ]) ],
+            &IsOpen(ref is_open)        => vec![ PropertyAction::from_property(is_open.clone(), |is_open| vec![ WidgetWindow::SetOpen(is_open.to_bool().unwrap_or(true)).into() ]) ],
+            &Kind(kind)                 => vec![ WidgetWindow::SetKind(kind).into() ].into_actions(),
+            &Size(width, height)        => vec![ WidgetWindow::SetSize(width, height).into() ].into_actions()
+        }
+    }
+}
+
 impl ToGtkActions for Font {
     fn to_gtk_actions(&self) -> Vec<PropertyWidgetAction> {
         vec![ self.clone().into() ].into_actions()
@@ -12,6 +12,7 @@ use super::super::gtk_widget_event_type::*;
 use flo_ui::*;
 use flo_ui::session::*;
 use flo_stream::*;
+use flo_binding::*;
 use ::desync::*;
 
 use gtk;
@@ -42,7 +43,10 @@ struct GtkSessionCore {
     controller_for_widget: HashMap<WidgetId, Rc<Vec<String>>>,
 
     /// Maps canvas names (controller and canvas name) to the widgets that they're being drawn upon
-    widgets_for_canvas: HashMap<(Rc<Vec<String>>, String), HashSet<WidgetId>>
+    widgets_for_canvas: HashMap<(Rc<Vec<String>>, String), HashSet<WidgetId>>,
+
+    /// The size of the main window
+    window_size: Binding<(i32, i32)>
 }
 
 ///
@@ -61,11 +65,19 @@ impl<Ui: CoreUserInterface> GtkSession<Ui> {
     /// Creates a new session connecting a core UI to a Gtk UI
     ///
     pub fn new(core_ui: Ui, gtk_ui: GtkUserInterface) -> GtkSession<Ui> {
+        Self::new_with_window_size(core_ui, gtk_ui, bind((1920, 1080)))
+    }
+
+    ///
+    /// Creates a new session connecting a core UI to a Gtk UI. The main window initially has the size in the
+    /// `window_size` binding, which is updated whenever the user resizes the window.
+    ///
+    pub fn new_with_window_size(core_ui: Ui, gtk_ui: GtkUserInterface, window_size: Binding<(i32, i32)>) -> GtkSession<Ui> {
         // Get the GTK event streams
         let mut gtk_action_sink     = Arc::new(Desync::new(gtk_ui.get_input_sink()));
 
         // Create the main window (always ID 0)
-        Self::create_main_window(&mut gtk_action_sink);
+        Self::create_main_window(&mut gtk_action_sink, window_size.get());
 
         // Create the viewmodel (which gets its own input sink)
         let viewmodel = GtkSessionViewModel::new();
@@ -77,7 +89,8 @@ impl<Ui: CoreUserInterface> GtkSession<Ui> {
             gtk_ui:                 gtk_ui,
             viewmodel:              viewmodel,
             controller_for_widget:  HashMap::new(),
-            widgets_for_canvas:     HashMap::new()
+            widgets_for_canvas:     HashMap::new(),
+            window_size:            window_size
         };
         let core = Arc::new(Mutex::new(core));
 
@@ -199,7 +212,7 @@ impl<Ui: CoreUserInterface> GtkSession<Ui> {
     ///
     /// Creates the main window (ID 0) to run our session in
     ///
-    fn create_main_window(action_sink: &mut GtkActionSink) {
+    fn create_main_window(action_sink: &mut GtkActionSink, (width, height): (i32, i32)) {
         use self::GtkAction::*;
         use self::GtkWindowAction::*;
 
@@ -208,7 +221,7 @@ impl<Ui: CoreUserInterface> GtkSession<Ui> {
             Window(WindowId::Assigned(0), vec![
                 New(gtk::WindowType::Toplevel),
                 SetPosition(gtk::WindowPosition::Center),
-                SetDefaultSize(1920, 1080),             // TODO: make configurable (?)
+                SetDefaultSize(width, height),
                 SetTitle("FlowBetween".to_string()),    // TODO: make configurable
                 ShowAll
             ])
@@ -226,6 +239,8 @@ impl GtkSessionCore {
         match event {
             None                                        => vec![],
             CloseWindow(_window_id)                     => vec![],
+            ResizeWindow(WindowId::Assigned(0), w, h)   => { self.window_size.set((w, h)); vec![] },
+            ResizeWindow(_window_id, _w, _h)            => vec![],
             Tick                                        => vec![ UiEvent::Tick ],
             Event(widget, event_name, parameter)        => self.controller_for_widget.get(&widget)
                 .map(|controller| vec![ UiEvent::Action((**controller).clone(), event_name, parameter.into()) ])
@@ -244,10 +259,20 @@ impl GtkSessionCore {
             Start                                   => vec![],
             UpdateUi(ui_differences)                => self.update_ui(ui_differences),
             UpdateCanvas(canvas_differences)        => self.update_canvases(canvas_differences),
-            UpdateViewModel(viewmodel_differences)  => self.update_viewmodel(viewmodel_differences)
+            UpdateViewModel(viewmodel_differences)  => self.update_viewmodel(viewmodel_differences),
+            UpdateTheme(theme)                      => self.update_theme(theme)
         }
     }
 
+    ///
+    /// Generates the style sheet for a new theme
+    ///
+    fn update_theme(&mut self, theme: Theme) -> Vec<GtkAction> {
+        let style_sheet = theme.to_css(CssDialect::Gtk, |control_type| format!(".{}", widget_class_for_control_type(control_type)));
+
+        vec![GtkAction::SetStyleSheet(style_sheet)]
+    }
+
     ///
     /// Creates an ID for a widget in this core
     ///
@@ -501,6 +526,25 @@ impl GtkSessionCore {
         }
     }
 
+    ///
+    /// Generates the actions that tell the widgets with transitions in a new control tree which widget they're replacing
+    /// in the old tree (so they can animate from where the old widget was)
+    ///
+    fn replacement_actions(old_control: &GtkControl, new_control: &GtkControl, new_ui: &Control) -> Vec<GtkAction> {
+        let mut actions = vec![];
+
+        if new_ui.attributes().any(|attr| attr.transition().is_some()) {
+            actions.push(GtkAction::Widget(new_control.widget_id, vec![ WidgetLayout::Replaces(old_control.widget_id).into() ]));
+        }
+
+        // Child controls are matched up by their index
+        for ((old_child, new_child), child_ui) in old_control.child_controls.iter().zip(new_control.child_controls.iter()).zip(new_ui.subcomponents().unwrap_or(&vec![]).iter()) {
+            actions.extend(Self::replacement_actions(old_child, new_child, child_ui));
+        }
+
+        actions
+    }
+
     ///
     /// Generates the actions to update the UI with a particular diff
     ///
@@ -510,11 +554,17 @@ impl GtkSessionCore {
         // Create the actions to generate the control in this diff
         let (new_control, new_control_actions) = self.create_control(&diff.new_ui, Rc::new(controller_path), diff.address.len() == 0);
 
+        // Any transitions in the new control start from where the old control was (this must be done before the old control is deleted)
+        let replacement_actions = self.control_at_address_mut(&diff.address)
+            .map(|old_control| Self::replacement_actions(old_control, &new_control, &diff.new_ui))
+            .unwrap_or_else(|| vec![]);
+
         // Replace the control at the specified address with our new control
         let replace_actions = self.replace_control(&diff.address, new_control);
 
         // Generate the new control then replace the old control
         new_control_actions.into_iter()
+            .chain(replacement_actions)
             .chain(replace_actions)
             .collect()
     }
@@ -601,7 +651,10 @@ impl GtkSessionCore {
                     CancelEdit                      => vec![ /* TODO */ ],
                     EditValue                       => vec![ RequestEvent(GtkWidgetEventType::EditValue, action_name) ],
                     SetValue                        => vec![ RequestEvent(GtkWidgetEventType::SetValue, action_name) ],
-                    VirtualScroll(width, height)    => vec![ RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), action_name) ]
+                    VirtualScroll(width, height)    => vec![ RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), action_name) ],
+                    SelectItems                     => vec![ RequestEvent(GtkWidgetEventType::SelectItems, action_name) ],
+                    ExpandItem                      => vec![ RequestEvent(GtkWidgetEventType::ExpandItem, action_name) ],
+                    SelectMenuItem                  => vec![ RequestEvent(GtkWidgetEventType::SelectMenuItem, action_name) ]
                 }
             })
             .collect()
@@ -0,0 +1,89 @@
+use super::widget::*;
+use super::super::gtk_action::*;
+use super::super::gtk_thread::*;
+
+use flo_ui::*;
+
+use gtk;
+use gtk::prelude::*;
+use atk;
+use atk::prelude::*;
+
+///
+/// The position of a widget in its parent's keyboard focus order (stored as widget data)
+///
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FocusOrder(pub u32);
+
+///
+/// Converts an accessibility role to the equivalent ATK role
+///
+fn atk_role(role: AccessibilityRole) -> atk::Role {
+    use self::AccessibilityRole::*;
+
+    match role {
+        Button          => atk::Role::PushButton,
+        ToggleButton    => atk::Role::ToggleButton,
+        CheckBox        => atk::Role::CheckBox,
+        Slider          => atk::Role::Slider,
+        TextBox         => atk::Role::Entry,
+        Label           => atk::Role::Label,
+        Image           => atk::Role::Image,
+        Canvas          => atk::Role::Canvas,
+        ComboBox        => atk::Role::ComboBox,
+        List            => atk::Role::List,
+        ListItem        => atk::Role::ListItem,
+        Tree            => atk::Role::Tree,
+        TreeItem        => atk::Role::TreeItem,
+        Toolbar         => atk::Role::ToolBar,
+        Group           => atk::Role::Panel
+    }
+}
+
+///
+/// Processes an accessibility action for a widget
+///
+pub fn process_accessibility_action<W: GtkUiWidget>(widget: &W, flo_gtk: &mut FloGtk, accessibility: &WidgetAccessibility) {
+    use self::WidgetAccessibility::*;
+
+    let underlying = widget.get_underlying();
+
+    match accessibility {
+        SetTooltip(tooltip)         => { underlying.set_tooltip_text(if tooltip.len() > 0 { Some(tooltip.as_str()) } else { None }); },
+        SetLabel(label)             => { underlying.get_accessible().map(|accessible| accessible.set_name(label)); },
+        SetDescription(description) => { underlying.get_accessible().map(|accessible| accessible.set_description(description)); },
+        SetRole(role)               => { underlying.get_accessible().map(|accessible| accessible.set_role(atk_role(*role))); },
+        SetFocusOrder(order)        => { flo_gtk.widget_data().set_widget_data(widget.id(), FocusOrder(*order)); }
+    }
+}
+
+///
+/// After the children of a widget have been set, updates its focus chain so that children with a focus order are
+/// focused first (children without one follow in their original order)
+///
+pub fn update_focus_chain<W: GtkUiWidget>(widget: &W, children: &Vec<WidgetId>, flo_gtk: &mut FloGtk) {
+    let widget_data     = flo_gtk.widget_data();
+    let focus_orders    = children.iter()
+        .map(|child_id| widget_data.get_widget_data::<FocusOrder>(*child_id).map(|order| { let order = order.borrow(); order.0 }))
+        .collect::<Vec<_>>();
+
+    // Leave the default focus chain alone if no child has a focus order
+    if focus_orders.iter().all(|order| order.is_none()) {
+        return;
+    }
+
+    let container = match widget.get_underlying().clone().dynamic_cast::<gtk::Container>() {
+        Ok(container)   => container,
+        Err(_)          => { return; }
+    };
+
+    // Sorting is stable, so children with the same order stay in the order they were added
+    let mut focus_chain = children.iter()
+        .zip(focus_orders)
+        .filter_map(|(child_id, order)| widget_data.get_widget(*child_id).map(|child| (order.unwrap_or(u32::max_value()), child.borrow().get_underlying().clone())))
+        .collect::<Vec<_>>();
+    focus_chain.sort_by_key(|(order, _child)| *order);
+
+    let focus_chain = focus_chain.into_iter().map(|(_order, child)| child).collect::<Vec<_>>();
+    container.set_focus_chain(&focus_chain);
+}
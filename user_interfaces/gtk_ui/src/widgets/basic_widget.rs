@@ -2,8 +2,12 @@ use super::drag::*;
 use super::click::*;
 use super::paint::*;
 use super::layout::*;
+use super::layout::Layout;
+use super::menu::*;
+use super::accessibility::*;
 use super::widget::*;
 use super::flo_layout::*;
+use super::flo_list_widget::*;
 use super::custom_style::*;
 use super::super::gtk_action::*;
 use super::super::gtk_thread::*;
@@ -85,6 +89,10 @@ pub fn process_basic_widget_action<W: GtkUiWidget>(widget: &mut W, flo_gtk: &mut
         &Font(ref font)                             => process_basic_widget_font(widget, flo_gtk, font),
         &Scroll(ref scroll)                         => process_basic_widget_scroll(widget.get_underlying(), flo_gtk, scroll),
         &Popup(ref _popup)                          => (),
+        &Window(ref _window)                        => (),
+        &Items(ref items)                           => process_list_item_action(widget, flo_gtk, items),
+        &Menu(ref menu)                             => process_menu_action(widget, flo_gtk, menu),
+        &Accessibility(ref accessibility)           => process_accessibility_action(widget, flo_gtk, accessibility),
 
         &Show                                       => { widget.get_underlying().show() },
         &New(_widget_type)                          => (),
@@ -115,6 +123,15 @@ pub fn process_basic_widget_layout<W: Clone+WidgetExt+IsA<gtk::Widget>>(id: Widg
     // Update it with the content of the command
     widget_layout.map(move |widget_layout| widget_layout.borrow_mut().update(layout));
 
+    // Widgets that replace another widget start their transitions from where that widget was laid out
+    if let &WidgetLayout::Replaces(replaced_id) = layout {
+        let replaced_position = widget_data.get_widget_data::<WidgetPosition>(replaced_id).map(|position| *position.borrow());
+
+        if let Some(replaced_position) = replaced_position {
+            widget_data.set_widget_data(id, ReplacedPosition(replaced_position));
+        }
+    }
+
     // For floating widgets, we may need to reallocate them immediately
     if let &WidgetLayout::Floating(float_x, float_y) = layout {
         // Update the floating position data (so the next layout will use it)
@@ -163,6 +180,7 @@ pub fn process_basic_widget_layout<W: Clone+WidgetExt+IsA<gtk::Widget>>(id: Widg
 ///
 pub fn set_widget_parent<W: GtkUiWidget>(widget: &mut W, children: &Vec<WidgetId>, flo_gtk: &mut FloGtk) {
     // Fetch the widget information
+    let children_ids    = children;
     let widget_data     = flo_gtk.widget_data();
     let children        = children.iter()
         .map(|child_id| widget_data.get_widget(*child_id))
@@ -171,6 +189,7 @@ pub fn set_widget_parent<W: GtkUiWidget>(widget: &mut W, children: &Vec<WidgetId
         .collect();
 
     widget.set_children(children);
+    update_focus_chain(widget, children_ids, flo_gtk);
 }
 
 ///
@@ -233,6 +252,9 @@ pub fn process_basic_widget_state<W: GtkUiWidget>(widget: &W, state: &WidgetStat
 
             // TODO: toggle buttons probably should get their own class thing
             widget.get_underlying().clone().dynamic_cast::<gtk::ToggleButton>().ok().map(|toggle| { toggle.set_active(selected); });
+
+            // Items in list views are selected by selecting their row
+            select_list_item(widget.get_underlying(), selected);
         },
         &SetBadged(badged)          => {
             let context = widget.get_underlying()
@@ -308,6 +330,10 @@ pub fn process_basic_event_request<W: GtkUiWidget>(widget: &W, flo_gtk: &mut Flo
             DragActions::wire_widget(flo_gtk.widget_data(), event_sink, widget, action_name.clone());
         },
 
-        VirtualScroll(_, _) | EditValue | SetValue | Dismiss => { }
+        SelectMenuItem => {
+            wire_select_menu_item(widget, flo_gtk, &action_name);
+        },
+
+        VirtualScroll(_, _) | EditValue | SetValue | Dismiss | SelectItems | ExpandItem => { }
     }
 }
@@ -2,6 +2,7 @@ use super::widget::*;
 use super::flo_bin_widget::*;
 use super::flo_fixed_widget::*;
 use super::flo_popover_widget::*;
+use super::flo_window_widget::*;
 use super::flo_label_widget::*;
 use super::flo_scale_widget::*;
 use super::flo_rotor_widget::*;
@@ -10,6 +11,8 @@ use super::flo_canvas_widget::*;
 use super::flo_overlay_widget::*;
 use super::flo_textbox_widget::*;
 use super::flo_checkbox_widget::*;
+use super::flo_combobox_widget::*;
+use super::flo_list_widget::*;
 use super::flo_render_canvas_widget::*;
 use super::flo_canvas_layout_widget::*;
 use super::widget_data::*;
@@ -36,9 +39,11 @@ pub fn create_widget(id: WidgetId, widget_type: GtkWidgetType, widget_data: Rc<W
         Button              => Box::new(FloBinWidget::new(id, gtk::Button::new(), widget_data)),
         ToggleButton        => Box::new(FloBinWidget::new(id, gtk::ToggleButton::new(), widget_data)),
         CheckBox            => Box::new(FloCheckBoxWidget::new(id, gtk::CheckButton::new())),
+        ComboBox            => Box::new(FloComboBoxWidget::new(id, gtk::ComboBoxText::new())),
         TextBox             => Box::new(FloTextBoxWidget::new(id, gtk::Entry::new())),
         Label               => Box::new(FloLabelWidget::new(id, gtk::Label::new(None))),
         Popover             => Box::new(FloPopoverWidget::new(id, gtk::Layout::new::<gtk::Adjustment, gtk::Adjustment>(None, None), widget_data)),
+        Window              => Box::new(FloWindowWidget::new(id, gtk::Layout::new::<gtk::Adjustment, gtk::Adjustment>(None, None), widget_data)),
 
         Overlay             => Box::new(FloOverlayWidget::new(id, gtk::Overlay::new(), gtk::Layout::new::<gtk::Adjustment, gtk::Adjustment>(None, None), widget_data)),
 
@@ -46,6 +51,10 @@ pub fn create_widget(id: WidgetId, widget_type: GtkWidgetType, widget_data: Rc<W
             let no_adjustment: Option<gtk::Adjustment> = None;
             Box::new(FloScrollWidget::new(id, gtk::ScrolledWindow::new(no_adjustment.as_ref(), no_adjustment.as_ref()), widget_data))
         },
+        ListView            => {
+            let no_adjustment: Option<gtk::Adjustment> = None;
+            Box::new(FloListWidget::new(id, gtk::ScrolledWindow::new(no_adjustment.as_ref(), no_adjustment.as_ref()), widget_data))
+        },
         Rotor               => Box::new(FloRotorWidget::new(id, gtk::DrawingArea::new())),
         CanvasDrawingArea   => Box::new(FloDrawingWidget::new(id, gtk::DrawingArea::new(), widget_data)),
         CanvasLayout        => {
@@ -0,0 +1,153 @@
+use super::widget::*;
+use super::basic_widget::*;
+use super::super::gtk_event::*;
+use super::super::gtk_thread::*;
+use super::super::gtk_action::*;
+use super::super::gtk_event_parameter::*;
+use super::super::gtk_widget_event_type::*;
+
+use gtk;
+use gtk::prelude::*;
+
+use std::rc::*;
+use std::cell::*;
+
+///
+/// Implements behaviour for the combo box widget
+///
+/// The options in a Flo combo box are supplied as label controls, which we read the text of to generate the
+/// entries in the Gtk combo box (the labels themselves are never displayed)
+///
+pub struct FloComboBoxWidget {
+    /// The ID of this widget
+    id: WidgetId,
+
+    /// The combo box widget
+    widget: gtk::ComboBoxText,
+
+    /// The combo box again, but cast to a widget
+    as_widget: gtk::Widget,
+
+    /// The labels that supply the text of each option
+    labels: Rc<RefCell<Vec<gtk::Label>>>,
+
+    /// The index of the option that should be selected
+    selected: Rc<Cell<Option<u32>>>,
+
+    /// Set to true while we're updating the combo box (so changes are not reported as user selections)
+    updating: Rc<Cell<bool>>
+}
+
+impl FloComboBoxWidget {
+    ///
+    /// Creates a new combo box widget
+    ///
+    pub fn new<W: Clone+Cast+IsA<gtk::ComboBoxText>+IsA<gtk::Widget>>(id: WidgetId, combo_box: W) -> FloComboBoxWidget {
+        FloComboBoxWidget {
+            id:             id,
+            widget:         combo_box.clone().upcast::<gtk::ComboBoxText>(),
+            as_widget:      combo_box.clone().upcast::<gtk::Widget>(),
+            labels:         Rc::new(RefCell::new(vec![])),
+            selected:       Rc::new(Cell::new(None)),
+            updating:       Rc::new(Cell::new(false))
+        }
+    }
+
+    ///
+    /// Regenerates the entries in a combo box from the text of a set of labels
+    ///
+    fn update_options(combo_box: &gtk::ComboBoxText, labels: &Vec<gtk::Label>, selected: Option<u32>, updating: &Cell<bool>) {
+        updating.set(true);
+
+        combo_box.remove_all();
+        for label in labels.iter() {
+            combo_box.append_text(&label.get_text());
+        }
+        combo_box.set_active(selected);
+
+        updating.set(false);
+    }
+}
+
+impl GtkUiWidget for FloComboBoxWidget {
+    ///
+    /// Retrieves the ID assigned to this widget
+    ///
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    ///
+    /// Processes an action for this widget
+    ///
+    fn process(&mut self, flo_gtk: &mut FloGtk, action: &GtkWidgetAction) {
+        use self::GtkWidgetAction::*;
+
+        match action {
+            // The value is the index of the selected option
+            State(WidgetState::SetValueInt(index)) => {
+                let index = if *index >= 0 { Some(*index as u32) } else { None };
+
+                self.selected.set(index);
+                self.updating.set(true);
+                self.widget.set_active(index);
+                self.updating.set(false);
+            },
+
+            // EditValue events are ignored (choosing an option is always a final edit)
+            RequestEvent(GtkWidgetEventType::EditValue, _) => {
+            },
+
+            // Choosing an option causes a set value event
+            RequestEvent(GtkWidgetEventType::SetValue, event_name) => {
+                let id              = self.id;
+                let sink            = flo_gtk.get_event_sink();
+                let event_name      = event_name.clone();
+                let updating        = Rc::clone(&self.updating);
+
+                self.widget.connect_changed(move |widget| {
+                    if updating.get() { return; }
+
+                    if let Some(index) = widget.get_active() {
+                        publish_event(&sink, GtkEvent::Event(id, event_name.clone(), GtkEventParameter::SelectedIndex(index)));
+                    }
+                });
+            },
+
+            // Standard behaviour for all other actions
+            other_action => { process_basic_widget_action(self, flo_gtk, other_action); }
+        }
+    }
+
+    ///
+    /// Sets the children of this widget
+    ///
+    fn set_children(&mut self, children: Vec<Rc<RefCell<dyn GtkUiWidget>>>) {
+        // Only label children supply options
+        let labels = children.iter()
+            .filter_map(|child| child.borrow().get_underlying().clone().dynamic_cast::<gtk::Label>().ok())
+            .collect::<Vec<_>>();
+
+        // Update the options whenever the text of one of the labels changes
+        for label in labels.iter() {
+            let combo_box   = self.widget.clone();
+            let all_labels  = Rc::clone(&self.labels);
+            let selected    = Rc::clone(&self.selected);
+            let updating    = Rc::clone(&self.updating);
+
+            label.connect_property_label_notify(move |_label| {
+                Self::update_options(&combo_box, &*all_labels.borrow(), selected.get(), &*updating);
+            });
+        }
+
+        *self.labels.borrow_mut() = labels;
+        Self::update_options(&self.widget, &*self.labels.borrow(), self.selected.get(), &*self.updating);
+    }
+
+    ///
+    /// Retrieves the underlying widget for this UI widget
+    ///
+    fn get_underlying<'a>(&'a self) -> &'a gtk::Widget {
+        &self.as_widget
+    }
+}
@@ -1,15 +1,19 @@
 use super::layout::*;
+use super::layout::Layout;
 use super::widget_data::*;
 use super::super::gtk_action::*;
 
 use flo_ui::*;
+use flo_ui as ui;
 
 use gtk;
 use gtk::prelude::*;
 use gdk::prelude::*;
 
 use std::rc::*;
-use std::collections::HashSet;
+use std::cell::*;
+use std::time::Instant;
+use std::collections::{HashMap, HashSet};
 
 ///
 /// Indicates the floating position of a widget (used when laying it out again)
@@ -19,6 +23,11 @@ pub struct FloatingPosition {
     pub y: f64
 }
 
+///
+/// The position of the widget that a widget replaced (used as the starting point for its first transition)
+///
+pub struct ReplacedPosition(pub WidgetPosition);
+
 ///
 /// Provides the computed layout position for a widget
 ///
@@ -48,7 +57,13 @@ pub struct FloWidgetLayout {
     child_widget_ids: Vec<WidgetId>,
 
     /// Our copy of the widget data
-    widget_data: Rc<WidgetData>
+    widget_data: Rc<WidgetData>,
+
+    /// The transitions that are currently running for the child widgets, and when they started
+    transitions: HashMap<WidgetId, (LayoutTransition, Instant)>,
+
+    /// Set to true while we're waiting for the next frame to update the transitions
+    frame_pending: Rc<Cell<bool>>
 }
 
 impl WidgetPosition {
@@ -68,7 +83,9 @@ impl FloWidgetLayout {
             current_size:       None,
             parent_widget_id:   parent_widget_id,
             child_widget_ids:   vec![],
-            widget_data:        widget_data
+            widget_data:        widget_data,
+            transitions:        HashMap::new(),
+            frame_pending:      Rc::new(Cell::new(false))
         }
     }
 
@@ -78,117 +95,165 @@ impl FloWidgetLayout {
     pub fn set_children<W: IntoIterator<Item=WidgetId>>(&mut self, widgets: W) {
         self.current_size       = None;
         self.child_widget_ids   = widgets.into_iter().collect();
+
+        // Stop the transitions for any widget that's no longer a child of this one
+        let child_widget_ids    = &self.child_widget_ids;
+        self.transitions.retain(|widget_id, _| child_widget_ids.contains(widget_id));
     }
 
     ///
-    /// Turns a Position into an absolute position
+    /// Updates the transition for a widget that's just been laid out, and returns the position it should be displayed at
+    /// along with its opacity
     ///
-    pub fn layout_position(&self, last_pos: f64, next_pos: &Position, max_pos: f64, stretch_area: f64, total_stretch: f64) -> f64 {
-        use self::Position::*;
+    fn animate_widget(&mut self, widget_layout: &WidgetPosition, previous_position: Option<WidgetPosition>, transitions: &Vec<ui::Transition>) -> (WidgetPosition, f64) {
+        let widget_id = widget_layout.id;
 
-        match next_pos {
-            &At(pos)                        => pos as f64,
-            &Floating(ref _prop, offset)    => offset as f64,
-            &Offset(offset)                 => last_pos + (offset as f64),
-            &Stretch(portion)               => last_pos + stretch_area * ((portion as f64)/total_stretch),
-            &Start                          => 0.0,
-            &End                            => max_pos,
-            &After                          => last_pos
+        // Widgets with no transitions just snap to their new position
+        if transitions.len() == 0 {
+            self.transitions.remove(&widget_id);
+            return (*widget_layout, 1.0);
         }
+
+        let as_bounds       = |position: &WidgetPosition| ((position.x1 as f32, position.y1 as f32), (position.x2 as f32, position.y2 as f32));
+        let elapsed_ms      = |start: &Instant| start.elapsed().as_secs_f64() * 1000.0;
+        let target          = as_bounds(widget_layout);
+
+        // Start a new transition if the widget's target position has changed
+        let is_new_target   = self.transitions.get(&widget_id).map(|(transition, _)| transition.target() != target).unwrap_or(true);
+
+        if is_new_target {
+            // The new transition starts wherever the widget is being displayed right now
+            let from        = match self.transitions.get(&widget_id) {
+                Some((transition, start))   => Some(transition.bounds_at(elapsed_ms(start))),
+                None                        => previous_position.as_ref().map(as_bounds)
+            };
+            let transition  = LayoutTransition::new(transitions.iter(), from, target);
+
+            if transition.is_animated() {
+                self.transitions.insert(widget_id, (transition, Instant::now()));
+            } else {
+                self.transitions.remove(&widget_id);
+            }
+        }
+
+        // Work out where the widget is in its transition
+        let (position, opacity, finished) = match self.transitions.get(&widget_id) {
+            None                        => (*widget_layout, 1.0, false),
+            Some((transition, start))   => {
+                let elapsed                     = elapsed_ms(start);
+                let ((x1, y1), (x2, y2))        = transition.bounds_at(elapsed);
+                let position                    = WidgetPosition { x1: x1 as f64, y1: y1 as f64, x2: x2 as f64, y2: y2 as f64, ..*widget_layout };
+
+                (position, transition.opacity_at(elapsed), transition.is_finished(elapsed))
+            }
+        };
+
+        if finished {
+            self.transitions.remove(&widget_id);
+        }
+
+        (position, opacity)
     }
 
     ///
-    /// Returns the amount of stretch in a position
+    /// If any transitions are running, lays out the widgets in the specified container again on the next frame
     ///
-    fn get_stretch(&self, pos: &Position) -> f64 {
-        if let &Position::Stretch(portion) = pos {
-            portion as f64
-        } else {
-            0.0
+    fn request_transition_frame<T: Cast+Clone+IsA<gtk::Widget>>(&mut self, target: &T) {
+        if self.transitions.len() > 0 {
+            // The layout isn't finished until all the transitions are
+            self.current_size = None;
+
+            if !self.frame_pending.get() {
+                let frame_pending = Rc::clone(&self.frame_pending);
+                frame_pending.set(true);
+
+                target.clone().upcast::<gtk::Widget>().add_tick_callback(move |widget: &gtk::Widget, _clock| {
+                    frame_pending.set(false);
+                    widget.queue_resize();
+                    glib::Continue(false)
+                });
+            }
         }
     }
 
     ///
-    /// Performs layout of the widgets in this item
+    /// Returns the content size to use for a widget when laying it out in a row, column or grid
     ///
-    pub fn get_layout(&self, width: f64, height: f64) -> Vec<WidgetPosition> {
-        // Where we are in the current layout
-        let mut xpos    = 0.0;
-        let mut ypos    = 0.0;
-
-        let mut total_stretch_x = 0.0;
-        let mut total_stretch_y = 0.0;
-
-        // First pass: lay out the components with no stretch
-        let mut positions = vec![];
-
-        for widget_id in self.child_widget_ids.iter() {
-            // Get the layout for this widget
-            let layout = self.widget_data.get_widget_data::<Layout>(*widget_id);
-            let layout = layout
-                .map(|layout| layout.borrow().clone())
-                .unwrap_or_else(|| Layout::new());
-
-            // Most important part is the bounds
-            let bounds = layout.bounds.unwrap_or(Bounds::fill_all());
-
-            // Decide on the bounds for this element
-            let x1 = self.layout_position(xpos, &bounds.x1, width, 0.0, 1.0);
-            let x2 = self.layout_position(x1, &bounds.x2, width, 0.0, 1.0);
-            let y1 = self.layout_position(ypos, &bounds.y1, height, 0.0, 1.0);
-            let y2 = self.layout_position(y1, &bounds.y2, height, 0.0, 1.0);
-
-            // Incorporate any stretch
-            total_stretch_x += self.get_stretch(&bounds.x1);
-            total_stretch_x += self.get_stretch(&bounds.x2);
-            total_stretch_y += self.get_stretch(&bounds.y1);
-            total_stretch_y += self.get_stretch(&bounds.y2);
-
-            // Update the xpos and ypos for the next pass
-            xpos = x2;
-            ypos = y2;
-        }
+    fn get_content_size(&self, widget_id: WidgetId, layout: &Layout) -> (f32, f32) {
+        use self::Position::*;
 
-        // Second pass: layout with stretch and generate the final positions
-        if total_stretch_x == 0.0 { total_stretch_x = 1.0; }
-        if total_stretch_y == 0.0 { total_stretch_y = 1.0; }
-
-        let stretch_x = width - xpos;
-        let stretch_y = height - ypos;
-
-        xpos = 0.0;
-        ypos = 0.0;
-
-        for widget_id in self.child_widget_ids.iter() {
-            // Get the layout for this widget
-            let layout = self.widget_data.get_widget_data::<Layout>(*widget_id);
-            let layout = layout
-                .map(|layout| layout.borrow().clone())
-                .unwrap_or_else(|| Layout::new());
-
-            // Most important part is the bounds
-            let bounds  = layout.bounds.unwrap_or(Bounds::fill_all());
-            let z_index = layout.z_index.unwrap_or(0);
-
-            // Decide on the bounds for this element
-            let x1 = self.layout_position(xpos, &bounds.x1, width, stretch_x, total_stretch_x);
-            let x2 = self.layout_position(x1, &bounds.x2, width, stretch_x, total_stretch_x);
-            let y1 = self.layout_position(ypos, &bounds.y1, height, stretch_y, total_stretch_y);
-            let y2 = self.layout_position(y1, &bounds.y2, height, stretch_y, total_stretch_y);
-
-            // Add to the position
-            positions.push(WidgetPosition {
-                id: *widget_id,
-                x1, x2, y1, y2,
-                z_index
-            });
-
-            // Update the xpos and ypos for the next pass
-            xpos = x2;
-            ypos = y2;
+        // Explicit content sizes override the natural size of the widget
+        let explicit_size = layout.layout.iter()
+            .filter_map(|layout| match layout { ui::Layout::ContentSize(width, height) => Some((*width, *height)), _ => None })
+            .nth(0);
+        if let Some(explicit_size) = explicit_size {
+            return explicit_size;
         }
 
-        positions
+        // Otherwise, ask GTK for the natural size of the widget
+        let (natural_width, natural_height) = self.widget_data.get_widget(widget_id)
+            .map(|widget| {
+                let widget      = widget.borrow();
+                let underlying  = widget.get_underlying();
+
+                (underlying.get_preferred_width().1 as f32, underlying.get_preferred_height().1 as f32)
+            })
+            .unwrap_or((0.0, 0.0));
+
+        // Sizes set by the bounding box take priority over the natural size
+        let bounds = layout.bounds.clone().unwrap_or(Bounds::fill_all());
+        let width  = match bounds.x2 { Offset(width) => width, _ => natural_width };
+        let height = match bounds.y2 { Offset(height) => height, _ => natural_height };
+
+        (width, height)
+    }
+
+    ///
+    /// Performs layout of the widgets in this item
+    ///
+    pub fn get_layout(&self, width: f64, height: f64) -> Vec<WidgetPosition> {
+        // The layout attributes of the parent widget decide how the children are arranged
+        let parent_layout   = self.widget_data.get_widget_data::<Layout>(self.parent_widget_id)
+            .map(|layout| layout.borrow().layout.clone())
+            .unwrap_or_else(|| vec![]);
+        let control_layout  = ControlLayout::new(&parent_layout);
+
+        // Content sizes are only needed when the widgets aren't placed by their bounding boxes
+        let uses_content    = parent_layout.iter().any(|layout| match layout { ui::Layout::Mode(mode) => *mode != LayoutMode::Bounds, _ => false });
+
+        // Describe each child widget to the layout engine
+        let layouts: Vec<_> = self.child_widget_ids.iter()
+            .map(|widget_id| {
+                self.widget_data.get_widget_data::<Layout>(*widget_id)
+                    .map(|layout| layout.borrow().clone())
+                    .unwrap_or_else(|| Layout::new())
+            })
+            .collect();
+        let items: Vec<_>   = self.child_widget_ids.iter().zip(layouts.iter())
+            .map(|(widget_id, layout)| {
+                let content_size    = if uses_content { self.get_content_size(*widget_id, layout) } else { (0.0, 0.0) };
+                let mut item        = LayoutItem::new(layout.bounds.clone().unwrap_or(Bounds::fill_all()), layout.layout.clone(), content_size);
+                item.detached       = layout.detached;
+
+                item
+            })
+            .collect();
+
+        // Perform the layout
+        let positions       = control_layout.lay_out(&items, width as f32, height as f32);
+
+        self.child_widget_ids.iter().zip(layouts.iter()).zip(positions.into_iter())
+            .map(|((widget_id, layout), ((x1, y1), (x2, y2)))| {
+                WidgetPosition {
+                    id:         *widget_id,
+                    x1:         x1 as f64,
+                    y1:         y1 as f64,
+                    x2:         x2 as f64,
+                    y2:         y2 as f64,
+                    z_index:    layout.z_index.unwrap_or(0)
+                }
+            })
+            .collect()
     }
 
     ///
@@ -289,7 +354,7 @@ impl FloWidgetLayout {
     ///
     /// Performs container layout with a particular width and height
     ///
-    fn layout_in_container<'a, T, MoveFn>(&'a self, target: &T, move_widget: MoveFn, min_x: i32, min_y: i32, width: i32, height: i32) 
+    fn layout_in_container<'a, T, MoveFn>(&'a mut self, target: &T, move_widget: MoveFn, min_x: i32, min_y: i32, width: i32, height: i32) 
     where   T:      Cast+Clone+IsA<gtk::Container>+IsA<gtk::Widget>,
             MoveFn: 'a+Fn(&gtk::Widget, i32, i32) -> () {
         // When we call 'move_widget' the coordinate system goes from 0 - width, and when we call 'size_allocate' it goes from
@@ -310,13 +375,21 @@ impl FloWidgetLayout {
             // Fetch the widget we're going to lay out
             let widget = self.widget_data.get_widget(widget_layout.id);
 
+            // Find out where the widget was laid out before (or where the widget it replaced was laid out) so we can animate any transitions
+            let previous_position   = self.widget_data.get_widget_data::<WidgetPosition>(widget_layout.id).map(|position| *position.borrow())
+                .or_else(|| self.widget_data.get_widget_data::<ReplacedPosition>(widget_layout.id).map(|replaced| replaced.borrow().0));
+            let transitions         = self.widget_data.get_widget_data::<Layout>(widget_layout.id)
+                .map(|layout| layout.borrow().transitions.clone())
+                .unwrap_or_else(|| vec![]);
+
             // Store this layout data with the widget
             self.widget_data.set_widget_data(widget_layout.id, widget_layout);
 
             // If the widget exists, then position it according to its coordinates (and padding)
             if let Some(widget) = widget {
-                // Get the position from the layout
-                let (x1, y1, x2, y2)            = (widget_layout.x1 as f64, widget_layout.y1 as f64, widget_layout.x2 as f64, widget_layout.y2 as f64);
+                // Get the position from the layout (or from the transition if the widget is moving)
+                let (displayed, opacity)        = self.animate_widget(&widget_layout, previous_position, &transitions);
+                let (x1, y1, x2, y2)            = (displayed.x1 as f64, displayed.y1 as f64, displayed.x2 as f64, displayed.y2 as f64);
 
                 // Convert to x, y and width and height
                 let mut x       = x1;
@@ -337,6 +410,11 @@ impl FloWidgetLayout {
 
                 remaining.remove(underlying);
 
+                // Fade in widgets that have just appeared
+                if transitions.len() > 0 {
+                    underlying.set_opacity(opacity);
+                }
+
                 // Send a size request to the widget if its width or height has changed
                 let (new_x, new_y)          = (x.floor() as i32, y.floor() as i32);
                 let (new_x, new_y)          = (new_x + min_x, new_y + min_y);
@@ -366,6 +444,9 @@ impl FloWidgetLayout {
         // Order z-indices of the widgets we've just been through (assuming they have windows that can be ordered)
         self.order_zindex(z_indices);
 
+        // Keep laying out the widgets while they're moving
+        self.request_transition_frame(target);
+
         // Make any remaining widget fill the entire container
         let full_size = gtk::Rectangle { x: min_x, y: min_y, width: container_width, height: container_height };
         for extra_widget in remaining {
@@ -424,9 +505,9 @@ mod test {
             x2: End,    y2: Offset(256.0)
         };
 
-        widget_data.set_widget_data(top, Layout { bounds: Some(top_bounds), padding: None, z_index: None });
-        widget_data.set_widget_data(middle, Layout { bounds: Some(middle_bounds), padding: None, z_index: None });
-        widget_data.set_widget_data(bottom, Layout { bounds: Some(bottom_bounds), padding: None, z_index: None });
+        widget_data.set_widget_data(top, Layout { bounds: Some(top_bounds), padding: None, z_index: None, layout: vec![], detached: false, transitions: vec![] });
+        widget_data.set_widget_data(middle, Layout { bounds: Some(middle_bounds), padding: None, z_index: None, layout: vec![], detached: false, transitions: vec![] });
+        widget_data.set_widget_data(bottom, Layout { bounds: Some(bottom_bounds), padding: None, z_index: None, layout: vec![], detached: false, transitions: vec![] });
 
         // Create a layout for these bounds
         let mut layout = FloWidgetLayout::new(WidgetId::Assigned(4), Rc::clone(&widget_data));
@@ -0,0 +1,387 @@
+use super::widget::*;
+use super::widget_data::*;
+use super::basic_widget::*;
+use super::super::gtk_event::*;
+use super::super::gtk_action::*;
+use super::super::gtk_thread::*;
+use super::super::gtk_event_parameter::*;
+use super::super::gtk_widget_event_type::*;
+
+use flo_ui::*;
+
+use gtk;
+use gtk::prelude::*;
+
+use std::rc::*;
+use std::cell::*;
+
+/// How far each level of a tree is indented
+const INDENT_PER_LEVEL: i32 = 16;
+
+/// The width of the button used to expand or collapse an item in a tree
+const DISCLOSURE_WIDTH: i32 = 16;
+
+thread_local! {
+    /// Set to true while the selection of a list box is being changed by the controller rather than the user
+    static UPDATING_SELECTION: Cell<bool> = Cell::new(false);
+}
+
+///
+/// How an item in a list or tree view is displayed (stored as widget data for the item)
+///
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ListItem {
+    /// How deeply this item is nested in a tree
+    pub level: u32,
+
+    /// Whether or not this item is expanded (None if this item has no children)
+    pub expanded: Option<bool>
+}
+
+///
+/// The list widget displays its child widgets in the rows of a list box, in a scrolling window
+///
+/// List widgets are virtualised: the children represent the items from a particular index onwards, and the list box is
+/// given margins so that it's positioned as if the remaining items were present. Tree views are displayed using the
+/// same widget, by indenting each row according to its level and adding a button to expand or collapse it.
+///
+pub struct FloListWidget {
+    /// The ID of this widget
+    id:             WidgetId,
+
+    /// The widget data object
+    widget_data:    Rc<WidgetData>,
+
+    /// The scrolling window
+    scroll_window:  gtk::ScrolledWindow,
+
+    /// The same, cast as a widget
+    as_widget:      gtk::Widget,
+
+    /// The list box where the rows go
+    list_box:       gtk::ListBox,
+
+    /// The height of each row
+    row_height:     f64,
+
+    /// The total number of items (None to use the number of children)
+    count:          Option<u32>,
+
+    /// The index of the first child
+    first_index:    Rc<Cell<u32>>,
+
+    /// The IDs of the child widgets, in row order
+    item_ids:       Rc<RefCell<Vec<WidgetId>>>,
+
+    /// The event sink and action name to use when the user expands or collapses an item
+    expand_event:   Rc<RefCell<Option<(GtkEventSink, String)>>>
+}
+
+impl FloListWidget {
+    ///
+    /// Creates a new list widget
+    ///
+    pub fn new(id: WidgetId, scroll_window: gtk::ScrolledWindow, widget_data: Rc<WidgetData>) -> FloListWidget {
+        let list_box = gtk::ListBox::new();
+
+        // If the scroll window is created at 0 size, it generates a warning, so set a default min size to suppress it
+        scroll_window.set_min_content_width(16);
+        scroll_window.set_min_content_height(16);
+
+        scroll_window.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        scroll_window.add(&list_box);
+        list_box.set_selection_mode(gtk::SelectionMode::Single);
+        list_box.set_valign(gtk::Align::Start);
+        list_box.show();
+
+        let as_widget = scroll_window.clone().upcast::<gtk::Widget>();
+
+        FloListWidget {
+            id:             id,
+            widget_data:    widget_data,
+            scroll_window:  scroll_window,
+            as_widget:      as_widget,
+            list_box:       list_box,
+            row_height:     DEFAULT_ROW_HEIGHT as f64,
+            count:          None,
+            first_index:    Rc::new(Cell::new(0)),
+            item_ids:       Rc::new(RefCell::new(vec![])),
+            expand_event:   Rc::new(RefCell::new(None))
+        }
+    }
+
+    ///
+    /// Sets the margins of the list box so that the rows are where they would be if every item was present
+    ///
+    fn update_margins(&self) {
+        let num_rows    = self.item_ids.borrow().len() as u32;
+        let first_index = self.first_index.get();
+        let count       = self.count.unwrap_or(first_index + num_rows);
+        let after       = count.saturating_sub(first_index + num_rows);
+
+        self.list_box.set_margin_top((first_index as f64 * self.row_height) as i32);
+        self.list_box.set_margin_bottom((after as f64 * self.row_height) as i32);
+    }
+
+    ///
+    /// Sets the height of every row in the list
+    ///
+    fn update_row_heights(&self) {
+        let row_height = self.row_height as i32;
+        self.list_box.get_children().iter().for_each(|row| row.set_size_request(-1, row_height));
+    }
+
+    ///
+    /// Converts a selection mode into the equivalent Gtk selection mode
+    ///
+    fn gtk_selection_mode(mode: SelectionMode) -> gtk::SelectionMode {
+        match mode {
+            SelectionMode::None     => gtk::SelectionMode::None,
+            SelectionMode::Single   => gtk::SelectionMode::Single,
+            SelectionMode::Multiple => gtk::SelectionMode::Multiple
+        }
+    }
+
+    ///
+    /// Sends selection events when the user changes which rows are selected
+    ///
+    fn connect_select_items(&self, sink: GtkEventSink, action_name: String) {
+        let id          = self.id;
+        let first_index = Rc::clone(&self.first_index);
+
+        self.list_box.connect_selected_rows_changed(move |list_box| {
+            if UPDATING_SELECTION.with(|updating| updating.get()) { return; }
+
+            let first_index = first_index.get();
+            let selection   = list_box.get_selected_rows().iter()
+                .map(|row| row.get_index() as u32 + first_index)
+                .collect();
+
+            publish_event(&sink, GtkEvent::Event(id, action_name.clone(), GtkEventParameter::SelectedItems(selection)));
+        });
+    }
+
+    ///
+    /// Sends a virtual scroll event if the visible region of the list has moved
+    ///
+    fn generate_virtual_scroll_event(widget_id: WidgetId, last_scroll: &Cell<Option<((u32, u32), (u32, u32))>>, sink: &GtkEventSink, action_name: &str, scroll_window: &gtk::ScrolledWindow, width: f32, height: f32) {
+        let width       = width as f64;
+        let height      = height as f64;
+
+        let h_adjust    = scroll_window.get_hadjustment();
+        let v_adjust    = scroll_window.get_vadjustment();
+
+        let grid_x      = (h_adjust.get_value() / width).floor() as u32;
+        let grid_y      = (v_adjust.get_value() / height).floor() as u32;
+        let grid_w      = (h_adjust.get_page_size() / width + 0.5).ceil() as u32;
+        let grid_h      = (v_adjust.get_page_size() / height + 0.5).ceil() as u32;
+
+        let new_scroll  = Some(((grid_x, grid_y), (grid_w, grid_h)));
+
+        if last_scroll.get() != new_scroll {
+            last_scroll.set(new_scroll);
+
+            let scroll_parameter = GtkEventParameter::VirtualScroll((grid_x, grid_y), (grid_w, grid_h));
+            publish_event(sink, GtkEvent::Event(widget_id, action_name.to_string(), scroll_parameter));
+        }
+    }
+
+    ///
+    /// Begins generating virtual scroll events as the list is scrolled or resized
+    ///
+    fn start_virtual_scrolling(&self, sink: GtkEventSink, action_name: String, width: f32, height: f32) {
+        let id          = self.id;
+        let last_scroll = Rc::new(Cell::new(None));
+
+        Self::generate_virtual_scroll_event(id, &last_scroll, &sink, &action_name, &self.scroll_window, width, height);
+
+        let (resize_scroll, resize_sink, resize_name) = (Rc::clone(&last_scroll), sink.clone(), action_name.clone());
+        self.scroll_window.connect_size_allocate(move |scroll_window, _allocation| {
+            Self::generate_virtual_scroll_event(id, &resize_scroll, &resize_sink, &resize_name, scroll_window, width, height);
+        });
+
+        let weak_window = self.scroll_window.clone().downgrade();
+        self.scroll_window.get_vadjustment().connect_value_changed(move |_| {
+            if let Some(scroll_window) = weak_window.upgrade() {
+                Self::generate_virtual_scroll_event(id, &last_scroll, &sink, &action_name, &scroll_window, width, height);
+            }
+        });
+    }
+
+    ///
+    /// Creates the row for an item
+    ///
+    fn create_row(&self, item_id: WidgetId, item: &gtk::Widget) -> gtk::ListBoxRow {
+        let row         = gtk::ListBoxRow::new();
+        let row_box     = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        let disclosure  = gtk::Button::new();
+
+        // The disclosure button expands or collapses this item
+        disclosure.set_relief(gtk::ReliefStyle::None);
+        disclosure.set_size_request(DISCLOSURE_WIDTH, -1);
+        disclosure.get_style_context().add_class("flo-disclosure");
+
+        let widget_data     = Rc::clone(&self.widget_data);
+        let expand_event    = Rc::clone(&self.expand_event);
+        let first_index     = Rc::clone(&self.first_index);
+        let weak_row        = row.clone().downgrade();
+        let id              = self.id;
+        disclosure.connect_clicked(move |_| {
+            let list_item   = widget_data.get_widget_data::<ListItem>(item_id).map(|item| { let item = item.borrow(); *item });
+            let expanded    = list_item.and_then(|item| item.expanded);
+            let index       = weak_row.upgrade().map(|row| row.get_index() as u32 + first_index.get());
+
+            if let (Some(expanded), Some(index), Some((sink, action_name))) = (expanded, index, &*expand_event.borrow()) {
+                publish_event(sink, GtkEvent::Event(id, action_name.clone(), GtkEventParameter::ItemExpanded(index, !expanded)));
+            }
+        });
+
+        // Move the item into the row
+        let previous_parent = item.get_parent().and_then(|parent| parent.dynamic_cast::<gtk::Container>().ok());
+        previous_parent.map(|previous_parent| previous_parent.remove(item));
+
+        row_box.pack_start(&disclosure, false, false, 0);
+        row_box.pack_start(item, true, true, 0);
+        row.add(&row_box);
+        row.set_size_request(-1, self.row_height as i32);
+
+        disclosure.show();
+        row_box.show();
+        row.show();
+
+        // Indent the row according to the state of the item
+        let list_item = self.widget_data.get_widget_data::<ListItem>(item_id)
+            .map(|item| { let item = item.borrow(); *item })
+            .unwrap_or(ListItem { level: 0, expanded: None });
+        update_list_row(item, &list_item);
+
+        row
+    }
+}
+
+///
+/// Finds the box that an item was placed in when it was added to a list widget
+///
+fn row_box_for_item(item: &gtk::Widget) -> Option<gtk::Box> {
+    // Items may have been placed in an event box
+    let parent = item.get_parent();
+    let parent = match parent.as_ref().and_then(|parent| parent.clone().dynamic_cast::<gtk::EventBox>().ok()) {
+        Some(event_box) => event_box.get_parent(),
+        None            => parent
+    };
+
+    parent.and_then(|parent| parent.dynamic_cast::<gtk::Box>().ok())
+}
+
+///
+/// Updates the row containing an item in a list widget (if it's in one) after its level or expanded state has changed
+///
+fn update_list_row(item: &gtk::Widget, list_item: &ListItem) {
+    let row_box     = match row_box_for_item(item) { Some(row_box) => row_box, None => { return; } };
+    let disclosure  = row_box.get_children().into_iter().nth(0).and_then(|disclosure| disclosure.dynamic_cast::<gtk::Button>().ok());
+
+    row_box.set_margin_start(list_item.level as i32 * INDENT_PER_LEVEL);
+
+    if let Some(disclosure) = disclosure {
+        // Items without children keep the space for the disclosure button so they line up with their siblings
+        disclosure.set_child_visible(list_item.expanded.is_some());
+        disclosure.set_label(if list_item.expanded == Some(true) { "\u{25be}" } else { "\u{25b8}" });
+    }
+}
+
+///
+/// Processes an items action for a widget that's being displayed as an item in a list or tree view
+///
+pub fn process_list_item_action<W: GtkUiWidget>(widget: &W, flo_gtk: &mut FloGtk, items: &WidgetItems) {
+    use self::WidgetItems::*;
+
+    let widget_data = flo_gtk.widget_data();
+    let list_item   = widget_data.get_widget_data_or_insert(widget.id(), || ListItem { level: 0, expanded: None });
+
+    if let Some(list_item) = list_item {
+        let mut list_item = list_item.borrow_mut();
+
+        match items {
+            &SetLevel(level)            => { list_item.level = level; },
+            &SetExpanded(expanded)      => { list_item.expanded = Some(expanded); },
+
+            // The other item actions only apply to the list itself
+            &SetSelectionMode(_)        |
+            &SetRowHeight(_)            |
+            &SetCount(_)                |
+            &SetFirstIndex(_)           => { }
+        }
+
+        update_list_row(widget.get_underlying(), &*list_item);
+    }
+}
+
+///
+/// Selects or deselects the row containing an item in a list widget (if it's in one)
+///
+pub fn select_list_item(item: &gtk::Widget, selected: bool) {
+    let row         = row_box_for_item(item).and_then(|row_box| row_box.get_parent()).and_then(|row| row.dynamic_cast::<gtk::ListBoxRow>().ok());
+    let row         = match row { Some(row) => row, None => { return; } };
+    let list_box    = row.get_parent().and_then(|list_box| list_box.dynamic_cast::<gtk::ListBox>().ok());
+
+    if let Some(list_box) = list_box {
+        UPDATING_SELECTION.with(|updating| updating.set(true));
+        if selected { list_box.select_row(Some(&row)); } else { list_box.unselect_row(&row); }
+        UPDATING_SELECTION.with(|updating| updating.set(false));
+    }
+}
+
+impl GtkUiWidget for FloListWidget {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn process(&mut self, flo_gtk: &mut FloGtk, action: &GtkWidgetAction) {
+        use self::GtkWidgetAction::*;
+        use self::WidgetItems::*;
+
+        match action {
+            &Items(SetSelectionMode(mode))  => { self.list_box.set_selection_mode(Self::gtk_selection_mode(mode)); },
+            &Items(SetRowHeight(height))    => { self.row_height = height; self.update_row_heights(); self.update_margins(); },
+            &Items(SetCount(count))         => { self.count = Some(count); self.update_margins(); },
+            &Items(SetFirstIndex(index))    => { self.first_index.set(index); self.update_margins(); },
+
+            &RequestEvent(GtkWidgetEventType::SelectItems, ref name)                    => { self.connect_select_items(flo_gtk.get_event_sink(), name.clone()); },
+            &RequestEvent(GtkWidgetEventType::ExpandItem, ref name)                     => { *self.expand_event.borrow_mut() = Some((flo_gtk.get_event_sink(), name.clone())); },
+            &RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), ref name)   => { self.start_virtual_scrolling(flo_gtk.get_event_sink(), name.clone(), width, height); },
+
+            // All other actions are basic actions
+            other_action                    => { process_basic_widget_action(self, flo_gtk, other_action); }
+        }
+    }
+
+    fn set_children(&mut self, children: Vec<Rc<RefCell<dyn GtkUiWidget>>>) {
+        // Remove the existing rows
+        self.list_box.get_children().iter().for_each(|row| self.list_box.remove(row));
+
+        // Create a new row for each child
+        let mut item_ids = vec![];
+
+        for child in children {
+            let child       = child.borrow();
+            let item_id     = child.id();
+            let item        = child.get_underlying();
+            let row         = self.create_row(item_id, item);
+
+            self.list_box.add(&row);
+            item_ids.push(item_id);
+
+            // Items are selected using the 'selected' class
+            if item.get_style_context().has_class("selected") {
+                select_list_item(item, true);
+            }
+        }
+
+        *self.item_ids.borrow_mut() = item_ids;
+        self.update_margins();
+    }
+
+    fn get_underlying<'a>(&'a self) -> &'a gtk::Widget {
+        &self.as_widget
+    }
+}
@@ -0,0 +1,200 @@
+use super::widget::*;
+use super::widget_data::*;
+use super::basic_widget::*;
+use super::flo_fixed_widget::*;
+use super::super::gtk_event::*;
+use super::super::gtk_action::*;
+use super::super::gtk_thread::*;
+use super::super::gtk_event_parameter::*;
+use super::super::gtk_widget_event_type::*;
+
+use flo_ui::*;
+
+use gtk;
+use gtk::prelude::*;
+use gdk;
+
+use std::rc::*;
+use std::cell::*;
+
+///
+/// Data used with a window widget
+///
+struct FloWindowData {
+    /// Set to true if this window should be considered open
+    is_open: bool,
+
+    /// How the window is presented
+    kind: WindowKind,
+
+    /// The event sink and the name of the action to send when the user closes the window
+    dismiss: Option<(GtkEventSink, String)>
+}
+
+///
+/// The window widget displays its content in a separate top-level window
+///
+/// The window is only shown while the base widget is part of another window, so it opens and closes along with the
+/// part of the UI that contains it.
+///
+pub struct FloWindowWidget {
+    /// The ID of the widget
+    id: WidgetId,
+
+    /// The content of the window
+    content: FloFixedWidget,
+
+    /// The window itself
+    window: gtk::Window,
+
+    /// The base widget, which is placed in the parent widget
+    widget: gtk::Widget,
+
+    /// Data shared with the event handlers
+    window_data: Rc<RefCell<FloWindowData>>
+}
+
+impl FloWindowWidget {
+    ///
+    /// Creates a new window widget
+    ///
+    pub fn new<Src: Clone+Cast+IsA<gtk::Widget>>(id: WidgetId, widget: Src, widget_data: Rc<WidgetData>) -> FloWindowWidget {
+        // Create the various components
+        let widget          = widget.upcast::<gtk::Widget>();
+        let window          = gtk::Window::new(gtk::WindowType::Toplevel);
+        let content         = gtk::Layout::new::<gtk::Adjustment, gtk::Adjustment>(None, None);
+
+        let window_data     = Rc::new(RefCell::new(FloWindowData {
+            is_open:    true,
+            kind:       WindowKind::Normal,
+            dismiss:    None
+        }));
+
+        // Set them up
+        window.add(&content);
+        window.set_default_size(DEFAULT_WINDOW_SIZE.0 as i32, DEFAULT_WINDOW_SIZE.1 as i32);
+        window.get_style_context().add_class("flo-window");
+
+        Self::connect_show_with_base(&widget, window.clone(), Rc::clone(&window_data));
+        Self::connect_close(&window, id, Rc::clone(&window_data));
+
+        // Content widget used to contain the content for this window
+        let content = FloFixedWidget::new(id, content, widget_data);
+
+        FloWindowWidget {
+            id:             id,
+            content:        content,
+            window:         window,
+            widget:         widget,
+            window_data:    window_data
+        }
+    }
+
+    ///
+    /// Shows or hides the window whenever the base widget moves in or out of another window
+    ///
+    fn connect_show_with_base(base_widget: &gtk::Widget, window: gtk::Window, window_data: Rc<RefCell<FloWindowData>>) {
+        base_widget.connect_hierarchy_changed(move |base_widget, _| {
+            window_data.borrow().update_visibility(base_widget, &window);
+        });
+    }
+
+    ///
+    /// Sends the dismiss action when the user closes the window (or just hides the window if there's no dismiss action)
+    ///
+    fn connect_close(window: &gtk::Window, widget_id: WidgetId, window_data: Rc<RefCell<FloWindowData>>) {
+        window.connect_delete_event(move |window, _event| {
+            let dismiss = window_data.borrow().dismiss.clone();
+
+            if let Some((sink, action_name)) = dismiss {
+                // The controller decides whether or not the window actually closes
+                publish_event(&sink, GtkEvent::Event(widget_id, action_name, GtkEventParameter::None));
+            } else {
+                window_data.borrow_mut().is_open = false;
+                window.hide();
+            }
+
+            gtk::Inhibit(true)
+        });
+    }
+}
+
+impl FloWindowData {
+    ///
+    /// Shows the window if it's open and the base widget is in a window, or hides it otherwise
+    ///
+    fn update_visibility(&self, base_widget: &gtk::Widget, window: &gtk::Window) {
+        let parent_window = base_widget.get_toplevel()
+            .and_then(|toplevel| toplevel.dynamic_cast::<gtk::Window>().ok())
+            .filter(|toplevel| toplevel != window);
+
+        match (self.is_open, parent_window) {
+            (true, Some(parent_window)) => {
+                // Utility windows stay above the window that contains them
+                match self.kind {
+                    WindowKind::Normal  => window.set_transient_for::<gtk::Window>(None),
+                    WindowKind::Utility => window.set_transient_for(Some(&parent_window))
+                }
+
+                window.show_all();
+            },
+
+            _ => window.hide()
+        }
+    }
+}
+
+impl GtkUiWidget for FloWindowWidget {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn process(&mut self, flo_gtk: &mut FloGtk, action: &GtkWidgetAction) {
+        use GtkWidgetAction::{Window, RequestEvent, Delete};
+        use WidgetWindow::*;
+
+        match action {
+            &Window(SetTitle(ref title))    => { self.window.set_title(&*title); },
+            &Window(SetSize(width, height)) => { self.window.set_default_size(width as i32, height as i32); },
+
+            &Window(SetKind(kind))          => {
+                self.window_data.borrow_mut().kind = kind;
+
+                let type_hint = match kind {
+                    WindowKind::Normal  => gdk::WindowTypeHint::Normal,
+                    WindowKind::Utility => gdk::WindowTypeHint::Utility
+                };
+                self.window.set_type_hint(type_hint);
+                self.window_data.borrow().update_visibility(&self.widget, &self.window);
+            },
+
+            &Window(SetOpen(is_open))       => {
+                self.window_data.borrow_mut().is_open = is_open;
+                self.window_data.borrow().update_visibility(&self.widget, &self.window);
+            },
+
+            &RequestEvent(GtkWidgetEventType::Dismiss, ref action_name) => {
+                let sink = flo_gtk.get_event_sink();
+                self.window_data.borrow_mut().dismiss = Some((sink, action_name.clone()));
+            },
+
+            &Delete                         => {
+                // The window goes away along with the widget
+                unsafe { self.window.destroy(); }
+                process_basic_widget_action(self, flo_gtk, action);
+            },
+
+            // Everything else is processed as if we were a basic widget
+            other_action    => { process_basic_widget_action(self, flo_gtk, other_action); }
+        }
+    }
+
+    fn set_children(&mut self, children: Vec<Rc<RefCell<dyn GtkUiWidget>>>) {
+        // Pass on to the content widget
+        self.content.set_children(children);
+    }
+
+    fn get_underlying<'a>(&'a self) -> &'a gtk::Widget {
+        &self.widget
+    }
+}
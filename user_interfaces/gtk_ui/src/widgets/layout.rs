@@ -1,6 +1,9 @@
 use super::super::gtk_action::*;
 
 use flo_ui::*;
+use flo_ui as ui;
+
+use std::mem;
 
 ///
 /// Data associated with a widget used for describing how it will be laid out
@@ -14,7 +17,16 @@ pub struct Layout {
     pub padding: Option<(u32, u32, u32, u32)>,
 
     /// The Z-index for this widget
-    pub z_index: Option<u32>
+    pub z_index: Option<u32>,
+
+    /// The layout attributes for this widget (the most recent attribute of each kind)
+    pub layout: Vec<ui::Layout>,
+
+    /// True if this widget takes up no space in its parent
+    pub detached: bool,
+
+    /// How changes to the layout of this widget are animated (the most recent attribute of each kind)
+    pub transitions: Vec<ui::Transition>
 }
 
 impl Layout {
@@ -23,9 +35,12 @@ impl Layout {
     ///
     pub fn new() -> Layout {
         Layout {
-            bounds:     None,
-            padding:    None,
-            z_index:    None
+            bounds:         None,
+            padding:        None,
+            z_index:        None,
+            layout:         vec![],
+            detached:       false,
+            transitions:    vec![]
         }
     }
 
@@ -39,7 +54,19 @@ impl Layout {
             &BoundingBox(ref bounds)                => self.bounds = Some(bounds.clone()),
             &ZIndex(z_index)                        => self.z_index = Some(z_index),
             &Padding((left, top), (right, bottom))  => self.padding = Some((left, top, right, bottom)),
-            &Floating(_, _)                         => ()
+            &WidgetLayout::Layout(ref layout)       => {
+                // Replace any existing attribute of the same kind
+                self.layout.retain(|existing| mem::discriminant(existing) != mem::discriminant(layout));
+                self.layout.push(layout.clone());
+            },
+            &Detached                               => self.detached = true,
+            &Transition(ref transition)             => {
+                // Replace any existing transition for the same property
+                self.transitions.retain(|existing| mem::discriminant(existing) != mem::discriminant(transition));
+                self.transitions.push(transition.clone());
+            },
+            &Floating(_, _)                         => (),
+            &Replaces(_)                            => ()
         }
     }
 }
@@ -0,0 +1,276 @@
+use super::widget::*;
+use super::super::gtk_event::*;
+use super::super::gtk_action::*;
+use super::super::gtk_thread::*;
+use super::super::gtk_event_parameter::*;
+
+use flo_ui::*;
+
+use gtk;
+use gtk::prelude::*;
+use gdk;
+
+use std::rc::*;
+use std::cell::*;
+use std::collections::HashMap;
+
+/// The style class of the box that holds the content of a window (the root widget, and the menu bar if there is one)
+pub const WINDOW_CONTENT_CLASS: &str = "flo-window-content";
+
+///
+/// The menus attached to a widget (stored as widget data)
+///
+struct MenuState {
+    /// The menu bar for the window containing this widget
+    menu_bar: Option<gtk::MenuBar>,
+
+    /// The menu displayed when this widget is right-clicked
+    context_menu: Option<gtk::Menu>,
+
+    /// The menu items for each command, by command ID
+    commands: HashMap<String, gtk::MenuItem>,
+
+    /// The event sink and action name to use when a command is picked
+    select_event: Rc<RefCell<Option<(GtkEventSink, String)>>>,
+
+    /// Set to true while we're updating the menu items (so changes are not reported as the user picking a command)
+    updating: Rc<Cell<bool>>,
+
+    /// True if we've connected the right-click event for the context menu
+    context_menu_wired: bool,
+
+    /// True if we've connected the event that installs the menu bar when the widget is added to a window
+    menu_bar_wired: bool
+}
+
+impl MenuState {
+    fn new() -> MenuState {
+        MenuState {
+            menu_bar:           None,
+            context_menu:       None,
+            commands:           HashMap::new(),
+            select_event:       Rc::new(RefCell::new(None)),
+            updating:           Rc::new(Cell::new(false)),
+            context_menu_wired: false,
+            menu_bar_wired:     false
+        }
+    }
+}
+
+///
+/// Converts a shortcut description (eg, 'Ctrl+Shift+Z') to a GTK accelerator string (eg, '<Control><Shift>z')
+///
+fn accelerator_for_shortcut(shortcut: &str) -> String {
+    let mut accelerator = String::new();
+    let mut parts       = shortcut.split('+').map(|part| part.trim()).collect::<Vec<_>>();
+    let key             = parts.pop().unwrap_or("");
+
+    for modifier in parts {
+        match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" | "cmd" | "command"  => accelerator.push_str("<Control>"),
+            "shift"                                 => accelerator.push_str("<Shift>"),
+            "alt" | "option"                        => accelerator.push_str("<Alt>"),
+            _                                       => { }
+        }
+    }
+
+    if key.chars().count() == 1 {
+        accelerator.push_str(&key.to_lowercase());
+    } else {
+        accelerator.push_str(key);
+    }
+
+    accelerator
+}
+
+///
+/// Returns the initial text for a label property (bound labels are filled in when the property is evaluated)
+///
+fn initial_label(label: &Property) -> String {
+    match label {
+        Property::String(label) => label.clone(),
+        _                       => String::new()
+    }
+}
+
+///
+/// Adds GTK menu items for a set of Flo menu items to a menu shell
+///
+fn create_menu_items<Shell: IsA<gtk::MenuShell>>(widget_id: WidgetId, shell: &Shell, items: &Vec<MenuItem>, state: &mut MenuState) {
+    for item in items.iter() {
+        match item {
+            MenuItem::Separator                 => {
+                shell.append(&gtk::SeparatorMenuItem::new());
+            },
+
+            MenuItem::SubMenu(label, sub_items) => {
+                let menu_item   = gtk::MenuItem::with_label(&initial_label(label));
+                let sub_menu    = gtk::Menu::new();
+
+                create_menu_items(widget_id, &sub_menu, sub_items, state);
+                menu_item.set_submenu(Some(&sub_menu));
+                shell.append(&menu_item);
+            },
+
+            MenuItem::Command(command)          => {
+                let label       = initial_label(&command.label);
+                let menu_item   = if command.checked.is_some() {
+                    gtk::CheckMenuItem::with_label(&label).upcast::<gtk::MenuItem>()
+                } else {
+                    gtk::MenuItem::with_label(&label)
+                };
+
+                // Shortcuts are displayed alongside the label
+                if let Some(ref shortcut) = command.shortcut {
+                    let (key, modifiers) = gtk::accelerator_parse(&accelerator_for_shortcut(shortcut));
+                    let accel_label      = menu_item.get_child().and_then(|child| child.dynamic_cast::<gtk::AccelLabel>().ok());
+
+                    accel_label.map(|accel_label| accel_label.set_accel(key, modifiers));
+                }
+
+                // Picking the item generates the select menu item event
+                let select_event    = Rc::clone(&state.select_event);
+                let updating        = Rc::clone(&state.updating);
+                let command_id      = command.id.clone();
+                menu_item.connect_activate(move |menu_item| {
+                    if updating.get() { return; }
+
+                    // The checked state of an item is set by the controller, so undo GTK's toggle
+                    if let Some(check_item) = menu_item.clone().dynamic_cast::<gtk::CheckMenuItem>().ok() {
+                        updating.set(true);
+                        check_item.set_active(!check_item.get_active());
+                        updating.set(false);
+                    }
+
+                    if let Some((ref sink, ref action_name)) = *select_event.borrow() {
+                        publish_event(sink, GtkEvent::Event(widget_id, action_name.clone(), GtkEventParameter::MenuItem(command_id.clone())));
+                    }
+                });
+
+                state.commands.insert(command.id.clone(), menu_item.clone());
+                shell.append(&menu_item);
+            }
+        }
+    }
+}
+
+///
+/// Adds a menu bar to the window containing a widget, if the widget is the root of a window
+///
+fn install_menu_bar(widget: &gtk::Widget, menu_bar: &gtk::MenuBar) {
+    let content = widget.get_parent()
+        .and_then(|parent| parent.dynamic_cast::<gtk::Box>().ok())
+        .filter(|content| content.get_style_context().has_class(WINDOW_CONTENT_CLASS));
+
+    if let Some(content) = content {
+        if menu_bar.get_parent().as_ref() != Some(content.upcast_ref::<gtk::Widget>()) {
+            let previous_parent = menu_bar.get_parent().and_then(|parent| parent.dynamic_cast::<gtk::Container>().ok());
+            previous_parent.map(|previous_parent| previous_parent.remove(menu_bar));
+
+            content.pack_start(menu_bar, false, false, 0);
+        }
+
+        content.reorder_child(menu_bar, 0);
+        menu_bar.show_all();
+    }
+}
+
+///
+/// Processes a menu action for a widget
+///
+pub fn process_menu_action<W: GtkUiWidget>(widget: &W, flo_gtk: &mut FloGtk, menu: &WidgetMenu) {
+    use self::WidgetMenu::*;
+
+    let widget_id   = widget.id();
+    let widget_data = flo_gtk.widget_data();
+    let state       = match widget_data.get_widget_data_or_insert(widget_id, || MenuState::new()) { Some(state) => state, None => { return; } };
+    let weak_state  = Rc::downgrade(&*state);
+    let mut state   = state.borrow_mut();
+
+    match menu {
+        SetMenuBar(items)                       => {
+            // Replace any existing menu bar
+            let old_menu_bar = state.menu_bar.take();
+            let old_parent   = old_menu_bar.as_ref().and_then(|menu_bar| menu_bar.get_parent()).and_then(|parent| parent.dynamic_cast::<gtk::Container>().ok());
+            if let (Some(old_menu_bar), Some(old_parent)) = (old_menu_bar, old_parent) {
+                old_parent.remove(&old_menu_bar);
+            }
+
+            let menu_bar = gtk::MenuBar::new();
+            menu_bar.get_style_context().add_class("flo-menubar");
+            create_menu_items(widget_id, &menu_bar, items, &mut *state);
+
+            install_menu_bar(widget.get_underlying(), &menu_bar);
+            state.menu_bar = Some(menu_bar);
+
+            // The widget might not be the root of the window yet, so install the menu bar whenever it changes parent
+            if !state.menu_bar_wired {
+                state.menu_bar_wired = true;
+
+                widget.get_underlying().connect_parent_set(move |widget, _old_parent| {
+                    let menu_bar = weak_state.upgrade().and_then(|state| state.borrow().menu_bar.clone());
+                    menu_bar.map(|menu_bar| install_menu_bar(widget, &menu_bar));
+                });
+            }
+        },
+
+        SetContextMenu(items)                   => {
+            let context_menu = gtk::Menu::new();
+            context_menu.get_style_context().add_class("flo-contextmenu");
+            create_menu_items(widget_id, &context_menu, items, &mut *state);
+            context_menu.show_all();
+
+            state.context_menu = Some(context_menu);
+
+            // Right-clicking the widget displays the context menu
+            if !state.context_menu_wired {
+                state.context_menu_wired = true;
+
+                widget.get_underlying().add_events(gdk::EventMask::BUTTON_PRESS_MASK);
+                widget.get_underlying().connect_button_press_event(move |_, button| {
+                    let context_menu = weak_state.upgrade().and_then(|state| state.borrow().context_menu.clone());
+
+                    match context_menu {
+                        Some(context_menu) if button.get_button() == 3  => {
+                            context_menu.popup_easy(button.get_button(), button.get_time());
+                            Inhibit(true)
+                        },
+
+                        _                                               => Inhibit(false)
+                    }
+                });
+            }
+        },
+
+        SetCommandLabel(command_id, label)      => {
+            state.commands.get(command_id).map(|menu_item| menu_item.set_label(label));
+        },
+
+        SetCommandEnabled(command_id, enabled)  => {
+            state.commands.get(command_id).map(|menu_item| menu_item.set_sensitive(*enabled));
+        },
+
+        SetCommandChecked(command_id, checked)  => {
+            let check_item = state.commands.get(command_id).and_then(|menu_item| menu_item.clone().dynamic_cast::<gtk::CheckMenuItem>().ok());
+
+            if let Some(check_item) = check_item {
+                state.updating.set(true);
+                check_item.set_active(*checked);
+                state.updating.set(false);
+            }
+        }
+    }
+}
+
+///
+/// Sets the action that's generated when a command is picked from one of the menus attached to a widget
+///
+pub fn wire_select_menu_item<W: GtkUiWidget>(widget: &W, flo_gtk: &mut FloGtk, action_name: &String) {
+    let event_sink  = flo_gtk.get_event_sink();
+    let widget_data = flo_gtk.widget_data();
+    let state       = widget_data.get_widget_data_or_insert(widget.id(), || MenuState::new());
+
+    if let Some(state) = state {
+        *state.borrow().select_event.borrow_mut() = Some((event_sink, action_name.clone()));
+    }
+}
@@ -6,6 +6,7 @@ pub mod flo_bin_widget;
 pub mod flo_label_widget;
 pub mod flo_fixed_widget;
 pub mod flo_popover_widget;
+pub mod flo_window_widget;
 pub mod flo_scale_widget;
 pub mod flo_rotor_widget;
 pub mod flo_scroll_widget;
@@ -13,6 +14,8 @@ pub mod flo_canvas_widget;
 pub mod flo_overlay_widget;
 pub mod flo_textbox_widget;
 pub mod flo_checkbox_widget;
+pub mod flo_combobox_widget;
+pub mod flo_list_widget;
 pub mod flo_render_canvas_widget;
 pub mod flo_canvas_layout_widget;
 pub mod proxy_widget;
@@ -24,6 +27,8 @@ mod factory;
 mod image;
 mod click;
 mod drag;
+mod menu;
+mod accessibility;
 mod paint;
 mod events;
 mod scroll_size;
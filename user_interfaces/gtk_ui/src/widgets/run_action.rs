@@ -9,6 +9,7 @@ use gtk;
 use gtk::prelude::*;
 
 use std::rc::*;
+use std::cell::Cell;
 
 ///
 /// Executes a Gtk action
@@ -17,7 +18,8 @@ pub fn run_action(flo_gtk: &mut FloGtk, action: &GtkAction) {
     match action {
         &GtkAction::Stop                                    => gtk::main_quit(),
         &GtkAction::Window(window_id, ref window_action)    => run_window_action(flo_gtk, window_id, window_action),
-        &GtkAction::Widget(widget_id, ref widget_action)    => run_widget_action(flo_gtk, widget_id, widget_action)
+        &GtkAction::Widget(widget_id, ref widget_action)    => run_widget_action(flo_gtk, widget_id, widget_action),
+        &GtkAction::SetStyleSheet(ref style_sheet)          => flo_gtk.set_style_sheet(style_sheet)
     }
 }
 
@@ -29,7 +31,21 @@ fn wire_up_window(new_window: &gtk::Window, window_id: WindowId, flo_gtk: &mut F
     let event_sink = flo_gtk.get_event_sink();
 
     // Send the close event when the window is closed
-    new_window.connect_hide(move |_window| { publish_event(&event_sink, GtkEvent::CloseWindow(window_id)); });
+    let close_sink = event_sink.clone();
+    new_window.connect_hide(move |_window| { publish_event(&close_sink, GtkEvent::CloseWindow(window_id)); });
+
+    // Send the new size when the window is resized
+    let last_size = Cell::new(new_window.get_size());
+    new_window.connect_configure_event(move |window, _event| {
+        let new_size = window.get_size();
+
+        if new_size != last_size.get() {
+            last_size.set(new_size);
+            publish_event(&event_sink, GtkEvent::ResizeWindow(window_id, new_size.0, new_size.1));
+        }
+
+        false
+    });
 }
 
 ///
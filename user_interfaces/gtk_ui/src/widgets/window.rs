@@ -1,3 +1,4 @@
+use super::menu::*;
 use super::super::gtk_thread::*;
 use super::super::gtk_action::*;
 
@@ -33,9 +34,26 @@ impl GtkUiWindow for gtk::Window {
     }
 
     fn set_root(&mut self, _flo_gtk: &mut FloGtk, widget: &gtk::Widget) {
-        // Replace any existing child of this window with the specified widget
-        self.get_child().map(|child| self.remove(&child));
-        self.add(widget);
+        // The window contains a box that holds the root widget (and the menu bar if the root widget has one)
+        let content = self.get_child()
+            .and_then(|child| child.dynamic_cast::<gtk::Box>().ok())
+            .filter(|content| content.get_style_context().has_class(WINDOW_CONTENT_CLASS));
+        let content = match content {
+            Some(content)   => content,
+            None            => {
+                let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+                content.get_style_context().add_class(WINDOW_CONTENT_CLASS);
+
+                self.get_child().map(|child| self.remove(&child));
+                self.add(&content);
+                content
+            }
+        };
+
+        // Replace any existing content of the window with the specified widget (which will add its menu bar back if it has one)
+        content.get_children().iter().for_each(|child| content.remove(child));
+        content.pack_end(widget, true, true, 0);
+        content.show();
         widget.show_all();
     }
 }
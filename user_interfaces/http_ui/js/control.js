@@ -468,6 +468,57 @@ let flo_control = (function () {
         });
     };
 
+    ///
+    /// Sets up a control as a window
+    ///
+    let load_window = (window_node) => {
+        // Set the initial state (windows are open by default)
+        let is_open = window_node.flo_window_open || { 'Bool': true };
+        let title   = window_node.flo_window_title || { 'String': '' };
+
+        let title_node  = window_node.querySelector('deco-title > .title');
+        let close_node  = window_node.querySelector('deco-title > .close');
+
+        // Functions to update the state of the window
+        function set_is_open(new_open) {
+            is_open = new_open;
+
+            window_node.style.visibility = (is_open['Bool'] !== false) ? 'visible' : 'hidden';
+        }
+
+        function set_title(new_title) {
+            title = new_title;
+
+            if (title_node) {
+                title_node.textContent = title['String'] || '';
+            }
+        }
+
+        set_is_open(is_open);
+        set_title(title);
+
+        // Replace the window properties with ones that update the window
+        Object.defineProperty(window_node, 'flo_window_open', {
+            get: () => is_open,
+            set: new_value => set_is_open(new_value)
+        });
+        Object.defineProperty(window_node, 'flo_window_title', {
+            get: () => title,
+            set: new_value => set_title(new_value)
+        });
+
+        // The close button sends the dismiss action, if there is one
+        if (close_node) {
+            close_node.addEventListener('click', () => {
+                if (window_node.flo_dismiss) {
+                    window_node.flo_dismiss();
+                } else {
+                    set_is_open({ 'Bool': false });
+                }
+            });
+        }
+    };
+
     ///
     /// Determines if a document element is the root element or not
     ///
@@ -655,6 +706,28 @@ let flo_control = (function () {
         return target_area;
     };
 
+    ///
+    /// Performs layout on a window control
+    ///
+    /// Windows are shown as panels floating in the centre of the page
+    ///
+    let layout_window = (window_node, attributes) => {
+        let window      = attributes.window();
+        let size        = window['Size'] || [400, 300];
+
+        // Windows are centred in the area available to their parent
+        let layout_area = total_client_area(window_node.parentNode);
+        let x1          = Math.max(layout_area.x1, (layout_area.x1+layout_area.x2)/2.0 - size[0]/2.0);
+        let y1          = Math.max(layout_area.y1, (layout_area.y1+layout_area.y2)/2.0 - size[1]/2.0);
+
+        return {
+            x1: x1,
+            y1: y1,
+            x2: x1 + size[0],
+            y2: y1 + size[1]
+        };
+    };
+
     ///
     /// Recurses through a node and ensures that any item it contains that has a fixed scroll position stays where it is
     /// We use the margin CSS property for this, which makes it unavailable for other uses
@@ -813,13 +886,280 @@ let flo_control = (function () {
         });
     };
 
+    ///
+    /// Loads a combo box control (the options are the text of its flo subnodes)
+    ///
+    let load_combobox = (node, add_action_event) => {
+        // Get the select element
+        let flo_value   = node.flo_value || { 'Int': -1 };
+        let select      = node.getElementsByTagName('select')[0];
+
+        // The options are read from the text of the flo nodes inside this one (which are not displayed)
+        let update_options = () => {
+            let options = [].slice.apply(node.children)
+                .filter(element => element.tagName.toLowerCase().startsWith('flo-'))
+                .map(element => element.textContent);
+
+            while (select.options.length > 0) {
+                select.remove(0);
+            }
+
+            options.forEach(option_text => {
+                let option          = document.createElement('option');
+                option.textContent  = option_text;
+                select.appendChild(option);
+            });
+
+            select.selectedIndex = flo_value['Int'] !== undefined ? flo_value['Int'] : -1;
+        };
+
+        // Update the options whenever the subnodes change
+        let observer = new MutationObserver(() => update_options());
+        observer.observe(node, { childList: true, subtree: true, characterData: true });
+        update_options();
+
+        // Define a property to update the selected option
+        Object.defineProperty(node, 'flo_value', {
+            get: () => flo_value,
+            set: new_value => {
+                if (new_value !== flo_value) {
+                    flo_value = new_value;
+                    select.selectedIndex = new_value['Int'] !== undefined ? new_value['Int'] : -1;
+                }
+            }
+        });
+
+        // Create 'SetValue' events when the user picks a new option
+        add_action_event(select, 'change', event => {
+            if (node.flo_set_value) {
+                node.flo_set_value({ 'Int': select.selectedIndex });
+            }
+        });
+
+        add_action_event(node, 'focus', event => {
+            if (node.flo_was_focused) {
+                node.flo_was_focused();
+            }
+        });
+    };
+
+    ///
+    /// The default height of a row in a list or tree view
+    ///
+    const default_row_height = 24;
+
+    ///
+    /// How far each level in a tree view is indented
+    ///
+    const tree_indent = 16;
+
+    ///
+    /// Positions the items in a list or tree view in rows
+    ///
+    let layout_list_rows = (node) => {
+        let row_height  = node.flo_row_height || default_row_height;
+        let first_index = node.flo_first_index || 0;
+        let items       = [].slice.apply(node.children).filter(element => element.tagName.toLowerCase().startsWith('flo-'));
+        let item_count  = node.flo_item_count !== undefined ? node.flo_item_count : first_index + items.length;
+        let is_tree     = node.tagName.toLowerCase() === 'flo-treeview';
+        let width       = node.clientWidth;
+
+        items.forEach((item, index) => {
+            // Tree items are indented according to their level, with space for the disclosure button
+            let indent = is_tree ? ((item.flo_item_level || 0) + 1) * tree_indent : 0;
+
+            item.style.left     = indent + 'px';
+            item.style.top      = ((first_index + index) * row_height) + 'px';
+            item.style.width    = (width - indent) + 'px';
+            item.style.height   = row_height + 'px';
+        });
+
+        // The scroll canvas makes the list scroll as if every item was present
+        let canvas_deco = node.getElementsByTagName('deco-scroll-canvas')[0];
+        if (canvas_deco) {
+            canvas_deco.style.height = (item_count * row_height) + 'px';
+        }
+    };
+
+    ///
+    /// Loads a list or tree view control
+    ///
+    let load_list = (node, add_action_event) => {
+        let last_clicked = null;
+
+        // Returns the index of the item containing a DOM element, and the item itself
+        let find_item = (element) => {
+            let items = [].slice.apply(node.children).filter(element => element.tagName.toLowerCase().startsWith('flo-'));
+
+            while (element && element.parentElement !== node) {
+                element = element.parentElement;
+            }
+
+            let index = items.indexOf(element);
+            return index >= 0 ? { index: index + (node.flo_first_index || 0), item: element } : null;
+        };
+
+        // Returns the indexes of the items that are currently selected
+        let current_selection = () => {
+            let first_index = node.flo_first_index || 0;
+
+            return [].slice.apply(node.children)
+                .filter(element => element.tagName.toLowerCase().startsWith('flo-'))
+                .map((element, index) => element.classList.contains('selected') ? index + first_index : null)
+                .filter(index => index !== null);
+        };
+
+        add_action_event(node, 'click', event => {
+            let clicked = find_item(event.target);
+            if (!clicked) {
+                return;
+            }
+
+            // Clicking to the left of a tree item expands or collapses it
+            let is_expandable = clicked.item.classList.contains('expanded') || clicked.item.classList.contains('collapsed');
+            if (is_expandable && event.clientX < clicked.item.getBoundingClientRect().left) {
+                if (node.flo_expand_item) {
+                    node.flo_expand_item(clicked.index, !clicked.item.classList.contains('expanded'));
+                }
+                return;
+            }
+
+            // Otherwise, clicking changes the selection
+            let selection_mode  = node.flo_selection_mode || 'Single';
+            let selection       = [clicked.index];
+
+            if (selection_mode === 'None') {
+                return;
+            } else if (selection_mode === 'Multiple' && (event.ctrlKey || event.metaKey)) {
+                let existing    = current_selection();
+                selection       = existing.indexOf(clicked.index) >= 0 ? existing.filter(index => index !== clicked.index) : existing.concat([clicked.index]);
+            } else if (selection_mode === 'Multiple' && event.shiftKey && last_clicked !== null) {
+                let from        = Math.min(last_clicked, clicked.index);
+                let to          = Math.max(last_clicked, clicked.index);
+                selection       = [];
+                for (let index=from; index<=to; ++index) {
+                    selection.push(index);
+                }
+            }
+
+            last_clicked = clicked.index;
+
+            if (node.flo_select_items) {
+                node.flo_select_items(selection);
+            }
+        });
+    };
+
+    ///
+    /// Creates the elements for a list of menu items (in the format sent by the server), calling on_select
+    /// with the ID of a command when it's picked
+    ///
+    /// Returns the menu element and an object mapping command IDs to the elements for those commands
+    ///
+    let create_menu = (tag_name, menu_items, on_select) => {
+        let menu_element    = document.createElement(tag_name);
+        let commands        = {};
+
+        let add_items = (parent, items) => {
+            items.forEach(item => {
+                if (item === 'Separator') {
+                    parent.appendChild(document.createElement('deco-menu-separator'));
+
+                } else if (item['SubMenu']) {
+                    // Submenus are displayed when the mouse is over their label
+                    let sub_menu_item   = document.createElement('deco-menu-item');
+                    let label           = document.createElement('deco-menu-label');
+                    let sub_menu        = document.createElement('deco-menu');
+
+                    label.innerText     = item['SubMenu'][0]['String'] || '';
+                    sub_menu_item.classList.add('submenu');
+                    sub_menu_item.appendChild(label);
+                    sub_menu_item.appendChild(sub_menu);
+                    add_items(sub_menu, item['SubMenu'][1]);
+
+                    parent.appendChild(sub_menu_item);
+
+                } else if (item['Command']) {
+                    // Commands call on_select when they're clicked
+                    let command         = item['Command'];
+                    let command_item    = document.createElement('deco-menu-item');
+                    let label           = document.createElement('deco-menu-label');
+                    let shortcut        = document.createElement('deco-menu-shortcut');
+
+                    label.innerText     = command['label']['String'] || '';
+                    shortcut.innerText  = command['shortcut'] || '';
+                    if (command['checked']) {
+                        command_item.classList.add('checkable');
+                    }
+
+                    command_item.appendChild(label);
+                    command_item.appendChild(shortcut);
+                    command_item.addEventListener('click', event => {
+                        event.stopPropagation();
+
+                        if (!command_item.classList.contains('disabled')) {
+                            on_select(command['id']);
+                        }
+                    });
+
+                    commands[command['id']] = command_item;
+                    parent.appendChild(command_item);
+                }
+            });
+        };
+
+        add_items(menu_element, menu_items);
+
+        return { element: menu_element, commands: commands };
+    };
+
+    ///
+    /// Displays a context menu at the specified position, until the user clicks somewhere
+    ///
+    let show_context_menu = (menu_element, x, y) => {
+        menu_element.style.left = x + 'px';
+        menu_element.style.top  = y + 'px';
+        document.body.appendChild(menu_element);
+
+        // Keep the menu inside the window
+        let bounds = menu_element.getBoundingClientRect();
+        if (bounds.right > window.innerWidth) {
+            menu_element.style.left = Math.max(0, x - bounds.width) + 'px';
+        }
+        if (bounds.bottom > window.innerHeight) {
+            menu_element.style.top = Math.max(0, y - bounds.height) + 'px';
+        }
+
+        // Any click (including picking a command) hides the menu again
+        let dismiss = () => {
+            document.removeEventListener('click', dismiss, true);
+            document.removeEventListener('contextmenu', dismiss, true);
+
+            if (menu_element.parentNode) {
+                menu_element.parentNode.removeChild(menu_element);
+            }
+        };
+
+        requestAnimationFrame(() => {
+            document.addEventListener('click', dismiss, true);
+            document.addEventListener('contextmenu', dismiss, true);
+        });
+    };
+
     return {
         load_slider:            load_slider,
         load_rotor:             load_rotor,
         load_popup:             load_popup,
+        load_window:            load_window,
         load_textbox:           load_textbox,
         load_checkbox:          load_checkbox,
+        load_combobox:          load_combobox,
+        load_list:              load_list,
+        layout_list_rows:       layout_list_rows,
         layout_popup:           layout_popup,
+        layout_window:          layout_window,
+        create_menu:            create_menu,
+        show_context_menu:      show_context_menu,
         on_drag:                on_drag,
         fix_scroll_positions:   fix_scroll_positions
     };
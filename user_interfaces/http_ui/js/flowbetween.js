@@ -580,6 +580,28 @@ function flowbetween(root_node) {
             return Object.assign.apply(null, [{}].concat(popups));
         };
 
+        // window returns the list of window attributes (combined into a single object)
+        let window_attrs = () => {
+            let windows = get_attrs('Window');
+            return Object.assign.apply(null, [{}].concat(windows));
+        };
+
+        // transitions returns the list of transition attributes for this control
+        let transitions = () => {
+            return get_attrs('Transition') || [];
+        };
+
+        // layout returns the list of layout attributes for this control
+        let layout = () => {
+            return get_attrs('Layout') || [];
+        };
+
+        // items returns the list of attributes describing the items in a list (combined into a single object)
+        let items = () => {
+            let items = get_attrs('Items');
+            return Object.assign.apply(null, [{}].concat(items));
+        };
+
         // Return an object that can be used to get information about these attributes
         return {
             all:            all,
@@ -590,7 +612,11 @@ function flowbetween(root_node) {
             bounding_box:   bounding_box,
             padding:        padding,
             popup:          popup,
-            scrolls:        scrolls
+            window:         window_attrs,
+            scrolls:        scrolls,
+            transitions:    transitions,
+            layout:         layout,
+            items:          items
         };
     };
 
@@ -775,6 +801,63 @@ function flowbetween(root_node) {
         }
     };
 
+    ///
+    /// Returns the layout mode of a control ('Bounds' if it doesn't set one)
+    ///
+    let layout_mode = (attributes) => {
+        let mode = 'Bounds';
+
+        attributes.layout().forEach(layout => {
+            if (layout['Mode'] !== undefined) {
+                // Grids are encoded as { Grid: columns }
+                mode = typeof(layout['Mode']) === 'string' ? layout['Mode'] : 'Grid';
+            }
+        });
+
+        return mode;
+    };
+
+    ///
+    /// Finds the address of a flo node (the reverse of node_at_address)
+    ///
+    let address_of_node = (node) => {
+        let root_control    = get_flo_subnodes(root_node)[0];
+        let address         = [];
+
+        while (node && node !== root_control) {
+            let parent_node = node.parentNode;
+
+            address.unshift(get_flo_subnodes(parent_node).indexOf(node));
+            node = parent_node;
+        }
+
+        return address;
+    };
+
+    ///
+    /// Asks the server where the subcomponents of a node go when it's laid out in an area of the specified size
+    ///
+    /// The node is laid out again when the positions arrive in an 'UpdateLayout' update.
+    ///
+    let request_layout = (node, width, height) => {
+        node.flo_layout_requested = [width, height];
+
+        let request = make_request([ make_event({ Layout: [address_of_node(node), width, height] }) ], running_session_id);
+        send_request(request);
+    };
+
+    ///
+    /// Forgets the sizes that the positions of the subcomponents of a node and its ancestors were requested for
+    ///
+    /// The content of a node affects how its ancestors are laid out, so they all need to ask the server again.
+    ///
+    let invalidate_layout_requests = (node) => {
+        while (node && node !== root_node) {
+            node.flo_layout_requested = null;
+            node = node.parentNode;
+        }
+    };
+
     ///
     /// Lays out the subcomponents associated with a particular node
     ///
@@ -807,12 +890,65 @@ function flowbetween(root_node) {
             });
         }
 
+        // List and tree views lay out their items in rows rather than by their bounding boxes
+        let tag_name = parent_node.tagName.toLowerCase();
+        if (tag_name === 'flo-listview' || tag_name === 'flo-treeview') {
+            let list_items = attributes.items();
+
+            parent_node.flo_row_height      = list_items['RowHeight'];
+            parent_node.flo_selection_mode  = list_items['Selection'];
+
+            for (let node_index=0; node_index<subcomponents.length; ++node_index) {
+                subnodes[node_index].flo_item_level = get_attributes(subcomponents[node_index]).items()['Level'] || 0;
+            }
+
+            flo_control.layout_list_rows(parent_node);
+            return;
+        }
+
         // Take account of the padding
         let padding = attributes.padding() || { top: 0, left: 0, right: 0, bottom: 0 };
 
         total_width     -= padding.left+padding.right;
         total_height    -= padding.top+padding.bottom;
 
+        // Containers with a layout mode have their subcomponents placed in rows, columns, etc by the server
+        if (layout_mode(attributes) !== 'Bounds') {
+            let width       = Math.max(total_width, 0);
+            let height      = Math.max(total_height, 0);
+            let requested   = parent_node.flo_layout_requested;
+            let placements  = parent_node.flo_layout_positions;
+
+            if (!requested || requested[0] !== width || requested[1] !== height) {
+                request_layout(parent_node, width, height);
+            }
+
+            // Use the last positions we received until the new ones arrive
+            if (!placements || placements.length !== subcomponents.length) {
+                return;
+            }
+
+            for (let node_index=0; node_index<subcomponents.length; ++node_index) {
+                let element                 = subnodes[node_index];
+                let [[x1, y1], [x2, y2]]    = placements[node_index];
+                let prev_width              = element.clientWidth;
+                let prev_height             = element.clientHeight;
+
+                element.style.left      = (x1+padding.left) + 'px';
+                element.style.top       = (y1+padding.top) + 'px';
+                element.style.width     = (x2-x1) + 'px';
+                element.style.height    = (y2-y1) + 'px';
+
+                // If the node has an on resize property, then call that after laying it out
+                let on_resize = element.flo_resize;
+                if (on_resize && (prev_width !== element.clientWidth || prev_height !== element.clientHeight)) {
+                    on_resize(element.clientWidth, element.clientHeight, element);
+                }
+            }
+
+            return;
+        }
+
         // First pass: position all of the nodes, assuming stretch nodes have 0 width/height
         let xpos        = 0;
         let ypos        = 0;
@@ -1193,13 +1329,25 @@ function flowbetween(root_node) {
         } else if (action_type === 'SetValue') {
             node.flo_set_value = new_property_value => perform_action(controller_path, action_name, { 'Value': new_property_value });
 
+        } else if (action_type === 'SelectItems') {
+            node.flo_select_items = selection => perform_action(controller_path, action_name, { 'Selection': selection });
+
+        } else if (action_type === 'ExpandItem') {
+            node.flo_expand_item = (index, expanded) => perform_action(controller_path, action_name, { 'Expanded': [index, expanded] });
+
+        } else if (action_type === 'SelectMenuItem') {
+            node.flo_select_menu_item = command_id => perform_action(controller_path, action_name, { 'MenuItem': command_id });
+
         } else if (action_type === 'CancelEdit') {
             node.flo_cancel_edit = new_property_value => perform_action(controller_path, action_name, null);
 
         } else if (action_type === 'Dismiss') {
             node.flo_dismiss = () => perform_action(controller_path, action_name, null);
 
-            waiting_for_dismissal.push(node);
+            // Windows are only dismissed by their close button, not by clicking elsewhere
+            if (node.tagName.toLowerCase() !== 'flo-window') {
+                waiting_for_dismissal.push(node);
+            }
             remove_action = () => {
                 node.flo_dismiss        = null;
                 waiting_for_dismissal   = waiting_for_dismissal.filter(dismiss_node => dismiss_node !== node);
@@ -1318,6 +1466,88 @@ function flowbetween(root_node) {
         on_scroll();
     };
 
+    ///
+    /// Creates the menu bar or context menu for a node and binds the properties of its commands, returning a function
+    /// that removes the menu again
+    ///
+    let bind_menu = (node, menu, controller_path) => {
+        let is_menu_bar = menu.hasOwnProperty('Bar');
+        let menu_items  = is_menu_bar ? menu['Bar'] : menu['Context'];
+        let on_select   = command_id => { if (node.flo_select_menu_item) { node.flo_select_menu_item(command_id); } };
+        let created     = flo_control.create_menu(is_menu_bar ? 'deco-menubar' : 'deco-contextmenu', menu_items, on_select);
+        let unbind      = [];
+
+        // Bind the label, enabled and checked properties for each command
+        let bind_commands = (items) => {
+            items.forEach(item => {
+                if (item['SubMenu']) {
+                    bind_commands(item['SubMenu'][1]);
+                } else if (item['Command']) {
+                    let command         = item['Command'];
+                    let command_item    = created.commands[command['id']];
+                    let label           = command_item.getElementsByTagName('deco-menu-label')[0];
+
+                    unbind.push(on_property_change(controller_path, command['label'], new_label => {
+                        label.innerText = new_label['String'] || '';
+                        return true;
+                    }));
+
+                    unbind.push(on_property_change(controller_path, command['enabled'], is_enabled => {
+                        if (is_enabled['Bool']) {
+                            remove_class(command_item, 'disabled');
+                        } else {
+                            add_class(command_item, 'disabled');
+                        }
+                        return true;
+                    }));
+
+                    if (command['checked']) {
+                        unbind.push(on_property_change(controller_path, command['checked'], is_checked => {
+                            if (is_checked['Bool']) {
+                                add_class(command_item, 'checked');
+                            } else {
+                                remove_class(command_item, 'checked');
+                            }
+                            return true;
+                        }));
+                    }
+                }
+            });
+        };
+        bind_commands(menu_items);
+
+        let remove_menu = null;
+        if (is_menu_bar) {
+            // The menu bar is displayed above the root node, which is made smaller to fit it
+            root_node.parentNode.insertBefore(created.element, root_node);
+            add_class(document.body, 'has-menubar');
+            window.dispatchEvent(new Event('resize'));
+
+            remove_menu = () => {
+                created.element.parentNode.removeChild(created.element);
+                if (document.getElementsByTagName('deco-menubar').length === 0) {
+                    remove_class(document.body, 'has-menubar');
+                    window.dispatchEvent(new Event('resize'));
+                }
+            };
+        } else {
+            // The context menu is displayed when the node is right-clicked
+            let show_menu = event => {
+                event.preventDefault();
+                event.stopPropagation();
+                flo_control.show_context_menu(created.element, event.clientX, event.clientY);
+            };
+            node.addEventListener('contextmenu', show_menu);
+
+            remove_menu = () => node.removeEventListener('contextmenu', show_menu);
+        }
+
+        return () => {
+            unbind.forEach(unbind_property => unbind_property());
+            remove_menu();
+        };
+    };
+
     ///
     /// Binds a single attribute to a node
     ///
@@ -1403,6 +1633,20 @@ function flowbetween(root_node) {
                 return true;
             });
 
+        } else if (attribute['Window'] && attribute['Window']['IsOpen']) {
+            // Updates the window open property
+            remove_action = on_property_change(controller_path, attribute['Window']['IsOpen'], new_value => {
+                node.flo_window_open = new_value;
+                return true;
+            });
+
+        } else if (attribute['Window'] && attribute['Window']['Title']) {
+            // Updates the window title property
+            remove_action = on_property_change(controller_path, attribute['Window']['Title'], new_value => {
+                node.flo_window_title = new_value;
+                return true;
+            });
+
         } else if (attribute['Scroll']) {
             let scroll = attribute['Scroll'];
 
@@ -1412,6 +1656,66 @@ function flowbetween(root_node) {
                 canvas_deco.style.height    = scroll['MinimumContentSize'][1] + 'px';
             }
 
+        } else if (attribute['Items']) {
+            let items = attribute['Items'];
+
+            if (items['Count']) {
+                // Changing the number of items resizes the list
+                remove_action = on_property_change(controller_path, items['Count'], new_value => {
+                    node.flo_item_count = new_value['Int'] || 0;
+                    flo_control.layout_list_rows(node);
+                    return true;
+                });
+
+            } else if (items['FirstIndex']) {
+                // Changing the first index moves the items
+                remove_action = on_property_change(controller_path, items['FirstIndex'], new_value => {
+                    node.flo_first_index = new_value['Int'] || 0;
+                    flo_control.layout_list_rows(node);
+                    return true;
+                });
+
+            } else if (items['Expanded']) {
+                // Expandable items are marked as expanded or collapsed
+                remove_action = on_property_change(controller_path, items['Expanded'], is_expanded => {
+                    if (is_expanded['Bool']) {
+                        add_class(node, 'expanded');
+                        remove_class(node, 'collapsed');
+                    } else {
+                        add_class(node, 'collapsed');
+                        remove_class(node, 'expanded');
+                    }
+
+                    return true;
+                });
+            }
+
+        } else if (attribute['Menu']) {
+            // Menus are created when they're bound to the node
+            remove_action = bind_menu(node, attribute['Menu'], controller_path);
+
+        } else if (attribute['Accessibility']) {
+            // Tooltips and labels are set as attributes on the node (roles and focus order are set when the HTML is generated)
+            let accessibility   = attribute['Accessibility'];
+            let set_attribute   = (attribute_name, property) => on_property_change(controller_path, property, new_value => {
+                let text = new_value['String'] || '';
+
+                if (text.length > 0) {
+                    node.setAttribute(attribute_name, text);
+                } else {
+                    node.removeAttribute(attribute_name);
+                }
+                return true;
+            });
+
+            if (accessibility['Tooltip']) {
+                remove_action = set_attribute('title', accessibility['Tooltip']);
+            } else if (accessibility['Label']) {
+                remove_action = set_attribute('aria-label', accessibility['Label']);
+            } else if (accessibility['Description']) {
+                remove_action = set_attribute('aria-description', accessibility['Description']);
+            }
+
         } else if (attribute['FocusPriority']) {
             // Updates the focus priority for this node
             remove_action = on_property_change(controller_path, attribute['FocusPriority'], focus_priority => {
@@ -1846,6 +2150,109 @@ function flowbetween(root_node) {
         visit_dom(dom_node, control_data, (node, attributes, controller_path) => layout_subcomponents(node, attributes, controller_path));
     };
 
+    ///
+    /// Records where a node and its subnodes are currently displayed, indexed by their address relative to the node
+    ///
+    let capture_layout = (dom_node) => {
+        let layout = {};
+
+        let capture = (node, address) => {
+            // Use the computed style so that nodes that are part-way through a transition start from where they are now
+            let style       = window.getComputedStyle(node);
+            layout[address] = { left: style.left, top: style.top, width: style.width, height: style.height };
+
+            get_flo_subnodes(node).forEach((subnode, index) => capture(subnode, address + '/' + index));
+        };
+
+        if (dom_node) {
+            capture(dom_node, '');
+        }
+
+        return layout;
+    };
+
+    ///
+    /// Animates the controls with transitions in a newly laid out node, starting from where the controls they are
+    /// replacing were displayed (as recorded by capture_layout)
+    ///
+    let start_transitions = (dom_node, control_data, previous_layout) => {
+        let css_easing = {
+            'Linear':       'linear',
+            'EaseIn':       'ease-in',
+            'EaseOut':      'ease-out',
+            'EaseInOut':    'ease-in-out'
+        };
+
+        let visit = (node, control_data, address) => {
+            if (!node || !control_data) {
+                return;
+            }
+
+            let attributes  = get_attributes(control_data);
+            let previous    = previous_layout[address];
+            let start       = {};
+            let properties  = [];
+            let duration    = 0;
+
+            // Work out where each property starts from and how it's animated
+            attributes.transitions().forEach(transition => {
+                let kind    = Object.keys(transition)[0];
+                let timing  = transition[kind][0] + 'ms ' + css_easing[transition[kind][1]];
+
+                if (kind === 'Position' && previous) {
+                    start.left      = previous.left;
+                    start.top       = previous.top;
+                    properties.push('left ' + timing, 'top ' + timing);
+                } else if (kind === 'Size' && previous) {
+                    start.width     = previous.width;
+                    start.height    = previous.height;
+                    properties.push('width ' + timing, 'height ' + timing);
+                } else if (kind === 'Opacity' && !previous) {
+                    // Controls fade in when they first appear
+                    start.opacity   = '0';
+                    properties.push('opacity ' + timing);
+                } else {
+                    return;
+                }
+
+                duration = Math.max(duration, transition[kind][0]);
+            });
+
+            if (properties.length > 0) {
+                // Move the node to where it's starting from
+                let target = {};
+                Object.keys(start).forEach(property => {
+                    target[property]        = node.style[property];
+                    node.style[property]    = start[property];
+                });
+
+                node.style.transition = 'none';
+                void node.offsetWidth;
+
+                // Animate to the position it was laid out at
+                let css_transition      = properties.join(', ');
+                node.style.transition   = css_transition;
+                Object.keys(target).forEach(property => node.style[property] = target[property]);
+
+                // Later layout changes (eg, when the window is resized) should snap
+                setTimeout(() => {
+                    if (node.style.transition === css_transition) {
+                        node.style.transition = '';
+                    }
+                }, duration);
+            }
+
+            // Visit the subcomponents
+            let subcomponents = attributes.subcomponents();
+            if (subcomponents) {
+                let subnodes = get_flo_subnodes(node);
+                subcomponents.forEach((subcomponent, index) => visit(subnodes[index], subcomponent, address + '/' + index));
+            }
+        };
+
+        visit(dom_node, control_data, '');
+    };
+
     ///
     /// Given a node and its control data, wires up any events
     ///
@@ -1926,6 +2333,7 @@ function flowbetween(root_node) {
             updates.forEach(update => {
                 update.original_node    = node_at_address(update.address);
                 update.original_data    = data_at_address(update.address);
+                update.original_layout  = capture_layout(update.original_node);
             });
 
             // Unwire the original DOM
@@ -1933,6 +2341,11 @@ function flowbetween(root_node) {
                 unwire_node(update.original_node);
             });
 
+            // The server needs to lay out the containers of the new nodes again
+            updates.forEach(update => {
+                invalidate_layout_requests(update.original_node.parentNode);
+            });
+
             // Replace the data for each element involved in the update
             updates.forEach(update => {
                 let address = update.address;
@@ -1979,6 +2392,11 @@ function flowbetween(root_node) {
                 layout_tree(get_flo_subnodes(get_root())[0], root_control_data);
             }
 
+            // Animate any controls with transitions from where the controls they replaced were
+            updates.forEach(update => {
+                start_transitions(update.new_element, update.ui_tree, update.original_layout);
+            });
+
             // Tidy canvases if necessary
             flo_canvas.update_canvas_map();
 
@@ -2030,6 +2448,44 @@ function flowbetween(root_node) {
         });
     };
 
+    ///
+    /// Handles a style sheet update event (replaces the style sheet for the theme)
+    ///
+    let on_update_style_sheet = (style_sheet) => {
+        note('Updating theme');
+
+        return new Promise((resolve) => {
+            let theme_style = document.getElementById('flo-theme');
+
+            if (!theme_style) {
+                theme_style     = document.createElement('style');
+                theme_style.id  = 'flo-theme';
+                document.head.appendChild(theme_style);
+            }
+
+            theme_style.textContent = style_sheet;
+            resolve();
+        });
+    };
+
+    ///
+    /// The server has worked out where the subcomponents of the node at an address go
+    ///
+    let on_update_layout = (address, positions) => {
+        return new Promise((resolve) => {
+            let node = node_at_address(address);
+            let data = data_at_address(address);
+
+            if (node && data.data) {
+                // Lay out the node again, along with anything inside it whose size has changed as a result
+                node.flo_layout_positions = positions;
+                visit_dom(node, data.data, (node, attributes, controller_path) => layout_subcomponents(node, attributes, controller_path), data.controller_path);
+            }
+
+            resolve();
+        });
+    };
+
     ///
     /// Dispatches updates in a request
     ///
@@ -2097,6 +2553,21 @@ function flowbetween(root_node) {
                     current_promise = current_promise
                         .then(() => on_update_html(updates));
 
+                } else if (update['UpdateStyleSheet'] !== undefined) {
+
+                    let style_sheet = update['UpdateStyleSheet'];
+
+                    current_promise = current_promise
+                        .then(() => on_update_style_sheet(style_sheet));
+
+                } else if (update['UpdateLayout']) {
+
+                    let address     = update['UpdateLayout'][0];
+                    let positions   = update['UpdateLayout'][1];
+
+                    current_promise = current_promise
+                        .then(() => on_update_layout(address, positions));
+
                 } else {
                     warn('Unknown update type', Object.keys(update)[0], update);
                 }
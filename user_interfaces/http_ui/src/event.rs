@@ -33,5 +33,11 @@ pub enum Event {
     ///
     /// Sends a tick event to the controllers
     ///
-    Tick
+    Tick,
+
+    ///
+    /// Requests the positions of the subcomponents of the control at the specified address, when it's laid out in an
+    /// area of the specified width and height
+    ///
+    Layout(Vec<u32>, f32, f32)
 }
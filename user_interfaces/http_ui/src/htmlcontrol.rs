@@ -44,13 +44,33 @@ pub fn html_controller_path_for_address<'a>(ui_tree: &'a Control, address: &Vec<
     }
 }
 
+///
+/// Finds the control at a particular address in a UI tree
+///
+pub fn control_for_address<'a>(ui_tree: &'a Control, address: &[u32]) -> Option<&'a Control> {
+    let mut current_node = ui_tree;
+
+    for index in address.iter() {
+        current_node = current_node.subcomponents()?.get(*index as usize)?;
+    }
+
+    Some(current_node)
+}
+
 ///
 /// Returns the class for a control
 ///
 fn control_class(ctrl: &Control) -> &str {
+    control_type_class(ctrl.control_type())
+}
+
+///
+/// Returns the name of the element used for a particular type of control
+///
+pub fn control_type_class(control_type: ControlType) -> &'static str {
     use ui::ControlType::*;
 
-    match ctrl.control_type() {
+    match control_type {
         Empty               => "flo-empty",
         Container           => "flo-container",
         CroppingContainer   => "flo-cropping",
@@ -62,7 +82,11 @@ fn control_class(ctrl: &Control) -> &str {
         Slider              => "flo-slider",
         Rotor               => "flo-rotor",
         TextBox             => "flo-textbox",
-        CheckBox            => "flo-checkbox"
+        CheckBox            => "flo-checkbox",
+        ComboBox            => "flo-combobox",
+        ListView            => "flo-listview",
+        TreeView            => "flo-treeview",
+        Window              => "flo-window"
     }
 }
 
@@ -199,8 +223,22 @@ impl ToHtml for ControlAttribute {
             &FontAttr(ref font_attribute)   => font_attribute.to_html_subcomponent(base_path, controller_path),
             &StateAttr(ref state)           => state.to_html_subcomponent(base_path, controller_path),
             &PopupAttr(ref popup)           => popup.to_html_subcomponent(base_path, controller_path),
+            &WindowAttr(ref window)         => window.to_html_subcomponent(base_path, controller_path),
             &ScrollAttr(ref scroll)         => scroll.to_html_subcomponent(base_path, controller_path),
             &HintAttr(ref hint)             => hint.to_html_subcomponent(base_path, controller_path),
+            &AccessibilityAttr(ref access)  => access.to_html_subcomponent(base_path, controller_path),
+
+            // Items are laid out by the client
+            &ItemsAttr(_)                   => DomEmpty::new(),
+
+            // Menus are created by the client when the control is bound to the viewmodel
+            &MenuAttr(_)                    => DomEmpty::new(),
+
+            // The server works out where the subcomponents of a container with a layout mode go when the client asks for them
+            &LayoutAttr(_)                  => DomEmpty::new(),
+
+            // Transitions are animated by the client when the layout changes
+            &TransitionAttr(_)              => DomEmpty::new(),
 
             &BoundingBox(_) => DomEmpty::new(),
             &Id(_)          => DomEmpty::new(),
@@ -221,6 +259,53 @@ impl ToHtml for Hint {
     }
 }
 
+impl ToHtml for Accessibility {
+    fn to_html_subcomponent(&self, base_path: &str, controller_path: &str) -> DomNode {
+        use ui::Accessibility::*;
+
+        // Bound properties are set by the client when the control is bound to the viewmodel
+        match self {
+            &Tooltip(Property::Bind(_))     => DomEmpty::new(),
+            &Label(Property::Bind(_))       => DomEmpty::new(),
+            &Description(Property::Bind(_)) => DomEmpty::new(),
+
+            &Tooltip(ref tooltip)           => DomAttribute::new("title", &tooltip.to_string()),
+            &Label(ref label)               => DomAttribute::new("aria-label", &label.to_string()),
+            &Description(ref description)   => DomAttribute::new("aria-description", &description.to_string()),
+            &Role(role)                     => role.to_html_subcomponent(base_path, controller_path),
+
+            // A tabindex of 0 means 'in document order' so positive orders start at 1
+            &FocusOrder(order)              => DomAttribute::new("tabindex", &format!("{}", order+1))
+        }
+    }
+}
+
+impl ToHtml for AccessibilityRole {
+    fn to_html_subcomponent(&self, _base_path: &str, _controller_path: &str) -> DomNode {
+        use ui::AccessibilityRole::*;
+
+        let aria_role = match self {
+            &Button         => "button",
+            &ToggleButton   => "switch",
+            &CheckBox       => "checkbox",
+            &Slider         => "slider",
+            &TextBox        => "textbox",
+            &Label          => "note",
+            &Image          => "img",
+            &Canvas         => "img",
+            &ComboBox       => "combobox",
+            &List           => "listbox",
+            &ListItem       => "option",
+            &Tree           => "tree",
+            &TreeItem       => "treeitem",
+            &Toolbar        => "toolbar",
+            &Group          => "group"
+        };
+
+        DomAttribute::new("role", aria_role)
+    }
+}
+
 impl ToHtml for Scroll {
     fn to_html_subcomponent(&self, _base_path: &str, _controller_path: &str) -> DomNode {
         use ui::Scroll::*;
@@ -331,6 +416,19 @@ impl ToHtml for Popup {
     }
 }
 
+impl ToHtml for Window {
+    fn to_html_subcomponent(&self, _base_path: &str, _controller_path: &str) -> DomNode {
+        use ui::Window::*;
+
+        match self {
+            &Title(_)       => DomEmpty::new(),
+            &IsOpen(_)      => DomEmpty::new(),
+            &Kind(_)        => DomEmpty::new(),
+            &Size(_, _)     => DomEmpty::new()
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use canvas::*;
@@ -372,6 +470,24 @@ mod test {
         assert!(Control::container().with(vec![Control::button()]).to_html("").to_string() == "<flo-container><flo-button></flo-button></flo-container>")
     }
 
+    #[test]
+    fn can_convert_list_view_to_html() {
+        let list_view = Control::list_view()
+            .with(Items::RowHeight(20.0))
+            .with(vec![Control::label().with(Items::Level(1))]);
+
+        assert!(list_view.to_html("").to_string() == "<flo-listview><flo-label></flo-label></flo-listview>")
+    }
+
+    #[test]
+    fn can_convert_tooltip_to_html() {
+        let button = Control::button()
+            .with(Accessibility::Tooltip("Pencil".to_property()))
+            .with(Accessibility::Label(Property::Bind("ToolName".to_string())));
+
+        assert!(button.to_html("").to_string() == "<flo-button title=\"Pencil\"></flo-button>");
+    }
+
     #[test]
     fn can_convert_zindex_to_html() {
         assert!(Control::empty().with(ControlAttribute::ZIndex(23)).to_html("").to_string() == "<flo-empty style=\"z-index: 23;\"></flo-empty>");
@@ -70,6 +70,9 @@ pub struct HttpUserInterface<CoreUi> {
 
     /// Publishes events to the core UI
     event_publisher: Publisher<Vec<Event>>,
+
+    /// The layout requests that are waiting to be sent back with the next update (address, width, height)
+    layout_requests: Arc<Mutex<Vec<(Vec<u32>, f32, f32)>>>
 }
 
 impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
@@ -79,15 +82,17 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
     pub fn new(ui: Arc<CoreUi>, base_path: String) -> (HttpUserInterface<CoreUi>, impl Future<Output=()>) {
         let ui_tree             = ui.ui_tree();
         let event_publisher     = Publisher::new(100);
+        let layout_requests     = Arc::new(Mutex::new(vec![]));
 
         // Create the run loop
-        let run_loop        = Self::run(event_publisher.republish_weak(), ui.get_input_sink());
+        let run_loop        = Self::run(event_publisher.republish_weak(), ui.get_input_sink(), Arc::clone(&layout_requests));
 
         let user_interface  = HttpUserInterface {
             core_ui:            ui,
             ui_tree:            ui_tree,
             base_path:          base_path,
-            event_publisher:    event_publisher
+            event_publisher:    event_publisher,
+            layout_requests:    layout_requests
         };
 
         (user_interface, run_loop)
@@ -103,7 +108,7 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
     ///
     /// Runs the HTTP UI
     ///
-    async fn run(mut http_events: WeakPublisher<Vec<Event>>, mut ui_events: WeakPublisher<Vec<UiEvent>>) {
+    async fn run(mut http_events: WeakPublisher<Vec<Event>>, mut ui_events: WeakPublisher<Vec<UiEvent>>, layout_requests: Arc<Mutex<Vec<(Vec<u32>, f32, f32)>>>) {
         // Subscribe to the events
         let mut http_subscriber = http_events.subscribe();
 
@@ -115,8 +120,16 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
             // Finish the UI loop if there are no more events
             if next_events.is_none() { break; }
 
+            // Layout requests are answered alongside the update generated for these events
+            let next_events = next_events.unwrap();
+            layout_requests.lock().unwrap().extend(next_events.iter()
+                .filter_map(|event| match event {
+                    Event::Layout(address, width, height)   => Some((address.clone(), *width, *height)),
+                    _                                       => None
+                }));
+
             // Process the events into HTTP events
-            let http_events = next_events.unwrap().into_iter()
+            let http_events = next_events.into_iter()
                 .map(|event| Self::http_event_to_core_event(event))
                 .collect::<Vec<_>>();
 
@@ -161,6 +174,7 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
             NewSession      => UiEvent::Tick,
             UiRefresh       => UiEvent::Tick,
             Tick            => UiEvent::Tick,
+            Layout(_, _, _) => UiEvent::Tick,
             SuspendUpdates  => UiEvent::SuspendUpdates,
             ResumeUpdates   => UiEvent::ResumeUpdates,
 
@@ -266,7 +280,9 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
 
             UpdateCanvas(canvas_diffs) => vec![Update::UpdateCanvas(canvas_diffs.into_iter().map(|diff| Self::map_canvas_diff(diff)).collect())],
 
-            UpdateViewModel(view_model_diffs) => vec![Update::UpdateViewModel(view_model_diffs)]
+            UpdateViewModel(view_model_diffs) => vec![Update::UpdateViewModel(view_model_diffs)],
+
+            UpdateTheme(theme) => vec![Update::UpdateStyleSheet(theme.to_css(CssDialect::Html, |control_type| control_type_class(control_type).to_string()))]
         }
     }
 
@@ -290,6 +306,24 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
         }
     }
 
+    ///
+    /// Works out where the subcomponents of the control at an address go when it's laid out in an area of the specified size
+    ///
+    fn layout_update(ui_tree: &Control, address: Vec<u32>, width: f32, height: f32) -> Update {
+        let positions = control_for_address(ui_tree, &address)
+            .and_then(|control| control.subcomponents().map(|subcomponents| (control, subcomponents)))
+            .map(|(control, subcomponents)| {
+                let items = subcomponents.iter()
+                    .map(|subcomponent| LayoutItem::from_control(subcomponent))
+                    .collect::<Vec<_>>();
+
+                ControlLayout::for_control(control).lay_out(&items, width, height)
+            })
+            .unwrap_or_default();
+
+        Update::UpdateLayout(address, positions)
+    }
+
     ///
     /// Retrieves the controller used for this UI
     ///
@@ -312,15 +346,22 @@ impl<CoreUi: CoreUserInterface> UserInterface<Vec<Event>, Vec<Update>, ()> for H
         let core_updates = self.core_ui.get_updates();
 
         // Fetch the extra components we need to map events from this object
-        let ui_tree     = BindRef::clone(&self.ui_tree);
-        let base_path   = self.base_path.clone();
+        let ui_tree         = BindRef::clone(&self.ui_tree);
+        let base_path       = self.base_path.clone();
+        let layout_requests = Arc::clone(&self.layout_requests);
 
         // Turn into HTTP updates
         let mapped_updates = core_updates.map(move |core_updates| {
             core_updates.map(|core_updates| {
-                let ui_tree = ui_tree.get();
+                let ui_tree     = ui_tree.get();
+                let mut updates = Self::core_updates_to_http_updates(core_updates, &base_path, &ui_tree);
 
-                Self::core_updates_to_http_updates(core_updates, &base_path, &ui_tree)
+                // Answer any layout requests using the UI tree as it is after these updates
+                let requests    = mem::take(&mut *layout_requests.lock().unwrap());
+                updates.extend(requests.into_iter()
+                    .map(|(address, width, height)| Self::layout_update(&ui_tree, address, width, height)));
+
+                updates
             })
         });
 
@@ -392,4 +433,34 @@ mod test {
             ])));
         });
     }
+
+    #[test]
+    fn answers_layout_requests() {
+        let thread_pool                     = executor::ThreadPool::new().unwrap();
+        let ui                              = Control::container()
+            .with(LayoutMode::Row)
+            .with(vec![
+                Control::empty().with(Layout::ContentSize(10.0, 20.0)),
+                Control::empty().with(Layout::ContentSize(30.0, 20.0))
+            ]);
+        let controller                      = TestController { ui: bind(ui) };
+        let (core_session, core_run_loop)   = UiSession::new(controller);
+        let (http_session, http_run_loop)   = HttpUserInterface::new(Arc::new(core_session), "test/session".to_string());
+
+        thread_pool.spawn_ok(core_run_loop);
+        thread_pool.spawn_ok(http_run_loop);
+        let mut http_stream             = http_session.get_updates();
+        let mut input                   = http_session.get_input_sink();
+
+        executor::block_on(async {
+            // Skip the initial update
+            http_stream.next().await;
+
+            // Ask where the subcomponents of the root control go
+            input.publish(vec![Event::Layout(vec![], 100.0, 20.0)]).await;
+            let updates = http_stream.next().await.unwrap().unwrap();
+
+            assert!(updates.contains(&Update::UpdateLayout(vec![], vec![((0.0, 0.0), (10.0, 20.0)), ((10.0, 0.0), (40.0, 20.0))])));
+        });
+    }
 }
@@ -79,5 +79,16 @@ pub enum Update {
     ///
     /// Specifies that a canvas should be updated
     ///
-    UpdateCanvas(Vec<CanvasUpdate>)
+    UpdateCanvas(Vec<CanvasUpdate>),
+
+    ///
+    /// Replaces the style sheet used to theme the user interface
+    ///
+    UpdateStyleSheet(String),
+
+    ///
+    /// Supplies the positions of the subcomponents of the control at an address, as requested by a `Layout` event
+    /// (the address and the position of each subcomponent as ((x1, y1), (x2, y2)))
+    ///
+    UpdateLayout(Vec<u32>, Vec<((f32, f32), (f32, f32))>)
 }
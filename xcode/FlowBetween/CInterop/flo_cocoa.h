@@ -49,12 +49,16 @@ typedef struct AppPainting AppPainting;
 - (void) sendChangeValue: (NSString*) name isSet: (BOOL) isSet withDouble: (double) value;
 - (void) sendChangeValue: (NSString*) name isSet: (BOOL) isSet withString: (NSString*) value;
 - (void) sendVirtualScroll: (NSString*) name left: (uint32_t) left top: (uint32_t) top width: (uint32_t) width height: (uint32_t) height;
+- (void) sendSelectItems: (NSString*) name withIndexes: (NSArray<NSNumber*>*) indexes;
+- (void) sendExpandItem: (NSString*) name index: (uint32_t) index expanded: (BOOL) expanded;
+- (void) sendSelectMenuItem: (NSString*) name withId: (NSString*) commandId;
 - (void) sendDrag: (NSString*) name dragAction: (uint32_t) action fromX: (double) fromX fromY: (double) fromY toX: (double) toX toY: (double) toY;
 - (void) sendPaintStartForDevice: (uint32_t) deviceId name: (NSString*) name action: (AppPainting) action;
 - (void) sendPaintContinueForDevice: (uint32_t) deviceId name: (NSString*) name action: (AppPainting) action;
 - (void) sendPaintFinishForDevice: (uint32_t) deviceId name: (NSString*) name action: (AppPainting) action;
 - (void) sendPaintCancelForDevice: (uint32_t) deviceId name: (NSString*) name action: (AppPainting) action;
 - (void) redrawCanvasWithSize: (NSSize) size viewport: (NSRect) viewport;
+- (NSArray<NSValue*>*) framesForSubviews: (NSArray<FloEvents*>*) subviews withSize: (NSSize) size;
 
 @end
 
@@ -89,6 +93,9 @@ typedef struct AppPainting AppPainting;
 - (void) requestEditValue: (FloEvents*) events withName: (NSString*) name;
 - (void) requestSetValue: (FloEvents*) events withName: (NSString*) name;
 - (void) requestCancelEdit: (FloEvents*) events withName: (NSString*) name;
+- (void) requestSelectItems: (FloEvents*) events withName: (NSString*) name;
+- (void) requestExpandItem: (FloEvents*) events withName: (NSString*) name;
+- (void) requestSelectMenuItem: (FloEvents*) events withName: (NSString*) name;
 
 - (void) viewRemoveFromSuperview;
 - (void) viewAddSubView: (NSObject*) subview;
@@ -101,9 +108,14 @@ typedef struct AppPainting AppPainting;
 - (void) viewSetSideAtEnd: (int32_t) side;
 - (void) viewSetSideAfter: (int32_t) side;
 - (void) viewSetPaddingWithLeft: (double) left top: (double) top right: (double) right bottom: (double) bottom;
+- (void) viewSetLayout: (FloEvents*) events;
+- (void) viewAddTransition: (uint32_t) kind duration: (double) duration easing: (uint32_t) easing;
+- (void) viewReplacesView: (NSObject*) oldView;
 - (void) viewSetZIndex: (double) zIndex;
 - (void) viewSetForegroundRed: (double) red green: (double) green blue: (double) blue alpha: (double) alpha;
 - (void) viewSetBackgroundRed: (double) red green: (double) green blue: (double) blue alpha: (double) alpha;
+- (void) viewSetBorderWidth: (double) width red: (double) red green: (double) green blue: (double) blue alpha: (double) alpha;
+- (void) viewSetCornerRadius: (double) radius;
 - (void) viewSetText: (FloProperty*) text;
 - (void) viewSetImage: (NSImage*) image;
 - (void) viewSetFontSize: (double) size;
@@ -122,11 +134,37 @@ typedef struct AppPainting AppPainting;
 - (void) viewFixScrollAxis: (uint32_t) axis;
 - (void) viewAddClassName: (NSString*) className;
 
+- (void) viewSetSelectionMode: (uint32_t) mode;
+- (void) viewSetRowHeight: (double) height;
+- (void) viewSetItemCount: (FloProperty*) count;
+- (void) viewSetFirstItemIndex: (FloProperty*) index;
+- (void) viewSetItemLevel: (double) level;
+- (void) viewSetItemExpanded: (FloProperty*) isExpanded;
+
+- (void) viewBeginMenuBar;
+- (void) viewBeginContextMenu;
+- (void) viewBeginSubMenu: (FloProperty*) label;
+- (void) viewEndSubMenu;
+- (void) viewEndMenu;
+- (void) viewAddMenuCommand: (NSString*) commandId label: (FloProperty*) label shortcut: (NSString*) shortcut enabled: (FloProperty*) enabled checked: (FloProperty*) checked;
+- (void) viewAddMenuSeparator;
+
+- (void) viewSetTooltip: (FloProperty*) tooltip;
+- (void) viewSetAccessibilityLabel: (FloProperty*) label;
+- (void) viewSetAccessibilityHelp: (FloProperty*) help;
+- (void) viewSetAccessibilityRole: (uint32_t) role;
+- (void) viewSetFocusOrder: (double) order;
+
 - (void) viewSetPopupOpen: (FloProperty*) isOpen;
 - (void) viewSetPopupDirection: (uint32_t) direction;
 - (void) viewSetPopupSizeWithWidth: (double) width height: (double) height;
 - (void) viewSetPopupOffset: (double) offset;
 
+- (void) viewSetWindowOpen: (FloProperty*) isOpen;
+- (void) viewSetWindowTitle: (FloProperty*) title;
+- (void) viewSetWindowKind: (uint32_t) kind;
+- (void) viewSetWindowSizeWithWidth: (double) width height: (double) height;
+
 - (CGContextRef) viewGetCanvasForDrawing: (FloEvents*) events layer: (uint32_t) layer_id;
 - (FloCacheLayer*) viewCopyLayerWithId: (uint32_t) layer_id;
 - (void) viewUpdateCache: (FloCacheLayer*) layer fromLayerWithId: (uint32_t) layer_id;
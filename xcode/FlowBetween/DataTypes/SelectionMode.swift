@@ -0,0 +1,15 @@
+//
+//  SelectionMode.swift
+//  FlowBetween
+//
+
+import Foundation
+
+///
+/// How many items can be selected at once in a list or tree view
+///
+enum SelectionMode : UInt32 {
+    case None       = 0;
+    case Single     = 1;
+    case Multiple   = 2;
+}
@@ -26,6 +26,15 @@ class ViewState {
     var layoutX:        [FloProperty]   = []
     var layoutY:        [FloProperty]   = []
 
+    var itemCount:      FloProperty?
+    var firstItemIndex: FloProperty?
+    var itemExpanded:   FloProperty?
+    var itemLevel:      Double          = 0.0
+    var rowHeight:      Double          = 24.0
+    var selectionMode:  SelectionMode   = SelectionMode.Single
+    var focusOrder:     Double?
+    var accessibility:  [FloProperty]   = []
+
     var fixedAxis:      FixedAxis       = FixedAxis.None
     let subviewIndex:   FloProperty     = FloProperty(withInt: 0)
     let isFirst:        FloProperty     = FloProperty(withBool: false)
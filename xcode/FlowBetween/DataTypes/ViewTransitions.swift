@@ -0,0 +1,61 @@
+//
+//  ViewTransitions.swift
+//  FlowBetween
+//
+
+import Cocoa
+
+///
+/// Describes how a transition progresses from its start to its end
+///
+enum TransitionEasing : UInt32 {
+    case Linear     = 0
+    case EaseIn     = 1
+    case EaseOut    = 2
+    case EaseInOut  = 3
+
+    /// The Core Animation timing function for this easing
+    var timingFunction: CAMediaTimingFunction {
+        switch self {
+        case .Linear:       return CAMediaTimingFunction(name: .linear)
+        case .EaseIn:       return CAMediaTimingFunction(name: .easeIn)
+        case .EaseOut:      return CAMediaTimingFunction(name: .easeOut)
+        case .EaseInOut:    return CAMediaTimingFunction(name: .easeInEaseOut)
+        }
+    }
+}
+
+///
+/// A single transition, with its duration in milliseconds
+///
+struct ViewTransition {
+    var duration: Double
+    var easing: TransitionEasing
+
+    ///
+    /// Runs an animation using this transition's timing
+    ///
+    func animate(_ changes: () -> ()) {
+        NSAnimationContext.runAnimationGroup({ context in
+            context.duration        = duration / 1000.0
+            context.timingFunction  = easing.timingFunction
+            context.allowsImplicitAnimation = true
+
+            changes()
+        })
+    }
+}
+
+///
+/// How changes to the layout of a view are animated (views with no transitions snap to their new layout)
+///
+struct ViewTransitions {
+    var position: ViewTransition?   = nil
+    var size: ViewTransition?       = nil
+    var opacity: ViewTransition?    = nil
+
+    /// True if there are no transitions set
+    var isEmpty: Bool {
+        return position == nil && size == nil && opacity == nil
+    }
+}
@@ -25,6 +25,18 @@ public class FloView : NSObject, FloViewDelegate {
     /// The padding set for this view
     fileprivate var _padding: Padding?
 
+    /// The events object used to ask where the subviews of this view go
+    fileprivate var _layoutEvents: FloEvents?
+
+    /// How changes to the layout of this view are animated
+    fileprivate var _transitions = ViewTransitions()
+
+    /// The frame this view was last given by the layout of its superview (nil if it hasn't been laid out yet)
+    fileprivate var _layoutFrame: NSRect?
+
+    /// Where the transitions for this view start from, if it's replacing another view
+    fileprivate var _transitionStartFrame: NSRect?
+
     /// The subviews of this view
     fileprivate var _subviews: [FloView]
 
@@ -34,6 +46,9 @@ public class FloView : NSObject, FloViewDelegate {
     /// Set to true when we're planning to sort the subviews
     fileprivate var _willSortSubviews: Bool = false
 
+    /// Set to true if we've queued up an update to the key view loop for the subviews
+    fileprivate var _willUpdateKeyViewLoop: Bool = false
+
     /// Events
     fileprivate var _onClick: (() -> ())?
     fileprivate var _onDismiss: (() -> ())?
@@ -41,6 +56,24 @@ public class FloView : NSObject, FloViewDelegate {
     /// The layer to draw on, if there is one
     fileprivate var _drawingLayer: FloCanvasLayer?
 
+    /// Event sent when a command is picked from one of the menus attached to this view
+    fileprivate var _onSelectMenuItem: ((String) -> ())?
+
+    /// The menus that are being built (the last menu is the one that items are added to)
+    fileprivate var _buildingMenus: [NSMenu] = []
+
+    /// True if the menu being built is the menu bar, false if it's the context menu
+    fileprivate var _buildingMenuBar = false
+
+    /// The properties used by the menu that's being built
+    fileprivate var _buildingMenuProperties: [FloProperty] = []
+
+    /// The items in the menu bar to display while this view's window is the key window, and the properties that they use
+    fileprivate var _menuBar: ([NSMenuItem], [FloProperty])?
+
+    /// The properties used by the context menu for this view
+    fileprivate var _contextMenuProperties: [FloProperty] = []
+
     override init() {
         _bounds = Bounds(
             x1: Position.Start,
@@ -99,6 +132,13 @@ public class FloView : NSObject, FloViewDelegate {
         return _padding
     }
 
+    ///
+    /// The events object that identifies this view's layout to the Rust side
+    ///
+    internal var layoutEvents: FloEvents? {
+        return _layoutEvents
+    }
+
     ///
     /// The view that this is managing
     ///
@@ -124,8 +164,13 @@ public class FloView : NSObject, FloViewDelegate {
     /// Performs layout of this view immediately
     ///
     public func performLayout(_ size: NSSize) {
-        // Just pass the request on to the layout class
-        FloLayout.layoutView(view: self, size: size, state: _view.viewState)
+        if let listView = _view as? FloListView {
+            // Lists lay out their subviews as rows
+            listView.layoutDocumentView()
+        } else {
+            // Just pass the request on to the layout class
+            FloLayout.layoutView(view: self, size: size, state: _view.viewState)
+        }
     }
 
     ///
@@ -211,6 +256,13 @@ public class FloView : NSObject, FloViewDelegate {
         // Register the dismiss event
         _onDismiss = { events.sendDismiss(withName) }
 
+        // Windows are dismissed by their close button rather than by clicking elsewhere
+        if let windowView = _view as? FloWindowView {
+            weak var this = self
+            windowView.onDismiss = { this?.sendDismiss() }
+            return
+        }
+
         // Request a dismiss action from the app delegate
         if let appDelegate = NSApp.delegate as? FloAppDelegate {
             appDelegate.requestDismiss(forView: self)
@@ -293,6 +345,10 @@ public class FloView : NSObject, FloViewDelegate {
 
     @objc public func viewSetSelected(_ property: FloProperty!) {
         _view.setState(selector: ViewStateSelector.Selected, toProperty: property)
+
+        // Lists highlight their selected items
+        weak var this = self
+        property.trackValue { _ in this?.invalidateListLayout() }
     }
 
     @objc public func viewSetBadged(_ property: FloProperty!) {
@@ -326,6 +382,97 @@ public class FloView : NSObject, FloViewDelegate {
         _view.viewState.classes.append(className)
     }
 
+    ///
+    /// If this view is an item in a list, invalidates the layout of the list
+    ///
+    func invalidateListLayout() {
+        if let superview = _superview, superview._view is FloListView {
+            superview.invalidateLayout()
+        }
+    }
+
+    ///
+    /// Sets how many items can be selected at once in a list
+    ///
+    /// Selection modes are:
+    ///     0 - None
+    ///     1 - Single
+    ///     2 - Multiple
+    ///
+    @objc public func viewSetSelectionMode(_ mode: UInt32) {
+        if let mode = SelectionMode(rawValue: mode) {
+            _view.viewState.selectionMode = mode
+        }
+    }
+
+    ///
+    /// Sets the height of the rows in a list
+    ///
+    @objc public func viewSetRowHeight(_ height: Float64) {
+        _view.viewState.rowHeight = height
+        invalidateLayout()
+    }
+
+    ///
+    /// Sets the property containing the total number of items in a list
+    ///
+    @objc public func viewSetItemCount(_ property: FloProperty!) {
+        _view.viewState.itemCount   = property
+        weak var this               = self
+
+        property.trackValue { _ in this?.invalidateLayout() }
+    }
+
+    ///
+    /// Sets the property containing the index of the item represented by the first subview of a list
+    ///
+    @objc public func viewSetFirstItemIndex(_ property: FloProperty!) {
+        _view.viewState.firstItemIndex  = property
+        weak var this                   = self
+
+        property.trackValue { _ in this?.invalidateLayout() }
+    }
+
+    ///
+    /// Sets how deeply this item is nested in a tree
+    ///
+    @objc public func viewSetItemLevel(_ level: Float64) {
+        _view.viewState.itemLevel = level
+        invalidateListLayout()
+    }
+
+    ///
+    /// Sets the property indicating whether or not this item in a tree is expanded
+    ///
+    @objc public func viewSetItemExpanded(_ property: FloProperty!) {
+        _view.viewState.itemExpanded    = property
+        weak var this                   = self
+
+        property.trackValue { _ in this?.invalidateListLayout() }
+    }
+
+    ///
+    /// Sends an event when the user changes which items are selected in a list
+    ///
+    @objc public func requestSelectItems(_ events: FloEvents!, withName name: String!) {
+        if let listView = _view as? FloListView {
+            listView.onSelectItems = { selection in
+                events.sendSelectItems(name, withIndexes: selection.map { index in NSNumber(value: index) })
+            }
+        }
+    }
+
+    ///
+    /// Sends an event when the user expands or collapses an item in a tree
+    ///
+    @objc public func requestExpandItem(_ events: FloEvents!, withName name: String!) {
+        if let listView = _view as? FloListView {
+            listView.onExpandItem = { index, expanded in
+                events.sendExpandItem(name, index: index, expanded: expanded)
+            }
+        }
+    }
+
     ///
     /// Sends an event if this view is scrolled
     ///
@@ -405,6 +552,10 @@ public class FloView : NSObject, FloViewDelegate {
         // View will need to be laid out again
         invalidateLayout()
         invalidateSubviewOrder()
+
+        if subview.viewState.focusOrder != nil {
+            invalidateKeyViewLoop()
+        }
     }
 
     ///
@@ -423,6 +574,10 @@ public class FloView : NSObject, FloViewDelegate {
 
         // View will need to be laid out again
         invalidateLayout()
+
+        if subview.viewState.focusOrder != nil {
+            invalidateKeyViewLoop()
+        }
     }
 
     ///
@@ -474,6 +629,85 @@ public class FloView : NSObject, FloViewDelegate {
         _padding = Padding(left: left, top: top, right: right, bottom: bottom)
     }
 
+    ///
+    /// Sets the events object used to find out where the subviews of this view go
+    ///
+    @objc public func viewSetLayout(_ events: FloEvents!) {
+        _layoutEvents = events
+    }
+
+    ///
+    /// Animates changes to the layout of this view (kind 0 = position, 1 = size, 2 = opacity, duration in milliseconds)
+    ///
+    @objc public func viewAddTransition(_ kind: UInt32, duration: Double, easing: UInt32) {
+        let transition = ViewTransition(duration: duration, easing: TransitionEasing(rawValue: easing) ?? .Linear)
+
+        switch kind {
+        case 0:     _transitions.position = transition
+        case 1:     _transitions.size = transition
+        case 2:     _transitions.opacity = transition
+        default:    break
+        }
+    }
+
+    ///
+    /// Indicates that this view is replacing another one, so its transitions start from where that view was laid out
+    ///
+    @objc public func viewReplacesView(_ oldView: NSObject!) {
+        if let oldView = oldView as? FloView {
+            _transitionStartFrame = oldView._layoutFrame ?? oldView.view?.frame
+        }
+    }
+
+    ///
+    /// Moves this view to the frame chosen for it by its superview's layout, animating the change if it has any transitions
+    ///
+    func setLayoutFrame(_ frame: NSRect) {
+        guard let view = view else { return }
+
+        // Nothing to do if the view is already at (or moving to) this frame
+        if _layoutFrame == frame && _transitionStartFrame == nil {
+            return
+        }
+
+        let startFrame          = _transitionStartFrame ?? _layoutFrame
+        _layoutFrame            = frame
+        _transitionStartFrame   = nil
+
+        // Views with no transitions snap to their new layout
+        if _transitions.isEmpty {
+            view.frame = frame
+            return
+        }
+
+        guard let from = startFrame else {
+            // Views that are appearing for the first time fade in
+            view.frame = frame
+
+            if let opacity = _transitions.opacity {
+                view.alphaValue = 0.0
+                opacity.animate { view.animator().alphaValue = 1.0 }
+            }
+
+            return
+        }
+
+        // Position and size change from where the view was laid out before
+        view.frame = from
+
+        if let position = _transitions.position {
+            position.animate { view.animator().setFrameOrigin(frame.origin) }
+        } else {
+            view.setFrameOrigin(frame.origin)
+        }
+
+        if let size = _transitions.size {
+            size.animate { view.animator().setFrameSize(frame.size) }
+        } else {
+            view.setFrameSize(frame.size)
+        }
+    }
+
     ///
     /// Sets the z-ordering of this view
     ///
@@ -500,6 +734,24 @@ public class FloView : NSObject, FloViewDelegate {
         _view.asView.layer?.backgroundColor = col.cgColor
     }
 
+    ///
+    /// Sets the width and colour of the border around the view
+    ///
+    @objc public func viewSetBorderWidth(_ width: Float64, red: Float64, green: Float64, blue: Float64, alpha: Float64) {
+        let col = NSColor(calibratedRed: CGFloat(red), green: CGFloat(green), blue: CGFloat(blue), alpha: CGFloat(alpha))
+
+        _view.asView.layer?.borderWidth = CGFloat(width)
+        _view.asView.layer?.borderColor = col.cgColor
+    }
+
+    ///
+    /// Sets the radius of the corners of the view
+    ///
+    @objc public func viewSetCornerRadius(_ radius: Float64) {
+        _view.asView.layer?.cornerRadius    = CGFloat(radius)
+        _view.asView.layer?.masksToBounds   = radius > 0.0
+    }
+
     ///
     /// Sets the text for the view
     ///
@@ -842,6 +1094,367 @@ public class FloView : NSObject, FloViewDelegate {
         }
     }
 
+    ///
+    /// Sets the property that describes whether or not the window for this view is open
+    ///
+    @objc public func viewSetWindowOpen(_ isOpen: FloProperty!) {
+        weak var this = self
+
+        // Track when the window opens or closes
+        isOpen.trackValue { isOpen in
+            if let window = this?._view as? FloWindowView {
+                window.setWindowOpen(isOpen.toBool(default: true))
+            }
+        }
+    }
+
+    ///
+    /// Sets the title of the window for this view
+    ///
+    @objc public func viewSetWindowTitle(_ title: FloProperty!) {
+        weak var this = self
+
+        title.trackValue { title in
+            if case let PropertyValue.String(title) = title {
+                if let window = this?._view as? FloWindowView {
+                    window.setWindowTitle(title)
+                }
+            }
+        }
+    }
+
+    ///
+    /// Sets how the window for this view is presented
+    ///
+    @objc public func viewSetWindowKind(_ kind: UInt32) {
+        if let actualKind = WindowKind(rawValue: kind) {
+            if let window = _view as? FloWindowView {
+                window.setWindowKind(actualKind)
+            }
+        }
+    }
+
+    ///
+    /// Sets the size of the content of the window for this view
+    ///
+    @objc public func viewSetWindowSize(withWidth width: Double, height: Double) {
+        if let window = _view as? FloWindowView {
+            window.setWindowSize(width: CGFloat(width), height: CGFloat(height))
+        }
+    }
+
+    ///
+    /// Starts building the menu bar for the window containing this view
+    ///
+    @objc public func viewBeginMenuBar() {
+        _buildingMenus          = [NSMenu()]
+        _buildingMenuBar        = true
+        _buildingMenuProperties = []
+    }
+
+    ///
+    /// Starts building the menu that's displayed when this view is right-clicked
+    ///
+    @objc public func viewBeginContextMenu() {
+        _buildingMenus          = [NSMenu()]
+        _buildingMenuBar        = false
+        _buildingMenuProperties = []
+    }
+
+    ///
+    /// Starts a submenu in the menu that's being built (items are added to the submenu until viewEndSubMenu is called)
+    ///
+    @objc public func viewBeginSubMenu(_ label: FloProperty!) {
+        guard let parentMenu = _buildingMenus.last else { return }
+
+        let item        = NSMenuItem(title: "", action: nil, keyEquivalent: "")
+        let subMenu     = NSMenu()
+        item.submenu    = subMenu
+        parentMenu.addItem(item)
+
+        weak var weakItem = item
+        label.trackValue { value in
+            if case .String(let title) = value {
+                weakItem?.title     = title
+                weakItem?.submenu?.title = title
+            }
+        }
+
+        _buildingMenuProperties.append(label)
+        _buildingMenus.append(subMenu)
+    }
+
+    ///
+    /// Finishes the current submenu
+    ///
+    @objc public func viewEndSubMenu() {
+        if _buildingMenus.count > 1 {
+            _buildingMenus.removeLast()
+        }
+    }
+
+    ///
+    /// Converts a shortcut description like 'Ctrl+Shift+Z' into a key equivalent and its modifiers
+    ///
+    /// Ctrl is the command key on the Mac.
+    ///
+    static func keyEquivalent(forShortcut shortcut: String) -> (String, NSEvent.ModifierFlags) {
+        var modifiers   = NSEvent.ModifierFlags()
+        var key         = ""
+
+        for part in shortcut.split(separator: "+") {
+            switch part.lowercased() {
+            case "ctrl", "cmd":     modifiers.insert(.command)
+            case "shift":           modifiers.insert(.shift)
+            case "alt", "option":   modifiers.insert(.option)
+            case "del", "delete":   key = String(Character(UnicodeScalar(NSDeleteCharacter)!))
+            case "backspace":       key = String(Character(UnicodeScalar(NSBackspaceCharacter)!))
+            default:                key = part.count == 1 ? part.lowercased() : ""
+            }
+        }
+
+        if key == "" {
+            modifiers = NSEvent.ModifierFlags()
+        }
+
+        return (key, modifiers)
+    }
+
+    ///
+    /// Adds a command to the menu that's being built
+    ///
+    @objc public func viewAddMenuCommand(_ commandId: String!, label: FloProperty!, shortcut: String!, enabled: FloProperty!, checked: FloProperty?) {
+        guard let menu = _buildingMenus.last else { return }
+
+        let (key, modifiers)            = FloView.keyEquivalent(forShortcut: shortcut ?? "")
+        let item                        = NSMenuItem(title: "", action: #selector(FloView.menuItemSelected(_:)), keyEquivalent: key)
+        item.keyEquivalentModifierMask  = modifiers
+        item.target                     = self
+        item.representedObject          = commandId
+        menu.autoenablesItems           = false
+        menu.addItem(item)
+
+        weak var weakItem = item
+
+        label.trackValue { value in
+            if case .String(let title) = value { weakItem?.title = title }
+        }
+        enabled.trackValue { value in weakItem?.isEnabled = value.toBool(default: true) }
+        _buildingMenuProperties.append(label)
+        _buildingMenuProperties.append(enabled)
+
+        if let checked = checked {
+            checked.trackValue { value in weakItem?.state = value.toBool(default: false) ? .on : .off }
+            _buildingMenuProperties.append(checked)
+        }
+    }
+
+    ///
+    /// Adds a separator to the menu that's being built
+    ///
+    @objc public func viewAddMenuSeparator() {
+        _buildingMenus.last?.addItem(NSMenuItem.separator())
+    }
+
+    ///
+    /// Finishes building a menu and attaches it to this view
+    ///
+    @objc public func viewEndMenu() {
+        guard let menu = _buildingMenus.first else { return }
+
+        if _buildingMenuBar {
+            _menuBar = (menu.items, _buildingMenuProperties)
+            installMenuBar()
+
+            NotificationCenter.default.removeObserver(self, name: NSWindow.didBecomeKeyNotification, object: nil)
+            NotificationCenter.default.addObserver(self, selector: #selector(FloView.windowDidBecomeKey(_:)), name: NSWindow.didBecomeKeyNotification, object: nil)
+        } else {
+            _contextMenuProperties  = _buildingMenuProperties
+            _view.asView.menu       = menu
+        }
+
+        _buildingMenus          = []
+        _buildingMenuProperties = []
+    }
+
+    ///
+    /// Replaces the items after the application menu in the main menu with the menu bar for this view
+    ///
+    func installMenuBar() {
+        guard let menuBarItems = _menuBar?.0 else { return }
+
+        // Only the menu bar for the key window is displayed (or the first window if none is key yet)
+        if let window = _view.asView.window, !window.isKeyWindow && NSApp.keyWindow != nil {
+            return
+        }
+
+        // Menu items can only be in one menu at once, so they're moved to the new main menu
+        let mainMenu = NSMenu()
+
+        for item in Array((NSApp.mainMenu?.items ?? []).prefix(1)) + menuBarItems {
+            item.menu?.removeItem(item)
+            mainMenu.addItem(item)
+        }
+
+        NSApp.mainMenu = mainMenu
+    }
+
+    ///
+    /// Displays the menu bar for this view when its window becomes the key window
+    ///
+    @objc func windowDidBecomeKey(_ notification: Notification) {
+        if let window = notification.object as? NSWindow, window === _view.asView.window {
+            installMenuBar()
+        }
+    }
+
+    ///
+    /// Sends an event when the user picks a command from one of the menus attached to this view
+    ///
+    @objc public func requestSelectMenuItem(_ events: FloEvents!, withName name: String!) {
+        _onSelectMenuItem = { commandId in events.sendSelectMenuItem(name, withId: commandId) }
+    }
+
+    ///
+    /// The user picked a command from one of the menus attached to this view
+    ///
+    @objc func menuItemSelected(_ sender: NSMenuItem) {
+        if let commandId = sender.representedObject as? String {
+            _onSelectMenuItem?(commandId)
+        }
+    }
+
+    ///
+    /// Sets the tooltip displayed when the pointer hovers over this view
+    ///
+    @objc public func viewSetTooltip(_ tooltip: FloProperty!) {
+        _view.viewState.accessibility.append(tooltip)
+        weak var this = self
+
+        tooltip.trackValue { value in
+            if case .String(let text) = value {
+                this?._view.asView.toolTip = text.isEmpty ? nil : text
+            }
+        }
+    }
+
+    ///
+    /// Sets the name of this view as read by a screen reader
+    ///
+    @objc public func viewSetAccessibilityLabel(_ label: FloProperty!) {
+        _view.viewState.accessibility.append(label)
+        weak var this = self
+
+        label.trackValue { value in
+            if case .String(let text) = value {
+                this?._view.asView.setAccessibilityElement(true)
+                this?._view.asView.setAccessibilityLabel(text)
+            }
+        }
+    }
+
+    ///
+    /// Sets a longer description of what this view does, as read by a screen reader
+    ///
+    @objc public func viewSetAccessibilityHelp(_ help: FloProperty!) {
+        _view.viewState.accessibility.append(help)
+        weak var this = self
+
+        help.trackValue { value in
+            if case .String(let text) = value {
+                this?._view.asView.setAccessibilityHelp(text)
+            }
+        }
+    }
+
+    ///
+    /// Sets the role of this view as reported to assistive technologies
+    ///
+    /// Roles are:
+    ///     0 - Button          1 - Toggle button   2 - Check box       3 - Slider          4 - Text box
+    ///     5 - Label           6 - Image           7 - Canvas          8 - Combo box       9 - List
+    ///     10 - List item      11 - Tree           12 - Tree item      13 - Toolbar        14 - Group
+    ///
+    @objc public func viewSetAccessibilityRole(_ role: UInt32) {
+        let view = _view.asView
+
+        switch role {
+        case 0:     view.setAccessibilityRole(.button)
+        case 1:     view.setAccessibilityRole(.button); view.setAccessibilitySubrole(.toggle)
+        case 2:     view.setAccessibilityRole(.checkBox)
+        case 3:     view.setAccessibilityRole(.slider)
+        case 4:     view.setAccessibilityRole(.textField)
+        case 5:     view.setAccessibilityRole(.staticText)
+        case 6:     view.setAccessibilityRole(.image)
+        case 7:     view.setAccessibilityRole(.layoutArea)
+        case 8:     view.setAccessibilityRole(.popUpButton)
+        case 9:     view.setAccessibilityRole(.list)
+        case 10:    view.setAccessibilityRole(.row)
+        case 11:    view.setAccessibilityRole(.outline)
+        case 12:    view.setAccessibilityRole(.row)
+        case 13:    view.setAccessibilityRole(.toolbar)
+        case 14:    view.setAccessibilityRole(.group)
+        default:    return
+        }
+
+        view.setAccessibilityElement(true)
+    }
+
+    ///
+    /// Sets the position of this view in the keyboard focus order amongst its siblings
+    ///
+    @objc public func viewSetFocusOrder(_ order: Float64) {
+        _view.viewState.focusOrder = order
+        _superview?.invalidateKeyViewLoop()
+    }
+
+    ///
+    /// The view that receives keyboard focus for this view
+    ///
+    var keyView: NSView {
+        if let controlView = _view as? FloControlView {
+            return controlView._control
+        } else {
+            return _view.asView
+        }
+    }
+
+    ///
+    /// Updates the key view loop for the subviews of this view the next time through the runloop
+    ///
+    func invalidateKeyViewLoop() {
+        if !_willUpdateKeyViewLoop {
+            _willUpdateKeyViewLoop = true
+
+            RunLoop.main.perform(inModes: [.default, .eventTracking], block: {
+                self._willUpdateKeyViewLoop = false
+                self.updateKeyViewLoop()
+            })
+        }
+    }
+
+    ///
+    /// Links the subviews that have a focus order into the key view loop, in order
+    ///
+    func updateKeyViewLoop() {
+        let ordered = _subviews
+            .filter { subview in subview.viewState.focusOrder != nil }
+            .sorted { a, b in a.viewState.focusOrder! < b.viewState.focusOrder! }
+
+        if ordered.count == 0 { return }
+
+        // The window would otherwise replace the loop with one based on the view positions
+        _view.asView.window?.autorecalculatesKeyViewLoop = false
+
+        // The ordered views come first, followed by the views with no order
+        let unordered   = _subviews.filter { subview in subview.viewState.focusOrder == nil }
+        let loop        = ordered + unordered
+
+        for index in 0..<(loop.count-1) {
+            loop[index].keyView.nextKeyView = loop[index+1].keyView
+        }
+    }
+
     ///
     /// Finds the FloView 'neares't to the specified view
     ///
@@ -0,0 +1,113 @@
+//
+//  FloComboBoxView.swift
+//  FlowBetween
+//
+
+import Cocoa
+
+///
+/// Control view that displays a pop-up button with an option for each of its subviews
+///
+/// The value of a combo box is the index of the selected option
+///
+class FloComboBoxView : FloControlView {
+    /// Set to true if the options will be read from the subviews the next time through the runloop
+    fileprivate var _willUpdateOptions = false
+
+    /// The index of the option that should be selected
+    fileprivate var _selectedIndex = -1
+
+    /// The pop-up button displaying the options
+    var popUpButton: NSPopUpButton { return _control as! NSPopUpButton }
+
+    ///
+    /// Reads the options from the text of the subviews of this view
+    ///
+    func updateOptions() {
+        let subviews    = floView?.layoutSubviews ?? []
+        let menu        = NSMenu()
+
+        for subview in subviews {
+            var title = ""
+            if case .String(let text)? = subview.viewState.text?.value {
+                title = text
+            }
+
+            menu.addItem(NSMenuItem(title: title, action: nil, keyEquivalent: ""))
+        }
+
+        popUpButton.menu = menu
+        selectOption(_selectedIndex)
+    }
+
+    ///
+    /// Updates the options the next time through the runloop (so several subviews can be changed at once)
+    ///
+    func invalidateOptions() {
+        if !_willUpdateOptions {
+            _willUpdateOptions = true
+
+            RunLoop.main.perform(inModes: [.default, .eventTracking], block: {
+                self._willUpdateOptions = false
+                self.updateOptions()
+            })
+        }
+    }
+
+    ///
+    /// Selects the option with the specified index (or nothing if the index is out of range)
+    ///
+    func selectOption(_ index: Int) {
+        _selectedIndex = index
+
+        if index >= 0 && index < popUpButton.numberOfItems {
+            popUpButton.selectItem(at: index)
+        } else {
+            popUpButton.select(nil)
+        }
+    }
+
+    /// The user chose an option
+    @objc override func controlAction() {
+        _selectedIndex = popUpButton.indexOfSelectedItem
+        onSetValue?(PropertyValue.Int(Int64(_selectedIndex)))
+    }
+
+    /// Adds a subview to this container view
+    override func addContainerSubview(_ subview: NSView) {
+        // The subviews are displayed as the options in the menu, so follow their text
+        if let containerView = subview as? FloContainerView, let text = containerView.viewState.text {
+            weak var this = self
+            text.trackValue { _ in this?.invalidateOptions() }
+        }
+
+        invalidateOptions()
+    }
+
+    /// Updates the frame size of this view
+    override func setFrameSize(_ newSize: NSSize) {
+        super.setFrameSize(newSize)
+
+        // Options are re-read after layout, as this happens after subviews are added or removed
+        invalidateOptions()
+    }
+
+    /// Sets the text label for this view
+    override func setTextLabel(label: String) {
+        // The label of a combo box is the text of its selected option
+    }
+
+    /// Sets part of the state of this control
+    override func setState(selector: ViewStateSelector, toProperty: FloProperty) {
+        switch (selector) {
+        case .Value:
+            viewState.retainProperty(selector: selector, property: toProperty)
+
+            weak var this = self
+            toProperty.trackValue { value in this?.selectOption(Int(value.toInt(default: -1))) }
+
+        default:
+            super.setState(selector: selector, toProperty: toProperty)
+        }
+    }
+}
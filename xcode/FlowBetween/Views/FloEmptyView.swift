@@ -482,6 +482,9 @@ class FloEmptyView : NSView, FloContainerView {
                 return
             }
         }
+
+        // Display the context menu, if there is one
+        super.rightMouseDown(with: event)
     }
 
     ///
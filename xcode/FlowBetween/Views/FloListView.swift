@@ -0,0 +1,181 @@
+//
+//  FloListView.swift
+//  FlowBetween
+//
+
+import Cocoa
+
+///
+/// Scrolling view that displays its subviews as the rows of a list or a tree
+///
+/// Lists are virtualised: the item count describes how many rows there are in total, and the subviews are the rows
+/// starting at the first item index. Trees are displayed as a flattened list, with each row indented by its level and
+/// a disclosure button for the rows that can be expanded.
+///
+class FloListView : FloScrollingView {
+    /// The width of each level of indentation in a tree
+    let indentWidth = CGFloat(16.0)
+
+    /// True if this is displaying a tree
+    var isTree = false
+
+    /// Event handler: user changed which items are selected
+    var onSelectItems: (([UInt32]) -> ())?
+
+    /// Event handler: user expanded or collapsed an item
+    var onExpandItem: ((UInt32, Bool) -> ())?
+
+    /// The items that are selected
+    fileprivate var _selection: [UInt32] = []
+
+    /// The item that the user clicked on last (the start of the range when extending the selection)
+    fileprivate var _anchor: UInt32?
+
+    /// The disclosure buttons for the expandable rows in a tree
+    fileprivate var _disclosureButtons: [NSButton] = []
+
+    /// The layers that highlight the selected rows
+    fileprivate var _highlightLayers: [CALayer] = []
+
+    /// The height of a row in this list
+    var rowHeight: CGFloat {
+        return CGFloat(Double.maximum(viewState.rowHeight, 1.0))
+    }
+
+    /// The index of the item represented by the first subview
+    var firstItemIndex: Int {
+        return Int(viewState.firstItemIndex?.value.toInt(default: 0) ?? 0)
+    }
+
+    /// The total number of items in this list
+    var itemCount: Int {
+        let numSubviews = floView?.layoutSubviews.count ?? 0
+        return Int(viewState.itemCount?.value.toInt(default: Int64(numSubviews)) ?? Int64(numSubviews))
+    }
+
+    ///
+    /// Lays out the rows of the list in the document view
+    ///
+    override func layoutDocumentView() {
+        let rowHeight       = self.rowHeight
+        let firstIndex      = self.firstItemIndex
+        let contentSize     = contentView.bounds.size
+        let width           = contentSize.width
+        let height          = CGFloat.maximum(rowHeight * CGFloat(itemCount), contentSize.height)
+
+        documentView?.setFrameSize(NSSize(width: width, height: height))
+        documentView?.wantsLayer = true
+
+        // Remove the old disclosure buttons and highlights
+        _disclosureButtons.forEach { button in button.removeFromSuperview() }
+        _highlightLayers.forEach { layer in layer.removeFromSuperlayer() }
+        _disclosureButtons  = []
+        _highlightLayers    = []
+
+        // Each subview is one row
+        let subviews = floView?.layoutSubviews ?? []
+        for (index, subview) in subviews.enumerated() {
+            let itemIndex   = firstIndex + index
+            let rowY        = CGFloat(itemIndex) * rowHeight
+            var indent      = CGFloat(0.0)
+
+            if isTree {
+                // Tree items are indented by their level, leaving space for a disclosure button
+                let level       = CGFloat(subview.viewState.itemLevel)
+                indent          = (level + 1.0) * indentWidth
+
+                if let expanded = subview.viewState.itemExpanded {
+                    let button          = NSButton(frame: NSRect(x: level * indentWidth, y: rowY, width: indentWidth, height: rowHeight))
+                    button.bezelStyle   = .disclosure
+                    button.setButtonType(.pushOnPushOff)
+                    button.title        = ""
+                    button.state        = expanded.value.toBool(default: false) ? .on : .off
+                    button.tag          = itemIndex
+                    button.target       = self
+                    button.action       = #selector(FloListView.toggleExpanded(_:))
+
+                    documentView?.addSubview(button)
+                    _disclosureButtons.append(button)
+                }
+            }
+
+            subview.view.frame = NSRect(x: indent, y: rowY, width: CGFloat.maximum(width - indent, 0.0), height: rowHeight)
+
+            // Update the selection from the items
+            if let selected = subview.viewState.selected {
+                let isSelected = selected.value.toBool(default: false)
+
+                _selection.removeAll(where: { selectedIndex in selectedIndex == UInt32(itemIndex) })
+                if isSelected {
+                    _selection.append(UInt32(itemIndex))
+
+                    let highlight               = CALayer()
+                    highlight.frame             = NSRect(x: 0, y: rowY, width: width, height: rowHeight)
+                    highlight.backgroundColor   = NSColor.selectedContentBackgroundColor.cgColor
+                    highlight.zPosition         = -1.0
+                    documentView?.layer?.addSublayer(highlight)
+                    _highlightLayers.append(highlight)
+                }
+            }
+        }
+    }
+
+    ///
+    /// The user clicked a disclosure button
+    ///
+    @objc func toggleExpanded(_ sender: NSButton) {
+        onExpandItem?(UInt32(sender.tag), sender.state == .on)
+    }
+
+    ///
+    /// Works out the new selection after the user clicks on an item
+    ///
+    func selectionAfterClicking(item: UInt32, modifiers: NSEvent.ModifierFlags) -> [UInt32] {
+        switch viewState.selectionMode {
+        case .None:
+            return _selection
+
+        case .Single:
+            return [item]
+
+        case .Multiple:
+            if modifiers.contains(.shift), let anchor = _anchor {
+                // Shift-click selects a range of items
+                return Array(UInt32.minimum(anchor, item)...UInt32.maximum(anchor, item))
+            } else if modifiers.contains(.command) {
+                // Command-click toggles a single item
+                if _selection.contains(item) {
+                    return _selection.filter { selectedIndex in selectedIndex != item }
+                } else {
+                    return _selection + [item]
+                }
+            } else {
+                return [item]
+            }
+        }
+    }
+
+    ///
+    /// Clicking on a row selects it
+    ///
+    override func mouseDown(with event: NSEvent) {
+        guard let documentView = documentView else { return }
+
+        let point   = documentView.convert(event.locationInWindow, from: nil)
+        let row     = Int(floor(point.y / rowHeight))
+
+        if row >= 0 && row < itemCount && viewState.selectionMode != .None {
+            let item        = UInt32(row)
+            let selection   = selectionAfterClicking(item: item, modifiers: event.modifierFlags)
+
+            if !event.modifierFlags.contains(.shift) {
+                _anchor = item
+            }
+
+            if selection != _selection {
+                _selection = selection
+                onSelectItems?(selection)
+            }
+        }
+    }
+}
@@ -111,6 +111,39 @@ import Cocoa
         return view
     }
 
+    ///
+    /// Creates a view that lets the user choose one of the options supplied as its subviews
+    ///
+    @objc public static func createAsComboBox() -> FloView {
+        let popUpButton     = NSPopUpButton(frame: .zero, pullsDown: false)
+        let comboBoxView    = FloComboBoxView(frame: .zero, control: popUpButton)
+        let view            = FloView(withView: comboBoxView)
+
+        return view
+    }
+
+    ///
+    /// Creates a view that displays its subviews as the rows of a list
+    ///
+    @objc public static func createAsListView() -> FloView {
+        let list    = FloListView(frame: .zero)
+        let view    = FloView(withView: list)
+
+        return view
+    }
+
+    ///
+    /// Creates a view that displays its subviews as the rows of a tree
+    ///
+    @objc public static func createAsTreeView() -> FloView {
+        let tree    = FloListView(frame: .zero)
+        tree.isTree = true
+
+        let view    = FloView(withView: tree)
+
+        return view
+    }
+
     ///
     /// Creates a view that shows a pop-up window
     ///
@@ -120,4 +153,14 @@ import Cocoa
 
         return view
     }
+
+    ///
+    /// Creates a view that displays its subviews in a separate window
+    ///
+    @objc public static func createAsWindow() -> FloView {
+        let window  = FloWindowView(frame: .zero)
+        let view    = FloView(withView: window)
+
+        return view
+    }
 }
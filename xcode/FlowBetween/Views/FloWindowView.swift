@@ -0,0 +1,309 @@
+//
+//  FloWindowView.swift
+//  FlowBetween
+//
+
+import Cocoa
+
+///
+/// How a window is presented relative to the main window
+///
+enum WindowKind : UInt32 {
+    case Normal     = 0
+    case Utility    = 1
+}
+
+///
+/// The content view of a window created by a window view (flipped so it lays out in the same way as the other views)
+///
+fileprivate class FloWindowContentView : NSView {
+    /// Called when the size of this view changes
+    var onResize: (() -> ())?
+
+    override var isFlipped: Bool { return true }
+
+    override func setFrameSize(_ newSize: NSSize) {
+        super.setFrameSize(newSize)
+        onResize?()
+    }
+}
+
+///
+/// Flo view that displays its content in a separate window
+///
+/// The window is only displayed while this view is part of another window, so it opens and closes along with the
+/// part of the UI that contains it.
+///
+class FloWindowView : NSView, FloContainerView, NSWindowDelegate {
+    /// The size of the content of a window that doesn't specify one
+    fileprivate static let defaultSize = NSSize(width: 400, height: 300)
+
+    /// The window that displays the content of this view
+    fileprivate var _contentWindow: NSWindow
+
+    /// The view that contains the content of the window
+    fileprivate let _contentView = FloWindowContentView(frame: CGRect(size: FloWindowView.defaultSize))
+
+    /// How the window is presented
+    fileprivate var _kind: WindowKind = .Normal
+
+    /// The title of the window
+    fileprivate var _title: String = ""
+
+    /// Whether or not the window should be on screen (windows are open by default)
+    fileprivate var _isOpen: Bool = true
+
+    override init(frame frameRect: NSRect) {
+        _contentWindow = FloWindowView.createWindow(kind: .Normal, contentSize: FloWindowView.defaultSize)
+
+        super.init(frame: frameRect)
+
+        setUpContent()
+    }
+
+    required init?(coder decoder: NSCoder) {
+        _contentWindow = FloWindowView.createWindow(kind: .Normal, contentSize: FloWindowView.defaultSize)
+
+        super.init(coder: decoder)
+
+        setUpContent()
+    }
+
+    deinit {
+        _contentWindow.delegate = nil
+        _contentWindow.orderOut(nil)
+    }
+
+    ///
+    /// Creates the window used to display the content for a particular kind of window view
+    ///
+    fileprivate static func createWindow(kind: WindowKind, contentSize: NSSize) -> NSWindow {
+        let contentRect = CGRect(origin: .zero, size: contentSize)
+        let window: NSWindow
+
+        switch kind {
+        case .Normal:
+            window = NSWindow(contentRect: contentRect,
+                              styleMask: [.titled, .closable, .miniaturizable, .resizable],
+                              backing: .buffered,
+                              defer: true)
+
+        case .Utility:
+            // Utility windows stay above the main window
+            let panel = NSPanel(contentRect: contentRect,
+                                styleMask: [.titled, .closable, .resizable, .utilityWindow],
+                                backing: .buffered,
+                                defer: true)
+            panel.isFloatingPanel   = true
+            panel.hidesOnDeactivate = true
+            window                  = panel
+        }
+
+        window.isReleasedWhenClosed = false
+
+        return window
+    }
+
+    ///
+    /// Attaches the content view to the window
+    ///
+    fileprivate func setUpContent() {
+        weak var this = self
+
+        _contentView.wantsLayer             = true
+        _contentView.onResize               = { this?.contentDidResize() }
+
+        _contentWindow.contentView          = _contentView
+        _contentWindow.delegate             = self
+        _contentWindow.title                = _title
+    }
+
+    ///
+    /// Lays out the content of the window again after it has changed size
+    ///
+    fileprivate func contentDidResize() {
+        performLayout?(layoutSize)
+        triggerBoundsChanged()
+    }
+
+    ///
+    /// Shows the window if it's open and this view is in a window, or hides it otherwise
+    ///
+    fileprivate func updateVisibility() {
+        if _isOpen && window != nil {
+            if !_contentWindow.isVisible {
+                _contentWindow.center()
+            }
+
+            _contentWindow.orderFront(self)
+        } else {
+            _contentWindow.orderOut(self)
+        }
+    }
+
+    override func viewDidMoveToWindow() {
+        super.viewDidMoveToWindow()
+        updateVisibility()
+    }
+
+    /// Event handler: user has asked to close the window (if not set, the window is just closed)
+    var onDismiss: (() -> ())?
+
+    ///
+    /// The user has clicked the close button on the window
+    ///
+    func windowShouldClose(_ sender: NSWindow) -> Bool {
+        if let onDismiss = onDismiss {
+            // The controller decides whether or not the window actually closes
+            onDismiss()
+            return false
+        } else {
+            _isOpen = false
+            return true
+        }
+    }
+
+    /// Adds a subview to this container view
+    func addContainerSubview(_ subview: NSView) {
+        _contentView.addSubview(subview)
+    }
+
+    /// Sets the layer displayed for the canvas
+    func setCanvasLayer(_ layer: CALayer) {
+        _contentView.layer = layer
+    }
+
+    /// Stores the general state of this view
+    var viewState : ViewState = ViewState()
+
+    /// The size of the layout area for this view
+    var layoutSize : NSSize {
+        return _contentView.bounds.size
+    }
+
+    /// The FloView that owns this container view (should be a weak reference)
+    weak var floView: FloView?
+
+    /// Returns this view as an NSView
+    var asView : NSView {
+        return self
+    }
+
+    /// Event handler: user clicked in the view
+    var onClick: (() -> Bool)?
+
+    /// Event handler: user scrolled/resized so that a particular region is visible
+    var onScroll: ((NSRect) -> ())?
+
+    /// Event handler: value has changed
+    var onEditValue: ((PropertyValue) -> ())?
+
+    /// Event handler: value has been set
+    var onSetValue: ((PropertyValue) -> ())?
+
+    /// Event handler: control has obtained keyboard focus
+    var onFocused: (() -> ())?
+
+    /// Event handler: user has dragged this control
+    var onDrag: ((DragAction, CGPoint, CGPoint) -> ())?
+
+    /// Events handlers when a particular device is used for painting
+    var onPaint: [FloPaintDevice: (FloPaintStage, AppPainting) -> ()] = [FloPaintDevice: (FloPaintStage, AppPainting) -> ()]()
+
+    /// The affine transform for the canvas layer
+    var canvasAffineTransform: CGAffineTransform?
+
+    /// Event handler: user performed layout on this view
+    var performLayout: ((NSSize) -> ())?
+
+    /// Event handler: The bounds of the container have changed
+    var boundsChanged: ((ContainerBounds) -> ())?
+
+    /// The minimum size of the scroll area for this view
+    var scrollMinimumSize: (Float64, Float64) = (0.0, 0.0)
+
+    /// The visibility of the horizontal and vertical scroll bars
+    var scrollBarVisibility: (ScrollBarVisibility, ScrollBarVisibility) = (ScrollBarVisibility.Never, ScrollBarVisibility.Never)
+
+    /// Triggers the click event for this view
+    func triggerClick() {
+        bubbleUpEvent(source: self) { $0.onClick?() ?? false }
+    }
+
+    ///
+    /// Computes the container bounds for this view
+    ///
+    func getContainerBounds() -> ContainerBounds {
+        let viewport = CGRect(size: layoutSize)
+
+        return ContainerBounds(visibleRect: viewport, totalSize: viewport.size)
+    }
+
+    /// Triggers the bounds changed event for this view
+    func triggerBoundsChanged() {
+        boundsChanged?(getContainerBounds())
+    }
+
+    /// Sets the text label for this view
+    func setTextLabel(label: String) {
+    }
+
+    /// Sets the font size for this view
+    func setFontSize(points: Float64) {
+    }
+
+    /// Sets the foreground colour of the control
+    func setForegroundColor(color: NSColor) {
+    }
+
+    /// Sets the font weight for this view
+    func setFontWeight(weight: Float64) {
+    }
+
+    /// Sets the text alignment for this view
+    func setTextAlignment(alignment: NSTextAlignment) {
+    }
+
+    /// Sets part of the state of this control
+    func setState(selector: ViewStateSelector, toProperty: FloProperty) {
+    }
+
+    /// Sets whether or not the window is on screen
+    func setWindowOpen(_ isOpen: Bool) {
+        _isOpen = isOpen
+        updateVisibility()
+    }
+
+    /// Sets the title of the window
+    func setWindowTitle(_ title: String) {
+        _title                  = title
+        _contentWindow.title    = title
+    }
+
+    /// Sets how the window is presented relative to the main window
+    func setWindowKind(_ kind: WindowKind) {
+        if kind == _kind {
+            return
+        }
+
+        // Cocoa can't change a window into a panel, so the content is moved to a new window
+        let oldWindow           = _contentWindow
+        let frame               = oldWindow.frame
+
+        oldWindow.delegate      = nil
+        oldWindow.contentView   = nil
+        oldWindow.orderOut(self)
+
+        _kind                   = kind
+        _contentWindow          = FloWindowView.createWindow(kind: kind, contentSize: _contentView.frame.size)
+        setUpContent()
+        _contentWindow.setFrame(frame, display: false)
+
+        updateVisibility()
+    }
+
+    /// Sets the size of the content of the window
+    func setWindowSize(width: CGFloat, height: CGFloat) {
+        _contentWindow.setContentSize(NSSize(width: width, height: height))
+    }
+}